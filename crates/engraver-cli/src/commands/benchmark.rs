@@ -4,20 +4,26 @@
 //! before committing to a potentially long write operation. It helps identify
 //! slow drives or USB connections.
 //!
-//! **Warning:** This is a destructive operation that will overwrite data on the target device.
+//! **Warning:** This is a destructive operation that will overwrite data on the target device,
+//! unless `--read` is used to measure read speed instead.
 
 use anyhow::{bail, Context, Result};
 use console::style;
-use dialoguer::Confirm;
 use indicatif::{ProgressBar, ProgressStyle};
 use std::str::FromStr;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::time::Duration;
 
+use super::history::format_age;
+use crate::error::CliError;
+use crate::json;
+use crate::prompt;
+use engraver_core::benchmark::parse_size;
 use engraver_core::{
-    format_size, is_power_of_two, parse_block_sizes, parse_size, BenchmarkConfig, BenchmarkError,
-    BenchmarkProgress, BenchmarkResult, BenchmarkRunner, BlockSizeTestResult, DataPattern,
+    format_size, is_power_of_two, parse_block_sizes, BenchmarkConfig, BenchmarkError, BenchmarkLog,
+    BenchmarkMode, BenchmarkProgress, BenchmarkRecord, BenchmarkResult, BenchmarkRunner,
+    BlockSizeTestResult, CancelToken, DataPattern,
 };
+
 use engraver_detect::list_drives;
 use engraver_platform::{has_elevated_privileges, open_device, unmount_device, OpenOptions};
 
@@ -35,14 +41,24 @@ pub struct BenchmarkArgs {
     pub passes: u32,
     /// Output in JSON format
     pub json: bool,
+    /// Emit the unversioned JSON shape (`--json-compat-0`)
+    pub json_compat: bool,
     /// Skip confirmation prompt
     pub skip_confirm: bool,
     /// Silent mode (minimal output)
     pub silent: bool,
     /// Test multiple block sizes
     pub test_block_sizes: Option<String>,
+    /// Measure read speed instead of write speed (non-destructive)
+    pub read: bool,
+    /// Benchmark log to record results in and compare against
+    pub benchmark_log: Option<BenchmarkLog>,
+    /// Append the result to the benchmark log
+    pub record: bool,
+    /// Print the previous comparable run alongside the result
+    pub compare: bool,
     /// Cancellation flag
-    pub cancel_flag: Arc<AtomicBool>,
+    pub cancel_flag: CancelToken,
 }
 
 /// Execute the benchmark command
@@ -54,28 +70,15 @@ pub fn execute(args: BenchmarkArgs) -> Result<()> {
 
     // Step 1: Check for elevated privileges
     if !has_elevated_privileges() {
-        #[cfg(unix)]
-        bail!(
-            "Root privileges required.\n\
-             Try running with: sudo engraver benchmark ..."
-        );
-
-        #[cfg(windows)]
-        bail!(
-            "Administrator privileges required.\n\
-             Right-click and select 'Run as administrator'."
-        );
-
-        #[cfg(not(any(unix, windows)))]
-        bail!("Elevated privileges required for raw device access.");
+        return Err(CliError::privileges_required("benchmark").into());
     }
 
     // Step 2: Find and validate target device
     let drives = list_drives().context("Failed to list drives")?;
     let target_drive = find_target_drive(&drives, &args.target)?;
 
-    // Refuse to benchmark system drives
-    if target_drive.is_system {
+    // Refuse to write-benchmark system drives
+    if target_drive.is_system && !args.read {
         bail!(
             "Refusing to benchmark system drive: {}\n\
              This appears to be your system drive and benchmarking would destroy your OS.",
@@ -104,9 +107,15 @@ pub fn execute(args: BenchmarkArgs) -> Result<()> {
     };
 
     // Step 4: Display configuration
+    let mode = if args.read {
+        BenchmarkMode::Read
+    } else {
+        BenchmarkMode::Write
+    };
     if !args.json {
         display_benchmark_info(
             target_drive,
+            mode,
             base_test_size,
             block_size,
             &pattern,
@@ -117,10 +126,37 @@ pub fn execute(args: BenchmarkArgs) -> Result<()> {
         );
     }
 
+    // Read benchmarks never modify the device, so skip confirmation and unmounting
+    if args.read {
+        println_if!(
+            silent,
+            "\n{} Opening device (read-only)...",
+            style("▶").cyan()
+        );
+        let mut device = open_device(
+            &target_drive.path,
+            OpenOptions::new().write(false).direct_io(true),
+        )
+        .context("Failed to open device for reading")?;
+        println_if!(silent, "  {} Device opened", style("✓").green());
+
+        let result = run_read_benchmark(
+            &mut *device,
+            &target_drive.path,
+            base_test_size,
+            block_size,
+            args.json,
+            args.json_compat,
+            silent,
+            args.cancel_flag.clone(),
+        )?;
+        track_result(&args, target_drive, &result);
+        return Ok(());
+    }
+
     // Step 5: Safety confirmation
     if !args.skip_confirm && !confirm_benchmark(target_drive)? {
-        println_if!(silent, "{}", style("Aborted.").yellow());
-        return Ok(());
+        return Err(CliError::Aborted.into());
     }
 
     // Step 6: Unmount device
@@ -154,9 +190,11 @@ pub fn execute(args: BenchmarkArgs) -> Result<()> {
             &block_sizes,
             pattern,
             args.json,
+            args.json_compat,
             silent,
-            args.cancel_flag,
+            args.cancel_flag.clone(),
         )
+        .map(|()| None)
     } else {
         run_single_benchmark(
             &mut *device,
@@ -166,9 +204,11 @@ pub fn execute(args: BenchmarkArgs) -> Result<()> {
             pattern,
             args.passes,
             args.json,
+            args.json_compat,
             silent,
-            args.cancel_flag,
+            args.cancel_flag.clone(),
         )
+        .map(Some)
     };
 
     // Step 9: Sync device to flush any pending writes
@@ -176,7 +216,18 @@ pub fn execute(args: BenchmarkArgs) -> Result<()> {
         tracing::debug!("Sync after benchmark: {}", e);
     }
 
-    if result.is_ok() && !args.json {
+    // Step 10: Record and compare against earlier runs (if enabled)
+    match result? {
+        Some(ref result) => track_result(&args, target_drive, result),
+        None if args.record || args.compare => println_if!(
+            silent || args.json,
+            "{} Block size sweeps are not kept in the benchmark history",
+            style("ℹ").blue()
+        ),
+        None => {}
+    }
+
+    if !args.json {
         println_if!(
             silent,
             "{}",
@@ -184,12 +235,114 @@ pub fn execute(args: BenchmarkArgs) -> Result<()> {
         );
     }
 
-    result
+    Ok(())
+}
+
+/// Average speed drop from the previous run that is worth a warning, in percent
+const DEGRADED_SPEED_PERCENT: f64 = 20.0;
+
+/// Record `result` in the benchmark log and print the previous run, as enabled
+///
+/// Runs are keyed by the drive's serial number, so drives without one are
+/// skipped. Failing to read or append the log only logs a warning.
+fn track_result(args: &BenchmarkArgs, drive: &engraver_detect::Drive, result: &BenchmarkResult) {
+    if !args.record && !args.compare {
+        return;
+    }
+    let Some(ref log) = args.benchmark_log else {
+        return;
+    };
+    let quiet = args.silent || args.json;
+
+    let Some(serial) = drive.serial.as_deref() else {
+        println_if!(
+            quiet,
+            "{} {} reports no serial number, so its benchmark history is not kept",
+            style("ℹ").blue(),
+            drive.path
+        );
+        return;
+    };
+
+    let record = BenchmarkRecord::new(serial, result);
+
+    if args.compare && !quiet {
+        match log.previous(&record) {
+            Ok(previous) => print_comparison(&record, previous.as_ref(), record.timestamp),
+            Err(e) => tracing::warn!("Failed to read benchmark history: {}", e),
+        }
+    }
+
+    if args.record {
+        if let Err(e) = log.append(&record) {
+            tracing::warn!("Failed to record benchmark: {}", e);
+        }
+    }
+}
+
+/// Print `current` alongside the previous comparable run
+fn print_comparison(current: &BenchmarkRecord, previous: Option<&BenchmarkRecord>, now: u64) {
+    let Some(previous) = previous else {
+        println!(
+            "{} No earlier {} benchmark of this drive at {} blocks to compare with",
+            style("ℹ").blue(),
+            format_mode(current.mode),
+            format_size(current.block_size)
+        );
+        return;
+    };
+
+    let speed = engraver_core::benchmark::format_speed;
+    let change = current.speed_change_percent(previous);
+
+    println!();
+    println!("{}", style("Compared to Previous Run:").bold().green());
+    println!(
+        "  Previous Run:   {} on {}",
+        format_age(now.saturating_sub(previous.timestamp)),
+        previous.device_path
+    );
+    println!(
+        "  Average Speed:  {} (was {}{})",
+        style(speed(current.average_speed_bps)).cyan().bold(),
+        speed(previous.average_speed_bps),
+        change.map_or_else(String::new, |c| format!(", {:+.1}%", c))
+    );
+    println!(
+        "  Minimum Speed:  {} (was {})",
+        speed(current.min_speed_bps),
+        speed(previous.min_speed_bps)
+    );
+    println!(
+        "  Maximum Speed:  {} (was {})",
+        speed(current.max_speed_bps),
+        speed(previous.max_speed_bps)
+    );
+
+    if let Some(change) = change.filter(|c| *c <= -DEGRADED_SPEED_PERCENT) {
+        println!(
+            "{} Average speed dropped {:.1}% since the previous run; the drive may be failing",
+            style("⚠").yellow().bold(),
+            -change
+        );
+    }
+    println!();
+}
+
+/// Lowercase name of a benchmark mode for messages
+fn format_mode(mode: BenchmarkMode) -> &'static str {
+    match mode {
+        BenchmarkMode::Read => "read",
+        _ => "write",
+    }
 }
 
 /// Validate command arguments before any I/O
 fn validate_args(args: &BenchmarkArgs) -> Result<()> {
     // Check mutual exclusivity
+    if args.read && args.test_block_sizes.is_some() {
+        bail!("--read does not support --test-block-sizes. Use --block-size instead.");
+    }
     if args.test_size.is_some() && args.test_block_sizes.is_some() {
         bail!(
             "Cannot use both --size and --test-block-sizes options.\n\
@@ -277,6 +430,7 @@ fn find_target_drive<'a>(
 /// Display benchmark configuration
 fn display_benchmark_info(
     drive: &engraver_detect::Drive,
+    mode: BenchmarkMode,
     test_size: u64,
     block_size: u64,
     pattern: &DataPattern,
@@ -303,6 +457,13 @@ fn display_benchmark_info(
     println_if!(silent);
     println_if!(silent, "{}", style("Configuration:").bold());
 
+    if mode == BenchmarkMode::Read {
+        println_if!(silent, "  Mode: read (non-destructive)");
+        println_if!(silent, "  Test size: {}", format_size(test_size));
+        println_if!(silent, "  Block size: {}", format_size(block_size));
+        return;
+    }
+
     if is_multi_block {
         let sizes_display: Vec<String> = block_sizes.iter().map(|s| format_size(*s)).collect();
         println_if!(silent, "  Block sizes: {}", sizes_display.join(", "));
@@ -373,11 +534,7 @@ fn confirm_benchmark(drive: &engraver_detect::Drive) -> Result<bool> {
         drive.path, size_str
     );
 
-    Confirm::new()
-        .with_prompt(confirm_text)
-        .default(false)
-        .interact()
-        .context("Failed to get user confirmation")
+    prompt::confirm(confirm_text, false)
 }
 
 /// Get progress bar style based on percentage
@@ -407,9 +564,10 @@ fn run_single_benchmark<W>(
     pattern: DataPattern,
     passes: u32,
     json: bool,
+    json_compat: bool,
     silent: bool,
-    cancel_flag: Arc<AtomicBool>,
-) -> Result<()>
+    cancel_flag: CancelToken,
+) -> Result<BenchmarkResult>
 where
     W: std::io::Write + std::io::Seek + ?Sized,
 {
@@ -438,12 +596,12 @@ where
 
     // Set up cancellation
     let runner_cancel = runner.cancel_handle();
-    let cancel_flag_clone = Arc::clone(&cancel_flag);
+    let cancel_flag_clone = cancel_flag.clone();
     std::thread::spawn(move || {
-        while !cancel_flag_clone.load(Ordering::Relaxed) {
+        while !cancel_flag_clone.is_cancelled() {
             std::thread::sleep(std::time::Duration::from_millis(100));
         }
-        runner_cancel.store(true, Ordering::Relaxed);
+        runner_cancel.store(true, std::sync::atomic::Ordering::Relaxed);
     });
 
     let pb_clone = pb.clone();
@@ -467,15 +625,89 @@ where
     match result {
         Ok(result) => {
             if json {
-                output_json(&result)?;
+                output_json(&result, json_compat)?;
+            } else {
+                output_human_readable(&result, silent);
+            }
+            Ok(result)
+        }
+        Err(BenchmarkError::Cancelled) => {
+            println_if!(silent, "\n{} Benchmark cancelled", style("✗").red());
+            Err(CliError::Cancelled.into())
+        }
+        Err(e) => Err(anyhow::anyhow!("Benchmark failed: {}", e)),
+    }
+}
+
+/// Run a non-destructive read benchmark
+fn run_read_benchmark<R>(
+    device: &mut R,
+    device_path: &str,
+    test_size: u64,
+    block_size: u64,
+    json: bool,
+    json_compat: bool,
+    silent: bool,
+    cancel_flag: CancelToken,
+) -> Result<BenchmarkResult>
+where
+    R: std::io::Read + std::io::Seek + ?Sized,
+{
+    let config = BenchmarkConfig::new(test_size, block_size);
+    let effective_size = config.effective_test_size();
+
+    println_if!(silent, "\n{} Benchmarking reads...", style("▶").cyan());
+
+    let pb = if silent || json {
+        ProgressBar::hidden()
+    } else {
+        let pb = ProgressBar::new(effective_size);
+        pb.set_style(get_progress_style(0));
+        pb
+    };
+
+    let runner = BenchmarkRunner::new(config);
+
+    // Set up cancellation
+    let runner_cancel = runner.cancel_handle();
+    let cancel_flag_clone = cancel_flag.clone();
+    std::thread::spawn(move || {
+        while !cancel_flag_clone.is_cancelled() {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        runner_cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+    });
+
+    let pb_clone = pb.clone();
+    let result = runner.run_read(
+        device,
+        device_path,
+        Some(move |progress: &BenchmarkProgress| {
+            let pct = progress.percentage();
+            pb_clone.set_style(get_progress_style(pct));
+            pb_clone.set_position(progress.bytes_written);
+            pb_clone.set_message(format!(
+                "{} {}",
+                progress.speed_display(),
+                format_eta(progress)
+            ));
+        }),
+    );
+
+    pb.finish_and_clear();
+
+    match result {
+        Ok(result) => {
+            if json {
+                output_json(&result, json_compat)?;
             } else {
                 output_human_readable(&result, silent);
             }
-            Ok(())
+            Ok(result)
         }
         Err(BenchmarkError::Cancelled) => {
             println_if!(silent, "\n{} Benchmark cancelled", style("✗").red());
-            Ok(())
+            Err(CliError::Cancelled.into())
         }
         Err(e) => Err(anyhow::anyhow!("Benchmark failed: {}", e)),
     }
@@ -489,8 +721,9 @@ fn run_multi_block_benchmark<W>(
     block_sizes: &[u64],
     pattern: DataPattern,
     json: bool,
+    json_compat: bool,
     silent: bool,
-    cancel_flag: Arc<AtomicBool>,
+    cancel_flag: CancelToken,
 ) -> Result<()>
 where
     W: std::io::Write + std::io::Seek + ?Sized,
@@ -509,9 +742,9 @@ where
     let mut results: Vec<BlockSizeTestResult> = Vec::new();
 
     for (idx, &block_size) in block_sizes.iter().enumerate() {
-        if cancel_flag.load(Ordering::Relaxed) {
+        if cancel_flag.is_cancelled() {
             println_if!(silent, "\n{} Benchmark cancelled", style("✗").red());
-            return Ok(());
+            return Err(CliError::Cancelled.into());
         }
 
         println_if!(
@@ -577,7 +810,7 @@ where
             }
             Err(BenchmarkError::Cancelled) => {
                 println_if!(silent, "\n{} Benchmark cancelled", style("✗").red());
-                return Ok(());
+                return Err(CliError::Cancelled.into());
             }
             Err(e) => {
                 println_if!(
@@ -592,7 +825,7 @@ where
     }
 
     if json {
-        output_multi_block_json(&results)?;
+        output_multi_block_json(&results, json_compat)?;
     } else {
         output_multi_block_human(&results, silent);
     }
@@ -617,23 +850,23 @@ fn format_eta(progress: &BenchmarkProgress) -> String {
 }
 
 /// Output results as JSON
-fn output_json(result: &BenchmarkResult) -> Result<()> {
-    let json = serde_json::to_string_pretty(result)?;
-    println!("{}", json);
-    Ok(())
+fn output_json(result: &BenchmarkResult, compat: bool) -> Result<()> {
+    json::print("result", result, compat)
 }
 
 /// Output multi-block results as JSON
-fn output_multi_block_json(results: &[BlockSizeTestResult]) -> Result<()> {
-    let json = serde_json::to_string_pretty(results)?;
-    println!("{}", json);
-    Ok(())
+fn output_multi_block_json(results: &[BlockSizeTestResult], compat: bool) -> Result<()> {
+    json::print("results", results, compat)
 }
 
 /// Output results in human-readable format
 fn output_human_readable(result: &BenchmarkResult, silent: bool) {
+    let heading = match result.mode {
+        BenchmarkMode::Read => "Read Results:",
+        _ => "Results:",
+    };
     println_if!(silent);
-    println_if!(silent, "{}", style("Results:").bold().green());
+    println_if!(silent, "{}", style(heading).bold().green());
     println_if!(
         silent,
         "  Average Speed:  {}",
@@ -658,10 +891,24 @@ fn output_human_readable(result: &BenchmarkResult, silent: bool) {
         "  Total Time:     {}",
         engraver_core::benchmark::format_duration(result.summary.total_elapsed)
     );
+    let latency = &result.summary.latency;
+    println_if!(
+        silent,
+        "  Block Latency:  p50 {}, p95 {}, p99 {}",
+        format_latency(latency.p50),
+        format_latency(latency.p95),
+        format_latency(latency.p99)
+    );
+    println_if!(silent, "  Longest Stall:  {}", format_latency(latency.max));
     println_if!(silent);
     println_if!(silent, "{} Benchmark complete!", style("✓").green().bold());
 }
 
+/// Format a block latency in milliseconds, e.g. `12.3 ms`
+fn format_latency(latency: Duration) -> String {
+    format!("{:.1} ms", latency.as_secs_f64() * 1000.0)
+}
+
 /// Output multi-block results in human-readable format
 fn output_multi_block_human(results: &[BlockSizeTestResult], silent: bool) {
     println_if!(silent);
@@ -725,10 +972,15 @@ mod tests {
             pattern: "zeros".to_string(),
             passes: 1,
             json: false,
+            json_compat: false,
             skip_confirm: true,
             silent: false,
             test_block_sizes: None,
-            cancel_flag: Arc::new(AtomicBool::new(true)),
+            read: false,
+            benchmark_log: None,
+            record: false,
+            compare: false,
+            cancel_flag: CancelToken::new(),
         };
 
         assert_eq!(args.target, "/dev/sdb");
@@ -753,15 +1005,44 @@ mod tests {
             pattern: "zeros".to_string(),
             passes: 1,
             json: false,
+            json_compat: false,
             skip_confirm: true,
             silent: false,
             test_block_sizes: None,
-            cancel_flag: Arc::new(AtomicBool::new(true)),
+            read: false,
+            benchmark_log: None,
+            record: false,
+            compare: false,
+            cancel_flag: CancelToken::new(),
         };
 
         assert!(validate_args(&args).is_ok());
     }
 
+    #[test]
+    fn test_validate_args_read_rejects_block_size_sweep() {
+        let args = BenchmarkArgs {
+            target: "/dev/sdb".to_string(),
+            test_size: None,
+            block_size: "4M".to_string(),
+            pattern: "zeros".to_string(),
+            passes: 1,
+            json: false,
+            json_compat: false,
+            skip_confirm: false,
+            silent: false,
+            test_block_sizes: Some("4K,1M".to_string()),
+            read: true,
+            benchmark_log: None,
+            record: false,
+            compare: false,
+            cancel_flag: CancelToken::new(),
+        };
+
+        let err = validate_args(&args).unwrap_err();
+        assert!(err.to_string().contains("--read"));
+    }
+
     #[test]
     fn test_validate_args_mutual_exclusion() {
         let args = BenchmarkArgs {
@@ -771,10 +1052,15 @@ mod tests {
             pattern: "zeros".to_string(),
             passes: 1,
             json: false,
+            json_compat: false,
             skip_confirm: true,
             silent: false,
             test_block_sizes: Some("4K,1M,4M".to_string()),
-            cancel_flag: Arc::new(AtomicBool::new(true)),
+            read: false,
+            benchmark_log: None,
+            record: false,
+            compare: false,
+            cancel_flag: CancelToken::new(),
         };
 
         let result = validate_args(&args);
@@ -792,10 +1078,15 @@ mod tests {
             pattern: "zeros".to_string(),
             passes: 1,
             json: false,
+            json_compat: false,
             skip_confirm: true,
             silent: false,
             test_block_sizes: None,
-            cancel_flag: Arc::new(AtomicBool::new(true)),
+            read: false,
+            benchmark_log: None,
+            record: false,
+            compare: false,
+            cancel_flag: CancelToken::new(),
         };
 
         let result = validate_args(&args);
@@ -813,10 +1104,15 @@ mod tests {
             pattern: "zeros".to_string(),
             passes: 1,
             json: false,
+            json_compat: false,
             skip_confirm: true,
             silent: false,
             test_block_sizes: None,
-            cancel_flag: Arc::new(AtomicBool::new(true)),
+            read: false,
+            benchmark_log: None,
+            record: false,
+            compare: false,
+            cancel_flag: CancelToken::new(),
         };
 
         let result = validate_args(&args);
@@ -834,10 +1130,15 @@ mod tests {
             pattern: "invalid_pattern".to_string(),
             passes: 1,
             json: false,
+            json_compat: false,
             skip_confirm: true,
             silent: false,
             test_block_sizes: None,
-            cancel_flag: Arc::new(AtomicBool::new(true)),
+            read: false,
+            benchmark_log: None,
+            record: false,
+            compare: false,
+            cancel_flag: CancelToken::new(),
         };
 
         let result = validate_args(&args);
@@ -854,10 +1155,15 @@ mod tests {
                 pattern: pattern.to_string(),
                 passes: 1,
                 json: false,
+                json_compat: false,
                 skip_confirm: true,
                 silent: false,
                 test_block_sizes: None,
-                cancel_flag: Arc::new(AtomicBool::new(true)),
+                read: false,
+                benchmark_log: None,
+                record: false,
+                compare: false,
+                cancel_flag: CancelToken::new(),
             };
 
             assert!(
@@ -877,10 +1183,15 @@ mod tests {
             pattern: "zeros".to_string(),
             passes: 1,
             json: false,
+            json_compat: false,
             skip_confirm: true,
             silent: false,
             test_block_sizes: Some("4K,64K,1M,4M,16M".to_string()),
-            cancel_flag: Arc::new(AtomicBool::new(true)),
+            read: false,
+            benchmark_log: None,
+            record: false,
+            compare: false,
+            cancel_flag: CancelToken::new(),
         };
 
         assert!(validate_args(&args).is_ok());
@@ -987,6 +1298,10 @@ mod tests {
                 None
             },
             usb_speed: Some(UsbSpeed::SuperSpeed),
+            media_present: true,
+            enclosure: false,
+            rotational: false,
+            caution_reason: None,
         }
     }
 
@@ -1023,4 +1338,103 @@ mod tests {
             assert!(result.is_ok());
         }
     }
+
+    // -------------------------------------------------------------------------
+    // track_result tests
+    // -------------------------------------------------------------------------
+
+    fn tracking_args(log: BenchmarkLog, record: bool, compare: bool) -> BenchmarkArgs {
+        BenchmarkArgs {
+            target: "/dev/sdb".to_string(),
+            test_size: None,
+            block_size: "4M".to_string(),
+            pattern: "zeros".to_string(),
+            passes: 1,
+            json: false,
+            json_compat: false,
+            skip_confirm: true,
+            silent: true,
+            test_block_sizes: None,
+            read: false,
+            benchmark_log: Some(log),
+            record,
+            compare,
+            cancel_flag: CancelToken::new(),
+        }
+    }
+
+    fn benchmark_result(average_speed_bps: u64) -> BenchmarkResult {
+        BenchmarkResult {
+            device_path: "/dev/sdb".to_string(),
+            mode: BenchmarkMode::Write,
+            test_size: 1024 * 1024,
+            block_size: 4 * 1024 * 1024,
+            pattern: DataPattern::Zeros,
+            passes: vec![],
+            summary: engraver_core::BenchmarkSummary {
+                total_bytes_written: 1024 * 1024,
+                total_elapsed: Duration::from_secs(1),
+                average_speed_bps,
+                min_speed_bps: average_speed_bps,
+                max_speed_bps: average_speed_bps,
+                latency: engraver_core::LatencyStats::default(),
+            },
+        }
+    }
+
+    fn drive_with_serial(serial: Option<&str>) -> Drive {
+        Drive {
+            serial: serial.map(str::to_string),
+            ..create_test_drive("/dev/sdb", false)
+        }
+    }
+
+    #[test]
+    fn test_track_result_records_by_serial() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let log = BenchmarkLog::new(dir.path().join("benchmarks.jsonl"));
+        let args = tracking_args(log.clone(), true, true);
+
+        track_result(
+            &args,
+            &drive_with_serial(Some("AA01")),
+            &benchmark_result(40),
+        );
+        track_result(
+            &args,
+            &drive_with_serial(Some("AA01")),
+            &benchmark_result(30),
+        );
+
+        let records = log.records().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].serial, "AA01");
+        assert_eq!(records[1].average_speed_bps, 30);
+    }
+
+    #[test]
+    fn test_track_result_compare_only_does_not_record() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let log = BenchmarkLog::new(dir.path().join("benchmarks.jsonl"));
+        let args = tracking_args(log.clone(), false, true);
+
+        track_result(
+            &args,
+            &drive_with_serial(Some("AA01")),
+            &benchmark_result(40),
+        );
+
+        assert!(log.records().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_track_result_skips_drive_without_serial() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let log = BenchmarkLog::new(dir.path().join("benchmarks.jsonl"));
+        let args = tracking_args(log.clone(), true, false);
+
+        track_result(&args, &drive_with_serial(None), &benchmark_result(40));
+
+        assert!(log.records().unwrap().is_empty());
+    }
 }
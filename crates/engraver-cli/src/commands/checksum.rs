@@ -1,18 +1,186 @@
 //! Checksum command - calculates checksum of an image
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use console::style;
-use indicatif::{ProgressBar, ProgressStyle};
+use std::path::Path;
 
-use engraver_core::{validate_source, ChecksumAlgorithm, Source, Verifier, VerifyConfig};
+use crate::error::CliError;
+use crate::progress::ReporterKind;
+use engraver_core::{
+    find_checksum_for_file, parse_checksum_file, validate_source, Checksum, ChecksumAlgorithm,
+    ChecksumEntry, Source, Verifier, VerifyConfig,
+};
+
+/// Checksum file line layout for `checksum --format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumFormat {
+    /// `<hex>  <filename>`, as written by `sha256sum`
+    Gnu,
+    /// `SHA256 (<filename>) = <hex>`, as written by `shasum --tag`
+    Bsd,
+    /// The bare hex digest
+    Plain,
+}
+
+impl std::str::FromStr for ChecksumFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "gnu" => Ok(ChecksumFormat::Gnu),
+            "bsd" | "tag" => Ok(ChecksumFormat::Bsd),
+            "plain" | "hex" => Ok(ChecksumFormat::Plain),
+            _ => bail!("Unknown checksum format '{}'. Use: gnu, bsd or plain", s),
+        }
+    }
+}
+
+impl ChecksumFormat {
+    /// The line for `source`'s checksum, naming the source by its file name
+    pub fn line(self, algo: ChecksumAlgorithm, hex: &str, source: &str) -> String {
+        let filename = source.split('/').next_back().unwrap_or(source);
+        match self {
+            ChecksumFormat::Gnu => format!("{}  {}", hex, filename),
+            ChecksumFormat::Bsd => format!("{} ({}) = {}", algo.bsd_tag(), filename, hex),
+            ChecksumFormat::Plain => hex.to_string(),
+        }
+    }
+}
 
 /// Execute the checksum command
-pub fn execute(source: &str, algorithm: &str, silent: bool) -> Result<()> {
+///
+/// With `format`, only the checksum line is printed to stdout, so it can be
+/// redirected into a sums file.
+pub fn execute(
+    source: &str,
+    algorithm: &str,
+    format: Option<ChecksumFormat>,
+    silent: bool,
+    progress: ReporterKind,
+) -> Result<()> {
     // Parse algorithm
     let algo: ChecksumAlgorithm = algorithm
         .parse()
         .with_context(|| format!("Invalid algorithm: {}", algorithm))?;
 
+    let checksum = calculate(source, algo, silent || format.is_some(), progress)?;
+
+    // Output result - always print the checksum hash even in silent mode (it's the useful output)
+    if let Some(format) = format {
+        println!("{}", format.line(algo, &checksum.to_hex(), source));
+    } else if silent {
+        // In silent mode, just output the bare checksum
+        println!("{}", checksum.to_hex());
+    } else {
+        println!();
+        println!("{} ({}):", style(algo.name()).green().bold(), source);
+        println!("{}", checksum.to_hex());
+
+        // Also output in common checksum file format
+        println!();
+        println!("{}:", style("Checksum file format").dim());
+        println!(
+            "{}",
+            ChecksumFormat::Gnu.line(algo, &checksum.to_hex(), source)
+        );
+    }
+
+    Ok(())
+}
+
+/// Check the source against its entry in a sums file (`--compare-with`)
+///
+/// The entry is looked up by the source's file name. If the file lists the
+/// source under several algorithms, `algorithm` picks one; otherwise the
+/// first entry with a recognized hash length is used.
+pub fn execute_compare(
+    source: &str,
+    sums_file: &Path,
+    algorithm: Option<&str>,
+    silent: bool,
+    progress: ReporterKind,
+) -> Result<()> {
+    let algorithm: Option<ChecksumAlgorithm> = algorithm
+        .map(|a| {
+            a.parse()
+                .with_context(|| format!("Invalid algorithm: {}", a))
+        })
+        .transpose()?;
+
+    let content = std::fs::read_to_string(sums_file)
+        .with_context(|| format!("Failed to read checksum file: {}", sums_file.display()))?;
+    let entries = parse_checksum_file(&content);
+    let filename = source.split('/').next_back().unwrap_or(source);
+    let Some(entry) = select_entry(&entries, filename, algorithm) else {
+        bail!(
+            "No {}checksum for {} in {}",
+            algorithm
+                .map(|a| format!("{} ", a.name()))
+                .unwrap_or_default(),
+            filename,
+            sums_file.display()
+        );
+    };
+    // `select_entry` only returns entries with a known algorithm
+    let algo = entry.algorithm.unwrap_or(ChecksumAlgorithm::Sha256);
+
+    let checksum = calculate(source, algo, silent, progress)?;
+
+    if !checksum.to_hex().eq_ignore_ascii_case(&entry.checksum) {
+        println_if!(silent, "\n{} {}", style("FAIL").red().bold(), source);
+        return Err(CliError::VerificationFailed(format!(
+            "{} checksum mismatch for {}\n\
+             Expected: {} (from {})\n\
+             Actual:   {}",
+            algo.name(),
+            source,
+            entry.checksum,
+            sums_file.display(),
+            checksum.to_hex()
+        ))
+        .into());
+    }
+
+    println_if!(
+        silent,
+        "\n{} {} ({} matches {})",
+        style("PASS").green().bold(),
+        source,
+        algo.name(),
+        sums_file.display()
+    );
+    Ok(())
+}
+
+/// Find the sums-file entry for `filename` with a usable algorithm
+///
+/// Entries whose hash length matches no supported algorithm are ignored, so
+/// mixed-algorithm files still resolve. With `algorithm` set, only entries
+/// of that algorithm qualify.
+fn select_entry(
+    entries: &[ChecksumEntry],
+    filename: &str,
+    algorithm: Option<ChecksumAlgorithm>,
+) -> Option<ChecksumEntry> {
+    let candidates: Vec<ChecksumEntry> = entries
+        .iter()
+        .filter(|e| match (e.algorithm, algorithm) {
+            (Some(found), Some(wanted)) => found == wanted,
+            (found, None) => found.is_some(),
+            (None, Some(_)) => false,
+        })
+        .cloned()
+        .collect();
+    find_checksum_for_file(&candidates, filename).cloned()
+}
+
+/// Hash the source with `algo`, showing progress
+fn calculate(
+    source: &str,
+    algo: ChecksumAlgorithm,
+    silent: bool,
+    progress: ReporterKind,
+) -> Result<Checksum> {
     // Validate source
     println_if!(
         silent,
@@ -41,58 +209,21 @@ pub fn execute(source: &str, algorithm: &str, silent: bool) -> Result<()> {
     let mut source_reader =
         Source::open(source).with_context(|| format!("Failed to open source: {}", source))?;
 
-    // Create progress bar
-    let pb = if silent {
-        ProgressBar::hidden()
-    } else {
-        match source_size {
-            Some(size) => ProgressBar::new(size),
-            None => ProgressBar::new_spinner(),
-        }
-    };
-
-    if !silent {
-        pb.set_style(
-            ProgressStyle::default_bar()
-                .template("  {spinner:.green} [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})")
-                .unwrap()
-                .progress_chars("█▓░"),
-        );
-    }
-
     // Calculate checksum
+    let reporter = progress.create("Checksumming", source_size, None);
     let config = VerifyConfig::new();
-    let pb_clone = pb.clone();
-    let mut verifier = Verifier::with_config(config).on_progress(move |progress| {
-        pb_clone.set_position(progress.bytes_processed);
+    let reporter_clone = reporter.clone();
+    let mut verifier = Verifier::with_config(config).on_progress(move |p| {
+        reporter_clone.on_verify(p);
     });
 
     let checksum = verifier
         .calculate_checksum(&mut source_reader, algo, source_size)
         .context("Failed to calculate checksum")?;
 
-    pb.finish_and_clear();
-
-    // Output result - always print the checksum hash even in silent mode (it's the useful output)
-    if silent {
-        // In silent mode, just output the bare checksum
-        println!("{}", checksum.to_hex());
-    } else {
-        println!();
-        println!("{} ({}):", style(algo.name()).green().bold(), source);
-        println!("{}", checksum.to_hex());
-
-        // Also output in common checksum file format
-        println!();
-        println!("{}:", style("Checksum file format").dim());
-        println!(
-            "{}  {}",
-            checksum.to_hex(),
-            source.split('/').next_back().unwrap_or(source)
-        );
-    }
+    reporter.finish();
 
-    Ok(())
+    Ok(checksum)
 }
 
 /// Format size for display
@@ -116,6 +247,97 @@ fn format_size(bytes: u64) -> String {
 mod tests {
     use super::*;
 
+    // -------------------------------------------------------------------------
+    // select_entry tests
+    // -------------------------------------------------------------------------
+
+    const SHA256_HEX: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+    const MD5_HEX: &str = "d41d8cd98f00b204e9800998ecf8427e";
+
+    #[test]
+    fn test_select_entry_by_filename() {
+        let content = format!("{}  other.iso\n{}  image.iso\n", MD5_HEX, SHA256_HEX);
+        let entries = parse_checksum_file(&content);
+
+        let entry = select_entry(&entries, "image.iso", None).unwrap();
+        assert_eq!(entry.checksum, SHA256_HEX);
+        assert!(select_entry(&entries, "missing.iso", None).is_none());
+    }
+
+    #[test]
+    fn test_select_entry_mixed_algorithms() {
+        let content = format!(
+            "nothex  image.iso\nMD5 (image.iso) = {}\nSHA256 (image.iso) = {}\n",
+            MD5_HEX, SHA256_HEX
+        );
+        let entries = parse_checksum_file(&content);
+
+        // Unrecognized hash lengths are skipped
+        let entry = select_entry(&entries, "image.iso", None).unwrap();
+        assert_eq!(entry.algorithm, Some(ChecksumAlgorithm::Md5));
+
+        let entry = select_entry(&entries, "image.iso", Some(ChecksumAlgorithm::Sha256)).unwrap();
+        assert_eq!(entry.checksum, SHA256_HEX);
+        assert!(select_entry(&entries, "image.iso", Some(ChecksumAlgorithm::Sha512)).is_none());
+    }
+
+    // -------------------------------------------------------------------------
+    // ChecksumFormat tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_checksum_format_parse() {
+        assert_eq!(
+            "GNU".parse::<ChecksumFormat>().unwrap(),
+            ChecksumFormat::Gnu
+        );
+        assert_eq!(
+            "bsd".parse::<ChecksumFormat>().unwrap(),
+            ChecksumFormat::Bsd
+        );
+        assert_eq!(
+            "plain".parse::<ChecksumFormat>().unwrap(),
+            ChecksumFormat::Plain
+        );
+        assert!("json".parse::<ChecksumFormat>().is_err());
+    }
+
+    #[test]
+    fn test_checksum_format_lines() {
+        let algo = ChecksumAlgorithm::Sha256;
+        assert_eq!(
+            ChecksumFormat::Gnu.line(algo, SHA256_HEX, "/images/image.iso"),
+            format!("{}  image.iso", SHA256_HEX)
+        );
+        assert_eq!(
+            ChecksumFormat::Bsd.line(algo, SHA256_HEX, "/images/image.iso"),
+            format!("SHA256 (image.iso) = {}", SHA256_HEX)
+        );
+        assert_eq!(
+            ChecksumFormat::Plain.line(algo, SHA256_HEX, "/images/image.iso"),
+            SHA256_HEX
+        );
+    }
+
+    #[test]
+    fn test_checksum_format_round_trips_through_parser() {
+        for (format, algo, hex) in [
+            (ChecksumFormat::Gnu, ChecksumAlgorithm::Sha256, SHA256_HEX),
+            (ChecksumFormat::Bsd, ChecksumAlgorithm::Sha256, SHA256_HEX),
+            (ChecksumFormat::Bsd, ChecksumAlgorithm::Md5, MD5_HEX),
+        ] {
+            let line = format.line(algo, hex, "dir/image.iso");
+            let entries = parse_checksum_file(&line);
+            let entry = select_entry(&entries, "image.iso", Some(algo)).unwrap();
+            assert_eq!(entry.checksum, hex, "{:?}", format);
+            assert_eq!(entry.filename, "image.iso");
+        }
+
+        // Plain output has no file name; it is the digest itself
+        let line = ChecksumFormat::Plain.line(ChecksumAlgorithm::Sha256, SHA256_HEX, "image.iso");
+        assert!(Checksum::from_hex(ChecksumAlgorithm::Sha256, &line).is_ok());
+    }
+
     // -------------------------------------------------------------------------
     // format_size tests
     // -------------------------------------------------------------------------
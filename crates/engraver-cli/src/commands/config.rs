@@ -2,9 +2,20 @@
 
 use anyhow::{Context, Result};
 use console::style;
+
+use crate::json;
 use engraver_core::Settings;
 use std::path::PathBuf;
 
+/// A single setting to read or change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigAction {
+    /// Print the resolved value of a dotted key
+    Get(String),
+    /// Store a value under a dotted key and save the file
+    Set(String, String),
+}
+
 /// Arguments for the config command
 pub struct ConfigArgs {
     /// Initialize a new configuration file with defaults
@@ -13,10 +24,18 @@ pub struct ConfigArgs {
     pub path: bool,
     /// Show configuration in JSON format
     pub json: bool,
+    /// Read or change a single setting instead of showing them all
+    pub action: Option<ConfigAction>,
+    /// Emit the unversioned JSON shape (`--json-compat-0`)
+    pub json_compat: bool,
     /// Suppress output (for scripting)
     pub silent: bool,
     /// Custom configuration file path (overrides default)
     pub config_file: Option<PathBuf>,
+    /// Profile to apply when showing or reading settings (`--profile`)
+    pub profile: Option<String>,
+    /// List the profiles defined in the configuration file
+    pub list_profiles: bool,
 }
 
 /// Execute the config command
@@ -41,8 +60,29 @@ pub fn execute(args: ConfigArgs) -> Result<()> {
         return init_config(config_path, args.silent);
     }
 
+    if args.list_profiles {
+        return list_profiles(config_path, args.json, args.json_compat, args.silent);
+    }
+
+    let profile = args.profile.as_deref();
+    match args.action {
+        Some(ConfigAction::Get(key)) => {
+            return get_setting(config_path, profile, &key, args.silent)
+        }
+        Some(ConfigAction::Set(key, value)) => {
+            return set_setting(config_path, &key, &value, args.silent)
+        }
+        None => {}
+    }
+
     // Default: show current configuration
-    show_config(config_path, args.json, args.silent)
+    show_config(
+        config_path,
+        profile,
+        args.json,
+        args.json_compat,
+        args.silent,
+    )
 }
 
 /// Initialize a new configuration file with default values
@@ -90,20 +130,96 @@ fn init_config(config_path: Option<PathBuf>, silent: bool) -> Result<()> {
     Ok(())
 }
 
+/// Print the resolved value of one setting
+fn get_setting(
+    config_path: Option<PathBuf>,
+    profile: Option<&str>,
+    key: &str,
+    silent: bool,
+) -> Result<()> {
+    let value = Settings::load_from_path(config_path)
+        .with_profile(profile)?
+        .get(key)?;
+    println_if!(silent, "{}", value);
+    Ok(())
+}
+
+/// Print the names of the defined profiles, marking the default one
+fn list_profiles(
+    config_path: Option<PathBuf>,
+    json: bool,
+    json_compat: bool,
+    silent: bool,
+) -> Result<()> {
+    if silent {
+        return Ok(());
+    }
+
+    let settings = Settings::load_from_path(config_path);
+    let names = settings.profile_names();
+    if json {
+        return json::print("profiles", &names, json_compat)
+            .context("Failed to serialize profiles to JSON");
+    }
+
+    if names.is_empty() {
+        println!("{}", style("No profiles defined.").dim());
+        println!(
+            "{}",
+            style("Add a [profiles.<name>] table to the configuration file to create one.").dim()
+        );
+        return Ok(());
+    }
+    let default = settings.behavior.default_profile.as_deref();
+    for name in names {
+        if Some(name) == default {
+            println!("{} {}", name, style("(default)").dim());
+        } else {
+            println!("{}", name);
+        }
+    }
+    Ok(())
+}
+
+/// Change one setting and write the configuration file back
+fn set_setting(config_path: Option<PathBuf>, key: &str, value: &str, silent: bool) -> Result<()> {
+    let path = config_path.context("Could not determine configuration directory")?;
+
+    // A file that doesn't parse is reported, never replaced with defaults
+    let settings = Settings::set_in_file(&path, key, value)?;
+
+    println_if!(
+        silent,
+        "{} Set {} = {} in {}",
+        style("✓").green().bold(),
+        key,
+        settings.get(key)?,
+        path.display()
+    );
+    Ok(())
+}
+
 /// Show the current configuration
-fn show_config(config_path: Option<PathBuf>, json: bool, silent: bool) -> Result<()> {
+fn show_config(
+    config_path: Option<PathBuf>,
+    profile: Option<&str>,
+    json: bool,
+    json_compat: bool,
+    silent: bool,
+) -> Result<()> {
     if silent {
         return Ok(());
     }
 
     let config_exists = config_path.as_ref().is_some_and(|p| p.exists());
-    let settings = Settings::load_from_path(config_path.clone());
+    let base = Settings::load_from_path(config_path.clone());
+    let settings = base.with_profile(profile)?;
+    let active_profile = profile.or(base.behavior.default_profile.as_deref());
 
     if json {
         // Output as JSON for scripting
-        let json_output = serde_json::to_string_pretty(&settings)
+        json::print("settings", &settings, json_compat)
             .context("Failed to serialize settings to JSON")?;
-        println!("{}", json_output);
     } else {
         // Human-readable output
         println!("{}", style("Engraver Configuration").bold());
@@ -121,6 +237,9 @@ fn show_config(config_path: Option<PathBuf>, json: bool, silent: bool) -> Result
                 );
             }
         }
+        if let Some(name) = active_profile {
+            println!("  {} {}", style("Profile:").dim(), name);
+        }
         println!();
 
         println!("{}", style("[write]").cyan());
@@ -140,6 +259,7 @@ fn show_config(config_path: Option<PathBuf>, json: bool, silent: bool) -> Result
             settings.behavior.skip_confirmation
         );
         println!("  quiet = {}", settings.behavior.quiet);
+        println!("  record_history = {}", settings.behavior.record_history);
 
         if !config_exists {
             println!();
@@ -173,6 +293,7 @@ mod tests {
                 retry_attempts: 3,
                 retry_delay_ms: 100,
                 read_buffer_size: "64K".to_string(),
+                checkpoint_interval_seconds: 30,
             },
             checksum: ChecksumSettings {
                 algorithm: "sha512".to_string(),
@@ -181,9 +302,12 @@ mod tests {
             behavior: BehaviorSettings {
                 skip_confirmation: false,
                 quiet: false,
+                record_history: true,
+                default_profile: None,
             },
             benchmark: BenchmarkSettings::default(),
             network: NetworkSettings::default(),
+            profiles: Default::default(),
         };
         (settings, config_path)
     }
@@ -194,8 +318,12 @@ mod tests {
             init: false,
             path: false,
             json: false,
+            action: None,
+            json_compat: false,
             silent: false,
             config_file: None,
+            profile: None,
+            list_profiles: false,
         };
         assert!(!args.init);
         assert!(!args.path);
@@ -207,14 +335,14 @@ mod tests {
     #[test]
     fn test_show_config_silent() {
         // Silent mode should not panic and return Ok
-        let result = show_config(None, false, true);
+        let result = show_config(None, None, false, false, true);
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_show_config_json_silent() {
         // Silent mode with JSON should still return Ok
-        let result = show_config(None, true, true);
+        let result = show_config(None, None, true, false, true);
         assert!(result.is_ok());
     }
 
@@ -224,8 +352,12 @@ mod tests {
             init: false,
             path: true,
             json: false,
+            action: None,
+            json_compat: false,
             silent: true,
             config_file: None,
+            profile: None,
+            list_profiles: false,
         };
         let result = execute(args);
         assert!(result.is_ok());
@@ -237,8 +369,12 @@ mod tests {
             init: false,
             path: false,
             json: false,
+            action: None,
+            json_compat: false,
             silent: true,
             config_file: None,
+            profile: None,
+            list_profiles: false,
         };
         let result = execute(args);
         assert!(result.is_ok());
@@ -271,4 +407,108 @@ mod tests {
         assert!(json_str.contains("algorithm"));
         assert!(json_str.contains("skip_confirmation"));
     }
+
+    // ---- get/set tests ----
+
+    fn action_args(config_path: &std::path::Path, action: ConfigAction) -> ConfigArgs {
+        ConfigArgs {
+            init: false,
+            path: false,
+            json: false,
+            action: Some(action),
+            json_compat: false,
+            silent: true,
+            config_file: Some(config_path.to_path_buf()),
+            profile: None,
+            list_profiles: false,
+        }
+    }
+
+    #[test]
+    fn test_set_creates_and_updates_config_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("engraver_config.toml");
+
+        execute(action_args(
+            &config_path,
+            ConfigAction::Set("write.block_size".to_string(), "1M".to_string()),
+        ))
+        .unwrap();
+        execute(action_args(
+            &config_path,
+            ConfigAction::Set("checksum.algorithm".to_string(), "md5".to_string()),
+        ))
+        .unwrap();
+
+        let loaded = Settings::load_from_path(Some(config_path));
+        assert_eq!(loaded.write.block_size, "1M");
+        assert_eq!(loaded.checksum.algorithm, "md5");
+    }
+
+    #[test]
+    fn test_set_invalid_value_leaves_file_untouched() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("engraver_config.toml");
+
+        let result = execute(action_args(
+            &config_path,
+            ConfigAction::Set("write.block_size".to_string(), "1G".to_string()),
+        ));
+        assert!(result.is_err());
+        assert!(!config_path.exists());
+    }
+
+    #[test]
+    fn test_set_malformed_config_aborts() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("engraver_config.toml");
+        let original = "[write]\nblock_size = \"2M\"\nverify = yes\n";
+        std::fs::write(&config_path, original).unwrap();
+
+        let err = execute(action_args(
+            &config_path,
+            ConfigAction::Set("checksum.algorithm".to_string(), "md5".to_string()),
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("Failed to parse"));
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), original);
+    }
+
+    #[test]
+    fn test_get_unknown_key_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("engraver_config.toml");
+
+        let err = execute(action_args(
+            &config_path,
+            ConfigAction::Get("write.colour".to_string()),
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("write.block_size"));
+    }
+
+    #[test]
+    fn test_get_with_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("engraver_config.toml");
+        std::fs::write(
+            &config_path,
+            "[write]\nblock_size = \"2M\"\n\n[profiles.sd.write]\nblock_size = \"1M\"\n",
+        )
+        .unwrap();
+
+        let get = |profile: Option<&str>| {
+            execute(ConfigArgs {
+                profile: profile.map(String::from),
+                ..action_args(
+                    &config_path,
+                    ConfigAction::Get("write.block_size".to_string()),
+                )
+            })
+        };
+        assert!(get(None).is_ok());
+        assert!(get(Some("sd")).is_ok());
+        let err = get(Some("ssd")).unwrap_err();
+        assert!(err.to_string().contains("Unknown profile 'ssd'"));
+    }
 }
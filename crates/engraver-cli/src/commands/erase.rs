@@ -6,15 +6,18 @@
 
 use anyhow::{bail, Context, Result};
 use console::style;
-use dialoguer::Confirm;
 use indicatif::{ProgressBar, ProgressStyle};
 use std::io::Write;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
 use std::time::Instant;
 
+use crate::error::CliError;
+use crate::prompt;
+
+use engraver_core::{parse_block_size_clamped, CancelToken, MAX_BLOCK_SIZE, MIN_BLOCK_SIZE};
 use engraver_detect::{list_drives, Drive};
-use engraver_platform::{has_elevated_privileges, open_device, unmount_device, OpenOptions};
+use engraver_platform::{
+    has_elevated_privileges, open_device, unmount_device_with_progress, OpenOptions,
+};
 
 /// Arguments for the erase command
 pub struct EraseArgs {
@@ -23,7 +26,7 @@ pub struct EraseArgs {
     pub block_size: String,
     pub force: bool,
     pub no_unmount: bool,
-    pub cancel_flag: Arc<AtomicBool>,
+    pub cancel_flag: CancelToken,
     pub silent: bool,
 }
 
@@ -34,20 +37,7 @@ pub fn execute(args: EraseArgs) -> Result<()> {
 
     // Step 1: Check for elevated privileges
     if !has_elevated_privileges() {
-        #[cfg(unix)]
-        bail!(
-            "Root privileges required.\n\
-             Try running with: sudo engraver erase ..."
-        );
-
-        #[cfg(windows)]
-        bail!(
-            "Administrator privileges required.\n\
-             Right-click and select 'Run as administrator'."
-        );
-
-        #[cfg(not(any(unix, windows)))]
-        bail!("Elevated privileges required for raw device access.");
+        return Err(CliError::privileges_required("erase").into());
     }
 
     // Step 2: Validate target device
@@ -82,13 +72,28 @@ pub fn execute(args: EraseArgs) -> Result<()> {
             style("Warning:").yellow().bold()
         );
         if !args.skip_confirm {
-            let proceed = Confirm::new()
-                .with_prompt("Are you absolutely sure you want to continue?")
-                .default(false)
-                .interact()?;
+            let proceed = prompt::confirm("Are you absolutely sure you want to continue?", false)?;
+
+            if !proceed {
+                return Err(CliError::Aborted.into());
+            }
+        }
+    }
+
+    // Removable but possibly holding data, like a USB hard drive
+    if let Some(reason) = &target_drive.caution_reason {
+        eprintln!(
+            "{} {} may not be a flash drive: {}",
+            style("Caution:").yellow().bold(),
+            target_drive.path,
+            reason
+        );
+        if !args.skip_confirm {
+            let proceed =
+                prompt::confirm("Is this really the drive you mean to overwrite?", false)?;
 
             if !proceed {
-                bail!("Aborted by user");
+                return Err(CliError::Aborted.into());
             }
         }
     }
@@ -152,14 +157,10 @@ pub fn execute(args: EraseArgs) -> Result<()> {
             format_size(target_drive.size)
         );
 
-        let proceed = Confirm::new()
-            .with_prompt(confirm_text)
-            .default(false)
-            .interact()?;
+        let proceed = prompt::confirm(confirm_text, false)?;
 
         if !proceed {
-            println!("{}", style("Aborted.").yellow());
-            return Ok(());
+            return Err(CliError::Aborted.into());
         }
     }
 
@@ -167,7 +168,14 @@ pub fn execute(args: EraseArgs) -> Result<()> {
     if !args.no_unmount {
         println_if!(silent, "\n{}", style("Unmounting device...").bold());
 
-        match unmount_device(&target_drive.path) {
+        let spinner = create_unmount_spinner(silent);
+        let result = unmount_device_with_progress(&target_drive.path, &mut |status| {
+            spinner.set_message(status.to_string());
+            spinner.tick();
+        });
+        spinner.finish_and_clear();
+
+        match result {
             Ok(()) => println_if!(silent, "  {} Device unmounted", style("✓").green()),
             Err(e) => {
                 tracing::debug!("Unmount result: {}", e);
@@ -206,14 +214,14 @@ pub fn execute(args: EraseArgs) -> Result<()> {
 
     loop {
         // Check cancellation
-        if !cancel_flag.load(Ordering::SeqCst) {
+        if cancel_flag.is_cancelled() {
             pb.finish_and_clear();
             // Sync to flush any pending writes before returning
             if let Err(e) = target.sync() {
                 tracing::debug!("Sync after cancel: {}", e);
             }
             println_if!(silent, "\n{}", style("Erase cancelled by user.").yellow());
-            return Ok(());
+            return Err(CliError::Cancelled.into());
         }
 
         let remaining = total_size - bytes_written;
@@ -320,11 +328,12 @@ fn find_drive<'a>(drives: &'a [Drive], path: &str) -> Result<&'a Drive> {
         }
     }
 
-    bail!(
+    Err(CliError::DeviceNotFound(format!(
         "Device '{}' not found.\n\
          Run 'engraver list' to see available drives.",
         path
-    )
+    ))
+    .into())
 }
 
 /// Get the raw device path for a given device path
@@ -339,34 +348,10 @@ fn get_raw_device_path(path: &str) -> String {
     path.to_string()
 }
 
-/// Parse a human-readable block size (e.g., "4M", "1M", "512K")
+/// Parse a human-readable block size (e.g., "4M", "1.5M", "512K") between 4K and 64M
 fn parse_block_size(s: &str) -> Result<usize> {
-    let s = s.trim().to_uppercase();
-
-    let (num_str, multiplier) = if s.ends_with('K') {
-        (&s[..s.len() - 1], 1024)
-    } else if s.ends_with('M') {
-        (&s[..s.len() - 1], 1024 * 1024)
-    } else if s.ends_with('G') {
-        (&s[..s.len() - 1], 1024 * 1024 * 1024)
-    } else {
-        (s.as_str(), 1)
-    };
-
-    let num: usize = num_str
-        .parse()
-        .with_context(|| format!("Invalid block size: {}", s))?;
-
-    let size = num * multiplier;
-
-    if size < 4096 {
-        bail!("Block size must be at least 4K");
-    }
-    if size > 64 * 1024 * 1024 {
-        bail!("Block size must be at most 64M");
-    }
-
-    Ok(size)
+    let size = parse_block_size_clamped(s, MIN_BLOCK_SIZE as u64, MAX_BLOCK_SIZE as u64)?;
+    Ok(size as usize)
 }
 
 /// Format a size in bytes to human-readable format
@@ -401,6 +386,22 @@ fn format_eta(secs: f64) -> String {
     }
 }
 
+/// Create a spinner for the unmount step
+fn create_unmount_spinner(silent: bool) -> ProgressBar {
+    if silent {
+        return ProgressBar::hidden();
+    }
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_style(
+        ProgressStyle::default_spinner()
+            .template("  {spinner:.green} {msg}")
+            .unwrap(),
+    );
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+    pb
+}
+
 /// Create a progress bar for the erase operation
 fn create_erase_progress_bar(total: u64, silent: bool) -> ProgressBar {
     if silent {
@@ -443,6 +444,10 @@ mod tests {
             is_system: false,
             system_reason: None,
             usb_speed: None,
+            media_present: true,
+            enclosure: false,
+            rotational: false,
+            caution_reason: None,
         }
     }
 
@@ -795,6 +800,8 @@ mod tests {
             filesystem: Some("ext4".to_string()),
             size: 8 * 1024 * 1024 * 1024,
             mount_point: Some("/media/data".to_string()),
+            used: None,
+            available: None,
         }];
         let drives = vec![drive];
 
@@ -823,6 +830,8 @@ mod tests {
             filesystem: None,
             size: 0,
             mount_point: None,
+            used: None,
+            available: None,
         }];
 
         let mut drive2 = make_drive("/dev/sdb");
@@ -833,6 +842,8 @@ mod tests {
                 filesystem: None,
                 size: 0,
                 mount_point: None,
+                used: None,
+                available: None,
             },
             engraver_detect::Partition {
                 path: "/dev/sdb2".to_string(),
@@ -840,6 +851,8 @@ mod tests {
                 filesystem: None,
                 size: 0,
                 mount_point: None,
+                used: None,
+                available: None,
             },
         ];
 
@@ -903,7 +916,7 @@ mod tests {
             block_size: "4M".to_string(),
             force: false,
             no_unmount: false,
-            cancel_flag: Arc::new(AtomicBool::new(true)),
+            cancel_flag: CancelToken::new(),
             silent: false,
         };
 
@@ -912,7 +925,7 @@ mod tests {
         assert_eq!(args.block_size, "4M");
         assert!(!args.force);
         assert!(!args.no_unmount);
-        assert!(args.cancel_flag.load(Ordering::Relaxed));
+        assert!(!args.cancel_flag.is_cancelled());
         assert!(!args.silent);
     }
 
@@ -924,7 +937,7 @@ mod tests {
             block_size: "1M".to_string(),
             force: true,
             no_unmount: true,
-            cancel_flag: Arc::new(AtomicBool::new(false)),
+            cancel_flag: CancelToken::new(),
             silent: true,
         };
 
@@ -933,13 +946,13 @@ mod tests {
         assert_eq!(args.block_size, "1M");
         assert!(args.force);
         assert!(args.no_unmount);
-        assert!(!args.cancel_flag.load(Ordering::Relaxed));
+        assert!(!args.cancel_flag.is_cancelled());
         assert!(args.silent);
     }
 
     #[test]
     fn test_erase_args_cancel_flag_shared() {
-        let cancel = Arc::new(AtomicBool::new(false));
+        let cancel = CancelToken::new();
         let cancel_clone = cancel.clone();
 
         let args = EraseArgs {
@@ -953,7 +966,7 @@ mod tests {
         };
 
         // Simulate cancellation from another thread
-        cancel_clone.store(true, Ordering::SeqCst);
-        assert!(args.cancel_flag.load(Ordering::SeqCst));
+        cancel_clone.cancel(engraver_core::CancelReason::UserRequested);
+        assert!(args.cancel_flag.is_cancelled());
     }
 }
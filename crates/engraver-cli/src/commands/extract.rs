@@ -0,0 +1,129 @@
+//! Extract command - streams a decompressed or downloaded image to stdout
+//!
+//! Stdout carries only image bytes; the source summary, progress and the
+//! final total go to stderr so the output can be piped into another tool.
+
+use anyhow::{bail, Context, Result};
+use console::style;
+use std::io::{IsTerminal, Write};
+
+use crate::commands::write::CancelForward;
+use crate::error::CliError;
+use crate::progress::ReporterKind;
+use engraver_core::{
+    format_duration, validate_source_with_entry, CancelToken, Prefetch, Source, StreamTarget,
+    WriteConfig, Writer, DEFAULT_BLOCK_SIZE,
+};
+use engraver_detect::format_bytes;
+
+/// Arguments for the extract command
+pub struct ExtractArgs {
+    /// Source image (local file, URL or cloud URI)
+    pub source: String,
+    /// Archive entry to extract from a tar source
+    pub entry: Option<String>,
+    /// Suppress the stderr summary and progress
+    pub silent: bool,
+    /// How to show progress on stderr
+    pub progress: ReporterKind,
+    /// Cancellation token set by Ctrl+C or --timeout
+    pub cancel_flag: CancelToken,
+}
+
+/// Execute the extract command
+pub fn execute(args: ExtractArgs) -> Result<()> {
+    let stdout = std::io::stdout();
+    if stdout.is_terminal() {
+        bail!(
+            "Refusing to write image data to a terminal; redirect stdout or pipe it to a command"
+        );
+    }
+
+    eprintln_if!(
+        args.silent,
+        "{} {}",
+        style("Source:").bold(),
+        style(&args.source).cyan()
+    );
+    let source_info = validate_source_with_entry(&args.source, None, args.entry.as_deref())
+        .with_context(|| format!("Failed to validate source: {}", args.source))?;
+    let source_size = source_info.size.or(source_info.compressed_size);
+    match source_size {
+        Some(size) => eprintln_if!(
+            args.silent,
+            "  {} ({}, {})",
+            style("✓").green(),
+            format_bytes(size),
+            source_info.source_type.description()
+        ),
+        None => eprintln_if!(
+            args.silent,
+            "  {} (size unknown, {})",
+            style("✓").green(),
+            source_info.source_type.description()
+        ),
+    }
+
+    let mut source = match args.entry.as_deref() {
+        Some(entry) => Source::open_with_entry(&args.source, 0, Some(entry)),
+        None => Source::open_from_info(&source_info, 0),
+    }
+    .context("Failed to open source")?;
+
+    // Retrying a pipe write could repeat bytes the reader already consumed
+    let config = WriteConfig::new()
+        .block_size(DEFAULT_BLOCK_SIZE)
+        .retry_attempts(0)
+        .sync_on_complete(false);
+    let mut writer = Writer::with_config(config);
+    if let (None, Some(total), Some(counter)) = (
+        source_info.size,
+        source_info.compressed_size,
+        source.compressed_counter(),
+    ) {
+        writer = writer.track_compressed_input(counter, total);
+    }
+    let reporter = args.progress.create("Extracting", source_size, None);
+    let reporter_clone = reporter.clone();
+    let mut writer = writer.on_progress(move |progress| {
+        reporter_clone.on_write(progress);
+    });
+
+    let mut target = StreamTarget::new(stdout.lock());
+    let write_result = {
+        let _cancel_forward = CancelForward::spawn(&args.cancel_flag, writer.cancel_handle());
+        writer.write(
+            Prefetch::new(&mut source),
+            &mut target,
+            source_size.unwrap_or(0),
+        )
+    };
+    reporter.finish();
+
+    let result = match write_result {
+        Ok(result) => result,
+        Err(engraver_core::Error::Cancelled(_)) => return Err(CliError::Cancelled.into()),
+        // The reader went away (e.g. `| head`); stop quietly, like `cat`
+        Err(engraver_core::Error::Io(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => {
+            eprintln_if!(
+                args.silent,
+                "{} Output closed before the end of the image",
+                style("ℹ").blue()
+            );
+            return Ok(());
+        }
+        Err(e) => return Err(anyhow::Error::new(e).context("Failed to extract image")),
+    };
+    target.flush().context("Failed to flush stdout")?;
+
+    eprintln_if!(
+        args.silent,
+        "{} Extracted {} in {} ({})",
+        style("✓").green(),
+        format_bytes(result.bytes_written),
+        format_duration(result.elapsed.as_secs()),
+        result.speed_display()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,197 @@
+//! Formats command - shows which sources and checksums this build supports
+
+use anyhow::{Context, Result};
+use console::style;
+use serde::Serialize;
+
+use crate::json;
+use engraver_core::{BuildFeatures, SourceType};
+
+/// Execute the formats command
+pub fn execute(json: bool, json_compat: bool, silent: bool) -> Result<()> {
+    let features = engraver_core::build_features();
+    let formats = Formats::from(&features);
+
+    // JSON output mode - always output even in silent mode (it's machine-readable)
+    if json {
+        return json::print("formats", &formats, json_compat)
+            .context("Failed to serialize formats");
+    }
+
+    if silent {
+        return Ok(());
+    }
+
+    println!("{}", style("Sources:").bold());
+    for source in &formats.sources {
+        let matched_by = if !source.url_schemes.is_empty() {
+            source
+                .url_schemes
+                .iter()
+                .map(|scheme| format!("{}://", scheme))
+                .collect::<Vec<_>>()
+                .join(", ")
+        } else if !source.extensions.is_empty() {
+            source.extensions.join(", ")
+        } else {
+            "any other path".to_string()
+        };
+        println!(
+            "  {} {:<28} {}",
+            style("✓").green(),
+            source.description,
+            style(matched_by).dim()
+        );
+    }
+
+    println!();
+    println!(
+        "{} {}",
+        style("Compression:").bold(),
+        list(&formats.compression)
+    );
+    println!(
+        "{} {}",
+        style("Checksums:").bold(),
+        list(&formats.checksums)
+    );
+    println!(
+        "{} {}",
+        style("URL schemes:").bold(),
+        list(&formats.url_schemes)
+    );
+
+    if !formats.missing_features.is_empty() {
+        println!();
+        println!(
+            "{}",
+            style(format!(
+                "Not in this build: {} (rebuild with --features to enable)",
+                formats.missing_features.join(", ")
+            ))
+            .dim()
+        );
+    }
+
+    Ok(())
+}
+
+fn list(items: &[&str]) -> String {
+    if items.is_empty() {
+        "none".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+/// JSON form of `formats --json`
+///
+/// Changing these fields requires bumping [`crate::json::SCHEMA_VERSION`].
+#[derive(Serialize)]
+struct Formats {
+    sources: Vec<SourceFormat>,
+    compression: Vec<&'static str>,
+    checksums: Vec<&'static str>,
+    url_schemes: Vec<&'static str>,
+    missing_features: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+struct SourceFormat {
+    source_type: SourceType,
+    description: &'static str,
+    compressed: bool,
+    extensions: &'static [&'static str],
+    url_schemes: &'static [&'static str],
+}
+
+impl From<&BuildFeatures> for Formats {
+    fn from(features: &BuildFeatures) -> Self {
+        let source_types = features.source_types();
+        let sources: Vec<SourceFormat> = source_types
+            .iter()
+            .map(|&source_type| SourceFormat {
+                source_type,
+                description: source_type.description(),
+                compressed: source_type.is_compressed(),
+                extensions: source_type.extensions(),
+                url_schemes: source_type.url_schemes(),
+            })
+            .collect();
+
+        Self {
+            compression: source_types
+                .iter()
+                .filter(|source_type| source_type.is_compressed() && !source_type.is_archive())
+                .filter_map(|source_type| source_type.extension())
+                .map(|extension| extension.trim_start_matches('.'))
+                .collect(),
+            checksums: features
+                .checksum_algorithms()
+                .iter()
+                .map(|algorithm| algorithm.short_name())
+                .collect(),
+            url_schemes: sources
+                .iter()
+                .flat_map(|source| source.url_schemes.iter().copied())
+                .collect(),
+            missing_features: features.disabled(),
+            sources,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats_follow_features() {
+        let features = BuildFeatures {
+            compression: false,
+            remote: false,
+            checksum: false,
+            ..engraver_core::build_features()
+        };
+        let formats = Formats::from(&features);
+
+        assert!(formats.compression.is_empty());
+        assert!(formats.checksums.is_empty());
+        assert!(!formats.url_schemes.contains(&"https"));
+        assert!(formats.missing_features.contains(&"compression"));
+        assert!(formats
+            .sources
+            .iter()
+            .any(|source| source.source_type == SourceType::LocalFile));
+    }
+
+    #[test]
+    fn test_formats_json_lists_extensions() {
+        let features = BuildFeatures {
+            compression: true,
+            remote: true,
+            ..engraver_core::build_features()
+        };
+        let json = json::render("formats", &Formats::from(&features), false).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["schema_version"], crate::json::SCHEMA_VERSION);
+        let formats = &value["formats"];
+        assert!(formats["compression"]
+            .as_array()
+            .unwrap()
+            .contains(&"xz".into()));
+        assert!(formats["url_schemes"]
+            .as_array()
+            .unwrap()
+            .contains(&"https".into()));
+        let gzip = formats["sources"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|source| source["source_type"] == "Gzip")
+            .unwrap();
+        assert_eq!(gzip["extensions"][0], ".gz");
+        assert_eq!(gzip["compressed"], true);
+    }
+}
@@ -0,0 +1,179 @@
+//! History command - shows recently completed writes
+
+use anyhow::{Context, Result};
+use console::style;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::json;
+use engraver_core::{format_duration, HistoryEntry, HistoryLog};
+use engraver_detect::format_bytes;
+
+/// Arguments for the history command
+pub struct HistoryArgs {
+    /// History log to read
+    pub log: HistoryLog,
+    /// Maximum number of entries to show (newest first)
+    pub limit: Option<usize>,
+    /// Output in JSON format
+    pub json: bool,
+    /// Emit the unversioned JSON shape (`--json-compat-0`)
+    pub json_compat: bool,
+    /// Redact URLs in the source column
+    pub no_urls: bool,
+    /// Suppress output (for scripting)
+    pub silent: bool,
+}
+
+/// Execute the history command
+pub fn execute(args: HistoryArgs) -> Result<()> {
+    let mut entries = args
+        .log
+        .recent(args.limit)
+        .with_context(|| format!("Failed to read history: {}", args.log.path().display()))?;
+
+    if args.no_urls {
+        for entry in &mut entries {
+            entry.source = entry.display_source(true);
+        }
+    }
+
+    // JSON output mode - always output even in silent mode (it's machine-readable)
+    if args.json {
+        return json::print("entries", &entries, args.json_compat)
+            .context("Failed to serialize history");
+    }
+
+    if args.silent {
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("No writes recorded yet.");
+        println!(
+            "{}",
+            style(format!("History file: {}", args.log.path().display())).dim()
+        );
+        return Ok(());
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    println!(
+        "{} {} recent write(s):\n",
+        style("Showing").green().bold(),
+        entries.len()
+    );
+
+    for entry in &entries {
+        print_entry(entry, now);
+    }
+
+    Ok(())
+}
+
+/// Print a single history entry
+fn print_entry(entry: &HistoryEntry, now: u64) {
+    let verified = if entry.verified {
+        style("verified").green()
+    } else {
+        style("not verified").yellow()
+    };
+
+    println!(
+        "{} {} {} {}",
+        style("✓").green().bold(),
+        style(&entry.source).white().bold(),
+        style("→").dim(),
+        style(&entry.target).cyan()
+    );
+    println!(
+        "    {} | {} in {} | {}",
+        style(format_age(now.saturating_sub(entry.timestamp))).dim(),
+        format_bytes(entry.bytes_written),
+        format_duration(entry.elapsed as u64),
+        verified
+    );
+}
+
+/// Format an age in seconds as a short relative time (e.g., "5m ago")
+pub(crate) fn format_age(seconds: u64) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = MINUTE * 60;
+    const DAY: u64 = HOUR * 24;
+
+    if seconds >= DAY {
+        format!("{}d ago", seconds / DAY)
+    } else if seconds >= HOUR {
+        format!("{}h ago", seconds / HOUR)
+    } else if seconds >= MINUTE {
+        format!("{}m ago", seconds / MINUTE)
+    } else {
+        "just now".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn args_with_log(log: HistoryLog) -> HistoryArgs {
+        HistoryArgs {
+            log,
+            limit: None,
+            json: false,
+            json_compat: false,
+            no_urls: false,
+            silent: true,
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // format_age tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(0), "just now");
+        assert_eq!(format_age(59), "just now");
+        assert_eq!(format_age(60), "1m ago");
+        assert_eq!(format_age(3599), "59m ago");
+        assert_eq!(format_age(3600), "1h ago");
+        assert_eq!(format_age(86_400), "1d ago");
+        assert_eq!(format_age(3 * 86_400 + 5), "3d ago");
+    }
+
+    // -------------------------------------------------------------------------
+    // execute tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_execute_empty_history() {
+        let dir = TempDir::new().unwrap();
+        let log = HistoryLog::new(dir.path().join("history.jsonl"));
+        assert!(execute(args_with_log(log)).is_ok());
+    }
+
+    #[test]
+    fn test_execute_with_entries() {
+        let dir = TempDir::new().unwrap();
+        let log = HistoryLog::new(dir.path().join("history.jsonl"));
+        log.append(&HistoryEntry::new(
+            "https://example.com/a.iso?token=x",
+            "/dev/sdb",
+            1024,
+            Duration::from_secs(3),
+            true,
+        ))
+        .unwrap();
+
+        let mut args = args_with_log(log);
+        args.no_urls = true;
+        args.limit = Some(1);
+        assert!(execute(args).is_ok());
+    }
+}
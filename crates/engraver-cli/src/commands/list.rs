@@ -1,10 +1,43 @@
 //! List command - displays available drives
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use console::style;
+use engraver_platform::{open_device, DeviceInfo, OpenOptions};
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::json;
+
+/// How long `--identify` flashes a drive's activity LED
+pub const IDENTIFY_DURATION: Duration = Duration::from_secs(5);
+
+/// Flash the activity LED of the drive at `path` so it can be found
+///
+/// Needs read access to the device, so usually elevated privileges.
+pub fn identify(path: &str, silent: bool) -> Result<()> {
+    println_if!(
+        silent,
+        "{} Flashing the activity LED of {} for {}s...",
+        style("ℹ").blue(),
+        style(path).cyan(),
+        IDENTIFY_DURATION.as_secs()
+    );
+    engraver_platform::identify_device(path, IDENTIFY_DURATION)
+        .with_context(|| format!("Failed to identify {}", path))?;
+    println_if!(silent, "{} Done", style("✓").green().bold());
+    Ok(())
+}
 
 /// Execute the list command
-pub fn execute(show_all: bool, json: bool, silent: bool) -> Result<()> {
+///
+/// With `verbose`, each drive is opened read-only to print its geometry.
+pub fn execute(
+    show_all: bool,
+    json: bool,
+    json_compat: bool,
+    verbose: bool,
+    silent: bool,
+) -> Result<()> {
     let all_drives = engraver_detect::list_drives()?;
 
     let drives: Vec<_> = if show_all {
@@ -12,16 +45,14 @@ pub fn execute(show_all: bool, json: bool, silent: bool) -> Result<()> {
     } else {
         all_drives
             .iter()
-            .filter(|d| d.is_safe_target())
+            .filter(|d| d.is_safe_target() && d.media_present)
             .cloned()
             .collect()
     };
 
     // JSON output mode - always output even in silent mode (it's machine-readable)
     if json {
-        let output = serde_json_drives(&drives);
-        println!("{}", output);
-        return Ok(());
+        return json::print("drives", &drive_entries(&drives), json_compat);
     }
 
     // Silent mode - no human-readable output
@@ -50,11 +81,15 @@ pub fn execute(show_all: bool, json: bool, silent: bool) -> Result<()> {
     );
 
     for drive in &drives {
-        print_drive(drive);
+        print_drive(drive, verbose);
     }
 
     if !show_all {
-        let hidden = all_drives.len() - drives.len();
+        let empty = all_drives
+            .iter()
+            .filter(|d| d.is_safe_target() && !d.media_present)
+            .count();
+        let hidden = all_drives.len() - drives.len() - empty;
         if hidden > 0 {
             println!(
                 "{}",
@@ -65,13 +100,23 @@ pub fn execute(show_all: bool, json: bool, silent: bool) -> Result<()> {
                 .dim()
             );
         }
+        if empty > 0 {
+            println!(
+                "{}",
+                style(format!(
+                    "Note: {} card reader(s) with no card hidden. Use --all to show.",
+                    empty
+                ))
+                .dim()
+            );
+        }
     }
 
     Ok(())
 }
 
 /// Print a single drive's information
-fn print_drive(drive: &engraver_detect::Drive) {
+fn print_drive(drive: &engraver_detect::Drive, verbose: bool) {
     let status = if drive.is_safe_target() {
         style("✓").green().bold()
     } else {
@@ -84,12 +129,18 @@ fn print_drive(drive: &engraver_detect::Drive) {
         style("internal").yellow()
     };
 
+    let size = if drive.media_present {
+        drive.size_display()
+    } else {
+        style("no media").yellow().to_string()
+    };
+
     println!(
         "{} {} {} ({}, {})",
         status,
         style(&drive.path).white().bold(),
         style(&drive.display_name()).white(),
-        drive.size_display(),
+        size,
         removable
     );
 
@@ -124,6 +175,29 @@ fn print_drive(drive: &engraver_detect::Drive) {
         println!("    Reason: {}", style(reason).dim());
     }
 
+    // Show why a safe target still deserves a second look
+    if let Some(reason) = &drive.caution_reason {
+        println!("    Caution: {}", style(reason).yellow());
+    }
+
+    // Show block sizes and media type, which needs read access to the device
+    if verbose && drive.media_present {
+        let options = OpenOptions::new()
+            .write(false)
+            .direct_io(false)
+            .exclusive(false);
+        match open_device(&drive.path, options) {
+            Ok(device) => println!(
+                "    Geometry: {}",
+                style(geometry_line(device.info())).dim()
+            ),
+            Err(e) => println!(
+                "    Geometry: {}",
+                style(format!("unavailable ({})", e)).yellow()
+            ),
+        }
+    }
+
     // Show mount points
     if !drive.mount_points.is_empty() {
         println!(
@@ -144,12 +218,18 @@ fn print_drive(drive: &engraver_detect::Drive) {
                 .map(|m| format!(" → {}", m))
                 .unwrap_or_default();
 
+            let usage = part
+                .usage_display()
+                .map(|usage| format!(" {}", usage))
+                .unwrap_or_default();
+
             println!(
-                "      {} {} [{}]{}",
+                "      {} {} [{}]{}{}",
                 style(&part.path).dim(),
                 label,
                 fs,
-                style(mount).cyan()
+                style(mount).cyan(),
+                style(usage).dim()
             );
         }
     }
@@ -157,156 +237,83 @@ fn print_drive(drive: &engraver_detect::Drive) {
     println!();
 }
 
-/// Simple JSON serialization without serde dependency on Drive
-fn serde_json_drives(drives: &[engraver_detect::Drive]) -> String {
-    let mut output = String::from("[\n");
-
-    for (i, drive) in drives.iter().enumerate() {
-        output.push_str("  {\n");
-        output.push_str(&format!(
-            "    \"path\": \"{}\",\n",
-            escape_json(&drive.path)
-        ));
-        output.push_str(&format!(
-            "    \"vendor\": {},\n",
-            opt_json_str(&drive.vendor)
-        ));
-        output.push_str(&format!("    \"model\": {},\n", opt_json_str(&drive.model)));
-        output.push_str(&format!("    \"size\": {},\n", drive.size));
-        output.push_str(&format!(
-            "    \"size_display\": \"{}\",\n",
-            drive.size_display()
-        ));
-        output.push_str(&format!("    \"removable\": {},\n", drive.removable));
-        output.push_str(&format!("    \"is_system\": {},\n", drive.is_system));
-        output.push_str(&format!(
-            "    \"is_safe_target\": {},\n",
-            drive.is_safe_target()
-        ));
-        output.push_str(&format!("    \"drive_type\": \"{}\",\n", drive.drive_type));
-        output.push_str(&format!(
-            "    \"usb_speed\": {},\n",
-            drive
-                .usb_speed
-                .as_ref()
-                .map_or("null".to_string(), |s| format!("\"{}\"", s))
-        ));
-        output.push_str(&format!(
-            "    \"usb_speed_slow\": {},\n",
-            drive.usb_speed.as_ref().is_some_and(|s| s.is_slow())
-        ));
-        output.push_str(&format!(
-            "    \"mount_points\": [{}],\n",
-            drive
-                .mount_points
-                .iter()
-                .map(|m| format!("\"{}\"", escape_json(m)))
-                .collect::<Vec<_>>()
-                .join(", ")
-        ));
-        output.push_str(&format!(
-            "    \"partition_count\": {}\n",
-            drive.partitions.len()
-        ));
-        output.push_str("  }");
-
-        if i < drives.len() - 1 {
-            output.push(',');
-        }
-        output.push('\n');
+/// Describe a device's geometry, e.g. `logical 512 B | physical 4096 B | SSD`
+///
+/// Fields the platform doesn't report are left out.
+fn geometry_line(info: &DeviceInfo) -> String {
+    let mut parts = vec![format!("logical {} B", info.block_size)];
+    if info.physical_block_size > 0 {
+        parts.push(format!("physical {} B", info.physical_block_size));
     }
-
-    output.push(']');
-    output
+    if let Some(optimal) = info.optimal_io_size {
+        parts.push(format!("optimal I/O {} B", optimal));
+    }
+    parts.push(
+        if info.is_rotational {
+            "rotational"
+        } else {
+            "non-rotational"
+        }
+        .to_string(),
+    );
+    parts.join(" | ")
 }
 
-fn escape_json(s: &str) -> String {
-    s.replace('\\', "\\\\")
-        .replace('"', "\\\"")
-        .replace('\n', "\\n")
-        .replace('\r', "\\r")
-        .replace('\t', "\\t")
+/// JSON form of a drive for `list --json`
+///
+/// Changing these fields requires bumping [`crate::json::SCHEMA_VERSION`].
+#[derive(Serialize)]
+struct DriveEntry<'a> {
+    path: &'a str,
+    vendor: Option<&'a str>,
+    model: Option<&'a str>,
+    size: u64,
+    size_display: String,
+    removable: bool,
+    media_present: bool,
+    is_system: bool,
+    is_safe_target: bool,
+    drive_type: String,
+    usb_speed: Option<String>,
+    usb_speed_slow: bool,
+    mount_points: &'a [String],
+    partition_count: usize,
+    partitions: &'a [engraver_detect::Partition],
 }
 
-fn opt_json_str(opt: &Option<String>) -> String {
-    match opt {
-        Some(s) => format!("\"{}\"", escape_json(s)),
-        None => "null".to_string(),
+impl<'a> From<&'a engraver_detect::Drive> for DriveEntry<'a> {
+    fn from(drive: &'a engraver_detect::Drive) -> Self {
+        Self {
+            path: &drive.path,
+            vendor: drive.vendor.as_deref(),
+            model: drive.model.as_deref(),
+            size: drive.size,
+            size_display: drive.size_display(),
+            removable: drive.removable,
+            media_present: drive.media_present,
+            is_system: drive.is_system,
+            is_safe_target: drive.is_safe_target(),
+            drive_type: drive.drive_type.to_string(),
+            usb_speed: drive.usb_speed.as_ref().map(|s| s.to_string()),
+            usb_speed_slow: drive.usb_speed.as_ref().is_some_and(|s| s.is_slow()),
+            mount_points: &drive.mount_points,
+            partition_count: drive.partitions.len(),
+            partitions: &drive.partitions,
+        }
     }
 }
 
+fn drive_entries(drives: &[engraver_detect::Drive]) -> Vec<DriveEntry<'_>> {
+    drives.iter().map(DriveEntry::from).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use engraver_detect::{Drive, DriveType, Partition, UsbSpeed};
 
     // -------------------------------------------------------------------------
-    // escape_json tests
-    // -------------------------------------------------------------------------
-
-    #[test]
-    fn test_escape_json_no_special_chars() {
-        assert_eq!(escape_json("hello world"), "hello world");
-        assert_eq!(escape_json("simple"), "simple");
-        assert_eq!(escape_json(""), "");
-    }
-
-    #[test]
-    fn test_escape_json_backslash() {
-        assert_eq!(escape_json("path\\to\\file"), "path\\\\to\\\\file");
-        assert_eq!(escape_json("\\"), "\\\\");
-    }
-
-    #[test]
-    fn test_escape_json_quotes() {
-        assert_eq!(escape_json("say \"hello\""), "say \\\"hello\\\"");
-        assert_eq!(escape_json("\"quoted\""), "\\\"quoted\\\"");
-    }
-
-    #[test]
-    fn test_escape_json_newlines() {
-        assert_eq!(escape_json("line1\nline2"), "line1\\nline2");
-        assert_eq!(escape_json("with\r\nwindows"), "with\\r\\nwindows");
-    }
-
-    #[test]
-    fn test_escape_json_tabs() {
-        assert_eq!(escape_json("col1\tcol2"), "col1\\tcol2");
-    }
-
-    #[test]
-    fn test_escape_json_combined() {
-        assert_eq!(
-            escape_json("path\\with \"quotes\"\nand\ttabs"),
-            "path\\\\with \\\"quotes\\\"\\nand\\ttabs"
-        );
-    }
-
-    // -------------------------------------------------------------------------
-    // opt_json_str tests
-    // -------------------------------------------------------------------------
-
-    #[test]
-    fn test_opt_json_str_some() {
-        assert_eq!(opt_json_str(&Some("value".to_string())), "\"value\"");
-        assert_eq!(opt_json_str(&Some("SanDisk".to_string())), "\"SanDisk\"");
-    }
-
-    #[test]
-    fn test_opt_json_str_none() {
-        assert_eq!(opt_json_str(&None), "null");
-    }
-
-    #[test]
-    fn test_opt_json_str_escapes() {
-        assert_eq!(
-            opt_json_str(&Some("with \"quotes\"".to_string())),
-            "\"with \\\"quotes\\\"\""
-        );
-    }
-
-    // -------------------------------------------------------------------------
-    // serde_json_drives tests
+    // list --json tests
     // -------------------------------------------------------------------------
 
     fn create_test_drive() -> Drive {
@@ -325,20 +332,51 @@ mod tests {
             is_system: false,
             system_reason: None,
             usb_speed: Some(UsbSpeed::SuperSpeed),
+            media_present: true,
+            enclosure: false,
+            rotational: false,
+            caution_reason: None,
+        }
+    }
+
+    fn create_test_info() -> DeviceInfo {
+        DeviceInfo {
+            path: "/dev/sdb".to_string(),
+            size: 16 * 1024 * 1024 * 1024,
+            block_size: 512,
+            direct_io: false,
+            physical_block_size: 4096,
+            optimal_io_size: Some(1024 * 1024),
+            is_rotational: false,
         }
     }
 
+    fn render_compat(drives: &[Drive]) -> String {
+        json::render("drives", &drive_entries(drives), true).unwrap()
+    }
+
     #[test]
     fn test_serde_json_drives_empty() {
         let drives: Vec<Drive> = vec![];
-        let json = serde_json_drives(&drives);
-        assert_eq!(json, "[\n]");
+        let json = render_compat(&drives);
+        assert_eq!(json, "[]");
+    }
+
+    #[test]
+    fn test_list_json_versioned() {
+        let drives = vec![create_test_drive()];
+        let json = json::render("drives", &drive_entries(&drives), false).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["schema_version"], crate::json::SCHEMA_VERSION);
+        assert_eq!(value["drives"][0]["path"], "/dev/sdb");
+        assert_eq!(value["drives"][0]["partition_count"], 0);
     }
 
     #[test]
     fn test_serde_json_drives_single() {
         let drives = vec![create_test_drive()];
-        let json = serde_json_drives(&drives);
+        let json = render_compat(&drives);
 
         assert!(json.starts_with("[\n"));
         assert!(json.ends_with("\n]"));
@@ -347,11 +385,24 @@ mod tests {
         assert!(json.contains("\"model\": \"Ultra USB 3.0\""));
         assert!(json.contains("\"size\": 17179869184"));
         assert!(json.contains("\"removable\": true"));
+        assert!(json.contains("\"media_present\": true"));
         assert!(json.contains("\"is_system\": false"));
         assert!(json.contains("\"is_safe_target\": true"));
         assert!(json.contains("\"drive_type\": \"USB\""));
         assert!(json.contains("\"usb_speed\": \"USB 3.0 (5 Gbps)\""));
-        assert!(json.contains("\"mount_points\": [\"/mnt/usb\"]"));
+        assert!(json.contains("\"mount_points\": [\n      \"/mnt/usb\"\n    ]"));
+    }
+
+    #[test]
+    fn test_serde_json_drives_no_media() {
+        let mut drive = create_test_drive();
+        drive.size = 0;
+        drive.media_present = false;
+
+        let json = render_compat(&[drive]);
+
+        assert!(json.contains("\"media_present\": false"));
+        assert!(json.contains("\"size\": 0"));
     }
 
     #[test]
@@ -362,7 +413,7 @@ mod tests {
         drive2.vendor = None;
 
         let drives = vec![drive1, drive2];
-        let json = serde_json_drives(&drives);
+        let json = render_compat(&drives);
 
         // Should have comma between objects
         assert!(json.contains("},\n"));
@@ -384,6 +435,8 @@ mod tests {
                 filesystem: Some("vfat".to_string()),
                 label: Some("BOOT".to_string()),
                 mount_point: Some("/boot/efi".to_string()),
+                used: Some(1024),
+                available: Some(4096),
             },
             Partition {
                 path: "/dev/sdb2".to_string(),
@@ -391,13 +444,18 @@ mod tests {
                 filesystem: Some("ext4".to_string()),
                 label: None,
                 mount_point: None,
+                used: None,
+                available: None,
             },
         ];
 
         let drives = vec![drive];
-        let json = serde_json_drives(&drives);
+        let json = render_compat(&drives);
 
         assert!(json.contains("\"partition_count\": 2"));
+        assert!(json.contains("\"used\": 1024"));
+        assert!(json.contains("\"available\": 4096"));
+        assert!(json.contains("\"used\": null"));
     }
 
     #[test]
@@ -406,7 +464,7 @@ mod tests {
         drive.model = Some("Model \"with\" quotes".to_string());
 
         let drives = vec![drive];
-        let json = serde_json_drives(&drives);
+        let json = render_compat(&drives);
 
         assert!(json.contains("Model \\\"with\\\" quotes"));
     }
@@ -417,7 +475,7 @@ mod tests {
         drive.usb_speed = Some(UsbSpeed::High); // USB 2.0 High Speed
 
         let drives = vec![drive];
-        let json = serde_json_drives(&drives);
+        let json = render_compat(&drives);
 
         // High speed (USB 2.0) is considered slow for USB 3.0 capable devices
         assert!(json.contains("\"usb_speed\":"));
@@ -430,9 +488,32 @@ mod tests {
         drive.usb_speed = None;
 
         let drives = vec![drive];
-        let json = serde_json_drives(&drives);
+        let json = render_compat(&drives);
 
         assert!(json.contains("\"usb_speed\": null"));
         assert!(json.contains("\"usb_speed_slow\": false"));
     }
+
+    // -------------------------------------------------------------------------
+    // list --verbose tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_geometry_line_full() {
+        assert_eq!(
+            geometry_line(&create_test_info()),
+            "logical 512 B | physical 4096 B | optimal I/O 1048576 B | non-rotational"
+        );
+    }
+
+    #[test]
+    fn test_geometry_line_skips_unreported() {
+        let info = DeviceInfo {
+            physical_block_size: 0,
+            optimal_io_size: None,
+            is_rotational: true,
+            ..create_test_info()
+        };
+        assert_eq!(geometry_line(&info), "logical 512 B | rotational");
+    }
 }
@@ -4,6 +4,10 @@ pub mod benchmark;
 pub mod checksum;
 pub mod config;
 pub mod erase;
+pub mod extract;
+pub mod formats;
+pub mod history;
 pub mod list;
+pub mod resume;
 pub mod verify;
 pub mod write;
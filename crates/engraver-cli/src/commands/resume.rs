@@ -0,0 +1,192 @@
+//! Resume command - lists and clears checkpoints of interrupted writes
+
+use anyhow::{Context, Result};
+use console::style;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::history::format_age;
+use engraver_core::{CheckpointManager, WriteCheckpoint};
+
+/// What the resume command should do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResumeAction {
+    /// Print every pending checkpoint
+    List,
+    /// Delete every checkpoint
+    ClearAll,
+    /// Delete the checkpoint with this session ID
+    Clear(String),
+}
+
+/// Arguments for the resume command
+pub struct ResumeArgs {
+    /// Checkpoint store to operate on
+    pub manager: CheckpointManager,
+    /// Action to perform
+    pub action: ResumeAction,
+    /// Suppress output (for scripting)
+    pub silent: bool,
+}
+
+/// Execute the resume command
+pub fn execute(args: ResumeArgs) -> Result<()> {
+    match args.action {
+        ResumeAction::List => list(&args.manager, args.silent),
+        ResumeAction::ClearAll => {
+            let removed = args
+                .manager
+                .clear()
+                .context("Failed to clear checkpoints")?;
+            println_if!(
+                args.silent,
+                "{} Removed {} checkpoint(s)",
+                style("✓").green().bold(),
+                removed
+            );
+            Ok(())
+        }
+        ResumeAction::Clear(session_id) => {
+            let removed = args
+                .manager
+                .remove_session(&session_id)
+                .context("Failed to remove checkpoint")?;
+            if !removed {
+                anyhow::bail!("No checkpoint with session ID {}", session_id);
+            }
+            println_if!(
+                args.silent,
+                "{} Removed checkpoint {}",
+                style("✓").green().bold(),
+                session_id
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Print every pending checkpoint, most recent first
+fn list(manager: &CheckpointManager, silent: bool) -> Result<()> {
+    let checkpoints = manager.list().context("Failed to read checkpoints")?;
+
+    if silent {
+        return Ok(());
+    }
+
+    if checkpoints.is_empty() {
+        println!("No interrupted writes to resume.");
+        return Ok(());
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    println!(
+        "{} {} interrupted write(s):\n",
+        style("Found").green().bold(),
+        checkpoints.len()
+    );
+
+    for checkpoint in &checkpoints {
+        print_checkpoint(checkpoint, now);
+    }
+
+    println!(
+        "\n{}",
+        style("Resume with: engraver write <SOURCE> <TARGET> --resume").dim()
+    );
+
+    Ok(())
+}
+
+/// Print a single checkpoint
+fn print_checkpoint(checkpoint: &WriteCheckpoint, now: u64) {
+    println!(
+        "{} {} {} {}",
+        style("•").yellow().bold(),
+        style(&checkpoint.source_path).white().bold(),
+        style("→").dim(),
+        style(&checkpoint.target_path).cyan()
+    );
+    println!(
+        "    {} | {:.1}% | {} | resumed {} time(s)",
+        style(&checkpoint.session_id).dim(),
+        checkpoint.percentage(),
+        format_age(now.saturating_sub(checkpoint.last_update)),
+        checkpoint.resume_count
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engraver_core::{SourceInfo, WriteConfig};
+    use tempfile::TempDir;
+
+    fn manager_with_checkpoint(dir: &TempDir) -> (CheckpointManager, WriteCheckpoint) {
+        let manager = CheckpointManager::new(dir.path()).unwrap();
+        let mut checkpoint = WriteCheckpoint::new(
+            &SourceInfo::local("/tmp/image.img", 1024 * 1024),
+            "/dev/sdb",
+            8 * 1024 * 1024,
+            &WriteConfig::new(),
+        );
+        checkpoint.session_id = "abc-123".to_string();
+        manager.save(&checkpoint).unwrap();
+        (manager, checkpoint)
+    }
+
+    fn args(manager: CheckpointManager, action: ResumeAction) -> ResumeArgs {
+        ResumeArgs {
+            manager,
+            action,
+            silent: true,
+        }
+    }
+
+    // ---- execute tests ----
+
+    #[test]
+    fn test_list_succeeds() {
+        let dir = TempDir::new().unwrap();
+        let (manager, _) = manager_with_checkpoint(&dir);
+        execute(args(manager, ResumeAction::List)).unwrap();
+    }
+
+    #[test]
+    fn test_clear_session() {
+        let dir = TempDir::new().unwrap();
+        let (manager, checkpoint) = manager_with_checkpoint(&dir);
+
+        execute(args(
+            manager.clone(),
+            ResumeAction::Clear(checkpoint.session_id.clone()),
+        ))
+        .unwrap();
+        assert!(manager.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_clear_unknown_session_fails() {
+        let dir = TempDir::new().unwrap();
+        let (manager, _) = manager_with_checkpoint(&dir);
+
+        let err = execute(args(
+            manager.clone(),
+            ResumeAction::Clear("nope".to_string()),
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("nope"));
+        assert_eq!(manager.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_clear_all() {
+        let dir = TempDir::new().unwrap();
+        let (manager, _) = manager_with_checkpoint(&dir);
+
+        execute(args(manager.clone(), ResumeAction::ClearAll)).unwrap();
+        assert!(manager.list().unwrap().is_empty());
+    }
+}
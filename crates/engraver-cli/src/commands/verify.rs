@@ -2,43 +2,234 @@
 
 use anyhow::{bail, Context, Result};
 use console::style;
-use indicatif::{ProgressBar, ProgressStyle};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use serde::Serialize;
 
+use crate::commands::write::{target_identity, CancelForward};
+use crate::error::CliError;
+use crate::json;
+use crate::progress::ReporterKind;
 use engraver_core::{
-    validate_source, ChecksumAlgorithm, Source, SourceType, Verifier, VerifyConfig,
+    checksum_from_sums, parse_block_size_clamped, parse_size, validate_source,
+    validate_verify_checkpoint, CancelToken, CheckpointManager, Checksum, ChecksumAlgorithm,
+    Source, SourceType, TargetIdentity, VerificationResult, Verifier, VerifyCheckpoint,
+    VerifyConfig, MAX_BLOCK_SIZE, MIN_BLOCK_SIZE,
 };
-use engraver_detect::list_drives;
+
+use engraver_detect::{list_drives, Drive};
 use engraver_platform::{has_elevated_privileges, open_device, OpenOptions};
+use std::io::Read;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+/// How the verify command reports progress and its outcome
+#[derive(Debug, Clone, Copy)]
+pub struct VerifyOutput {
+    /// Suppress human-readable output
+    pub silent: bool,
+    /// How progress is shown
+    pub progress: ReporterKind,
+    /// Print the outcome as a JSON object on stdout instead of text
+    pub json: bool,
+    /// Emit JSON without the schema_version wrapper
+    pub json_compat: bool,
+}
+
+impl VerifyOutput {
+    /// Whether human-readable output is suppressed (always with `--json`)
+    fn quiet(&self) -> bool {
+        self.silent || self.json
+    }
+
+    /// Print `report` if `--json` was given
+    fn report(&self, report: &VerifyReport) -> Result<()> {
+        if self.json {
+            json::print("verification", report, self.json_compat)?;
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of a verification, as printed by `verify --json`
+#[derive(Debug, Serialize)]
+struct VerifyReport {
+    success: bool,
+    bytes_verified: u64,
+    mismatches: u64,
+    first_mismatch_offset: Option<u64>,
+    elapsed_seconds: f64,
+    speed_bps: u64,
+}
+
+impl VerifyReport {
+    /// Report for a checksum comparison, where a mismatch has no offset
+    fn checksum(matched: bool, bytes_verified: u64, elapsed: Duration) -> Self {
+        let speed_bps = if elapsed.as_secs_f64() > 0.0 {
+            (bytes_verified as f64 / elapsed.as_secs_f64()) as u64
+        } else {
+            0
+        };
+        Self {
+            success: matched,
+            bytes_verified,
+            mismatches: u64::from(!matched),
+            first_mismatch_offset: None,
+            elapsed_seconds: elapsed.as_secs_f64(),
+            speed_bps,
+        }
+    }
+}
+
+impl From<&VerificationResult> for VerifyReport {
+    fn from(result: &VerificationResult) -> Self {
+        Self {
+            success: result.success,
+            bytes_verified: result.bytes_verified,
+            mismatches: result.mismatches,
+            first_mismatch_offset: result.first_mismatch_offset,
+            elapsed_seconds: result.elapsed.as_secs_f64(),
+            speed_bps: result.speed_bps,
+        }
+    }
+}
+
+/// What the verify command compares the target against
+#[derive(Debug)]
+pub enum VerifyMode {
+    /// Compare the target with a source image
+    Image {
+        /// Source image (local file or URL)
+        source: String,
+        /// Target device
+        target: String,
+    },
+    /// Hash the first `size` bytes of the target and compare with a known hash
+    Checksum {
+        /// Target device
+        target: String,
+        /// Expected checksum
+        expected: Checksum,
+        /// Number of bytes to read from the target
+        size: u64,
+    },
+}
+
+impl VerifyMode {
+    /// Work out the mode from the command-line arguments
+    ///
+    /// With `--checksum`, the only positional argument is the target device.
+    pub fn resolve(
+        source: Option<String>,
+        target: Option<String>,
+        checksum: Option<String>,
+        algorithm: Option<String>,
+        size: Option<String>,
+    ) -> Result<Self> {
+        let Some(hash) = checksum else {
+            if size.is_some() {
+                bail!("--size only applies with --checksum or --sums");
+            }
+            return match (source, target) {
+                (Some(source), Some(target)) => Ok(VerifyMode::Image { source, target }),
+                _ => bail!(
+                    "A source image and a target device are required \
+                     (or use --checksum <HASH> --size <N> <TARGET>)"
+                ),
+            };
+        };
+
+        let target = lone_target(source, target, "--checksum")?;
+        let size = checksum_size(size, "--checksum")?;
+
+        let algorithm = match algorithm {
+            Some(name) => name.parse::<ChecksumAlgorithm>()?,
+            None => ChecksumAlgorithm::from_hex_length(hash.trim().len()).with_context(|| {
+                format!(
+                    "Cannot infer the checksum algorithm from a {}-character hash; pass --algorithm",
+                    hash.trim().len()
+                )
+            })?,
+        };
+        let expected = Checksum::from_hex(algorithm, &hash)?;
+
+        Ok(VerifyMode::Checksum {
+            target,
+            expected,
+            size,
+        })
+    }
+
+    /// Work out a checksum mode whose hash is `name`'s entry in the `sums` file
+    ///
+    /// As with `--checksum`, the only positional argument is the target device.
+    pub fn resolve_sums(
+        source: Option<String>,
+        target: Option<String>,
+        sums: &Path,
+        name: &str,
+        size: Option<String>,
+    ) -> Result<Self> {
+        let target = lone_target(source, target, "--sums")?;
+        let size = checksum_size(size, "--sums")?;
+        let expected = checksum_from_sums(sums, name)
+            .with_context(|| format!("Failed to look up {} in {}", name, sums.display()))?;
+
+        Ok(VerifyMode::Checksum {
+            target,
+            expected,
+            size,
+        })
+    }
+}
+
+/// The target device when `flag` stands in for the source image
+fn lone_target(source: Option<String>, target: Option<String>, flag: &str) -> Result<String> {
+    match (source, target) {
+        (Some(target), None) => Ok(target),
+        (Some(_), Some(_)) => {
+            bail!(
+                "{} replaces the source image; pass only the target device",
+                flag
+            )
+        }
+        _ => bail!("A target device is required with {}", flag),
+    }
+}
+
+/// The `--size` to hash, which `flag` requires
+fn checksum_size(size: Option<String>, flag: &str) -> Result<u64> {
+    let size = size.with_context(|| format!("--size is required with {}", flag))?;
+    let size = parse_size(&size).with_context(|| format!("Invalid --size: {}", size))?;
+    if size == 0 {
+        bail!("--size must be greater than zero");
+    }
+    Ok(size)
+}
 
 /// Execute the verify command
+///
+/// With `resume`, a local-image verification continues from the checkpoint
+/// saved when a previous run was cancelled. Unless `drop_cache` is false, the
+/// target's cached pages are dropped first so the device itself is read.
 pub fn execute(
     source: &str,
     target: &str,
     block_size_str: &str,
-    cancel_flag: Arc<AtomicBool>,
-    silent: bool,
+    resume: bool,
+    drop_cache: bool,
+    cancel_flag: CancelToken,
+    output: VerifyOutput,
 ) -> Result<()> {
+    let silent = output.quiet();
+    let progress = output.progress;
+
     // Parse block size
     let block_size = parse_block_size(block_size_str)?;
 
     // Check for elevated privileges (needed for raw device access)
     if !has_elevated_privileges() {
-        #[cfg(unix)]
-        bail!(
-            "Root privileges required.\n\
-             Try running with: sudo engraver verify ..."
-        );
-
-        #[cfg(windows)]
-        bail!(
-            "Administrator privileges required.\n\
-             Right-click and select 'Run as administrator'."
-        );
-
-        #[cfg(not(any(unix, windows)))]
-        bail!("Elevated privileges required for raw device access.");
+        return Err(CliError::privileges_required("verify").into());
     }
 
     // Validate source
@@ -69,22 +260,7 @@ pub fn execute(
         style(target).cyan()
     );
 
-    let drives = list_drives().context("Failed to list drives")?;
-    let target_drive = drives
-        .iter()
-        .find(|d| d.path == target || d.raw_path == target);
-
-    if let Some(drive) = target_drive {
-        println_if!(
-            silent,
-            "  {} {} ({})",
-            style("✓").green(),
-            drive.display_name(),
-            format_size(drive.size)
-        );
-    } else {
-        println_if!(silent, "  {} Device found", style("✓").green());
-    }
+    let target_drive = find_target_drive(target, silent)?;
 
     // Open target device for reading using platform layer
     let device_path = get_raw_device_path(target);
@@ -101,59 +277,121 @@ pub fn execute(
 
     println_if!(silent, "\n{}", style("Verifying...").bold());
 
-    // Set up cancel handler
-    let cancel_clone = cancel_flag.clone();
-
     if source_is_local {
         // Direct byte-by-byte comparison for local files
         let mut source_file = std::fs::File::open(source)
             .with_context(|| format!("Failed to open source: {}", source))?;
 
-        // Create progress bar
-        let pb = if silent {
-            ProgressBar::hidden()
-        } else if total_size > 0 {
-            ProgressBar::new(total_size)
-        } else {
-            ProgressBar::new_spinner()
+        let identity = target_drive
+            .as_ref()
+            .map_or_else(TargetIdentity::default, target_identity);
+        let manager = match CheckpointManager::default_location() {
+            Ok(manager) => Some(manager),
+            Err(e) => {
+                tracing::warn!("Failed to create checkpoint manager: {}", e);
+                None
+            }
+        };
+        let resumed = match (&manager, resume) {
+            (Some(manager), true) => {
+                find_resume_checkpoint(manager, source, target, total_size, &identity, silent)
+            }
+            _ => None,
         };
+        let start_offset = resumed.as_ref().map_or(0, |c| c.bytes_verified);
+        let mut checkpoint = resumed.unwrap_or_else(|| {
+            VerifyCheckpoint::new(source, total_size, target, identity.size, block_size)
+                .with_source_modified(source_modified(source))
+                .with_target_identity(&identity)
+        });
 
-        if !silent {
-            pb.set_style(
-                ProgressStyle::default_bar()
-                    .template(
-                        "  {spinner:.green} Comparing [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})",
-                    )
-                    .unwrap()
-                    .progress_chars("█▓░"),
-            );
-        }
+        let reporter = progress.create("Comparing", Some(total_size), None);
 
         // Set up verifier
-        let config = VerifyConfig::new().block_size(block_size);
+        let config = VerifyConfig::new()
+            .block_size(block_size)
+            .drop_cache(drop_cache);
         let verifier = Verifier::with_config(config);
+        verifier
+            .drop_target_cache(&*target_reader)
+            .context("Failed to drop cached pages before verifying")?;
 
         // Connect cancel flag
-        let verifier_cancel = verifier.cancel_handle();
-        std::thread::spawn(move || {
-            while cancel_clone.load(Ordering::SeqCst) {
-                std::thread::sleep(std::time::Duration::from_millis(100));
-            }
-            verifier_cancel.store(true, Ordering::SeqCst);
-        });
-
-        // Add progress callback
-        let pb_clone = pb.clone();
-        let verifier = verifier.on_progress(move |progress| {
-            pb_clone.set_position(progress.bytes_processed);
+        let _forward = CancelForward::spawn(&cancel_flag, verifier.cancel_handle());
+
+        // Add progress callback, remembering how far the compare got for a checkpoint
+        let reporter_clone = reporter.clone();
+        let verified = Arc::new(AtomicU64::new(start_offset));
+        let verified_clone = verified.clone();
+        let verifier = verifier.on_progress(move |p| {
+            reporter_clone.on_verify(p);
+            verified_clone.store(p.bytes_processed, Ordering::Relaxed);
         });
 
+        let started = Instant::now();
         let mut verifier = verifier;
-        let result = verifier.compare(&mut source_file, &mut *target_reader, total_size);
+        let result = if start_offset > 0 {
+            // The skipped prefix is not re-read, so there is no source checksum
+            verifier
+                .compare_from_offset(
+                    &mut source_file,
+                    &mut *target_reader,
+                    total_size,
+                    start_offset,
+                )
+                .map(|result| (result, None))
+        } else {
+            // Hash the source while comparing so the checksum comes for free
+            verifier
+                .compare_and_hash(
+                    &mut source_file,
+                    &mut *target_reader,
+                    total_size,
+                    ChecksumAlgorithm::Sha256,
+                )
+                .map(|(result, checksum)| (result, Some(checksum)))
+        };
 
-        pb.finish_and_clear();
+        reporter.finish();
+
+        if let Some(manager) = &manager {
+            match &result {
+                Err(engraver_core::Error::Cancelled(_)) => {
+                    let elapsed =
+                        Duration::from_secs_f64(checkpoint.elapsed_seconds) + started.elapsed();
+                    checkpoint.update_progress(verified.load(Ordering::Relaxed), elapsed);
+                    match manager.save_verify(&checkpoint) {
+                        Ok(()) => println_if!(
+                            silent,
+                            "\n  {} Progress saved at {} ({:.1}%); run again with --resume to continue",
+                            style("ℹ").blue(),
+                            format_size(checkpoint.bytes_verified),
+                            checkpoint.percentage()
+                        ),
+                        Err(e) => tracing::warn!("Failed to save verify checkpoint: {}", e),
+                    }
+                }
+                Ok(_) => {
+                    if let Err(e) = manager.remove_verify(&checkpoint) {
+                        tracing::warn!("Failed to remove verify checkpoint: {}", e);
+                    }
+                }
+                Err(_) => {}
+            }
+        }
 
-        handle_verify_result(result, silent)
+        let checksum = result
+            .as_ref()
+            .ok()
+            .and_then(|(_, checksum)| checksum.as_ref().map(Checksum::to_hex));
+        if let Ok((result, _)) = &result {
+            output.report(&VerifyReport::from(result))?;
+        }
+        handle_verify_result(result.map(|(result, _)| result), silent)?;
+        if let Some(hex) = checksum {
+            println_if!(silent, "    SHA-256: {}", hex);
+        }
+        Ok(())
     } else {
         // For remote/compressed sources, compare checksums
         println_if!(
@@ -161,27 +399,28 @@ pub fn execute(
             "  {} Source is remote/compressed, using checksum verification",
             style("ℹ").blue()
         );
-
-        // Calculate checksum of target
-        let pb = if silent {
-            ProgressBar::hidden()
-        } else {
-            ProgressBar::new(total_size)
-        };
-        if !silent {
-            pb.set_style(
-                ProgressStyle::default_bar()
-                    .template("  {spinner:.green} Checksumming target [{bar:40.cyan/blue}] {bytes}/{total_bytes}")
-                    .unwrap()
-                    .progress_chars("█▓░"),
+        if resume {
+            println_if!(
+                silent,
+                "  {} --resume only applies to local images; verifying from the start",
+                style("ℹ").blue()
             );
         }
 
-        let config = VerifyConfig::new().block_size(block_size);
-        let pb_clone = pb.clone();
+        // Calculate checksum of target
+        let start = Instant::now();
+        let reporter = progress.create("Checksumming target", Some(total_size), None);
+
+        let config = VerifyConfig::new()
+            .block_size(block_size)
+            .drop_cache(drop_cache);
+        let reporter_clone = reporter.clone();
         let mut verifier = Verifier::with_config(config).on_progress(move |p| {
-            pb_clone.set_position(p.bytes_processed);
+            reporter_clone.on_verify(p);
         });
+        verifier
+            .drop_target_cache(&*target_reader)
+            .context("Failed to drop cached pages before verifying")?;
 
         let target_checksum = verifier
             .calculate_checksum(
@@ -191,42 +430,34 @@ pub fn execute(
             )
             .context("Failed to checksum target")?;
 
-        pb.finish_and_clear();
+        reporter.finish();
 
         // Calculate checksum of source
         println_if!(silent, "  Calculating source checksum...");
         let mut source_reader =
             Source::open(source).with_context(|| format!("Failed to open source: {}", source))?;
 
-        let pb = if silent {
-            ProgressBar::hidden()
-        } else if let Some(size) = source_size {
-            ProgressBar::new(size)
-        } else {
-            ProgressBar::new_spinner()
-        };
-        if !silent {
-            pb.set_style(
-                ProgressStyle::default_bar()
-                    .template("  {spinner:.green} Checksumming source [{bar:40.cyan/blue}] {bytes}/{total_bytes}")
-                    .unwrap()
-                    .progress_chars("█▓░"),
-            );
-        }
+        let reporter = progress.create("Checksumming source", source_size, None);
 
         let config = VerifyConfig::new().block_size(block_size);
-        let pb_clone = pb.clone();
+        let reporter_clone = reporter.clone();
         let mut verifier = Verifier::with_config(config).on_progress(move |p| {
-            pb_clone.set_position(p.bytes_processed);
+            reporter_clone.on_verify(p);
         });
 
         let source_checksum = verifier
             .calculate_checksum(&mut source_reader, ChecksumAlgorithm::Sha256, source_size)
             .context("Failed to checksum source")?;
 
-        pb.finish_and_clear();
+        reporter.finish();
 
-        if target_checksum.matches(&source_checksum) {
+        let matched = target_checksum.matches(&source_checksum);
+        output.report(&VerifyReport::checksum(
+            matched,
+            total_size,
+            start.elapsed(),
+        ))?;
+        if matched {
             println_if!(
                 silent,
                 "  {} Checksum verification passed!",
@@ -242,11 +473,228 @@ pub fn execute(
             );
             println_if!(silent, "    Source:  {}", source_checksum.to_hex());
             println_if!(silent, "    Target:  {}", target_checksum.to_hex());
-            bail!("Verification failed: checksums do not match");
+            Err(CliError::VerificationFailed(
+                "Verification failed: checksums do not match".to_string(),
+            )
+            .into())
         }
     }
 }
 
+/// Execute the verify command against a known checksum instead of a source image
+pub fn execute_checksum(
+    target: &str,
+    expected: &Checksum,
+    size: u64,
+    block_size_str: &str,
+    drop_cache: bool,
+    cancel_flag: CancelToken,
+    output: VerifyOutput,
+) -> Result<()> {
+    let silent = output.quiet();
+    let progress = output.progress;
+    let block_size = parse_block_size(block_size_str)?;
+
+    if !has_elevated_privileges() {
+        return Err(CliError::privileges_required("verify").into());
+    }
+
+    let algorithm = expected.algorithm;
+    println_if!(
+        silent,
+        "{} {} of the first {}",
+        style("Expected:").bold(),
+        algorithm,
+        format_size(size)
+    );
+    println_if!(silent, "  {} {}", style("✓").green(), expected.to_hex());
+
+    println_if!(
+        silent,
+        "\n{} {}",
+        style("Target:").bold(),
+        style(target).cyan()
+    );
+    if let Some(drive) = find_target_drive(target, silent)? {
+        if drive.size > 0 && drive.size < size {
+            bail!(
+                "--size ({}) is larger than the target device ({})",
+                format_size(size),
+                format_size(drive.size)
+            );
+        }
+    }
+
+    let device_path = get_raw_device_path(target);
+    let options = OpenOptions::new()
+        .read(true)
+        .write(false)
+        .direct_io(false)
+        .block_size(block_size);
+    let mut target_reader = open_device(&device_path, options)
+        .with_context(|| format!("Failed to open device: {}", device_path))?;
+
+    println_if!(silent, "\n{}", style("Verifying...").bold());
+
+    let start = Instant::now();
+    let reporter = progress.create("Checksumming target", Some(size), None);
+
+    let config = VerifyConfig::new()
+        .block_size(block_size)
+        .drop_cache(drop_cache);
+    let reporter_clone = reporter.clone();
+    let mut verifier = Verifier::with_config(config).on_progress(move |p| {
+        reporter_clone.on_verify(p);
+    });
+    verifier
+        .drop_target_cache(&*target_reader)
+        .context("Failed to drop cached pages before verifying")?;
+    let _forward = CancelForward::spawn(&cancel_flag, verifier.cancel_handle());
+
+    // Only the first `size` bytes belong to the image; the rest of the device is ignored
+    let result = verifier.verify_checksum(
+        &mut (&mut *target_reader).take(size),
+        algorithm,
+        &expected.to_hex(),
+        Some(size),
+    );
+
+    reporter.finish();
+
+    match result {
+        Ok(result) => {
+            output.report(&VerifyReport::from(&result))?;
+            println_if!(
+                silent,
+                "  {} Checksum verification passed!",
+                style("✓").green().bold()
+            );
+            println_if!(
+                silent,
+                "    {} bytes verified in {:.1}s",
+                result.bytes_verified,
+                result.elapsed.as_secs_f64()
+            );
+            println_if!(silent, "    {}: {}", algorithm, expected.to_hex());
+            Ok(())
+        }
+        Err(engraver_core::Error::ChecksumMismatch { expected, actual }) => {
+            output.report(&VerifyReport::checksum(false, size, start.elapsed()))?;
+            println_if!(
+                silent,
+                "  {} Checksum verification FAILED!",
+                style("✗").red().bold()
+            );
+            println_if!(silent, "    Expected: {}", expected);
+            println_if!(silent, "    Target:   {}", actual);
+            Err(CliError::VerificationFailed(
+                "Verification failed: checksums do not match".to_string(),
+            )
+            .into())
+        }
+        Err(engraver_core::Error::Cancelled(_)) => {
+            println_if!(silent, "\n{}", style("Verification cancelled.").yellow());
+            Err(CliError::Cancelled.into())
+        }
+        Err(e) => Err(anyhow::Error::new(e).context("Failed to checksum target")),
+    }
+}
+
+/// Look up the target among detected drives and print what was found
+fn find_target_drive(target: &str, silent: bool) -> Result<Option<Drive>> {
+    let drives = list_drives().context("Failed to list drives")?;
+    let target_drive = drives
+        .into_iter()
+        .find(|d| d.path == target || d.raw_path == target);
+
+    if let Some(drive) = &target_drive {
+        println_if!(
+            silent,
+            "  {} {} ({})",
+            style("✓").green(),
+            drive.display_name(),
+            format_size(drive.size)
+        );
+    } else {
+        println_if!(silent, "  {} Device found", style("✓").green());
+    }
+
+    Ok(target_drive)
+}
+
+/// Modification time of a local file as a Unix timestamp, if available
+fn source_modified(path: &str) -> Option<u64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    modified
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|elapsed| elapsed.as_secs())
+}
+
+/// Load the verify checkpoint to resume from, if one exists and still applies
+///
+/// A checkpoint whose source or target has changed is removed, and the
+/// verification starts from the beginning.
+fn find_resume_checkpoint(
+    manager: &CheckpointManager,
+    source: &str,
+    target: &str,
+    source_size: u64,
+    identity: &TargetIdentity,
+    silent: bool,
+) -> Option<VerifyCheckpoint> {
+    let checkpoint = match manager.find_verify_checkpoint(source, target) {
+        Ok(Some(checkpoint)) => checkpoint,
+        Ok(None) => {
+            println_if!(
+                silent,
+                "  {} No interrupted verification found; starting from the beginning",
+                style("ℹ").blue()
+            );
+            return None;
+        }
+        Err(e) => {
+            tracing::warn!("Failed to look up verify checkpoint: {}", e);
+            return None;
+        }
+    };
+
+    let validation =
+        validate_verify_checkpoint(&checkpoint, source_size, source_modified(source), identity);
+    if !validation.valid {
+        println_if!(
+            silent,
+            "  {} Cannot resume verification:",
+            style("⚠").yellow()
+        );
+        for msg in &validation.messages {
+            println_if!(silent, "    {}", msg);
+        }
+        let _ = manager.remove_verify(&checkpoint);
+        println_if!(
+            silent,
+            "  {} Starting from the beginning",
+            style("ℹ").blue()
+        );
+        return None;
+    }
+
+    for warning in &validation.warnings {
+        println_if!(silent, "  {} {}", style("Warning:").yellow(), warning);
+    }
+    println_if!(
+        silent,
+        "  {} Resuming from {} ({:.1}% already verified)",
+        style("✓").green(),
+        format_size(checkpoint.bytes_verified),
+        checkpoint.percentage()
+    );
+
+    let mut checkpoint = checkpoint;
+    checkpoint.mark_resumed();
+    Some(checkpoint)
+}
+
 /// Handle verification result
 fn handle_verify_result(
     result: std::result::Result<engraver_core::VerificationResult, engraver_core::Error>,
@@ -274,11 +722,18 @@ fn handle_verify_result(
             if let Some(offset) = result.first_mismatch_offset {
                 println_if!(silent, "    First mismatch at byte offset: {}", offset);
             }
-            bail!("Verification failed");
+            if result.target_has_trailing_data {
+                println_if!(
+                    silent,
+                    "    Target has leftover data past byte {}",
+                    result.bytes_verified
+                );
+            }
+            Err(CliError::VerificationFailed("Verification failed".to_string()).into())
         }
-        Err(engraver_core::Error::Cancelled) => {
+        Err(engraver_core::Error::Cancelled(_)) => {
             println_if!(silent, "\n{}", style("Verification cancelled.").yellow());
-            Ok(())
+            Err(CliError::Cancelled.into())
         }
         Err(e) => {
             bail!("Verification error: {}", e);
@@ -299,23 +754,10 @@ fn get_raw_device_path(path: &str) -> String {
     path.to_string()
 }
 
-/// Parse a human-readable block size
+/// Parse a human-readable block size between 4K and 64M
 fn parse_block_size(s: &str) -> Result<usize> {
-    let s = s.trim().to_uppercase();
-
-    let (num_str, multiplier) = if s.ends_with('K') {
-        (&s[..s.len() - 1], 1024)
-    } else if s.ends_with('M') {
-        (&s[..s.len() - 1], 1024 * 1024)
-    } else {
-        (s.as_str(), 1)
-    };
-
-    let num: usize = num_str
-        .parse()
-        .with_context(|| format!("Invalid block size: {}", s))?;
-
-    Ok(num * multiplier)
+    let size = parse_block_size_clamped(s, MIN_BLOCK_SIZE as u64, MAX_BLOCK_SIZE as u64)?;
+    Ok(size as usize)
 }
 
 /// Format size for display
@@ -338,6 +780,148 @@ fn format_size(bytes: u64) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use engraver_core::CancelReason;
+
+    // -------------------------------------------------------------------------
+    // VerifyMode tests
+    // -------------------------------------------------------------------------
+
+    const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    #[test]
+    fn test_verify_mode_image() {
+        let mode = VerifyMode::resolve(
+            Some("image.iso".to_string()),
+            Some("/dev/sdb".to_string()),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(matches!(
+            mode,
+            VerifyMode::Image { ref source, ref target } if source == "image.iso" && target == "/dev/sdb"
+        ));
+    }
+
+    #[test]
+    fn test_verify_mode_requires_source_or_checksum() {
+        let err = VerifyMode::resolve(Some("/dev/sdb".to_string()), None, None, None, None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("required"));
+        assert!(err.contains("--checksum"));
+    }
+
+    #[test]
+    fn test_verify_mode_checksum() {
+        let mode = VerifyMode::resolve(
+            Some("/dev/sdb".to_string()),
+            None,
+            Some(HELLO_SHA256.to_uppercase()),
+            None,
+            Some("4K".to_string()),
+        )
+        .unwrap();
+        match mode {
+            VerifyMode::Checksum {
+                target,
+                expected,
+                size,
+            } => {
+                assert_eq!(target, "/dev/sdb");
+                assert_eq!(expected.algorithm, ChecksumAlgorithm::Sha256);
+                assert_eq!(expected.to_hex(), HELLO_SHA256);
+                assert_eq!(size, 4096);
+            }
+            other => panic!("unexpected mode: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_verify_mode_checksum_invalid() {
+        let checksum = |source: Option<&str>,
+                        target: Option<&str>,
+                        hash: &str,
+                        algo: Option<&str>,
+                        size: &str| {
+            VerifyMode::resolve(
+                source.map(String::from),
+                target.map(String::from),
+                Some(hash.to_string()),
+                algo.map(String::from),
+                Some(size.to_string()),
+            )
+        };
+
+        // Both a source and a target alongside --checksum
+        assert!(checksum(
+            Some("image.iso"),
+            Some("/dev/sdb"),
+            HELLO_SHA256,
+            None,
+            "4K"
+        )
+        .is_err());
+        // Zero or malformed size
+        assert!(checksum(Some("/dev/sdb"), None, HELLO_SHA256, None, "0").is_err());
+        assert!(checksum(Some("/dev/sdb"), None, HELLO_SHA256, None, "lots").is_err());
+        // Hash length that matches no algorithm, or the wrong one
+        assert!(checksum(Some("/dev/sdb"), None, "abc", None, "4K").is_err());
+        assert!(checksum(Some("/dev/sdb"), None, HELLO_SHA256, Some("md5"), "4K").is_err());
+    }
+
+    #[test]
+    fn test_verify_mode_image_rejects_size() {
+        let err = VerifyMode::resolve(
+            Some("image.iso".to_string()),
+            Some("/dev/sdb".to_string()),
+            None,
+            None,
+            Some("4K".to_string()),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("--size only applies"));
+    }
+
+    #[test]
+    fn test_verify_mode_sums() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let sums = temp_dir.path().join("SHA256SUMS");
+        std::fs::write(&sums, format!("{} *image.img\n", HELLO_SHA256)).unwrap();
+
+        let mode = VerifyMode::resolve_sums(
+            Some("/dev/sdb".to_string()),
+            None,
+            &sums,
+            "image.img",
+            Some("5".to_string()),
+        )
+        .unwrap();
+        match mode {
+            VerifyMode::Checksum {
+                target,
+                expected,
+                size,
+            } => {
+                assert_eq!(target, "/dev/sdb");
+                assert_eq!(expected.algorithm, ChecksumAlgorithm::Sha256);
+                assert_eq!(expected.to_hex(), HELLO_SHA256);
+                assert_eq!(size, 5);
+            }
+            other => panic!("unexpected mode: {:?}", other),
+        }
+
+        let err = VerifyMode::resolve_sums(
+            Some("/dev/sdb".to_string()),
+            None,
+            &sums,
+            "other.img",
+            Some("5".to_string()),
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("No checksum for other.img"));
+    }
 
     // -------------------------------------------------------------------------
     // parse_block_size tests
@@ -379,6 +963,14 @@ mod tests {
         assert!(parse_block_size("-1K").is_err());
     }
 
+    #[test]
+    fn test_parse_block_size_range() {
+        assert_eq!(parse_block_size("64M").unwrap(), 64 * 1024 * 1024);
+        assert!(parse_block_size("512").is_err());
+        assert!(parse_block_size("128M").is_err());
+        assert!(parse_block_size("1G").is_err());
+    }
+
     // -------------------------------------------------------------------------
     // format_size tests
     // -------------------------------------------------------------------------
@@ -444,6 +1036,62 @@ mod tests {
         assert_eq!(get_raw_device_path("./local/file"), "./local/file");
     }
 
+    // -------------------------------------------------------------------------
+    // Verify checkpoint tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_find_resume_checkpoint_resumes_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("image.img");
+        std::fs::write(&image, [0u8; 64]).unwrap();
+        let image = image.to_str().unwrap();
+        let manager = CheckpointManager::new(dir.path().join("checkpoints")).unwrap();
+        let identity = TargetIdentity::new(128).serial(Some("SN1".to_string()));
+
+        let mut checkpoint = VerifyCheckpoint::new(image, 64, "/dev/sdb", 128, 4096)
+            .with_source_modified(source_modified(image))
+            .with_target_identity(&identity);
+        checkpoint.update_progress(32, Duration::from_secs(1));
+        manager.save_verify(&checkpoint).unwrap();
+
+        let found =
+            find_resume_checkpoint(&manager, image, "/dev/sdb", 64, &identity, true).unwrap();
+        assert_eq!(found.bytes_verified, 32);
+        assert_eq!(found.resume_count, 1);
+    }
+
+    #[test]
+    fn test_find_resume_checkpoint_discards_changed_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = CheckpointManager::new(dir.path()).unwrap();
+        let identity = TargetIdentity::new(128).serial(Some("SN1".to_string()));
+        let checkpoint = VerifyCheckpoint::new("image.img", 64, "/dev/sdb", 128, 4096)
+            .with_target_identity(&identity);
+        manager.save_verify(&checkpoint).unwrap();
+
+        let other = TargetIdentity::new(128).serial(Some("SN2".to_string()));
+        assert!(
+            find_resume_checkpoint(&manager, "image.img", "/dev/sdb", 64, &other, true).is_none()
+        );
+        assert!(manager
+            .find_verify_checkpoint("image.img", "/dev/sdb")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_find_resume_checkpoint_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = CheckpointManager::new(dir.path()).unwrap();
+        let identity = TargetIdentity::new(128);
+
+        assert!(
+            find_resume_checkpoint(&manager, "image.img", "/dev/sdb", 64, &identity, true)
+                .is_none()
+        );
+    }
+
     // -------------------------------------------------------------------------
     // handle_verify_result tests
     // -------------------------------------------------------------------------
@@ -457,6 +1105,10 @@ mod tests {
             first_mismatch_offset: None,
             elapsed: std::time::Duration::from_secs(1),
             speed_bps: 1024 * 1024,
+            target_has_trailing_data: false,
+            sampled: false,
+            sampled_bytes: 0,
+            mismatch_offsets: Vec::new(),
         };
 
         let handled = handle_verify_result(Ok(result), true);
@@ -472,6 +1124,10 @@ mod tests {
             first_mismatch_offset: Some(1024),
             elapsed: std::time::Duration::from_secs(1),
             speed_bps: 512 * 1024,
+            target_has_trailing_data: false,
+            sampled: false,
+            sampled_bytes: 0,
+            mismatch_offsets: Vec::new(),
         };
 
         let handled = handle_verify_result(Ok(result), true);
@@ -482,9 +1138,16 @@ mod tests {
 
     #[test]
     fn test_handle_verify_result_cancelled() {
-        let handled = handle_verify_result(Err(engraver_core::Error::Cancelled), true);
-        // Cancelled is not an error - returns Ok
-        assert!(handled.is_ok());
+        let handled = handle_verify_result(
+            Err(engraver_core::Error::Cancelled(CancelReason::UserRequested)),
+            true,
+        );
+        // Cancelled surfaces as an error so the process exits with its own code
+        let err = handled.unwrap_err();
+        assert_eq!(
+            crate::error::ExitCode::from_error(&err),
+            crate::error::ExitCode::Cancelled
+        );
     }
 
     #[test]
@@ -493,4 +1156,43 @@ mod tests {
         let handled = handle_verify_result(Err(engraver_core::Error::Io(io_err)), true);
         assert!(handled.is_err());
     }
+
+    // -------------------------------------------------------------------------
+    // VerifyReport tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_verify_report_from_result() {
+        let mut result = VerificationResult::success(4096, Duration::from_secs(2));
+        result.success = false;
+        result.mismatches = 3;
+        result.first_mismatch_offset = Some(512);
+
+        let value = serde_json::to_value(VerifyReport::from(&result)).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "success": false,
+                "bytes_verified": 4096,
+                "mismatches": 3,
+                "first_mismatch_offset": 512,
+                "elapsed_seconds": 2.0,
+                "speed_bps": 2048,
+            })
+        );
+    }
+
+    #[test]
+    fn test_verify_report_checksum() {
+        let passed = VerifyReport::checksum(true, 1000, Duration::from_secs(1));
+        assert!(passed.success);
+        assert_eq!(passed.mismatches, 0);
+        assert_eq!(passed.speed_bps, 1000);
+
+        let failed = VerifyReport::checksum(false, 1000, Duration::ZERO);
+        assert!(!failed.success);
+        assert_eq!(failed.mismatches, 1);
+        assert_eq!(failed.first_mismatch_offset, None);
+        assert_eq!(failed.speed_bps, 0);
+    }
 }
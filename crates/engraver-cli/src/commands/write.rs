@@ -7,75 +7,249 @@
 //! - Unmounting partitions
 //! - Writing with progress display
 //! - Optional verification
+//! - Writing to several devices in parallel
 
 use anyhow::{bail, Context, Result};
 use console::style;
-use dialoguer::Confirm;
 use indicatif::{ProgressBar, ProgressStyle};
-use std::io::{Seek, SeekFrom, Write};
+use std::collections::HashSet;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 
+use crate::error::{CliError, ExitCode};
+use crate::json;
+use crate::progress::{BatchProgress, ProgressReporter, ReporterKind};
+use crate::prompt;
+
 use engraver_core::{
-    auto_detect_checksum, validate_checkpoint, validate_source, CheckpointManager,
-    ChecksumAlgorithm, Source, SourceType, Verifier, VerifyConfig, WriteCheckpoint, WriteConfig,
-    WritePhase, Writer,
+    auto_detect_checksum, boot_signature, detect_source_type, image_kind, is_block_device,
+    looks_like_disk_image, parse_block_size_clamped, validate_checkpoint_for,
+    validate_source_with_entry, CancelToken, CheckpointAutosave, CheckpointManager, Checksum,
+    ChecksumAlgorithm, DownloadCache, HistoryEntry, HistoryLog, Prefetch, Source, SourceType,
+    TargetIdentity, Verifier, VerifyConfig, WriteCheckpoint, WriteConfig, WritePhase,
+    WriteProgress, WriteResult, Writer, BLANK_CHECK_SAMPLES, DEFAULT_DEVICE_BLOCK_SIZE,
+    DEFAULT_SMALL_HEAD_BYTES, DISK_IMAGE_PROBE_SIZE, MAX_BLOCK_SIZE, MIN_BLOCK_SIZE,
 };
 #[cfg(feature = "partition-info")]
 use engraver_core::{inspect_from_buffer, read_partition_header, PartitionTableType};
-use engraver_detect::{list_drives, Drive};
-use engraver_platform::{has_elevated_privileges, open_device, unmount_device, OpenOptions};
+use engraver_detect::{
+    find_drive_by, find_drives_matching, list_drives, list_removable_drives, Drive, TargetSelector,
+};
+use engraver_platform::{
+    create_data_partition, data_partition_span, eject_device, has_elevated_privileges, open_device,
+    reread_partition_table, unmount_device, unmount_device_with_progress, DataFilesystem,
+    OpenOptions, RawDevice, DATA_PARTITION_LABEL,
+};
+
+/// How the post-write verification pass compares source and target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerifyMode {
+    /// Byte-by-byte comparison of source and target (local sources only)
+    #[default]
+    Bytes,
+    /// Hash the source and the read-back target, then compare digests
+    Checksum,
+}
+
+impl std::str::FromStr for VerifyMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "bytes" | "byte" => Ok(VerifyMode::Bytes),
+            "checksum" | "hash" => Ok(VerifyMode::Checksum),
+            _ => bail!("Unknown verify mode '{}'. Use: bytes or checksum", s),
+        }
+    }
+}
+
+/// Parse a `--verify-sample` percentage such as `10%` or `2.5` into a fraction
+pub fn parse_sample_percent(s: &str) -> Result<f64, String> {
+    let number = s.trim().trim_end_matches('%').trim_end();
+    match number.parse::<f64>() {
+        Ok(percent) if percent > 0.0 && percent <= 100.0 => Ok(percent / 100.0),
+        _ => Err(format!(
+            "Invalid sample percentage: {s:?} (expected e.g. 10% or 2.5)"
+        )),
+    }
+}
 
 /// Arguments for the write command
 pub struct WriteArgs {
     pub source: String,
-    pub target: String,
+    /// One target, or several to write in parallel (empty with `--watch`)
+    pub targets: Vec<String>,
+    /// Pick the single target by serial number or partition label instead
+    pub target_selector: Option<TargetSelector>,
+    pub entry: Option<String>,
+    /// Bytes at the start of the source to skip instead of writing
+    pub input_offset: u64,
+    /// Bytes at the end of each target that must not be written
+    pub preserve_tail: u64,
     pub verify: bool,
+    pub verify_mode: VerifyMode,
+    /// Byte-compare only this fraction of blocks instead of everything
+    pub verify_sample: Option<f64>,
+    /// Read back through the page cache instead of dropping it first
+    pub no_verify_cache_drop: bool,
     pub skip_confirm: bool,
+    pub confirm_device: Option<String>,
     pub block_size: String,
-    pub checksum: Option<String>,
+    /// Expected source checksums, as `ALGO:HEX` or bare hex in `checksum_algo`
+    pub checksums: Vec<String>,
     pub checksum_algo: String,
     pub force: bool,
+    /// Refuse to write over a target that already holds data, unless `force`
+    pub require_blank: bool,
     pub no_unmount: bool,
-    pub cancel_flag: Arc<AtomicBool>,
+    pub no_direct_io: bool,
+    /// Write the first [`DEFAULT_SMALL_HEAD_BYTES`] one device block at a time
+    pub small_head: bool,
+    /// Read every block back as it is written (`--verify-each-block`)
+    pub verify_each_block: bool,
+    pub eject: bool,
+    /// Read back the boot sector after writing and warn if it looks unbootable
+    pub check_bootable: bool,
+    pub partition_after: Option<DataFilesystem>,
+    pub cancel_flag: CancelToken,
     pub silent: bool,
     pub resume: bool,
     pub checkpoint: bool,
+    pub checkpoint_interval_secs: u64,
     pub auto_checksum: bool,
     pub show_partitions: bool,
+    pub show_destruction: bool,
+    pub json: bool,
+    pub json_compat: bool,
+    pub progress: ReporterKind,
+    pub watch: bool,
+    pub history_log: Option<HistoryLog>,
+    /// Download a URL source here before writing
+    pub cache_dir: Option<PathBuf>,
+    /// Keep the cached download after a successful write
+    pub keep_cache: bool,
+    /// Flash the target's activity LED before the confirmation prompt
+    pub identify: bool,
+    /// Check the source checksum and stop, without touching any target
+    pub verify_source_only: bool,
+    /// Treat each target as a glob over detected drive paths
+    pub glob: bool,
 }
 
 /// Shared context for the write command's helper functions
 struct WriteContext {
     silent: bool,
     block_size: usize,
+    /// How progress is shown for each step
+    progress: ReporterKind,
+    /// Ctrl+C / `--timeout` token, forwarded to each verifier
+    cancel: CancelToken,
+    /// Drop the target's cached pages before reading it back
+    drop_cache: bool,
+}
+
+/// Forwards cancellation from the CLI token to one operation's own handle
+///
+/// The watcher thread exits once the guard is dropped, so repeated writes
+/// (--watch) do not leave threads behind.
+pub(crate) struct CancelForward {
+    done: Arc<AtomicBool>,
+}
+
+impl CancelForward {
+    pub(crate) fn spawn(from: &CancelToken, to: CancelToken) -> Self {
+        let done = Arc::new(AtomicBool::new(false));
+        let done_for_thread = done.clone();
+        let from = from.clone();
+        std::thread::spawn(move || {
+            while !from.is_cancelled() {
+                if done_for_thread.load(Ordering::SeqCst) {
+                    return;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(100));
+            }
+            if let Some(reason) = from.reason() {
+                to.cancel(reason);
+            }
+        });
+        Self { done }
+    }
+}
+
+impl Drop for CancelForward {
+    fn drop(&mut self) {
+        self.done.store(true, Ordering::SeqCst);
+    }
 }
 
 /// Check that the process has elevated privileges, bail if not
 fn check_privileges() -> Result<()> {
     if !has_elevated_privileges() {
-        #[cfg(unix)]
-        bail!(
-            "Root privileges required.\n\
-             Try running with: sudo engraver write ..."
-        );
+        return Err(CliError::privileges_required("write").into());
+    }
+    Ok(())
+}
 
-        #[cfg(windows)]
-        bail!(
-            "Administrator privileges required.\n\
-             Right-click and select 'Run as administrator'."
-        );
+/// Open the source checked by [`validate_source_info`], from `offset`
+///
+/// URLs reuse the validated size and ETag, so a resume cannot splice a
+/// replaced file onto the old one. A tar entry is still picked by name.
+/// `offset` counts from the end of any `--input-offset` header.
+fn open_validated_source(
+    args: &WriteArgs,
+    source_info: &engraver_core::SourceInfo,
+    offset: u64,
+) -> engraver_core::Result<Source> {
+    open_past_header(args.input_offset, source_info, offset, |start| {
+        match args.entry.as_deref() {
+            Some(entry) => Source::open_with_entry(&args.source, start, Some(entry)),
+            None => Source::open_from_info(source_info, start),
+        }
+    })
+}
+
+/// Open a source with `open`, skipping a `header`-byte prefix first
+///
+/// Seekable and range-capable sources are opened past the header directly.
+/// Compressed streams and archives can only start at 0, so the header is
+/// read and discarded instead.
+fn open_past_header(
+    header: u64,
+    source_info: &engraver_core::SourceInfo,
+    offset: u64,
+    open: impl FnOnce(u64) -> engraver_core::Result<Source>,
+) -> engraver_core::Result<Source> {
+    if header == 0 || source_info.seekable || source_info.resumable {
+        return open(header + offset);
+    }
+    let mut source = open(offset)?;
+    skip_header(&mut source, header)?;
+    Ok(source)
+}
 
-        #[cfg(not(any(unix, windows)))]
-        bail!("Elevated privileges required for raw device access.");
+/// Read and discard the first `len` bytes of `source`
+fn skip_header(source: &mut Source, len: u64) -> engraver_core::Result<()> {
+    let skipped = std::io::copy(&mut source.take(len), &mut std::io::sink())?;
+    if skipped < len {
+        return Err(engraver_core::Error::InvalidConfig(format!(
+            "--input-offset {} is past the end of the source ({} bytes)",
+            len, skipped
+        )));
     }
     Ok(())
 }
 
 /// Validate the source image and display info
+///
+/// The returned size excludes the `input_offset` header, which must be
+/// smaller than the source when its size is known.
 fn validate_source_info(
     source: &str,
+    entry: Option<&str>,
+    input_offset: u64,
     silent: bool,
 ) -> Result<(engraver_core::SourceInfo, Option<u64>)> {
     println_if!(
@@ -85,24 +259,26 @@ fn validate_source_info(
         style(source).cyan()
     );
 
-    let source_info = validate_source(source)
+    let source_info = validate_source_with_entry(source, None, entry)
         .with_context(|| format!("Failed to validate source: {}", source))?;
 
-    let source_size = source_info.size.or(source_info.compressed_size);
+    if let Some(size) = source_info.size {
+        if input_offset >= size {
+            bail!(
+                "--input-offset {} must be smaller than the source ({})",
+                format_size(input_offset),
+                format_size(size)
+            );
+        }
+    }
+    let source_size = source_info
+        .size
+        .map(|size| size - input_offset)
+        .or(source_info.compressed_size);
     let source_type_str = match source_info.source_type {
-        SourceType::LocalFile => "local file",
-        SourceType::Remote => "remote URL",
-        SourceType::Gzip => "gzip compressed",
-        SourceType::Xz => "xz compressed",
-        SourceType::Zstd => "zstd compressed",
-        SourceType::Bzip2 => "bzip2 compressed",
-        #[cfg(feature = "s3")]
-        SourceType::S3 => "S3 object",
-        #[cfg(feature = "gcs")]
-        SourceType::Gcs => "GCS object",
-        #[cfg(feature = "azure")]
-        SourceType::Azure => "Azure blob",
-        _ => "unknown",
+        SourceType::LocalFile if is_block_device(source) => "block device",
+        SourceType::LocalFile => image_kind(source).unwrap_or("local file"),
+        other => other.description(),
     };
 
     if let Some(size) = source_size {
@@ -121,16 +297,73 @@ fn validate_source_info(
             source_type_str
         );
     }
+    if input_offset > 0 {
+        println_if!(
+            silent,
+            "  {} Skipping the first {} of the source",
+            style("ℹ").blue(),
+            format_size(input_offset)
+        );
+    }
 
     Ok((source_info, source_size))
 }
 
+/// Warn (without failing) if the source does not look like a disk image
+///
+/// Catches mistakes such as writing an HTML error page served by a mirror.
+/// The first [`DISK_IMAGE_PROBE_SIZE`] bytes of the (decompressed) source are
+/// checked for ISO 9660, GPT, FAT or MBR signatures. Sources whose extension
+/// names a known image kind (see [`image_kind`]) skip the signature warning.
+fn warn_if_not_disk_image(args: &WriteArgs, source_info: &engraver_core::SourceInfo, silent: bool) {
+    let source = args.source.as_str();
+    if let Some(ref content_type) = source_info.content_type {
+        if content_type.to_ascii_lowercase().starts_with("text/html") {
+            println_if!(
+                silent,
+                "  {} Server returned {}; this may be an error page, not an image",
+                style("⚠").yellow().bold(),
+                content_type
+            );
+        }
+    }
+
+    if let Some(kind) = image_kind(source) {
+        tracing::debug!("Skipping disk image signature check for {}", kind);
+        return;
+    }
+
+    let mut header = Vec::with_capacity(DISK_IMAGE_PROBE_SIZE);
+    let probe = open_past_header(args.input_offset, source_info, 0, |start| {
+        Source::open_with_entry(source, start, args.entry.as_deref())
+    })
+    .and_then(|s| {
+        s.take(DISK_IMAGE_PROBE_SIZE as u64)
+            .read_to_end(&mut header)
+            .map_err(engraver_core::Error::Io)
+    });
+    if let Err(e) = probe {
+        tracing::debug!("Skipping disk image signature check: {}", e);
+        return;
+    }
+
+    match looks_like_disk_image(&header) {
+        Some(format) => tracing::debug!("Source looks like a disk image: {}", format),
+        None => println_if!(
+            silent,
+            "  {} No ISO 9660, GPT, FAT or MBR signature found; the source may not be a bootable image",
+            style("⚠").yellow().bold()
+        ),
+    }
+}
+
 /// Validate the target device, performing safety checks
 fn validate_target_device<'a>(
     target: &str,
     drives: &'a [Drive],
     force: bool,
     skip_confirm: bool,
+    confirm_device: Option<&str>,
     source_size: Option<u64>,
     silent: bool,
 ) -> Result<&'a Drive> {
@@ -143,6 +376,10 @@ fn validate_target_device<'a>(
 
     let target_drive = find_drive(drives, target)?;
 
+    if let Some(expected) = confirm_device {
+        check_confirm_device(target_drive, expected)?;
+    }
+
     // Safety check
     if target_drive.is_system && !force {
         bail!(
@@ -164,28 +401,34 @@ fn validate_target_device<'a>(
             style("Warning:").yellow().bold()
         );
         if !skip_confirm {
-            let proceed = Confirm::new()
-                .with_prompt("Are you absolutely sure you want to continue?")
-                .default(false)
-                .interact()?;
+            let proceed = prompt::confirm("Are you absolutely sure you want to continue?", false)?;
 
             if !proceed {
-                bail!("Aborted by user");
+                return Err(CliError::Aborted.into());
             }
         }
     }
 
-    // Check size compatibility
-    if let Some(src_size) = source_size {
-        if src_size > target_drive.size {
-            bail!(
-                "Source ({}) is larger than target ({})",
-                format_size(src_size),
-                format_size(target_drive.size)
-            );
+    // Removable but possibly holding data, like a USB hard drive
+    if let Some(reason) = &target_drive.caution_reason {
+        eprintln!(
+            "{} {} may not be a flash drive: {}",
+            style("Caution:").yellow().bold(),
+            target_drive.path,
+            reason
+        );
+        if !skip_confirm {
+            let proceed =
+                prompt::confirm("Is this really the drive you mean to overwrite?", false)?;
+
+            if !proceed {
+                return Err(CliError::Aborted.into());
+            }
         }
     }
 
+    check_target_size(target_drive, source_size)?;
+
     println_if!(
         silent,
         "  {} {} ({})",
@@ -231,10 +474,28 @@ fn validate_target_device<'a>(
     Ok(target_drive)
 }
 
+/// Check that `expected` exactly matches the drive's display name or path
+///
+/// Used by `--confirm-device` in place of the interactive prompt, so a wrong
+/// target path aborts instead of being written.
+fn check_confirm_device(drive: &Drive, expected: &str) -> Result<()> {
+    let display_name = drive.display_name();
+    if expected == display_name || expected == drive.path {
+        return Ok(());
+    }
+
+    Err(CliError::DeviceMismatch(format!(
+        "Device confirmation failed: expected '{}', but {} is '{}'.\n\
+         Pass the exact device name or path to --confirm-device.",
+        expected, drive.path, display_name
+    ))
+    .into())
+}
+
 /// Display the confirmation dialog and return whether to proceed
 fn confirm_write(
     source_info: &engraver_core::SourceInfo,
-    drive: &Drive,
+    drives: &[&Drive],
     skip_confirm: bool,
 ) -> Result<bool> {
     if skip_confirm {
@@ -275,17 +536,14 @@ fn confirm_write(
             .split('/')
             .next_back()
             .unwrap_or(&source_info.path),
-        drive.path
+        drives
+            .iter()
+            .map(|d| d.path.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
     );
 
-    let proceed = Confirm::new()
-        .with_prompt(confirm_text)
-        .default(false)
-        .interact()?;
-
-    if !proceed {
-        println!("{}", style("Aborted.").yellow());
-    }
+    let proceed = prompt::confirm(confirm_text, false)?;
 
     Ok(proceed)
 }
@@ -294,7 +552,7 @@ fn confirm_write(
 fn unmount_target(path: &str, silent: bool) {
     println_if!(silent, "\n{}", style("Unmounting device...").bold());
 
-    match unmount_device(path) {
+    match unmount_with_spinner(path, silent) {
         Ok(()) => println_if!(silent, "  {} Device unmounted", style("✓").green()),
         Err(e) => {
             tracing::debug!("Unmount result: {}", e);
@@ -303,9 +561,249 @@ fn unmount_target(path: &str, silent: bool) {
     }
 }
 
-/// Auto-detect or use explicit checksum, verify if found
+/// Unmount `path` with a spinner showing each status update from the platform
+fn unmount_with_spinner(path: &str, silent: bool) -> engraver_platform::Result<()> {
+    let spinner = create_status_spinner(silent);
+    let result = unmount_device_with_progress(path, &mut |status| {
+        spinner.set_message(status.to_string());
+        spinner.tick();
+    });
+    spinner.finish_and_clear();
+    result
+}
+
+/// Flush buffered writes to `target` with a spinner counting down the dirty data
+fn sync_with_spinner(target: &dyn RawDevice, silent: bool) -> engraver_platform::Result<()> {
+    let spinner = create_status_spinner(silent);
+    spinner.set_message("Syncing...");
+    let result = target.sync_with_progress(&mut |dirty| {
+        spinner.set_message(format!("Flushing: {} remaining", format_size(dirty)));
+    });
+    spinner.finish_and_clear();
+    result
+}
+
+/// Bytes [`WriteConfig::small_head_bytes`] is set to for `--small-head`
+fn small_head_bytes(args: &WriteArgs) -> u64 {
+    if args.small_head {
+        DEFAULT_SMALL_HEAD_BYTES
+    } else {
+        0
+    }
+}
+
+/// Sample `target` and abort if it already holds data (`--require-blank`)
+///
+/// With `force`, a non-blank device is reported but the write goes ahead.
+fn require_blank_target(
+    target: &mut dyn RawDevice,
+    path: &str,
+    block_size: usize,
+    force: bool,
+    silent: bool,
+) -> Result<()> {
+    let writer = Writer::with_config(WriteConfig::new().block_size(block_size));
+    let blank = writer
+        .is_blank(target, BLANK_CHECK_SAMPLES)
+        .with_context(|| format!("Failed to read {} for --require-blank", path))?;
+    target.seek(SeekFrom::Start(0))?;
+
+    if blank {
+        println_if!(silent, "  {} Device is blank", style("✓").green());
+    } else if force {
+        println_if!(
+            silent,
+            "  {} {} already contains data; writing anyway (--force)",
+            style("⚠").yellow(),
+            path
+        );
+    } else {
+        bail!(
+            "{} already contains data, refusing to overwrite it (--require-blank).\n\
+             Check that this is the right device, or add --force to write anyway.",
+            path
+        );
+    }
+    Ok(())
+}
+
+/// Ask the OS to pick up the partition table that was just written
+///
+/// Failures are reported as warnings; replugging the drive has the same effect.
+fn reread_target_partitions(path: &str, silent: bool) {
+    match reread_partition_table(path) {
+        Ok(()) => tracing::debug!("Re-read partition table on {}", path),
+        Err(e) => {
+            tracing::warn!("Partition table re-read failed: {}", e);
+            println_if!(
+                silent,
+                "  {} Could not refresh partitions ({}); replug the drive to see them",
+                style("⚠").yellow(),
+                e
+            );
+        }
+    }
+}
+
+/// What the first sectors of a written device say about whether it can boot
+#[derive(Debug, PartialEq, Eq)]
+enum BootSector {
+    /// A signed MBR or GPT protective MBR
+    Bootable(&'static str),
+    /// No boot sector, but a recognizable image such as a non-hybrid ISO
+    UnbootableImage(&'static str),
+    /// Nothing bootable or recognizable
+    Missing,
+}
+
+/// Classify the first [`DISK_IMAGE_PROBE_SIZE`] bytes read back from a device
+fn classify_boot_sector(header: &[u8]) -> BootSector {
+    if let Some(kind) = boot_signature(header) {
+        return BootSector::Bootable(kind);
+    }
+    match looks_like_disk_image(header) {
+        Some(kind) => BootSector::UnbootableImage(kind),
+        None => BootSector::Missing,
+    }
+}
+
+/// Read back the start of the device and warn if it has no plausible boot sector
+///
+/// Advisory only: nothing here fails the write.
+fn check_bootable(path: &str, block_size: usize, silent: bool) {
+    let device_path = get_raw_device_path(path);
+    let options = OpenOptions::new()
+        .read(true)
+        .write(false)
+        .direct_io(false)
+        .block_size(block_size);
+    let mut header = vec![0u8; DISK_IMAGE_PROBE_SIZE];
+    let read =
+        open_device(&device_path, options).and_then(|mut device| device.read_at(0, &mut header));
+    match read {
+        Ok(n) => header.truncate(n),
+        Err(e) => {
+            println_if!(
+                silent,
+                "  {} Could not read back the boot sector: {}",
+                style("⚠").yellow(),
+                e
+            );
+            return;
+        }
+    }
+
+    match classify_boot_sector(&header) {
+        BootSector::Bootable(kind) => {
+            println_if!(
+                silent,
+                "  {} Boot sector found ({})",
+                style("✓").green(),
+                kind
+            );
+        }
+        BootSector::UnbootableImage(kind) => println_if!(
+            silent,
+            "  {} No boot sector, but the drive holds a {} image; it may not boot from USB",
+            style("ℹ").blue(),
+            kind
+        ),
+        BootSector::Missing => println_if!(
+            silent,
+            "  {} No MBR or GPT boot sector found on {}; the drive will probably not boot",
+            style("⚠").yellow().bold(),
+            path
+        ),
+    }
+}
+
+/// Fail before writing if `--partition-after` would have no room on the drive
+fn check_data_partition_room(
+    drive: &Drive,
+    fs: DataFilesystem,
+    image_size: u64,
+    silent: bool,
+) -> Result<()> {
+    let (_, free) = data_partition_span(drive.size, image_size)
+        .context("Cannot use --partition-after on this drive")?;
+    println_if!(
+        silent,
+        "  {} A {} data partition will use the remaining {}",
+        style("ℹ").blue(),
+        fs,
+        format_size(free)
+    );
+    Ok(())
+}
+
+/// Create and format a partition in the space left after the image
+fn create_data_partition_after(
+    path: &str,
+    fs: DataFilesystem,
+    image_end: u64,
+    silent: bool,
+) -> Result<()> {
+    println_if!(
+        silent,
+        "\n{}",
+        style(format!("Creating {} data partition...", fs)).bold()
+    );
+
+    // The OS may have mounted the image's partitions after the re-read
+    if let Err(e) = unmount_device(path) {
+        tracing::debug!("Unmount before partitioning: {}", e);
+    }
+
+    create_data_partition(path, fs, image_end)
+        .context("The image was written, but creating the data partition failed")?;
+    println_if!(
+        silent,
+        "  {} Created {} partition \"{}\"",
+        style("✓").green(),
+        fs,
+        DATA_PARTITION_LABEL
+    );
+    Ok(())
+}
+
+/// Eject or power down the target after a successful write
+///
+/// Failures are reported as warnings; the data has already been written.
+fn eject_target(path: &str, silent: bool) {
+    println_if!(silent, "\n{}", style("Ejecting device...").bold());
+
+    match eject_device(path) {
+        Ok(()) => println_if!(silent, "  {} Device ejected", style("✓").green()),
+        Err(e) => {
+            tracing::warn!("Eject failed: {}", e);
+            println_if!(
+                silent,
+                "  {} Could not eject device: {}",
+                style("⚠").yellow(),
+                e
+            );
+        }
+    }
+}
+
+/// Auto-detect or use explicit checksums, verify if found
+///
+/// Every expected checksum is checked in a single pass over the source.
 fn setup_checksum(args: &WriteArgs, source_size: Option<u64>, ctx: &WriteContext) -> Result<()> {
-    let (effective_checksum, effective_algo) = if args.checksum.is_none() && args.auto_checksum {
+    let expected = expected_checksums(args, args.auto_checksum, ctx)?;
+    if expected.is_empty() {
+        return Ok(());
+    }
+    verify_source_checksums(args, &expected, source_size, ctx)
+}
+
+/// The `--checksum` values, or with `auto_detect` a checksum file found next to the source
+fn expected_checksums(
+    args: &WriteArgs,
+    auto_detect: bool,
+    ctx: &WriteContext,
+) -> Result<Vec<Checksum>> {
+    let expected = if args.checksums.is_empty() && auto_detect {
         if let Some(detected) = auto_detect_checksum(&args.source) {
             println_if!(
                 ctx.silent,
@@ -313,69 +811,210 @@ fn setup_checksum(args: &WriteArgs, source_size: Option<u64>, ctx: &WriteContext
                 style("✓").green(),
                 detected.source_file.display()
             );
-            (Some(detected.checksum), Some(detected.algorithm))
+            vec![
+                Checksum::from_hex(detected.algorithm, &detected.checksum).with_context(|| {
+                    format!("Invalid checksum in {}", detected.source_file.display())
+                })?,
+            ]
         } else {
             println_if!(
                 ctx.silent,
                 "\n{} No checksum file found (tried .sha256, .sha512, .md5, SHA256SUMS, etc.)",
                 style("ℹ").blue()
             );
-            (None, None)
+            Vec::new()
         }
     } else {
-        let algo = args.checksum.as_ref().map(|_| {
-            args.checksum_algo
-                .parse::<ChecksumAlgorithm>()
-                .unwrap_or(ChecksumAlgorithm::Sha256)
-        });
-        (args.checksum.clone(), algo)
+        let default_algo = args
+            .checksum_algo
+            .parse::<ChecksumAlgorithm>()
+            .unwrap_or(ChecksumAlgorithm::Sha256);
+        args.checksums
+            .iter()
+            .map(|spec| parse_checksum_spec(spec, default_algo))
+            .collect::<Result<Vec<_>>>()?
     };
+    Ok(expected)
+}
 
-    if let Some(expected_checksum) = &effective_checksum {
-        println_if!(
-            ctx.silent,
-            "\n{}",
-            style("Verifying source checksum...").bold()
-        );
+/// Hash the source once and compare it with every expected checksum
+fn verify_source_checksums(
+    args: &WriteArgs,
+    expected: &[Checksum],
+    source_size: Option<u64>,
+    ctx: &WriteContext,
+) -> Result<()> {
+    println_if!(
+        ctx.silent,
+        "\n{}",
+        style("Verifying source checksum...").bold()
+    );
 
-        let algo: ChecksumAlgorithm = effective_algo.unwrap_or_else(|| {
-            args.checksum_algo
-                .parse()
-                .unwrap_or(ChecksumAlgorithm::Sha256)
-        });
+    // Published checksums cover the whole file, including any --input-offset header
+    let mut source_for_checksum = Source::open_with_entry(&args.source, 0, args.entry.as_deref())
+        .context("Failed to open source for checksum")?;
+    let source_size = source_size.map(|size| size + args.input_offset);
+
+    let reporter = ctx.progress.create("Checksumming", source_size, None);
 
-        let mut source_for_checksum =
-            Source::open(&args.source).context("Failed to open source for checksum")?;
+    let config = VerifyConfig::new().block_size(ctx.block_size);
+    let reporter_clone = reporter.clone();
+    let mut verifier = Verifier::with_config(config).on_progress(move |p| {
+        reporter_clone.on_verify(p);
+    });
 
-        let pb = create_progress_bar(source_size, "Checksumming", ctx.silent);
+    let algorithms: Vec<ChecksumAlgorithm> = expected.iter().map(|c| c.algorithm).collect();
+    let result = verifier.calculate_checksums(&mut source_for_checksum, &algorithms, source_size);
 
-        let config = VerifyConfig::new().block_size(ctx.block_size);
-        let pb_clone = pb.clone();
-        let mut verifier = Verifier::with_config(config).on_progress(move |p| {
-            pb_clone.set_position(p.bytes_processed);
-        });
+    reporter.finish();
 
-        let result = verifier.verify_checksum(
-            &mut source_for_checksum,
-            algo,
-            expected_checksum,
-            source_size,
+    let actual = result.map_err(|e| {
+        CliError::VerificationFailed(format!("Checksum verification failed: {}", e))
+    })?;
+
+    if expected.iter().zip(&actual).all(|(e, a)| e.matches(a)) {
+        let names: Vec<&str> = algorithms.iter().map(|a| a.name()).collect();
+        println_if!(
+            ctx.silent,
+            "  {} Checksum verified ({})",
+            style("✓").green(),
+            names.join(", ")
         );
+        Ok(())
+    } else {
+        Err(CliError::VerificationFailed(format!(
+            "Checksum verification failed:\n{}",
+            checksum_report(expected, &actual)
+        ))
+        .into())
+    }
+}
 
-        pb.finish_and_clear();
+/// Whether `--verify` hashes the source during the write instead of comparing afterwards
+///
+/// Bytes mode compares the source and target block by block once the write
+/// is done, unless the source can't be reread as-is (remote, compressed or
+/// with a skipped header), where hashing while writing saves a second read.
+/// A sampled verify always compares afterwards, and a resumed write can't be
+/// hashed in parallel because the hash would only cover the remaining bytes.
+fn uses_parallel_verify(
+    verify_mode: VerifyMode,
+    verify_sample: Option<f64>,
+    byte_comparable: bool,
+    resume_offset: u64,
+) -> bool {
+    verify_sample.is_none()
+        && resume_offset == 0
+        && (verify_mode == VerifyMode::Checksum || !byte_comparable)
+}
 
-        match result {
-            Ok(_) => println_if!(
-                ctx.silent,
-                "  {} Checksum verified ({})",
+/// The server's digest of a remote source, if it should be checked during the write
+///
+/// It is only used when no `--checksum` was given, and not on a resumed
+/// write, whose hash would cover just the remaining bytes.
+fn server_digest_to_check(
+    args: &WriteArgs,
+    source_info: &engraver_core::SourceInfo,
+    resume_offset: u64,
+    silent: bool,
+) -> Option<(ChecksumAlgorithm, String)> {
+    if !args.checksums.is_empty() {
+        return None;
+    }
+    let (algorithm, hex) = source_info.server_digest.clone()?;
+    if args.input_offset > 0 {
+        println_if!(
+            silent,
+            "  {} Not checking the server's {} digest, which includes the skipped header",
+            style("ℹ").blue(),
+            algorithm
+        );
+        return None;
+    }
+    if resume_offset > 0 {
+        println_if!(
+            silent,
+            "  {} Not checking the server's {} digest on a resumed write",
+            style("ℹ").blue(),
+            algorithm
+        );
+        return None;
+    }
+    println_if!(
+        silent,
+        "  {} Server sent a {} digest; the download will be checked against it",
+        style("ℹ").blue(),
+        algorithm
+    );
+    Some((algorithm, hex))
+}
+
+/// Compare the checksum of the written stream with the server's digest
+fn check_server_digest(
+    algorithm: ChecksumAlgorithm,
+    expected: &str,
+    actual: Option<&str>,
+    silent: bool,
+) -> Result<()> {
+    match actual {
+        Some(actual) if actual.eq_ignore_ascii_case(expected) => {
+            println_if!(
+                silent,
+                "  {} Download matches the server's {} digest",
                 style("✓").green(),
-                algo.name()
-            ),
-            Err(e) => bail!("Checksum verification failed: {}", e),
+                algorithm
+            );
+            Ok(())
         }
+        _ => Err(CliError::VerificationFailed(format!(
+            "Download doesn't match the server's {} digest\n\
+             Expected: {}\n\
+             Actual:   {}\n\
+             \n\
+             The image was corrupted in transit; write it again.",
+            algorithm,
+            expected,
+            actual.unwrap_or("unknown")
+        ))
+        .into()),
     }
+}
 
-    Ok(())
+/// Parse a `--checksum` value: `ALGO:HEX`, or bare hex in `default_algo`
+fn parse_checksum_spec(spec: &str, default_algo: ChecksumAlgorithm) -> Result<Checksum> {
+    let checksum = match spec.split_once(':') {
+        Some((algo, hex)) => Checksum::from_hex(algo.parse()?, hex),
+        None => Checksum::from_hex(default_algo, spec),
+    };
+    checksum.with_context(|| format!("Invalid --checksum '{}'", spec))
+}
+
+/// One line per expected checksum, showing the actual value for mismatches
+fn checksum_report(expected: &[Checksum], actual: &[Checksum]) -> String {
+    expected
+        .iter()
+        .zip(actual)
+        .map(|(expected, actual)| {
+            if expected.matches(actual) {
+                format!("  {}: OK", expected.algorithm.name())
+            } else {
+                format!(
+                    "  {}: expected {}, got {}",
+                    expected.algorithm.name(),
+                    expected,
+                    actual
+                )
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Build the checkpoint identity (size, serial, model) for a detected drive
+pub(crate) fn target_identity(drive: &Drive) -> TargetIdentity {
+    TargetIdentity::new(drive.size)
+        .serial(drive.serial.clone())
+        .model(drive.model.clone())
 }
 
 /// Set up checkpoint manager and handle resume logic
@@ -403,7 +1042,11 @@ fn setup_checkpoint(
     if args.resume {
         if let Some(ref mgr) = checkpoint_manager {
             if let Ok(Some(checkpoint)) = mgr.find_checkpoint(&args.source, &target_drive.path) {
-                let validation = validate_checkpoint(&checkpoint, source_info, target_drive.size);
+                let validation = validate_checkpoint_for(
+                    &checkpoint,
+                    source_info,
+                    &target_identity(target_drive),
+                );
 
                 if validation.valid {
                     println_if!(
@@ -423,82 +1066,921 @@ fn setup_checkpoint(
                     );
                     println_if!(silent, "  Resume count: {}", checkpoint.resume_count);
 
-                    for warning in &validation.warnings {
-                        println_if!(silent, "  {} {}", style("Warning:").yellow(), warning);
-                    }
+                    for warning in &validation.warnings {
+                        println_if!(silent, "  {} {}", style("Warning:").yellow(), warning);
+                    }
+
+                    let should_resume = if args.skip_confirm {
+                        true
+                    } else {
+                        prompt::confirm("Resume from checkpoint?", true)?
+                    };
+
+                    if should_resume {
+                        resume_offset = checkpoint.bytes_written;
+                        existing_checkpoint = Some(checkpoint);
+                        println_if!(
+                            silent,
+                            "  {} Resuming from byte {}",
+                            style("✓").green(),
+                            resume_offset
+                        );
+                    } else {
+                        let _ = mgr.remove(&checkpoint);
+                        println_if!(silent, "  {} Starting fresh write", style("ℹ").blue());
+                    }
+                } else {
+                    println_if!(
+                        silent,
+                        "\n{}",
+                        style("Existing checkpoint is invalid:").yellow()
+                    );
+                    for msg in &validation.messages {
+                        println_if!(silent, "  {}", msg);
+                    }
+                    let _ = mgr.remove(&checkpoint);
+                    println_if!(silent, "  {} Starting fresh write", style("ℹ").blue());
+                }
+            }
+        }
+    }
+
+    Ok((checkpoint_manager, resume_offset, existing_checkpoint))
+}
+
+/// Compare the blocks just before `resume_offset` with the local source
+///
+/// Returns the offset to resume from, rewound to the last verified block
+/// boundary if the tail of the previous write is torn.
+fn verify_resume_tail(
+    source_path: &str,
+    target: &mut dyn RawDevice,
+    resume_offset: u64,
+    ctx: &WriteContext,
+) -> Result<u64> {
+    let mut source = std::fs::File::open(source_path)
+        .with_context(|| format!("Failed to open source: {}", source_path))?;
+    let writer = Writer::with_config(
+        WriteConfig::new()
+            .block_size(ctx.block_size)
+            .verify_on_resume(true),
+    );
+
+    let verified_offset = writer.verify_resume_offset(&mut source, target, resume_offset)?;
+    if verified_offset < resume_offset {
+        println_if!(
+            ctx.silent,
+            "  {} Last blocks before the checkpoint did not match; resuming from byte {}",
+            style("⚠").yellow(),
+            verified_offset
+        );
+    } else {
+        println_if!(
+            ctx.silent,
+            "  {} Verified data before resume point",
+            style("✓").green()
+        );
+    }
+
+    Ok(verified_offset)
+}
+
+/// Verify the written data by hashing the source and the read-back target
+///
+/// Only the first `total_size` bytes of the target are hashed, so any data
+/// beyond the end of the image on the device is ignored. The source's first
+/// `header` bytes were not written and are skipped.
+fn verify_by_checksum(
+    source: &Source,
+    header: u64,
+    target: &mut dyn RawDevice,
+    source_size: Option<u64>,
+    total_size: u64,
+    algorithm: ChecksumAlgorithm,
+    ctx: &WriteContext,
+) -> Result<()> {
+    let silent = ctx.silent;
+    let block_size = ctx.block_size;
+
+    // Calculate checksum of what we wrote
+    target.seek(SeekFrom::Start(0))?;
+
+    let reporter = ctx
+        .progress
+        .create("Checksumming", Some(total_size), Some(block_size));
+
+    let config = VerifyConfig::new()
+        .block_size(block_size)
+        .drop_cache(ctx.drop_cache);
+    let reporter_clone = reporter.clone();
+    let mut verifier = Verifier::with_config(config).on_progress(move |p| {
+        reporter_clone.on_verify(p);
+    });
+    verifier
+        .drop_target_cache(&*target)
+        .context("Failed to drop cached pages before verifying")?;
+
+    let cancel_forward = CancelForward::spawn(&ctx.cancel, verifier.cancel_handle());
+    let written_checksum = verifier
+        .calculate_checksum(&mut *target, algorithm, Some(total_size))
+        .context("Failed to checksum written data")?;
+    drop(cancel_forward);
+
+    reporter.finish();
+
+    // Re-open source and calculate its checksum
+    println_if!(silent, "  Calculating source checksum...");
+    let mut source_for_checksum = source.reopen().context("Failed to reopen source")?;
+    skip_header(&mut source_for_checksum, header).context("Failed to reopen source")?;
+
+    let reporter = ctx
+        .progress
+        .create("Checksumming source", source_size, Some(block_size));
+
+    let config = VerifyConfig::new().block_size(block_size);
+    let reporter_clone = reporter.clone();
+    let mut verifier = Verifier::with_config(config).on_progress(move |p| {
+        reporter_clone.on_verify(p);
+    });
+
+    let cancel_forward = CancelForward::spawn(&ctx.cancel, verifier.cancel_handle());
+    let source_checksum = verifier
+        .calculate_checksum(&mut source_for_checksum, algorithm, source_size)
+        .context("Failed to checksum source")?;
+    drop(cancel_forward);
+
+    reporter.finish();
+
+    if written_checksum.matches(&source_checksum) {
+        println_if!(
+            silent,
+            "  {} Checksum verification passed ({})",
+            style("✓").green(),
+            algorithm
+        );
+        println_if!(silent, "    {}", written_checksum.to_hex());
+        Ok(())
+    } else {
+        Err(CliError::VerificationFailed(format!(
+            "Checksum mismatch!\n\
+             Source:  {}\n\
+             Written: {}\n\
+             \n\
+             The written data doesn't match the source.\n\
+             \n\
+             Possible causes:\n\
+             • Faulty USB drive or SD card\n\
+             • Data corruption during write\n\
+             • Source file changed during operation\n\
+             \n\
+             Suggestions:\n\
+             • Try writing again\n\
+             • Use a different device",
+            source_checksum.to_hex(),
+            written_checksum.to_hex()
+        ))
+        .into())
+    }
+}
+
+/// Execute the write command
+pub fn execute(mut args: WriteArgs) -> Result<()> {
+    if let Some(selector) = &args.target_selector {
+        let drives = list_drives().context("Failed to list drives")?;
+        let drive = find_drive_by(&drives, selector)?;
+        println_if!(
+            args.silent,
+            "{} Selected {} by {}",
+            style("ℹ").blue(),
+            drive.path,
+            selector
+        );
+        args.targets = vec![drive.path.clone()];
+    }
+
+    if args.glob {
+        let drives = list_drives().context("Failed to list drives")?;
+        args.targets = expand_target_globs(&args.targets, &drives, args.skip_confirm, args.silent)?;
+    }
+
+    let cached = match &args.cache_dir {
+        Some(dir) => download_to_cache(&args, dir)?,
+        None => None,
+    };
+    if let Some(path) = &cached {
+        args.source = path.display().to_string();
+    }
+
+    let result = if args.verify_source_only {
+        verify_source_only(&args)
+    } else if args.watch {
+        execute_watch(&args)
+    } else {
+        match args.targets.as_slice() {
+            [target] => write_to_target(&args, target),
+            _ => execute_many(&args),
+        }
+    };
+
+    if let Some(path) = cached {
+        if result.is_ok() && !args.keep_cache {
+            match std::fs::remove_file(&path) {
+                Ok(()) => println_if!(
+                    args.silent,
+                    "{} Removed cached download {}",
+                    style("ℹ").blue(),
+                    path.display()
+                ),
+                Err(e) => println_if!(
+                    args.silent,
+                    "{} Could not remove cached download {}: {}",
+                    style("⚠").yellow(),
+                    path.display(),
+                    e
+                ),
+            }
+        } else {
+            println_if!(
+                args.silent,
+                "{} Cached download kept at {}",
+                style("ℹ").blue(),
+                path.display()
+            );
+        }
+    }
+    result
+}
+
+/// Validate the source and check its checksum, without opening any target
+///
+/// For scripts that gate a flash on source integrity: the checksum comes
+/// from `--checksum` or, failing that, a checksum file next to the image.
+/// Having neither is an error, so a missing checksum never passes silently.
+fn verify_source_only(args: &WriteArgs) -> Result<()> {
+    let ctx = WriteContext {
+        silent: args.silent,
+        block_size: parse_block_size(&args.block_size)?,
+        progress: args.progress,
+        cancel: args.cancel_flag.clone(),
+        drop_cache: !args.no_verify_cache_drop,
+    };
+
+    let (_, source_size) = validate_source_info(
+        &args.source,
+        args.entry.as_deref(),
+        args.input_offset,
+        ctx.silent,
+    )?;
+    let expected = expected_checksums(args, true, &ctx)?;
+    if expected.is_empty() {
+        bail!(
+            "--verify-source-only needs a checksum: pass --checksum, or put a \
+             .sha256/SHA256SUMS file next to the image"
+        );
+    }
+    verify_source_checksums(args, &expected, source_size, &ctx)?;
+
+    println_if!(
+        ctx.silent,
+        "\n{} Source verified; no device was opened (--verify-source-only)",
+        style("✓").green().bold()
+    );
+    Ok(())
+}
+
+/// Expand `--glob` target patterns into the safe drives they match
+///
+/// Only removable, non-system drives with media count. Unless `--yes` was
+/// given, each matched drive is confirmed on its own and skipped if declined.
+fn expand_target_globs(
+    patterns: &[String],
+    drives: &[Drive],
+    skip_confirm: bool,
+    silent: bool,
+) -> Result<Vec<String>> {
+    let mut matched: Vec<&Drive> = Vec::new();
+    for pattern in patterns {
+        for drive in find_drives_matching(drives, pattern) {
+            if drive.is_safe_target()
+                && drive.media_present
+                && !matched.iter().any(|d| d.path == drive.path)
+            {
+                matched.push(drive);
+            }
+        }
+    }
+    if matched.is_empty() {
+        bail!(
+            "{} matches no safe removable drives (system, internal and empty drives are never matched)",
+            patterns.join(" ")
+        );
+    }
+
+    println_if!(
+        silent,
+        "{} --glob matched {} drive(s): {}",
+        style("ℹ").blue(),
+        matched.len(),
+        matched
+            .iter()
+            .map(|d| d.path.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    if skip_confirm {
+        return Ok(matched.iter().map(|d| d.path.clone()).collect());
+    }
+
+    let mut targets = Vec::new();
+    for drive in matched {
+        let write = prompt::confirm(
+            format!(
+                "Include {} ({}, {})?",
+                drive.path,
+                drive.display_name(),
+                drive.size_display()
+            ),
+            false,
+        )?;
+        if write {
+            targets.push(drive.path.clone());
+        }
+    }
+    if targets.is_empty() {
+        return Err(CliError::Aborted.into());
+    }
+    Ok(targets)
+}
+
+/// Download a URL source into `--cache-dir`, returning the local copy
+///
+/// Returns `None` for sources that are not HTTP(S), which are written as-is.
+fn download_to_cache(args: &WriteArgs, dir: &Path) -> Result<Option<PathBuf>> {
+    if detect_source_type(&args.source) != SourceType::Remote {
+        println_if!(
+            args.silent,
+            "{} --cache-dir only applies to HTTP(S) sources, ignoring it",
+            style("ℹ").blue()
+        );
+        return Ok(None);
+    }
+
+    let spinner = create_status_spinner(args.silent);
+    spinner.set_message("Downloading to cache...");
+    let progress_spinner = spinner.clone();
+    let mut cache = DownloadCache::new(dir).on_progress(move |done, total| {
+        progress_spinner.set_message(match total {
+            Some(total) => format!(
+                "Downloading: {} / {}",
+                format_size(done),
+                format_size(total)
+            ),
+            None => format!("Downloading: {}", format_size(done)),
+        });
+    });
+
+    let result = {
+        let _forward = CancelForward::spawn(&args.cancel_flag, cache.cancel_handle());
+        cache.fetch(&args.source)
+    };
+    spinner.finish_and_clear();
+
+    let path = result
+        .with_context(|| format!("Failed to download {} into {}", args.source, dir.display()))?;
+    println_if!(
+        args.silent,
+        "{} Downloaded to {}",
+        style("✓").green().bold(),
+        path.display()
+    );
+    Ok(Some(path))
+}
+
+/// Write the image to every target in `args.targets` in parallel
+///
+/// All targets are validated and confirmed together before anything is
+/// written. Each device then gets its own thread and source reader, so one
+/// failing device does not stop the others; the outcome of each is
+/// summarized at the end, and the command fails if any device failed.
+///
+/// `--verify` always uses checksum read-back here (the source is hashed
+/// while writing), and resume/checkpointing is not supported.
+fn execute_many(args: &WriteArgs) -> Result<()> {
+    if args.resume || args.checkpoint {
+        bail!("--resume and --checkpoint only support a single target");
+    }
+    if args.confirm_device.is_some() {
+        bail!("--confirm-device only supports a single target");
+    }
+    if args.partition_after.is_some() {
+        bail!("--partition-after only supports a single target");
+    }
+    if args.verify_sample.is_some() {
+        bail!("--verify-sample only supports a single target");
+    }
+    if args.identify {
+        bail!("--identify only supports a single target");
+    }
+
+    let block_size = parse_block_size(&args.block_size)?;
+    let silent = args.silent;
+    let ctx = WriteContext {
+        silent,
+        block_size,
+        progress: args.progress,
+        cancel: args.cancel_flag.clone(),
+        drop_cache: !args.no_verify_cache_drop,
+    };
+
+    check_privileges()?;
+
+    let (source_info, source_size) = validate_source_info(
+        &args.source,
+        args.entry.as_deref(),
+        args.input_offset,
+        silent,
+    )?;
+    warn_if_not_disk_image(args, &source_info, silent);
+
+    let drives = list_drives().context("Failed to list drives")?;
+    let mut targets: Vec<&Drive> = Vec::with_capacity(args.targets.len());
+    for target in &args.targets {
+        let drive = validate_target_device(
+            target,
+            &drives,
+            args.force,
+            args.skip_confirm,
+            None,
+            source_size,
+            silent,
+        )?;
+        if targets.iter().any(|d| d.path == drive.path) {
+            bail!("Target {} was given more than once", drive.path);
+        }
+        preserved_write_limit(
+            drive,
+            args.preserve_tail,
+            source_info.size.and(source_size),
+            preflight_block_size(args),
+        )?;
+        if args.show_destruction {
+            display_destruction_summary(drive, args.json, args.json_compat, silent)?;
+        }
+        targets.push(drive);
+    }
+    check_device_source(
+        &args.source,
+        is_block_device(&args.source),
+        &targets,
+        args.force,
+    )?;
+
+    if args.show_partitions {
+        display_source_partitions(&args.source, silent)?;
+    }
+
+    if !confirm_write(&source_info, &targets, args.skip_confirm)? {
+        return Err(CliError::Aborted.into());
+    }
+
+    if !args.no_unmount {
+        for drive in &targets {
+            unmount_target(&drive.path, silent);
+        }
+    }
+
+    setup_checksum(args, source_size, &ctx)?;
+
+    let total_size = source_size.unwrap_or(0);
+    println_if!(
+        silent,
+        "\n{}",
+        style(format!("Writing image to {} devices...", targets.len())).bold()
+    );
+
+    let batch = BatchProgress::new(args.progress, total_size, targets.len());
+    let start_time = Instant::now();
+    let source_info = &source_info;
+    let outcomes: Vec<Result<WriteResult>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = targets
+            .iter()
+            .map(|drive| {
+                let reporter = batch.device(&drive.path, Some(block_size));
+                scope.spawn(move || {
+                    write_one_of_many(args, source_info, drive, block_size, total_size, reporter)
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("Write thread panicked")))
+            })
+            .collect()
+    });
+    batch.finish();
+
+    // Close out successful devices one at a time so their output doesn't interleave
+    for (drive, outcome) in targets.iter().zip(&outcomes) {
+        if outcome.is_ok() {
+            reread_target_partitions(&drive.path, silent);
+            if args.check_bootable {
+                check_bootable(&drive.path, block_size, silent);
+            }
+            if args.eject {
+                eject_target(&drive.path, silent);
+            }
+        }
+    }
+
+    let failed = print_batch_summary(&targets, &outcomes, start_time.elapsed(), silent);
+    if failed == 0 {
+        return Ok(());
+    }
+    if args.cancel_flag.is_cancelled() {
+        return Err(CliError::Cancelled.into());
+    }
+    bail!("{} of {} devices failed", failed, targets.len())
+}
+
+/// Measure progress by compressed input when the decompressed size is unknown
+///
+/// The progress total is then the compressed size, so the bar and the ETA
+/// follow the compressed bytes read instead of overrunning it.
+fn track_compressed_progress(
+    writer: Writer,
+    source: &Source,
+    source_info: &engraver_core::SourceInfo,
+) -> Writer {
+    match (
+        source_info.size,
+        source_info.compressed_size,
+        source.compressed_counter(),
+    ) {
+        (None, Some(total), Some(counter)) => writer.track_compressed_input(counter, total),
+        _ => writer,
+    }
+}
+
+/// Write the image to one device of a batch, from its own source reader
+fn write_one_of_many(
+    args: &WriteArgs,
+    source_info: &engraver_core::SourceInfo,
+    drive: &Drive,
+    block_size: usize,
+    total_size: u64,
+    reporter: Arc<dyn ProgressReporter>,
+) -> Result<WriteResult> {
+    let mut source =
+        open_validated_source(args, source_info, 0).context("Failed to open source")?;
+
+    let device_path = get_raw_device_path(&drive.path);
+    let options = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .direct_io(!args.no_direct_io)
+        .block_size(block_size);
+    let mut target = open_device(&device_path, options)
+        .with_context(|| format!("Failed to open device: {}", device_path))?;
+
+    if args.require_blank {
+        require_blank_target(&mut *target, &drive.path, block_size, args.force, true)?;
+    }
+
+    let direct_io = target.info().direct_io;
+    let device_block_size = target.info().block_size as usize;
+    let write_limit = preserved_write_limit(
+        drive,
+        args.preserve_tail,
+        source_info.size.and(Some(total_size)),
+        if direct_io {
+            device_block_size as u64
+        } else {
+            1
+        },
+    )?;
+    let mut config = WriteConfig::new()
+        .block_size(block_size)
+        .sync_each_block(false)
+        .sync_on_complete(true)
+        .pad_final_block(direct_io)
+        .device_block_size(device_block_size)
+        .drop_cache(!args.no_verify_cache_drop)
+        .write_limit(write_limit)
+        .small_head_bytes(small_head_bytes(args))
+        .verify_each_block(args.verify_each_block);
+    if args.verify {
+        let algorithm = args
+            .checksum_algo
+            .parse()
+            .unwrap_or(ChecksumAlgorithm::Sha256);
+        config = config.checksum_algorithm(Some(algorithm));
+    }
+
+    let reporter_clone = reporter.clone();
+    let writer = track_compressed_progress(Writer::with_config(config), &source, source_info);
+    let mut writer = writer.on_progress(move |progress| {
+        reporter_clone.on_write(progress);
+    });
+
+    let start_time = Instant::now();
+    let write_result = {
+        let _cancel_forward = CancelForward::spawn(&args.cancel_flag, writer.cancel_handle());
+        if args.verify {
+            writer.write_and_verify_device(Prefetch::new(&mut source), &mut *target, total_size)
+        } else {
+            writer.write_to_device(Prefetch::new(&mut source), &mut *target, total_size, 0)
+        }
+    };
+    reporter.finish();
+
+    let result = match write_result {
+        Ok(result) => result,
+        Err(engraver_core::Error::Cancelled(_)) => {
+            if let Err(e) = target.sync() {
+                tracing::debug!("Sync after cancel: {}", e);
+            }
+            return Err(CliError::Cancelled.into());
+        }
+        Err(e) => {
+            if let Err(sync_err) = target.sync() {
+                tracing::debug!("Sync after error: {}", sync_err);
+            }
+            let message = format_write_error(&e);
+            return Err(anyhow::Error::new(e).context(message));
+        }
+    };
+
+    if result.verified == Some(false) {
+        return Err(CliError::VerificationFailed(format!(
+            "Verification failed: source checksum {}, written checksum {}",
+            result.source_checksum.as_deref().unwrap_or("unknown"),
+            result.target_checksum.as_deref().unwrap_or("unknown")
+        ))
+        .into());
+    }
+
+    target.sync().context("Failed to sync device")?;
+
+    if let Some(ref log) = args.history_log {
+        let entry = HistoryEntry::new(
+            &args.source,
+            &drive.path,
+            result.bytes_written,
+            start_time.elapsed(),
+            result.verified == Some(true),
+        );
+        if let Err(e) = log.append(&entry) {
+            tracing::warn!("Failed to record write history: {}", e);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Print one line per device of a batch write, returning how many failed
+///
+/// Failures go to stderr with their exit status, so they are shown even
+/// with `--silent`.
+fn print_batch_summary(
+    targets: &[&Drive],
+    outcomes: &[Result<WriteResult>],
+    elapsed: std::time::Duration,
+    silent: bool,
+) -> usize {
+    println_if!(silent, "\n{}", style("Summary:").bold());
+
+    let mut failed = 0;
+    for (drive, outcome) in targets.iter().zip(outcomes) {
+        match outcome {
+            Ok(result) => println_if!(
+                silent,
+                "  {} {}: wrote {}{}",
+                style("✓").green(),
+                drive.path,
+                format_size(result.bytes_written),
+                if result.verified == Some(true) {
+                    ", verified"
+                } else {
+                    ""
+                }
+            ),
+            Err(e) => {
+                failed += 1;
+                eprintln!(
+                    "  {} {}: {:#} (exit status {})",
+                    style("✗").red(),
+                    drive.path,
+                    e,
+                    ExitCode::from_error(e).code()
+                );
+            }
+        }
+    }
+
+    println_if!(
+        silent,
+        "\n{} {} of {} devices written in {:.1}s",
+        if failed == 0 {
+            style("✓").green().bold()
+        } else {
+            style("✗").red().bold()
+        },
+        targets.len() - failed,
+        targets.len(),
+        elapsed.as_secs_f64()
+    );
+
+    failed
+}
 
-                    let should_resume = if args.skip_confirm {
-                        true
-                    } else {
-                        Confirm::new()
-                            .with_prompt("Resume from checkpoint?")
-                            .default(true)
-                            .interact()?
-                    };
+/// How often `--watch` polls for newly inserted drives
+const WATCH_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
 
-                    if should_resume {
-                        resume_offset = checkpoint.bytes_written;
-                        existing_checkpoint = Some(checkpoint);
-                        println_if!(
-                            silent,
-                            "  {} Resuming from byte {}",
-                            style("✓").green(),
-                            resume_offset
-                        );
-                    } else {
-                        let _ = mgr.remove(&checkpoint);
-                        println_if!(silent, "  {} Starting fresh write", style("ℹ").blue());
-                    }
-                } else {
-                    println_if!(
-                        silent,
-                        "\n{}",
-                        style("Existing checkpoint is invalid:").yellow()
-                    );
-                    for msg in &validation.messages {
-                        println_if!(silent, "  {}", msg);
-                    }
-                    let _ = mgr.remove(&checkpoint);
-                    println_if!(silent, "  {} Starting fresh write", style("ℹ").blue());
+/// Wait for new removable drives and write the image to each one
+///
+/// Drives present when watching starts are ignored. A drive is written once
+/// per insertion; removing and reinserting it makes it eligible again. Runs
+/// until Ctrl+C, which also cancels a write in progress.
+fn execute_watch(args: &WriteArgs) -> Result<()> {
+    check_privileges()?;
+
+    let mut known: HashSet<String> = list_removable_drives()
+        .context("Failed to list drives")?
+        .into_iter()
+        .map(|d| d.path)
+        .collect();
+    let mut written = 0usize;
+    let mut failed = 0usize;
+
+    eprintln!(
+        "{} Watching for new drives (Ctrl+C to stop)...",
+        style("ℹ").blue()
+    );
+
+    while !args.cancel_flag.is_cancelled() {
+        std::thread::sleep(WATCH_POLL_INTERVAL);
+
+        let drives = match list_removable_drives() {
+            Ok(drives) => drives,
+            Err(e) => {
+                tracing::warn!("Failed to list drives: {}", e);
+                continue;
+            }
+        };
+
+        // Forget removed drives so a reinserted card is written again
+        known.retain(|path| drives.iter().any(|d| &d.path == path));
+
+        for drive in new_safe_drives(&drives, &known) {
+            known.insert(drive.path.clone());
+            println_if!(
+                args.silent,
+                "\n{} {} ({})",
+                style("New drive detected:").bold().green(),
+                drive.display_name(),
+                drive.path
+            );
+
+            match write_to_target(args, &drive.path) {
+                Ok(()) => written += 1,
+                Err(e)
+                    if ExitCode::from_error(&e) == ExitCode::Cancelled
+                        || args.cancel_flag.is_cancelled() =>
+                {
+                    return Err(e);
+                }
+                Err(e) => {
+                    failed += 1;
+                    eprintln!("{} {:#}", style("Error:").red().bold(), e);
                 }
             }
+
+            if args.cancel_flag.is_cancelled() {
+                break;
+            }
+            eprintln!(
+                "{} Waiting for the next drive (Ctrl+C to stop)...",
+                style("ℹ").blue()
+            );
         }
     }
 
-    Ok((checkpoint_manager, resume_offset, existing_checkpoint))
+    println_if!(
+        args.silent,
+        "\n{} Watch stopped: {} drive(s) written, {} failed",
+        style("ℹ").blue(),
+        written,
+        failed
+    );
+
+    Ok(())
 }
 
-/// Execute the write command
-pub fn execute(args: WriteArgs) -> Result<()> {
+/// Drives that are not in `known` and pass the basic safety check
+fn new_safe_drives<'a>(drives: &'a [Drive], known: &HashSet<String>) -> Vec<&'a Drive> {
+    drives
+        .iter()
+        .filter(|d| !known.contains(&d.path) && d.is_safe_target())
+        .collect()
+}
+
+/// Write the image to a single target device
+fn write_to_target(args: &WriteArgs, target: &str) -> Result<()> {
     let block_size = parse_block_size(&args.block_size)?;
     let silent = args.silent;
-    let ctx = WriteContext { silent, block_size };
+    let ctx = WriteContext {
+        silent,
+        block_size,
+        progress: args.progress,
+        cancel: args.cancel_flag.clone(),
+        drop_cache: !args.no_verify_cache_drop,
+    };
 
     // Step 0: Check for elevated privileges
     check_privileges()?;
 
     // Step 1: Validate source
-    let (source_info, source_size) = validate_source_info(&args.source, silent)?;
+    let (source_info, source_size) = validate_source_info(
+        &args.source,
+        args.entry.as_deref(),
+        args.input_offset,
+        silent,
+    )?;
+    warn_if_not_disk_image(args, &source_info, silent);
+    if args.verify_sample.is_some() && source_info.source_type != SourceType::LocalFile {
+        bail!(
+            "--verify-sample needs a seekable source; {} is remote or compressed.\n\
+             Use --verify --verify-mode checksum for a full checksum verify instead.",
+            args.source
+        );
+    }
 
     // Step 2: Validate target device
     let drives = list_drives().context("Failed to list drives")?;
     let target_drive = validate_target_device(
-        &args.target,
+        target,
         &drives,
         args.force,
         args.skip_confirm,
+        args.confirm_device.as_deref(),
         source_size,
         silent,
     )?;
 
+    // Step 2.2: Cloning from a device needs --force and a different target
+    check_device_source(
+        &args.source,
+        is_block_device(&args.source),
+        &[target_drive],
+        args.force,
+    )?;
+
+    // Step 2.3: Keep the write out of the region --preserve-tail protects
+    let write_limit = preserved_write_limit(
+        target_drive,
+        args.preserve_tail,
+        source_info.size.and(source_size),
+        preflight_block_size(args),
+    )?;
+    if let Some(limit) = write_limit {
+        println_if!(
+            silent,
+            "  {} Preserving the last {} of the device; writes stop at {}",
+            style("ℹ").blue(),
+            format_size(args.preserve_tail),
+            format_size(limit)
+        );
+    }
+
+    // Step 2.4: Make sure a requested data partition will fit
+    if let (Some(fs), Some(size)) = (args.partition_after, source_size) {
+        check_data_partition_room(target_drive, fs, size, silent)?;
+    }
+
     // Step 2.5: Show partition information if requested
     if args.show_partitions {
         display_source_partitions(&args.source, silent)?;
     }
 
+    // Step 2.6: Show what will be destroyed on the target if requested
+    if args.show_destruction {
+        display_destruction_summary(target_drive, args.json, args.json_compat, silent)?;
+    }
+
+    // Step 2.7: Flash the target so the user can check it is the right drive
+    if args.identify {
+        let path = get_raw_device_path(&target_drive.path);
+        if let Err(e) = super::list::identify(&path, silent) {
+            println_if!(silent, "  {} {:#}", style("⚠").yellow(), e);
+        }
+    }
+
     // Step 3: Confirmation
-    if !confirm_write(&source_info, target_drive, args.skip_confirm)? {
-        return Ok(());
+    if !confirm_write(&source_info, &[target_drive], args.skip_confirm)? {
+        return Err(CliError::Aborted.into());
     }
 
     // Step 4: Unmount device
@@ -507,11 +1989,11 @@ pub fn execute(args: WriteArgs) -> Result<()> {
     }
 
     // Step 5: Checksum verification
-    setup_checksum(&args, source_size, &ctx)?;
+    setup_checksum(args, source_size, &ctx)?;
 
     // Step 6: Check for existing checkpoint (resume support)
-    let (checkpoint_manager, resume_offset, mut existing_checkpoint) =
-        setup_checkpoint(&args, &source_info, target_drive)?;
+    let (checkpoint_manager, mut resume_offset, mut existing_checkpoint) =
+        setup_checkpoint(args, &source_info, target_drive)?;
 
     // Step 7: Open source and target device
     let total_blocks = source_size
@@ -526,15 +2008,15 @@ pub fn execute(args: WriteArgs) -> Result<()> {
         total_blocks
     );
 
-    let mut source =
-        Source::open_with_offset(&args.source, resume_offset).context("Failed to open source")?;
+    let mut source = open_validated_source(args, &source_info, resume_offset)
+        .context("Failed to open source")?;
 
     // Open target device using platform layer with direct I/O
     let device_path = get_raw_device_path(&target_drive.path);
     let options = OpenOptions::new()
         .read(true)
         .write(true)
-        .direct_io(true) // Bypass page cache for better performance
+        .direct_io(!args.no_direct_io) // Bypass page cache for better performance
         .block_size(block_size);
 
     let mut target = open_device(&device_path, options)
@@ -542,16 +2024,59 @@ pub fn execute(args: WriteArgs) -> Result<()> {
 
     let device_info = target.info().clone();
     tracing::debug!(
-        "Opened device: {} ({} bytes, block_size={}, direct_io={})",
+        "Opened device: {} ({} bytes, block_size={}, physical_block_size={}, optimal_io_size={:?}, direct_io={})",
         device_info.path,
         device_info.size,
         device_info.block_size,
+        device_info.physical_block_size,
+        device_info.optimal_io_size,
         device_info.direct_io
     );
+    if !args.no_direct_io && !device_info.direct_io {
+        println_if!(
+            silent,
+            "  {} Direct I/O not supported by this device; using buffered I/O (may be slower)",
+            style("⚠").yellow()
+        );
+    }
+
+    // Now the device's block size is known, keep padded blocks inside the limit
+    let write_limit = preserved_write_limit(
+        target_drive,
+        args.preserve_tail,
+        source_info.size.and(source_size),
+        if device_info.direct_io {
+            u64::from(device_info.block_size)
+        } else {
+            1
+        },
+    )?;
+
+    // A resumed write has already put data on the device
+    if args.require_blank && resume_offset == 0 {
+        require_blank_target(
+            &mut *target,
+            &target_drive.path,
+            block_size,
+            args.force,
+            silent,
+        )?;
+    }
+
+    // Re-check the tail of the previous write before trusting the checkpoint
+    if resume_offset > 0 && source_info.source_type == SourceType::LocalFile {
+        let verified_offset = verify_resume_tail(&args.source, &mut *target, resume_offset, &ctx)?;
+        if verified_offset < resume_offset {
+            resume_offset = verified_offset;
+            source = Source::open_with_entry(&args.source, resume_offset, args.entry.as_deref())
+                .context("Failed to reopen source")?;
+        }
+    }
 
     // Step 8: Create or update checkpoint
     let mut checkpoint = if let Some(mut cp) = existing_checkpoint.take() {
         cp.mark_resumed();
+        cp.rewind_to(resume_offset);
         cp
     } else {
         let write_config = WriteConfig::new()
@@ -564,107 +2089,120 @@ pub fn execute(args: WriteArgs) -> Result<()> {
             target_drive.size,
             &write_config,
         )
+        .with_target_identity(&target_identity(target_drive))
     };
 
     // Step 9: Write with progress and checkpointing
     let total_size = source_size.unwrap_or(0);
-    let pb = create_write_progress_bar(total_size, silent);
+    let reporter = args.progress.create("Writing", Some(total_size), None);
     if resume_offset > 0 {
-        pb.set_position(resume_offset);
+        let mut resumed = WriteProgress::new(total_size, block_size);
+        resumed.current_offset = resume_offset;
+        resumed.current_block = resume_offset / block_size as u64;
+        reporter.on_write(&resumed);
     }
 
     let cancel_flag = args.cancel_flag.clone();
 
-    // Enable parallel verification: hash source data during write, then read back
-    // target to verify. Cannot be used with resume (partial hash would be incorrect).
-    let use_parallel_verify = args.verify && resume_offset == 0;
-    let verify_algo: ChecksumAlgorithm = args
-        .checksum_algo
-        .parse()
-        .unwrap_or(ChecksumAlgorithm::Sha256);
+    let source_is_local = source_info.source_type == SourceType::LocalFile;
+    let use_parallel_verify = args.verify
+        && uses_parallel_verify(
+            args.verify_mode,
+            args.verify_sample,
+            source_is_local && args.input_offset == 0,
+            resume_offset,
+        );
+    let server_digest = server_digest_to_check(args, &source_info, resume_offset, silent);
+    // Hashing once serves both checks, so the server's algorithm wins
+    let verify_algo: ChecksumAlgorithm = match server_digest {
+        Some((algorithm, _)) => algorithm,
+        None => args
+            .checksum_algo
+            .parse()
+            .unwrap_or(ChecksumAlgorithm::Sha256),
+    };
 
+    // Direct I/O rejects an unaligned last block, so it is zero-padded
     let mut config = WriteConfig::new()
         .block_size(block_size)
         .sync_each_block(false)
-        .sync_on_complete(true);
-
-    if use_parallel_verify {
+        .sync_on_complete(true)
+        .pad_final_block(device_info.direct_io)
+        .device_block_size(device_info.block_size as usize)
+        .drop_cache(ctx.drop_cache)
+        .write_limit(write_limit)
+        .small_head_bytes(small_head_bytes(args))
+        .verify_each_block(args.verify_each_block);
+
+    if use_parallel_verify || server_digest.is_some() {
         config = config.checksum_algorithm(Some(verify_algo));
     }
 
     let writer = Writer::with_config(config);
 
     // Set up progress callback with checkpoint saving
-    let pb_clone = pb.clone();
+    let reporter_clone = reporter.clone();
     let last_checkpoint_bytes =
         std::sync::Arc::new(std::sync::atomic::AtomicU64::new(resume_offset));
     let last_checkpoint_clone = last_checkpoint_bytes.clone();
-    let phase_switched = std::sync::Arc::new(AtomicBool::new(false));
-    let phase_switched_clone = phase_switched.clone();
 
+    let writer = track_compressed_progress(writer, &source, &source_info);
     let writer = writer.on_progress(move |progress| {
-        // When the phase switches to Verifying, update the progress bar style
-        if progress.phase == WritePhase::Verifying
-            && !phase_switched_clone.swap(true, Ordering::Relaxed)
-        {
-            pb_clone.set_position(0);
-            pb_clone.set_style(
-                ProgressStyle::default_bar()
-                    .template("  {spinner:.green} Verifying [{bar:40.cyan/blue}] {bytes}/{total_bytes} {msg}")
-                    .unwrap()
-                    .progress_chars("█▓░"),
-            );
-        }
-
-        pb_clone.set_position(progress.bytes_written);
-
-        // Build detailed progress message
-        let mut msg = format!(
-            "{}/s | Block {}/{} | ETA: {}",
-            format_size(progress.speed_bps),
-            progress.current_block,
-            progress.total_blocks,
-            progress.eta_display()
-        );
-
-        // Show retry count if any retries have occurred (only during write phase)
-        if progress.retry_count > 0 {
-            msg.push_str(&format!(" | {} retries", progress.retry_count));
-        }
-
-        pb_clone.set_message(msg);
+        reporter_clone.on_write(progress);
 
         // Track progress for checkpointing (checkpoint saved in main thread)
         if progress.phase == WritePhase::Writing {
-            last_checkpoint_clone.store(progress.bytes_written, Ordering::Relaxed);
+            last_checkpoint_clone.store(progress.current_offset, Ordering::Relaxed);
         }
     });
 
-    // Connect cancel flag
-    let writer_cancel = writer.cancel_handle();
-    let cancel_flag_for_thread = cancel_flag.clone();
-    std::thread::spawn(move || {
-        while cancel_flag_for_thread.load(Ordering::SeqCst) {
-            std::thread::sleep(std::time::Duration::from_millis(100));
+    // Periodically persist the checkpoint from a background thread, so a
+    // crash or power loss still leaves resume state behind
+    let autosave = match checkpoint_manager {
+        Some(ref mgr) if args.checkpoint_interval_secs > 0 => {
+            Some(CheckpointAutosave::spawn(mgr.clone()))
         }
-        writer_cancel.store(true, Ordering::SeqCst);
-    });
+        _ => None,
+    };
+    let writer = if let Some(ref autosave) = autosave {
+        let autosave = autosave.clone();
+        let mut snapshot = checkpoint.clone();
+        let interval = std::time::Duration::from_secs(args.checkpoint_interval_secs);
+        writer.on_checkpoint(interval, move |progress| {
+            let blocks_written = progress.current_offset / block_size as u64;
+            snapshot.update_progress(progress.current_offset, blocks_written, progress.elapsed);
+            autosave.submit(snapshot.clone());
+        })
+    } else {
+        writer
+    };
+
+    // Connect cancel flag for the duration of the write
+    let cancel_forward = CancelForward::spawn(&cancel_flag, writer.cancel_handle());
 
     let mut writer = writer;
     let start_time = Instant::now();
 
-    // Use write_and_verify for parallel verification, write_from_offset otherwise
+    // Use write_and_verify for parallel verification, write_to_device otherwise;
+    // the source is read ahead on its own thread either way
+    let prefetch = Prefetch::new(&mut source);
     let write_result = if use_parallel_verify {
-        writer.write_and_verify(&mut source, &mut *target, total_size)
+        writer.write_and_verify_device(prefetch, &mut *target, total_size)
     } else {
-        writer.write_from_offset(&mut source, &mut *target, total_size, resume_offset)
+        writer.write_to_device(prefetch, &mut *target, total_size, resume_offset)
     };
 
-    pb.finish_and_clear();
+    drop(cancel_forward);
+    reporter.finish();
+
+    // Stop auto-saving before the checkpoint is saved or removed below
+    if let Some(ref autosave) = autosave {
+        autosave.finish();
+    }
 
     // Handle write result
-    let write_success = match &write_result {
-        Ok(result) => {
+    let write_success = match write_result {
+        Ok(ref result) => {
             let elapsed = start_time.elapsed();
             let total_written = result.bytes_written;
             let resumed_bytes = if resume_offset > 0 { resume_offset } else { 0 };
@@ -730,7 +2268,7 @@ pub fn execute(args: WriteArgs) -> Result<()> {
                         println_if!(silent, "    {}", checksum);
                     }
                 } else {
-                    bail!(
+                    return Err(CliError::VerificationFailed(format!(
                         "Verification failed!\n\
                          Source checksum:  {}\n\
                          Written checksum: {}\n\
@@ -742,13 +2280,23 @@ pub fn execute(args: WriteArgs) -> Result<()> {
                          • Use a different USB port (preferably USB 3.0)",
                         result.source_checksum.as_deref().unwrap_or("unknown"),
                         result.target_checksum.as_deref().unwrap_or("unknown")
-                    );
+                    ))
+                    .into());
                 }
             }
 
+            if let Some((algorithm, ref expected)) = server_digest {
+                check_server_digest(
+                    algorithm,
+                    expected,
+                    result.source_checksum.as_deref(),
+                    silent,
+                )?;
+            }
+
             true
         }
-        Err(engraver_core::Error::Cancelled) => {
+        Err(engraver_core::Error::Cancelled(reason)) => {
             // Save checkpoint on cancel
             if let Some(ref mgr) = checkpoint_manager {
                 let bytes_written = last_checkpoint_bytes.load(Ordering::Relaxed);
@@ -771,8 +2319,12 @@ pub fn execute(args: WriteArgs) -> Result<()> {
             if let Err(e) = target.sync() {
                 tracing::debug!("Sync after cancel: {}", e);
             }
-            println_if!(silent, "\n{}", style("Write cancelled by user.").yellow());
-            return Ok(());
+            println_if!(
+                silent,
+                "\n{}",
+                style(format!("Write cancelled ({}).", reason)).yellow()
+            );
+            return Err(CliError::Cancelled.into());
         }
         Err(e) => {
             // Save checkpoint on error
@@ -797,7 +2349,8 @@ pub fn execute(args: WriteArgs) -> Result<()> {
                 tracing::debug!("Sync after error: {}", sync_err);
             }
             // Provide user-friendly error with suggestions
-            bail!("{}", format_write_error(e));
+            let message = format_write_error(&e);
+            return Err(anyhow::Error::new(e).context(message));
         }
     };
 
@@ -811,12 +2364,8 @@ pub fn execute(args: WriteArgs) -> Result<()> {
     }
 
     // Step 10: Sync using platform layer
-    print_if!(silent, "  Syncing... ");
-    if !silent {
-        std::io::stdout().flush()?;
-    }
-    target.sync().context("Failed to sync device")?;
-    println_if!(silent, "{}", style("done").green());
+    sync_with_spinner(&*target, silent).context("Failed to sync device")?;
+    println_if!(silent, "  Syncing... {}", style("done").green());
 
     // Step 11: Verify (if requested)
     // Skip if parallel verification already completed during write
@@ -826,9 +2375,10 @@ pub fn execute(args: WriteArgs) -> Result<()> {
         // For verification, we need a seekable source
         // For local uncompressed files, open directly
         // For remote/compressed, we recalculate checksum instead
-        let source_is_local = source_info.source_type == SourceType::LocalFile;
-
-        if source_is_local {
+        if source_is_local
+            && args.input_offset == 0
+            && (args.verify_mode == VerifyMode::Bytes || args.verify_sample.is_some())
+        {
             // Direct byte-by-byte comparison for local files
             let mut source_file = std::fs::File::open(&args.source)
                 .context("Failed to reopen source for verification")?;
@@ -836,32 +2386,48 @@ pub fn execute(args: WriteArgs) -> Result<()> {
             // Seek target back to start
             target.seek(SeekFrom::Start(0))?;
 
-            let pb = create_progress_bar(source_size, "Verifying", silent);
+            let reporter = ctx
+                .progress
+                .create("Verifying", source_size, Some(block_size));
 
-            let config = VerifyConfig::new().block_size(block_size);
-            let pb_clone = pb.clone();
-            let verify_block_size = block_size;
+            let mut config = VerifyConfig::new()
+                .block_size(block_size)
+                .drop_cache(ctx.drop_cache);
+            if let Some(fraction) = args.verify_sample {
+                config = config.sample_fraction(fraction);
+            }
+            let reporter_clone = reporter.clone();
             let mut verifier = Verifier::with_config(config).on_progress(move |p| {
-                pb_clone.set_position(p.bytes_processed);
-                let blocks = p.bytes_processed.div_ceil(verify_block_size as u64);
-                let total = p
-                    .total_bytes
-                    .map(|t| t.div_ceil(verify_block_size as u64))
-                    .unwrap_or(0);
-                pb_clone.set_message(format!(
-                    "{}/s | Block {}/{} | ETA: {}",
-                    p.speed_display(),
-                    blocks,
-                    total,
-                    p.eta_display()
-                ));
+                reporter_clone.on_verify(p);
             });
+            verifier
+                .drop_target_cache(&*target)
+                .context("Failed to drop cached pages before verifying")?;
 
-            let verify_result = verifier.compare(&mut source_file, &mut *target, total_size);
+            let verify_result = {
+                let _cancel_forward = CancelForward::spawn(&ctx.cancel, verifier.cancel_handle());
+                verifier.compare(&mut source_file, &mut *target, total_size)
+            };
 
-            pb.finish_and_clear();
+            reporter.finish();
 
             match verify_result {
+                Ok(result) if result.success && result.sampled => {
+                    println_if!(
+                        silent,
+                        "  {} Sampled verification passed: {} of {} compared in {:.1}s ({}/s)",
+                        style("✓").green(),
+                        format_size(result.sampled_bytes),
+                        format_size(total_size),
+                        result.elapsed.as_secs_f64(),
+                        format_size(result.speed_bps)
+                    );
+                    println_if!(
+                        silent,
+                        "  {} Only a sample of blocks was checked; use --verify for full coverage",
+                        style("ℹ").blue()
+                    );
+                }
                 Ok(result) if result.success => {
                     let blocks_verified = result.bytes_verified.div_ceil(block_size as u64);
                     println_if!(
@@ -875,7 +2441,7 @@ pub fn execute(args: WriteArgs) -> Result<()> {
                     );
                 }
                 Ok(result) => {
-                    bail!(
+                    return Err(CliError::VerificationFailed(format!(
                         "Verification failed! {} mismatch(es) found.\n\
                          First mismatch at offset {} (block {})\n\
                          \n\
@@ -892,112 +2458,77 @@ pub fn execute(args: WriteArgs) -> Result<()> {
                         result.mismatches,
                         result.first_mismatch_offset.unwrap_or(0),
                         result.first_mismatch_offset.unwrap_or(0) / block_size as u64
-                    );
+                    ))
+                    .into());
+                }
+                Err(e @ engraver_core::Error::Cancelled(_)) => {
+                    return Err(anyhow::Error::new(e).context("Verification stopped"));
                 }
                 Err(e) => {
-                    bail!("Verification failed: {}", e);
+                    return Err(CliError::VerificationFailed(format!(
+                        "Verification failed: {}",
+                        e
+                    ))
+                    .into());
                 }
             }
         } else {
-            // For remote/compressed sources, verify via checksum
-            println_if!(
-                silent,
-                "  {} Source is remote/compressed, using checksum verification",
-                style("ℹ").blue()
-            );
-
-            // Calculate checksum of what we wrote
-            target.seek(SeekFrom::Start(0))?;
-
-            let pb = create_progress_bar(Some(total_size), "Checksumming", silent);
-
-            let config = VerifyConfig::new().block_size(block_size);
-            let pb_clone = pb.clone();
-            let checksum_block_size = block_size;
-            let checksum_total = total_size;
-            let mut verifier = Verifier::with_config(config).on_progress(move |p| {
-                pb_clone.set_position(p.bytes_processed);
-                let blocks = p.bytes_processed.div_ceil(checksum_block_size as u64);
-                let total_blocks = checksum_total.div_ceil(checksum_block_size as u64);
-                pb_clone.set_message(format!(
-                    "{}/s | Block {}/{} | ETA: {}",
-                    p.speed_display(),
-                    blocks,
-                    total_blocks,
-                    p.eta_display()
-                ));
-            });
-
-            let written_checksum = verifier
-                .calculate_checksum(&mut *target, ChecksumAlgorithm::Sha256, Some(total_size))
-                .context("Failed to checksum written data")?;
-
-            pb.finish_and_clear();
-
-            // Re-open source and calculate its checksum
-            println_if!(silent, "  Calculating source checksum...");
-            let mut source_for_checksum =
-                Source::open(&args.source).context("Failed to reopen source")?;
-
-            let source_total = source_size.unwrap_or(0);
-            let pb = create_progress_bar(source_size, "Checksumming source", silent);
-
-            let config = VerifyConfig::new().block_size(block_size);
-            let pb_clone = pb.clone();
-            let source_block_size = block_size;
-            let mut verifier = Verifier::with_config(config).on_progress(move |p| {
-                pb_clone.set_position(p.bytes_processed);
-                let blocks = p.bytes_processed.div_ceil(source_block_size as u64);
-                let total_blocks = source_total.div_ceil(source_block_size as u64);
-                pb_clone.set_message(format!(
-                    "{}/s | Block {}/{} | ETA: {}",
-                    p.speed_display(),
-                    blocks,
-                    total_blocks,
-                    p.eta_display()
-                ));
-            });
-
-            let source_checksum = verifier
-                .calculate_checksum(
-                    &mut source_for_checksum,
-                    ChecksumAlgorithm::Sha256,
-                    source_size,
-                )
-                .context("Failed to checksum source")?;
-
-            pb.finish_and_clear();
-
-            if written_checksum.matches(&source_checksum) {
+            if !source_is_local {
                 println_if!(
                     silent,
-                    "  {} Checksum verification passed (SHA-256)",
-                    style("✓").green()
+                    "  {} Source is remote/compressed, using checksum verification",
+                    style("ℹ").blue()
                 );
-                println_if!(silent, "    {}", written_checksum.to_hex());
-            } else {
-                bail!(
-                    "Checksum mismatch!\n\
-                     Source:  {}\n\
-                     Written: {}\n\
-                     \n\
-                     The written data doesn't match the source.\n\
-                     \n\
-                     Possible causes:\n\
-                     • Faulty USB drive or SD card\n\
-                     • Data corruption during write\n\
-                     • Source file changed during operation\n\
-                     \n\
-                     Suggestions:\n\
-                     • Try writing again\n\
-                     • Use a different device",
-                    source_checksum.to_hex(),
-                    written_checksum.to_hex()
+            } else if args.input_offset > 0 && args.verify_mode == VerifyMode::Bytes {
+                println_if!(
+                    silent,
+                    "  {} Source header is skipped, using checksum verification",
+                    style("ℹ").blue()
                 );
             }
+            verify_by_checksum(
+                &source,
+                args.input_offset,
+                &mut *target,
+                source_size,
+                total_size,
+                verify_algo,
+                &ctx,
+            )?;
+        }
+    }
+
+    // Step 12: Record the completed write in history
+    if let Some(ref log) = args.history_log {
+        let bytes_written = write_result.as_ref().map_or(0, |r| r.bytes_written);
+        let entry = HistoryEntry::new(
+            &args.source,
+            &target_drive.path,
+            bytes_written,
+            start_time.elapsed(),
+            args.verify,
+        );
+        if let Err(e) = log.append(&entry) {
+            tracing::warn!("Failed to record write history: {}", e);
         }
     }
 
+    // Step 13: Close the device so the OS sees the new partitions, then eject
+    drop(target);
+    reread_target_partitions(&target_drive.path, silent);
+    if args.check_bootable {
+        check_bootable(&target_drive.path, block_size, silent);
+    }
+    if let Some(fs) = args.partition_after {
+        let image_end = write_result
+            .as_ref()
+            .map_or(total_size, |r| r.bytes_written);
+        create_data_partition_after(&target_drive.path, fs, image_end, silent)?;
+    }
+    if args.eject {
+        eject_target(&target_drive.path, silent);
+    }
+
     // Done!
     println_if!(silent);
     println_if!(
@@ -1033,11 +2564,138 @@ fn find_drive<'a>(drives: &'a [Drive], path: &str) -> Result<&'a Drive> {
         }
     }
 
-    bail!(
+    Err(CliError::DeviceNotFound(format!(
         "Device '{}' not found.\n\
          Run 'engraver list' to see available drives.",
         path
-    )
+    ))
+    .into())
+}
+
+/// Check that the target has media inserted and is large enough for the source
+///
+/// An empty card reader reports a size of 0, so it is rejected before the
+/// size comparison rather than as "larger than target (0 B)".
+fn check_target_size(drive: &Drive, source_size: Option<u64>) -> Result<()> {
+    if !drive.media_present {
+        bail!(
+            "No card inserted in {} ({})\n\
+             Insert a card and run the command again.",
+            drive.path,
+            drive.display_name()
+        );
+    }
+
+    if let Some(src_size) = source_size {
+        if src_size > drive.size {
+            bail!(
+                "Source ({}) is larger than target ({})",
+                format_size(src_size),
+                format_size(drive.size)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Check a clone from a block device (`source_is_device`) onto `targets`
+///
+/// Cloning is refused without `--force`, and always if the source is one of
+/// the targets or a partition on one of them, since the write would destroy
+/// the data still being read.
+fn check_device_source(
+    source: &str,
+    source_is_device: bool,
+    targets: &[&Drive],
+    force: bool,
+) -> Result<()> {
+    if !source_is_device {
+        return Ok(());
+    }
+
+    let canonical =
+        |path: &str| std::fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path));
+    let source_path = canonical(source);
+    for drive in targets {
+        let on_target = [&drive.path, &drive.raw_path]
+            .into_iter()
+            .chain(drive.partitions.iter().map(|p| &p.path))
+            .any(|path| canonical(path) == source_path);
+        if on_target {
+            bail!(
+                "Source {} is on the target {}; a device cannot be cloned onto itself",
+                source,
+                drive.path
+            );
+        }
+    }
+
+    if !force {
+        bail!(
+            "Source {} is a block device. Cloning one device to another overwrites the \
+             target with everything on the source, including its partition table.\n\
+             Use --force to clone it.",
+            source
+        );
+    }
+    Ok(())
+}
+
+/// First target byte a write must not reach when `--preserve-tail` is set
+///
+/// Returns `None` when nothing is preserved. The limit is rounded down to
+/// `device_block_size`, the unit a direct I/O write is padded to (1 when
+/// nothing is padded). Fails if the preserved region covers the whole
+/// device, or if a source of known size, once padded, would overlap it.
+/// This only guards a byte range; partitions in it are not moved or resized.
+fn preserved_write_limit(
+    drive: &Drive,
+    preserve_tail: u64,
+    source_size: Option<u64>,
+    device_block_size: u64,
+) -> Result<Option<u64>> {
+    if preserve_tail == 0 {
+        return Ok(None);
+    }
+
+    let device_block_size = device_block_size.max(1);
+    let limit = drive.size.saturating_sub(preserve_tail) / device_block_size * device_block_size;
+    if limit == 0 {
+        bail!(
+            "--preserve-tail {} leaves no room to write on {} ({})",
+            format_size(preserve_tail),
+            drive.path,
+            format_size(drive.size)
+        );
+    }
+
+    if let Some(size) = source_size {
+        if size.div_ceil(device_block_size) * device_block_size > limit {
+            bail!(
+                "Source ({}) would overlap the last {} of {}, which --preserve-tail protects\n\
+                 At most {} can be written to this device.",
+                format_size(size),
+                format_size(preserve_tail),
+                drive.path,
+                format_size(limit)
+            );
+        }
+    }
+
+    Ok(Some(limit))
+}
+
+/// Block size assumed by the `--preserve-tail` check before the device is opened
+///
+/// The device may report a larger logical block size once open, which is
+/// checked again then.
+fn preflight_block_size(args: &WriteArgs) -> u64 {
+    if args.no_direct_io {
+        1
+    } else {
+        DEFAULT_DEVICE_BLOCK_SIZE as u64
+    }
 }
 
 /// Get the raw device path for a given device path
@@ -1053,35 +2711,10 @@ fn get_raw_device_path(path: &str) -> String {
     path.to_string()
 }
 
-/// Parse a human-readable block size (e.g., "4M", "1M", "512K")
+/// Parse a human-readable block size (e.g., "4M", "1.5M", "512K") between 4K and 64M
 fn parse_block_size(s: &str) -> Result<usize> {
-    let s = s.trim().to_uppercase();
-
-    let (num_str, multiplier) = if s.ends_with('K') {
-        (&s[..s.len() - 1], 1024)
-    } else if s.ends_with('M') {
-        (&s[..s.len() - 1], 1024 * 1024)
-    } else if s.ends_with('G') {
-        (&s[..s.len() - 1], 1024 * 1024 * 1024)
-    } else {
-        (s.as_str(), 1)
-    };
-
-    let num: usize = num_str
-        .parse()
-        .with_context(|| format!("Invalid block size: {}", s))?;
-
-    let size = num * multiplier;
-
-    // Validate range
-    if size < 4096 {
-        bail!("Block size must be at least 4K");
-    }
-    if size > 64 * 1024 * 1024 {
-        bail!("Block size must be at most 64M");
-    }
-
-    Ok(size)
+    let size = parse_block_size_clamped(s, MIN_BLOCK_SIZE as u64, MAX_BLOCK_SIZE as u64)?;
+    Ok(size as usize)
 }
 
 /// Format a size in bytes to human-readable format
@@ -1165,6 +2798,30 @@ fn format_write_error(error: &engraver_core::Error) -> String {
                 expected, actual
             )
         }
+        Error::DeviceFull { bytes_written } => {
+            format!(
+                "Ran out of space after {}\n\
+                 \n\
+                 The target filled up before the whole image was written.\n\
+                 \n\
+                 Suggestions:\n\
+                 • Free up space on the file system holding the target file\n\
+                 • Write to a larger device or file system",
+                format_size(*bytes_written)
+            )
+        }
+        Error::WriteLimitExceeded { limit } => {
+            format!(
+                "The image reaches past {}, into the region --preserve-tail protects\n\
+                 \n\
+                 Nothing at or after that offset was written.\n\
+                 \n\
+                 Suggestions:\n\
+                 • Use a smaller image, or a smaller --preserve-tail\n\
+                 • Check the decompressed size of a compressed image",
+                format_size(*limit)
+            )
+        }
         Error::DeviceNotFound(path) => {
             format!(
                 "Device not found: {}\n\
@@ -1242,7 +2899,7 @@ fn format_write_error(error: &engraver_core::Error) -> String {
                 message
             )
         }
-        Error::Cancelled => "Operation cancelled".to_string(),
+        Error::Cancelled(reason) => format!("Operation cancelled: {}", reason),
         _ => format!("Write failed: {}", error),
     }
 }
@@ -1549,49 +3206,19 @@ fn format_windows_error(code: i32, base_msg: &str) -> String {
     }
 }
 
-/// Create a progress bar for operations
-fn create_progress_bar(total: Option<u64>, operation: &str, silent: bool) -> ProgressBar {
-    if silent {
-        return ProgressBar::hidden();
-    }
-
-    let pb = match total {
-        Some(t) => ProgressBar::new(t),
-        None => ProgressBar::new_spinner(),
-    };
-
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template(&format!(
-                "  {{spinner:.green}} {} [{{bar:40.cyan/blue}}] {{bytes}}/{{total_bytes}} ({{eta}})",
-                operation
-            ))
-            .unwrap()
-            .progress_chars("█▓░"),
-    );
-
-    pb
-}
-
-/// Create a progress bar for write operations
-fn create_write_progress_bar(total: u64, silent: bool) -> ProgressBar {
+/// Create a spinner for a step that reports status messages
+fn create_status_spinner(silent: bool) -> ProgressBar {
     if silent {
         return ProgressBar::hidden();
     }
 
-    let pb = if total > 0 {
-        ProgressBar::new(total)
-    } else {
-        ProgressBar::new_spinner()
-    };
-
+    let pb = ProgressBar::new_spinner();
     pb.set_style(
-        ProgressStyle::default_bar()
-            .template("  {spinner:.green} Writing [{bar:40.cyan/blue}] {bytes}/{total_bytes} {msg}")
-            .unwrap()
-            .progress_chars("█▓░"),
+        ProgressStyle::default_spinner()
+            .template("  {spinner:.green} {msg}")
+            .unwrap(),
     );
-
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
     pb
 }
 
@@ -1736,6 +3363,72 @@ fn display_source_partitions(_source_path: &str, silent: bool) -> Result<()> {
     Ok(())
 }
 
+/// Build the JSON form of the `--show-destruction` summary
+fn destruction_summary_json(drive: &Drive) -> Result<serde_json::Value> {
+    Ok(serde_json::json!({
+        "device": drive.path,
+        "name": drive.display_name(),
+        "size": drive.size,
+        "used_bytes": drive.partitions_size(),
+        "partitions": serde_json::to_value(&drive.partitions)?,
+    }))
+}
+
+/// Display the partitions on the target that will be destroyed by the write
+///
+/// Like `list --json`, the JSON form is printed even in silent mode.
+fn display_destruction_summary(
+    drive: &Drive,
+    json: bool,
+    json_compat: bool,
+    silent: bool,
+) -> Result<()> {
+    if json {
+        return json::print(
+            "destruction",
+            &destruction_summary_json(drive)?,
+            json_compat,
+        );
+    }
+
+    println_if!(
+        silent,
+        "\n{} {}",
+        style("Will be destroyed on").bold(),
+        style(&drive.path).bold()
+    );
+
+    if drive.partitions.is_empty() {
+        println_if!(silent, "  {} No partitions found", style("ℹ").blue());
+        return Ok(());
+    }
+
+    for part in &drive.partitions {
+        println_if!(
+            silent,
+            "  {} {}  {}  {}  {}{}",
+            style("⚠").yellow(),
+            part.path,
+            part.label.as_deref().unwrap_or("(no label)"),
+            part.filesystem.as_deref().unwrap_or("unknown"),
+            format_size(part.size),
+            part.mount_point
+                .as_deref()
+                .map(|m| format!("  mounted at {m}"))
+                .unwrap_or_default()
+        );
+    }
+
+    println_if!(
+        silent,
+        "  Total: {} in {} partition(s)",
+        style(format_size(drive.partitions_size())).yellow().bold(),
+        drive.partitions.len()
+    );
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1855,6 +3548,30 @@ mod tests {
         assert_eq!(get_raw_device_path("relative/path"), "relative/path");
     }
 
+    // -------------------------------------------------------------------------
+    // target_identity tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_target_identity_from_drive() {
+        let mut drive = Drive::new("/dev/sdb").with_size(8 * 1024 * 1024 * 1024);
+        drive.serial = Some("4C530001".to_string());
+        drive.model = Some("Cruzer Blade".to_string());
+
+        let identity = target_identity(&drive);
+        assert_eq!(identity.size, 8 * 1024 * 1024 * 1024);
+        assert_eq!(identity.serial.as_deref(), Some("4C530001"));
+        assert_eq!(identity.model.as_deref(), Some("Cruzer Blade"));
+    }
+
+    #[test]
+    fn test_target_identity_without_serial() {
+        let drive = Drive::new("/dev/sdc").with_size(1024);
+        let identity = target_identity(&drive);
+        assert!(identity.serial.is_none());
+        assert!(identity.model.is_none());
+    }
+
     // -------------------------------------------------------------------------
     // find_drive tests
     // -------------------------------------------------------------------------
@@ -1876,6 +3593,10 @@ mod tests {
             is_system: false,
             system_reason: None,
             usb_speed: None,
+            media_present: true,
+            enclosure: false,
+            rotational: false,
+            caution_reason: None,
         }];
 
         let result = find_drive(&drives, "/dev/sdb");
@@ -1900,6 +3621,10 @@ mod tests {
             is_system: true,
             system_reason: Some("Contains /".to_string()),
             usb_speed: None,
+            media_present: true,
+            enclosure: false,
+            rotational: false,
+            caution_reason: None,
         }];
 
         let result = find_drive(&drives, "/dev/sdb");
@@ -1926,11 +3651,17 @@ mod tests {
                 filesystem: Some("vfat".to_string()),
                 label: Some("UBUNTU".to_string()),
                 mount_point: Some("/mnt/usb".to_string()),
+                used: None,
+                available: None,
             }],
             mount_points: vec!["/mnt/usb".to_string()],
             is_system: false,
             system_reason: None,
             usb_speed: None,
+            media_present: true,
+            enclosure: false,
+            rotational: false,
+            caution_reason: None,
         }];
 
         // Trying to write to a partition should fail with helpful message
@@ -1942,51 +3673,487 @@ mod tests {
     }
 
     // -------------------------------------------------------------------------
-    // Progress bar creation tests
+    // check_target_size tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_check_target_size_fits() {
+        let drive = sandisk_drive();
+        assert!(check_target_size(&drive, Some(drive.size)).is_ok());
+        assert!(check_target_size(&drive, None).is_ok());
+    }
+
+    #[test]
+    fn test_check_target_size_too_large() {
+        let drive = sandisk_drive();
+        let err = check_target_size(&drive, Some(drive.size + 1))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("larger than target"));
+    }
+
+    #[test]
+    fn test_check_target_size_rejects_empty_reader() {
+        let drive = Drive {
+            size: 0,
+            media_present: false,
+            ..sandisk_drive()
+        };
+
+        // Even an unknown source size is rejected: there is nothing to write to
+        for source_size in [Some(1024), Some(0), None] {
+            let err = check_target_size(&drive, source_size)
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains("No card inserted in /dev/sdb"), "{}", err);
+            assert!(!err.contains("larger than target"));
+        }
+    }
+
+    #[test]
+    fn test_check_target_size_zero_size_with_media() {
+        let drive = Drive {
+            size: 0,
+            ..sandisk_drive()
+        };
+
+        let err = check_target_size(&drive, Some(1)).unwrap_err().to_string();
+        assert!(err.contains("larger than target (0 B)"));
+        assert!(check_target_size(&drive, Some(0)).is_ok());
+    }
+
+    // -------------------------------------------------------------------------
+    // check_device_source tests (regular files stand in for devices)
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_check_device_source_ignores_images() {
+        let drive = sandisk_drive();
+        assert!(check_device_source("image.img", false, &[&drive], false).is_ok());
+    }
+
+    #[test]
+    fn test_check_device_source_requires_force() {
+        let source = tempfile::NamedTempFile::new().unwrap();
+        let path = source.path().to_str().unwrap();
+        let drive = sandisk_drive();
+
+        let err = check_device_source(path, true, &[&drive], false).unwrap_err();
+        assert!(err.to_string().contains("--force"));
+        assert!(check_device_source(path, true, &[&drive], true).is_ok());
+    }
+
+    #[test]
+    fn test_check_device_source_rejects_target() {
+        let source = tempfile::NamedTempFile::new().unwrap();
+        let path = source.path().to_str().unwrap();
+        let mut drive = sandisk_drive();
+        drive.path = path.to_string();
+
+        let err = check_device_source(path, true, &[&drive], true).unwrap_err();
+        assert!(err.to_string().contains("cannot be cloned onto itself"));
+    }
+
+    #[test]
+    fn test_check_device_source_rejects_target_partition() {
+        let dir = tempfile::tempdir().unwrap();
+        let partition = dir.path().join("sdb1");
+        std::fs::write(&partition, b"").unwrap();
+        let mut drive = sandisk_drive();
+        drive.partitions.push(engraver_detect::Partition {
+            path: partition.to_string_lossy().into_owned(),
+            label: None,
+            filesystem: None,
+            size: 0,
+            mount_point: None,
+            used: None,
+            available: None,
+        });
+
+        // A relative spelling of the same path is still caught
+        let source = dir.path().join(".").join("sdb1");
+        let err = check_device_source(source.to_str().unwrap(), true, &[&drive], true).unwrap_err();
+        assert!(err.to_string().contains("is on the target"));
+    }
+
+    // -------------------------------------------------------------------------
+    // preserved_write_limit tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_preserved_write_limit_without_tail() {
+        let drive = sandisk_drive();
+        assert_eq!(
+            preserved_write_limit(&drive, 0, Some(1), 512).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_preserved_write_limit_leaves_tail() {
+        let drive = sandisk_drive();
+        let gb = 1024 * 1024 * 1024;
+        let limit = drive.size - gb;
+        assert_eq!(
+            preserved_write_limit(&drive, gb, Some(limit), 512).unwrap(),
+            Some(limit)
+        );
+        assert_eq!(
+            preserved_write_limit(&drive, gb, None, 512).unwrap(),
+            Some(limit)
+        );
+    }
+
+    #[test]
+    fn test_preserved_write_limit_rejects_overlapping_source() {
+        let drive = sandisk_drive();
+        let gb = 1024 * 1024 * 1024;
+        let err = preserved_write_limit(&drive, gb, Some(drive.size - gb + 1), 1)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("would overlap the last 1.00 GB"), "{}", err);
+    }
+
+    #[test]
+    fn test_preserved_write_limit_rounds_to_device_block() {
+        let drive = sandisk_drive();
+        let tail = 1024 * 1024 + 100;
+        let limit = preserved_write_limit(&drive, tail, None, 4096)
+            .unwrap()
+            .unwrap();
+        assert_eq!(limit % 4096, 0);
+        assert!(limit <= drive.size - tail);
+        assert!(drive.size - tail - limit < 4096);
+    }
+
+    #[test]
+    fn test_preserved_write_limit_rejects_padded_overlap() {
+        let drive = sandisk_drive();
+        let tail = 4096 + 100;
+        let source_size = drive.size - tail - 100;
+        // Fits byte for byte, but its padded last block would cross the limit
+        assert!(preserved_write_limit(&drive, tail, Some(source_size), 1).is_ok());
+        let err = preserved_write_limit(&drive, tail, Some(source_size), 4096)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("would overlap"), "{}", err);
+    }
+
+    #[test]
+    fn test_preserved_write_limit_rejects_whole_device() {
+        let drive = sandisk_drive();
+        let err = preserved_write_limit(&drive, drive.size, None, 512)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("leaves no room"), "{}", err);
+    }
+
+    // -------------------------------------------------------------------------
+    // check_confirm_device tests
+    // -------------------------------------------------------------------------
+
+    fn sandisk_drive() -> Drive {
+        Drive {
+            path: "/dev/sdb".to_string(),
+            raw_path: "/dev/sdb".to_string(),
+            name: "sdb".to_string(),
+            size: 16 * 1024 * 1024 * 1024,
+            removable: true,
+            drive_type: engraver_detect::DriveType::Usb,
+            vendor: Some("SanDisk".to_string()),
+            model: Some("Ultra".to_string()),
+            serial: None,
+            partitions: vec![],
+            mount_points: vec![],
+            is_system: false,
+            system_reason: None,
+            usb_speed: None,
+            media_present: true,
+            enclosure: false,
+            rotational: false,
+            caution_reason: None,
+        }
+    }
+
+    #[test]
+    fn test_check_confirm_device_matches_name_or_path() {
+        let drive = sandisk_drive();
+        assert!(check_confirm_device(&drive, "SanDisk Ultra").is_ok());
+        assert!(check_confirm_device(&drive, "/dev/sdb").is_ok());
+    }
+
+    #[test]
+    fn test_check_confirm_device_requires_exact_match() {
+        let drive = sandisk_drive();
+        for expected in ["sandisk ultra", "SanDisk", "SanDisk Ultra ", "/dev/sdc", ""] {
+            let err = check_confirm_device(&drive, expected).unwrap_err();
+            assert_eq!(
+                crate::error::ExitCode::from_error(&err),
+                crate::error::ExitCode::Aborted
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_confirm_device_error_message() {
+        let err = check_confirm_device(&sandisk_drive(), "Kingston DataTraveler").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("'Kingston DataTraveler'"));
+        assert!(message.contains("/dev/sdb is 'SanDisk Ultra'"));
+    }
+
+    #[test]
+    fn test_validate_target_device_caution_skipped_with_yes() {
+        let drives = vec![Drive {
+            caution_reason: Some("Spinning hard disk".to_string()),
+            ..sandisk_drive()
+        }];
+        let drive =
+            validate_target_device("/dev/sdb", &drives, false, true, None, None, true).unwrap();
+        assert_eq!(drive.path, "/dev/sdb");
+    }
+
+    // -------------------------------------------------------------------------
+    // Glob target tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_expand_target_globs_keeps_safe_matches() {
+        let drive = |path: &str| Drive {
+            path: path.to_string(),
+            raw_path: path.to_string(),
+            ..sandisk_drive()
+        };
+        let drives = vec![
+            Drive {
+                is_system: true,
+                ..drive("/dev/sda")
+            },
+            drive("/dev/sdb"),
+            Drive {
+                media_present: false,
+                ..drive("/dev/sdc")
+            },
+            drive("/dev/sdd"),
+            drive("/dev/sdg"),
+        ];
+
+        let patterns = ["/dev/sd[a-f]".to_string(), "/dev/sdb".to_string()];
+        let targets = expand_target_globs(&patterns, &drives, true, true).unwrap();
+        assert_eq!(targets, ["/dev/sdb", "/dev/sdd"]);
+    }
+
+    #[test]
+    fn test_expand_target_globs_refuses_no_safe_match() {
+        let drives = vec![Drive {
+            is_system: true,
+            ..sandisk_drive()
+        }];
+        let err = expand_target_globs(&["/dev/sd*".to_string()], &drives, true, true).unwrap_err();
+        assert!(err.to_string().contains("matches no safe removable drives"));
+    }
+
+    // -------------------------------------------------------------------------
+    // Watch mode tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_new_safe_drives_skips_known_and_unsafe() {
+        let known_drive = sandisk_drive();
+        let new_drive = Drive {
+            path: "/dev/sdc".to_string(),
+            ..sandisk_drive()
+        };
+        let system_drive = Drive {
+            path: "/dev/sda".to_string(),
+            is_system: true,
+            ..sandisk_drive()
+        };
+        let drives = vec![known_drive, new_drive, system_drive];
+        let known: HashSet<String> = ["/dev/sdb".to_string()].into_iter().collect();
+
+        let found = new_safe_drives(&drives, &known);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, "/dev/sdc");
+    }
+
+    #[test]
+    fn test_new_safe_drives_none_when_nothing_inserted() {
+        let drives = vec![sandisk_drive()];
+        let known: HashSet<String> = drives.iter().map(|d| d.path.clone()).collect();
+        assert!(new_safe_drives(&drives, &known).is_empty());
+    }
+
+    // -------------------------------------------------------------------------
+    // Destruction summary tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_destruction_summary_json() {
+        let mut drive = sandisk_drive();
+        drive.partitions = vec![
+            engraver_detect::Partition {
+                path: "/dev/sdb1".to_string(),
+                label: Some("BOOT".to_string()),
+                filesystem: Some("vfat".to_string()),
+                size: 256 * 1024 * 1024,
+                mount_point: Some("/media/boot".to_string()),
+                used: None,
+                available: None,
+            },
+            engraver_detect::Partition {
+                path: "/dev/sdb2".to_string(),
+                label: None,
+                filesystem: Some("ext4".to_string()),
+                size: 1024 * 1024 * 1024,
+                mount_point: None,
+                used: None,
+                available: None,
+            },
+        ];
+
+        let summary = destruction_summary_json(&drive).unwrap();
+        assert_eq!(summary["device"], "/dev/sdb");
+        assert_eq!(summary["name"], "SanDisk Ultra");
+        assert_eq!(summary["used_bytes"], 1280 * 1024 * 1024_u64);
+        assert_eq!(summary["partitions"].as_array().unwrap().len(), 2);
+        assert_eq!(summary["partitions"][0]["label"], "BOOT");
+        assert_eq!(summary["partitions"][0]["mount_point"], "/media/boot");
+        assert!(summary["partitions"][1]["label"].is_null());
+    }
+
+    #[test]
+    fn test_destruction_summary_json_no_partitions() {
+        let summary = destruction_summary_json(&sandisk_drive()).unwrap();
+        assert_eq!(summary["used_bytes"], 0);
+        assert!(summary["partitions"].as_array().unwrap().is_empty());
+    }
+
+    // -------------------------------------------------------------------------
+    // Data partition tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_check_data_partition_room() {
+        let drive = sandisk_drive();
+        assert!(
+            check_data_partition_room(&drive, DataFilesystem::Fat32, 1024 * 1024, true).is_ok()
+        );
+
+        let err =
+            check_data_partition_room(&drive, DataFilesystem::ExFat, drive.size, true).unwrap_err();
+        assert!(format!("{:#}", err).contains("no room for a data partition"));
+    }
+
+    // -------------------------------------------------------------------------
+    // Checksum spec tests
     // -------------------------------------------------------------------------
 
+    const MD5_HELLO: &str = "5d41402abc4b2a76b9719d911017c592";
+
+    #[test]
+    fn test_parse_checksum_spec_with_algorithm() {
+        let checksum =
+            parse_checksum_spec(&format!("md5:{}", MD5_HELLO), ChecksumAlgorithm::Sha256).unwrap();
+        assert_eq!(checksum.algorithm, ChecksumAlgorithm::Md5);
+        assert_eq!(checksum.to_hex(), MD5_HELLO);
+    }
+
+    #[test]
+    fn test_parse_checksum_spec_bare_hex_uses_default() {
+        let checksum = parse_checksum_spec(MD5_HELLO, ChecksumAlgorithm::Md5).unwrap();
+        assert_eq!(checksum.algorithm, ChecksumAlgorithm::Md5);
+    }
+
     #[test]
-    fn test_create_progress_bar_silent() {
-        let pb = create_progress_bar(Some(1024), "Test", true);
-        // Silent progress bar should be hidden
-        assert!(pb.is_hidden());
+    fn test_parse_checksum_spec_invalid() {
+        assert!(parse_checksum_spec("blake3:abcd", ChecksumAlgorithm::Sha256).is_err());
+        let err = parse_checksum_spec("sha256:abcd", ChecksumAlgorithm::Sha256).unwrap_err();
+        assert!(format!("{:#}", err).contains("sha256:abcd"));
     }
 
     #[test]
-    fn test_create_progress_bar_with_size() {
-        let pb = create_progress_bar(Some(1024 * 1024), "Writing", false);
-        // Progress bar may be hidden in test environment without a terminal
-        // Just verify it was created with the right length
-        assert_eq!(pb.length(), Some(1024 * 1024));
+    fn test_checksum_report_lists_each_algorithm() {
+        let expected = [
+            Checksum::from_hex(ChecksumAlgorithm::Md5, MD5_HELLO).unwrap(),
+            Checksum::from_hex(ChecksumAlgorithm::Crc32, "00000000").unwrap(),
+        ];
+        let actual = [
+            Checksum::from_hex(ChecksumAlgorithm::Md5, MD5_HELLO).unwrap(),
+            Checksum::from_hex(ChecksumAlgorithm::Crc32, "3610a686").unwrap(),
+        ];
+
+        let report = checksum_report(&expected, &actual);
+        assert!(report.contains("MD5: OK"));
+        assert!(report.contains("CRC32: expected 00000000, got 3610a686"));
     }
 
     #[test]
-    fn test_create_progress_bar_unknown_size() {
-        let pb = create_progress_bar(None, "Downloading", false);
-        // Spinner doesn't have a length - just verify creation succeeds
-        assert!(pb.length().is_none() || pb.length() == Some(0));
+    fn test_check_server_digest_match() {
+        let upper = MD5_HELLO.to_uppercase();
+        assert!(check_server_digest(ChecksumAlgorithm::Md5, &upper, Some(MD5_HELLO), true).is_ok());
     }
 
     #[test]
-    fn test_create_write_progress_bar_silent() {
-        let pb = create_write_progress_bar(1024, true);
-        assert!(pb.is_hidden());
+    fn test_check_server_digest_mismatch() {
+        let err =
+            check_server_digest(ChecksumAlgorithm::Md5, MD5_HELLO, Some("00"), true).unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.contains("doesn't match the server's MD5 digest"));
+        assert!(message.contains(MD5_HELLO));
+
+        assert!(check_server_digest(ChecksumAlgorithm::Md5, MD5_HELLO, None, true).is_err());
+    }
+
+    // -------------------------------------------------------------------------
+    // Input offset tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_open_past_header_opens_seekable_source_at_offset() {
+        let info = engraver_core::SourceInfo::local("image.img", 16);
+        let mut opened_at = None;
+        open_past_header(4, &info, 2, |start| {
+            opened_at = Some(start);
+            Ok(Source::from_bytes(Vec::new(), "image.img"))
+        })
+        .unwrap();
+        assert_eq!(opened_at, Some(6));
+    }
+
+    #[test]
+    fn test_open_past_header_discards_header_of_stream() {
+        let info = engraver_core::SourceInfo::compressed("image.img.gz", 16, SourceType::Gzip);
+        let mut source = open_past_header(3, &info, 0, |start| {
+            assert_eq!(start, 0);
+            Ok(Source::from_bytes(b"HDRimage".to_vec(), "image.img.gz"))
+        })
+        .unwrap();
+
+        let mut rest = String::new();
+        source.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "image");
     }
 
     #[test]
-    fn test_create_write_progress_bar_with_size() {
-        let pb = create_write_progress_bar(1024 * 1024, false);
-        // Progress bar may be hidden in test environment without a terminal
-        // Just verify it was created with the right length
-        assert_eq!(pb.length(), Some(1024 * 1024));
+    fn test_skip_header_past_end_of_source() {
+        let mut source = Source::from_bytes(b"short".to_vec(), "image.img");
+        let err = skip_header(&mut source, 10).unwrap_err();
+        assert!(err.to_string().contains("past the end of the source"));
     }
 
     #[test]
-    fn test_create_write_progress_bar_zero_size() {
-        let pb = create_write_progress_bar(0, false);
-        // Zero size creates a spinner - just verify creation succeeds
-        // Spinner may have length 0 or None depending on indicatif version
-        let _ = pb.length(); // Just ensure it doesn't panic
+    fn test_validate_source_info_subtracts_input_offset() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("image.img");
+        std::fs::write(&path, [0u8; 1024]).unwrap();
+        let path = path.to_str().unwrap();
+
+        let (_, size) = validate_source_info(path, None, 512, true).unwrap();
+        assert_eq!(size, Some(512));
+
+        let err = validate_source_info(path, None, 1024, true).unwrap_err();
+        assert!(err.to_string().contains("must be smaller than the source"));
     }
 
     // -------------------------------------------------------------------------
@@ -1997,30 +4164,57 @@ mod tests {
     fn test_write_args_creation() {
         let args = WriteArgs {
             source: "ubuntu.iso".to_string(),
-            target: "/dev/sdb".to_string(),
+            targets: vec!["/dev/sdb".to_string()],
+            target_selector: None,
+            entry: None,
+            input_offset: 0,
+            preserve_tail: 0,
             verify: true,
+            verify_mode: VerifyMode::Bytes,
+            verify_sample: None,
+            no_verify_cache_drop: false,
             skip_confirm: false,
+            confirm_device: None,
             block_size: "4M".to_string(),
-            checksum: Some("abc123".to_string()),
+            checksums: vec!["abc123".to_string()],
             checksum_algo: "sha256".to_string(),
             force: false,
+            require_blank: false,
             no_unmount: false,
-            cancel_flag: Arc::new(AtomicBool::new(false)),
+            no_direct_io: false,
+            small_head: false,
+            verify_each_block: false,
+            eject: false,
+            check_bootable: false,
+            partition_after: None,
+            cancel_flag: CancelToken::new(),
             silent: false,
             resume: false,
             checkpoint: true,
+            checkpoint_interval_secs: 30,
             auto_checksum: false,
             show_partitions: false,
+            show_destruction: false,
+            json: false,
+            json_compat: false,
+            progress: ReporterKind::Null,
+            watch: false,
+            history_log: None,
+            cache_dir: None,
+            keep_cache: false,
+            identify: false,
+            verify_source_only: false,
+            glob: false,
         };
 
         assert_eq!(args.source, "ubuntu.iso");
-        assert_eq!(args.target, "/dev/sdb");
+        assert_eq!(args.targets, ["/dev/sdb"]);
         assert!(args.verify);
         assert!(!args.skip_confirm);
         assert_eq!(args.block_size, "4M");
-        assert!(args.checksum.is_some());
+        assert_eq!(args.checksums.len(), 1);
         assert!(!args.force);
-        assert!(!args.cancel_flag.load(Ordering::Relaxed));
+        assert!(!args.cancel_flag.is_cancelled());
         assert!(!args.auto_checksum);
         assert!(!args.show_partitions);
     }
@@ -2029,28 +4223,55 @@ mod tests {
     fn test_write_args_with_show_partitions() {
         let args = WriteArgs {
             source: "debian.img".to_string(),
-            target: "/dev/sdc".to_string(),
+            targets: vec!["/dev/sdc".to_string()],
+            target_selector: None,
+            entry: None,
+            input_offset: 0,
+            preserve_tail: 0,
             verify: false,
+            verify_mode: VerifyMode::Bytes,
+            verify_sample: None,
+            no_verify_cache_drop: false,
             skip_confirm: true,
+            confirm_device: None,
             block_size: "1M".to_string(),
-            checksum: None,
+            checksums: Vec::new(),
             checksum_algo: "sha256".to_string(),
             force: false,
+            require_blank: false,
             no_unmount: true,
-            cancel_flag: Arc::new(AtomicBool::new(false)),
+            no_direct_io: false,
+            small_head: false,
+            verify_each_block: false,
+            eject: false,
+            check_bootable: false,
+            partition_after: None,
+            cancel_flag: CancelToken::new(),
             silent: true,
             resume: false,
             checkpoint: false,
+            checkpoint_interval_secs: 30,
             auto_checksum: true,
             show_partitions: true,
+            show_destruction: false,
+            json: false,
+            json_compat: false,
+            progress: ReporterKind::Null,
+            watch: false,
+            history_log: None,
+            cache_dir: None,
+            keep_cache: false,
+            identify: false,
+            verify_source_only: false,
+            glob: false,
         };
 
         assert_eq!(args.source, "debian.img");
-        assert_eq!(args.target, "/dev/sdc");
+        assert_eq!(args.targets, ["/dev/sdc"]);
         assert!(!args.verify);
         assert!(args.skip_confirm);
         assert_eq!(args.block_size, "1M");
-        assert!(args.checksum.is_none());
+        assert!(args.checksums.is_empty());
         assert!(!args.force);
         assert!(args.no_unmount);
         assert!(args.silent);
@@ -2064,31 +4285,159 @@ mod tests {
     fn test_write_args_all_flags_enabled() {
         let args = WriteArgs {
             source: "image.iso".to_string(),
-            target: "/dev/sdd".to_string(),
+            targets: vec!["/dev/sdd".to_string()],
+            target_selector: None,
+            entry: None,
+            input_offset: 0,
+            preserve_tail: 0,
             verify: true,
+            verify_mode: VerifyMode::Checksum,
+            verify_sample: None,
+            no_verify_cache_drop: false,
             skip_confirm: true,
+            confirm_device: None,
             block_size: "8M".to_string(),
-            checksum: Some("deadbeef".to_string()),
+            checksums: vec!["md5:deadbeef".to_string()],
             checksum_algo: "md5".to_string(),
             force: true,
+            require_blank: true,
             no_unmount: true,
-            cancel_flag: Arc::new(AtomicBool::new(true)),
+            no_direct_io: false,
+            small_head: false,
+            verify_each_block: false,
+            eject: true,
+            check_bootable: true,
+            partition_after: None,
+            cancel_flag: CancelToken::new(),
             silent: true,
             resume: true,
             checkpoint: true,
+            checkpoint_interval_secs: 30,
             auto_checksum: true,
             show_partitions: true,
+            show_destruction: false,
+            json: false,
+            json_compat: false,
+            progress: ReporterKind::Null,
+            watch: false,
+            history_log: None,
+            cache_dir: None,
+            keep_cache: false,
+            identify: false,
+            verify_source_only: false,
+            glob: false,
         };
 
         assert!(args.verify);
         assert!(args.skip_confirm);
         assert!(args.force);
         assert!(args.no_unmount);
-        assert!(args.cancel_flag.load(Ordering::Relaxed));
+        assert!(!args.cancel_flag.is_cancelled());
         assert!(args.silent);
         assert!(args.resume);
         assert!(args.checkpoint);
         assert!(args.auto_checksum);
         assert!(args.show_partitions);
+        assert!(args.eject);
+        assert_eq!(args.verify_mode, VerifyMode::Checksum);
+    }
+
+    // -------------------------------------------------------------------------
+    // VerifyMode tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_verify_mode_default_is_bytes() {
+        assert_eq!(VerifyMode::default(), VerifyMode::Bytes);
+    }
+
+    #[test]
+    fn test_verify_mode_from_str() {
+        assert_eq!("bytes".parse::<VerifyMode>().unwrap(), VerifyMode::Bytes);
+        assert_eq!("BYTES".parse::<VerifyMode>().unwrap(), VerifyMode::Bytes);
+        assert_eq!(
+            "checksum".parse::<VerifyMode>().unwrap(),
+            VerifyMode::Checksum
+        );
+        assert_eq!("hash".parse::<VerifyMode>().unwrap(), VerifyMode::Checksum);
+    }
+
+    #[test]
+    fn test_bytes_mode_compares_after_write() {
+        // A local source is byte-compared, not hashed during the write
+        assert!(!uses_parallel_verify(VerifyMode::Bytes, None, true, 0));
+        assert!(uses_parallel_verify(VerifyMode::Checksum, None, true, 0));
+    }
+
+    #[test]
+    fn test_parallel_verify_when_bytes_are_unavailable() {
+        // Remote or compressed sources hash in parallel in either mode
+        assert!(uses_parallel_verify(VerifyMode::Bytes, None, false, 0));
+        assert!(!uses_parallel_verify(
+            VerifyMode::Checksum,
+            Some(0.1),
+            true,
+            0
+        ));
+        assert!(!uses_parallel_verify(
+            VerifyMode::Checksum,
+            None,
+            false,
+            4096
+        ));
+    }
+
+    #[test]
+    fn test_verify_mode_from_str_invalid() {
+        let err = "compare".parse::<VerifyMode>().unwrap_err();
+        assert!(err.to_string().contains("Unknown verify mode"));
+    }
+
+    #[test]
+    fn test_parse_sample_percent() {
+        assert_eq!(parse_sample_percent("10%").unwrap(), 0.1);
+        assert_eq!(parse_sample_percent("25").unwrap(), 0.25);
+        assert_eq!(parse_sample_percent(" 100 % ").unwrap(), 1.0);
+        assert!(parse_sample_percent("0").is_err());
+        assert!(parse_sample_percent("150%").is_err());
+        assert!(parse_sample_percent("half").is_err());
+    }
+
+    // -------------------------------------------------------------------------
+    // Boot sector check tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_classify_boot_sector_bootable() {
+        let mut header = vec![0u8; DISK_IMAGE_PROBE_SIZE];
+        header[510] = 0x55;
+        header[511] = 0xAA;
+        assert_eq!(classify_boot_sector(&header), BootSector::Bootable("MBR"));
+
+        header[0x1BE + 4] = 0xEE;
+        assert_eq!(
+            classify_boot_sector(&header),
+            BootSector::Bootable("GPT protective MBR")
+        );
+    }
+
+    #[test]
+    fn test_classify_boot_sector_filesystem_image() {
+        // A non-hybrid ISO has no boot signature but is still a valid image
+        let mut header = vec![0u8; DISK_IMAGE_PROBE_SIZE];
+        header[0x8001..0x8006].copy_from_slice(b"CD001");
+        assert_eq!(
+            classify_boot_sector(&header),
+            BootSector::UnbootableImage("ISO 9660")
+        );
+    }
+
+    #[test]
+    fn test_classify_boot_sector_missing() {
+        assert_eq!(
+            classify_boot_sector(&vec![0u8; DISK_IMAGE_PROBE_SIZE]),
+            BootSector::Missing
+        );
+        assert_eq!(classify_boot_sector(&[]), BootSector::Missing);
     }
 }
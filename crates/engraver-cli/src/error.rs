@@ -0,0 +1,369 @@
+//! Exit codes and machine-readable error reporting
+//!
+//! Every failure that reaches `main` is mapped to a stable [`ExitCode`] so
+//! scripts can tell a bad write from a missing device or a declined prompt.
+//! The code is derived from the first typed error found in the error chain
+//! ([`CliError`], `engraver_core::Error`, `DetectError` or `PlatformError`);
+//! anything else exits with [`ExitCode::Failure`].
+
+use engraver_detect::DetectError;
+use engraver_platform::PlatformError;
+
+/// Process exit codes returned by `engraver`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    /// Command completed successfully
+    Success = 0,
+    /// Unclassified failure
+    Failure = 1,
+    /// Written or source data did not match the expected contents
+    VerificationFailed = 2,
+    /// The source file or target device could not be found
+    DeviceNotFound = 3,
+    /// Missing privileges or access to a device or file was denied
+    PermissionDenied = 4,
+    /// The user declined a confirmation prompt
+    Aborted = 5,
+    /// The operation was cancelled (Ctrl+C)
+    Cancelled = 6,
+    /// The operation exceeded its `--timeout`
+    TimedOut = 7,
+}
+
+impl ExitCode {
+    /// All exit codes, in numeric order
+    pub const ALL: [ExitCode; 8] = [
+        ExitCode::Success,
+        ExitCode::Failure,
+        ExitCode::VerificationFailed,
+        ExitCode::DeviceNotFound,
+        ExitCode::PermissionDenied,
+        ExitCode::Aborted,
+        ExitCode::Cancelled,
+        ExitCode::TimedOut,
+    ];
+
+    /// Numeric process exit status
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// Stable identifier used for the `kind` field of JSON errors
+    pub fn kind(self) -> &'static str {
+        match self {
+            ExitCode::Success => "success",
+            ExitCode::Failure => "error",
+            ExitCode::VerificationFailed => "verification_failed",
+            ExitCode::DeviceNotFound => "not_found",
+            ExitCode::PermissionDenied => "permission_denied",
+            ExitCode::Aborted => "aborted",
+            ExitCode::Cancelled => "cancelled",
+            ExitCode::TimedOut => "timeout",
+        }
+    }
+
+    /// One-line description for documentation
+    pub fn description(self) -> &'static str {
+        match self {
+            ExitCode::Success => "Success",
+            ExitCode::Failure => "General error",
+            ExitCode::VerificationFailed => "Verification or checksum mismatch",
+            ExitCode::DeviceNotFound => "Source or target device not found",
+            ExitCode::PermissionDenied => "Insufficient privileges or permission denied",
+            ExitCode::Aborted => "Aborted by user at a confirmation prompt",
+            ExitCode::Cancelled => "Operation cancelled (Ctrl+C)",
+            ExitCode::TimedOut => "Operation exceeded --timeout",
+        }
+    }
+
+    /// Derive the exit code from an error and its chain of causes
+    pub fn from_error(err: &anyhow::Error) -> Self {
+        err.chain().find_map(classify).unwrap_or(ExitCode::Failure)
+    }
+}
+
+/// Map a single error in the chain to an exit code, if it is a known type
+fn classify(err: &(dyn std::error::Error + 'static)) -> Option<ExitCode> {
+    if let Some(e) = err.downcast_ref::<CliError>() {
+        return Some(match e {
+            CliError::VerificationFailed(_) => ExitCode::VerificationFailed,
+            CliError::DeviceNotFound(_) => ExitCode::DeviceNotFound,
+            CliError::PrivilegesRequired(_) => ExitCode::PermissionDenied,
+            CliError::Aborted | CliError::DeviceMismatch(_) | CliError::NoTty => ExitCode::Aborted,
+            CliError::Cancelled => ExitCode::Cancelled,
+            CliError::TimedOut(_) => ExitCode::TimedOut,
+        });
+    }
+
+    if let Some(e) = err.downcast_ref::<engraver_core::Error>() {
+        use engraver_core::{CancelReason, Error, ErrorKind};
+        return match e.kind() {
+            ErrorKind::Checksum => Some(ExitCode::VerificationFailed),
+            ErrorKind::NotFound => Some(ExitCode::DeviceNotFound),
+            ErrorKind::Permission => Some(ExitCode::PermissionDenied),
+            ErrorKind::Cancelled if matches!(e, Error::Cancelled(CancelReason::Timeout)) => {
+                Some(ExitCode::TimedOut)
+            }
+            ErrorKind::Cancelled => Some(ExitCode::Cancelled),
+            _ => None,
+        };
+    }
+
+    if let Some(e) = err.downcast_ref::<DetectError>() {
+        return match e {
+            DetectError::PermissionDenied(_) => Some(ExitCode::PermissionDenied),
+            DetectError::DriveNotFound(_) => Some(ExitCode::DeviceNotFound),
+            DetectError::Io(io) => classify_io(io),
+            _ => None,
+        };
+    }
+
+    if let Some(e) = err.downcast_ref::<PlatformError>() {
+        return match e {
+            PlatformError::PermissionDenied(_) => Some(ExitCode::PermissionDenied),
+            PlatformError::DeviceNotFound(_) => Some(ExitCode::DeviceNotFound),
+            PlatformError::Io(io) => classify_io(io),
+            _ => None,
+        };
+    }
+
+    err.downcast_ref::<std::io::Error>().and_then(classify_io)
+}
+
+fn classify_io(err: &std::io::Error) -> Option<ExitCode> {
+    match err.kind() {
+        std::io::ErrorKind::NotFound => Some(ExitCode::DeviceNotFound),
+        std::io::ErrorKind::PermissionDenied => Some(ExitCode::PermissionDenied),
+        _ => None,
+    }
+}
+
+/// Errors raised by the CLI itself that carry a specific exit code
+///
+/// The message is shown as-is, so existing wording is preserved.
+#[derive(Debug, thiserror::Error)]
+pub enum CliError {
+    /// Verification of written or source data failed
+    #[error("{0}")]
+    VerificationFailed(String),
+
+    /// Target device could not be found
+    #[error("{0}")]
+    DeviceNotFound(String),
+
+    /// The command needs elevated privileges
+    #[error("{0}")]
+    PrivilegesRequired(String),
+
+    /// The user declined a confirmation prompt
+    #[error("Aborted by user")]
+    Aborted,
+
+    /// `--confirm-device` did not match the target device
+    #[error("{0}")]
+    DeviceMismatch(String),
+
+    /// The user cancelled a running operation
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    /// The operation was stopped by `--timeout`
+    #[error("{0}")]
+    TimedOut(String),
+
+    /// A confirmation was needed but stdin is not a terminal
+    #[error("no TTY for confirmation; pass --yes or set ENGRAVER_ASSUME_YES=1")]
+    NoTty,
+}
+
+impl CliError {
+    /// Privilege error with a platform-appropriate hint for `command`
+    pub fn privileges_required(command: &str) -> Self {
+        #[cfg(unix)]
+        let message = format!(
+            "Root privileges required.\n\
+             Try running with: sudo engraver {} ...",
+            command
+        );
+
+        #[cfg(windows)]
+        let message = {
+            let _ = command;
+            "Administrator privileges required.\n\
+             Right-click and select 'Run as administrator'."
+                .to_string()
+        };
+
+        #[cfg(not(any(unix, windows)))]
+        let message = {
+            let _ = command;
+            "Elevated privileges required for raw device access.".to_string()
+        };
+
+        CliError::PrivilegesRequired(message)
+    }
+}
+
+/// Build the `{ "schema_version", "error": { "kind", "code", "message" } }` JSON report
+///
+/// With `compat` (`--json-compat-0`) the `schema_version` field is omitted.
+pub fn json_report(err: &anyhow::Error, compat: bool) -> serde_json::Value {
+    let code = ExitCode::from_error(err);
+    let mut report = serde_json::json!({
+        "error": {
+            "kind": code.kind(),
+            "code": code.code(),
+            "message": format!("{:#}", err),
+        }
+    });
+    if !compat {
+        report["schema_version"] = crate::json::SCHEMA_VERSION.into();
+    }
+    report
+}
+
+/// Render the EXIT STATUS section for man pages
+pub fn render_exit_status_section(w: &mut dyn std::io::Write) -> std::io::Result<()> {
+    use clap_mangen::roff::{roman, Roff};
+
+    let mut roff = Roff::default();
+    roff.control("SH", ["EXIT STATUS"]);
+    for code in ExitCode::ALL {
+        roff.control("TP", []);
+        roff.text([roman(code.code().to_string())]);
+        roff.text([roman(code.description())]);
+    }
+    roff.text([roman(
+        "With --json, failures are reported on stderr as { \"error\": { \"kind\": ..., \"message\": ... } }.",
+    )]);
+    roff.to_writer(w)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // -------------------------------------------------------------------------
+    // ExitCode tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_exit_code_values() {
+        let codes: Vec<i32> = ExitCode::ALL.iter().map(|c| c.code()).collect();
+        assert_eq!(codes, vec![0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_exit_code_from_cli_error() {
+        let cases = [
+            (
+                CliError::VerificationFailed("bad".into()),
+                ExitCode::VerificationFailed,
+            ),
+            (
+                CliError::DeviceNotFound("gone".into()),
+                ExitCode::DeviceNotFound,
+            ),
+            (
+                CliError::privileges_required("write"),
+                ExitCode::PermissionDenied,
+            ),
+            (CliError::Aborted, ExitCode::Aborted),
+            (CliError::DeviceMismatch("wrong".into()), ExitCode::Aborted),
+            (CliError::Cancelled, ExitCode::Cancelled),
+            (CliError::TimedOut("late".into()), ExitCode::TimedOut),
+        ];
+        for (err, expected) in cases {
+            assert_eq!(ExitCode::from_error(&err.into()), expected);
+        }
+    }
+
+    #[test]
+    fn test_exit_code_from_core_error() {
+        let err = anyhow::Error::new(engraver_core::Error::Cancelled(
+            engraver_core::CancelReason::UserRequested,
+        ));
+        assert_eq!(ExitCode::from_error(&err), ExitCode::Cancelled);
+
+        let err = anyhow::Error::new(engraver_core::Error::Cancelled(
+            engraver_core::CancelReason::Timeout,
+        ));
+        assert_eq!(ExitCode::from_error(&err), ExitCode::TimedOut);
+
+        let err = anyhow::Error::new(engraver_core::Error::DeviceNotFound("/dev/sdz".into()));
+        assert_eq!(ExitCode::from_error(&err), ExitCode::DeviceNotFound);
+
+        let err = anyhow::Error::new(engraver_core::Error::InvalidConfig("bad".into()));
+        assert_eq!(ExitCode::from_error(&err), ExitCode::Failure);
+    }
+
+    #[test]
+    fn test_exit_code_from_platform_and_detect_errors() {
+        let err = anyhow::Error::new(PlatformError::PermissionDenied("/dev/sdb".into()));
+        assert_eq!(ExitCode::from_error(&err), ExitCode::PermissionDenied);
+
+        let err = anyhow::Error::new(PlatformError::DeviceNotFound("/dev/sdb".into()));
+        assert_eq!(ExitCode::from_error(&err), ExitCode::DeviceNotFound);
+
+        let err = anyhow::Error::new(DetectError::PermissionDenied("sysfs".into()));
+        assert_eq!(ExitCode::from_error(&err), ExitCode::PermissionDenied);
+
+        let err = anyhow::Error::new(DetectError::DriveNotFound("No drive with serial".into()));
+        assert_eq!(ExitCode::from_error(&err), ExitCode::DeviceNotFound);
+    }
+
+    #[test]
+    fn test_exit_code_looks_through_context() {
+        let err = anyhow::Error::new(PlatformError::DeviceNotFound("/dev/sdb".into()))
+            .context("Failed to open device");
+        assert_eq!(ExitCode::from_error(&err), ExitCode::DeviceNotFound);
+    }
+
+    #[test]
+    fn test_exit_code_untyped_error_is_failure() {
+        let err = anyhow::anyhow!("something went wrong");
+        assert_eq!(ExitCode::from_error(&err), ExitCode::Failure);
+    }
+
+    // -------------------------------------------------------------------------
+    // JSON report tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_json_report_shape() {
+        let err = anyhow::Error::new(CliError::Aborted);
+        let report = json_report(&err, false);
+        assert_eq!(report["schema_version"], crate::json::SCHEMA_VERSION);
+        assert_eq!(report["error"]["kind"], "aborted");
+        assert_eq!(report["error"]["code"], 5);
+        assert_eq!(report["error"]["message"], "Aborted by user");
+    }
+
+    #[test]
+    fn test_json_report_compat_omits_schema_version() {
+        let report = json_report(&anyhow::Error::new(CliError::Aborted), true);
+        assert!(report.get("schema_version").is_none());
+        assert_eq!(report["error"]["kind"], "aborted");
+    }
+
+    #[test]
+    fn test_json_report_includes_causes() {
+        let err = anyhow::Error::new(PlatformError::DeviceNotFound("/dev/sdb".into()))
+            .context("Failed to open device");
+        let message = json_report(&err, false)["error"]["message"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert!(message.starts_with("Failed to open device"));
+        assert!(message.contains("/dev/sdb"));
+    }
+
+    #[test]
+    fn test_render_exit_status_section() {
+        let mut out = Vec::new();
+        render_exit_status_section(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("EXIT STATUS"));
+        assert!(text.contains("Aborted by user"));
+    }
+}
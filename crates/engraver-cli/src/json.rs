@@ -0,0 +1,78 @@
+//! Versioned `--json` output
+//!
+//! Every `--json` document is wrapped as `{ "schema_version": N, "<key>": ... }`
+//! so consumers can detect format changes. `--json-compat-0` emits the bare
+//! payload that was printed before versioning was introduced.
+
+use anyhow::Result;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+/// Version of the JSON output format
+///
+/// Bump this whenever the shape of any `--json` payload changes, including
+/// the drive serialization used by `list --json` and new enum values such
+/// as a `drive_type`.
+pub const SCHEMA_VERSION: u32 = 6;
+
+/// A payload wrapped with [`SCHEMA_VERSION`] under `key`
+pub struct Versioned<'a, T: ?Sized> {
+    key: &'static str,
+    payload: &'a T,
+}
+
+impl<'a, T: ?Sized> Versioned<'a, T> {
+    /// Wrap `payload` so it serializes as `{ "schema_version", key: payload }`
+    pub fn new(key: &'static str, payload: &'a T) -> Self {
+        Self { key, payload }
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for Versioned<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("schema_version", &SCHEMA_VERSION)?;
+        map.serialize_entry(self.key, self.payload)?;
+        map.end()
+    }
+}
+
+/// Render `payload` as pretty JSON, versioned unless `compat` is set
+pub fn render<T: Serialize + ?Sized>(
+    key: &'static str,
+    payload: &T,
+    compat: bool,
+) -> Result<String> {
+    let output = if compat {
+        serde_json::to_string_pretty(payload)?
+    } else {
+        serde_json::to_string_pretty(&Versioned::new(key, payload))?
+    };
+    Ok(output)
+}
+
+/// Print `payload` to stdout, versioned unless `compat` is set
+pub fn print<T: Serialize + ?Sized>(key: &'static str, payload: &T, compat: bool) -> Result<()> {
+    println!("{}", render(key, payload, compat)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_versioned() {
+        let output = render("drives", &["a", "b"], false).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+        assert_eq!(value["drives"], serde_json::json!(["a", "b"]));
+        assert!(output.find("schema_version") < output.find("drives"));
+    }
+
+    #[test]
+    fn test_render_compat_is_bare_payload() {
+        let output = render("drives", &["a", "b"], true).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value, serde_json::json!(["a", "b"]));
+    }
+}
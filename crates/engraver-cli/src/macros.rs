@@ -7,6 +7,15 @@ macro_rules! print_if {
     };
 }
 
+/// Conditionally eprintln based on silent mode, for commands whose stdout is data
+macro_rules! eprintln_if {
+    ($silent:expr, $($arg:tt)*) => {
+        if !$silent {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
 /// Conditionally println based on silent mode
 macro_rules! println_if {
     ($silent:expr) => {
@@ -14,6 +14,9 @@
 //!
 //! # Write from URL
 //! engraver write https://releases.ubuntu.com/24.04/ubuntu.iso /dev/sdb
+//!
+//! # Show recent writes
+//! engraver history
 //! ```
 
 use anyhow::Result;
@@ -21,19 +24,30 @@ use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::{generate, Shell};
 use clap_mangen::Man;
 use console::style;
-use engraver_core::Settings;
+use engraver_core::{
+    format_duration, BenchmarkLog, CancelReason, CancelToken, HistoryLog, Settings,
+};
+use engraver_detect::TargetSelector;
+use engraver_platform::DataFilesystem;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 use tracing_subscriber::EnvFilter;
 
 #[macro_use]
 mod macros;
 mod commands;
+mod error;
+mod json;
 mod progress;
+mod prompt;
+mod timeout;
+
+use error::{CliError, ExitCode};
 
 /// Engraver - A safe, fast tool for creating bootable USB drives
 #[derive(Parser)]
 #[command(name = "engraver")]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, long_version = long_version(), about, long_about = None)]
 #[command(propagate_version = true)]
 struct Cli {
     /// Enable verbose output
@@ -52,48 +66,146 @@ struct Cli {
     #[arg(long, global = true, value_name = "PATH")]
     config_file: Option<PathBuf>,
 
+    /// Apply the named [profiles.NAME] settings from the configuration file
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Cancel the command if it runs longer than this (e.g. 90s, 30m, 1h)
+    #[arg(long, global = true, value_name = "DURATION", value_parser = timeout::parse_duration)]
+    timeout: Option<std::time::Duration>,
+
+    /// Report failures as JSON on stderr (implied by a subcommand's --json); write, verify and checksum progress becomes JSON lines
+    #[arg(long)]
+    json: bool,
+
+    /// Emit JSON without the schema_version wrapper (deprecated; removed next release)
+    #[arg(long = "json-compat-0", global = true)]
+    json_compat: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
 
+impl Cli {
+    /// Whether errors should be reported as JSON
+    fn json_errors(&self) -> bool {
+        self.json
+            || matches!(
+                self.command,
+                Commands::List { json: true, .. }
+                    | Commands::Config { json: true, .. }
+                    | Commands::History { json: true, .. }
+                    | Commands::Formats { json: true, .. }
+                    | Commands::Benchmark { json: true, .. }
+                    | Commands::Write { json: true, .. }
+                    | Commands::Verify { json: true, .. }
+            )
+    }
+}
+
 #[derive(Subcommand)]
+// Parsed once per run, so the size of `Write` does not matter
+#[allow(clippy::large_enum_variant)]
 enum Commands {
     /// List available drives
     List {
-        /// Show all drives including system drives
+        /// Show all drives including system drives and empty card readers
         #[arg(short, long)]
         all: bool,
 
         /// Output in JSON format
         #[arg(long)]
         json: bool,
+
+        /// Flash the activity LED of the drive at PATH instead of listing
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["all", "json"])]
+        identify: Option<String>,
     },
 
     /// Write an image to a drive
     Write {
-        /// Source image (local file or URL)
-        source: String,
-
-        /// Target device (e.g., /dev/sdb, /dev/disk2, \\.\PhysicalDrive1)
-        target: String,
+        /// Source image (local file, URL, or first part of a split image, e.g. image.img.001)
+        #[arg(required_unless_present = "parts")]
+        source: Option<String>,
+
+        /// Target device(s) (e.g., /dev/sdb, /dev/disk2, \\.\PhysicalDrive1); several are written in parallel
+        #[arg(
+            value_name = "TARGET",
+            required_unless_present_any = ["watch", "parts", "by_serial", "by_label", "verify_source_only"]
+        )]
+        targets: Vec<String>,
+
+        /// Select the target drive by its exact serial number instead of TARGET
+        #[arg(long, value_name = "SERIAL", conflicts_with_all = ["targets", "watch", "by_label"])]
+        by_serial: Option<String>,
+
+        /// Select the target drive by the label of any of its partitions instead of TARGET
+        #[arg(long, value_name = "LABEL", conflicts_with_all = ["targets", "watch"])]
+        by_label: Option<String>,
+
+        /// Read a split image from the numbered parts matching GLOB (e.g. 'image.img.*') instead of SOURCE
+        #[arg(long, value_name = "GLOB", conflicts_with = "entry")]
+        parts: Option<String>,
+
+        /// Wait for removable drives to be inserted and write the image to each one
+        #[arg(long, conflicts_with_all = ["targets", "resume", "confirm_device"])]
+        watch: bool,
+
+        /// File to extract from a .tar/.tar.gz source (glob, e.g. '*.img')
+        #[arg(long, value_name = "GLOB")]
+        entry: Option<String>,
+
+        /// Skip this many bytes at the start of the source, e.g. a signed header (512, 4K, 1M)
+        #[arg(
+            long,
+            value_name = "SIZE",
+            value_parser = engraver_core::parse_size,
+            conflicts_with_all = ["resume", "checkpoint", "verify_sample"]
+        )]
+        input_offset: Option<u64>,
+
+        /// Never write the last SIZE bytes of the target, e.g. a data partition kept there (1G, 512M)
+        #[arg(
+            long,
+            value_name = "SIZE",
+            value_parser = engraver_core::parse_size,
+            conflicts_with = "partition_after"
+        )]
+        preserve_tail: Option<u64>,
 
         /// Verify write by reading back and comparing (can be set in config)
         #[arg(long)]
         verify: bool,
 
+        /// How to verify: bytes (byte-by-byte compare) or checksum (hash both sides)
+        #[arg(long, value_name = "MODE", default_value = "bytes")]
+        verify_mode: String,
+
+        /// Verify only a reproducible sample of blocks, e.g. 10% (implies --verify; local images only)
+        #[arg(long, value_name = "PERCENT", value_parser = commands::write::parse_sample_percent)]
+        verify_sample: Option<f64>,
+
+        /// Read back through the OS page cache instead of dropping it before verifying
+        #[arg(long)]
+        no_verify_cache_drop: bool,
+
         /// Skip confirmation prompt (use with caution!)
         #[arg(short = 'y', long)]
         yes: bool,
 
+        /// Skip the prompt only if NAME exactly matches the target's name or path
+        #[arg(long, value_name = "NAME")]
+        confirm_device: Option<String>,
+
         /// Block size for writing (e.g., 4M, 1M, 512K). Default from config or 4M
         #[arg(short, long)]
         block_size: Option<String>,
 
-        /// Verify checksum against expected value
+        /// Verify the source against an expected checksum before writing; repeatable, as ALGO:HEX or bare HEX
         #[arg(long, value_name = "CHECKSUM")]
-        checksum: Option<String>,
+        checksum: Vec<String>,
 
-        /// Checksum algorithm (sha256, sha512, md5). Default from config or sha256
+        /// Checksum algorithm for a bare --checksum HEX (sha256, sha512, sha1, md5, sha3-256). Default from config or sha256
         #[arg(long)]
         checksum_algo: Option<String>,
 
@@ -101,10 +213,38 @@ enum Commands {
         #[arg(long, hide = true)]
         force: bool,
 
+        /// Abort if the target already contains data (sampled blocks not all 0x00/0xFF); --force overrides
+        #[arg(long)]
+        require_blank: bool,
+
         /// Do not unmount partitions before writing
         #[arg(long)]
         no_unmount: bool,
 
+        /// Use buffered I/O instead of direct I/O (for devices that reject O_DIRECT)
+        #[arg(long)]
+        no_direct_io: bool,
+
+        /// Write the first 1 MiB in 512-byte blocks, for hybrid ISOs that won't boot on old BIOSes
+        #[arg(long)]
+        small_head: bool,
+
+        /// Read each block back right after writing it and rewrite on mismatch (slow, pinpoints bad sectors)
+        #[arg(long)]
+        verify_each_block: bool,
+
+        /// Eject (or power down) the drive after a successful write
+        #[arg(long)]
+        eject: bool,
+
+        /// After writing, read back the boot sector and warn if the drive looks unbootable
+        #[arg(long)]
+        check_bootable: bool,
+
+        /// After writing, turn the free space past the image into a partition (fat32 or exfat)
+        #[arg(long, value_name = "FS")]
+        partition_after: Option<String>,
+
         /// Resume an interrupted write operation
         #[arg(long)]
         resume: bool,
@@ -120,19 +260,92 @@ enum Commands {
         /// Show partition layout of source image before writing
         #[arg(long, short = 'p')]
         show_partitions: bool,
+
+        /// List the target partitions that will be destroyed before confirming
+        #[arg(long)]
+        show_destruction: bool,
+
+        /// Print the --show-destruction summary in JSON format
+        #[arg(long, requires = "show_destruction")]
+        json: bool,
+
+        /// Download a URL source into this directory first, resuming a partial download
+        #[arg(long, value_name = "DIR")]
+        cache_dir: Option<PathBuf>,
+
+        /// Keep the downloaded image in --cache-dir after a successful write
+        #[arg(long, requires = "cache_dir")]
+        keep_cache: bool,
+
+        /// Flash the target drive's activity LED before asking for confirmation
+        #[arg(long)]
+        identify: bool,
+
+        /// Check the source checksum (--checksum or a checksum file) and exit without opening any device
+        #[arg(
+            long,
+            alias = "source-checksum-only",
+            conflicts_with_all = ["watch", "resume", "by_serial", "by_label"]
+        )]
+        verify_source_only: bool,
+
+        /// Treat each TARGET as a glob over drive paths (e.g. '/dev/sd[b-f]') and write every
+        /// matching safe drive; each is confirmed unless --yes is given
+        #[arg(
+            long,
+            conflicts_with_all = ["watch", "by_serial", "by_label", "confirm_device", "verify_source_only"]
+        )]
+        glob: bool,
+
+        /// Progress display: bar, or lines (a plain status line every few seconds, for logs)
+        #[arg(long, value_name = "FORMAT", default_value = "bar")]
+        progress_format: String,
     },
 
     /// Verify a drive against a source image
     Verify {
-        /// Source image (local file or URL)
-        source: String,
+        /// Source image (local file or URL); omit when using --checksum or --sums
+        #[arg(required_unless_present_any = ["checksum", "sums"])]
+        source: Option<String>,
 
         /// Target device to verify
-        target: String,
+        target: Option<String>,
 
         /// Block size for reading
         #[arg(short, long, default_value = "4M")]
         block_size: String,
+
+        /// Verify the device against this known hash instead of a source image
+        #[arg(long, value_name = "HASH", requires = "size")]
+        checksum: Option<String>,
+
+        /// Checksum algorithm for --checksum (sha256, sha512, sha1, md5, crc32, sha3-256). Default: from hash length
+        #[arg(long, requires = "checksum")]
+        algorithm: Option<String>,
+
+        /// Verify the device against an entry in this checksum file (e.g. SHA256SUMS)
+        #[arg(long, value_name = "FILE", requires_all = ["name", "size"], conflicts_with = "checksum")]
+        sums: Option<PathBuf>,
+
+        /// Filename to look up in the --sums file (e.g. ubuntu-24.04-desktop-amd64.iso)
+        #[arg(long, value_name = "FILENAME", requires = "sums")]
+        name: Option<String>,
+
+        /// Number of bytes to read from the device for --checksum or --sums (e.g. 4G, 1234567)
+        #[arg(long)]
+        size: Option<String>,
+
+        /// Continue a cancelled verification from where it stopped (local images only)
+        #[arg(long, conflicts_with_all = ["checksum", "sums"])]
+        resume: bool,
+
+        /// Read through the OS page cache instead of dropping it before verifying
+        #[arg(long)]
+        no_verify_cache_drop: bool,
+
+        /// Print the result as JSON (printed even when verification fails)
+        #[arg(long)]
+        json: bool,
     },
 
     /// Calculate checksum of an image
@@ -140,9 +353,30 @@ enum Commands {
         /// Source image (local file or URL)
         source: String,
 
-        /// Checksum algorithm (sha256, sha512, md5, crc32). Default from config or sha256
+        /// Checksum algorithm (sha256, sha512, sha1, md5, crc32, sha3-256). Default from config or sha256
         #[arg(short, long)]
         algorithm: Option<String>,
+
+        /// Check the source against its entry in a sums file (e.g. SHA256SUMS)
+        #[arg(long, value_name = "FILE")]
+        compare_with: Option<PathBuf>,
+
+        /// Print only a checksum file line: gnu (`HEX  NAME`), bsd (`SHA256 (NAME) = HEX`) or plain
+        #[arg(long, value_name = "FORMAT", conflicts_with = "compare_with")]
+        format: Option<String>,
+    },
+
+    /// Decompress or download an image and write its raw bytes to stdout
+    ///
+    /// Progress and the final total go to stderr, so the output can be piped
+    /// into another tool: `engraver extract image.img.xz | dd of=/dev/sdb`.
+    Extract {
+        /// Source image (local file, URL or cloud URI)
+        source: String,
+
+        /// Entry to extract from a tar archive (glob matched against the path or file name)
+        #[arg(long, value_name = "PATTERN")]
+        entry: Option<String>,
     },
 
     /// Generate shell completions
@@ -169,11 +403,53 @@ enum Commands {
         #[arg(long)]
         path: bool,
 
+        /// List the profiles defined in the configuration file
+        #[arg(long, conflicts_with_all = ["init", "path"])]
+        list_profiles: bool,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+
+        #[command(subcommand)]
+        action: Option<ConfigCommand>,
+    },
+
+    /// Show the source types, compression formats and checksums this build supports
+    Formats {
         /// Output in JSON format
         #[arg(long)]
         json: bool,
     },
 
+    /// Show recently completed writes
+    History {
+        /// Maximum number of entries to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+
+        /// Redact URLs (hosts, credentials, query strings) in the output
+        #[arg(long)]
+        no_urls: bool,
+    },
+
+    /// List or clear checkpoints of interrupted writes
+    ///
+    /// Resume a listed write with `engraver write <SOURCE> <TARGET> --resume`.
+    Resume {
+        /// List pending checkpoints (the default)
+        #[arg(long, conflicts_with = "clear")]
+        list: bool,
+
+        /// Delete all checkpoints, or only the one with SESSION_ID
+        #[arg(long, value_name = "SESSION_ID", num_args = 0..=1)]
+        clear: Option<Option<String>>,
+    },
+
     /// Erase a drive by zero-filling the entire device
     Erase {
         /// Target device (e.g., /dev/sdb, /dev/disk2, \\.\PhysicalDrive1)
@@ -221,23 +497,61 @@ enum Commands {
         #[arg(long)]
         test_block_sizes: Option<String>,
 
+        /// Measure read speed instead (non-destructive, no confirmation needed)
+        #[arg(long, conflicts_with_all = ["pattern", "passes", "test_block_sizes"])]
+        read: bool,
+
         /// Output in JSON format
         #[arg(long)]
         json: bool,
 
+        /// Show the previous run on this drive (matched by serial number) alongside the result
+        #[arg(long)]
+        compare: bool,
+
         /// Skip confirmation prompt (DANGEROUS!)
         #[arg(short = 'y', long)]
         yes: bool,
     },
 }
 
+/// Subcommands of `engraver config`
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Print a setting, e.g. `engraver config get write.block_size`
+    Get {
+        /// Dotted setting key (section.field)
+        key: String,
+    },
+
+    /// Change a setting in the configuration file
+    Set {
+        /// Dotted setting key (section.field)
+        key: String,
+
+        /// New value
+        value: String,
+    },
+}
+
 fn main() {
     // Set up panic handler for nicer error messages
     std::panic::set_hook(Box::new(|panic_info| {
         eprintln!("{} {}", style("Error:").red().bold(), panic_info);
     }));
 
-    if let Err(e) = run() {
+    let cli = Cli::parse();
+    let json_errors = cli.json_errors();
+    let json_compat = cli.json_compat;
+
+    if let Err(e) = run(cli) {
+        let exit_code = ExitCode::from_error(&e);
+
+        if json_errors {
+            eprintln!("{}", error::json_report(&e, json_compat));
+            std::process::exit(exit_code.code());
+        }
+
         eprintln!("{} {}", style("Error:").red().bold(), e);
 
         // Show cause chain in verbose mode
@@ -249,19 +563,116 @@ fn main() {
             }
         }
 
-        std::process::exit(1);
+        std::process::exit(exit_code.code());
     }
 }
 
-fn run() -> Result<()> {
-    let cli = Cli::parse();
+/// `--version` text: the version and what this build was compiled with
+///
+/// `-V` prints the version alone. A prebuilt binary may leave out optional
+/// features, so bug reports should include this.
+fn long_version() -> &'static str {
+    static TEXT: OnceLock<String> = OnceLock::new();
+    TEXT.get_or_init(|| {
+        let core = engraver_core::build_features();
+        let mut features = core.enabled();
+        if cfg!(feature = "io-uring") {
+            features.push("io-uring");
+        }
+        let sources: Vec<String> = core
+            .source_types()
+            .iter()
+            .map(|source_type| format!("{:?}", source_type))
+            .collect();
+        let checksums: Vec<&str> = core
+            .checksum_algorithms()
+            .iter()
+            .map(|algorithm| algorithm.name())
+            .collect();
+        let list = |items: String| {
+            if items.is_empty() {
+                "none".to_string()
+            } else {
+                items
+            }
+        };
+
+        format!(
+            "{}\nengraver-core {}\nfeatures: {}\nsources: {}\nchecksums: {}",
+            env!("CARGO_PKG_VERSION"),
+            core.version,
+            list(features.join(", ")),
+            list(sources.join(", ")),
+            list(checksums.join(", "))
+        )
+    })
+}
+
+/// Locate the write history log
+///
+/// A custom `--config-file` keeps its history alongside it; otherwise the
+/// default config directory is used.
+fn history_log(config_file: Option<&std::path::Path>) -> Result<HistoryLog> {
+    match config_file.and_then(|p| p.parent()) {
+        Some(dir) => Ok(HistoryLog::new(
+            dir.join(engraver_core::history::HISTORY_FILE_NAME),
+        )),
+        None => Ok(HistoryLog::default_location()?),
+    }
+}
+
+/// Locate the benchmark log, alongside the write history log
+fn benchmark_log(config_file: Option<&std::path::Path>) -> Result<BenchmarkLog> {
+    match config_file.and_then(|p| p.parent()) {
+        Some(dir) => Ok(BenchmarkLog::new(
+            dir.join(engraver_core::history::BENCHMARK_FILE_NAME),
+        )),
+        None => Ok(BenchmarkLog::default_location()?),
+    }
+}
+
+/// Render a man page, adding an EXIT STATUS section before VERSION
+///
+/// Mirrors the section order of [`Man::render`].
+fn render_man_page(cmd: clap::Command, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+    let man = Man::new(cmd.clone());
+    man.render_title(w)?;
+    man.render_name_section(w)?;
+    man.render_synopsis_section(w)?;
+    man.render_description_section(w)?;
+    if cmd.get_arguments().any(|a| !a.is_hide_set()) {
+        man.render_options_section(w)?;
+    }
+    if cmd.get_subcommands().any(|s| !s.is_hide_set()) {
+        man.render_subcommands_section(w)?;
+    }
+    if cmd.get_after_long_help().is_some() || cmd.get_after_help().is_some() {
+        man.render_extra_section(w)?;
+    }
+    error::render_exit_status_section(w)?;
+    if cmd.get_version().is_some() || cmd.get_long_version().is_some() {
+        man.render_version_section(w)?;
+    }
+    if cmd.get_author().is_some() {
+        man.render_authors_section(w)?;
+    }
+    Ok(())
+}
 
+fn run(cli: Cli) -> Result<()> {
     // Load user settings from config file (custom path takes precedence)
     let settings = if let Some(ref config_path) = cli.config_file {
         Settings::load_from_path(Some(config_path.clone()))
     } else {
         Settings::load()
     };
+    // CLI flags still override the profile, which overrides the base settings
+    let settings = match settings.with_profile(cli.profile.as_deref()) {
+        Ok(resolved) => resolved,
+        // `config` resolves profiles itself, and must still run to fix a bad one
+        Err(_) if matches!(cli.command, Commands::Config { .. }) => settings,
+        Err(e) => return Err(e.into()),
+    };
 
     // Initialize logging
     // --silent implies --quiet (no logs at all, not even errors to tracing)
@@ -280,22 +691,25 @@ fn run() -> Result<()> {
         .without_time()
         .init();
 
-    // --silent implies --yes (skip confirmations)
+    // --silent implies --yes (skip confirmations), and so does ENGRAVER_ASSUME_YES
     let silent = cli.silent;
+    let assume_yes = prompt::assume_yes_from_env();
+    let json_compat = cli.json_compat;
+    let progress = progress::ReporterKind::from_flags(silent, cli.json);
 
     // Set up Ctrl+C handler (suppress messages in silent mode)
-    let running = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
-    let r = running.clone();
+    let cancel = CancelToken::new();
+    let handler_cancel = cancel.clone();
     let silent_for_handler = silent;
     ctrlc::set_handler(move || {
-        if !r.load(std::sync::atomic::Ordering::SeqCst) {
+        if handler_cancel.is_cancelled() {
             // Second Ctrl+C, force exit
             if !silent_for_handler {
                 eprintln!("\n{}", style("Forced exit").red().bold());
             }
             std::process::exit(130);
         }
-        r.store(false, std::sync::atomic::Ordering::SeqCst);
+        handler_cancel.cancel(CancelReason::UserRequested);
         if !silent_for_handler {
             eprintln!(
                 "\n{}",
@@ -304,50 +718,211 @@ fn run() -> Result<()> {
         }
     })?;
 
-    match cli.command {
-        Commands::List { all, json } => commands::list::execute(all, json, silent),
+    // The watchdog shares the Ctrl+C token, so a timeout stops every phase
+    // (including verification) and leaves a checkpoint like a manual cancel
+    if let Some(limit) = cli.timeout {
+        let silent_for_watchdog = silent;
+        timeout::spawn_watchdog(cancel.clone(), limit, move || {
+            if !silent_for_watchdog {
+                eprintln!(
+                    "\n{}",
+                    style(format!(
+                        "Timeout of {} exceeded, cancelling...",
+                        format_duration(limit.as_secs())
+                    ))
+                    .yellow()
+                );
+            }
+        });
+    }
+    let timeout_token = cancel.clone();
+
+    let result = match cli.command {
+        Commands::List {
+            identify: Some(path),
+            ..
+        } => commands::list::identify(&path, silent),
+        Commands::List { all, json, .. } => {
+            commands::list::execute(all, json, json_compat, cli.verbose, silent)
+        }
         Commands::Write {
             source,
-            target,
+            targets,
+            by_serial,
+            by_label,
+            parts,
+            entry,
+            input_offset,
+            preserve_tail,
             verify,
+            verify_mode,
+            verify_sample,
+            no_verify_cache_drop,
             yes,
+            confirm_device,
             block_size,
             checksum,
             checksum_algo,
             force,
+            require_blank,
             no_unmount,
+            no_direct_io,
+            small_head,
+            verify_each_block,
+            eject,
+            check_bootable,
+            partition_after,
             resume,
             checkpoint,
             auto_checksum,
             show_partitions,
+            show_destruction,
+            json,
+            watch,
+            cache_dir,
+            keep_cache,
+            identify,
+            verify_source_only,
+            glob,
+            progress_format,
         } => {
+            // With --parts there is no SOURCE, so the first positional is a target
+            let (source, targets) = match parts {
+                Some(glob) => {
+                    if engraver_core::detect_source_type(&glob) != engraver_core::SourceType::Split
+                    {
+                        anyhow::bail!(
+                            "--parts expects a glob or a first part ending in .001: {}",
+                            glob
+                        );
+                    }
+                    (glob, source.into_iter().chain(targets).collect::<Vec<_>>())
+                }
+                None => (source.unwrap_or_default(), targets),
+            };
+            let target_selector = by_serial
+                .map(TargetSelector::Serial)
+                .or(by_label.map(TargetSelector::Label));
+            if watch && !targets.is_empty() {
+                anyhow::bail!("--watch cannot be used with a TARGET");
+            }
+            if target_selector.is_some() && !targets.is_empty() {
+                anyhow::bail!("--by-serial and --by-label cannot be used with a TARGET");
+            }
+            if !watch && !verify_source_only && targets.is_empty() && target_selector.is_none() {
+                anyhow::bail!("A TARGET device is required");
+            }
+
             // Apply settings as defaults when CLI options are not explicitly set
             let effective_block_size =
                 block_size.unwrap_or_else(|| settings.write.block_size.clone());
             let effective_checksum_algo =
                 checksum_algo.unwrap_or_else(|| settings.checksum.algorithm.clone());
             // CLI flags || settings defaults
-            let effective_verify = verify || settings.write.verify;
-            let effective_checkpoint = checkpoint || resume || settings.write.checkpoint;
-            let effective_skip_confirm = yes || silent || settings.behavior.skip_confirmation;
+            let effective_verify = verify || verify_sample.is_some() || settings.write.verify;
+            // A checkpoint records target offsets only, so it could not resume past a header
+            let effective_checkpoint =
+                (checkpoint || resume || settings.write.checkpoint) && input_offset.is_none();
+            // A matching --confirm-device stands in for the interactive prompt;
+            // a mismatch aborts before any prompt would be shown
+            // A glob can match more drives than intended, so only an explicit
+            // --yes (or ENGRAVER_ASSUME_YES) skips it
+            let yes = yes || assume_yes;
+            let effective_skip_confirm = if glob {
+                yes
+            } else {
+                yes || silent || settings.behavior.skip_confirmation || confirm_device.is_some()
+            };
             let effective_auto_checksum = auto_checksum || settings.checksum.auto_detect;
+            let verify_mode: commands::write::VerifyMode = verify_mode.parse()?;
+            let progress = progress.with_format(progress_format.parse()?);
+            let partition_after = partition_after
+                .as_deref()
+                .map(str::parse::<DataFilesystem>)
+                .transpose()?;
+            let history_log = if settings.behavior.record_history {
+                history_log(cli.config_file.as_deref()).ok()
+            } else {
+                None
+            };
 
             commands::write::execute(commands::write::WriteArgs {
                 source,
-                target,
+                targets,
+                target_selector,
+                entry,
+                input_offset: input_offset.unwrap_or(0),
+                preserve_tail: preserve_tail.unwrap_or(0),
                 verify: effective_verify,
+                verify_mode,
+                verify_sample,
+                no_verify_cache_drop,
                 skip_confirm: effective_skip_confirm,
+                confirm_device,
                 block_size: effective_block_size,
-                checksum,
+                checksums: checksum,
                 checksum_algo: effective_checksum_algo,
                 force,
+                require_blank,
                 no_unmount,
-                cancel_flag: running,
+                no_direct_io,
+                small_head,
+                verify_each_block,
+                eject,
+                check_bootable,
+                partition_after,
+                cancel_flag: cancel,
                 silent,
                 resume,
                 checkpoint: effective_checkpoint,
+                checkpoint_interval_secs: settings.write.checkpoint_interval_seconds,
                 auto_checksum: effective_auto_checksum,
                 show_partitions,
+                show_destruction,
+                json,
+                json_compat,
+                progress,
+                watch,
+                history_log,
+                cache_dir,
+                keep_cache,
+                identify,
+                verify_source_only,
+                glob,
+            })
+        }
+        Commands::Extract { source, entry } => {
+            commands::extract::execute(commands::extract::ExtractArgs {
+                source,
+                entry,
+                silent,
+                progress,
+                cancel_flag: cancel,
+            })
+        }
+        Commands::Formats { json } => commands::formats::execute(json, json_compat, silent),
+        Commands::History {
+            limit,
+            json,
+            no_urls,
+        } => commands::history::execute(commands::history::HistoryArgs {
+            log: history_log(cli.config_file.as_deref())?,
+            limit: Some(limit),
+            json,
+            json_compat,
+            no_urls,
+            silent,
+        }),
+        Commands::Resume { list: _, clear } => {
+            let action = match clear {
+                None => commands::resume::ResumeAction::List,
+                Some(None) => commands::resume::ResumeAction::ClearAll,
+                Some(Some(session_id)) => commands::resume::ResumeAction::Clear(session_id),
+            };
+            commands::resume::execute(commands::resume::ResumeArgs {
+                manager: engraver_core::CheckpointManager::default_location()?,
+                action,
+                silent,
             })
         }
         Commands::Erase {
@@ -359,7 +934,8 @@ fn run() -> Result<()> {
         } => {
             let effective_block_size =
                 block_size.unwrap_or_else(|| settings.write.block_size.clone());
-            let effective_skip_confirm = yes || silent || settings.behavior.skip_confirmation;
+            let effective_skip_confirm =
+                yes || assume_yes || silent || settings.behavior.skip_confirmation;
 
             commands::erase::execute(commands::erase::EraseArgs {
                 target,
@@ -367,7 +943,7 @@ fn run() -> Result<()> {
                 block_size: effective_block_size,
                 force,
                 no_unmount,
-                cancel_flag: running,
+                cancel_flag: cancel,
                 silent,
             })
         }
@@ -375,11 +951,83 @@ fn run() -> Result<()> {
             source,
             target,
             block_size,
-        } => commands::verify::execute(&source, &target, &block_size, running, silent),
-        Commands::Checksum { source, algorithm } => {
+            checksum,
+            algorithm,
+            sums,
+            name,
+            size,
+            resume,
+            no_verify_cache_drop,
+            json,
+        } => {
+            // With `verify --json` alone, progress stays quiet so stdout holds only the result
+            let output = commands::verify::VerifyOutput {
+                silent,
+                progress: if json && !cli.json {
+                    progress::ReporterKind::Null
+                } else {
+                    progress
+                },
+                json,
+                json_compat,
+            };
+            let mode = match (sums, name) {
+                (Some(sums), Some(name)) => {
+                    commands::verify::VerifyMode::resolve_sums(source, target, &sums, &name, size)?
+                }
+                _ => commands::verify::VerifyMode::resolve(
+                    source, target, checksum, algorithm, size,
+                )?,
+            };
+            match mode {
+                commands::verify::VerifyMode::Image { source, target } => {
+                    commands::verify::execute(
+                        &source,
+                        &target,
+                        &block_size,
+                        resume,
+                        !no_verify_cache_drop,
+                        cancel,
+                        output,
+                    )
+                }
+                commands::verify::VerifyMode::Checksum {
+                    target,
+                    expected,
+                    size,
+                } => commands::verify::execute_checksum(
+                    &target,
+                    &expected,
+                    size,
+                    &block_size,
+                    !no_verify_cache_drop,
+                    cancel,
+                    output,
+                ),
+            }
+        }
+        Commands::Checksum {
+            source,
+            algorithm,
+            compare_with: Some(sums_file),
+            ..
+        } => commands::checksum::execute_compare(
+            &source,
+            &sums_file,
+            algorithm.as_deref(),
+            silent,
+            progress,
+        ),
+        Commands::Checksum {
+            source,
+            algorithm,
+            compare_with: None,
+            format,
+        } => {
             let effective_algorithm =
                 algorithm.unwrap_or_else(|| settings.checksum.algorithm.clone());
-            commands::checksum::execute(&source, &effective_algorithm, silent)
+            let format = format.map(|f| f.parse()).transpose()?;
+            commands::checksum::execute(&source, &effective_algorithm, format, silent, progress)
         }
         Commands::Completions { shell } => {
             let mut cmd = Cli::command();
@@ -393,9 +1041,8 @@ fn run() -> Result<()> {
             std::fs::create_dir_all(out_path)?;
 
             // Generate main man page
-            let man = Man::new(cmd.clone());
             let mut buffer = Vec::new();
-            man.render(&mut buffer)?;
+            render_man_page(cmd.clone(), &mut buffer)?;
             std::fs::write(out_path.join("engraver.1"), buffer)?;
             if !silent {
                 println!("Generated: {}/engraver.1", out_dir);
@@ -413,9 +1060,8 @@ fn run() -> Result<()> {
                     continue;
                 }
 
-                let man = Man::new(subcommand.clone());
                 let mut buffer = Vec::new();
-                man.render(&mut buffer)?;
+                render_man_page(subcommand.clone(), &mut buffer)?;
                 let filename = format!("engraver-{}.1", name);
                 std::fs::write(out_path.join(&filename), buffer)?;
                 if !silent {
@@ -431,13 +1077,29 @@ fn run() -> Result<()> {
             }
             Ok(())
         }
-        Commands::Config { init, path, json } => {
+        Commands::Config {
+            init,
+            path,
+            list_profiles,
+            json,
+            action,
+        } => {
+            let action = action.map(|action| match action {
+                ConfigCommand::Get { key } => commands::config::ConfigAction::Get(key),
+                ConfigCommand::Set { key, value } => {
+                    commands::config::ConfigAction::Set(key, value)
+                }
+            });
             commands::config::execute(commands::config::ConfigArgs {
                 init,
                 path,
                 json,
+                action,
+                json_compat,
                 silent,
                 config_file: cli.config_file,
+                profile: cli.profile,
+                list_profiles,
             })
         }
         Commands::Benchmark {
@@ -447,10 +1109,13 @@ fn run() -> Result<()> {
             pattern,
             passes,
             test_block_sizes,
+            read,
             json,
+            compare,
             yes,
         } => {
-            let effective_skip_confirm = yes || silent || settings.behavior.skip_confirmation;
+            let effective_skip_confirm =
+                yes || assume_yes || silent || settings.behavior.skip_confirmation;
 
             // Apply settings as defaults when CLI options are not explicitly set
             let effective_block_size =
@@ -468,6 +1133,12 @@ fn run() -> Result<()> {
             });
             // JSON output: CLI flag overrides config
             let effective_json = json || settings.benchmark.json;
+            let record = settings.benchmark.record;
+            let benchmark_log = if record || compare {
+                benchmark_log(cli.config_file.as_deref()).ok()
+            } else {
+                None
+            };
 
             commands::benchmark::execute(commands::benchmark::BenchmarkArgs {
                 target,
@@ -476,11 +1147,29 @@ fn run() -> Result<()> {
                 pattern: effective_pattern,
                 passes: effective_passes,
                 json: effective_json,
+                json_compat,
                 skip_confirm: effective_skip_confirm,
                 silent,
                 test_block_sizes,
-                cancel_flag: running,
+                read,
+                benchmark_log,
+                record,
+                compare,
+                cancel_flag: cancel,
             })
         }
+    };
+
+    match (result, cli.timeout) {
+        // Whatever the command reported, the timeout is why it stopped
+        (Err(e), Some(limit)) if timeout_token.reason() == Some(CancelReason::Timeout) => {
+            tracing::debug!("Command stopped after timeout: {:#}", e);
+            Err(CliError::TimedOut(format!(
+                "Operation timed out after {}",
+                format_duration(limit.as_secs())
+            ))
+            .into())
+        }
+        (result, _) => result,
     }
 }
@@ -1,10 +1,626 @@
-//! Progress bar utilities for the CLI
+//! Progress reporting for the CLI
 //!
-//! These utility functions are available for custom progress display
-//! implementations but aren't used by the default CLI commands.
+//! Commands report progress through a [`ProgressReporter`] instead of driving
+//! an indicatif bar directly, so the same code path can render a terminal bar
+//! ([`BarReporter`]), print plain text lines for logs ([`LineReporter`]), emit
+//! JSON lines for scripts ([`JsonReporter`]) or stay quiet ([`NullReporter`]).
+//! [`ReporterKind::from_flags`] picks one from the global flags. [`BatchProgress`] hands out one reporter per device when
+//! writing to several targets at once.
+
+use engraver_core::{VerificationOperation, VerificationProgress, WritePhase, WriteProgress};
+use engraver_detect::format_bytes;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Minimum time between two JSON progress lines
+pub const JSON_PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Minimum time between two plain-text progress lines
+pub const LINE_PROGRESS_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Receives progress updates from write and verify operations
+///
+/// Methods take `&self` so one reporter can be shared with the `Fn`
+/// callbacks of `Writer` and `Verifier`.
+pub trait ProgressReporter: Send + Sync {
+    /// A block was written (or read back, during `WritePhase::Verifying`)
+    fn on_write(&self, progress: &WriteProgress);
+
+    /// A block was hashed or compared
+    fn on_verify(&self, progress: &VerificationProgress);
+
+    /// The operation ended; clears bars and flushes pending output
+    fn finish(&self);
+}
+
+/// How progress should be shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReporterKind {
+    /// Terminal progress bar
+    Bar,
+    /// Plain text status lines on stderr, without ANSI escapes
+    Lines,
+    /// One JSON object per line on stderr
+    Json,
+    /// No progress output
+    Null,
+}
+
+impl ReporterKind {
+    /// Choose a reporter from `--silent` and the global `--json` flag
+    ///
+    /// JSON wins over `--silent`, as for other machine-readable output.
+    pub fn from_flags(silent: bool, json: bool) -> Self {
+        if json {
+            ReporterKind::Json
+        } else if silent {
+            ReporterKind::Null
+        } else {
+            ReporterKind::Bar
+        }
+    }
+
+    /// Apply `--progress-format`, which only replaces the terminal bar
+    pub fn with_format(self, format: ProgressFormat) -> Self {
+        match (self, format) {
+            (ReporterKind::Bar, ProgressFormat::Lines) => ReporterKind::Lines,
+            (kind, _) => kind,
+        }
+    }
+
+    /// Create a reporter for `operation` (e.g. "Writing", "Checksumming source")
+    ///
+    /// `block_size` adds a "Block n/m" counter to verification messages.
+    pub fn create(
+        self,
+        operation: &str,
+        total: Option<u64>,
+        block_size: Option<usize>,
+    ) -> Arc<dyn ProgressReporter> {
+        match self {
+            ReporterKind::Bar => Arc::new(BarReporter::new(operation, total, block_size)),
+            ReporterKind::Lines => Arc::new(LineReporter::new(operation)),
+            ReporterKind::Json => Arc::new(JsonReporter::new(operation)),
+            ReporterKind::Null => Arc::new(NullReporter),
+        }
+    }
+}
+
+/// How a progress bar should be drawn (`write --progress-format`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProgressFormat {
+    /// Animated terminal bar
+    #[default]
+    Bar,
+    /// A plain line every few seconds, for logs and dumb terminals
+    Lines,
+}
+
+impl std::str::FromStr for ProgressFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.to_lowercase().as_str() {
+            "bar" => Ok(ProgressFormat::Bar),
+            "lines" | "line" | "plain" => Ok(ProgressFormat::Lines),
+            _ => anyhow::bail!("Unknown progress format '{}'. Use: bar or lines", s),
+        }
+    }
+}
+
+/// indicatif progress bar
+pub struct BarReporter {
+    bar: ProgressBar,
+    block_size: Option<u64>,
+    verifying: AtomicBool,
+    verify_prefix: String,
+}
+
+impl BarReporter {
+    /// Create a bar, or a spinner when the total is unknown or zero
+    pub fn new(operation: &str, total: Option<u64>, block_size: Option<usize>) -> Self {
+        Self::with_bar(new_bar(total), operation, "Verifying", block_size)
+    }
+
+    /// Wrap an existing bar, e.g. one added to a `MultiProgress`
+    ///
+    /// `verify_prefix` replaces `operation` once the read-back phase starts.
+    fn with_bar(
+        bar: ProgressBar,
+        operation: &str,
+        verify_prefix: &str,
+        block_size: Option<usize>,
+    ) -> Self {
+        bar.set_style(
+            ProgressStyle::default_bar()
+                .template(
+                    "  {spinner:.green} {prefix} [{bar:40.cyan/blue}] {bytes}/{total_bytes} {msg}",
+                )
+                .unwrap()
+                .progress_chars("█▓░"),
+        );
+        bar.set_prefix(operation.to_string());
+
+        Self {
+            bar,
+            block_size: block_size.map(|b| b as u64),
+            verifying: AtomicBool::new(false),
+            verify_prefix: verify_prefix.to_string(),
+        }
+    }
+}
+
+/// A bar, or a spinner when the total is unknown or zero
+fn new_bar(total: Option<u64>) -> ProgressBar {
+    match total {
+        Some(t) if t > 0 => ProgressBar::new(t),
+        _ => ProgressBar::new_spinner(),
+    }
+}
+
+impl ProgressReporter for BarReporter {
+    fn on_write(&self, progress: &WriteProgress) {
+        // Read-back after the write reuses the bar from the start
+        if progress.phase == WritePhase::Verifying && !self.verifying.swap(true, Ordering::Relaxed)
+        {
+            self.bar.set_position(0);
+            self.bar.set_prefix(self.verify_prefix.clone());
+        }
+
+        // Position by the absolute offset so a resumed write shows true
+        // completion, or by the compressed input when that is all that's known
+        let mut msg = if let Some((read, _)) = progress.compressed_position() {
+            self.bar.set_position(read);
+            format!(
+                "{} | {} written | ETA: {}",
+                progress.speed_display(),
+                format_bytes(progress.current_offset),
+                progress.eta_display()
+            )
+        } else {
+            self.bar.set_position(progress.current_offset);
+            format!(
+                "{} | Block {}/{} | ETA: {}",
+                progress.speed_display(),
+                progress.current_block,
+                progress.total_blocks,
+                progress.eta_display()
+            )
+        };
+        if progress.retry_count > 0 {
+            msg.push_str(&format!(" | {} retries", progress.retry_count));
+        }
+        self.bar.set_message(msg);
+    }
+
+    fn on_verify(&self, progress: &VerificationProgress) {
+        self.bar.set_position(progress.bytes_processed);
+
+        let mut msg = progress.speed_display();
+        if let Some(block_size) = self.block_size {
+            let blocks = progress.bytes_processed.div_ceil(block_size);
+            let total = progress
+                .total_bytes
+                .map(|t| t.div_ceil(block_size))
+                .unwrap_or(0);
+            msg.push_str(&format!(" | Block {}/{}", blocks, total));
+        }
+        msg.push_str(&format!(" | ETA: {}", progress.eta_display()));
+        self.bar.set_message(msg);
+    }
+
+    fn finish(&self) {
+        self.bar.finish_and_clear();
+    }
+}
+
+/// Progress as JSON lines on stderr, at most one per [`JSON_PROGRESS_INTERVAL`]
+///
+/// Each line looks like `{"event":"progress","operation":"Writing",...}`;
+/// the latest update is always flushed by [`finish`](ProgressReporter::finish).
+pub struct JsonReporter {
+    operation: String,
+    state: Mutex<JsonState>,
+}
+
+struct JsonState {
+    throttle: Throttle,
+    pending: Option<serde_json::Value>,
+}
+
+impl JsonReporter {
+    /// Create a reporter for `operation`
+    pub fn new(operation: &str) -> Self {
+        Self {
+            operation: operation.to_string(),
+            state: Mutex::new(JsonState {
+                throttle: Throttle::new(JSON_PROGRESS_INTERVAL),
+                pending: None,
+            }),
+        }
+    }
+
+    fn event(
+        &self,
+        phase: &str,
+        bytes: u64,
+        total: Option<u64>,
+        speed_bps: u64,
+        eta_seconds: Option<u64>,
+    ) -> serde_json::Value {
+        serde_json::json!({
+            "event": "progress",
+            "operation": self.operation,
+            "phase": phase,
+            "bytes": bytes,
+            "total": total,
+            "speed_bps": speed_bps,
+            "eta_seconds": eta_seconds,
+        })
+    }
+
+    fn report(&self, line: serde_json::Value) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.throttle.ready() {
+            state.pending = None;
+            eprintln!("{}", line);
+        } else {
+            state.pending = Some(line);
+        }
+    }
+}
+
+impl ProgressReporter for JsonReporter {
+    fn on_write(&self, progress: &WriteProgress) {
+        let phase = match progress.phase {
+            WritePhase::Verifying => "verifying",
+            _ => "writing",
+        };
+        let mut line = self.event(
+            phase,
+            progress.current_offset,
+            Some(progress.total_bytes),
+            progress.speed_bps,
+            progress.eta_seconds,
+        );
+        line["compressed_bytes"] = progress.compressed_bytes_read.into();
+        line["compressed_total"] = progress.compressed_total.into();
+        self.report(line);
+    }
+
+    fn on_verify(&self, progress: &VerificationProgress) {
+        let phase = match progress.operation {
+            VerificationOperation::Checksum => "checksumming",
+            VerificationOperation::Compare => "comparing",
+            _ => "reading",
+        };
+        self.report(self.event(
+            phase,
+            progress.bytes_processed,
+            progress.total_bytes,
+            progress.speed_bps,
+            progress.eta_seconds,
+        ));
+    }
+
+    fn finish(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(line) = state.pending.take() {
+            eprintln!("{}", line);
+        }
+    }
+}
+
+/// Plain text progress, e.g. `Writing: [ 45%] 1.2 GB / 2.6 GB at 38.00 MB/s ETA 00:36`
+///
+/// Lines go to stderr at most once per [`LINE_PROGRESS_INTERVAL`]; the
+/// latest throttled update is printed by [`finish`](ProgressReporter::finish).
+pub struct LineReporter {
+    operation: String,
+    state: Mutex<LineState>,
+}
+
+struct LineState {
+    throttle: Throttle,
+    pending: Option<String>,
+}
+
+impl LineReporter {
+    /// Create a reporter for `operation`
+    pub fn new(operation: &str) -> Self {
+        Self {
+            operation: operation.to_string(),
+            state: Mutex::new(LineState {
+                throttle: Throttle::new(LINE_PROGRESS_INTERVAL),
+                pending: None,
+            }),
+        }
+    }
+
+    fn report(&self, line: String) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.throttle.ready() {
+            state.pending = None;
+            eprintln!("{}", line);
+        } else {
+            state.pending = Some(line);
+        }
+    }
+}
+
+/// One progress line; the percentage and ETA are left out when unknown
+///
+/// `unit` follows the byte counts, e.g. " compressed read".
+fn format_line(
+    operation: &str,
+    bytes: u64,
+    total: Option<u64>,
+    unit: &str,
+    speed_bps: u64,
+    eta_seconds: Option<u64>,
+) -> String {
+    let mut line = format!("{}: ", operation);
+    match total {
+        Some(total) if total > 0 => {
+            let percent = u128::from(bytes.min(total)) * 100 / u128::from(total);
+            line.push_str(&format!(
+                "[{:>3}%] {} / {}",
+                percent,
+                format_bytes(bytes),
+                format_bytes(total)
+            ));
+        }
+        _ => line.push_str(&format_bytes(bytes)),
+    }
+    line.push_str(unit);
+    line.push_str(&format!(" at {}", format_speed(speed_bps)));
+    if let Some(eta) = eta_seconds {
+        line.push_str(&format!(" ETA {}", format_clock(eta)));
+    }
+    line
+}
+
+impl ProgressReporter for LineReporter {
+    fn on_write(&self, progress: &WriteProgress) {
+        let operation = match progress.phase {
+            WritePhase::Verifying => "Verifying",
+            _ => &self.operation,
+        };
+        let (bytes, total, unit) = match progress.compressed_position() {
+            Some((read, total)) => (read, total, " compressed read"),
+            None => (progress.current_offset, progress.total_bytes, ""),
+        };
+        self.report(format_line(
+            operation,
+            bytes,
+            Some(total),
+            unit,
+            progress.speed_bps,
+            progress.eta_seconds,
+        ));
+    }
+
+    fn on_verify(&self, progress: &VerificationProgress) {
+        self.report(format_line(
+            &self.operation,
+            progress.bytes_processed,
+            progress.total_bytes,
+            "",
+            progress.speed_bps,
+            progress.eta_seconds,
+        ));
+    }
+
+    fn finish(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(line) = state.pending.take() {
+            eprintln!("{}", line);
+        }
+    }
+}
+
+/// Reporter that shows nothing (`--silent`)
+pub struct NullReporter;
+
+impl ProgressReporter for NullReporter {
+    fn on_write(&self, _progress: &WriteProgress) {}
+
+    fn on_verify(&self, _progress: &VerificationProgress) {}
+
+    fn finish(&self) {}
+}
+
+/// Progress for writing one image to several devices at once
+///
+/// [`device`](Self::device) creates a reporter per target. With
+/// [`ReporterKind::Bar`] each one is a bar in a shared `MultiProgress`, above
+/// a total line showing the combined bytes written and speed. Other kinds
+/// fall back to one reporter per device, labelled with the device path.
+pub struct BatchProgress {
+    kind: ReporterKind,
+    multi: Option<MultiProgress>,
+    total: u64,
+    shared: Arc<BatchShared>,
+}
+
+/// Per-device totals feeding the aggregate line
+struct BatchShared {
+    aggregate: Option<ProgressBar>,
+    devices: Mutex<Vec<DeviceTotals>>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct DeviceTotals {
+    bytes: u64,
+    speed_bps: u64,
+}
+
+impl BatchProgress {
+    /// Create batch progress for writing `total` bytes to `count` devices
+    pub fn new(kind: ReporterKind, total: u64, count: usize) -> Self {
+        let (multi, aggregate) = if kind == ReporterKind::Bar {
+            let multi = MultiProgress::new();
+            let aggregate = multi.add(new_bar(Some(total.saturating_mul(count as u64))));
+            aggregate.set_style(
+                ProgressStyle::default_bar()
+                    .template("  {prefix:.bold} [{bar:40.green/white}] {bytes}/{total_bytes} {msg}")
+                    .unwrap()
+                    .progress_chars("█▓░"),
+            );
+            aggregate.set_prefix(format!("Total ({} devices)", count));
+            (Some(multi), Some(aggregate))
+        } else {
+            (None, None)
+        };
+
+        Self {
+            kind,
+            multi,
+            total,
+            shared: Arc::new(BatchShared {
+                aggregate,
+                devices: Mutex::new(Vec::with_capacity(count)),
+            }),
+        }
+    }
+
+    /// Create the reporter for the device at `path`
+    pub fn device(&self, path: &str, block_size: Option<usize>) -> Arc<dyn ProgressReporter> {
+        let inner: Arc<dyn ProgressReporter> = match (&self.multi, &self.shared.aggregate) {
+            (Some(multi), Some(aggregate)) => {
+                let bar = multi.insert_before(aggregate, new_bar(Some(self.total)));
+                Arc::new(BarReporter::with_bar(
+                    bar,
+                    path,
+                    &format!("{} (verifying)", path),
+                    block_size,
+                ))
+            }
+            _ => self
+                .kind
+                .create(&format!("Writing {}", path), Some(self.total), block_size),
+        };
+
+        let mut devices = self.shared.lock();
+        devices.push(DeviceTotals::default());
+        Arc::new(BatchDeviceReporter {
+            index: devices.len() - 1,
+            inner,
+            shared: self.shared.clone(),
+        })
+    }
+
+    /// Clear the total line once every device has finished
+    pub fn finish(&self) {
+        if let Some(ref aggregate) = self.shared.aggregate {
+            aggregate.finish_and_clear();
+        }
+    }
+}
+
+impl BatchShared {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<DeviceTotals>> {
+        self.devices.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn totals(&self) -> (u64, u64) {
+        self.lock().iter().fold((0, 0), |(bytes, speed), d| {
+            (bytes + d.bytes, speed + d.speed_bps)
+        })
+    }
+
+    /// Record one device's progress and refresh the total line
+    fn update(&self, index: usize, totals: DeviceTotals) {
+        if let Some(slot) = self.lock().get_mut(index) {
+            *slot = totals;
+        }
+        if let Some(ref aggregate) = self.aggregate {
+            let (bytes, speed) = self.totals();
+            aggregate.set_position(bytes);
+            aggregate.set_message(format!("{} combined", format_speed(speed)));
+        }
+    }
+}
+
+/// One device's reporter within a [`BatchProgress`]
+struct BatchDeviceReporter {
+    index: usize,
+    inner: Arc<dyn ProgressReporter>,
+    shared: Arc<BatchShared>,
+}
+
+impl ProgressReporter for BatchDeviceReporter {
+    fn on_write(&self, progress: &WriteProgress) {
+        self.inner.on_write(progress);
+
+        // The total tracks bytes written; the read-back pass is per device only
+        if progress.phase == WritePhase::Writing {
+            self.shared.update(
+                self.index,
+                DeviceTotals {
+                    bytes: progress
+                        .compressed_position()
+                        .map_or(progress.current_offset, |(read, _)| read),
+                    speed_bps: progress.speed_bps,
+                },
+            );
+        }
+    }
+
+    fn on_verify(&self, progress: &VerificationProgress) {
+        self.inner.on_verify(progress);
+    }
+
+    fn finish(&self) {
+        self.inner.finish();
+
+        // A finished device no longer contributes to the combined speed
+        let bytes = self.shared.lock().get(self.index).map_or(0, |d| d.bytes);
+        self.shared.update(
+            self.index,
+            DeviceTotals {
+                bytes,
+                speed_bps: 0,
+            },
+        );
+    }
+}
+
+/// Rate limiter for progress output
+///
+/// The first call is always ready; later calls are ready once `interval`
+/// has passed since the last ready call.
+#[derive(Debug)]
+pub struct Throttle {
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl Throttle {
+    /// Create a throttle that fires at most once per `interval`
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: None,
+        }
+    }
+
+    /// Whether output is due now; records the time if so
+    pub fn ready(&mut self) -> bool {
+        let now = Instant::now();
+        match self.last {
+            Some(last) if now.duration_since(last) < self.interval => false,
+            _ => {
+                self.last = Some(now);
+                true
+            }
+        }
+    }
+}
 
 /// Format bytes per second for display
-#[allow(dead_code)]
 pub fn format_speed(bytes_per_sec: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
@@ -21,6 +637,20 @@ pub fn format_speed(bytes_per_sec: u64) -> String {
     }
 }
 
+/// Format seconds as a clock, `MM:SS` or `H:MM:SS` from an hour up
+pub fn format_clock(seconds: u64) -> String {
+    if seconds >= 3600 {
+        format!(
+            "{}:{:02}:{:02}",
+            seconds / 3600,
+            (seconds % 3600) / 60,
+            seconds % 60
+        )
+    } else {
+        format!("{:02}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
 /// Format duration for display
 #[allow(dead_code)]
 pub fn format_eta(seconds: u64) -> String {
@@ -51,4 +681,264 @@ mod tests {
         assert_eq!(format_eta(90), "1m 30s");
         assert_eq!(format_eta(3661), "1h 1m");
     }
+
+    #[test]
+    fn test_format_clock() {
+        assert_eq!(format_clock(36), "00:36");
+        assert_eq!(format_clock(754), "12:34");
+        assert_eq!(format_clock(3723), "1:02:03");
+    }
+
+    // -------------------------------------------------------------------------
+    // ReporterKind tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_reporter_kind_from_flags() {
+        assert_eq!(ReporterKind::from_flags(false, false), ReporterKind::Bar);
+        assert_eq!(ReporterKind::from_flags(true, false), ReporterKind::Null);
+        assert_eq!(ReporterKind::from_flags(false, true), ReporterKind::Json);
+        assert_eq!(ReporterKind::from_flags(true, true), ReporterKind::Json);
+    }
+
+    #[test]
+    fn test_reporter_kind_with_format() {
+        assert_eq!(
+            ReporterKind::Bar.with_format(ProgressFormat::Lines),
+            ReporterKind::Lines
+        );
+        assert_eq!(
+            ReporterKind::Bar.with_format(ProgressFormat::Bar),
+            ReporterKind::Bar
+        );
+        assert_eq!(
+            ReporterKind::Json.with_format(ProgressFormat::Lines),
+            ReporterKind::Json
+        );
+        assert_eq!(
+            ReporterKind::Null.with_format(ProgressFormat::Lines),
+            ReporterKind::Null
+        );
+    }
+
+    #[test]
+    fn test_progress_format_from_str() {
+        assert_eq!(
+            "lines".parse::<ProgressFormat>().unwrap(),
+            ProgressFormat::Lines
+        );
+        assert_eq!(
+            "BAR".parse::<ProgressFormat>().unwrap(),
+            ProgressFormat::Bar
+        );
+        assert!("fancy".parse::<ProgressFormat>().is_err());
+    }
+
+    // -------------------------------------------------------------------------
+    // BarReporter tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_bar_reporter_with_size() {
+        let reporter = BarReporter::new("Writing", Some(1024 * 1024), None);
+        // Progress bar may be hidden in test environment without a terminal
+        // Just verify it was created with the right length
+        assert_eq!(reporter.bar.length(), Some(1024 * 1024));
+    }
+
+    #[test]
+    fn test_bar_reporter_unknown_or_zero_size() {
+        // Spinner doesn't have a length - just verify creation succeeds
+        for total in [None, Some(0)] {
+            let reporter = BarReporter::new("Downloading", total, None);
+            let length = reporter.bar.length();
+            assert!(length.is_none() || length == Some(0));
+        }
+    }
+
+    #[test]
+    fn test_bar_reporter_positions_by_current_offset() {
+        let reporter = BarReporter::new("Writing", Some(1000), None);
+        let mut progress = WriteProgress::new(1000, 100);
+        progress.bytes_written = 100;
+        progress.current_offset = 600;
+
+        reporter.on_write(&progress);
+        assert_eq!(reporter.bar.position(), 600);
+
+        // Switching to read-back restarts the bar
+        progress.phase = WritePhase::Verifying;
+        progress.current_offset = 200;
+        reporter.on_write(&progress);
+        assert_eq!(reporter.bar.position(), 200);
+        assert_eq!(reporter.bar.prefix(), "Verifying");
+    }
+
+    #[test]
+    fn test_bar_reporter_positions_by_compressed_input() {
+        let reporter = BarReporter::new("Writing", Some(1000), None);
+        let mut progress = WriteProgress::new(1000, 100);
+        progress.current_offset = 4000;
+        progress.compressed_bytes_read = Some(250);
+        progress.compressed_total = Some(1000);
+
+        reporter.on_write(&progress);
+        assert_eq!(reporter.bar.position(), 250);
+        assert!(reporter.bar.message().contains("written"));
+    }
+
+    #[test]
+    fn test_bar_reporter_custom_verify_prefix() {
+        let reporter = BarReporter::with_bar(
+            ProgressBar::hidden(),
+            "/dev/sdb",
+            "/dev/sdb (verifying)",
+            None,
+        );
+        assert_eq!(reporter.bar.prefix(), "/dev/sdb");
+
+        let mut progress = WriteProgress::new(1000, 100);
+        progress.phase = WritePhase::Verifying;
+        reporter.on_write(&progress);
+        assert_eq!(reporter.bar.prefix(), "/dev/sdb (verifying)");
+    }
+
+    // -------------------------------------------------------------------------
+    // BatchProgress tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_batch_progress_sums_devices() {
+        let batch = BatchProgress::new(ReporterKind::Null, 1000, 2);
+        let sdb = batch.device("/dev/sdb", None);
+        let sdc = batch.device("/dev/sdc", None);
+
+        let mut progress = WriteProgress::new(1000, 100);
+        progress.current_offset = 300;
+        progress.speed_bps = 10;
+        sdb.on_write(&progress);
+        progress.current_offset = 500;
+        progress.speed_bps = 20;
+        sdc.on_write(&progress);
+        assert_eq!(batch.shared.totals(), (800, 30));
+
+        // Read-back progress does not count towards the total
+        progress.phase = WritePhase::Verifying;
+        progress.current_offset = 100;
+        sdc.on_write(&progress);
+        assert_eq!(batch.shared.totals(), (800, 30));
+
+        // Finished devices keep their bytes but stop adding speed
+        sdb.finish();
+        assert_eq!(batch.shared.totals(), (800, 20));
+    }
+
+    #[test]
+    fn test_batch_progress_bar_total_line() {
+        let batch = BatchProgress::new(ReporterKind::Bar, 1000, 3);
+        let aggregate = batch.shared.aggregate.as_ref().unwrap();
+        assert_eq!(aggregate.length(), Some(3000));
+
+        let sdb = batch.device("/dev/sdb", None);
+        let mut progress = WriteProgress::new(1000, 100);
+        progress.current_offset = 400;
+        sdb.on_write(&progress);
+        assert_eq!(aggregate.position(), 400);
+        batch.finish();
+    }
+
+    // -------------------------------------------------------------------------
+    // Throttle tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_throttle_first_call_ready() {
+        let mut throttle = Throttle::new(Duration::from_secs(3600));
+        assert!(throttle.ready());
+        assert!(!throttle.ready());
+    }
+
+    #[test]
+    fn test_throttle_zero_interval_always_ready() {
+        let mut throttle = Throttle::new(Duration::ZERO);
+        assert!(throttle.ready());
+        assert!(throttle.ready());
+    }
+
+    // -------------------------------------------------------------------------
+    // LineReporter tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_format_line() {
+        let gb = 1024 * 1024 * 1024;
+        assert_eq!(
+            format_line("Writing", gb / 2, Some(gb), "", 38 * 1024 * 1024, Some(36)),
+            "Writing: [ 50%] 512.0 MB / 1.0 GB at 38.00 MB/s ETA 00:36"
+        );
+        assert_eq!(
+            format_line("Checksumming source", 2048, None, "", 512, None),
+            "Checksumming source: 2.0 KB at 512 B/s"
+        );
+    }
+
+    #[test]
+    fn test_line_reporter_uses_compressed_position() {
+        let reporter = LineReporter::new("Writing");
+        let mut progress = WriteProgress::new(1000, 100);
+        progress.current_offset = 4000;
+        progress.compressed_bytes_read = Some(250);
+        progress.compressed_total = Some(1000);
+
+        reporter.on_write(&progress);
+        reporter.on_write(&progress);
+
+        let pending = reporter.state.lock().unwrap().pending.clone().unwrap();
+        assert!(pending.starts_with("Writing: [ 25%] 250 B / 1000 B compressed read"));
+    }
+
+    #[test]
+    fn test_line_reporter_keeps_latest_pending_update() {
+        let reporter = LineReporter::new("Writing");
+        let mut progress = WriteProgress::new(1000, 100);
+
+        for offset in [100, 200, 300] {
+            progress.current_offset = offset;
+            reporter.on_write(&progress);
+        }
+
+        let pending = reporter.state.lock().unwrap().pending.clone().unwrap();
+        assert!(pending.starts_with("Writing: [ 30%] 300 B / 1000 B"));
+        assert!(!pending.contains('\x1b'));
+
+        reporter.finish();
+        assert!(reporter.state.lock().unwrap().pending.is_none());
+    }
+
+    // -------------------------------------------------------------------------
+    // JsonReporter tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_json_reporter_keeps_latest_pending_update() {
+        let reporter = JsonReporter::new("Writing");
+        let mut progress = WriteProgress::new(1000, 100);
+
+        // The first update is printed, the following ones are throttled
+        for offset in [100, 200, 300] {
+            progress.current_offset = offset;
+            reporter.on_write(&progress);
+        }
+
+        let pending = reporter.state.lock().unwrap().pending.clone().unwrap();
+        assert_eq!(pending["event"], "progress");
+        assert_eq!(pending["operation"], "Writing");
+        assert_eq!(pending["phase"], "writing");
+        assert_eq!(pending["bytes"], 300);
+        assert_eq!(pending["total"], 1000);
+        assert!(pending["compressed_bytes"].is_null());
+
+        reporter.finish();
+        assert!(reporter.state.lock().unwrap().pending.is_none());
+    }
 }
@@ -0,0 +1,65 @@
+//! Confirmation prompts
+//!
+//! Prompts read from the terminal, so without one (containers, CI) they
+//! cannot be answered. [`confirm`] fails fast in that case instead of
+//! blocking, and `ENGRAVER_ASSUME_YES=1` opts in to answering every prompt
+//! yes, the same as `--yes`.
+
+use anyhow::Result;
+use dialoguer::Confirm;
+use std::io::IsTerminal;
+
+use crate::error::CliError;
+
+/// Environment variable that answers every confirmation yes when set to `1`
+pub const ASSUME_YES_ENV: &str = "ENGRAVER_ASSUME_YES";
+
+/// Whether [`ASSUME_YES_ENV`] opts in to skipping confirmations
+pub fn assume_yes_from_env() -> bool {
+    is_opt_in(std::env::var(ASSUME_YES_ENV).ok().as_deref())
+}
+
+/// Only an explicit `1`, `true` or `yes` opts in; anything else is ignored
+fn is_opt_in(value: Option<&str>) -> bool {
+    matches!(
+        value.map(|v| v.trim().to_ascii_lowercase()).as_deref(),
+        Some("1" | "true" | "yes")
+    )
+}
+
+/// Ask a yes/no question on the terminal
+///
+/// Returns [`CliError::NoTty`] without prompting when stdin is not a terminal.
+pub fn confirm(prompt: impl Into<String>, default: bool) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        return Err(CliError::NoTty.into());
+    }
+    Ok(Confirm::new()
+        .with_prompt(prompt)
+        .default(default)
+        .interact()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_opt_in() {
+        assert!(is_opt_in(Some("1")));
+        assert!(is_opt_in(Some("true")));
+        assert!(is_opt_in(Some(" YES ")));
+        assert!(!is_opt_in(Some("0")));
+        assert!(!is_opt_in(Some("")));
+        assert!(!is_opt_in(Some("no")));
+        assert!(!is_opt_in(None));
+    }
+
+    #[test]
+    fn test_no_tty_error_message() {
+        assert_eq!(
+            CliError::NoTty.to_string(),
+            "no TTY for confirmation; pass --yes or set ENGRAVER_ASSUME_YES=1"
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Wall-clock limit for a whole command (`--timeout`)
+//!
+//! A watchdog thread cancels the shared [`CancelToken`] with
+//! [`CancelReason::Timeout`] once the limit passes, so every phase that
+//! already honours Ctrl+C (writing, verification, erasing) stops the same way.
+
+use engraver_core::{CancelReason, CancelToken};
+use std::time::Duration;
+
+/// Parse a duration such as `"90s"`, `"30m"` or `"1h"`
+///
+/// A bare number is taken as seconds. Units are case-insensitive; zero is
+/// rejected because it would cancel before any work starts.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let invalid = || format!("Invalid duration: {s:?} (expected e.g. 90s, 30m, 1h)");
+
+    let split_pos = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split_pos);
+    if number.is_empty() {
+        return Err(invalid());
+    }
+
+    let multiplier = match unit.trim_start().to_ascii_lowercase().as_str() {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        _ => return Err(invalid()),
+    };
+
+    let secs = number
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(invalid)?;
+    if secs == 0 {
+        return Err("Duration must be greater than zero".to_string());
+    }
+
+    Ok(Duration::from_secs(secs))
+}
+
+/// Cancel `token` with [`CancelReason::Timeout`] once `limit` has passed
+///
+/// The thread is detached; it dies with the process if the command finishes
+/// first. `on_timeout` runs only if this watchdog was the one to cancel.
+pub fn spawn_watchdog<F>(token: CancelToken, limit: Duration, on_timeout: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    std::thread::spawn(move || {
+        std::thread::sleep(limit);
+        token.cancel(CancelReason::Timeout);
+        if token.reason() == Some(CancelReason::Timeout) {
+            on_timeout();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_units() {
+        let cases = [
+            ("45", 45),
+            ("45s", 45),
+            ("30m", 30 * 60),
+            ("1h", 3600),
+            ("2H", 7200),
+            (" 10 m ", 600),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                parse_duration(input).unwrap(),
+                Duration::from_secs(expected),
+                "input: {:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_duration_invalid() {
+        for input in [
+            "",
+            "m",
+            "1.5h",
+            "10d",
+            "-5s",
+            "1h30m",
+            "99999999999999999999h",
+        ] {
+            assert!(parse_duration(input).is_err(), "input: {:?}", input);
+        }
+        assert!(parse_duration("0s")
+            .unwrap_err()
+            .contains("greater than zero"));
+    }
+
+    #[test]
+    fn test_watchdog_cancels_with_timeout_reason() {
+        let token = CancelToken::new();
+        let (tx, rx) = std::sync::mpsc::channel();
+        spawn_watchdog(token.clone(), Duration::from_millis(10), move || {
+            tx.send(()).unwrap();
+        });
+
+        rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(token.reason(), Some(CancelReason::Timeout));
+    }
+
+    #[test]
+    fn test_watchdog_keeps_earlier_reason() {
+        let token = CancelToken::new();
+        token.cancel(CancelReason::UserRequested);
+        let (tx, rx) = std::sync::mpsc::channel::<()>();
+        spawn_watchdog(token.clone(), Duration::from_millis(10), move || {
+            tx.send(()).unwrap();
+        });
+
+        // The callback is dropped without running, closing the channel
+        assert!(rx.recv_timeout(Duration::from_secs(5)).is_err());
+        assert_eq!(token.reason(), Some(CancelReason::UserRequested));
+    }
+}
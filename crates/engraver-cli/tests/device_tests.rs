@@ -116,6 +116,34 @@ fn test_write_and_verify() {
         .success();
 }
 
+#[test]
+#[ignore]
+fn test_write_verify_mode_bytes_compares_blocks() {
+    let device = require_test_device();
+    let dir = TempDir::new().unwrap();
+    let image = create_test_image(&dir);
+
+    // Only the post-write byte comparison reports a block count
+    engraver()
+        .args([
+            "write",
+            image.to_str().unwrap(),
+            &device,
+            "--yes",
+            "--force",
+            "--no-unmount",
+            "--verify",
+            "--verify-mode",
+            "bytes",
+            "--block-size",
+            "4K",
+        ])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Verifying write..."))
+        .stdout(predicates::str::contains("256 blocks"));
+}
+
 #[test]
 #[ignore]
 fn test_write_then_standalone_verify() {
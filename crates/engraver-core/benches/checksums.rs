@@ -27,6 +27,7 @@ fn bench_checksum_algorithms(c: &mut Criterion) {
     let algorithms = [
         (ChecksumAlgorithm::Sha256, "SHA-256"),
         (ChecksumAlgorithm::Sha512, "SHA-512"),
+        (ChecksumAlgorithm::Sha1, "SHA-1"),
         (ChecksumAlgorithm::Md5, "MD5"),
         (ChecksumAlgorithm::Crc32, "CRC32"),
     ];
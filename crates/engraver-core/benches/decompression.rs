@@ -0,0 +1,80 @@
+//! Benchmarks for zstd decode throughput.
+//!
+//! Decodes the same 16 MB image compressed normally and with long-distance
+//! matching (`zstd --long`), read through [`Source`] in 4 MB blocks as the
+//! writer would.
+//!
+//! Run with: cargo bench -p engraver-core --bench decompression
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use engraver_core::{Source, ZstdOptions};
+use std::io::{Read, Write};
+use tempfile::TempDir;
+
+/// Uncompressed image size
+const IMAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// Read size, matching the CLI's default block size
+const READ_SIZE: usize = 4 * 1024 * 1024;
+
+/// Image-like data: repeated 1 MB runs with some noise, so LDM has matches to find
+fn generate_image() -> Vec<u8> {
+    let chunk: Vec<u8> = (0..1024 * 1024u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+        .collect();
+    (0..IMAGE_SIZE)
+        .map(|i| chunk[i % chunk.len()] ^ u8::from(i % 4099 == 0))
+        .collect()
+}
+
+/// Compress `data` into `dir`, with long-distance matching over a `window_log` window if set
+fn compress(dir: &TempDir, name: &str, data: &[u8], long_window: Option<u32>) -> String {
+    let path = dir.path().join(name);
+    let mut encoder = zstd::Encoder::new(std::fs::File::create(&path).unwrap(), 3).unwrap();
+    if let Some(window_log) = long_window {
+        encoder.long_distance_matching(true).unwrap();
+        encoder.window_log(window_log).unwrap();
+    }
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+/// Read all of `source`, returning the number of bytes decoded
+fn drain(mut source: Source) -> usize {
+    let mut buffer = vec![0u8; READ_SIZE];
+    let mut total = 0;
+    loop {
+        match source.read(&mut buffer).unwrap() {
+            0 => return total,
+            n => total += n,
+        }
+    }
+}
+
+fn bench_zstd_decode(c: &mut Criterion) {
+    let dir = TempDir::new().unwrap();
+    let data = generate_image();
+    let images = [
+        ("default", compress(&dir, "default.img.zst", &data, None)),
+        ("long", compress(&dir, "long.img.zst", &data, Some(27))),
+    ];
+
+    let mut group = c.benchmark_group("zstd_decode");
+    group.throughput(Throughput::Bytes(IMAGE_SIZE as u64));
+    group.sample_size(20);
+
+    for (name, path) in &images {
+        group.bench_with_input(BenchmarkId::from_parameter(name), path, |b, path| {
+            b.iter(|| {
+                let source = Source::open_zstd_with_options(path, ZstdOptions::default()).unwrap();
+                assert_eq!(drain(source), IMAGE_SIZE);
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_zstd_decode);
+criterion_main!(benches);
@@ -0,0 +1,85 @@
+//! Benchmarks for the write pipeline.
+//!
+//! Compares read-ahead buffer counts against a source and target that each
+//! take a fixed time per block, as a network or decompressing source and a
+//! USB stick would. With one buffer each block costs read + write time; with
+//! two or more the reads overlap the writes.
+//!
+//! Run with: cargo bench -p engraver-core --bench writer
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use engraver_core::{Prefetch, WriteConfig, Writer, MIN_BLOCK_SIZE};
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+use std::time::Duration;
+
+/// Blocks written per iteration
+const BLOCKS: usize = 16;
+
+/// Simulated latency per block on each side
+const LATENCY: Duration = Duration::from_millis(2);
+
+/// A source that sleeps before every read
+struct SlowReader(Cursor<Vec<u8>>);
+
+impl Read for SlowReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        std::thread::sleep(LATENCY);
+        self.0.read(buf)
+    }
+}
+
+/// A target that sleeps before every write
+struct SlowWriter(Cursor<Vec<u8>>);
+
+impl Read for SlowWriter {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl Write for SlowWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        std::thread::sleep(LATENCY);
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for SlowWriter {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.0.seek(pos)
+    }
+}
+
+/// Benchmark writes with 1 (no read-ahead), 2 and 4 buffers
+fn bench_buffer_count(c: &mut Criterion) {
+    let mut group = c.benchmark_group("write_buffer_count");
+    let size = MIN_BLOCK_SIZE * BLOCKS;
+    let data = vec![0xA5u8; size];
+    group.throughput(Throughput::Bytes(size as u64));
+    group.sample_size(20);
+
+    for count in [1, 2, 4] {
+        group.bench_with_input(BenchmarkId::from_parameter(count), &count, |b, &count| {
+            b.iter(|| {
+                let config = WriteConfig::new()
+                    .block_size(MIN_BLOCK_SIZE)
+                    .buffer_count(count)
+                    .sync_on_complete(false);
+                let source = Prefetch::new(SlowReader(Cursor::new(data.clone())));
+                let target = SlowWriter(Cursor::new(Vec::with_capacity(size)));
+                Writer::with_config(config)
+                    .write(source, target, size as u64)
+                    .unwrap()
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_buffer_count);
+criterion_main!(benches);
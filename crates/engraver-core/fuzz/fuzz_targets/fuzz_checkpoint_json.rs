@@ -70,6 +70,10 @@ pub struct WriteCheckpoint {
     // Target Information
     pub target_path: String,
     pub target_size: u64,
+    #[serde(default)]
+    pub target_serial: Option<String>,
+    #[serde(default)]
+    pub target_model: Option<String>,
 
     // Write Configuration
     pub block_size: usize,
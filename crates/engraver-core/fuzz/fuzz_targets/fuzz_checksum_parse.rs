@@ -36,6 +36,7 @@ fuzz_target!(|data: &str| {
 pub enum ChecksumAlgorithm {
     Sha256,
     Sha512,
+    Sha1,
     Md5,
     Crc32,
 }
@@ -45,6 +46,7 @@ impl ChecksumAlgorithm {
         match self {
             ChecksumAlgorithm::Sha256 => 32,
             ChecksumAlgorithm::Sha512 => 64,
+            ChecksumAlgorithm::Sha1 => 20,
             ChecksumAlgorithm::Md5 => 16,
             ChecksumAlgorithm::Crc32 => 4,
         }
@@ -58,6 +60,7 @@ impl ChecksumAlgorithm {
         match self {
             ChecksumAlgorithm::Sha256 => "SHA-256",
             ChecksumAlgorithm::Sha512 => "SHA-512",
+            ChecksumAlgorithm::Sha1 => "SHA-1",
             ChecksumAlgorithm::Md5 => "MD5",
             ChecksumAlgorithm::Crc32 => "CRC32",
         }
@@ -67,6 +70,7 @@ impl ChecksumAlgorithm {
         match len {
             64 => Some(ChecksumAlgorithm::Sha256),
             128 => Some(ChecksumAlgorithm::Sha512),
+            40 => Some(ChecksumAlgorithm::Sha1),
             32 => Some(ChecksumAlgorithm::Md5),
             8 => Some(ChecksumAlgorithm::Crc32),
             _ => None,
@@ -82,6 +86,7 @@ impl std::str::FromStr for ChecksumAlgorithm {
         match s.as_str() {
             "sha256" | "sha-256" => Ok(ChecksumAlgorithm::Sha256),
             "sha512" | "sha-512" => Ok(ChecksumAlgorithm::Sha512),
+            "sha1" | "sha-1" => Ok(ChecksumAlgorithm::Sha1),
             "md5" => Ok(ChecksumAlgorithm::Md5),
             "crc32" | "crc-32" => Ok(ChecksumAlgorithm::Crc32),
             _ => Err(()),
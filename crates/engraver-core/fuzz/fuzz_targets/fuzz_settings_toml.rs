@@ -88,11 +88,22 @@ impl Default for ChecksumSettings {
 }
 
 /// General behavior settings
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct BehaviorSettings {
     pub skip_confirmation: bool,
     pub quiet: bool,
+    pub record_history: bool,
+}
+
+impl Default for BehaviorSettings {
+    fn default() -> Self {
+        Self {
+            skip_confirmation: false,
+            quiet: false,
+            record_history: true,
+        }
+    }
 }
 
 /// Settings for benchmark operations
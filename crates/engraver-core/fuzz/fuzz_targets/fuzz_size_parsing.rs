@@ -6,7 +6,8 @@
 
 use libfuzzer_sys::fuzz_target;
 
-use engraver_core::{parse_block_sizes, parse_size};
+use engraver_core::benchmark::parse_size;
+use engraver_core::parse_block_sizes;
 
 fuzz_target!(|data: &str| {
     // Test parse_size - should never panic, only return Ok/Err
@@ -20,6 +21,12 @@ fuzz_target!(|data: &str| {
         assert!(size > 0, "Size should be positive");
     }
 
+    // Test the general-purpose parser - any success must satisfy the clamp
+    let _ = engraver_core::parse_size(data);
+    if let Ok(size) = engraver_core::parse_block_size_clamped(data, 4096, 64 * 1024 * 1024) {
+        assert!((4096..=64 * 1024 * 1024).contains(&size));
+    }
+
     // Test parse_block_sizes with single value
     let _ = parse_block_sizes(data);
 
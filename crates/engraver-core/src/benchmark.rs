@@ -2,13 +2,14 @@
 //!
 //! This module provides functionality to benchmark write speeds of storage devices,
 //! helping users identify slow drives or connections before committing to long write operations.
+//! A read-only mode measures read throughput without touching the device's contents.
 
-use std::io::{Seek, SeekFrom, Write};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 // Constants
@@ -83,6 +84,18 @@ impl std::str::FromStr for DataPattern {
     }
 }
 
+/// Benchmark direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum BenchmarkMode {
+    /// Write a data pattern to the device (destructive)
+    #[default]
+    Write,
+    /// Read from the device without modifying it
+    Read,
+}
+
 /// Configuration for benchmark operations
 #[derive(Debug, Clone)]
 pub struct BenchmarkConfig {
@@ -209,6 +222,47 @@ pub struct PassResult {
     pub min_speed_bps: u64,
     /// Maximum speed observed (bytes per second)
     pub max_speed_bps: u64,
+    /// Per-block latency in this pass
+    pub latency: LatencyStats,
+}
+
+/// Percentiles of how long each block took to write (or read)
+///
+/// Average speed hides stalls; a USB stick that flushes its write cache
+/// every few hundred megabytes shows up here as a p99 or max far above p50.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct LatencyStats {
+    /// Median block latency
+    #[serde(with = "duration_serde")]
+    pub p50: Duration,
+    /// 95th percentile block latency
+    #[serde(with = "duration_serde")]
+    pub p95: Duration,
+    /// 99th percentile block latency
+    #[serde(with = "duration_serde")]
+    pub p99: Duration,
+    /// Longest single block, the worst stall
+    #[serde(with = "duration_serde")]
+    pub max: Duration,
+}
+
+impl LatencyStats {
+    /// Nearest-rank percentiles over `samples`, sorting it in place
+    ///
+    /// All zero when there are no samples.
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_unstable();
+        let percentile = |p: usize| samples[(samples.len() * p).div_ceil(100).max(1) - 1];
+        Self {
+            p50: percentile(50),
+            p95: percentile(95),
+            p99: percentile(99),
+            max: samples[samples.len() - 1],
+        }
+    }
 }
 
 /// Complete benchmark results
@@ -216,6 +270,8 @@ pub struct PassResult {
 pub struct BenchmarkResult {
     /// Device path that was benchmarked
     pub device_path: String,
+    /// Whether this was a write or read benchmark
+    pub mode: BenchmarkMode,
     /// Test size used in bytes
     pub test_size: u64,
     /// Block size used in bytes
@@ -258,6 +314,8 @@ pub struct BenchmarkSummary {
     pub min_speed_bps: u64,
     /// Maximum speed observed (bytes per second)
     pub max_speed_bps: u64,
+    /// Per-block latency across all passes
+    pub latency: LatencyStats,
 }
 
 /// Result when testing multiple block sizes
@@ -418,6 +476,7 @@ impl BenchmarkRunner {
         let data_source = BenchmarkDataSource::new(self.config.pattern, block_size);
 
         let mut passes = Vec::with_capacity(self.config.passes as usize);
+        let mut latencies = Vec::new();
         let total_bytes_all_passes = effective_size * self.config.passes as u64;
 
         for pass in 1..=self.config.passes {
@@ -432,16 +491,18 @@ impl BenchmarkRunner {
                 total_bytes_all_passes,
                 (pass - 1) as u64 * effective_size,
                 &progress_callback,
+                &mut latencies,
             )?;
 
             passes.push(pass_result);
         }
 
         // Calculate summary
-        let summary = self.calculate_summary(&passes);
+        let summary = self.calculate_summary(&passes, &mut latencies);
 
         Ok(BenchmarkResult {
             device_path: device_path.to_string(),
+            mode: BenchmarkMode::Write,
             test_size: effective_size,
             block_size: self.config.block_size,
             pattern: self.config.pattern,
@@ -459,6 +520,7 @@ impl BenchmarkRunner {
         total_bytes_all_passes: u64,
         bytes_before_this_pass: u64,
         progress_callback: &Option<F>,
+        latencies: &mut Vec<Duration>,
     ) -> Result<PassResult>
     where
         W: Write,
@@ -467,6 +529,7 @@ impl BenchmarkRunner {
         let block_size = self.config.block_size as usize;
         let mut bytes_written: u64 = 0;
         let mut speed_tracker = SpeedTracker::new();
+        let pass_start = latencies.len();
         let start_time = Instant::now();
 
         speed_tracker.update(0);
@@ -482,8 +545,10 @@ impl BenchmarkRunner {
             let to_write = (block_size as u64).min(remaining) as usize;
             let block = &data_source.get_block()[..to_write];
 
-            // Write block
+            // Write block, timing it for the latency percentiles
+            let block_start = Instant::now();
             target.write_all(block)?;
+            latencies.push(block_start.elapsed());
             bytes_written += to_write as u64;
 
             // Update speed tracker
@@ -513,6 +578,9 @@ impl BenchmarkRunner {
             0
         };
 
+        // Sorting a copy keeps the combined samples for the summary
+        let mut pass_latencies = latencies[pass_start..].to_vec();
+
         Ok(PassResult {
             pass_number,
             bytes_written,
@@ -521,10 +589,103 @@ impl BenchmarkRunner {
             average_speed_bps: average_speed,
             min_speed_bps: speed_tracker.min_speed(),
             max_speed_bps: speed_tracker.max_speed(),
+            latency: LatencyStats::from_samples(&mut pass_latencies),
         })
     }
 
-    fn calculate_summary(&self, passes: &[PassResult]) -> BenchmarkSummary {
+    /// Run a non-destructive read benchmark on a readable source
+    ///
+    /// Reads `test_size` bytes from the start of the source in `block_size`
+    /// chunks, in a single pass. The configured pattern and pass count are
+    /// ignored. Reading stops early if the source ends first.
+    pub fn run_read<R, F>(
+        &self,
+        mut source: R,
+        device_path: &str,
+        progress_callback: Option<F>,
+    ) -> Result<BenchmarkResult>
+    where
+        R: Read + Seek,
+        F: Fn(&BenchmarkProgress),
+    {
+        self.config.validate()?;
+
+        let effective_size = self.config.effective_test_size();
+        let block_size = self.config.block_size as usize;
+        let mut buffer = vec![0u8; block_size];
+        let mut bytes_read: u64 = 0;
+        let mut speed_tracker = SpeedTracker::new();
+        let mut latencies = Vec::new();
+
+        source.seek(SeekFrom::Start(0))?;
+        let start_time = Instant::now();
+        speed_tracker.update(0);
+
+        while bytes_read < effective_size {
+            // Check for cancellation
+            if self.cancel_flag.load(Ordering::Relaxed) {
+                return Err(BenchmarkError::Cancelled);
+            }
+
+            let remaining = effective_size - bytes_read;
+            let to_read = (block_size as u64).min(remaining) as usize;
+            let block_start = Instant::now();
+            let n = source.read(&mut buffer[..to_read])?;
+            if n == 0 {
+                break;
+            }
+            latencies.push(block_start.elapsed());
+            bytes_read += n as u64;
+
+            speed_tracker.update(bytes_read);
+
+            if let Some(ref callback) = progress_callback {
+                callback(&BenchmarkProgress {
+                    bytes_written: bytes_read,
+                    total_bytes: effective_size,
+                    current_pass: 1,
+                    total_passes: 1,
+                    current_speed_bps: speed_tracker.current_speed(),
+                    elapsed: start_time.elapsed(),
+                });
+            }
+        }
+
+        let elapsed = start_time.elapsed();
+        let average_speed = if elapsed.as_secs_f64() > 0.0 {
+            (bytes_read as f64 / elapsed.as_secs_f64()) as u64
+        } else {
+            0
+        };
+
+        let passes = vec![PassResult {
+            pass_number: 1,
+            bytes_written: bytes_read,
+            block_size: self.config.block_size,
+            elapsed,
+            average_speed_bps: average_speed,
+            min_speed_bps: speed_tracker.min_speed(),
+            max_speed_bps: speed_tracker.max_speed(),
+            latency: LatencyStats::from_samples(&mut latencies),
+        }];
+        let summary = self.calculate_summary(&passes, &mut latencies);
+
+        Ok(BenchmarkResult {
+            device_path: device_path.to_string(),
+            mode: BenchmarkMode::Read,
+            test_size: effective_size,
+            block_size: self.config.block_size,
+            pattern: self.config.pattern,
+            passes,
+            summary,
+        })
+    }
+
+    fn calculate_summary(
+        &self,
+        passes: &[PassResult],
+        latencies: &mut [Duration],
+    ) -> BenchmarkSummary {
         let total_bytes: u64 = passes.iter().map(|p| p.bytes_written).sum();
         let total_elapsed: Duration = passes.iter().map(|p| p.elapsed).sum();
 
@@ -543,6 +704,7 @@ impl BenchmarkRunner {
             average_speed_bps: average_speed,
             min_speed_bps: min_speed,
             max_speed_bps: max_speed,
+            latency: LatencyStats::from_samples(latencies),
         }
     }
 
@@ -609,7 +771,9 @@ pub fn is_power_of_two(n: u64) -> bool {
     n > 0 && (n & (n - 1)) == 0
 }
 
-/// Parse a size string like "256M", "1G", "4K" into bytes
+/// Parse a power-of-two size string like "256M", "1G", "4K" into bytes
+///
+/// Accepts the same syntax as [`crate::size::parse_size`].
 pub fn parse_size(s: &str) -> Result<u64> {
     let s = s.trim();
     if s.is_empty() {
@@ -618,28 +782,12 @@ pub fn parse_size(s: &str) -> Result<u64> {
         ));
     }
 
-    let (num_str, suffix) = if s.chars().last().is_some_and(|c| c.is_alphabetic()) {
-        let split_pos = s.chars().position(|c| c.is_alphabetic()).unwrap_or(s.len());
-        (&s[..split_pos], &s[split_pos..])
-    } else {
-        (s, "")
-    };
-
-    let num: u64 = num_str
-        .parse()
-        .map_err(|_| BenchmarkError::InvalidSizeFormat(s.to_string()))?;
-
-    let multiplier = match suffix.to_uppercase().as_str() {
-        "" | "B" => 1,
-        "K" | "KB" => 1024,
-        "M" | "MB" => 1024 * 1024,
-        "G" | "GB" => 1024 * 1024 * 1024,
-        _ => return Err(BenchmarkError::InvalidSizeFormat(s.to_string())),
-    };
-
-    let result = num
-        .checked_mul(multiplier)
-        .ok_or_else(|| BenchmarkError::InvalidSizeFormat(format!("{} is too large", s)))?;
+    let result = crate::size::parse_size(s).map_err(|e| match e {
+        crate::size::SizeError::Overflow(_) => {
+            BenchmarkError::InvalidSizeFormat(format!("{} is too large", s))
+        }
+        _ => BenchmarkError::InvalidSizeFormat(s.to_string()),
+    })?;
 
     // Validate power of 2
     if !is_power_of_two(result) {
@@ -808,6 +956,55 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_latency_stats_percentiles() {
+        let mut samples: Vec<Duration> = (1..=100).rev().map(Duration::from_millis).collect();
+        let stats = LatencyStats::from_samples(&mut samples);
+        assert_eq!(stats.p50, Duration::from_millis(50));
+        assert_eq!(stats.p95, Duration::from_millis(95));
+        assert_eq!(stats.p99, Duration::from_millis(99));
+        assert_eq!(stats.max, Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_latency_stats_few_samples() {
+        assert_eq!(LatencyStats::from_samples(&mut []), LatencyStats::default());
+
+        let mut samples = vec![Duration::from_millis(3)];
+        let stats = LatencyStats::from_samples(&mut samples);
+        assert_eq!(stats.p50, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(3));
+    }
+
+    #[test]
+    fn test_benchmark_runner_records_latency() {
+        let config = BenchmarkConfig {
+            test_size: 64 * 1024,
+            block_size: 4 * 1024,
+            pattern: DataPattern::Zeros,
+            passes: 2,
+        };
+        let runner = BenchmarkRunner::new(config);
+        let result = runner
+            .run(
+                Cursor::new(vec![0u8; 128 * 1024]),
+                "/dev/test",
+                None::<fn(&BenchmarkProgress)>,
+            )
+            .unwrap();
+
+        let latency = result.summary.latency;
+        assert!(latency.p50 <= latency.p95 && latency.p95 <= latency.p99);
+        assert!(latency.p99 <= latency.max);
+        for pass in &result.passes {
+            assert!(pass.latency.max <= latency.max);
+        }
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert!(json["summary"]["latency"]["p99"].is_f64());
+        assert!(json["passes"][0]["latency"]["max"].is_f64());
+    }
+
     #[test]
     fn test_benchmark_runner_simple() {
         let config = BenchmarkConfig {
@@ -830,6 +1027,52 @@ mod tests {
         assert!(result.summary.average_speed_bps > 0);
     }
 
+    #[test]
+    fn test_benchmark_run_read() {
+        let config = BenchmarkConfig::new(64 * 1024, 4 * 1024);
+        let runner = BenchmarkRunner::new(config);
+        let data: Vec<u8> = (0..128 * 1024).map(|i| (i % 256) as u8).collect();
+        let mut cursor = Cursor::new(data.clone());
+
+        let result = runner
+            .run_read(&mut cursor, "/dev/test", None::<fn(&BenchmarkProgress)>)
+            .unwrap();
+
+        assert_eq!(result.mode, BenchmarkMode::Read);
+        assert_eq!(result.passes.len(), 1);
+        assert_eq!(result.passes[0].bytes_written, 64 * 1024);
+        assert_eq!(result.summary.total_bytes_written, 64 * 1024);
+        // Source is left untouched
+        assert_eq!(cursor.into_inner(), data);
+    }
+
+    #[test]
+    fn test_benchmark_run_read_stops_at_end_of_source() {
+        let config = BenchmarkConfig::new(64 * 1024, 4 * 1024);
+        let runner = BenchmarkRunner::new(config);
+        let cursor = Cursor::new(vec![0u8; 40 * 1024]);
+
+        let result = runner
+            .run_read(cursor, "/dev/test", None::<fn(&BenchmarkProgress)>)
+            .unwrap();
+
+        assert_eq!(result.passes[0].bytes_written, 40 * 1024);
+    }
+
+    #[test]
+    fn test_benchmark_mode_json() {
+        let runner = BenchmarkRunner::new(BenchmarkConfig::new(64 * 1024, 4 * 1024));
+        let result = runner
+            .run(
+                Cursor::new(vec![0u8; 64 * 1024]),
+                "/dev/test",
+                None::<fn(&BenchmarkProgress)>,
+            )
+            .unwrap();
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["mode"], "write");
+    }
+
     #[test]
     fn test_data_pattern_from_str() {
         assert_eq!(DataPattern::from_str("zeros").unwrap(), DataPattern::Zeros);
@@ -1099,9 +1342,17 @@ mod tests {
         assert_eq!(parse_size("1GB").unwrap(), 1024 * 1024 * 1024);
     }
 
+    #[test]
+    fn test_parse_size_shared_units() {
+        assert_eq!(parse_size("1T").unwrap(), 1024 * 1024 * 1024 * 1024);
+        assert_eq!(parse_size("64 KiB").unwrap(), 64 * 1024);
+        assert_eq!(parse_size("0.5M").unwrap(), 512 * 1024);
+        assert!(parse_size("1.5M").is_err()); // Not a power of 2
+    }
+
     #[test]
     fn test_parse_size_invalid_suffix() {
-        assert!(parse_size("4T").is_err());
+        assert!(parse_size("4P").is_err());
         assert!(parse_size("4X").is_err());
     }
 
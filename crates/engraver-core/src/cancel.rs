@@ -0,0 +1,262 @@
+//! Cooperative cancellation with a reason, and pausing
+//!
+//! A [`CancelToken`] is a cheap, cloneable handle shared between the code
+//! running an operation and whoever may stop it (a Ctrl+C handler, a GUI
+//! button, a watchdog). Unlike a bare `AtomicBool`, it records *why* the
+//! operation stopped, which is carried in [`Error::Cancelled`].
+//!
+//! A [`PauseHandle`] holds an operation between blocks instead of stopping
+//! it, e.g. to let a contended USB bus settle. A paused operation can still
+//! be cancelled.
+//!
+//! # Example
+//!
+//! ```
+//! use engraver_core::{CancelReason, CancelToken, Writer};
+//!
+//! let writer = Writer::new();
+//! let token: CancelToken = writer.cancel_handle();
+//!
+//! // From another thread, e.g. a Ctrl+C handler:
+//! token.cancel(CancelReason::UserRequested);
+//! assert_eq!(token.reason(), Some(CancelReason::UserRequested));
+//! ```
+
+use crate::{Error, Result};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How often a paused operation checks whether it may continue
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Why an operation was cancelled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CancelReason {
+    /// The user asked to stop (e.g. Ctrl+C or a Cancel button)
+    UserRequested,
+    /// The operation exceeded its time limit
+    Timeout,
+    /// The device failed or disappeared mid-operation
+    DeviceError,
+}
+
+impl CancelReason {
+    fn to_code(self) -> u8 {
+        match self {
+            CancelReason::UserRequested => 1,
+            CancelReason::Timeout => 2,
+            CancelReason::DeviceError => 3,
+        }
+    }
+
+    fn from_code(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(CancelReason::UserRequested),
+            2 => Some(CancelReason::Timeout),
+            3 => Some(CancelReason::DeviceError),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CancelReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CancelReason::UserRequested => write!(f, "requested by user"),
+            CancelReason::Timeout => write!(f, "timed out"),
+            CancelReason::DeviceError => write!(f, "device error"),
+        }
+    }
+}
+
+/// Shared cancellation flag that remembers the first reason given
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    state: Arc<AtomicU8>,
+}
+
+impl CancelToken {
+    /// Create a token that is not cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation
+    ///
+    /// Only the first call takes effect, so a later timeout does not hide
+    /// that the user cancelled first.
+    pub fn cancel(&self, reason: CancelReason) {
+        let _ =
+            self.state
+                .compare_exchange(0, reason.to_code(), Ordering::SeqCst, Ordering::SeqCst);
+    }
+
+    /// Whether cancellation has been requested
+    pub fn is_cancelled(&self) -> bool {
+        self.state.load(Ordering::SeqCst) != 0
+    }
+
+    /// The reason cancellation was requested, if it has been
+    pub fn reason(&self) -> Option<CancelReason> {
+        CancelReason::from_code(self.state.load(Ordering::SeqCst))
+    }
+
+    /// Clear a previous cancellation so the token can be reused
+    pub fn reset(&self) {
+        self.state.store(0, Ordering::SeqCst);
+    }
+
+    /// Return [`Error::Cancelled`] if cancellation has been requested
+    pub fn check(&self) -> Result<()> {
+        match self.reason() {
+            Some(reason) => Err(Error::Cancelled(reason)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Shared flag that holds an operation between blocks while set
+#[derive(Debug, Clone, Default)]
+pub struct PauseHandle {
+    paused: Arc<AtomicBool>,
+}
+
+impl PauseHandle {
+    /// Create a handle that is not paused
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hold the operation before its next block
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Let a paused operation continue
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    /// Whether a pause has been requested
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Block while paused, returning how long the wait took
+    ///
+    /// Returns [`Error::Cancelled`] if `cancel` is cancelled, paused or not.
+    pub(crate) fn wait(&self, cancel: &CancelToken) -> Result<Duration> {
+        cancel.check()?;
+        if !self.is_paused() {
+            return Ok(Duration::ZERO);
+        }
+
+        tracing::debug!("Paused");
+        let start = Instant::now();
+        while self.is_paused() {
+            std::thread::sleep(PAUSE_POLL_INTERVAL);
+            cancel.check()?;
+        }
+        tracing::debug!("Resumed after {:?}", start.elapsed());
+        Ok(start.elapsed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_token_default_not_cancelled() {
+        let token = CancelToken::new();
+        assert!(!token.is_cancelled());
+        assert_eq!(token.reason(), None);
+        assert!(token.check().is_ok());
+    }
+
+    #[test]
+    fn test_cancel_token_records_reason() {
+        let token = CancelToken::new();
+        token.cancel(CancelReason::Timeout);
+        assert!(token.is_cancelled());
+        assert_eq!(token.reason(), Some(CancelReason::Timeout));
+        assert!(matches!(
+            token.check(),
+            Err(Error::Cancelled(CancelReason::Timeout))
+        ));
+    }
+
+    #[test]
+    fn test_cancel_token_first_reason_wins() {
+        let token = CancelToken::new();
+        token.cancel(CancelReason::UserRequested);
+        token.cancel(CancelReason::DeviceError);
+        assert_eq!(token.reason(), Some(CancelReason::UserRequested));
+    }
+
+    #[test]
+    fn test_cancel_token_clones_share_state() {
+        let token = CancelToken::new();
+        let handle = token.clone();
+        handle.cancel(CancelReason::DeviceError);
+        assert_eq!(token.reason(), Some(CancelReason::DeviceError));
+
+        token.reset();
+        assert!(!handle.is_cancelled());
+    }
+
+    #[test]
+    fn test_cancel_reason_display() {
+        assert_eq!(CancelReason::UserRequested.to_string(), "requested by user");
+        assert_eq!(CancelReason::Timeout.to_string(), "timed out");
+        assert_eq!(CancelReason::DeviceError.to_string(), "device error");
+    }
+
+    #[test]
+    fn test_pause_handle_not_paused_returns_immediately() {
+        let pause = PauseHandle::new();
+        assert!(!pause.is_paused());
+        assert_eq!(pause.wait(&CancelToken::new()).unwrap(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_pause_handle_waits_until_resumed() {
+        let pause = PauseHandle::new();
+        pause.pause();
+        assert!(pause.is_paused());
+
+        let handle = pause.clone();
+        let resumer = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            handle.resume();
+        });
+
+        let waited = pause.wait(&CancelToken::new()).unwrap();
+        resumer.join().unwrap();
+        assert!(!pause.is_paused());
+        assert!(waited >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_pause_handle_cancel_ends_wait() {
+        let pause = PauseHandle::new();
+        pause.pause();
+        let token = CancelToken::new();
+
+        let canceller = token.clone();
+        let thread = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(60));
+            canceller.cancel(CancelReason::UserRequested);
+        });
+
+        assert!(matches!(
+            pause.wait(&token),
+            Err(Error::Cancelled(CancelReason::UserRequested))
+        ));
+        thread.join().unwrap();
+        assert!(pause.is_paused());
+    }
+}
@@ -0,0 +1,304 @@
+//! Local download cache for HTTP sources
+//!
+//! Writing straight from a URL ties the device write to the network: if the
+//! connection drops for good, the whole image has to be fetched again. A
+//! [`DownloadCache`] first downloads the image to `{dir}/{key}.img`, where
+//! `key` is a hash of the URL followed by the server's ETag, if any. An
+//! interrupted download is kept as `{key}.img.part` and resumed with a ranged
+//! request next time. The device write then reads the local file, so it can
+//! be resumed like any other local write.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use engraver_core::DownloadCache;
+//!
+//! let mut cache = DownloadCache::new("/var/cache/engraver")
+//!     .on_progress(|done, total| println!("{} of {:?} bytes", done, total));
+//! let image = cache.fetch("https://example.com/image.img")?;
+//! println!("Cached at {}", image.display());
+//! # Ok::<(), engraver_core::Error>(())
+//! ```
+
+use std::fs::OpenOptions;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::cancel::CancelToken;
+use crate::error::{Error, Result};
+use crate::source::{
+    detect_source_type, validate_http_source, HttpOptions, HttpSource, SourceType,
+};
+
+/// Extension of a cached image that is still downloading
+const PARTIAL_EXTENSION: &str = "img.part";
+
+/// Extension of a complete cached image
+const CACHED_EXTENSION: &str = "img";
+
+/// Longest ETag-derived cache key, in characters
+const MAX_KEY_LENGTH: usize = 64;
+
+/// Buffer size for copying a download to disk
+const DOWNLOAD_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Download progress callback: bytes on disk so far and the total, if known
+pub type DownloadProgressCallback = Box<dyn FnMut(u64, Option<u64>) + Send>;
+
+/// Downloads HTTP sources into a local directory before they are written
+pub struct DownloadCache {
+    dir: PathBuf,
+    options: HttpOptions,
+    progress_callback: Option<DownloadProgressCallback>,
+    cancel_flag: CancelToken,
+}
+
+impl DownloadCache {
+    /// Create a cache that stores downloads in `dir`
+    ///
+    /// The directory is created on the first [`fetch`](Self::fetch).
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            options: HttpOptions::default(),
+            progress_callback: None,
+            cancel_flag: CancelToken::new(),
+        }
+    }
+
+    /// Set the timeout and retry options used for the download
+    pub fn http_options(mut self, options: HttpOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Set progress callback
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(u64, Option<u64>) + Send + 'static,
+    {
+        self.progress_callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Get a handle to cancel the download
+    pub fn cancel_handle(&self) -> CancelToken {
+        self.cancel_flag.clone()
+    }
+
+    /// Directory downloads are stored in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Where `url` is cached, given the ETag the server reports for it
+    pub fn path_for(&self, url: &str, etag: Option<&str>) -> PathBuf {
+        self.dir
+            .join(cache_key(url, etag))
+            .with_extension(CACHED_EXTENSION)
+    }
+
+    /// Download `url` into the cache and return the local path
+    ///
+    /// A complete cached copy is returned without downloading only when the
+    /// server reports both an ETag and a size, and the size matches; without
+    /// them a stale copy can't be ruled out, so the image is fetched again. A
+    /// partial copy is resumed if the server accepts ranged requests, and
+    /// restarted otherwise. If the download ends short of the reported size
+    /// an error is returned and the partial file is kept for the next attempt.
+    ///
+    /// The URL is checked with the same [`HttpOptions`] as the download.
+    pub fn fetch(&mut self, url: &str) -> Result<PathBuf> {
+        self.cancel_flag.reset();
+
+        if detect_source_type(url) != SourceType::Remote {
+            return Err(Error::InvalidConfig(format!(
+                "Only HTTP(S) sources can be cached: {}",
+                url
+            )));
+        }
+        let info = validate_http_source(url, &self.options)?;
+
+        let path = self.path_for(url, info.etag.as_deref());
+        let total = info.size;
+        if let (Some(_), Some(total), Ok(metadata)) =
+            (info.etag.as_deref(), total, std::fs::metadata(&path))
+        {
+            if metadata.len() == total {
+                tracing::debug!("Using cached download {:?}", path);
+                return Ok(path);
+            }
+        }
+
+        std::fs::create_dir_all(&self.dir)?;
+        let partial = path.with_extension(PARTIAL_EXTENSION);
+        let mut offset = if info.resumable {
+            std::fs::metadata(&partial).map_or(0, |m| m.len())
+        } else {
+            0
+        };
+        if total.is_some_and(|total| offset > total) {
+            offset = 0;
+        }
+
+        // The GET response's length is authoritative; HEAD answers are not always
+        let total = if offset == 0 || total != Some(offset) {
+            self.download(url, &partial, offset, total)?
+        } else {
+            total
+        };
+
+        let downloaded = std::fs::metadata(&partial)?.len();
+        if let Some(total) = total {
+            if downloaded != total {
+                return Err(Error::Network {
+                    message: format!(
+                        "Download of {} incomplete: got {} of {} bytes",
+                        url, downloaded, total
+                    ),
+                    source: None,
+                });
+            }
+        }
+
+        std::fs::rename(&partial, &path)?;
+        tracing::debug!("Cached {} at {:?}", url, path);
+        Ok(path)
+    }
+
+    /// Stream `url` into `partial`, appending from `offset` if the server resumes there
+    ///
+    /// Returns the total size reported for the download, if any.
+    fn download(
+        &mut self,
+        url: &str,
+        partial: &Path,
+        offset: u64,
+        total: Option<u64>,
+    ) -> Result<Option<u64>> {
+        let mut source = HttpSource::open_with_options(url, offset, self.options.clone())?;
+        let total = source.info().size.or(total);
+        let resumed = offset > 0 && source.resumed();
+        if resumed {
+            tracing::info!("Resuming download of {} at byte {}", url, offset);
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resumed)
+            .truncate(!resumed)
+            .open(partial)?;
+        let mut downloaded = if resumed { offset } else { 0 };
+        let mut buffer = vec![0u8; DOWNLOAD_BUFFER_SIZE];
+
+        loop {
+            self.cancel_flag.check()?;
+
+            let n = match source.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(Error::Io(e)),
+            };
+            file.write_all(&buffer[..n])?;
+            downloaded += n as u64;
+
+            if let Some(ref mut callback) = self.progress_callback {
+                callback(downloaded, total);
+            }
+        }
+
+        file.sync_all()?;
+        Ok(total)
+    }
+}
+
+/// File name stem for a cached URL: a hash of the URL, then its ETag if usable
+///
+/// The URL is always part of the key, since different URLs can share an ETag.
+fn cache_key(url: &str, etag: Option<&str>) -> String {
+    let url_hash = format!("{:016x}", fnv1a(url.as_bytes()));
+    let from_etag: String = etag
+        .unwrap_or_default()
+        .trim_start_matches("W/")
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .take(MAX_KEY_LENGTH)
+        .collect();
+    if from_etag.is_empty() {
+        url_hash
+    } else {
+        format!("{}-{}", url_hash, from_etag)
+    }
+}
+
+/// 64-bit FNV-1a, stable across builds unlike `std`'s hasher
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_uses_etag() {
+        let hash = cache_key("http://a/b.img", None);
+        assert_eq!(
+            cache_key("http://a/b.img", Some("\"abc-123\"")),
+            format!("{}-abc-123", hash)
+        );
+        assert_eq!(
+            cache_key("http://a/b.img", Some("W/\"abc\"")),
+            format!("{}-abc", hash)
+        );
+    }
+
+    #[test]
+    fn test_cache_key_separates_urls_with_same_etag() {
+        assert_ne!(
+            cache_key("http://a/b.img", Some("\"v1\"")),
+            cache_key("http://cdn/b.img", Some("\"v1\""))
+        );
+    }
+
+    #[test]
+    fn test_cache_key_hashes_url_without_etag() {
+        let key = cache_key("http://a/b.img", None);
+        assert_eq!(key.len(), 16);
+        assert_eq!(key, cache_key("http://a/b.img", Some("\"\"")));
+        assert_ne!(key, cache_key("http://a/c.img", None));
+    }
+
+    #[test]
+    fn test_cache_key_strips_path_characters() {
+        let key = cache_key("http://a/b.img", Some("\"../../etc/passwd\""));
+        assert!(key.ends_with("-etcpasswd"));
+        assert!(!key.contains('/'));
+    }
+
+    #[test]
+    fn test_path_for() {
+        let cache = DownloadCache::new("/tmp/cache");
+        assert_eq!(
+            cache.path_for("http://a/b.img", Some("\"v1\"")),
+            PathBuf::from(format!(
+                "/tmp/cache/{:016x}-v1.img",
+                fnv1a(b"http://a/b.img")
+            ))
+        );
+    }
+
+    #[test]
+    fn test_fetch_rejects_local_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = DownloadCache::new(dir.path());
+        assert!(matches!(
+            cache.fetch("/tmp/image.img"),
+            Err(Error::InvalidConfig(_))
+        ));
+    }
+}
@@ -1,5 +1,6 @@
 //! Error types for the Engraver core library
 
+use crate::cancel::CancelReason;
 use thiserror::Error;
 
 /// Main error type for Engraver operations
@@ -61,9 +62,9 @@ pub enum Error {
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
 
-    /// Operation was cancelled
-    #[error("Operation cancelled")]
-    Cancelled,
+    /// Operation was cancelled, with the reason it was stopped
+    #[error("Operation cancelled: {0}")]
+    Cancelled(CancelReason),
 
     /// Partial write occurred
     #[error("Partial write: expected {expected} bytes, wrote {actual} bytes")]
@@ -78,6 +79,20 @@ pub enum Error {
     #[error("Device busy: {0}")]
     DeviceBusy(String),
 
+    /// The target ran out of space before the whole source was written
+    #[error("Device full after writing {bytes_written} bytes")]
+    DeviceFull {
+        /// Offset up to which the target holds the source's data
+        bytes_written: u64,
+    },
+
+    /// A block would have been written at or past the write limit
+    #[error("Refusing to write past byte {limit}: the rest of the device is preserved")]
+    WriteLimitExceeded {
+        /// First byte of the preserved region
+        limit: u64,
+    },
+
     /// Size mismatch between source and target
     #[error("Size mismatch: source is {source_size} bytes, target is {target_size} bytes")]
     SizeMismatch {
@@ -105,6 +120,81 @@ pub enum Error {
     PartitionParseError(String),
 }
 
+/// Broad category of an [`Error`], see [`Error::kind`]
+///
+/// Categories are coarser than variants and stay stable as variants are
+/// added or split, so callers can branch on them without an exhaustive match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Reading or writing failed
+    Io,
+    /// A source file or device does not exist
+    NotFound,
+    /// Access was denied
+    Permission,
+    /// A remote source could not be fetched
+    Network,
+    /// Data did not match its expected contents or checksum
+    Checksum,
+    /// The operation was cancelled or timed out
+    Cancelled,
+    /// A setting or argument is invalid
+    Config,
+    /// A compressed source could not be decoded
+    Decompression,
+    /// The target device cannot be used as requested
+    Device,
+    /// Anything not covered by another kind
+    Other,
+}
+
+impl Error {
+    /// The broad category of this error
+    ///
+    /// I/O errors are classified by their [`std::io::ErrorKind`], so a missing
+    /// file is [`ErrorKind::NotFound`] whether it surfaced as
+    /// [`Error::SourceNotFound`] or a raw [`Error::Io`].
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::SourceNotFound(_) | Error::DeviceNotFound(_) => ErrorKind::NotFound,
+            Error::Io(io) => match io.kind() {
+                std::io::ErrorKind::NotFound => ErrorKind::NotFound,
+                std::io::ErrorKind::PermissionDenied => ErrorKind::Permission,
+                _ => ErrorKind::Io,
+            },
+            Error::PermissionDenied(_) => ErrorKind::Permission,
+            Error::Network { .. } => ErrorKind::Network,
+            Error::VerificationFailed { .. } | Error::ChecksumMismatch { .. } => {
+                ErrorKind::Checksum
+            }
+            Error::Cancelled(_) => ErrorKind::Cancelled,
+            Error::InvalidConfig(_) => ErrorKind::Config,
+            Error::Decompression { .. } => ErrorKind::Decompression,
+            Error::SystemDriveProtection(_)
+            | Error::PartialWrite { .. }
+            | Error::DeviceBusy(_)
+            | Error::DeviceFull { .. }
+            | Error::WriteLimitExceeded { .. }
+            | Error::SizeMismatch { .. } => ErrorKind::Device,
+            Error::Unknown(_) | Error::PartitionParseError(_) => ErrorKind::Other,
+        }
+    }
+}
+
+impl From<engraver_platform::PlatformError> for Error {
+    fn from(err: engraver_platform::PlatformError) -> Self {
+        use engraver_platform::PlatformError;
+        match err {
+            PlatformError::Io(io) => Error::Io(io),
+            PlatformError::PermissionDenied(msg) => Error::PermissionDenied(msg),
+            PlatformError::DeviceBusy(msg) => Error::DeviceBusy(msg),
+            PlatformError::DeviceNotFound(msg) => Error::DeviceNotFound(msg),
+            other => Error::Io(std::io::Error::other(other.to_string())),
+        }
+    }
+}
+
 /// Result type alias using the Engraver error type
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -117,8 +207,11 @@ mod tests {
         let err = Error::SourceNotFound("/path/to/file.iso".to_string());
         assert!(err.to_string().contains("/path/to/file.iso"));
 
-        let err = Error::Cancelled;
-        assert_eq!(err.to_string(), "Operation cancelled");
+        let err = Error::Cancelled(CancelReason::UserRequested);
+        assert_eq!(err.to_string(), "Operation cancelled: requested by user");
+
+        let err = Error::Cancelled(CancelReason::Timeout);
+        assert_eq!(err.to_string(), "Operation cancelled: timed out");
 
         let err = Error::PartialWrite {
             expected: 4096,
@@ -135,6 +228,19 @@ mod tests {
         assert!(matches!(err, Error::Io(_)));
     }
 
+    #[test]
+    fn test_error_from_platform() {
+        use engraver_platform::PlatformError;
+
+        let err: Error = PlatformError::DeviceBusy("/dev/sdb".to_string()).into();
+        assert!(matches!(err, Error::DeviceBusy(_)));
+        assert_eq!(err.kind(), ErrorKind::Device);
+
+        let err: Error = PlatformError::NotSupported("fadvise".to_string()).into();
+        assert_eq!(err.kind(), ErrorKind::Io);
+        assert!(err.to_string().contains("fadvise"));
+    }
+
     #[test]
     fn test_verification_failed_error() {
         let err = Error::VerificationFailed {
@@ -265,7 +371,7 @@ mod tests {
 
     #[test]
     fn test_error_debug_format() {
-        let err = Error::Cancelled;
+        let err = Error::Cancelled(CancelReason::UserRequested);
         let debug_str = format!("{:?}", err);
         assert!(debug_str.contains("Cancelled"));
 
@@ -325,4 +431,82 @@ mod tests {
             "Network error without source should return None"
         );
     }
+
+    #[test]
+    fn test_error_kind() {
+        let io = |kind| Error::Io(std::io::Error::new(kind, "test"));
+        let cases = [
+            (Error::SourceNotFound("a".into()), ErrorKind::NotFound),
+            (Error::DeviceNotFound("a".into()), ErrorKind::NotFound),
+            (Error::SystemDriveProtection("a".into()), ErrorKind::Device),
+            (io(std::io::ErrorKind::Other), ErrorKind::Io),
+            (io(std::io::ErrorKind::NotFound), ErrorKind::NotFound),
+            (
+                io(std::io::ErrorKind::PermissionDenied),
+                ErrorKind::Permission,
+            ),
+            (
+                Error::VerificationFailed {
+                    offset: 0,
+                    expected: "a".into(),
+                    actual: "b".into(),
+                },
+                ErrorKind::Checksum,
+            ),
+            (
+                Error::Network {
+                    message: "a".into(),
+                    source: None,
+                },
+                ErrorKind::Network,
+            ),
+            (
+                Error::Decompression {
+                    message: "a".into(),
+                    source: None,
+                },
+                ErrorKind::Decompression,
+            ),
+            (Error::PermissionDenied("a".into()), ErrorKind::Permission),
+            (Error::InvalidConfig("a".into()), ErrorKind::Config),
+            (
+                Error::Cancelled(CancelReason::UserRequested),
+                ErrorKind::Cancelled,
+            ),
+            (
+                Error::Cancelled(CancelReason::Timeout),
+                ErrorKind::Cancelled,
+            ),
+            (
+                Error::PartialWrite {
+                    expected: 2,
+                    actual: 1,
+                },
+                ErrorKind::Device,
+            ),
+            (Error::DeviceBusy("a".into()), ErrorKind::Device),
+            (Error::DeviceFull { bytes_written: 1 }, ErrorKind::Device),
+            (Error::WriteLimitExceeded { limit: 1 }, ErrorKind::Device),
+            (
+                Error::SizeMismatch {
+                    source_size: 2,
+                    target_size: 1,
+                },
+                ErrorKind::Device,
+            ),
+            (
+                Error::ChecksumMismatch {
+                    expected: "a".into(),
+                    actual: "b".into(),
+                },
+                ErrorKind::Checksum,
+            ),
+            (Error::Unknown("a".into()), ErrorKind::Other),
+            (Error::PartitionParseError("a".into()), ErrorKind::Other),
+        ];
+
+        for (err, kind) in cases {
+            assert_eq!(err.kind(), kind, "{:?}", err);
+        }
+    }
 }
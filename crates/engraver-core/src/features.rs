@@ -0,0 +1,176 @@
+//! Features compiled into this build
+//!
+//! Compression, remote sources, checksums and cloud storage are optional
+//! Cargo features, so two builds of the same version can accept different
+//! sources. [`build_features`] reports what this one includes, for version
+//! output and bug reports.
+//!
+//! ## Example
+//!
+//! ```
+//! let features = engraver_core::build_features();
+//! println!("engraver-core {} ({})", features.version, features.enabled().join(", "));
+//! ```
+
+use serde::Serialize;
+
+use crate::source::SourceType;
+use crate::verifier::ChecksumAlgorithm;
+
+/// The optional features this build of `engraver-core` was compiled with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BuildFeatures {
+    /// Crate version, e.g. `0.1.0`
+    pub version: &'static str,
+    /// gzip, xz, zstd and bzip2 sources, and tar archives
+    pub compression: bool,
+    /// HTTP(S) sources
+    pub remote: bool,
+    /// Checksum calculation and verification
+    pub checksum: bool,
+    /// Partition table inspection
+    pub partition_info: bool,
+    /// S3 and S3-compatible sources
+    pub s3: bool,
+    /// Google Cloud Storage sources
+    pub gcs: bool,
+    /// Azure Blob Storage sources
+    pub azure: bool,
+}
+
+/// Describe the features this build was compiled with
+pub fn build_features() -> BuildFeatures {
+    BuildFeatures {
+        version: env!("CARGO_PKG_VERSION"),
+        compression: cfg!(feature = "compression"),
+        remote: cfg!(feature = "remote"),
+        checksum: cfg!(feature = "checksum"),
+        partition_info: cfg!(feature = "partition-info"),
+        s3: cfg!(feature = "s3"),
+        gcs: cfg!(feature = "gcs"),
+        azure: cfg!(feature = "azure"),
+    }
+}
+
+impl BuildFeatures {
+    /// Cargo names of the enabled features, e.g. `["compression", "remote"]`
+    pub fn enabled(&self) -> Vec<&'static str> {
+        self.flags()
+            .into_iter()
+            .filter_map(|(enabled, name)| enabled.then_some(name))
+            .collect()
+    }
+
+    /// Cargo names of the optional features this build was compiled without
+    pub fn disabled(&self) -> Vec<&'static str> {
+        self.flags()
+            .into_iter()
+            .filter_map(|(enabled, name)| (!enabled).then_some(name))
+            .collect()
+    }
+
+    fn flags(&self) -> [(bool, &'static str); 7] {
+        [
+            (self.compression, "compression"),
+            (self.remote, "remote"),
+            (self.checksum, "checksum"),
+            (self.partition_info, "partition-info"),
+            (self.s3, "s3"),
+            (self.gcs, "gcs"),
+            (self.azure, "azure"),
+        ]
+    }
+
+    /// Source types this build can open
+    pub fn source_types(&self) -> Vec<SourceType> {
+        let mut types = vec![SourceType::LocalFile, SourceType::Split];
+        if self.remote {
+            types.push(SourceType::Remote);
+        }
+        if self.compression {
+            types.extend([
+                SourceType::Gzip,
+                SourceType::Xz,
+                SourceType::Zstd,
+                SourceType::Bzip2,
+                SourceType::Tar,
+                SourceType::TarGz,
+            ]);
+        }
+        #[cfg(feature = "s3")]
+        if self.s3 {
+            types.push(SourceType::S3);
+        }
+        #[cfg(feature = "gcs")]
+        if self.gcs {
+            types.push(SourceType::Gcs);
+        }
+        #[cfg(feature = "azure")]
+        if self.azure {
+            types.push(SourceType::Azure);
+        }
+        types
+    }
+
+    /// Checksum algorithms this build can calculate; empty without `checksum`
+    pub fn checksum_algorithms(&self) -> &'static [ChecksumAlgorithm] {
+        if self.checksum {
+            ChecksumAlgorithm::all()
+        } else {
+            &[]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_features_match_cfg() {
+        let features = build_features();
+        assert_eq!(features.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(features.compression, cfg!(feature = "compression"));
+        assert_eq!(features.remote, cfg!(feature = "remote"));
+        assert_eq!(features.checksum, cfg!(feature = "checksum"));
+        assert_eq!(features.enabled().contains(&"remote"), features.remote);
+    }
+
+    #[test]
+    fn test_enabled_and_disabled_partition_features() {
+        let features = BuildFeatures {
+            s3: false,
+            checksum: true,
+            ..build_features()
+        };
+        assert!(features.disabled().contains(&"s3"));
+        assert!(!features.disabled().contains(&"checksum"));
+        assert_eq!(features.enabled().len() + features.disabled().len(), 7);
+    }
+
+    #[test]
+    fn test_source_types_follow_features() {
+        let features = build_features();
+        let types = features.source_types();
+        assert!(types.contains(&SourceType::LocalFile));
+        assert_eq!(types.contains(&SourceType::Gzip), features.compression);
+        assert_eq!(types.contains(&SourceType::Remote), features.remote);
+    }
+
+    #[test]
+    fn test_checksum_algorithms_empty_without_checksum() {
+        let features = BuildFeatures {
+            checksum: false,
+            ..build_features()
+        };
+        assert!(features.checksum_algorithms().is_empty());
+
+        let features = BuildFeatures {
+            checksum: true,
+            ..build_features()
+        };
+        assert!(features
+            .checksum_algorithms()
+            .contains(&ChecksumAlgorithm::Sha256));
+    }
+}
@@ -8,8 +8,13 @@
 //! - `writer`: High-performance block writing engine with progress tracking
 //! - `verifier`: Post-write verification and checksum validation
 //! - `error`: Error types and result aliases
+//! - `features`: Which optional features this build was compiled with
+//! - `cancel`: Cancellation tokens that record why an operation stopped, and pausing
 //! - `config`: Runtime configuration
+//! - `download`: Local cache that HTTP sources are downloaded to before writing
 //! - `settings`: Persistent user settings from configuration file
+//! - `history`: Append-only log of completed writes
+//! - `size`: Human-friendly size parsing shared by the CLI and library users
 //!
 //! ## Example
 //!
@@ -38,51 +43,87 @@
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 pub mod benchmark;
+pub mod cancel;
 pub mod config;
+#[cfg(feature = "remote")]
+pub mod download;
 pub mod error;
+pub mod features;
+pub mod history;
 #[cfg(feature = "partition-info")]
 pub mod partition;
 pub mod resume;
 pub mod settings;
+pub mod size;
 pub mod source;
+#[cfg(test)]
+mod test_util;
 pub mod verifier;
 pub mod writer;
 
 pub use benchmark::{
     format_duration as benchmark_format_duration, format_size,
-    format_speed as benchmark_format_speed, is_power_of_two, parse_block_sizes, parse_size,
-    BenchmarkConfig, BenchmarkError, BenchmarkProgress, BenchmarkResult, BenchmarkRunner,
-    BenchmarkSummary, BlockSizeTestResult, DataPattern, PassResult,
+    format_speed as benchmark_format_speed, is_power_of_two, parse_block_sizes, BenchmarkConfig,
+    BenchmarkError, BenchmarkMode, BenchmarkProgress, BenchmarkResult, BenchmarkRunner,
+    BenchmarkSummary, BlockSizeTestResult, DataPattern, LatencyStats, PassResult,
 };
+pub use cancel::{CancelReason, CancelToken, PauseHandle};
 pub use config::Config;
-pub use error::{Error, Result};
+#[cfg(feature = "remote")]
+pub use download::{DownloadCache, DownloadProgressCallback};
+pub use error::{Error, ErrorKind, Result};
+pub use features::{build_features, BuildFeatures};
+pub use history::{
+    default_history_dir, redact_url, strip_url_secrets, BenchmarkLog, BenchmarkRecord,
+    HistoryEntry, HistoryLog,
+};
 #[cfg(feature = "partition-info")]
 pub use partition::{
     inspect_from_buffer, inspect_partitions, read_partition_header, PartitionInfo,
     PartitionTableInfo, PartitionTableType, PARTITION_HEADER_SIZE,
 };
 pub use resume::{
-    default_checkpoint_dir, validate_checkpoint, CheckpointManager, CheckpointValidation,
-    WriteCheckpoint, CHECKPOINT_VERSION,
+    default_checkpoint_dir, validate_checkpoint, validate_checkpoint_for,
+    validate_verify_checkpoint, CheckpointAutosave, CheckpointManager, CheckpointValidation,
+    TargetIdentity, VerifyCheckpoint, WriteCheckpoint, CHECKPOINT_VERSION,
 };
 pub use settings::{
     BehaviorSettings, BenchmarkSettings, ChecksumSettings, NetworkSettings, Settings,
-    SettingsError, WriteSettings, DEFAULT_HTTP_TIMEOUT_SECS, DEFAULT_RETRY_ATTEMPTS,
+    SettingsError, WriteSettings, DEFAULT_CHECKPOINT_INTERVAL_SECS, DEFAULT_HTTP_BACKOFF_BASE_MS,
+    DEFAULT_HTTP_MAX_RETRIES, DEFAULT_HTTP_TIMEOUT_SECS, DEFAULT_RETRY_ATTEMPTS,
     DEFAULT_RETRY_DELAY_MS, DEFAULT_VALIDATION_TIMEOUT_SECS,
 };
+pub use size::{parse_block_size_clamped, parse_size, SizeError};
+#[cfg(feature = "s3")]
+pub use source::S3_ENDPOINT_ENV;
+pub use source::{
+    boot_signature, detect_source_type, get_source_size, image_kind, is_block_device,
+    looks_like_disk_image, select_tar_entry, split_parts, validate_source,
+    validate_source_with_entry, validate_source_with_settings, ByteCounter, CountingReader,
+    DeviceSource, Source, SourceInfo, SourceType, SplitSource, TarEntry, BOOT_SECTOR_SIZE,
+    DEFAULT_READ_BUFFER_SIZE, DISK_IMAGE_PROBE_SIZE,
+};
+#[cfg(feature = "compression")]
 pub use source::{
-    detect_source_type, get_source_size, validate_source, validate_source_with_settings, Source,
-    SourceInfo, SourceType, DEFAULT_READ_BUFFER_SIZE,
+    list_tar_entries, TarSource, ZstdOptions, ZSTD_WINDOW_LOG_MAX, ZSTD_WINDOW_LOG_MIN,
 };
 #[cfg(any(feature = "s3", feature = "gcs", feature = "azure"))]
 pub use source::{CloudSource, DEFAULT_CLOUD_CHUNK_SIZE};
+#[cfg(feature = "remote")]
+pub use source::{HttpClient, HttpClientBuilder, HttpOptions};
+#[cfg(feature = "checksum")]
+pub use verifier::verify_device_against_sums;
 pub use verifier::{
-    auto_detect_checksum, find_checksum_for_file, parse_checksum_file, verify_write, Checksum,
-    ChecksumAlgorithm, ChecksumEntry, DetectedChecksum, VerificationOperation,
-    VerificationProgress, VerificationResult, Verifier, VerifyConfig, DEFAULT_VERIFY_BLOCK_SIZE,
-    MAX_VERIFY_BLOCK_SIZE, MIN_VERIFY_BLOCK_SIZE,
+    auto_detect_checksum, checksum_from_sums, find_checksum_for_file, parse_checksum_file,
+    verify_write, Checksum, ChecksumAlgorithm, ChecksumEntry, DetectedChecksum,
+    VerificationOperation, VerificationProgress, VerificationResult, Verifier, VerifyConfig,
+    VerifyPhase, DEFAULT_MAX_RECORDED_MISMATCHES, DEFAULT_VERIFY_BLOCK_SIZE, MAX_VERIFY_BLOCK_SIZE,
+    MIN_VERIFY_BLOCK_SIZE, VERIFY_SAMPLE_SEED,
 };
 pub use writer::{
-    format_duration, format_speed, ReadSeek, WriteConfig, WritePhase, WriteProgress, WriteResult,
-    Writer, DEFAULT_BLOCK_SIZE, MAX_BLOCK_SIZE, MIN_BLOCK_SIZE,
+    format_duration, format_speed, BlockSource, CheckpointCallback, PhaseCallback, Prefetch,
+    ProgressInterval, ReadSeek, StreamTarget, WriteConfig, WritePhase, WriteProgress, WriteResult,
+    Writer, BLANK_CHECK_SAMPLES, DEFAULT_BLOCK_SIZE, DEFAULT_BUFFER_COUNT,
+    DEFAULT_DEVICE_BLOCK_SIZE, DEFAULT_PROGRESS_INTERVAL, DEFAULT_SMALL_HEAD_BYTES, MAX_BLOCK_SIZE,
+    MAX_BUFFER_COUNT, MIN_BLOCK_SIZE, RESUME_VERIFY_BLOCKS,
 };
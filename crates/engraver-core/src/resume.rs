@@ -4,11 +4,15 @@
 //! When a write is interrupted (Ctrl+C, power failure, etc.), the checkpoint file
 //! allows resuming from the last successfully written block.
 //!
+//! An interrupted verification is recorded the same way with a
+//! [`VerifyCheckpoint`], stored next to write checkpoints under a distinct
+//! extension.
+//!
 //! # Example
 //!
 //! ```no_run
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
-//! use engraver_core::resume::{WriteCheckpoint, CheckpointManager};
+//! use engraver_core::resume::{CheckpointAutosave, CheckpointManager, WriteCheckpoint};
 //! use engraver_core::{WriteConfig, SourceInfo};
 //!
 //! // Start a new write with checkpointing
@@ -20,6 +24,11 @@
 //! // ... write blocks, periodically calling:
 //! manager.save(&checkpoint)?;
 //!
+//! // Or save from a background thread instead of the write loop:
+//! let autosave = CheckpointAutosave::spawn(manager.clone());
+//! autosave.submit(checkpoint.clone());
+//! autosave.finish();
+//!
 //! // On successful completion:
 //! manager.remove(&checkpoint)?;
 //! # Ok(())
@@ -27,10 +36,13 @@
 //! ```
 
 use crate::{Error, Result, SourceInfo, SourceType, WriteConfig};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::{BufReader, BufWriter};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread::JoinHandle;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Current version of the checkpoint format
@@ -42,6 +54,9 @@ pub const CHECKPOINT_DIR_NAME: &str = "engraver";
 /// Checkpoint file extension
 pub const CHECKPOINT_EXTENSION: &str = "checkpoint";
 
+/// Verify checkpoint file extension
+pub const VERIFY_CHECKPOINT_EXTENSION: &str = "vcheckpoint";
+
 /// A checkpoint representing the state of an interrupted write operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WriteCheckpoint {
@@ -70,6 +85,10 @@ pub struct WriteCheckpoint {
     /// Whether the source supports resume (HTTP Range requests)
     pub source_resumable: bool,
 
+    /// ETag the server reported for a remote source (if any)
+    #[serde(default)]
+    pub source_etag: Option<String>,
+
     // ── Target Information ──────────────────────────────────────────────────
     /// Path to the target device
     pub target_path: String,
@@ -77,6 +96,14 @@ pub struct WriteCheckpoint {
     /// Size of the target device in bytes
     pub target_size: u64,
 
+    /// Serial number of the target device (if reported)
+    #[serde(default)]
+    pub target_serial: Option<String>,
+
+    /// Model of the target device (if reported)
+    #[serde(default)]
+    pub target_model: Option<String>,
+
     // ── Write Configuration ─────────────────────────────────────────────────
     /// Block size used for writing
     pub block_size: usize,
@@ -110,6 +137,10 @@ pub struct WriteCheckpoint {
 
     /// Total number of block retries across all attempts
     pub total_retries: u32,
+
+    /// Notes about adjustments made on resume (e.g. offset rewinds)
+    #[serde(default)]
+    pub notes: Vec<String>,
 }
 
 /// Serializable subset of WriteConfig for checkpoints
@@ -157,7 +188,8 @@ impl WriteCheckpoint {
 
         // Determine source properties
         let source_seekable = matches!(source_info.source_type, SourceType::LocalFile);
-        let source_resumable = matches!(source_info.source_type, SourceType::Remote);
+        let source_resumable =
+            matches!(source_info.source_type, SourceType::Remote) && source_info.resumable;
 
         let total_blocks = source_info
             .size
@@ -172,8 +204,11 @@ impl WriteCheckpoint {
             source_header_hash: None, // Set later after computing
             source_seekable,
             source_resumable,
+            source_etag: source_info.etag.clone(),
             target_path: target_path.to_string(),
             target_size,
+            target_serial: None,
+            target_model: None,
             block_size: config.block_size,
             config: WriteConfigCheckpoint::from(config),
             bytes_written: 0,
@@ -184,9 +219,20 @@ impl WriteCheckpoint {
             elapsed_seconds: 0.0,
             resume_count: 0,
             total_retries: 0,
+            notes: Vec::new(),
         }
     }
 
+    /// Record the identity (serial/model) of the target device
+    ///
+    /// Used by [`validate_checkpoint_for`] to detect that a different drive has
+    /// been connected since the checkpoint was written.
+    pub fn with_target_identity(mut self, target: &TargetIdentity) -> Self {
+        self.target_serial = target.serial.clone();
+        self.target_model = target.model.clone();
+        self
+    }
+
     /// Update progress in the checkpoint
     pub fn update_progress(&mut self, bytes_written: u64, blocks_written: u64, elapsed: Duration) {
         self.bytes_written = bytes_written;
@@ -207,6 +253,22 @@ impl WriteCheckpoint {
             .as_secs();
     }
 
+    /// Move the resume point back to `offset` after the tail failed verification
+    ///
+    /// Does nothing if `offset` is not before the current progress. The rewind
+    /// is recorded in [`notes`](Self::notes) against the current resume count.
+    pub fn rewind_to(&mut self, offset: u64) {
+        if offset >= self.bytes_written {
+            return;
+        }
+        self.notes.push(format!(
+            "Resume {}: rewound from byte {} to {} after verification mismatch",
+            self.resume_count, self.bytes_written, offset
+        ));
+        self.bytes_written = offset;
+        self.blocks_written = offset / self.block_size.max(1) as u64;
+    }
+
     /// Add retry count
     pub fn add_retries(&mut self, count: u32) {
         self.total_retries += count;
@@ -228,14 +290,142 @@ impl WriteCheckpoint {
 
     /// Get the checkpoint filename for this session
     pub fn filename(&self) -> String {
-        // Use a hash of source+target for the filename to avoid collisions
-        let key = format!("{}:{}", self.source_path, self.target_path);
-        let hash = simple_hash(&key);
-        format!("{:016x}.{}", hash, CHECKPOINT_EXTENSION)
+        checkpoint_filename(&self.source_path, &self.target_path, CHECKPOINT_EXTENSION)
+    }
+}
+
+/// A checkpoint representing the state of an interrupted verification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyCheckpoint {
+    /// Checkpoint format version (for future compatibility)
+    pub version: u32,
+
+    /// Unique identifier for this verify session
+    pub session_id: String,
+
+    // ── Source Information ──────────────────────────────────────────────────
+    /// Path to the source image
+    pub source_path: String,
+
+    /// Number of bytes being verified (the source size)
+    pub source_size: u64,
+
+    /// Modification time of the source (Unix timestamp, if known)
+    #[serde(default)]
+    pub source_modified: Option<u64>,
+
+    // ── Target Information ──────────────────────────────────────────────────
+    /// Path to the target device
+    pub target_path: String,
+
+    /// Size of the target device in bytes
+    pub target_size: u64,
+
+    /// Serial number of the target device (if reported)
+    #[serde(default)]
+    pub target_serial: Option<String>,
+
+    /// Model of the target device (if reported)
+    #[serde(default)]
+    pub target_model: Option<String>,
+
+    // ── Progress State ──────────────────────────────────────────────────────
+    /// Block size used for comparing
+    pub block_size: usize,
+
+    /// Number of bytes already compared and found to match
+    pub bytes_verified: u64,
+
+    // ── Timing Information ──────────────────────────────────────────────────
+    /// When the verification started (Unix timestamp)
+    pub start_time: u64,
+
+    /// When this checkpoint was last updated (Unix timestamp)
+    pub last_update: u64,
+
+    /// Total elapsed time before interruption (in seconds)
+    pub elapsed_seconds: f64,
+
+    /// Number of times this verification has been resumed
+    pub resume_count: u32,
+}
+
+impl VerifyCheckpoint {
+    /// Create a new checkpoint for verifying `source_size` bytes of a source against a target
+    pub fn new(
+        source_path: &str,
+        source_size: u64,
+        target_path: &str,
+        target_size: u64,
+        block_size: usize,
+    ) -> Self {
+        let now = unix_now();
+
+        Self {
+            version: CHECKPOINT_VERSION,
+            session_id: format!("{:x}-{:x}", now, std::process::id()),
+            source_path: source_path.to_string(),
+            source_size,
+            source_modified: None,
+            target_path: target_path.to_string(),
+            target_size,
+            target_serial: None,
+            target_model: None,
+            block_size,
+            bytes_verified: 0,
+            start_time: now,
+            last_update: now,
+            elapsed_seconds: 0.0,
+            resume_count: 0,
+        }
+    }
+
+    /// Record the source's modification time, so a changed image is detected on resume
+    pub fn with_source_modified(mut self, modified: Option<u64>) -> Self {
+        self.source_modified = modified;
+        self
+    }
+
+    /// Record the identity (serial/model) of the target device
+    pub fn with_target_identity(mut self, target: &TargetIdentity) -> Self {
+        self.target_serial = target.serial.clone();
+        self.target_model = target.model.clone();
+        self
+    }
+
+    /// Update progress in the checkpoint
+    pub fn update_progress(&mut self, bytes_verified: u64, elapsed: Duration) {
+        self.bytes_verified = bytes_verified;
+        self.elapsed_seconds = elapsed.as_secs_f64();
+        self.last_update = unix_now();
+    }
+
+    /// Mark this checkpoint as resumed
+    pub fn mark_resumed(&mut self) {
+        self.resume_count += 1;
+        self.last_update = unix_now();
+    }
+
+    /// Get percentage complete
+    pub fn percentage(&self) -> f64 {
+        if self.source_size == 0 {
+            return 0.0;
+        }
+        (self.bytes_verified as f64 / self.source_size as f64) * 100.0
+    }
+
+    /// Get the checkpoint filename for this session
+    pub fn filename(&self) -> String {
+        checkpoint_filename(
+            &self.source_path,
+            &self.target_path,
+            VERIFY_CHECKPOINT_EXTENSION,
+        )
     }
 }
 
 /// Manages checkpoint files for resume support
+#[derive(Debug, Clone)]
 pub struct CheckpointManager {
     /// Directory where checkpoints are stored
     checkpoint_dir: PathBuf,
@@ -272,24 +462,7 @@ impl CheckpointManager {
 
     /// Save a checkpoint to disk
     pub fn save(&self, checkpoint: &WriteCheckpoint) -> Result<()> {
-        let path = self.checkpoint_path(checkpoint);
-        let temp_path = path.with_extension("tmp");
-
-        // Write to temp file first, then rename (atomic on most systems)
-        let file = fs::File::create(&temp_path).map_err(Error::Io)?;
-        let writer = BufWriter::new(file);
-        serde_json::to_writer_pretty(writer, checkpoint).map_err(|e| {
-            Error::Io(std::io::Error::other(format!(
-                "Failed to serialize checkpoint: {}",
-                e
-            )))
-        })?;
-
-        // Atomic rename
-        fs::rename(&temp_path, &path).map_err(Error::Io)?;
-
-        tracing::debug!("Saved checkpoint to {:?}", path);
-        Ok(())
+        write_json(&self.checkpoint_path(checkpoint), checkpoint)
     }
 
     /// Load a checkpoint from disk
@@ -300,26 +473,8 @@ impl CheckpointManager {
 
     /// Load a checkpoint from a specific path
     pub fn load_from_path(&self, path: &Path) -> Result<WriteCheckpoint> {
-        let file = fs::File::open(path).map_err(Error::Io)?;
-        let reader = BufReader::new(file);
-        let checkpoint: WriteCheckpoint = serde_json::from_reader(reader).map_err(|e| {
-            Error::Io(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("Failed to parse checkpoint: {}", e),
-            ))
-        })?;
-
-        // Version check
-        if checkpoint.version > CHECKPOINT_VERSION {
-            return Err(Error::Io(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!(
-                    "Checkpoint version {} is newer than supported version {}",
-                    checkpoint.version, CHECKPOINT_VERSION
-                ),
-            )));
-        }
-
+        let checkpoint: WriteCheckpoint = read_json(path)?;
+        check_version(checkpoint.version)?;
         Ok(checkpoint)
     }
 
@@ -339,9 +494,7 @@ impl CheckpointManager {
         source_path: &str,
         target_path: &str,
     ) -> Result<Option<WriteCheckpoint>> {
-        let key = format!("{}:{}", source_path, target_path);
-        let hash = simple_hash(&key);
-        let filename = format!("{:016x}.{}", hash, CHECKPOINT_EXTENSION);
+        let filename = checkpoint_filename(source_path, target_path, CHECKPOINT_EXTENSION);
         let path = self.checkpoint_dir.join(&filename);
 
         if path.exists() {
@@ -359,8 +512,51 @@ impl CheckpointManager {
         }
     }
 
-    /// List all checkpoints in the directory
-    pub fn list_checkpoints(&self) -> Result<Vec<WriteCheckpoint>> {
+    /// Save a verify checkpoint to disk
+    pub fn save_verify(&self, checkpoint: &VerifyCheckpoint) -> Result<()> {
+        write_json(&self.checkpoint_dir.join(checkpoint.filename()), checkpoint)
+    }
+
+    /// Remove a verify checkpoint file
+    pub fn remove_verify(&self, checkpoint: &VerifyCheckpoint) -> Result<()> {
+        let path = self.checkpoint_dir.join(checkpoint.filename());
+        if path.exists() {
+            fs::remove_file(&path).map_err(Error::Io)?;
+            tracing::debug!("Removed verify checkpoint {:?}", path);
+        }
+        Ok(())
+    }
+
+    /// Find an existing verify checkpoint for a source/target combination
+    ///
+    /// A checkpoint that cannot be parsed is removed and treated as absent.
+    pub fn find_verify_checkpoint(
+        &self,
+        source_path: &str,
+        target_path: &str,
+    ) -> Result<Option<VerifyCheckpoint>> {
+        let filename = checkpoint_filename(source_path, target_path, VERIFY_CHECKPOINT_EXTENSION);
+        let path = self.checkpoint_dir.join(&filename);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let loaded = read_json::<VerifyCheckpoint>(&path)
+            .and_then(|checkpoint| check_version(checkpoint.version).map(|()| checkpoint));
+        match loaded {
+            Ok(checkpoint) => Ok(Some(checkpoint)),
+            Err(e) => {
+                tracing::warn!("Failed to load verify checkpoint {:?}: {}", path, e);
+                let _ = fs::remove_file(&path);
+                Ok(None)
+            }
+        }
+    }
+
+    /// List all checkpoints in the directory, most recently updated first
+    ///
+    /// Files that cannot be parsed are skipped with a warning.
+    pub fn list(&self) -> Result<Vec<WriteCheckpoint>> {
         let mut checkpoints = Vec::new();
 
         let entries = fs::read_dir(&self.checkpoint_dir).map_err(Error::Io)?;
@@ -377,11 +573,43 @@ impl CheckpointManager {
         }
 
         // Sort by last update time (most recent first)
-        checkpoints.sort_by(|a, b| b.last_update.cmp(&a.last_update));
+        checkpoints.sort_by_key(|b| std::cmp::Reverse(b.last_update));
 
         Ok(checkpoints)
     }
 
+    /// Remove the checkpoint with the given session ID
+    ///
+    /// Returns whether a matching checkpoint was found.
+    pub fn remove_session(&self, session_id: &str) -> Result<bool> {
+        match self.list()?.iter().find(|c| c.session_id == session_id) {
+            Some(checkpoint) => {
+                self.remove(checkpoint)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Remove every checkpoint file in the directory, including unreadable ones
+    ///
+    /// Returns the number of files removed.
+    pub fn clear(&self) -> Result<usize> {
+        let mut removed = 0;
+
+        let entries = fs::read_dir(&self.checkpoint_dir).map_err(Error::Io)?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == CHECKPOINT_EXTENSION) {
+                fs::remove_file(&path).map_err(Error::Io)?;
+                removed += 1;
+                tracing::debug!("Removed checkpoint {:?}", path);
+            }
+        }
+
+        Ok(removed)
+    }
+
     /// Clean up old checkpoints (older than the given duration)
     pub fn cleanup_old(&self, max_age: Duration) -> Result<usize> {
         let now = SystemTime::now()
@@ -410,6 +638,93 @@ impl CheckpointManager {
     }
 }
 
+/// Saves checkpoints on a background thread
+///
+/// [`submit`](Self::submit) only stores the checkpoint and wakes the thread,
+/// so a write loop never waits on the filesystem. If several checkpoints
+/// arrive while a save is in progress, only the most recent one is written.
+/// Clones share the same thread.
+#[derive(Clone)]
+pub struct CheckpointAutosave {
+    shared: Arc<AutosaveShared>,
+}
+
+struct AutosaveShared {
+    state: Mutex<AutosaveState>,
+    wakeup: Condvar,
+    handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+#[derive(Default)]
+struct AutosaveState {
+    pending: Option<WriteCheckpoint>,
+    stopping: bool,
+}
+
+impl CheckpointAutosave {
+    /// Start the background thread, saving through `manager`
+    pub fn spawn(manager: CheckpointManager) -> Self {
+        let shared = Arc::new(AutosaveShared {
+            state: Mutex::new(AutosaveState::default()),
+            wakeup: Condvar::new(),
+            handle: Mutex::new(None),
+        });
+
+        let worker = Arc::clone(&shared);
+        let handle = std::thread::spawn(move || autosave_loop(&manager, &worker));
+        *lock(&shared.handle) = Some(handle);
+
+        Self { shared }
+    }
+
+    /// Queue a checkpoint to be saved, replacing any not yet written
+    pub fn submit(&self, checkpoint: WriteCheckpoint) {
+        let mut state = lock(&self.shared.state);
+        if state.stopping {
+            return;
+        }
+        state.pending = Some(checkpoint);
+        self.shared.wakeup.notify_one();
+    }
+
+    /// Write any pending checkpoint and stop the background thread
+    ///
+    /// Call this before removing or saving the checkpoint directly, so a late
+    /// background save cannot overwrite it. Later submissions are ignored.
+    pub fn finish(&self) {
+        lock(&self.shared.state).stopping = true;
+        self.shared.wakeup.notify_one();
+
+        if let Some(handle) = lock(&self.shared.handle).take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn autosave_loop(manager: &CheckpointManager, shared: &AutosaveShared) {
+    let mut state = lock(&shared.state);
+    loop {
+        if let Some(checkpoint) = state.pending.take() {
+            drop(state);
+            if let Err(e) = manager.save(&checkpoint) {
+                tracing::warn!("Failed to autosave checkpoint: {}", e);
+            }
+            state = lock(&shared.state);
+        } else if state.stopping {
+            return;
+        } else {
+            state = shared
+                .wakeup
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
 /// Get the default checkpoint directory for the current platform
 pub fn default_checkpoint_dir() -> Result<PathBuf> {
     // Try XDG_STATE_HOME first (Linux), then fallback to home directory
@@ -436,6 +751,66 @@ pub fn default_checkpoint_dir() -> Result<PathBuf> {
     Ok(base.join(CHECKPOINT_DIR_NAME).join("checkpoints"))
 }
 
+/// Current time as a Unix timestamp
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Checkpoint filename for a source/target pair
+///
+/// Uses a hash of source+target to avoid collisions between sessions.
+fn checkpoint_filename(source_path: &str, target_path: &str, extension: &str) -> String {
+    let key = format!("{}:{}", source_path, target_path);
+    format!("{:016x}.{}", simple_hash(&key), extension)
+}
+
+/// Serialize `value` to `path` via a temp file and rename (atomic on most systems)
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let temp_path = path.with_extension("tmp");
+
+    let file = fs::File::create(&temp_path).map_err(Error::Io)?;
+    let writer = BufWriter::new(file);
+    serde_json::to_writer_pretty(writer, value).map_err(|e| {
+        Error::Io(std::io::Error::other(format!(
+            "Failed to serialize checkpoint: {}",
+            e
+        )))
+    })?;
+
+    fs::rename(&temp_path, path).map_err(Error::Io)?;
+
+    tracing::debug!("Saved checkpoint to {:?}", path);
+    Ok(())
+}
+
+/// Parse a checkpoint file
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let file = fs::File::open(path).map_err(Error::Io)?;
+    serde_json::from_reader(BufReader::new(file)).map_err(|e| {
+        Error::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Failed to parse checkpoint: {}", e),
+        ))
+    })
+}
+
+/// Reject checkpoints written by a newer format version
+fn check_version(version: u32) -> Result<()> {
+    if version > CHECKPOINT_VERSION {
+        return Err(Error::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "Checkpoint version {} is newer than supported version {}",
+                version, CHECKPOINT_VERSION
+            ),
+        )));
+    }
+    Ok(())
+}
+
 /// Simple hash function for generating checkpoint filenames
 fn simple_hash(s: &str) -> u64 {
     // FNV-1a hash
@@ -447,6 +822,45 @@ fn simple_hash(s: &str) -> u64 {
     hash
 }
 
+/// Identity of the currently connected target device
+///
+/// Built by the caller from whatever drive detection it uses, so this crate
+/// doesn't need to depend on `engraver-detect`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TargetIdentity {
+    /// Size of the target device in bytes
+    pub size: u64,
+
+    /// Serial number (if reported by the device)
+    pub serial: Option<String>,
+
+    /// Model name (if reported by the device)
+    pub model: Option<String>,
+}
+
+impl TargetIdentity {
+    /// Create a target identity with only a size
+    pub fn new(size: u64) -> Self {
+        Self {
+            size,
+            serial: None,
+            model: None,
+        }
+    }
+
+    /// Set the serial number
+    pub fn serial(mut self, serial: Option<String>) -> Self {
+        self.serial = serial.filter(|s| !s.trim().is_empty());
+        self
+    }
+
+    /// Set the model name
+    pub fn model(mut self, model: Option<String>) -> Self {
+        self.model = model.filter(|s| !s.trim().is_empty());
+        self
+    }
+}
+
 /// Validation result for a checkpoint
 #[derive(Debug)]
 pub struct CheckpointValidation {
@@ -492,11 +906,29 @@ impl CheckpointValidation {
 }
 
 /// Validate a checkpoint against current source/target state
+///
+/// Only the target's size is known here, so a checkpoint that recorded a
+/// serial number gets a warning. Use [`validate_checkpoint_for`] to tell
+/// drives apart.
 pub fn validate_checkpoint(
     checkpoint: &WriteCheckpoint,
     source_info: &SourceInfo,
     target_size: u64,
 ) -> CheckpointValidation {
+    validate_checkpoint_for(checkpoint, source_info, &TargetIdentity::new(target_size))
+}
+
+/// Validate a checkpoint against current source state and target identity
+///
+/// A serial number mismatch means a different drive is connected and makes
+/// the checkpoint invalid. If either side has no serial, a warning is
+/// emitted instead since the drive can't be positively identified.
+pub fn validate_checkpoint_for(
+    checkpoint: &WriteCheckpoint,
+    source_info: &SourceInfo,
+    target: &TargetIdentity,
+) -> CheckpointValidation {
+    let target_size = target.size;
     let mut result = CheckpointValidation::valid();
 
     // Check source path matches
@@ -517,6 +949,16 @@ pub fn validate_checkpoint(
         }
     }
 
+    // A remote file replaced since the checkpoint would be written half old, half new
+    if let (Some(cp_etag), Some(etag)) = (&checkpoint.source_etag, &source_info.etag) {
+        if cp_etag != etag {
+            return CheckpointValidation::invalid(format!(
+                "Source changed: checkpoint has ETag {}, current is {}",
+                cp_etag, etag
+            ));
+        }
+    }
+
     // Check target size
     if checkpoint.target_size != target_size {
         result = result.with_warning(format!(
@@ -525,6 +967,31 @@ pub fn validate_checkpoint(
         ));
     }
 
+    // Check the target is the same physical drive
+    match (&checkpoint.target_serial, &target.serial) {
+        (Some(cp_serial), Some(serial)) if cp_serial.trim() != serial.trim() => {
+            return CheckpointValidation::invalid(format!(
+                "Target serial mismatch: checkpoint has '{}', current is '{}' (a different drive is connected)",
+                cp_serial, serial
+            ));
+        }
+        (Some(_), Some(_)) => {}
+        _ => {
+            result = result.with_warning(
+                "Target serial number unavailable; cannot confirm this is the same drive",
+            );
+        }
+    }
+
+    if let (Some(cp_model), Some(model)) = (&checkpoint.target_model, &target.model) {
+        if cp_model.trim() != model.trim() {
+            result = result.with_warning(format!(
+                "Target model changed: checkpoint has '{}', current is '{}'",
+                cp_model, model
+            ));
+        }
+    }
+
     // Check if source can be resumed
     if !checkpoint.can_resume() {
         return CheckpointValidation::invalid(
@@ -545,6 +1012,67 @@ pub fn validate_checkpoint(
     result
 }
 
+/// Validate a verify checkpoint against the current source and target
+///
+/// Resuming is only safe if neither side has changed since the checkpoint
+/// was saved: the source must have the same size and modification time, and
+/// the target the same size and serial number. A missing serial gives a
+/// warning, as for [`validate_checkpoint_for`].
+pub fn validate_verify_checkpoint(
+    checkpoint: &VerifyCheckpoint,
+    source_size: u64,
+    source_modified: Option<u64>,
+    target: &TargetIdentity,
+) -> CheckpointValidation {
+    let mut result = CheckpointValidation::valid();
+
+    if checkpoint.source_size != source_size {
+        return CheckpointValidation::invalid(format!(
+            "Source size changed: checkpoint has {} bytes, current is {} bytes",
+            checkpoint.source_size, source_size
+        ));
+    }
+
+    if let (Some(cp_modified), Some(modified)) = (checkpoint.source_modified, source_modified) {
+        if cp_modified != modified {
+            return CheckpointValidation::invalid(
+                "Source has been modified since the verification was interrupted",
+            );
+        }
+    }
+
+    if checkpoint.target_size != target.size {
+        return CheckpointValidation::invalid(format!(
+            "Target size changed: checkpoint has {} bytes, current is {} bytes",
+            checkpoint.target_size, target.size
+        ));
+    }
+
+    match (&checkpoint.target_serial, &target.serial) {
+        (Some(cp_serial), Some(serial)) if cp_serial.trim() != serial.trim() => {
+            return CheckpointValidation::invalid(format!(
+                "Target serial mismatch: checkpoint has '{}', current is '{}' (a different drive is connected)",
+                cp_serial, serial
+            ));
+        }
+        (Some(_), Some(_)) => {}
+        _ => {
+            result = result.with_warning(
+                "Target serial number unavailable; cannot confirm this is the same drive",
+            );
+        }
+    }
+
+    if checkpoint.bytes_verified > checkpoint.source_size {
+        return CheckpointValidation::invalid(format!(
+            "Checkpoint bytes_verified ({}) exceeds source size ({})",
+            checkpoint.bytes_verified, checkpoint.source_size
+        ));
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -559,6 +1087,7 @@ mod tests {
             resumable: false,
             content_type: None,
             etag: None,
+            server_digest: None,
         }
     }
 
@@ -596,6 +1125,41 @@ mod tests {
         assert!((checkpoint.elapsed_seconds - 10.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_checkpoint_rewind_to() {
+        let source_info = create_test_source_info();
+        let config = create_test_config();
+        let mut checkpoint =
+            WriteCheckpoint::new(&source_info, "/dev/sdb", 32 * 1024 * 1024 * 1024, &config);
+        let block = checkpoint.block_size as u64;
+        checkpoint.update_progress(10 * block, 10, Duration::from_secs(1));
+        checkpoint.mark_resumed();
+
+        checkpoint.rewind_to(8 * block);
+        assert_eq!(checkpoint.bytes_written, 8 * block);
+        assert_eq!(checkpoint.blocks_written, 8);
+        assert_eq!(checkpoint.notes.len(), 1);
+        assert!(checkpoint.notes[0].starts_with("Resume 1:"));
+
+        // Rewinding forward is ignored
+        checkpoint.rewind_to(9 * block);
+        assert_eq!(checkpoint.bytes_written, 8 * block);
+        assert_eq!(checkpoint.notes.len(), 1);
+    }
+
+    #[test]
+    fn test_checkpoint_without_notes_deserializes() {
+        let source_info = create_test_source_info();
+        let config = create_test_config();
+        let checkpoint =
+            WriteCheckpoint::new(&source_info, "/dev/sdb", 32 * 1024 * 1024 * 1024, &config);
+        let mut value = serde_json::to_value(&checkpoint).unwrap();
+        value.as_object_mut().unwrap().remove("notes");
+
+        let loaded: WriteCheckpoint = serde_json::from_value(value).unwrap();
+        assert!(loaded.notes.is_empty());
+    }
+
     #[test]
     fn test_checkpoint_percentage() {
         let source_info = create_test_source_info();
@@ -629,6 +1193,7 @@ mod tests {
             resumable: false,
             content_type: None,
             etag: None,
+            server_digest: None,
         };
         let checkpoint = WriteCheckpoint::new(&local_info, "/dev/sdb", 1024 * 1024, &config);
         assert!(checkpoint.can_resume());
@@ -643,6 +1208,7 @@ mod tests {
             resumable: true,
             content_type: None,
             etag: None,
+            server_digest: None,
         };
         let checkpoint = WriteCheckpoint::new(&http_info, "/dev/sdb", 1024 * 1024, &config);
         assert!(checkpoint.can_resume());
@@ -657,6 +1223,7 @@ mod tests {
             resumable: false,
             content_type: None,
             etag: None,
+            server_digest: None,
         };
         let checkpoint = WriteCheckpoint::new(&gzip_info, "/dev/sdb", 1024 * 1024, &config);
         assert!(!checkpoint.can_resume());
@@ -722,6 +1289,69 @@ mod tests {
         assert!(result.messages[0].contains("size changed"));
     }
 
+    #[test]
+    fn test_validate_checkpoint_remote_etag_changed() {
+        let source_info = SourceInfo {
+            path: "https://example.com/file.iso".to_string(),
+            source_type: SourceType::Remote,
+            size: Some(1024),
+            compressed_size: Some(1024),
+            seekable: false,
+            resumable: true,
+            content_type: None,
+            etag: Some("\"v1\"".to_string()),
+            server_digest: None,
+        };
+        let config = create_test_config();
+        let checkpoint = WriteCheckpoint::new(&source_info, "/dev/sdb", 1024 * 1024, &config);
+        assert_eq!(checkpoint.source_etag.as_deref(), Some("\"v1\""));
+        assert!(checkpoint.can_resume());
+
+        let target = TargetIdentity::new(1024 * 1024);
+        assert!(validate_checkpoint_for(&checkpoint, &source_info, &target).valid);
+
+        let changed_info = SourceInfo {
+            etag: Some("\"v2\"".to_string()),
+            ..source_info.clone()
+        };
+        let result = validate_checkpoint_for(&checkpoint, &changed_info, &target);
+        assert!(!result.valid);
+        assert!(result.messages[0].contains("ETag"));
+    }
+
+    #[test]
+    fn test_checkpoint_remote_without_ranges_cannot_resume() {
+        let source_info = SourceInfo {
+            path: "https://example.com/file.iso".to_string(),
+            source_type: SourceType::Remote,
+            size: Some(1024),
+            compressed_size: Some(1024),
+            seekable: false,
+            resumable: false,
+            content_type: None,
+            etag: None,
+            server_digest: None,
+        };
+        let checkpoint =
+            WriteCheckpoint::new(&source_info, "/dev/sdb", 1024 * 1024, &create_test_config());
+        assert!(!checkpoint.can_resume());
+    }
+
+    #[test]
+    fn test_checkpoint_without_etag_deserializes() {
+        let checkpoint = WriteCheckpoint::new(
+            &create_test_source_info(),
+            "/dev/sdb",
+            1024 * 1024,
+            &create_test_config(),
+        );
+        let mut json: serde_json::Value = serde_json::to_value(&checkpoint).unwrap();
+        json.as_object_mut().unwrap().remove("source_etag");
+
+        let loaded: WriteCheckpoint = serde_json::from_value(json).unwrap();
+        assert!(loaded.source_etag.is_none());
+    }
+
     #[test]
     fn test_validate_checkpoint_compressed_source() {
         let gzip_info = SourceInfo {
@@ -733,6 +1363,7 @@ mod tests {
             resumable: false,
             content_type: None,
             etag: None,
+            server_digest: None,
         };
         let config = create_test_config();
         let checkpoint = WriteCheckpoint::new(&gzip_info, "/dev/sdb", 1024 * 1024, &config);
@@ -765,7 +1396,7 @@ mod tests {
         assert!(temp_dir.path().exists());
 
         // Manager should be functional
-        let checkpoints = manager.list_checkpoints().unwrap();
+        let checkpoints = manager.list().unwrap();
         assert!(checkpoints.is_empty());
     }
 
@@ -842,7 +1473,7 @@ mod tests {
     }
 
     #[test]
-    fn test_checkpoint_manager_list_checkpoints() {
+    fn test_checkpoint_manager_list() {
         let temp_dir = tempfile::tempdir().unwrap();
         let manager = CheckpointManager::new(temp_dir.path()).unwrap();
 
@@ -858,6 +1489,7 @@ mod tests {
             resumable: false,
             content_type: None,
             etag: None,
+            server_digest: None,
         };
         let checkpoint1 =
             WriteCheckpoint::new(&source_info1, "/dev/sdb", 32 * 1024 * 1024 * 1024, &config);
@@ -872,16 +1504,80 @@ mod tests {
             resumable: false,
             content_type: None,
             etag: None,
+            server_digest: None,
         };
         let checkpoint2 =
             WriteCheckpoint::new(&source_info2, "/dev/sdc", 64 * 1024 * 1024 * 1024, &config);
         manager.save(&checkpoint2).unwrap();
 
         // List should return both
-        let checkpoints = manager.list_checkpoints().unwrap();
+        let checkpoints = manager.list().unwrap();
         assert_eq!(checkpoints.len(), 2);
     }
 
+    #[test]
+    fn test_checkpoint_manager_list_skips_corrupt() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manager = CheckpointManager::new(temp_dir.path()).unwrap();
+
+        let checkpoint = WriteCheckpoint::new(
+            &create_test_source_info(),
+            "/dev/sdb",
+            32 * 1024 * 1024 * 1024,
+            &create_test_config(),
+        );
+        manager.save(&checkpoint).unwrap();
+        fs::write(temp_dir.path().join("broken.checkpoint"), "not json").unwrap();
+
+        let checkpoints = manager.list().unwrap();
+        assert_eq!(checkpoints.len(), 1);
+        assert_eq!(checkpoints[0].session_id, checkpoint.session_id);
+    }
+
+    #[test]
+    fn test_checkpoint_manager_remove_session() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manager = CheckpointManager::new(temp_dir.path()).unwrap();
+
+        let mut checkpoint = WriteCheckpoint::new(
+            &create_test_source_info(),
+            "/dev/sdb",
+            32 * 1024 * 1024 * 1024,
+            &create_test_config(),
+        );
+        checkpoint.session_id = "abc-123".to_string();
+        manager.save(&checkpoint).unwrap();
+
+        assert!(!manager.remove_session("does-not-exist").unwrap());
+        assert_eq!(manager.list().unwrap().len(), 1);
+
+        assert!(manager.remove_session("abc-123").unwrap());
+        assert!(manager.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_checkpoint_manager_clear() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manager = CheckpointManager::new(temp_dir.path()).unwrap();
+
+        let config = create_test_config();
+        for target in ["/dev/sdb", "/dev/sdc"] {
+            let checkpoint = WriteCheckpoint::new(
+                &create_test_source_info(),
+                target,
+                32 * 1024 * 1024 * 1024,
+                &config,
+            );
+            manager.save(&checkpoint).unwrap();
+        }
+        fs::write(temp_dir.path().join("broken.checkpoint"), "not json").unwrap();
+        fs::write(temp_dir.path().join("unrelated.txt"), "keep me").unwrap();
+
+        assert_eq!(manager.clear().unwrap(), 3);
+        assert!(manager.list().unwrap().is_empty());
+        assert!(temp_dir.path().join("unrelated.txt").exists());
+    }
+
     #[test]
     fn test_checkpoint_manager_cleanup_old() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -898,7 +1594,7 @@ mod tests {
         assert_eq!(removed, 0);
 
         // Checkpoint should still exist
-        let checkpoints = manager.list_checkpoints().unwrap();
+        let checkpoints = manager.list().unwrap();
         assert_eq!(checkpoints.len(), 1);
     }
 
@@ -989,6 +1685,7 @@ mod tests {
             resumable: false,
             content_type: None,
             etag: None,
+            server_digest: None,
         };
 
         let result = validate_checkpoint(&checkpoint, &different_source, 32 * 1024 * 1024 * 1024);
@@ -1025,6 +1722,100 @@ mod tests {
         assert!(result.warnings[0].contains("Target size changed"));
     }
 
+    // -------------------------------------------------------------------------
+    // Target identity tests
+    // -------------------------------------------------------------------------
+
+    fn create_identified_checkpoint(source_info: &SourceInfo) -> WriteCheckpoint {
+        let target = TargetIdentity::new(32 * 1024 * 1024 * 1024)
+            .serial(Some("SERIAL-A".to_string()))
+            .model(Some("Cruzer".to_string()));
+        WriteCheckpoint::new(source_info, "/dev/sdb", target.size, &create_test_config())
+            .with_target_identity(&target)
+    }
+
+    #[test]
+    fn test_target_identity_builder_ignores_blank() {
+        let target = TargetIdentity::new(1024)
+            .serial(Some("  ".to_string()))
+            .model(Some(String::new()));
+        assert_eq!(target.size, 1024);
+        assert!(target.serial.is_none());
+        assert!(target.model.is_none());
+    }
+
+    #[test]
+    fn test_checkpoint_with_target_identity() {
+        let checkpoint = create_identified_checkpoint(&create_test_source_info());
+        assert_eq!(checkpoint.target_serial.as_deref(), Some("SERIAL-A"));
+        assert_eq!(checkpoint.target_model.as_deref(), Some("Cruzer"));
+    }
+
+    #[test]
+    fn test_validate_checkpoint_same_serial() {
+        let source_info = create_test_source_info();
+        let checkpoint = create_identified_checkpoint(&source_info);
+        let target = TargetIdentity::new(checkpoint.target_size)
+            .serial(Some("SERIAL-A".to_string()))
+            .model(Some("Cruzer".to_string()));
+
+        let result = validate_checkpoint_for(&checkpoint, &source_info, &target);
+        assert!(result.valid);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_validate_checkpoint_serial_mismatch() {
+        let source_info = create_test_source_info();
+        let checkpoint = create_identified_checkpoint(&source_info);
+        let target =
+            TargetIdentity::new(checkpoint.target_size).serial(Some("SERIAL-B".to_string()));
+
+        let result = validate_checkpoint_for(&checkpoint, &source_info, &target);
+        assert!(!result.valid);
+        assert!(result.messages[0].contains("serial mismatch"));
+    }
+
+    #[test]
+    fn test_validate_checkpoint_serial_unavailable_warns() {
+        let source_info = create_test_source_info();
+        let checkpoint = create_identified_checkpoint(&source_info);
+        let target = TargetIdentity::new(checkpoint.target_size);
+
+        let result = validate_checkpoint_for(&checkpoint, &source_info, &target);
+        assert!(result.valid);
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.contains("serial number unavailable")));
+    }
+
+    #[test]
+    fn test_validate_checkpoint_model_changed_warns() {
+        let source_info = create_test_source_info();
+        let checkpoint = create_identified_checkpoint(&source_info);
+        let target = TargetIdentity::new(checkpoint.target_size)
+            .serial(Some("SERIAL-A".to_string()))
+            .model(Some("Ultra".to_string()));
+
+        let result = validate_checkpoint_for(&checkpoint, &source_info, &target);
+        assert!(result.valid);
+        assert!(result.warnings[0].contains("model changed"));
+    }
+
+    #[test]
+    fn test_checkpoint_without_target_identity_deserializes() {
+        let checkpoint = create_identified_checkpoint(&create_test_source_info());
+        let mut json: serde_json::Value = serde_json::to_value(&checkpoint).unwrap();
+        let obj = json.as_object_mut().unwrap();
+        obj.remove("target_serial");
+        obj.remove("target_model");
+
+        let loaded: WriteCheckpoint = serde_json::from_value(json).unwrap();
+        assert!(loaded.target_serial.is_none());
+        assert!(loaded.target_model.is_none());
+    }
+
     // -------------------------------------------------------------------------
     // WriteConfigCheckpoint tests
     // -------------------------------------------------------------------------
@@ -1074,6 +1865,7 @@ mod tests {
             resumable: true,
             content_type: None,
             etag: None,
+            server_digest: None,
         };
         let mut checkpoint = WriteCheckpoint::new(&source_info, "/dev/sdb", 1024 * 1024, &config);
         checkpoint.bytes_written = 500;
@@ -1094,6 +1886,7 @@ mod tests {
             resumable: false,
             content_type: None,
             etag: None,
+            server_digest: None,
         };
         let checkpoint = WriteCheckpoint::new(&source_info, "/dev/sdb", 1024 * 1024, &config);
 
@@ -1112,6 +1905,7 @@ mod tests {
             resumable: false,
             content_type: None,
             etag: None,
+            server_digest: None,
         };
         let checkpoint =
             WriteCheckpoint::new(&source_info, "/dev/sdb", 1024 * 1024 * 1024, &config);
@@ -1132,6 +1926,7 @@ mod tests {
             resumable: false,
             content_type: None,
             etag: None,
+            server_digest: None,
         };
         let checkpoint =
             WriteCheckpoint::new(&source_info, "/dev/sdb", 1024 * 1024 * 1024, &config);
@@ -1162,6 +1957,7 @@ mod tests {
             resumable: false,
             content_type: None,
             etag: None,
+            server_digest: None,
         };
         let checkpoint = WriteCheckpoint::new(&source_info, "/dev/sdb", 1024 * 1024, &config);
 
@@ -1184,7 +1980,7 @@ mod tests {
         assert!(nested_path.exists());
 
         // Should work normally
-        let checkpoints = manager.list_checkpoints().unwrap();
+        let checkpoints = manager.list().unwrap();
         assert!(checkpoints.is_empty());
     }
 
@@ -1231,4 +2027,225 @@ mod tests {
         let cp_config = WriteConfigCheckpoint::from(&config);
         assert!(!cp_config.verify);
     }
+
+    // -------------------------------------------------------------------------
+    // VerifyCheckpoint tests
+    // -------------------------------------------------------------------------
+
+    fn create_verify_checkpoint() -> VerifyCheckpoint {
+        VerifyCheckpoint::new("/path/to/image.iso", 4096, "/dev/sdb", 8192, 1024)
+            .with_source_modified(Some(1_700_000_000))
+            .with_target_identity(&TargetIdentity::new(8192).serial(Some("SN1".to_string())))
+    }
+
+    #[test]
+    fn test_verify_checkpoint_progress() {
+        let mut checkpoint = create_verify_checkpoint();
+        assert_eq!(checkpoint.percentage(), 0.0);
+
+        checkpoint.update_progress(1024, Duration::from_secs(2));
+        checkpoint.mark_resumed();
+
+        assert_eq!(checkpoint.bytes_verified, 1024);
+        assert_eq!(checkpoint.percentage(), 25.0);
+        assert_eq!(checkpoint.resume_count, 1);
+    }
+
+    #[test]
+    fn test_verify_checkpoint_filename_is_distinct() {
+        let checkpoint = create_verify_checkpoint();
+        let write = WriteCheckpoint::new(
+            &create_test_source_info(),
+            "/dev/sdb",
+            8192,
+            &create_test_config(),
+        );
+
+        assert!(checkpoint.filename().ends_with(".vcheckpoint"));
+        assert_eq!(
+            checkpoint.filename().split('.').next(),
+            write.filename().split('.').next()
+        );
+    }
+
+    #[test]
+    fn test_verify_checkpoint_save_find_remove() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manager = CheckpointManager::new(temp_dir.path()).unwrap();
+        let mut checkpoint = create_verify_checkpoint();
+        checkpoint.update_progress(2048, Duration::from_secs(1));
+
+        manager.save_verify(&checkpoint).unwrap();
+
+        let found = manager
+            .find_verify_checkpoint("/path/to/image.iso", "/dev/sdb")
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.bytes_verified, 2048);
+        assert_eq!(found.target_serial.as_deref(), Some("SN1"));
+
+        // Verify checkpoints are not write checkpoints
+        assert!(manager.list().unwrap().is_empty());
+        assert!(manager
+            .find_checkpoint("/path/to/image.iso", "/dev/sdb")
+            .unwrap()
+            .is_none());
+
+        manager.remove_verify(&checkpoint).unwrap();
+        assert!(manager
+            .find_verify_checkpoint("/path/to/image.iso", "/dev/sdb")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_find_verify_checkpoint_removes_corrupt_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manager = CheckpointManager::new(temp_dir.path()).unwrap();
+        let path = temp_dir.path().join(create_verify_checkpoint().filename());
+        fs::write(&path, "not json").unwrap();
+
+        assert!(manager
+            .find_verify_checkpoint("/path/to/image.iso", "/dev/sdb")
+            .unwrap()
+            .is_none());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_validate_verify_checkpoint_unchanged() {
+        let checkpoint = create_verify_checkpoint();
+        let target = TargetIdentity::new(8192).serial(Some("SN1".to_string()));
+
+        let result = validate_verify_checkpoint(&checkpoint, 4096, Some(1_700_000_000), &target);
+        assert!(result.valid);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_validate_verify_checkpoint_source_changed() {
+        let checkpoint = create_verify_checkpoint();
+        let target = TargetIdentity::new(8192).serial(Some("SN1".to_string()));
+
+        assert!(!validate_verify_checkpoint(&checkpoint, 4097, Some(1_700_000_000), &target).valid);
+        assert!(!validate_verify_checkpoint(&checkpoint, 4096, Some(1_700_000_001), &target).valid);
+    }
+
+    #[test]
+    fn test_validate_verify_checkpoint_target_changed() {
+        let checkpoint = create_verify_checkpoint();
+        let modified = Some(1_700_000_000);
+
+        let other = TargetIdentity::new(8192).serial(Some("SN2".to_string()));
+        assert!(!validate_verify_checkpoint(&checkpoint, 4096, modified, &other).valid);
+
+        let resized = TargetIdentity::new(16384).serial(Some("SN1".to_string()));
+        assert!(!validate_verify_checkpoint(&checkpoint, 4096, modified, &resized).valid);
+
+        let unknown = TargetIdentity::new(8192);
+        let result = validate_verify_checkpoint(&checkpoint, 4096, modified, &unknown);
+        assert!(result.valid);
+        assert_eq!(result.warnings.len(), 1);
+    }
+
+    // -------------------------------------------------------------------------
+    // CheckpointAutosave tests
+    // -------------------------------------------------------------------------
+
+    /// Poll the checkpoint file until it reports `expected` bytes written
+    fn wait_for_saved(manager: &CheckpointManager, checkpoint: &WriteCheckpoint, expected: u64) {
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        loop {
+            if let Ok(saved) = manager.load(checkpoint) {
+                if saved.bytes_written == expected {
+                    return;
+                }
+            }
+            assert!(
+                std::time::Instant::now() < deadline,
+                "checkpoint never reached {} bytes",
+                expected
+            );
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_autosave_bytes_written_advances() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manager = CheckpointManager::new(temp_dir.path()).unwrap();
+        let mut checkpoint = WriteCheckpoint::new(
+            &create_test_source_info(),
+            "/dev/sdb",
+            32 * 1024 * 1024 * 1024,
+            &create_test_config(),
+        );
+        let autosave = CheckpointAutosave::spawn(manager.clone());
+
+        // Simulate progress ticks from the write loop
+        for tick in 1..=3u64 {
+            let bytes = tick * 4 * 1024 * 1024;
+            checkpoint.update_progress(bytes, tick, Duration::from_secs(tick));
+            autosave.submit(checkpoint.clone());
+            wait_for_saved(&manager, &checkpoint, bytes);
+        }
+
+        autosave.finish();
+        assert_eq!(manager.load(&checkpoint).unwrap().blocks_written, 3);
+    }
+
+    #[test]
+    fn test_autosave_finish_flushes_pending_and_ignores_later_submits() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manager = CheckpointManager::new(temp_dir.path()).unwrap();
+        let mut checkpoint = WriteCheckpoint::new(
+            &create_test_source_info(),
+            "/dev/sdb",
+            32 * 1024 * 1024 * 1024,
+            &create_test_config(),
+        );
+        let autosave = CheckpointAutosave::spawn(manager.clone());
+
+        checkpoint.update_progress(1024, 1, Duration::from_secs(1));
+        autosave.submit(checkpoint.clone());
+        autosave.finish();
+        assert_eq!(manager.load(&checkpoint).unwrap().bytes_written, 1024);
+
+        // Nothing is written once finished, so removal cannot be undone
+        manager.remove(&checkpoint).unwrap();
+        checkpoint.update_progress(2048, 2, Duration::from_secs(2));
+        autosave.submit(checkpoint.clone());
+        autosave.finish();
+        assert!(manager.load(&checkpoint).is_err());
+    }
+
+    #[test]
+    fn test_autosave_from_writer_checkpoints() {
+        use crate::Writer;
+        use std::io::Cursor;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manager = CheckpointManager::new(temp_dir.path()).unwrap();
+        let data = vec![0xC3u8; 16 * 1024];
+        let source_info = SourceInfo::local("/path/to/image.iso", data.len() as u64);
+        let config = WriteConfig::new().block_size(4 * 1024);
+        let checkpoint = WriteCheckpoint::new(&source_info, "/dev/sdb", 1024 * 1024, &config);
+
+        let autosave = CheckpointAutosave::spawn(manager.clone());
+        let autosave_clone = autosave.clone();
+        let mut template = checkpoint.clone();
+        let mut writer = Writer::with_config(config).on_checkpoint(Duration::ZERO, move |p| {
+            template.update_progress(p.bytes_written, p.current_block, p.elapsed);
+            autosave_clone.submit(template.clone());
+        });
+        let mut target = Cursor::new(Vec::new());
+        writer
+            .write(Cursor::new(&data), &mut target, data.len() as u64)
+            .unwrap();
+        autosave.finish();
+
+        let saved = manager.load(&checkpoint).unwrap();
+        assert_eq!(saved.bytes_written, data.len() as u64);
+        assert_eq!(saved.blocks_written, 4);
+    }
 }
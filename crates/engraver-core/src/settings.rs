@@ -20,6 +20,7 @@
 //!
 //! [behavior]
 //! skip_confirmation = false
+//! record_history = true
 //!
 //! [benchmark]
 //! block_size = "4M"
@@ -27,16 +28,44 @@
 //! pattern = "zeros"
 //! passes = 1
 //! json = false
+//! record = false
 //!
 //! [network]
 //! http_timeout_secs = 30
 //! validation_timeout_secs = 10
+//! http_max_retries = 3
+//! http_backoff_base_ms = 250
 //! cloud_chunk_size = "4M"
 //! ```
+//!
+//! # Profiles
+//!
+//! A `[profiles.<name>]` table holds settings that override the ones above
+//! when that profile is selected, with `--profile <name>` or
+//! `behavior.default_profile`. Only the fields a profile sets change:
+//!
+//! ```toml
+//! [behavior]
+//! default_profile = "sd"
+//!
+//! [profiles.sd.write]
+//! block_size = "1M"
+//! verify = true
+//!
+//! [profiles.ssd.write]
+//! block_size = "16M"
+//! ```
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
 use std::path::PathBuf;
 
+use crate::benchmark::DataPattern;
+use crate::size::{parse_block_size_clamped, parse_size};
+use crate::verifier::ChecksumAlgorithm;
+use crate::writer::{MAX_BLOCK_SIZE, MIN_BLOCK_SIZE};
+
 /// Configuration file name
 const CONFIG_FILE_NAME: &str = "engraver_config.toml";
 
@@ -55,6 +84,9 @@ pub const DEFAULT_RETRY_DELAY_MS: u64 = 100;
 /// Default read buffer size string
 const DEFAULT_READ_BUFFER_SIZE_STR: &str = "64K";
 
+/// Default interval between checkpoint auto-saves during a write, in seconds
+pub const DEFAULT_CHECKPOINT_INTERVAL_SECS: u64 = 30;
+
 /// Default cloud chunk size string
 const DEFAULT_CLOUD_CHUNK_SIZE_STR: &str = "4M";
 
@@ -76,6 +108,10 @@ pub struct Settings {
 
     /// Network settings
     pub network: NetworkSettings,
+
+    /// Named overlays of the sections above, e.g. `[profiles.sd.write]`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, toml::Table>,
 }
 
 /// Settings for write operations
@@ -99,6 +135,9 @@ pub struct WriteSettings {
 
     /// Buffer size for reading files (e.g., "64K", "128K")
     pub read_buffer_size: String,
+
+    /// Seconds between checkpoint auto-saves while writing (0 disables)
+    pub checkpoint_interval_seconds: u64,
 }
 
 /// Settings for checksum operations
@@ -113,7 +152,7 @@ pub struct ChecksumSettings {
 }
 
 /// General behavior settings
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(default)]
 pub struct BehaviorSettings {
     /// Whether to skip confirmation prompts by default
@@ -121,6 +160,13 @@ pub struct BehaviorSettings {
 
     /// Whether to suppress non-error output
     pub quiet: bool,
+
+    /// Whether to record successful writes in the history log
+    pub record_history: bool,
+
+    /// Profile applied when no `--profile` is given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
 }
 
 /// Settings for benchmark operations
@@ -141,6 +187,9 @@ pub struct BenchmarkSettings {
 
     /// Output results in JSON format by default
     pub json: bool,
+
+    /// Append each result to the benchmark log, keyed by drive serial
+    pub record: bool,
 }
 
 /// Settings for network operations (HTTP, cloud storage)
@@ -153,6 +202,12 @@ pub struct NetworkSettings {
     /// Timeout for URL validation (HEAD requests) in seconds
     pub validation_timeout_secs: u64,
 
+    /// Number of times to retry a dropped or failed (5xx) HTTP download
+    pub http_max_retries: u32,
+
+    /// Initial delay between HTTP retries in milliseconds (doubles each attempt)
+    pub http_backoff_base_ms: u64,
+
     /// Chunk size for cloud storage streaming reads (e.g., "4M", "8M")
     pub cloud_chunk_size: String,
 }
@@ -163,11 +218,19 @@ pub const DEFAULT_HTTP_TIMEOUT_SECS: u64 = 30;
 /// Default validation timeout in seconds
 pub const DEFAULT_VALIDATION_TIMEOUT_SECS: u64 = 10;
 
+/// Default number of HTTP retries
+pub const DEFAULT_HTTP_MAX_RETRIES: u32 = 3;
+
+/// Default initial HTTP retry delay in milliseconds
+pub const DEFAULT_HTTP_BACKOFF_BASE_MS: u64 = 250;
+
 impl Default for NetworkSettings {
     fn default() -> Self {
         Self {
             http_timeout_secs: DEFAULT_HTTP_TIMEOUT_SECS,
             validation_timeout_secs: DEFAULT_VALIDATION_TIMEOUT_SECS,
+            http_max_retries: DEFAULT_HTTP_MAX_RETRIES,
+            http_backoff_base_ms: DEFAULT_HTTP_BACKOFF_BASE_MS,
             cloud_chunk_size: DEFAULT_CLOUD_CHUNK_SIZE_STR.to_string(),
         }
     }
@@ -181,6 +244,7 @@ impl Default for BenchmarkSettings {
             pattern: "zeros".to_string(),
             passes: 1,
             json: false,
+            record: false,
         }
     }
 }
@@ -194,6 +258,18 @@ impl Default for WriteSettings {
             retry_attempts: DEFAULT_RETRY_ATTEMPTS,
             retry_delay_ms: DEFAULT_RETRY_DELAY_MS,
             read_buffer_size: DEFAULT_READ_BUFFER_SIZE_STR.to_string(),
+            checkpoint_interval_seconds: DEFAULT_CHECKPOINT_INTERVAL_SECS,
+        }
+    }
+}
+
+impl Default for BehaviorSettings {
+    fn default() -> Self {
+        Self {
+            skip_confirmation: false,
+            quiet: false,
+            record_history: true,
+            default_profile: None,
         }
     }
 }
@@ -245,6 +321,80 @@ impl Settings {
         }
     }
 
+    /// Load settings from a specific path, failing if it can't be read or parsed
+    ///
+    /// Unlike [`load_from_path`](Self::load_from_path), only a missing file
+    /// gives the defaults, so a broken file is never mistaken for an empty one.
+    pub fn try_load_from_path(path: &Path) -> Result<Self, SettingsError> {
+        let contents = read_config(path)?;
+        toml::from_str(&contents).map_err(|e| SettingsError::Parse {
+            path: path.to_path_buf(),
+            source: e,
+        })
+    }
+
+    /// Set one setting in the configuration file at `path`, returning the new settings
+    ///
+    /// Only that key is rewritten: comments, formatting and every other key
+    /// are kept. The file must already parse; if it doesn't, or the value is
+    /// invalid, the file is left unchanged.
+    pub fn set_in_file(path: &Path, key: &str, value: &str) -> Result<Self, SettingsError> {
+        let mut settings = Self::try_load_from_path(path)?;
+        settings.set(key, value)?;
+
+        let mut table = settings.to_table()?;
+        let mut new_value = match field_mut(&mut table, key)? {
+            toml::Value::String(s) => toml_edit::Value::from(s.as_str()),
+            toml::Value::Boolean(b) => toml_edit::Value::from(*b),
+            toml::Value::Integer(n) => toml_edit::Value::from(*n),
+            other => toml_edit::Value::from(other.to_string()),
+        };
+        let mut document: toml_edit::DocumentMut =
+            read_config(path)?
+                .parse()
+                .map_err(|e: toml_edit::TomlError| SettingsError::Io {
+                    path: path.to_path_buf(),
+                    source: std::io::Error::new(std::io::ErrorKind::InvalidData, e),
+                })?;
+
+        let (section, field) = key.split_once('.').unwrap_or((key, ""));
+        let section = document
+            .entry(section)
+            .or_insert_with(toml_edit::table)
+            .as_table_like_mut()
+            .ok_or_else(|| SettingsError::UnknownKey {
+                key: key.to_string(),
+                valid: Self::keys(),
+            })?;
+        match section
+            .get_mut(field)
+            .and_then(toml_edit::Item::as_value_mut)
+        {
+            // Keep the key's spacing and any trailing comment
+            Some(existing) => {
+                *new_value.decor_mut() = existing.decor().clone();
+                *existing = new_value;
+            }
+            None => {
+                section.insert(field, toml_edit::Item::Value(new_value));
+            }
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| SettingsError::Io {
+                path: parent.to_path_buf(),
+                source: e,
+            })?;
+        }
+        std::fs::write(path, document.to_string()).map_err(|e| SettingsError::Io {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+
+        tracing::info!("Set {} in {:?}", key, path);
+        Ok(settings)
+    }
+
     /// Save settings to the configuration file
     pub fn save(&self) -> Result<PathBuf, SettingsError> {
         self.save_to_path(Self::config_path())
@@ -294,6 +444,184 @@ impl Settings {
         toml::to_string_pretty(&default)
             .unwrap_or_else(|_| String::from("# Failed to generate default config"))
     }
+
+    /// Dotted keys accepted by [`get`](Self::get) and [`set`](Self::set), e.g. `write.block_size`
+    pub fn keys() -> Vec<String> {
+        let Ok(sections) = Self::default().to_table() else {
+            return Vec::new();
+        };
+        sections
+            .iter()
+            .filter_map(|(section, fields)| Some((section, fields.as_table()?)))
+            .flat_map(|(section, fields)| fields.keys().map(move |f| format!("{}.{}", section, f)))
+            .collect()
+    }
+
+    /// Get a setting by dotted key, formatted as it would be passed to [`set`](Self::set)
+    pub fn get(&self, key: &str) -> Result<String, SettingsError> {
+        let mut table = self.to_table()?;
+        Ok(match field_mut(&mut table, key)? {
+            toml::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+    }
+
+    /// Set a setting by dotted key, parsing `value` as the field's type
+    ///
+    /// Sizes, block sizes, checksum algorithms and benchmark patterns are
+    /// validated; on error the settings are left unchanged.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), SettingsError> {
+        let invalid = |reason: &str| SettingsError::InvalidValue {
+            key: key.to_string(),
+            value: value.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let mut table = self.to_table()?;
+        let field = field_mut(&mut table, key)?;
+        *field = match field {
+            toml::Value::String(_) => toml::Value::String(value.to_string()),
+            toml::Value::Boolean(_) => toml::Value::Boolean(
+                value
+                    .parse()
+                    .map_err(|_| invalid("expected true or false"))?,
+            ),
+            toml::Value::Integer(_) => toml::Value::Integer(
+                value
+                    .parse::<i64>()
+                    .ok()
+                    .filter(|n| *n >= 0)
+                    .ok_or_else(|| invalid("expected a non-negative integer"))?,
+            ),
+            _ => return Err(invalid("unsupported setting type")),
+        };
+        validate_value(key, value).map_err(|reason| invalid(&reason))?;
+
+        *self = toml::Value::Table(table)
+            .try_into()
+            .map_err(|e: toml::de::Error| invalid(e.message()))?;
+        Ok(())
+    }
+
+    /// Names of the profiles defined in the configuration file
+    pub fn profile_names(&self) -> Vec<&str> {
+        self.profiles.keys().map(String::as_str).collect()
+    }
+
+    /// These settings with a profile's overrides applied
+    ///
+    /// `profile` falls back to `behavior.default_profile`; with neither, the
+    /// settings are returned unchanged. Each override must name an existing
+    /// setting and pass the same checks as [`set`](Self::set).
+    pub fn with_profile(&self, profile: Option<&str>) -> Result<Self, SettingsError> {
+        let Some(name) = profile.or(self.behavior.default_profile.as_deref()) else {
+            return Ok(self.clone());
+        };
+        let overrides = self
+            .profiles
+            .get(name)
+            .ok_or_else(|| SettingsError::UnknownProfile {
+                name: name.to_string(),
+                available: self.profiles.keys().cloned().collect(),
+            })?;
+
+        let mut table = self.to_table()?;
+        for (section, fields) in overrides {
+            let key = |field: &str| format!("profiles.{}.{}.{}", name, section, field);
+            let Some(fields) = fields.as_table() else {
+                return Err(SettingsError::UnknownKey {
+                    key: format!("profiles.{}.{}", name, section),
+                    valid: Settings::keys(),
+                });
+            };
+            for (field, value) in fields {
+                let dotted = format!("{}.{}", section, field);
+                let current =
+                    field_mut(&mut table, &dotted).map_err(|_| SettingsError::UnknownKey {
+                        key: key(field),
+                        valid: Settings::keys(),
+                    })?;
+                let text = match value {
+                    toml::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                let invalid = |reason: String| SettingsError::InvalidValue {
+                    key: key(field),
+                    value: text.clone(),
+                    reason,
+                };
+                if current.type_str() != value.type_str() {
+                    return Err(invalid(format!("expected a {}", current.type_str())));
+                }
+                validate_value(&dotted, &text).map_err(invalid)?;
+                *current = value.clone();
+            }
+        }
+
+        toml::Value::Table(table)
+            .try_into()
+            .map_err(SettingsError::Deserialize)
+    }
+
+    fn to_table(&self) -> Result<toml::Table, SettingsError> {
+        toml::Table::try_from(self).map_err(SettingsError::Serialize)
+    }
+}
+
+/// Read a configuration file, treating a missing one as empty
+fn read_config(path: &Path) -> Result<String, SettingsError> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Ok(contents),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(SettingsError::Io {
+            path: path.to_path_buf(),
+            source: e,
+        }),
+    }
+}
+
+/// Look up `section.field` in a serialized [`Settings`] table
+fn field_mut<'a>(
+    table: &'a mut toml::Table,
+    key: &str,
+) -> Result<&'a mut toml::Value, SettingsError> {
+    key.split_once('.')
+        .and_then(|(section, field)| table.get_mut(section)?.as_table_mut()?.get_mut(field))
+        .filter(|value| !value.is_table())
+        .ok_or_else(|| SettingsError::UnknownKey {
+            key: key.to_string(),
+            valid: Settings::keys(),
+        })
+}
+
+/// Check the parts of a value its TOML type does not capture
+fn validate_value(key: &str, value: &str) -> Result<(), String> {
+    match key {
+        "write.block_size" | "benchmark.block_size" => {
+            parse_block_size_clamped(value, MIN_BLOCK_SIZE as u64, MAX_BLOCK_SIZE as u64)
+                .map(drop)
+                .map_err(|e| e.to_string())
+        }
+        "write.read_buffer_size" | "benchmark.test_size" | "network.cloud_chunk_size" => {
+            match parse_size(value) {
+                Ok(0) => Err("size must be greater than zero".to_string()),
+                Ok(_) => Ok(()),
+                Err(e) => Err(e.to_string()),
+            }
+        }
+        "checksum.algorithm" => value.parse::<ChecksumAlgorithm>().map(drop).map_err(|_| {
+            let names: Vec<_> = ChecksumAlgorithm::all()
+                .iter()
+                .map(|a| a.short_name())
+                .collect();
+            format!("unknown algorithm, expected one of: {}", names.join(", "))
+        }),
+        "benchmark.pattern" => value
+            .parse::<DataPattern>()
+            .map(drop)
+            .map_err(|_| "expected zeros, random or sequential".to_string()),
+        _ => Ok(()),
+    }
 }
 
 /// Errors that can occur when working with settings
@@ -320,6 +648,44 @@ pub enum SettingsError {
     /// Failed to deserialize settings
     #[error("Failed to parse settings: {0}")]
     Deserialize(toml::de::Error),
+
+    /// The configuration file is not valid TOML or holds invalid settings
+    #[error("Failed to parse {path}: {source}")]
+    Parse {
+        /// The configuration file
+        path: PathBuf,
+        /// The underlying error
+        source: toml::de::Error,
+    },
+
+    /// No setting has this dotted key
+    #[error("Unknown setting '{key}'. Valid keys: {}", valid.join(", "))]
+    UnknownKey {
+        /// The key that was requested
+        key: String,
+        /// Every valid key
+        valid: Vec<String>,
+    },
+
+    /// A selected profile is not defined
+    #[error("Unknown profile '{name}'. Defined profiles: {}", if available.is_empty() { "none".to_string() } else { available.join(", ") })]
+    UnknownProfile {
+        /// The profile that was requested
+        name: String,
+        /// Every profile in the configuration file
+        available: Vec<String>,
+    },
+
+    /// A value cannot be stored in its setting
+    #[error("Invalid value '{value}' for {key}: {reason}")]
+    InvalidValue {
+        /// The setting's dotted key
+        key: String,
+        /// The rejected value
+        value: String,
+        /// Why it was rejected
+        reason: String,
+    },
 }
 
 #[cfg(test)]
@@ -340,12 +706,14 @@ mod tests {
         assert!(!settings.checksum.auto_detect);
         assert!(!settings.behavior.skip_confirmation);
         assert!(!settings.behavior.quiet);
+        assert!(settings.behavior.record_history);
         // Benchmark defaults
         assert_eq!(settings.benchmark.block_size, "4M");
         assert_eq!(settings.benchmark.test_size, "256M");
         assert_eq!(settings.benchmark.pattern, "zeros");
         assert_eq!(settings.benchmark.passes, 1);
         assert!(!settings.benchmark.json);
+        assert!(!settings.benchmark.record);
         // Network defaults
         assert_eq!(settings.network.cloud_chunk_size, "4M");
     }
@@ -363,6 +731,7 @@ mod tests {
                 retry_attempts: 5,
                 retry_delay_ms: 200,
                 read_buffer_size: "128K".to_string(),
+                checkpoint_interval_seconds: 10,
             },
             checksum: ChecksumSettings {
                 algorithm: "sha512".to_string(),
@@ -371,6 +740,8 @@ mod tests {
             behavior: BehaviorSettings {
                 skip_confirmation: true,
                 quiet: false,
+                record_history: false,
+                default_profile: None,
             },
             benchmark: BenchmarkSettings {
                 block_size: "16M".to_string(),
@@ -378,12 +749,16 @@ mod tests {
                 pattern: "random".to_string(),
                 passes: 3,
                 json: true,
+                record: true,
             },
             network: NetworkSettings {
                 http_timeout_secs: 45,
                 validation_timeout_secs: 20,
+                http_max_retries: 5,
+                http_backoff_base_ms: 1000,
                 cloud_chunk_size: "8M".to_string(),
             },
+            profiles: BTreeMap::new(),
         };
 
         // Save
@@ -500,6 +875,7 @@ verify = true
         assert_eq!(write.retry_attempts, 3);
         assert_eq!(write.retry_delay_ms, 100);
         assert_eq!(write.read_buffer_size, "64K");
+        assert_eq!(write.checkpoint_interval_seconds, 30);
     }
 
     #[test]
@@ -517,6 +893,7 @@ verify = true
         assert_eq!(benchmark.pattern, "zeros");
         assert_eq!(benchmark.passes, 1);
         assert!(!benchmark.json);
+        assert!(!benchmark.record);
     }
 
     #[test]
@@ -524,6 +901,7 @@ verify = true
         let behavior = BehaviorSettings::default();
         assert!(!behavior.skip_confirmation);
         assert!(!behavior.quiet);
+        assert!(behavior.record_history);
     }
 
     #[test]
@@ -543,6 +921,8 @@ verify = true
         let network = NetworkSettings::default();
         assert_eq!(network.http_timeout_secs, 30);
         assert_eq!(network.validation_timeout_secs, 10);
+        assert_eq!(network.http_max_retries, 3);
+        assert_eq!(network.http_backoff_base_ms, 250);
         assert_eq!(network.cloud_chunk_size, "4M");
     }
 
@@ -580,6 +960,7 @@ http_timeout_secs = 120
         assert_eq!(settings.network.http_timeout_secs, 120);
         // validation_timeout_secs should use default
         assert_eq!(settings.network.validation_timeout_secs, 10);
+        assert_eq!(settings.network.http_max_retries, DEFAULT_HTTP_MAX_RETRIES);
     }
 
     #[test]
@@ -588,6 +969,8 @@ http_timeout_secs = 120
         assert!(config_str.contains("[network]"));
         assert!(config_str.contains("http_timeout_secs"));
         assert!(config_str.contains("validation_timeout_secs"));
+        assert!(config_str.contains("http_max_retries"));
+        assert!(config_str.contains("http_backoff_base_ms"));
         assert!(config_str.contains("cloud_chunk_size"));
     }
 
@@ -638,4 +1021,249 @@ cloud_chunk_size = "8M"
         assert!(config_str.contains("retry_delay_ms"));
         assert!(config_str.contains("read_buffer_size"));
     }
+
+    // ---- get/set tests ----
+
+    #[test]
+    fn test_keys_cover_every_section() {
+        let keys = Settings::keys();
+        assert!(keys.contains(&"write.block_size".to_string()));
+        assert!(keys.contains(&"checksum.algorithm".to_string()));
+        assert!(keys.contains(&"behavior.record_history".to_string()));
+        assert!(keys.contains(&"benchmark.passes".to_string()));
+        assert!(keys.contains(&"network.cloud_chunk_size".to_string()));
+        assert!(keys.iter().all(|k| Settings::default().get(k).is_ok()));
+    }
+
+    #[test]
+    fn test_get_formats_values() {
+        let settings = Settings::default();
+        assert_eq!(settings.get("write.block_size").unwrap(), "4M");
+        assert_eq!(settings.get("write.verify").unwrap(), "false");
+        assert_eq!(settings.get("write.retry_attempts").unwrap(), "3");
+    }
+
+    #[test]
+    fn test_get_unknown_key_lists_valid_keys() {
+        let err = Settings::default().get("write.nope").unwrap_err();
+        assert!(matches!(err, SettingsError::UnknownKey { .. }));
+        assert!(err.to_string().contains("write.block_size"));
+
+        assert!(Settings::default().get("write").is_err());
+        assert!(Settings::default().get("block_size").is_err());
+    }
+
+    #[test]
+    fn test_set_updates_typed_fields() {
+        let mut settings = Settings::default();
+        settings.set("write.block_size", "1M").unwrap();
+        settings.set("write.verify", "true").unwrap();
+        settings.set("network.http_max_retries", "5").unwrap();
+        settings.set("checksum.algorithm", "sha512").unwrap();
+
+        assert_eq!(settings.write.block_size, "1M");
+        assert!(settings.write.verify);
+        assert_eq!(settings.network.http_max_retries, 5);
+        assert_eq!(settings.checksum.algorithm, "sha512");
+    }
+
+    #[test]
+    fn test_set_rejects_invalid_values() {
+        let mut settings = Settings::default();
+        for (key, value) in [
+            ("write.verify", "yes"),
+            ("write.retry_attempts", "-1"),
+            ("write.retry_attempts", "99999999999"),
+            ("write.block_size", "1G"),
+            ("write.block_size", "abc"),
+            ("write.read_buffer_size", "0"),
+            ("checksum.algorithm", "blake3"),
+            ("benchmark.pattern", "noise"),
+        ] {
+            let err = settings.set(key, value).unwrap_err();
+            assert!(
+                matches!(err, SettingsError::InvalidValue { .. }),
+                "{}={} gave {:?}",
+                key,
+                value,
+                err
+            );
+        }
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[test]
+    fn test_set_unknown_key() {
+        let mut settings = Settings::default();
+        let err = settings.set("checksum.sha", "1").unwrap_err();
+        assert!(matches!(err, SettingsError::UnknownKey { .. }));
+    }
+
+    #[test]
+    fn test_try_load_from_path_missing_file_is_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("missing.toml");
+        assert_eq!(
+            Settings::try_load_from_path(&path).unwrap(),
+            Settings::default()
+        );
+    }
+
+    #[test]
+    fn test_try_load_from_path_rejects_malformed_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        std::fs::write(&path, "[write\nblock_size = 4").unwrap();
+
+        let err = Settings::try_load_from_path(&path).unwrap_err();
+        assert!(matches!(err, SettingsError::Parse { .. }));
+    }
+
+    #[test]
+    fn test_set_in_file_keeps_comments_and_other_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        let original = "# My settings\n[write]\nblock_size = \"2M\" # fast stick\nverify = true\n";
+        std::fs::write(&path, original).unwrap();
+
+        let settings = Settings::set_in_file(&path, "write.block_size", "1M").unwrap();
+        assert_eq!(settings.write.block_size, "1M");
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# My settings\n[write]\nblock_size = \"1M\" # fast stick\nverify = true\n"
+        );
+
+        Settings::set_in_file(&path, "checksum.auto_detect", "true").unwrap();
+        let loaded = Settings::try_load_from_path(&path).unwrap();
+        assert!(loaded.checksum.auto_detect);
+        assert!(loaded.write.verify);
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .starts_with("# My settings\n"));
+    }
+
+    #[test]
+    fn test_set_in_file_leaves_malformed_file_untouched() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        std::fs::write(&path, "verify = = true").unwrap();
+
+        let err = Settings::set_in_file(&path, "write.verify", "false").unwrap_err();
+        assert!(matches!(err, SettingsError::Parse { .. }));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "verify = = true");
+    }
+
+    // ---- profile tests ----
+
+    const PROFILES_CONFIG: &str = r#"
+[write]
+block_size = "2M"
+verify = true
+
+[profiles.sd.write]
+block_size = "1M"
+
+[profiles.ssd.write]
+block_size = "16M"
+verify = false
+
+[profiles.ssd.checksum]
+algorithm = "sha512"
+"#;
+
+    fn profiles_settings() -> Settings {
+        toml::from_str(PROFILES_CONFIG).unwrap()
+    }
+
+    #[test]
+    fn test_profile_precedence() {
+        let base = profiles_settings();
+        let sd = base.with_profile(Some("sd")).unwrap();
+
+        // Profile beats base
+        assert_eq!(sd.write.block_size, "1M");
+        // Base beats built-in default where the profile is silent
+        assert!(sd.write.verify);
+        // Built-in default where neither sets a value
+        assert_eq!(sd.write.retry_attempts, DEFAULT_RETRY_ATTEMPTS);
+        assert_eq!(sd.checksum.algorithm, "sha256");
+
+        let ssd = base.with_profile(Some("ssd")).unwrap();
+        assert_eq!(ssd.write.block_size, "16M");
+        assert!(!ssd.write.verify);
+        assert_eq!(ssd.checksum.algorithm, "sha512");
+    }
+
+    #[test]
+    fn test_no_profile_leaves_settings_unchanged() {
+        let base = profiles_settings();
+        assert_eq!(base.with_profile(None).unwrap(), base);
+        assert_eq!(base.profile_names(), vec!["sd", "ssd"]);
+    }
+
+    #[test]
+    fn test_default_profile() {
+        let mut base = profiles_settings();
+        base.behavior.default_profile = Some("ssd".to_string());
+        assert_eq!(base.with_profile(None).unwrap().write.block_size, "16M");
+        // An explicit profile overrides the default one
+        assert_eq!(
+            base.with_profile(Some("sd")).unwrap().write.block_size,
+            "1M"
+        );
+    }
+
+    #[test]
+    fn test_unknown_profile() {
+        let base = profiles_settings();
+        let err = base.with_profile(Some("usb")).unwrap_err();
+        assert!(matches!(err, SettingsError::UnknownProfile { .. }));
+        assert!(err.to_string().contains("sd, ssd"));
+
+        let mut base = Settings::default();
+        base.behavior.default_profile = Some("sd".to_string());
+        let err = base.with_profile(None).unwrap_err();
+        assert!(err.to_string().contains("Defined profiles: none"));
+    }
+
+    #[test]
+    fn test_profile_rejects_bad_overrides() {
+        for (config, key) in [
+            ("[profiles.x.write]\ncolour = 1", "profiles.x.write.colour"),
+            (
+                "[profiles.x.write]\nverify = \"yes\"",
+                "profiles.x.write.verify",
+            ),
+            (
+                "[profiles.x.write]\nblock_size = \"1G\"",
+                "profiles.x.write.block_size",
+            ),
+            ("[profiles.x]\nwrite = 1", "profiles.x.write"),
+        ] {
+            let settings: Settings = toml::from_str(config).unwrap();
+            let err = settings.with_profile(Some("x")).unwrap_err();
+            assert!(err.to_string().contains(key), "{}: {}", config, err);
+        }
+    }
+
+    #[test]
+    fn test_profiles_survive_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("engraver_config.toml");
+
+        let mut settings = profiles_settings();
+        settings.set("write.retry_attempts", "7").unwrap();
+        settings.save_to_path(Some(config_path.clone())).unwrap();
+
+        let loaded = Settings::load_from_path(Some(config_path));
+        assert_eq!(loaded, settings);
+        assert_eq!(
+            loaded
+                .with_profile(Some("sd"))
+                .unwrap()
+                .write
+                .retry_attempts,
+            7
+        );
+    }
 }
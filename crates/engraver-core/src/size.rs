@@ -0,0 +1,352 @@
+//! Human-friendly size parsing
+//!
+//! Parses strings such as `"4M"`, `"512 KiB"` or `"1.5G"` into byte counts.
+//! All units are binary (1 K = 1024 bytes), matching how block sizes are
+//! described throughout the rest of Engraver:
+//!
+//! | Unit | Accepted spellings | Multiplier |
+//! |------|--------------------|------------|
+//! | bytes | *(none)*, `B` | 1 |
+//! | kibibytes | `K`, `KB`, `KiB` | 1024 |
+//! | mebibytes | `M`, `MB`, `MiB` | 1024² |
+//! | gibibytes | `G`, `GB`, `GiB` | 1024³ |
+//! | tebibytes | `T`, `TB`, `TiB` | 1024⁴ |
+//!
+//! Units are case-insensitive and may be separated from the number by
+//! whitespace. Decimal values are accepted as long as they resolve to a whole
+//! number of bytes (`"1.5K"` is 1536 bytes, `"1.5"` is rejected).
+//!
+//! ## Example
+//!
+//! ```
+//! use engraver_core::{parse_block_size_clamped, parse_size};
+//!
+//! assert_eq!(parse_size("4M").unwrap(), 4 * 1024 * 1024);
+//! assert_eq!(parse_size("1.5 KiB").unwrap(), 1536);
+//! assert!(parse_block_size_clamped("1G", 4096, 64 * 1024 * 1024).is_err());
+//! ```
+
+use thiserror::Error;
+
+const KIB: u64 = 1024;
+const MIB: u64 = KIB * 1024;
+const GIB: u64 = MIB * 1024;
+const TIB: u64 = GIB * 1024;
+
+/// Errors that can occur while parsing a size string
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SizeError {
+    /// The string is not a number followed by an optional unit
+    #[error("Invalid size format: {0}")]
+    InvalidFormat(String),
+
+    /// A decimal value does not resolve to a whole number of bytes
+    #[error("Size is not a whole number of bytes: {0}")]
+    FractionalBytes(String),
+
+    /// The value does not fit in 64 bits
+    #[error("Size is too large: {0}")]
+    Overflow(String),
+
+    /// A block size could not be parsed
+    #[error("Invalid block size: {0}")]
+    InvalidBlockSize(String),
+
+    /// A block size is below the allowed minimum (in bytes)
+    #[error("Block size must be at least {}", format_size_short(*.0))]
+    BlockSizeTooSmall(u64),
+
+    /// A block size is above the allowed maximum (in bytes)
+    #[error("Block size must be at most {}", format_size_short(*.0))]
+    BlockSizeTooLarge(u64),
+}
+
+/// Result type for size parsing
+pub type Result<T> = std::result::Result<T, SizeError>;
+
+/// Parse a size string like `"4K"`, `"1.5G"` or `"256 MiB"` into bytes
+pub fn parse_size(s: &str) -> Result<u64> {
+    let s = s.trim();
+    let invalid = || SizeError::InvalidFormat(s.to_string());
+
+    let split_pos = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split_pos);
+    let multiplier = unit_multiplier(unit.trim_start()).ok_or_else(invalid)?;
+
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if whole.is_empty()
+        || (number.contains('.') && fraction.is_empty())
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+
+    // Work in u128 scaled by 10^digits so decimals are exact
+    let overflow = || SizeError::Overflow(s.to_string());
+    let whole: u128 = whole.parse().map_err(|_| overflow())?;
+    let fraction_value: u128 = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse().map_err(|_| overflow())?
+    };
+    let scale = u32::try_from(fraction.len())
+        .ok()
+        .and_then(|digits| 10u128.checked_pow(digits))
+        .ok_or_else(overflow)?;
+
+    let scaled = whole
+        .checked_mul(scale)
+        .and_then(|v| v.checked_add(fraction_value))
+        .and_then(|v| v.checked_mul(u128::from(multiplier)))
+        .ok_or_else(overflow)?;
+
+    if scaled % scale != 0 {
+        return Err(SizeError::FractionalBytes(s.to_string()));
+    }
+
+    u64::try_from(scaled / scale).map_err(|_| overflow())
+}
+
+/// Parse a block size and check it lies within `min..=max` bytes
+///
+/// Unparseable input is reported as [`SizeError::InvalidBlockSize`]; values
+/// out of range as [`SizeError::BlockSizeTooSmall`] or
+/// [`SizeError::BlockSizeTooLarge`].
+pub fn parse_block_size_clamped(s: &str, min: u64, max: u64) -> Result<u64> {
+    let size = parse_size(s).map_err(|e| match e {
+        SizeError::Overflow(_) => SizeError::BlockSizeTooLarge(max),
+        _ => SizeError::InvalidBlockSize(s.trim().to_string()),
+    })?;
+
+    if size < min {
+        return Err(SizeError::BlockSizeTooSmall(min));
+    }
+    if size > max {
+        return Err(SizeError::BlockSizeTooLarge(max));
+    }
+
+    Ok(size)
+}
+
+fn unit_multiplier(unit: &str) -> Option<u64> {
+    match unit.to_ascii_uppercase().as_str() {
+        "" | "B" => Some(1),
+        "K" | "KB" | "KIB" => Some(KIB),
+        "M" | "MB" | "MIB" => Some(MIB),
+        "G" | "GB" | "GIB" => Some(GIB),
+        "T" | "TB" | "TIB" => Some(TIB),
+        _ => None,
+    }
+}
+
+/// Format a byte count in the same compact form accepted by [`parse_size`]
+fn format_size_short(bytes: u64) -> String {
+    for (unit, multiplier) in [("T", TIB), ("G", GIB), ("M", MIB), ("K", KIB)] {
+        if bytes >= multiplier && bytes.is_multiple_of(multiplier) {
+            return format!("{}{}", bytes / multiplier, unit);
+        }
+    }
+    format!("{} bytes", bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // -------------------------------------------------------------------------
+    // parse_size tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_parse_size_units() {
+        let cases = [
+            ("0", 0),
+            ("1", 1),
+            ("4096", 4096),
+            ("512B", 512),
+            ("4K", 4 * KIB),
+            ("4KB", 4 * KIB),
+            ("4KiB", 4 * KIB),
+            ("1M", MIB),
+            ("1MB", MIB),
+            ("1MiB", MIB),
+            ("2G", 2 * GIB),
+            ("2GB", 2 * GIB),
+            ("2GiB", 2 * GIB),
+            ("3T", 3 * TIB),
+            ("3TB", 3 * TIB),
+            ("3TiB", 3 * TIB),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse_size(input).unwrap(), expected, "input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_size_case_insensitive() {
+        for input in ["4k", "4K", "4kb", "4Kb", "4kib", "4KIB", "4KiB"] {
+            assert_eq!(parse_size(input).unwrap(), 4 * KIB, "input: {:?}", input);
+        }
+        assert_eq!(parse_size("1g").unwrap(), GIB);
+        assert_eq!(parse_size("1t").unwrap(), TIB);
+        assert_eq!(parse_size("10b").unwrap(), 10);
+    }
+
+    #[test]
+    fn test_parse_size_whitespace() {
+        assert_eq!(parse_size("  4K  ").unwrap(), 4 * KIB);
+        assert_eq!(parse_size("\t1M\n").unwrap(), MIB);
+        assert_eq!(parse_size("4 KB").unwrap(), 4 * KIB);
+        assert_eq!(parse_size("256 MiB").unwrap(), 256 * MIB);
+    }
+
+    #[test]
+    fn test_parse_size_decimals() {
+        let cases = [
+            ("1.5K", 1536),
+            ("0.5M", 512 * KIB),
+            ("1.25G", GIB + GIB / 4),
+            ("2.0", 2),
+            ("1.5 TiB", TIB + TIB / 2),
+            ("0.25K", 256),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse_size(input).unwrap(), expected, "input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_size_fractional_bytes_rejected() {
+        assert_eq!(
+            parse_size("1.5"),
+            Err(SizeError::FractionalBytes("1.5".to_string()))
+        );
+        assert!(matches!(
+            parse_size("0.1K"),
+            Err(SizeError::FractionalBytes(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_size_invalid_format() {
+        for input in [
+            "", " ", "K", "abc", "xyzK", "4X", "4P", "4KK", "-1K", "+4K", "4 K B", ".5K", "4.",
+            "1.2.3", "4K4",
+        ] {
+            assert!(
+                matches!(parse_size(input), Err(SizeError::InvalidFormat(_))),
+                "input: {:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_size_overflow() {
+        assert_eq!(parse_size("18446744073709551615").unwrap(), u64::MAX);
+        assert!(matches!(
+            parse_size("18446744073709551616"),
+            Err(SizeError::Overflow(_))
+        ));
+        assert!(matches!(
+            parse_size("16777216T"),
+            Err(SizeError::Overflow(_))
+        ));
+        assert!(matches!(
+            parse_size("999999999999999999999999999999999999999999K"),
+            Err(SizeError::Overflow(_))
+        ));
+        assert!(matches!(
+            parse_size(&format!("1.{}", "0".repeat(64))),
+            Err(SizeError::Overflow(_))
+        ));
+    }
+
+    // -------------------------------------------------------------------------
+    // parse_block_size_clamped tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_parse_block_size_clamped_within_range() {
+        assert_eq!(
+            parse_block_size_clamped("4K", 4 * KIB, 64 * MIB).unwrap(),
+            4 * KIB
+        );
+        assert_eq!(
+            parse_block_size_clamped("4M", 4 * KIB, 64 * MIB).unwrap(),
+            4 * MIB
+        );
+        assert_eq!(
+            parse_block_size_clamped("64M", 4 * KIB, 64 * MIB).unwrap(),
+            64 * MIB
+        );
+        assert_eq!(
+            parse_block_size_clamped("1.5M", 4 * KIB, 64 * MIB).unwrap(),
+            MIB + MIB / 2
+        );
+    }
+
+    #[test]
+    fn test_parse_block_size_clamped_out_of_range() {
+        assert_eq!(
+            parse_block_size_clamped("2K", 4 * KIB, 64 * MIB),
+            Err(SizeError::BlockSizeTooSmall(4 * KIB))
+        );
+        assert_eq!(
+            parse_block_size_clamped("65M", 4 * KIB, 64 * MIB),
+            Err(SizeError::BlockSizeTooLarge(64 * MIB))
+        );
+        assert_eq!(
+            parse_block_size_clamped("1G", 4 * KIB, 64 * MIB),
+            Err(SizeError::BlockSizeTooLarge(64 * MIB))
+        );
+        assert_eq!(
+            parse_block_size_clamped("99999999999999999999", 4 * KIB, 64 * MIB),
+            Err(SizeError::BlockSizeTooLarge(64 * MIB))
+        );
+    }
+
+    #[test]
+    fn test_parse_block_size_clamped_invalid() {
+        for input in ["abc", "", "K", "-4K", "1.5"] {
+            assert!(
+                matches!(
+                    parse_block_size_clamped(input, 4 * KIB, 64 * MIB),
+                    Err(SizeError::InvalidBlockSize(_))
+                ),
+                "input: {:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_block_size_clamped_error_messages() {
+        let err = parse_block_size_clamped("100", 4 * KIB, 64 * MIB).unwrap_err();
+        assert_eq!(err.to_string(), "Block size must be at least 4K");
+
+        let err = parse_block_size_clamped("128M", 4 * KIB, 64 * MIB).unwrap_err();
+        assert_eq!(err.to_string(), "Block size must be at most 64M");
+
+        let err = parse_block_size_clamped(" abc ", 4 * KIB, 64 * MIB).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid block size: abc");
+    }
+
+    // -------------------------------------------------------------------------
+    // format_size_short tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_format_size_short() {
+        assert_eq!(format_size_short(0), "0 bytes");
+        assert_eq!(format_size_short(100), "100 bytes");
+        assert_eq!(format_size_short(4 * KIB), "4K");
+        assert_eq!(format_size_short(1536), "1536 bytes");
+        assert_eq!(format_size_short(64 * MIB), "64M");
+        assert_eq!(format_size_short(GIB), "1G");
+        assert_eq!(format_size_short(2 * TIB), "2T");
+    }
+}
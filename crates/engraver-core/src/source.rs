@@ -2,8 +2,10 @@
 //!
 //! This module handles reading from various source types:
 //! - Local files (ISO, IMG, raw)
+//! - Block devices, for cloning one drive to another
 //! - Remote URLs (HTTP/HTTPS) with resume support
 //! - Compressed files (gzip, xz, zstd, bzip2)
+//! - Tar archives (.tar, .tar.gz/.tgz) containing a single image
 //!
 //! ## Example
 //!
@@ -25,16 +27,21 @@
 use crate::error::{Error, Result};
 use crate::settings::{NetworkSettings, WriteSettings};
 #[cfg(feature = "remote")]
-use crate::settings::{DEFAULT_HTTP_TIMEOUT_SECS, DEFAULT_VALIDATION_TIMEOUT_SECS};
+use crate::settings::{
+    DEFAULT_HTTP_BACKOFF_BASE_MS, DEFAULT_HTTP_MAX_RETRIES, DEFAULT_HTTP_TIMEOUT_SECS,
+    DEFAULT_VALIDATION_TIMEOUT_SECS,
+};
+use engraver_platform::glob_match;
 use serde::{Deserialize, Serialize};
 use std::fs::File;
-use std::io::{BufReader, Read, Seek, SeekFrom};
-use std::path::Path;
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 #[cfg(any(feature = "s3", feature = "gcs", feature = "azure"))]
 use object_store::ObjectStoreExt;
-#[cfg(any(feature = "s3", feature = "gcs", feature = "azure"))]
-use std::sync::Arc;
 
 /// Default read buffer size in bytes (64 KB)
 pub const DEFAULT_READ_BUFFER_SIZE: usize = 64 * 1024;
@@ -43,11 +50,19 @@ pub const DEFAULT_READ_BUFFER_SIZE: usize = 64 * 1024;
 #[cfg(any(feature = "s3", feature = "gcs", feature = "azure"))]
 pub const DEFAULT_CLOUD_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
 
+/// Environment variable overriding the S3 endpoint, for MinIO, Wasabi and
+/// other S3-compatible services
+///
+/// Takes precedence over `AWS_ENDPOINT_URL`, so engraver can point at a local
+/// store without changing the endpoint other AWS tools use.
+#[cfg(feature = "s3")]
+pub const S3_ENDPOINT_ENV: &str = "ENGRAVER_S3_ENDPOINT";
+
 /// Parse a size string (e.g., "64K", "4M") to bytes
 ///
 /// Returns the default value if parsing fails.
 fn parse_size_with_default(s: &str, default: usize) -> usize {
-    crate::benchmark::parse_size(s)
+    crate::size::parse_size(s)
         .map(|v| v as usize)
         .unwrap_or(default)
 }
@@ -72,6 +87,12 @@ pub enum SourceType {
     Zstd,
     /// Bzip2 compressed (.bz2)
     Bzip2,
+    /// Tar archive containing an image (.tar)
+    Tar,
+    /// Gzip compressed tar archive (.tar.gz, .tgz)
+    TarGz,
+    /// Numbered parts of a split image (.001, .002, ...)
+    Split,
     /// AWS S3 or S3-compatible storage (s3://)
     #[cfg(feature = "s3")]
     S3,
@@ -88,10 +109,19 @@ impl SourceType {
     pub fn is_compressed(&self) -> bool {
         matches!(
             self,
-            SourceType::Gzip | SourceType::Xz | SourceType::Zstd | SourceType::Bzip2
+            SourceType::Gzip
+                | SourceType::Xz
+                | SourceType::Zstd
+                | SourceType::Bzip2
+                | SourceType::TarGz
         )
     }
 
+    /// Check if this source type is a tar archive
+    pub fn is_archive(&self) -> bool {
+        matches!(self, SourceType::Tar | SourceType::TarGz)
+    }
+
     /// Check if this source type is remote
     pub fn is_remote(&self) -> bool {
         matches!(self, SourceType::Remote)
@@ -117,6 +147,58 @@ impl SourceType {
         }
     }
 
+    /// Short description for display, e.g. `gzip compressed`
+    pub fn description(&self) -> &'static str {
+        match self {
+            SourceType::LocalFile => "local file",
+            SourceType::Remote => "remote URL",
+            SourceType::Gzip => "gzip compressed",
+            SourceType::Xz => "xz compressed",
+            SourceType::Zstd => "zstd compressed",
+            SourceType::Bzip2 => "bzip2 compressed",
+            SourceType::Tar => "tar archive",
+            SourceType::TarGz => "gzip compressed tar archive",
+            SourceType::Split => "split image",
+            #[cfg(feature = "s3")]
+            SourceType::S3 => "S3 object",
+            #[cfg(feature = "gcs")]
+            SourceType::Gcs => "GCS object",
+            #[cfg(feature = "azure")]
+            SourceType::Azure => "Azure blob",
+        }
+    }
+
+    /// File name suffixes [`detect_source_type`] maps to this type
+    ///
+    /// Empty for local files, which are anything not matched otherwise, and
+    /// for URL-based types (see [`SourceType::url_schemes`]).
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            SourceType::Gzip => &[".gz", ".gzip"],
+            SourceType::Xz => &[".xz"],
+            SourceType::Zstd => &[".zst", ".zstd"],
+            SourceType::Bzip2 => &[".bz2", ".bzip2"],
+            SourceType::Tar => &[".tar"],
+            SourceType::TarGz => &[".tar.gz", ".tgz"],
+            SourceType::Split => &[".001"],
+            _ => &[],
+        }
+    }
+
+    /// URL schemes [`detect_source_type`] maps to this type, e.g. `["http", "https"]`
+    pub fn url_schemes(&self) -> &'static [&'static str] {
+        match self {
+            SourceType::Remote => &["http", "https"],
+            #[cfg(feature = "s3")]
+            SourceType::S3 => &["s3"],
+            #[cfg(feature = "gcs")]
+            SourceType::Gcs => &["gs"],
+            #[cfg(feature = "azure")]
+            SourceType::Azure => &["azure"],
+            _ => &[],
+        }
+    }
+
     /// Get the compression extension
     pub fn extension(&self) -> Option<&'static str> {
         match self {
@@ -124,6 +206,7 @@ impl SourceType {
             SourceType::Xz => Some(".xz"),
             SourceType::Zstd => Some(".zst"),
             SourceType::Bzip2 => Some(".bz2"),
+            SourceType::TarGz => Some(".tar.gz"),
             _ => None,
         }
     }
@@ -154,7 +237,11 @@ pub fn detect_source_type(path: &str) -> SourceType {
 
     // Check compression by extension
     let lower = path.to_lowercase();
-    if lower.ends_with(".gz") || lower.ends_with(".gzip") {
+    if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+        SourceType::TarGz
+    } else if lower.ends_with(".tar") {
+        SourceType::Tar
+    } else if lower.ends_with(".gz") || lower.ends_with(".gzip") {
         SourceType::Gzip
     } else if lower.ends_with(".xz") {
         SourceType::Xz
@@ -162,11 +249,39 @@ pub fn detect_source_type(path: &str) -> SourceType {
         SourceType::Zstd
     } else if lower.ends_with(".bz2") || lower.ends_with(".bzip2") {
         SourceType::Bzip2
+    } else if lower.ends_with(".001") || is_glob(path) {
+        SourceType::Split
     } else {
         SourceType::LocalFile
     }
 }
 
+/// Describe the image format implied by a source's extension, for display
+///
+/// Compression suffixes (`.gz`, `.xz`, `.zst`, `.bz2`) and URL query strings
+/// are ignored, so `core-image.wic.xz` is reported as a `.wic` image. Yocto
+/// `.wic` and `.sdcard` images are plain raw disk images. Returns `None` for
+/// unrecognized extensions.
+pub fn image_kind(path: &str) -> Option<&'static str> {
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+    let mut lower = path.to_lowercase();
+    for ext in [".gz", ".gzip", ".xz", ".zst", ".zstd", ".bz2", ".bzip2"] {
+        if let Some(stripped) = lower.strip_suffix(ext) {
+            lower = stripped.to_string();
+            break;
+        }
+    }
+
+    let (_, ext) = lower.rsplit_once('.')?;
+    match ext {
+        "wic" => Some("raw disk image (.wic)"),
+        "sdcard" => Some("raw disk image (.sdcard)"),
+        "img" => Some("raw disk image (.img)"),
+        "iso" => Some("ISO image (.iso)"),
+        _ => None,
+    }
+}
+
 /// Detect compression type from magic bytes
 pub fn detect_compression_from_magic(bytes: &[u8]) -> Option<SourceType> {
     if bytes.len() < 6 {
@@ -208,6 +323,61 @@ pub fn detect_compression_from_magic(bytes: &[u8]) -> Option<SourceType> {
     None
 }
 
+/// Number of leading bytes [`looks_like_disk_image`] expects to inspect
+pub const DISK_IMAGE_PROBE_SIZE: usize = 64 * 1024;
+
+/// Recognize common disk image signatures in the first bytes of a source
+///
+/// Returns a short name for the detected format (ISO 9660, GPT, FAT boot
+/// sector or MBR), or `None` if nothing matched. Used as an advisory check
+/// to catch unrelated files (e.g. an HTML error page) before writing; pass
+/// up to [`DISK_IMAGE_PROBE_SIZE`] bytes of decompressed data.
+pub fn looks_like_disk_image(first_bytes: &[u8]) -> Option<&'static str> {
+    // ISO 9660: "CD001" in the primary volume descriptor at sector 16
+    if first_bytes.get(0x8001..0x8006) == Some(b"CD001") {
+        return Some("ISO 9660");
+    }
+
+    // GPT: "EFI PART" header signature in LBA 1
+    if first_bytes.get(512..520) == Some(b"EFI PART") {
+        return Some("GPT");
+    }
+
+    if first_bytes.get(510..512) != Some(&[0x55, 0xAA]) {
+        return None;
+    }
+
+    // FAT boot sectors carry the boot signature too, plus a filesystem type string
+    if first_bytes.get(0x36..0x39) == Some(b"FAT") || first_bytes.get(0x52..0x57) == Some(b"FAT32")
+    {
+        return Some("FAT boot sector");
+    }
+
+    Some("MBR")
+}
+
+/// Size of the boot sector [`boot_signature`] inspects
+pub const BOOT_SECTOR_SIZE: usize = 512;
+
+/// Recognize a PC boot sector: the `55 AA` signature, optionally with a GPT protective partition
+///
+/// Returns "GPT protective MBR" if any of the four partition entries has type
+/// `0xEE`, "MBR" for any other signed sector, or `None` if the first
+/// [`BOOT_SECTOR_SIZE`] bytes carry no boot signature.
+pub fn boot_signature(sector: &[u8]) -> Option<&'static str> {
+    if sector.get(510..512) != Some(&[0x55, 0xAA]) {
+        return None;
+    }
+
+    // Partition entries are 16 bytes from 0x1BE; the type byte is at +4
+    let protective = (0..4).any(|i| sector[0x1BE + i * 16 + 4] == 0xEE);
+    Some(if protective {
+        "GPT protective MBR"
+    } else {
+        "MBR"
+    })
+}
+
 // ============================================================================
 // Source Information
 // ============================================================================
@@ -238,6 +408,11 @@ pub struct SourceInfo {
 
     /// ETag (for HTTP sources, used for resume validation)
     pub etag: Option<String>,
+
+    /// Digest the server sent for the file, as `(algorithm, hex)`
+    ///
+    /// Taken from `Repr-Digest` or `Content-MD5` (for HTTP sources).
+    pub server_digest: Option<(crate::verifier::ChecksumAlgorithm, String)>,
 }
 
 impl SourceInfo {
@@ -252,6 +427,15 @@ impl SourceInfo {
             resumable: false,
             content_type: None,
             etag: None,
+            server_digest: None,
+        }
+    }
+
+    /// Create info for a split image whose parts total `size` bytes
+    pub fn split(path: &str, size: u64) -> Self {
+        Self {
+            source_type: SourceType::Split,
+            ..Self::local(path, size)
         }
     }
 
@@ -266,6 +450,30 @@ impl SourceInfo {
             resumable: false,
             content_type: None,
             etag: None,
+            server_digest: None,
+        }
+    }
+
+    /// Create info for an image extracted from a tar archive
+    ///
+    /// The size is the entry size from the tar header, so it is known up front
+    /// even when the archive itself is compressed.
+    pub fn archive(
+        path: &str,
+        archive_size: u64,
+        source_type: SourceType,
+        entry_size: u64,
+    ) -> Self {
+        Self {
+            path: path.to_string(),
+            source_type,
+            compressed_size: Some(archive_size),
+            size: Some(entry_size),
+            seekable: false,
+            resumable: false,
+            content_type: None,
+            etag: None,
+            server_digest: None,
         }
     }
 }
@@ -338,22 +546,23 @@ impl Seek for LocalFileSource {
 }
 
 // ============================================================================
-// Compressed Source Wrappers
+// In-Memory Source
 // ============================================================================
 
-/// Wrapper for gzip-compressed sources
-#[cfg(feature = "compression")]
-pub struct GzipSource<R: Read> {
-    decoder: flate2::read::GzDecoder<R>,
+/// An in-memory source backed by a byte buffer
+pub struct MemorySource {
+    data: Cursor<Vec<u8>>,
     info: SourceInfo,
 }
 
-#[cfg(feature = "compression")]
-impl<R: Read> GzipSource<R> {
-    /// Create a new gzip source
-    pub fn new(reader: R, info: SourceInfo) -> Self {
+impl MemorySource {
+    /// Create a source from bytes already held in memory
+    ///
+    /// `name` is used as the source path for display and checkpointing.
+    pub fn new(data: Vec<u8>, name: &str) -> Self {
+        let info = SourceInfo::local(name, data.len() as u64);
         Self {
-            decoder: flate2::read::GzDecoder::new(reader),
+            data: Cursor::new(data),
             info,
         }
     }
@@ -364,28 +573,66 @@ impl<R: Read> GzipSource<R> {
     }
 }
 
-#[cfg(feature = "compression")]
-impl<R: Read> Read for GzipSource<R> {
+impl Read for MemorySource {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        self.decoder.read(buf)
+        self.data.read(buf)
     }
 }
 
-/// Wrapper for xz-compressed sources
-#[cfg(feature = "compression")]
-pub struct XzSource<R: Read> {
-    decoder: xz2::read::XzDecoder<R>,
+impl Seek for MemorySource {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.data.seek(pos)
+    }
+}
+
+// ============================================================================
+// Block Device Source
+// ============================================================================
+
+/// Whether `path` is a block device (or, on Windows, a physical drive)
+///
+/// Such paths are read through [`DeviceSource`], since file metadata does
+/// not report a device's size.
+pub fn is_block_device(path: &str) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        std::fs::metadata(path).is_ok_and(|m| m.file_type().is_block_device())
+    }
+    #[cfg(windows)]
+    {
+        path.to_ascii_lowercase().starts_with(r"\\.\physicaldrive")
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = path;
+        false
+    }
+}
+
+/// A block device read as a source, for device-to-device clones
+///
+/// The device is opened read-only through the platform layer, which reports
+/// its real size; `metadata().len()` is 0 for block devices on Linux.
+pub struct DeviceSource {
+    device: Box<dyn engraver_platform::RawDevice>,
     info: SourceInfo,
 }
 
-#[cfg(feature = "compression")]
-impl<R: Read> XzSource<R> {
-    /// Create a new xz source
-    pub fn new(reader: R, info: SourceInfo) -> Self {
-        Self {
-            decoder: xz2::read::XzDecoder::new(reader),
-            info,
-        }
+impl DeviceSource {
+    /// Open a device read-only
+    ///
+    /// The device is not claimed exclusively, so it can be read while
+    /// mounted, though a mounted filesystem may change under the copy.
+    pub fn open(path: &str) -> Result<Self> {
+        let options = engraver_platform::OpenOptions::new()
+            .read(true)
+            .write(false)
+            .direct_io(false)
+            .exclusive(false);
+        let device = engraver_platform::open_device(path, options)?;
+        let info = SourceInfo::local(path, device.size());
+        Ok(Self { device, info })
     }
 
     /// Get source info
@@ -394,57 +641,331 @@ impl<R: Read> XzSource<R> {
     }
 }
 
-#[cfg(feature = "compression")]
-impl<R: Read> Read for XzSource<R> {
+impl Read for DeviceSource {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        self.decoder.read(buf)
+        self.device.read(buf)
     }
 }
 
-/// Wrapper for zstd-compressed sources
-#[cfg(feature = "compression")]
-pub struct ZstdSource<'a, R: Read> {
-    decoder: zstd::Decoder<'a, BufReader<R>>,
+impl Seek for DeviceSource {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.device.seek(pos)
+    }
+}
+
+// ============================================================================
+// Split Image Source
+// ============================================================================
+
+/// One numbered part of a split image
+#[derive(Debug, Clone)]
+struct SplitPart {
+    path: PathBuf,
+    /// Offset of the part's first byte within the joined image
+    start: u64,
+    size: u64,
+}
+
+/// A split image part that could not be opened mid-stream
+#[derive(Debug, thiserror::Error)]
+#[error("Cannot open split image part {}", path.display())]
+struct SplitPartError {
+    path: PathBuf,
+    #[source]
+    source: Error,
+}
+
+/// A multi-volume image (`image.img.001`, `image.img.002`, ...) read as one stream
+///
+/// Each part is read through a [`LocalFileSource`] in numeric order, so the
+/// parts behave like a single local image of their combined size. Seeking is
+/// supported, which lets split images be resumed.
+pub struct SplitSource {
+    parts: Vec<SplitPart>,
+    current: usize,
+    reader: Option<LocalFileSource>,
+    position: u64,
     info: SourceInfo,
 }
 
-#[cfg(feature = "compression")]
-impl<'a, R: Read> ZstdSource<'a, R> {
-    /// Create a new zstd source
-    pub fn new(reader: R, info: SourceInfo) -> Result<Self> {
-        let decoder = zstd::Decoder::new(reader).map_err(|e| Error::Decompression {
-            message: "Failed to create zstd decoder".to_string(),
-            source: Some(Box::new(e)),
-        })?;
-        Ok(Self { decoder, info })
+impl SplitSource {
+    /// Open a split image from its first part or a glob matching its parts
+    ///
+    /// See [`split_parts`] for how the parts are found.
+    pub fn open(path: &str) -> Result<Self> {
+        Self::from_parts(path, split_parts(path)?)
+    }
+
+    /// Open a split image from parts already listed in order
+    ///
+    /// `name` is used as the source path for display and checkpointing.
+    pub fn from_parts(name: &str, paths: Vec<PathBuf>) -> Result<Self> {
+        if paths.is_empty() {
+            return Err(Error::SourceNotFound(name.to_string()));
+        }
+
+        let mut parts = Vec::with_capacity(paths.len());
+        let mut start = 0;
+        for path in paths {
+            let size = std::fs::metadata(&path)
+                .map_err(|_| Error::SourceNotFound(path.display().to_string()))?
+                .len();
+            parts.push(SplitPart { path, start, size });
+            start += size;
+        }
+
+        Ok(Self {
+            parts,
+            current: 0,
+            reader: None,
+            position: 0,
+            info: SourceInfo::split(name, start),
+        })
     }
 
     /// Get source info
     pub fn info(&self) -> &SourceInfo {
         &self.info
     }
+
+    /// Paths of the parts, in the order they are read
+    pub fn part_paths(&self) -> impl Iterator<Item = &Path> {
+        self.parts.iter().map(|part| part.path.as_path())
+    }
+
+    /// Open the current part, positioned at the stream's current offset
+    fn open_current(&mut self) -> std::io::Result<&mut LocalFileSource> {
+        if self.reader.is_none() {
+            let part = &self.parts[self.current];
+            let mut reader = LocalFileSource::open(&part.path.to_string_lossy()).map_err(|e| {
+                let kind = match &e {
+                    Error::Io(io) => io.kind(),
+                    Error::SourceNotFound(_) => std::io::ErrorKind::NotFound,
+                    Error::PermissionDenied(_) => std::io::ErrorKind::PermissionDenied,
+                    _ => std::io::ErrorKind::Other,
+                };
+                let path = part.path.clone();
+                std::io::Error::new(kind, SplitPartError { path, source: e })
+            })?;
+            reader.seek(SeekFrom::Start(self.position - part.start))?;
+            self.reader = Some(reader);
+        }
+        Ok(self.reader.as_mut().expect("reader was just opened"))
+    }
 }
 
-#[cfg(feature = "compression")]
-impl<R: Read> Read for ZstdSource<'_, R> {
+impl Read for SplitSource {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        self.decoder.read(buf)
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        while self.current < self.parts.len() {
+            let n = self.open_current()?.read(buf)?;
+            if n > 0 {
+                self.position += n as u64;
+                return Ok(n);
+            }
+
+            let part = &self.parts[self.current];
+            if self.position != part.start + part.size {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    format!(
+                        "Split image part {} ended early at {} of {} bytes",
+                        part.path.display(),
+                        self.position - part.start,
+                        part.size
+                    ),
+                ));
+            }
+            self.current += 1;
+            self.reader = None;
+        }
+
+        Ok(0)
     }
 }
 
-/// Wrapper for bzip2-compressed sources
+impl Seek for SplitSource {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let total = self.info.size.unwrap_or(0);
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(delta) => total.checked_add_signed(delta),
+            SeekFrom::Current(delta) => self.position.checked_add_signed(delta),
+        }
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "invalid seek to a negative position",
+            )
+        })?;
+
+        self.position = target;
+        self.current = self
+            .parts
+            .partition_point(|part| part.start + part.size <= target);
+        self.reader = None;
+        Ok(target)
+    }
+}
+
+/// Find the parts of a split image, in numeric order
+///
+/// `path` is either the first part (`image.img.001`) or a glob over the
+/// parts' file names (`image.img.*`). Matching files must end in a numeric
+/// extension, which gives their order; anything else the glob matches is
+/// ignored. Parts found from a first part must start at `.001`, and the
+/// numbering must have no gaps: a missing part is reported as
+/// [`Error::SourceNotFound`] naming it.
+pub fn split_parts(path: &str) -> Result<Vec<PathBuf>> {
+    let as_path = Path::new(path);
+    let dir = match as_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let name = as_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| Error::SourceNotFound(path.to_string()))?;
+
+    let (pattern, first) = if is_glob(name) {
+        (name.to_string(), None)
+    } else {
+        let stem = name
+            .strip_suffix(".001")
+            .ok_or_else(|| Error::InvalidConfig(format!("{} is not a first split part", path)))?;
+        (format!("{}.*", stem), Some(1))
+    };
+
+    let mut numbered: Vec<(u64, usize, PathBuf)> = std::fs::read_dir(dir)
+        .map_err(|_| Error::SourceNotFound(path.to_string()))?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let file_name = entry.file_name().into_string().ok()?;
+            if !glob_match(&pattern, &file_name) {
+                return None;
+            }
+            let (_, ext) = file_name.rsplit_once('.')?;
+            if ext.is_empty() || !ext.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            Some((ext.parse().ok()?, ext.len(), entry.path()))
+        })
+        .collect();
+    numbered.sort_by_key(|(number, _, _)| *number);
+
+    let Some(&(lowest, _, _)) = numbered.first() else {
+        return Err(Error::SourceNotFound(path.to_string()));
+    };
+    for (expected, (number, width, part)) in (first.unwrap_or(lowest)..).zip(&numbered) {
+        if *number != expected {
+            let missing = part.with_extension(format!("{:0width$}", expected, width = width));
+            return Err(Error::SourceNotFound(missing.display().to_string()));
+        }
+    }
+
+    Ok(numbered.into_iter().map(|(_, _, part)| part).collect())
+}
+
+/// Check whether a file name contains glob wildcards
+fn is_glob(name: &str) -> bool {
+    name.contains(['*', '?'])
+}
+
+// ============================================================================
+// Compressed Source Wrappers
+// ============================================================================
+
+/// Shared count of the bytes read through a [`CountingReader`]
+///
+/// Clones share the same count, so a handle can be kept while the reader
+/// itself moves to another thread.
+#[derive(Debug, Clone, Default)]
+pub struct ByteCounter(Arc<AtomicU64>);
+
+impl ByteCounter {
+    /// Create a counter at zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bytes counted so far
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn add(&self, bytes: usize) {
+        self.0.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+}
+
+/// Reader that counts the bytes read from the reader it wraps
+///
+/// The decompressing sources read their compressed input through one, so
+/// progress can be measured against the compressed size when the
+/// decompressed size is unknown.
+#[derive(Debug)]
+pub struct CountingReader<R> {
+    inner: R,
+    counter: ByteCounter,
+}
+
+impl<R> CountingReader<R> {
+    /// Wrap `inner`, starting the count at zero
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            counter: ByteCounter::new(),
+        }
+    }
+
+    /// Bytes read from the inner reader so far
+    pub fn bytes_read(&self) -> u64 {
+        self.counter.get()
+    }
+
+    /// A handle to the count that stays valid after the reader moves
+    pub fn counter(&self) -> ByteCounter {
+        self.counter.clone()
+    }
+
+    /// Get a reference to the inner reader
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Unwrap the inner reader
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.counter.add(n);
+        Ok(n)
+    }
+}
+
+/// Wrapper for gzip-compressed sources
 #[cfg(feature = "compression")]
-pub struct Bzip2Source<R: Read> {
-    decoder: bzip2::read::BzDecoder<R>,
+pub struct GzipSource<R: Read> {
+    decoder: flate2::read::GzDecoder<CountingReader<R>>,
+    counter: ByteCounter,
     info: SourceInfo,
 }
 
 #[cfg(feature = "compression")]
-impl<R: Read> Bzip2Source<R> {
-    /// Create a new bzip2 source
+impl<R: Read> GzipSource<R> {
+    /// Create a new gzip source
     pub fn new(reader: R, info: SourceInfo) -> Self {
+        let reader = CountingReader::new(reader);
         Self {
-            decoder: bzip2::read::BzDecoder::new(reader),
+            counter: reader.counter(),
+            decoder: flate2::read::GzDecoder::new(reader),
             info,
         }
     }
@@ -453,30 +974,661 @@ impl<R: Read> Bzip2Source<R> {
     pub fn info(&self) -> &SourceInfo {
         &self.info
     }
+
+    /// Compressed bytes consumed so far
+    pub fn compressed_bytes_read(&self) -> u64 {
+        self.counter.get()
+    }
+
+    /// A handle to the compressed byte count, usable from another thread
+    pub fn compressed_counter(&self) -> ByteCounter {
+        self.counter.clone()
+    }
+
+    /// The compressed stream, for [`Source::reopen`] of a remote source
+    #[cfg(any(feature = "remote", feature = "s3", feature = "gcs", feature = "azure"))]
+    fn inner(&self) -> &R {
+        self.decoder.get_ref().get_ref()
+    }
 }
 
 #[cfg(feature = "compression")]
-impl<R: Read> Read for Bzip2Source<R> {
+impl<R: Read> Read for GzipSource<R> {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         self.decoder.read(buf)
     }
 }
 
-// ============================================================================
-// HTTP/HTTPS Source
-// ============================================================================
-
-/// HTTP source with resume support
-#[cfg(feature = "remote")]
-pub struct HttpSource {
-    response: reqwest::blocking::Response,
+/// Wrapper for xz-compressed sources
+#[cfg(feature = "compression")]
+pub struct XzSource<R: Read> {
+    decoder: xz2::read::XzDecoder<CountingReader<R>>,
+    counter: ByteCounter,
     info: SourceInfo,
-    bytes_read: u64,
 }
 
-#[cfg(feature = "remote")]
-impl HttpSource {
-    /// Open an HTTP/HTTPS URL
+#[cfg(feature = "compression")]
+impl<R: Read> XzSource<R> {
+    /// Create a new xz source
+    pub fn new(reader: R, info: SourceInfo) -> Self {
+        let reader = CountingReader::new(reader);
+        Self {
+            counter: reader.counter(),
+            decoder: xz2::read::XzDecoder::new(reader),
+            info,
+        }
+    }
+
+    /// Get source info
+    pub fn info(&self) -> &SourceInfo {
+        &self.info
+    }
+
+    /// Compressed bytes consumed so far
+    pub fn compressed_bytes_read(&self) -> u64 {
+        self.counter.get()
+    }
+
+    /// A handle to the compressed byte count, usable from another thread
+    pub fn compressed_counter(&self) -> ByteCounter {
+        self.counter.clone()
+    }
+
+    /// The compressed stream, for [`Source::reopen`] of a remote source
+    #[cfg(any(feature = "remote", feature = "s3", feature = "gcs", feature = "azure"))]
+    fn inner(&self) -> &R {
+        self.decoder.get_ref().get_ref()
+    }
+}
+
+#[cfg(feature = "compression")]
+impl<R: Read> Read for XzSource<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.decoder.read(buf)
+    }
+}
+
+/// Smallest zstd window the decoder can be limited to, as a power of two
+#[cfg(feature = "compression")]
+pub const ZSTD_WINDOW_LOG_MIN: u32 = 10;
+
+/// Largest zstd window the decoder can accept, as a power of two
+#[cfg(feature = "compression")]
+pub const ZSTD_WINDOW_LOG_MAX: u32 = if cfg!(target_pointer_width = "64") {
+    31
+} else {
+    30
+};
+
+/// Decoder settings for zstd sources
+///
+/// Images compressed with long-distance matching (`zstd --long`) can declare
+/// windows of up to 2 GiB, but libzstd refuses any window over 128 MiB
+/// (`window_log` 27) unless told otherwise. The default here accepts every
+/// window the format allows. The decoder only allocates what the stream
+/// declares, so ordinary images cost no extra memory.
+///
+/// There is no thread count, because libzstd decodes a frame on one thread
+/// (its worker threads only compress). Decoding already overlaps the device
+/// write through the writer's read-ahead, see
+/// [`WriteConfig::buffer_count`](crate::WriteConfig::buffer_count).
+#[cfg(feature = "compression")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZstdOptions {
+    /// Largest window accepted, as a power of two
+    pub window_log: u32,
+}
+
+#[cfg(feature = "compression")]
+impl Default for ZstdOptions {
+    fn default() -> Self {
+        Self {
+            window_log: ZSTD_WINDOW_LOG_MAX,
+        }
+    }
+}
+
+#[cfg(feature = "compression")]
+impl ZstdOptions {
+    /// Create options that accept any window
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the largest accepted window (clamped to the range zstd supports)
+    ///
+    /// Lowering it caps decoder memory for untrusted images. A stream with a
+    /// larger window fails on the first read.
+    pub fn window_log(mut self, window_log: u32) -> Self {
+        self.window_log = window_log.clamp(ZSTD_WINDOW_LOG_MIN, ZSTD_WINDOW_LOG_MAX);
+        self
+    }
+}
+
+/// Wrapper for zstd-compressed sources
+#[cfg(feature = "compression")]
+pub struct ZstdSource<'a, R: Read> {
+    decoder: zstd::Decoder<'a, BufReader<CountingReader<R>>>,
+    counter: ByteCounter,
+    info: SourceInfo,
+}
+
+#[cfg(feature = "compression")]
+impl<'a, R: Read> ZstdSource<'a, R> {
+    /// Create a new zstd source with the default [`ZstdOptions`]
+    pub fn new(reader: R, info: SourceInfo) -> Result<Self> {
+        Self::with_options(reader, info, ZstdOptions::default())
+    }
+
+    /// Create a new zstd source with custom decoder settings
+    pub fn with_options(reader: R, info: SourceInfo, options: ZstdOptions) -> Result<Self> {
+        let decoder_error = |e| Error::Decompression {
+            message: "Failed to create zstd decoder".to_string(),
+            source: Some(Box::new(e)),
+        };
+        let reader = CountingReader::new(reader);
+        let counter = reader.counter();
+        let mut decoder = zstd::Decoder::new(reader).map_err(decoder_error)?;
+        decoder
+            .window_log_max(options.window_log)
+            .map_err(decoder_error)?;
+        Ok(Self {
+            decoder,
+            counter,
+            info,
+        })
+    }
+
+    /// Get source info
+    pub fn info(&self) -> &SourceInfo {
+        &self.info
+    }
+
+    /// Compressed bytes consumed so far
+    ///
+    /// The decoder reads ahead through its own buffer, so this can lead the
+    /// decompressed output by up to one buffer.
+    pub fn compressed_bytes_read(&self) -> u64 {
+        self.counter.get()
+    }
+
+    /// A handle to the compressed byte count, usable from another thread
+    pub fn compressed_counter(&self) -> ByteCounter {
+        self.counter.clone()
+    }
+
+    /// The compressed stream, for [`Source::reopen`] of a remote source
+    #[cfg(any(feature = "remote", feature = "s3", feature = "gcs", feature = "azure"))]
+    fn inner(&self) -> &R {
+        self.decoder.get_ref().get_ref().get_ref()
+    }
+}
+
+#[cfg(feature = "compression")]
+impl<R: Read> Read for ZstdSource<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.decoder.read(buf)
+    }
+}
+
+/// Wrapper for bzip2-compressed sources
+#[cfg(feature = "compression")]
+pub struct Bzip2Source<R: Read> {
+    decoder: bzip2::read::BzDecoder<CountingReader<R>>,
+    counter: ByteCounter,
+    info: SourceInfo,
+}
+
+#[cfg(feature = "compression")]
+impl<R: Read> Bzip2Source<R> {
+    /// Create a new bzip2 source
+    pub fn new(reader: R, info: SourceInfo) -> Self {
+        let reader = CountingReader::new(reader);
+        Self {
+            counter: reader.counter(),
+            decoder: bzip2::read::BzDecoder::new(reader),
+            info,
+        }
+    }
+
+    /// Get source info
+    pub fn info(&self) -> &SourceInfo {
+        &self.info
+    }
+
+    /// Compressed bytes consumed so far
+    pub fn compressed_bytes_read(&self) -> u64 {
+        self.counter.get()
+    }
+
+    /// A handle to the compressed byte count, usable from another thread
+    pub fn compressed_counter(&self) -> ByteCounter {
+        self.counter.clone()
+    }
+
+    /// The compressed stream, for [`Source::reopen`] of a remote source
+    #[cfg(any(feature = "remote", feature = "s3", feature = "gcs", feature = "azure"))]
+    fn inner(&self) -> &R {
+        self.decoder.get_ref().get_ref()
+    }
+}
+
+#[cfg(feature = "compression")]
+impl<R: Read> Read for Bzip2Source<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.decoder.read(buf)
+    }
+}
+
+// ============================================================================
+// Tar Archive Source
+// ============================================================================
+
+/// A regular file found inside a tar archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TarEntry {
+    /// Path of the entry within the archive
+    pub path: String,
+
+    /// Size of the entry's data in bytes
+    pub size: u64,
+
+    /// Offset of the entry's data from the start of the archive stream
+    pub data_offset: u64,
+}
+
+/// List the regular files in a tar archive stream
+///
+/// Directories, links and other special entries are skipped.
+#[cfg(feature = "compression")]
+pub fn list_tar_entries<R: Read>(reader: R) -> Result<Vec<TarEntry>> {
+    let tar_error = |e: std::io::Error| Error::Decompression {
+        message: "Failed to read tar archive".to_string(),
+        source: Some(Box::new(e)),
+    };
+
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = Vec::new();
+
+    for entry in archive.entries().map_err(tar_error)? {
+        let entry = entry.map_err(tar_error)?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        entries.push(TarEntry {
+            path: entry
+                .path()
+                .map_err(tar_error)?
+                .to_string_lossy()
+                .into_owned(),
+            size: entry.size(),
+            data_offset: entry.raw_file_position(),
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Pick the entry to extract from a tar archive
+///
+/// With a `pattern`, the entry whose path (or file name) matches the glob is
+/// chosen. Without one, the archive must contain exactly one regular file.
+/// Ambiguous or empty matches return an error listing the candidates.
+pub fn select_tar_entry(entries: &[TarEntry], pattern: Option<&str>) -> Result<TarEntry> {
+    let candidates: Vec<&TarEntry> = match pattern {
+        Some(pattern) => entries
+            .iter()
+            .filter(|e| {
+                let name = e.path.rsplit('/').next().unwrap_or(&e.path);
+                glob_match(pattern, &e.path) || glob_match(pattern, name)
+            })
+            .collect(),
+        None => entries.iter().collect(),
+    };
+
+    let list = |items: &[&TarEntry]| {
+        items
+            .iter()
+            .map(|e| format!("  {}", e.path))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    match (candidates.as_slice(), pattern) {
+        ([entry], _) => Ok((*entry).clone()),
+        ([], None) => Err(Error::InvalidConfig(
+            "Tar archive contains no regular files".to_string(),
+        )),
+        ([], Some(pattern)) => Err(Error::InvalidConfig(format!(
+            "No tar entry matches '{}'. Available entries:\n{}",
+            pattern,
+            list(&entries.iter().collect::<Vec<_>>())
+        ))),
+        (many, None) => Err(Error::InvalidConfig(format!(
+            "Tar archive contains multiple files; choose one with --entry:\n{}",
+            list(many)
+        ))),
+        (many, Some(pattern)) => Err(Error::InvalidConfig(format!(
+            "Multiple tar entries match '{}':\n{}",
+            pattern,
+            list(many)
+        ))),
+    }
+}
+
+/// Streams a single entry out of a tar archive
+#[cfg(feature = "compression")]
+pub struct TarSource<R: Read> {
+    reader: std::io::Take<R>,
+    info: SourceInfo,
+    entry: String,
+}
+
+#[cfg(feature = "compression")]
+impl<R: Read> TarSource<R> {
+    /// Create a tar source positioned at the start of the archive stream
+    ///
+    /// Skips forward to `entry`'s data and limits reads to its size.
+    pub fn new(mut reader: R, entry: &TarEntry, info: SourceInfo) -> Result<Self> {
+        let skipped = std::io::copy(
+            &mut (&mut reader).take(entry.data_offset),
+            &mut std::io::sink(),
+        )?;
+        if skipped < entry.data_offset {
+            return Err(Error::Decompression {
+                message: format!("Tar archive truncated before entry '{}'", entry.path),
+                source: None,
+            });
+        }
+
+        Ok(Self {
+            reader: reader.take(entry.size),
+            info,
+            entry: entry.path.clone(),
+        })
+    }
+
+    /// Get source info
+    pub fn info(&self) -> &SourceInfo {
+        &self.info
+    }
+
+    /// Path of the extracted entry within the archive
+    pub fn entry_path(&self) -> &str {
+        &self.entry
+    }
+}
+
+#[cfg(feature = "compression")]
+impl<R: Read> Read for TarSource<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
+
+/// Open a local tar archive, decompressing it when it is a `.tar.gz`
+#[cfg(feature = "compression")]
+fn open_tar_stream(path: &str, source_type: SourceType) -> Result<Box<dyn Read + Send>> {
+    let file = open_file_buffered(path)?;
+    if source_type == SourceType::TarGz {
+        Ok(Box::new(flate2::read::GzDecoder::new(file)))
+    } else {
+        Ok(Box::new(file))
+    }
+}
+
+/// Scan a local tar archive and pick the entry to extract
+#[cfg(feature = "compression")]
+fn find_tar_entry(path: &str, source_type: SourceType, pattern: Option<&str>) -> Result<TarEntry> {
+    let entries = list_tar_entries(open_tar_stream(path, source_type)?)?;
+    select_tar_entry(&entries, pattern)
+}
+
+// ============================================================================
+// HTTP/HTTPS Source
+// ============================================================================
+
+/// User agent sent with every HTTP request
+#[cfg(feature = "remote")]
+const HTTP_USER_AGENT: &str = concat!("engraver/", env!("CARGO_PKG_VERSION"));
+
+/// A pooled HTTP client whose connections are kept alive between requests
+///
+/// Clones share one connection pool, so checking a URL, fetching its
+/// checksum file and downloading the image reuse connections and TLS
+/// sessions. Timeouts are set per request from [`HttpOptions::timeout`], so
+/// one client serves every caller. Without an explicit client,
+/// [`HttpClient::shared`] is used.
+#[cfg(feature = "remote")]
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    inner: Arc<reqwest::blocking::Client>,
+}
+
+#[cfg(feature = "remote")]
+impl HttpClient {
+    /// The process-wide client, created on first use
+    pub fn shared() -> Result<Self> {
+        static SHARED: std::sync::OnceLock<HttpClient> = std::sync::OnceLock::new();
+        if let Some(client) = SHARED.get() {
+            return Ok(client.clone());
+        }
+        let client = HttpClientBuilder::new().build()?;
+        Ok(SHARED.get_or_init(|| client).clone())
+    }
+
+    /// Start configuring a client of its own
+    pub fn builder() -> HttpClientBuilder {
+        HttpClientBuilder::new()
+    }
+
+    /// Wrap an already configured `reqwest` client, e.g. one pointed at a test proxy
+    pub fn from_reqwest(client: reqwest::blocking::Client) -> Self {
+        Self {
+            inner: Arc::new(client),
+        }
+    }
+
+    fn get(&self, url: &str, timeout: std::time::Duration) -> reqwest::blocking::RequestBuilder {
+        self.inner.get(url).timeout(timeout)
+    }
+
+    fn head(&self, url: &str, timeout: std::time::Duration) -> reqwest::blocking::RequestBuilder {
+        self.inner.head(url).timeout(timeout)
+    }
+}
+
+/// Clients are equal when they share a connection pool
+#[cfg(feature = "remote")]
+impl PartialEq for HttpClient {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+/// Builder for an [`HttpClient`] with its own proxy, headers or pool settings
+#[cfg(feature = "remote")]
+#[derive(Debug, Clone, Default)]
+pub struct HttpClientBuilder {
+    connect_timeout: Option<std::time::Duration>,
+    pool_idle_timeout: Option<std::time::Duration>,
+    proxy: Option<String>,
+    headers: Vec<(String, String)>,
+}
+
+#[cfg(feature = "remote")]
+impl HttpClientBuilder {
+    /// Create a builder with `reqwest`'s defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit how long establishing a connection may take
+    pub fn connect_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// How long an idle keep-alive connection stays in the pool
+    pub fn pool_idle_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Send every request through the proxy at `url`
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Add a header sent with every request, e.g. an authorization token
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Build the client
+    ///
+    /// Fails if the proxy URL or a header is invalid.
+    pub fn build(self) -> Result<HttpClient> {
+        let invalid = |what: &str, e: &dyn std::fmt::Display| {
+            Error::InvalidConfig(format!("Invalid HTTP {}: {}", what, e))
+        };
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| invalid("header name", &e))?;
+            let value = reqwest::header::HeaderValue::from_str(value)
+                .map_err(|e| invalid("header value", &e))?;
+            headers.append(name, value);
+        }
+
+        let mut builder = reqwest::blocking::Client::builder()
+            .user_agent(HTTP_USER_AGENT)
+            .default_headers(headers)
+            // Per-request timeouts replace the blocking client's 30s default
+            .timeout(None);
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy).map_err(|e| invalid("proxy", &e))?);
+        }
+
+        let client = builder.build().map_err(|e| Error::Network {
+            message: "Failed to create HTTP client".to_string(),
+            source: Some(Box::new(e)),
+        })?;
+        Ok(HttpClient::from_reqwest(client))
+    }
+}
+
+/// Options controlling HTTP downloads
+#[cfg(feature = "remote")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpOptions {
+    /// Timeout for each HTTP request
+    pub timeout: std::time::Duration,
+    /// Maximum number of retries after a dropped connection or 5xx response
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled on each subsequent attempt
+    pub backoff_base: std::time::Duration,
+    /// Client to send requests with; `None` uses [`HttpClient::shared`]
+    pub client: Option<HttpClient>,
+}
+
+#[cfg(feature = "remote")]
+impl Default for HttpOptions {
+    fn default() -> Self {
+        Self {
+            timeout: std::time::Duration::from_secs(DEFAULT_HTTP_TIMEOUT_SECS),
+            max_retries: DEFAULT_HTTP_MAX_RETRIES,
+            backoff_base: std::time::Duration::from_millis(DEFAULT_HTTP_BACKOFF_BASE_MS),
+            client: None,
+        }
+    }
+}
+
+#[cfg(feature = "remote")]
+impl HttpOptions {
+    /// Create options with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the per-request timeout
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the maximum number of retries (0 disables retrying)
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the initial retry delay
+    pub fn backoff_base(mut self, backoff_base: std::time::Duration) -> Self {
+        self.backoff_base = backoff_base;
+        self
+    }
+
+    /// Send requests with `client` instead of the shared one
+    pub fn client(mut self, client: HttpClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Delay before the given retry attempt (0-based)
+    pub fn backoff_delay(&self, attempt: u32) -> std::time::Duration {
+        self.backoff_base.saturating_mul(1u32 << attempt.min(10))
+    }
+
+    /// The client requests are sent with
+    fn http_client(&self) -> Result<HttpClient> {
+        match &self.client {
+            Some(client) => Ok(client.clone()),
+            None => HttpClient::shared(),
+        }
+    }
+}
+
+#[cfg(feature = "remote")]
+impl From<&NetworkSettings> for HttpOptions {
+    fn from(settings: &NetworkSettings) -> Self {
+        Self {
+            timeout: std::time::Duration::from_secs(settings.http_timeout_secs),
+            max_retries: settings.http_max_retries,
+            backoff_base: std::time::Duration::from_millis(settings.http_backoff_base_ms),
+            client: None,
+        }
+    }
+}
+
+/// HTTP source with resume support
+///
+/// Transient failures (connection errors, timeouts, and 5xx responses) are
+/// retried with exponential backoff, both when opening and mid-stream. A
+/// dropped stream is resumed with a ranged request from the current offset
+/// when the server supports it.
+#[cfg(feature = "remote")]
+pub struct HttpSource {
+    response: reqwest::blocking::Response,
+    info: SourceInfo,
+    bytes_read: u64,
+    client: HttpClient,
+    options: HttpOptions,
+    resumed: bool,
+}
+
+#[cfg(feature = "remote")]
+impl HttpSource {
+    /// Open an HTTP/HTTPS URL
     pub fn open(url: &str) -> Result<Self> {
         Self::open_with_settings(url, 0, None)
     }
@@ -488,16 +1640,50 @@ impl HttpSource {
 
     /// Open an HTTP/HTTPS URL with custom network settings
     ///
-    /// If `settings` is `None`, default timeout values are used.
+    /// If `settings` is `None`, default timeout and retry values are used.
     pub fn open_with_settings(
         url: &str,
         offset: u64,
         settings: Option<&NetworkSettings>,
     ) -> Result<Self> {
-        let timeout_secs = settings
-            .map(|s| s.http_timeout_secs)
-            .unwrap_or(DEFAULT_HTTP_TIMEOUT_SECS);
+        let options = settings.map(HttpOptions::from).unwrap_or_default();
+        Self::open_with_options(url, offset, options)
+    }
 
+    /// Open an HTTP/HTTPS URL with explicit HTTP options
+    pub fn open_with_options(url: &str, offset: u64, options: HttpOptions) -> Result<Self> {
+        Self::open_inner(url, offset, options, None)
+    }
+
+    /// Open a URL that [`validate_source`] has already checked
+    ///
+    /// Headers the GET leaves out (size, `Accept-Ranges`, content type, ETag)
+    /// are taken from the validation's HEAD instead of being lost. A resumed
+    /// request carries the validated ETag in `If-Range`; if the server's copy
+    /// changed, or it ignores the range, an error is returned rather than
+    /// writing the new file's bytes after the old one's.
+    pub fn open_from_info(info: &SourceInfo, offset: u64, options: HttpOptions) -> Result<Self> {
+        let source = Self::open_inner(&info.path, offset, options, Some(info))?;
+        if offset > 0 && !source.resumed {
+            let reason = if info.etag.is_some() {
+                "the file changed since it was checked"
+            } else {
+                "the server ignored the range request"
+            };
+            return Err(Error::Network {
+                message: format!("Cannot resume {} at byte {}: {}", info.path, offset, reason),
+                source: None,
+            });
+        }
+        Ok(source)
+    }
+
+    fn open_inner(
+        url: &str,
+        offset: u64,
+        options: HttpOptions,
+        validated: Option<&SourceInfo>,
+    ) -> Result<Self> {
         // Validate URL
         let parsed_url = url::Url::parse(url).map_err(|e| Error::Network {
             message: format!("Invalid URL '{}'", url),
@@ -511,55 +1697,10 @@ impl HttpSource {
             });
         }
 
-        // Build request
-        let client = reqwest::blocking::Client::builder()
-            .user_agent(concat!("engraver/", env!("CARGO_PKG_VERSION")))
-            .timeout(std::time::Duration::from_secs(timeout_secs))
-            .build()
-            .map_err(|e| Error::Network {
-                message: "Failed to create HTTP client".to_string(),
-                source: Some(Box::new(e)),
-            })?;
-
-        let mut request = client.get(url);
-
-        // Add Range header for resume
-        if offset > 0 {
-            request = request.header("Range", format!("bytes={}-", offset));
-        }
-
-        // Send request
-        let response = request.send().map_err(|e| {
-            if e.is_timeout() {
-                Error::Network {
-                    message: format!("HTTP request timed out after {} seconds", timeout_secs),
-                    source: Some(Box::new(e)),
-                }
-            } else if e.is_connect() {
-                Error::Network {
-                    message: "Failed to connect to server".to_string(),
-                    source: Some(Box::new(e)),
-                }
-            } else {
-                Error::Network {
-                    message: "HTTP request failed".to_string(),
-                    source: Some(Box::new(e)),
-                }
-            }
-        })?;
-
-        // Check status
+        let client = options.http_client()?;
+        let validated_etag = validated.and_then(|info| info.etag.as_deref());
+        let response = request_with_retry(&client, url, offset, validated_etag, &options)?;
         let status = response.status();
-        if !status.is_success() && status.as_u16() != 206 {
-            return Err(Error::Network {
-                message: format!(
-                    "HTTP error {}: {}",
-                    status.as_u16(),
-                    status.canonical_reason().unwrap_or("Unknown")
-                ),
-                source: None,
-            });
-        }
 
         // Extract headers
         let content_length = response.content_length();
@@ -579,6 +1720,7 @@ impl HttpSource {
             .and_then(|v| v.to_str().ok())
             .map(|v| v == "bytes")
             .unwrap_or(false);
+        let server_digest = server_digest(response.headers(), status.as_u16() == 206);
 
         // Calculate total size (accounting for resume)
         let total_size = if offset > 0 && status.as_u16() == 206 {
@@ -587,6 +1729,31 @@ impl HttpSource {
             content_length
         };
 
+        // Resuming onto a different version of the file would corrupt the image
+        if let (Some(validated), Some(etag)) = (validated_etag, etag.as_deref()) {
+            if validated != etag && offset > 0 {
+                return Err(Error::Network {
+                    message: format!(
+                        "{} changed since it was checked (ETag {} is now {})",
+                        url, validated, etag
+                    ),
+                    source: None,
+                });
+            }
+        }
+
+        // Fill in what the GET left out from the validation's HEAD
+        let (total_size, accept_ranges, content_type, etag, server_digest) = match validated {
+            Some(info) => (
+                total_size.or(info.size),
+                accept_ranges || info.resumable,
+                content_type.or_else(|| info.content_type.clone()),
+                etag.or_else(|| info.etag.clone()),
+                server_digest.or_else(|| info.server_digest.clone()),
+            ),
+            None => (total_size, accept_ranges, content_type, etag, server_digest),
+        };
+
         let info = SourceInfo {
             path: url.to_string(),
             source_type: SourceType::Remote,
@@ -596,38 +1763,295 @@ impl HttpSource {
             resumable: accept_ranges,
             content_type,
             etag,
+            server_digest,
         };
 
         Ok(Self {
             response,
             info,
             bytes_read: offset,
+            client,
+            options,
+            resumed: offset > 0 && status.as_u16() == 206,
         })
     }
 
-    /// Get source info
-    pub fn info(&self) -> &SourceInfo {
-        &self.info
+    /// Get source info
+    pub fn info(&self) -> &SourceInfo {
+        &self.info
+    }
+
+    /// Get bytes read so far
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// Check if this source supports resume
+    pub fn supports_resume(&self) -> bool {
+        self.info.resumable
+    }
+
+    /// Whether the server answered the resume offset with a partial response
+    ///
+    /// `false` for a non-zero offset means the server ignored the `Range`
+    /// header and the stream starts at byte 0.
+    pub fn resumed(&self) -> bool {
+        self.resumed
+    }
+
+    /// Open the same URL again from the start, with the same HTTP options
+    pub fn reopen(&self) -> Result<Self> {
+        Self::open_with_options(&self.info.path, 0, self.options.clone())
+    }
+
+    /// Re-issue the request from the current offset after a dropped stream
+    ///
+    /// On failure, returns the error and whether it is worth retrying.
+    fn reconnect(&mut self) -> std::result::Result<(), (Error, bool)> {
+        let response = send_request(
+            &self.client,
+            &self.info.path,
+            self.bytes_read,
+            self.info.etag.as_deref(),
+            &self.options,
+        )?;
+
+        // A server that ignores the Range header would restart from byte 0
+        if self.bytes_read > 0 && response.status().as_u16() != 206 {
+            let error = Error::Network {
+                message: "Server did not honour range request while resuming".to_string(),
+                source: None,
+            };
+            return Err((error, false));
+        }
+
+        self.response = response;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "remote")]
+impl Read for HttpSource {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut attempt = 0;
+        loop {
+            match self.response.read(buf) {
+                Ok(n) => {
+                    self.bytes_read += n as u64;
+                    return Ok(n);
+                }
+                Err(e) if self.info.resumable && attempt < self.options.max_retries => {
+                    let delay = self.options.backoff_delay(attempt);
+                    attempt += 1;
+                    tracing::warn!(
+                        "HTTP stream interrupted at byte {} ({}), retrying in {:?} (attempt {}/{})",
+                        self.bytes_read,
+                        e,
+                        delay,
+                        attempt,
+                        self.options.max_retries
+                    );
+                    std::thread::sleep(delay);
+
+                    // A failed reconnect leaves the broken response in place,
+                    // so the next read fails again and uses up another attempt
+                    match self.reconnect() {
+                        Ok(()) => {}
+                        Err((error, true)) => tracing::debug!("Reconnect failed: {}", error),
+                        Err((error, false)) => return Err(std::io::Error::other(error)),
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Send a GET request (ranged if `offset > 0`), retrying transient failures
+#[cfg(feature = "remote")]
+fn request_with_retry(
+    client: &HttpClient,
+    url: &str,
+    offset: u64,
+    etag: Option<&str>,
+    options: &HttpOptions,
+) -> Result<reqwest::blocking::Response> {
+    let mut attempt = 0;
+    loop {
+        match send_request(client, url, offset, etag, options) {
+            Ok(response) => return Ok(response),
+            Err((error, true)) if attempt < options.max_retries => {
+                let delay = options.backoff_delay(attempt);
+                attempt += 1;
+                tracing::warn!(
+                    "{}, retrying in {:?} (attempt {}/{})",
+                    error,
+                    delay,
+                    attempt,
+                    options.max_retries
+                );
+                std::thread::sleep(delay);
+            }
+            Err((error, _)) => return Err(error),
+        }
+    }
+}
+
+/// Send a single GET request, returning the error and whether it is retryable
+///
+/// A ranged request with a strong `etag` sends it as `If-Range`, so a server
+/// whose copy has changed answers with the whole new file instead of a
+/// partial response.
+#[cfg(feature = "remote")]
+fn send_request(
+    client: &HttpClient,
+    url: &str,
+    offset: u64,
+    etag: Option<&str>,
+    options: &HttpOptions,
+) -> std::result::Result<reqwest::blocking::Response, (Error, bool)> {
+    let mut request = client.get(url, options.timeout);
+
+    // Add Range header for resume
+    if offset > 0 {
+        request = request.header("Range", format!("bytes={}-", offset));
+        // Weak ETags are not allowed in If-Range
+        if let Some(etag) = etag.filter(|e| !e.starts_with("W/")) {
+            request = request.header("If-Range", etag);
+        }
+    }
+
+    // Send request
+    let response = request.send().map_err(|e| {
+        if e.is_timeout() {
+            let message = format!(
+                "HTTP request timed out after {} seconds",
+                options.timeout.as_secs()
+            );
+            let error = Error::Network {
+                message,
+                source: Some(Box::new(e)),
+            };
+            (error, true)
+        } else if e.is_connect() {
+            let error = Error::Network {
+                message: "Failed to connect to server".to_string(),
+                source: Some(Box::new(e)),
+            };
+            (error, true)
+        } else {
+            let error = Error::Network {
+                message: "HTTP request failed".to_string(),
+                source: Some(Box::new(e)),
+            };
+            (error, false)
+        }
+    })?;
+
+    // Check status
+    let status = response.status();
+    if !status.is_success() && status.as_u16() != 206 {
+        let error = Error::Network {
+            message: format!(
+                "HTTP error {}: {}",
+                status.as_u16(),
+                status.canonical_reason().unwrap_or("Unknown")
+            ),
+            source: None,
+        };
+        return Err((error, status.is_server_error()));
     }
 
-    /// Get bytes read so far
-    pub fn bytes_read(&self) -> u64 {
-        self.bytes_read
-    }
+    Ok(response)
+}
 
-    /// Check if this source supports resume
-    pub fn supports_resume(&self) -> bool {
-        self.info.resumable
-    }
+/// The digest a server sent for the whole file, as `(algorithm, hex)`
+///
+/// `Repr-Digest` (RFC 9530) is preferred, taking its strongest supported
+/// algorithm. `Content-MD5` describes only the response body, so it is
+/// ignored on a `206 Partial Content` answer.
+#[cfg(feature = "remote")]
+fn server_digest(
+    headers: &reqwest::header::HeaderMap,
+    partial: bool,
+) -> Option<(crate::verifier::ChecksumAlgorithm, String)> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    header("repr-digest")
+        .and_then(parse_repr_digest)
+        .or_else(|| {
+            let md5 = header("content-md5").filter(|_| !partial)?;
+            let bytes = decode_base64(md5.trim()).filter(|b| b.len() == 16)?;
+            Some((
+                crate::verifier::ChecksumAlgorithm::Md5,
+                crate::verifier::bytes_to_hex(&bytes),
+            ))
+        })
 }
 
+/// Strongest supported digest in a `Repr-Digest` value like `sha-256=:base64:`
+///
+/// Unknown algorithms and malformed entries are skipped.
 #[cfg(feature = "remote")]
-impl Read for HttpSource {
-    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        let n = self.response.read(buf)?;
-        self.bytes_read += n as u64;
-        Ok(n)
+fn parse_repr_digest(value: &str) -> Option<(crate::verifier::ChecksumAlgorithm, String)> {
+    use crate::verifier::ChecksumAlgorithm;
+
+    value
+        .split(',')
+        .filter_map(|entry| {
+            let (name, encoded) = entry.trim().split_once('=')?;
+            let (algorithm, length) = match name.trim().to_ascii_lowercase().as_str() {
+                "sha-512" => (ChecksumAlgorithm::Sha512, 64),
+                "sha-256" => (ChecksumAlgorithm::Sha256, 32),
+                "md5" => (ChecksumAlgorithm::Md5, 16),
+                _ => return None,
+            };
+            let encoded = encoded.trim().strip_prefix(':')?.strip_suffix(':')?;
+            let bytes = decode_base64(encoded).filter(|b| b.len() == length)?;
+            Some((algorithm, crate::verifier::bytes_to_hex(&bytes)))
+        })
+        .max_by_key(|(algorithm, _)| match algorithm {
+            ChecksumAlgorithm::Sha512 => 2,
+            ChecksumAlgorithm::Sha256 => 1,
+            _ => 0,
+        })
+}
+
+/// Decode standard, padded base64, as used in digest headers
+#[cfg(feature = "remote")]
+#[allow(clippy::manual_is_multiple_of)]
+fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    let input = input.as_bytes();
+    if input.len() % 4 != 0 {
+        return None;
+    }
+
+    let value = |c: u8| match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    };
+
+    let mut out = Vec::with_capacity(input.len() / 4 * 3);
+    for (i, chunk) in input.chunks(4).enumerate() {
+        let last = i == input.len() / 4 - 1;
+        let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 || (padding > 0 && !last) {
+            return None;
+        }
+
+        let mut group = 0u32;
+        for &c in &chunk[..4 - padding] {
+            group = (group << 6) | u32::from(value(c)?);
+        }
+        group <<= 6 * padding;
+
+        let bytes = group.to_be_bytes();
+        out.extend_from_slice(&bytes[1..4 - padding]);
     }
+    Some(out)
 }
 
 // ============================================================================
@@ -641,7 +2065,8 @@ impl Read for HttpSource {
 ///
 /// ## S3-Compatible Services
 ///
-/// For S3-compatible services, set the `AWS_ENDPOINT_URL` environment variable:
+/// For S3-compatible services, set the `ENGRAVER_S3_ENDPOINT` (or
+/// `AWS_ENDPOINT_URL`) environment variable:
 /// - DigitalOcean Spaces: `https://nyc3.digitaloceanspaces.com`
 /// - MinIO: `http://localhost:9000`
 /// - Backblaze B2: `https://s3.us-west-000.backblazeb2.com`
@@ -701,8 +2126,7 @@ impl CloudSource {
     ) -> Result<Self> {
         let chunk_size = settings
             .map(|s| {
-                crate::benchmark::parse_size(&s.cloud_chunk_size)
-                    .unwrap_or(DEFAULT_CLOUD_CHUNK_SIZE)
+                crate::size::parse_size(&s.cloud_chunk_size).unwrap_or(DEFAULT_CLOUD_CHUNK_SIZE)
             })
             .unwrap_or(DEFAULT_CLOUD_CHUNK_SIZE);
 
@@ -742,6 +2166,7 @@ impl CloudSource {
             resumable: true, // Cloud storage supports Range headers
             content_type: None,
             etag,
+            server_digest: None,
         };
 
         Ok(Self {
@@ -769,8 +2194,8 @@ impl CloudSource {
         #[cfg(feature = "s3")]
         if uri.starts_with("s3://") {
             let (bucket, key) = parse_s3_uri(uri)?;
-            let store = object_store::aws::AmazonS3Builder::from_env()
-                .with_bucket_name(&bucket)
+            let endpoint = std::env::var(S3_ENDPOINT_ENV).ok();
+            let store = s3_builder(&bucket, endpoint.as_deref())
                 .build()
                 .map_err(|e| Error::Network {
                     message: "Failed to create S3 client".to_string(),
@@ -861,6 +2286,13 @@ impl CloudSource {
     pub fn supports_resume(&self) -> bool {
         true
     }
+
+    /// Open the same object again from the start, with the same chunk size
+    pub fn reopen(&self) -> Result<Self> {
+        let mut source = Self::open(&self.info.path)?;
+        source.chunk_size = self.chunk_size;
+        Ok(source)
+    }
 }
 
 #[cfg(any(feature = "s3", feature = "gcs", feature = "azure"))]
@@ -893,6 +2325,15 @@ pub enum Source {
     /// Local uncompressed file
     Local(LocalFileSource),
 
+    /// In-memory buffer
+    Memory(MemorySource),
+
+    /// Block device, read-only
+    Device(DeviceSource),
+
+    /// Numbered parts of a split image
+    Split(SplitSource),
+
     /// Gzip compressed local file
     #[cfg(feature = "compression")]
     Gzip(GzipSource<BufReader<File>>),
@@ -909,6 +2350,10 @@ pub enum Source {
     #[cfg(feature = "compression")]
     Bzip2(Bzip2Source<BufReader<File>>),
 
+    /// Single entry extracted from a local tar or tar.gz archive
+    #[cfg(feature = "compression")]
+    Tar(TarSource<Box<dyn Read + Send>>),
+
     /// HTTP/HTTPS remote source
     #[cfg(feature = "remote")]
     Http(HttpSource),
@@ -970,15 +2415,75 @@ impl Source {
         Self::open_with_offset(path, 0)
     }
 
+    /// Open a local zstd image with custom decoder settings
+    ///
+    /// [`open`](Self::open) uses [`ZstdOptions::default`]; this is for
+    /// callers that want to limit the decoder's window.
+    #[cfg(feature = "compression")]
+    pub fn open_zstd_with_options(path: &str, options: ZstdOptions) -> Result<Self> {
+        let file = open_file_buffered(path)?;
+        let compressed_size = file.get_ref().metadata()?.len();
+        let info = SourceInfo::compressed(path, compressed_size, SourceType::Zstd);
+        Ok(Source::Zstd(Box::new(ZstdSource::with_options(
+            file, info, options,
+        )?)))
+    }
+
+    /// Create a source from bytes already held in memory
+    ///
+    /// The source reports itself as a seekable local file of `data.len()` bytes,
+    /// so it can be passed to [`Writer`](crate::Writer) without a temp file.
+    pub fn from_bytes(data: Vec<u8>, name: &str) -> Self {
+        Source::Memory(MemorySource::new(data, name))
+    }
+
     /// Open a source from a path or URL, seeking to the specified offset
     ///
     /// This is useful for resuming interrupted writes. For local files, this
     /// seeks to the offset. For HTTP sources, this uses Range headers.
     /// Compressed sources cannot be resumed (returns error if offset > 0).
     pub fn open_with_offset(path: &str, offset: u64) -> Result<Self> {
+        Self::open_with_entry(path, offset, None)
+    }
+
+    /// Open a source described by [`validate_source`], from `offset`
+    ///
+    /// For HTTP(S) URLs the validated size, resumability and ETag are reused
+    /// rather than re-learned from the GET (see
+    /// [`HttpSource::open_from_info`]). Every other source is opened from
+    /// `info.path` as [`open_with_offset`](Self::open_with_offset) would;
+    /// tar archives need [`open_with_entry`](Self::open_with_entry) to pick
+    /// an entry.
+    pub fn open_from_info(info: &SourceInfo, offset: u64) -> Result<Self> {
+        match info.source_type {
+            #[cfg(feature = "remote")]
+            SourceType::Remote => Ok(Source::Http(HttpSource::open_from_info(
+                info,
+                offset,
+                HttpOptions::default(),
+            )?)),
+            _ => Self::open_with_offset(&info.path, offset),
+        }
+    }
+
+    /// Open a source, selecting which tar entry to extract
+    ///
+    /// `entry` is a glob matched against entry paths in `.tar`/`.tar.gz`
+    /// archives; it is ignored for every other source type. Without it, the
+    /// archive must contain exactly one regular file.
+    #[allow(unused_variables)] // entry only used with compression feature
+    pub fn open_with_entry(path: &str, offset: u64, entry: Option<&str>) -> Result<Self> {
         let source_type = detect_source_type(path);
 
         match source_type {
+            SourceType::LocalFile if is_block_device(path) => {
+                let mut source = DeviceSource::open(path)?;
+                if offset > 0 {
+                    source.seek(SeekFrom::Start(offset))?;
+                }
+                Ok(Source::Device(source))
+            }
+
             SourceType::LocalFile => {
                 let mut source = LocalFileSource::open(path)?;
                 if offset > 0 {
@@ -987,6 +2492,14 @@ impl Source {
                 Ok(Source::Local(source))
             }
 
+            SourceType::Split => {
+                let mut source = SplitSource::open(path)?;
+                if offset > 0 {
+                    source.seek(SeekFrom::Start(offset))?;
+                }
+                Ok(Source::Split(source))
+            }
+
             #[cfg(feature = "compression")]
             SourceType::Gzip => {
                 if offset > 0 {
@@ -1039,6 +2552,20 @@ impl Source {
                 Ok(Source::Bzip2(Bzip2Source::new(file, info)))
             }
 
+            #[cfg(feature = "compression")]
+            SourceType::Tar | SourceType::TarGz => {
+                if offset > 0 {
+                    return Err(Error::InvalidConfig(
+                        "Cannot resume from tar archive source".to_string(),
+                    ));
+                }
+                let tar_entry = find_tar_entry(path, source_type, entry)?;
+                let archive_size = std::fs::metadata(path)?.len();
+                let info = SourceInfo::archive(path, archive_size, source_type, tar_entry.size);
+                let stream = open_tar_stream(path, source_type)?;
+                Ok(Source::Tar(TarSource::new(stream, &tar_entry, info)?))
+            }
+
             #[cfg(feature = "remote")]
             SourceType::Remote => {
                 let http_source = HttpSource::open_with_resume(path, offset)?;
@@ -1064,7 +2591,12 @@ impl Source {
             }
 
             #[cfg(not(feature = "compression"))]
-            SourceType::Gzip | SourceType::Xz | SourceType::Zstd | SourceType::Bzip2 => {
+            SourceType::Gzip
+            | SourceType::Xz
+            | SourceType::Zstd
+            | SourceType::Bzip2
+            | SourceType::Tar
+            | SourceType::TarGz => {
                 if offset > 0 {
                     return Err(Error::InvalidConfig(
                         "Cannot resume from compressed source".to_string(),
@@ -1087,6 +2619,9 @@ impl Source {
     pub fn info(&self) -> &SourceInfo {
         match self {
             Source::Local(s) => s.info(),
+            Source::Memory(s) => s.info(),
+            Source::Device(s) => s.info(),
+            Source::Split(s) => s.info(),
             #[cfg(feature = "compression")]
             Source::Gzip(s) => s.info(),
             #[cfg(feature = "compression")]
@@ -1095,6 +2630,8 @@ impl Source {
             Source::Zstd(s) => s.info(),
             #[cfg(feature = "compression")]
             Source::Bzip2(s) => s.info(),
+            #[cfg(feature = "compression")]
+            Source::Tar(s) => s.info(),
             #[cfg(feature = "remote")]
             Source::Http(s) => s.info(),
             #[cfg(all(feature = "remote", feature = "compression"))]
@@ -1130,6 +2667,89 @@ impl Source {
         }
     }
 
+    /// Open an equivalent source, positioned at the start
+    ///
+    /// Local files and archives are opened again from their path (with the
+    /// same tar entry), remote sources are requested again with the options
+    /// they were opened with, and in-memory sources are copied. Useful for a
+    /// verification pass after the original source has been consumed.
+    pub fn reopen(&self) -> Result<Self> {
+        match self {
+            Source::Local(s) => Ok(Source::Local(LocalFileSource::open(&s.info().path)?)),
+            Source::Device(s) => Ok(Source::Device(DeviceSource::open(&s.info().path)?)),
+            Source::Memory(s) => Ok(Source::Memory(MemorySource::new(
+                s.data.get_ref().clone(),
+                &s.info().path,
+            ))),
+            Source::Split(s) => Ok(Source::Split(SplitSource::from_parts(
+                &s.info().path,
+                s.part_paths().map(Path::to_path_buf).collect(),
+            )?)),
+            #[cfg(feature = "compression")]
+            Source::Gzip(_) | Source::Xz(_) | Source::Zstd(_) | Source::Bzip2(_) => {
+                Self::open(&self.info().path)
+            }
+            #[cfg(feature = "compression")]
+            Source::Tar(s) => Self::open_with_entry(&s.info().path, 0, Some(s.entry_path())),
+            #[cfg(feature = "remote")]
+            Source::Http(s) => Ok(Source::Http(s.reopen()?)),
+            #[cfg(all(feature = "remote", feature = "compression"))]
+            Source::HttpGzip(s) => Ok(Source::HttpGzip(GzipSource::new(
+                s.inner().reopen()?,
+                s.info().clone(),
+            ))),
+            #[cfg(all(feature = "remote", feature = "compression"))]
+            Source::HttpXz(s) => Ok(Source::HttpXz(XzSource::new(
+                s.inner().reopen()?,
+                s.info().clone(),
+            ))),
+            #[cfg(all(feature = "remote", feature = "compression"))]
+            Source::HttpZstd(s) => Ok(Source::HttpZstd(Box::new(ZstdSource::new(
+                s.inner().reopen()?,
+                s.info().clone(),
+            )?))),
+            #[cfg(all(feature = "remote", feature = "compression"))]
+            Source::HttpBzip2(s) => Ok(Source::HttpBzip2(Bzip2Source::new(
+                s.inner().reopen()?,
+                s.info().clone(),
+            ))),
+            #[cfg(any(feature = "s3", feature = "gcs", feature = "azure"))]
+            Source::Cloud(s) => Ok(Source::Cloud(s.reopen()?)),
+            #[cfg(all(
+                any(feature = "s3", feature = "gcs", feature = "azure"),
+                feature = "compression"
+            ))]
+            Source::CloudGzip(s) => Ok(Source::CloudGzip(GzipSource::new(
+                s.inner().reopen()?,
+                s.info().clone(),
+            ))),
+            #[cfg(all(
+                any(feature = "s3", feature = "gcs", feature = "azure"),
+                feature = "compression"
+            ))]
+            Source::CloudXz(s) => Ok(Source::CloudXz(XzSource::new(
+                s.inner().reopen()?,
+                s.info().clone(),
+            ))),
+            #[cfg(all(
+                any(feature = "s3", feature = "gcs", feature = "azure"),
+                feature = "compression"
+            ))]
+            Source::CloudZstd(s) => Ok(Source::CloudZstd(Box::new(ZstdSource::new(
+                s.inner().reopen()?,
+                s.info().clone(),
+            )?))),
+            #[cfg(all(
+                any(feature = "s3", feature = "gcs", feature = "azure"),
+                feature = "compression"
+            ))]
+            Source::CloudBzip2(s) => Ok(Source::CloudBzip2(Bzip2Source::new(
+                s.inner().reopen()?,
+                s.info().clone(),
+            ))),
+        }
+    }
+
     /// Get the known size (uncompressed if available, otherwise compressed)
     pub fn size(&self) -> Option<u64> {
         let info = self.info();
@@ -1145,12 +2765,62 @@ impl Source {
     pub fn is_compressed(&self) -> bool {
         self.info().source_type.is_compressed()
     }
+
+    /// Count of compressed bytes consumed, for decompressing sources
+    ///
+    /// Compared against [`SourceInfo::compressed_size`] this measures
+    /// progress through a stream whose decompressed size is unknown. `None`
+    /// for sources that are not decompressed here, including tar archives.
+    pub fn compressed_counter(&self) -> Option<ByteCounter> {
+        match self {
+            #[cfg(feature = "compression")]
+            Source::Gzip(s) => Some(s.compressed_counter()),
+            #[cfg(feature = "compression")]
+            Source::Xz(s) => Some(s.compressed_counter()),
+            #[cfg(feature = "compression")]
+            Source::Zstd(s) => Some(s.compressed_counter()),
+            #[cfg(feature = "compression")]
+            Source::Bzip2(s) => Some(s.compressed_counter()),
+            #[cfg(all(feature = "remote", feature = "compression"))]
+            Source::HttpGzip(s) => Some(s.compressed_counter()),
+            #[cfg(all(feature = "remote", feature = "compression"))]
+            Source::HttpXz(s) => Some(s.compressed_counter()),
+            #[cfg(all(feature = "remote", feature = "compression"))]
+            Source::HttpZstd(s) => Some(s.compressed_counter()),
+            #[cfg(all(feature = "remote", feature = "compression"))]
+            Source::HttpBzip2(s) => Some(s.compressed_counter()),
+            #[cfg(all(
+                any(feature = "s3", feature = "gcs", feature = "azure"),
+                feature = "compression"
+            ))]
+            Source::CloudGzip(s) => Some(s.compressed_counter()),
+            #[cfg(all(
+                any(feature = "s3", feature = "gcs", feature = "azure"),
+                feature = "compression"
+            ))]
+            Source::CloudXz(s) => Some(s.compressed_counter()),
+            #[cfg(all(
+                any(feature = "s3", feature = "gcs", feature = "azure"),
+                feature = "compression"
+            ))]
+            Source::CloudZstd(s) => Some(s.compressed_counter()),
+            #[cfg(all(
+                any(feature = "s3", feature = "gcs", feature = "azure"),
+                feature = "compression"
+            ))]
+            Source::CloudBzip2(s) => Some(s.compressed_counter()),
+            _ => None,
+        }
+    }
 }
 
 impl Read for Source {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         match self {
             Source::Local(s) => s.read(buf),
+            Source::Memory(s) => s.read(buf),
+            Source::Device(s) => s.read(buf),
+            Source::Split(s) => s.read(buf),
             #[cfg(feature = "compression")]
             Source::Gzip(s) => s.read(buf),
             #[cfg(feature = "compression")]
@@ -1159,6 +2829,8 @@ impl Read for Source {
             Source::Zstd(s) => s.read(buf),
             #[cfg(feature = "compression")]
             Source::Bzip2(s) => s.read(buf),
+            #[cfg(feature = "compression")]
+            Source::Tar(s) => s.read(buf),
             #[cfg(feature = "remote")]
             Source::Http(s) => s.read(buf),
             #[cfg(all(feature = "remote", feature = "compression"))]
@@ -1214,6 +2886,25 @@ fn parse_s3_uri(uri: &str) -> Result<(String, String)> {
     Ok((parts[0].to_string(), parts[1].to_string()))
 }
 
+/// S3 client builder for `bucket`, using `endpoint` instead of AWS if given
+///
+/// A custom endpoint gets path-style requests (`endpoint/bucket/key`), which
+/// MinIO and most S3-compatible services expect, and may use plain `http://`.
+#[cfg(feature = "s3")]
+fn s3_builder(bucket: &str, endpoint: Option<&str>) -> object_store::aws::AmazonS3Builder {
+    let builder = object_store::aws::AmazonS3Builder::from_env().with_bucket_name(bucket);
+    match endpoint
+        .map(str::trim)
+        .filter(|endpoint| !endpoint.is_empty())
+    {
+        Some(endpoint) => builder
+            .with_endpoint(endpoint)
+            .with_virtual_hosted_style_request(false)
+            .with_allow_http(endpoint.starts_with("http://")),
+        None => builder,
+    }
+}
+
 /// Parse GCS URI (gs://bucket/object) into (bucket, object)
 #[cfg(feature = "gcs")]
 fn parse_gcs_uri(uri: &str) -> Result<(String, String)> {
@@ -1273,22 +2964,32 @@ pub fn get_source_size(path: &str) -> Result<Option<u64>> {
     let source_type = detect_source_type(path);
 
     match source_type {
+        SourceType::LocalFile if is_block_device(path) => Ok(DeviceSource::open(path)?.info().size),
         SourceType::LocalFile => {
             let metadata =
                 std::fs::metadata(path).map_err(|_| Error::SourceNotFound(path.to_string()))?;
             Ok(Some(metadata.len()))
         }
+        SourceType::Split => Ok(SplitSource::open(path)?.info().size),
         SourceType::Remote => {
             #[cfg(feature = "remote")]
             {
                 // Do a HEAD request to get size
-                let client = reqwest::blocking::Client::new();
-                let response = client.head(path).send().map_err(|e| Error::Network {
-                    message: "HEAD request failed".to_string(),
-                    source: Some(Box::new(e)),
-                })?;
+                let timeout = std::time::Duration::from_secs(DEFAULT_VALIDATION_TIMEOUT_SECS);
+                let response = HttpClient::shared()?
+                    .head(path, timeout)
+                    .send()
+                    .map_err(|e| Error::Network {
+                        message: "HEAD request failed".to_string(),
+                        source: Some(Box::new(e)),
+                    })?;
 
-                Ok(response.content_length())
+                // `content_length()` is the body size, which is always 0 for HEAD
+                Ok(response
+                    .headers()
+                    .get("content-length")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse().ok()))
             }
             #[cfg(not(feature = "remote"))]
             {
@@ -1316,19 +3017,36 @@ pub fn validate_source(path: &str) -> Result<SourceInfo> {
 /// Validate a source path or URL with custom network settings
 ///
 /// If `settings` is `None`, default timeout values are used.
-#[allow(unused_variables)] // settings only used with remote feature
 pub fn validate_source_with_settings(
     path: &str,
     settings: Option<&NetworkSettings>,
+) -> Result<SourceInfo> {
+    validate_source_with_entry(path, settings, None)
+}
+
+/// Validate a source, selecting which tar entry to extract
+///
+/// For `.tar`/`.tar.gz` archives the archive is scanned and the returned
+/// info carries the selected entry's size. See [`Source::open_with_entry`].
+#[allow(unused_variables)] // settings only used with remote feature
+pub fn validate_source_with_entry(
+    path: &str,
+    settings: Option<&NetworkSettings>,
+    entry: Option<&str>,
 ) -> Result<SourceInfo> {
     let source_type = detect_source_type(path);
 
     match source_type {
+        SourceType::LocalFile if is_block_device(path) => {
+            Ok(DeviceSource::open(path)?.info().clone())
+        }
         SourceType::LocalFile
         | SourceType::Gzip
         | SourceType::Xz
         | SourceType::Zstd
-        | SourceType::Bzip2 => {
+        | SourceType::Bzip2
+        | SourceType::Tar
+        | SourceType::TarGz => {
             let file_path = Path::new(path);
             if !file_path.exists() {
                 return Err(Error::SourceNotFound(path.to_string()));
@@ -1340,89 +3058,28 @@ pub fn validate_source_with_settings(
             }
 
             let size = metadata.len();
-            if source_type.is_compressed() {
+            #[cfg(feature = "compression")]
+            if source_type.is_archive() {
+                let tar_entry = find_tar_entry(path, source_type, entry)?;
+                return Ok(SourceInfo::archive(path, size, source_type, tar_entry.size));
+            }
+
+            if source_type.is_compressed() || source_type.is_archive() {
                 Ok(SourceInfo::compressed(path, size, source_type))
             } else {
                 Ok(SourceInfo::local(path, size))
             }
         }
+        SourceType::Split => Ok(SplitSource::open(path)?.info().clone()),
         SourceType::Remote => {
             #[cfg(feature = "remote")]
             {
                 let timeout_secs = settings
                     .map(|s| s.validation_timeout_secs)
                     .unwrap_or(DEFAULT_VALIDATION_TIMEOUT_SECS);
-
-                // Validate URL format
-                url::Url::parse(path).map_err(|e| Error::Network {
-                    message: "Invalid URL".to_string(),
-                    source: Some(Box::new(e)),
-                })?;
-
-                // Do a HEAD request to check availability
-                let client = reqwest::blocking::Client::builder()
-                    .timeout(std::time::Duration::from_secs(timeout_secs))
-                    .build()
-                    .map_err(|e| Error::Network {
-                        message: "Failed to create client".to_string(),
-                        source: Some(Box::new(e)),
-                    })?;
-
-                let response = client.head(path).send().map_err(|e| {
-                    if e.is_timeout() {
-                        Error::Network {
-                            message: format!(
-                                "URL validation timed out after {} seconds",
-                                timeout_secs
-                            ),
-                            source: Some(Box::new(e)),
-                        }
-                    } else if e.is_connect() {
-                        Error::Network {
-                            message: "Failed to connect to URL".to_string(),
-                            source: Some(Box::new(e)),
-                        }
-                    } else {
-                        Error::Network {
-                            message: "Failed to reach URL".to_string(),
-                            source: Some(Box::new(e)),
-                        }
-                    }
-                })?;
-
-                if !response.status().is_success() {
-                    return Err(Error::Network {
-                        message: format!("URL returned status {}", response.status()),
-                        source: None,
-                    });
-                }
-
-                let size = response.content_length();
-                let resumable = response
-                    .headers()
-                    .get("accept-ranges")
-                    .and_then(|v| v.to_str().ok())
-                    .map(|v| v == "bytes")
-                    .unwrap_or(false);
-
-                Ok(SourceInfo {
-                    path: path.to_string(),
-                    source_type: SourceType::Remote,
-                    compressed_size: size,
-                    size,
-                    seekable: false,
-                    resumable,
-                    content_type: response
-                        .headers()
-                        .get("content-type")
-                        .and_then(|v| v.to_str().ok())
-                        .map(String::from),
-                    etag: response
-                        .headers()
-                        .get("etag")
-                        .and_then(|v| v.to_str().ok())
-                        .map(String::from),
-                })
+                let options =
+                    HttpOptions::new().timeout(std::time::Duration::from_secs(timeout_secs));
+                validate_http_source(path, &options)
             }
             #[cfg(not(feature = "remote"))]
             {
@@ -1449,6 +3106,85 @@ pub fn validate_source_with_settings(
     }
 }
 
+/// Validate an HTTP(S) URL with a HEAD request sent with `options`
+///
+/// Only the client and timeout are used; a failed request is not retried.
+#[cfg(feature = "remote")]
+pub(crate) fn validate_http_source(path: &str, options: &HttpOptions) -> Result<SourceInfo> {
+    // Validate URL format
+    url::Url::parse(path).map_err(|e| Error::Network {
+        message: "Invalid URL".to_string(),
+        source: Some(Box::new(e)),
+    })?;
+
+    // Do a HEAD request to check availability
+    let response = options
+        .http_client()?
+        .head(path, options.timeout)
+        .send()
+        .map_err(|e| {
+            if e.is_timeout() {
+                Error::Network {
+                    message: format!(
+                        "URL validation timed out after {} seconds",
+                        options.timeout.as_secs()
+                    ),
+                    source: Some(Box::new(e)),
+                }
+            } else if e.is_connect() {
+                Error::Network {
+                    message: "Failed to connect to URL".to_string(),
+                    source: Some(Box::new(e)),
+                }
+            } else {
+                Error::Network {
+                    message: "Failed to reach URL".to_string(),
+                    source: Some(Box::new(e)),
+                }
+            }
+        })?;
+
+    if !response.status().is_success() {
+        return Err(Error::Network {
+            message: format!("URL returned status {}", response.status()),
+            source: None,
+        });
+    }
+
+    // `content_length()` is the body size, which is always 0 for HEAD
+    let size = response
+        .headers()
+        .get("content-length")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok());
+    let resumable = response
+        .headers()
+        .get("accept-ranges")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v == "bytes")
+        .unwrap_or(false);
+
+    Ok(SourceInfo {
+        path: path.to_string(),
+        source_type: SourceType::Remote,
+        compressed_size: size,
+        size,
+        seekable: false,
+        resumable,
+        content_type: response
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .map(String::from),
+        etag: response
+            .headers()
+            .get("etag")
+            .and_then(|v| v.to_str().ok())
+            .map(String::from),
+        server_digest: server_digest(response.headers(), false),
+    })
+}
+
 /// Validate a cloud source by checking if the object exists (via HEAD/metadata request)
 #[cfg(any(feature = "s3", feature = "gcs", feature = "azure"))]
 fn validate_cloud_source(path: &str, source_type: SourceType) -> Result<SourceInfo> {
@@ -1476,6 +3212,7 @@ fn validate_cloud_source(path: &str, source_type: SourceType) -> Result<SourceIn
         resumable: true,
         content_type: None,
         etag: meta.e_tag,
+        server_digest: None,
     })
 }
 
@@ -1486,6 +3223,8 @@ fn validate_cloud_source(path: &str, source_type: SourceType) -> Result<SourceIn
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "remote")]
+    use crate::verifier::ChecksumAlgorithm;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -1501,6 +3240,16 @@ mod tests {
         assert!(SourceType::Xz.is_compressed());
         assert!(SourceType::Zstd.is_compressed());
         assert!(SourceType::Bzip2.is_compressed());
+        assert!(!SourceType::Tar.is_compressed());
+        assert!(SourceType::TarGz.is_compressed());
+    }
+
+    #[test]
+    fn test_source_type_is_archive() {
+        assert!(SourceType::Tar.is_archive());
+        assert!(SourceType::TarGz.is_archive());
+        assert!(!SourceType::Gzip.is_archive());
+        assert!(!SourceType::LocalFile.is_archive());
     }
 
     #[test]
@@ -1518,6 +3267,8 @@ mod tests {
         assert_eq!(SourceType::Xz.extension(), Some(".xz"));
         assert_eq!(SourceType::Zstd.extension(), Some(".zst"));
         assert_eq!(SourceType::Bzip2.extension(), Some(".bz2"));
+        assert_eq!(SourceType::Tar.extension(), None);
+        assert_eq!(SourceType::TarGz.extension(), Some(".tar.gz"));
     }
 
     #[test]
@@ -1557,6 +3308,21 @@ mod tests {
         assert_eq!(detect_source_type("file"), SourceType::LocalFile);
     }
 
+    #[test]
+    fn test_extensions_and_schemes_match_detection() {
+        for source_type in crate::build_features().source_types() {
+            for extension in source_type.extensions() {
+                let path = format!("image{}", extension);
+                assert_eq!(detect_source_type(&path), source_type, "{}", path);
+            }
+            for scheme in source_type.url_schemes() {
+                let url = format!("{}://host/image.img", scheme);
+                assert_eq!(detect_source_type(&url), source_type, "{}", url);
+            }
+            assert!(!source_type.description().is_empty());
+        }
+    }
+
     #[test]
     fn test_detect_source_type_remote() {
         assert_eq!(
@@ -1575,28 +3341,102 @@ mod tests {
     }
 
     #[test]
-    fn test_detect_source_type_gzip() {
-        assert_eq!(detect_source_type("file.iso.gz"), SourceType::Gzip);
-        assert_eq!(detect_source_type("file.iso.gzip"), SourceType::Gzip);
-        assert_eq!(detect_source_type("FILE.ISO.GZ"), SourceType::Gzip);
+    fn test_detect_source_type_gzip() {
+        assert_eq!(detect_source_type("file.iso.gz"), SourceType::Gzip);
+        assert_eq!(detect_source_type("file.iso.gzip"), SourceType::Gzip);
+        assert_eq!(detect_source_type("FILE.ISO.GZ"), SourceType::Gzip);
+    }
+
+    #[test]
+    fn test_detect_source_type_xz() {
+        assert_eq!(detect_source_type("file.iso.xz"), SourceType::Xz);
+        assert_eq!(detect_source_type("FILE.ISO.XZ"), SourceType::Xz);
+    }
+
+    #[test]
+    fn test_detect_source_type_zstd() {
+        assert_eq!(detect_source_type("file.iso.zst"), SourceType::Zstd);
+        assert_eq!(detect_source_type("file.iso.zstd"), SourceType::Zstd);
+    }
+
+    #[test]
+    fn test_detect_source_type_bzip2() {
+        assert_eq!(detect_source_type("file.iso.bz2"), SourceType::Bzip2);
+        assert_eq!(detect_source_type("file.iso.bzip2"), SourceType::Bzip2);
+    }
+
+    #[test]
+    fn test_detect_source_type_tar() {
+        assert_eq!(detect_source_type("image.tar"), SourceType::Tar);
+        assert_eq!(detect_source_type("IMAGE.TAR"), SourceType::Tar);
+        assert_eq!(detect_source_type("image.tar.gz"), SourceType::TarGz);
+        assert_eq!(detect_source_type("image.tgz"), SourceType::TarGz);
+    }
+
+    // -------------------------------------------------------------------------
+    // Tar entry selection tests
+    // -------------------------------------------------------------------------
+
+    fn tar_entries(paths: &[&str]) -> Vec<TarEntry> {
+        paths
+            .iter()
+            .enumerate()
+            .map(|(i, p)| TarEntry {
+                path: p.to_string(),
+                size: 1024 * (i as u64 + 1),
+                data_offset: 512 * (i as u64 * 4 + 1),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_select_tar_entry_single() {
+        let entries = tar_entries(&["image.img"]);
+        let entry = select_tar_entry(&entries, None).unwrap();
+        assert_eq!(entry.path, "image.img");
+    }
+
+    #[test]
+    fn test_select_tar_entry_multiple_without_pattern() {
+        let entries = tar_entries(&["README.txt", "image.img"]);
+        let err = select_tar_entry(&entries, None).unwrap_err().to_string();
+        assert!(err.contains("--entry"));
+        assert!(err.contains("README.txt"));
+        assert!(err.contains("image.img"));
     }
 
     #[test]
-    fn test_detect_source_type_xz() {
-        assert_eq!(detect_source_type("file.iso.xz"), SourceType::Xz);
-        assert_eq!(detect_source_type("FILE.ISO.XZ"), SourceType::Xz);
+    fn test_select_tar_entry_by_pattern() {
+        let entries = tar_entries(&["README.txt", "out/image.img"]);
+        // Matches the file name as well as the full path
+        let entry = select_tar_entry(&entries, Some("*.img")).unwrap();
+        assert_eq!(entry.path, "out/image.img");
+        let entry = select_tar_entry(&entries, Some("out/*")).unwrap();
+        assert_eq!(entry.path, "out/image.img");
     }
 
     #[test]
-    fn test_detect_source_type_zstd() {
-        assert_eq!(detect_source_type("file.iso.zst"), SourceType::Zstd);
-        assert_eq!(detect_source_type("file.iso.zstd"), SourceType::Zstd);
+    fn test_select_tar_entry_no_match() {
+        let entries = tar_entries(&["README.txt"]);
+        let err = select_tar_entry(&entries, Some("*.img"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("No tar entry matches"));
+        assert!(err.contains("README.txt"));
     }
 
     #[test]
-    fn test_detect_source_type_bzip2() {
-        assert_eq!(detect_source_type("file.iso.bz2"), SourceType::Bzip2);
-        assert_eq!(detect_source_type("file.iso.bzip2"), SourceType::Bzip2);
+    fn test_select_tar_entry_ambiguous_pattern() {
+        let entries = tar_entries(&["a.img", "b.img"]);
+        let err = select_tar_entry(&entries, Some("*.img"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Multiple tar entries match"));
+    }
+
+    #[test]
+    fn test_select_tar_entry_empty_archive() {
+        assert!(select_tar_entry(&[], None).is_err());
     }
 
     #[cfg(feature = "s3")]
@@ -1669,6 +3509,42 @@ mod tests {
         assert!(parse_s3_uri("gs://bucket/key").is_err());
     }
 
+    #[cfg(feature = "s3")]
+    #[test]
+    fn test_s3_builder_endpoint_override() {
+        use object_store::aws::AmazonS3ConfigKey;
+
+        let builder = s3_builder("images", Some("http://localhost:9000"));
+        assert_eq!(
+            builder
+                .get_config_value(&AmazonS3ConfigKey::Endpoint)
+                .as_deref(),
+            Some("http://localhost:9000")
+        );
+        assert_eq!(
+            builder
+                .get_config_value(&AmazonS3ConfigKey::VirtualHostedStyleRequest)
+                .as_deref(),
+            Some("false")
+        );
+        assert_eq!(
+            builder
+                .get_config_value(&AmazonS3ConfigKey::Bucket)
+                .as_deref(),
+            Some("images")
+        );
+        assert!(builder.build().is_ok());
+
+        // A blank override leaves the environment's endpoint alone
+        let builder = s3_builder("images", Some("  "));
+        assert_eq!(
+            builder.get_config_value(&AmazonS3ConfigKey::Endpoint),
+            std::env::var("AWS_ENDPOINT_URL")
+                .or_else(|_| std::env::var("AWS_ENDPOINT"))
+                .ok()
+        );
+    }
+
     #[cfg(feature = "gcs")]
     #[test]
     fn test_parse_gcs_uri_valid() {
@@ -1748,6 +3624,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_looks_like_disk_image_iso() {
+        let mut data = vec![0u8; DISK_IMAGE_PROBE_SIZE];
+        data[0x8001..0x8006].copy_from_slice(b"CD001");
+        assert_eq!(looks_like_disk_image(&data), Some("ISO 9660"));
+    }
+
+    #[test]
+    fn test_looks_like_disk_image_partition_tables() {
+        let mut data = vec![0u8; 1024];
+        data[510] = 0x55;
+        data[511] = 0xAA;
+        assert_eq!(looks_like_disk_image(&data), Some("MBR"));
+
+        // Protective MBR plus GPT header
+        data[512..520].copy_from_slice(b"EFI PART");
+        assert_eq!(looks_like_disk_image(&data), Some("GPT"));
+    }
+
+    #[test]
+    fn test_looks_like_disk_image_fat() {
+        let mut fat16 = vec![0u8; 512];
+        fat16[510] = 0x55;
+        fat16[511] = 0xAA;
+        fat16[0x36..0x3E].copy_from_slice(b"FAT16   ");
+        assert_eq!(looks_like_disk_image(&fat16), Some("FAT boot sector"));
+
+        let mut fat32 = vec![0u8; 512];
+        fat32[510] = 0x55;
+        fat32[511] = 0xAA;
+        fat32[0x52..0x5A].copy_from_slice(b"FAT32   ");
+        assert_eq!(looks_like_disk_image(&fat32), Some("FAT boot sector"));
+    }
+
+    #[test]
+    fn test_looks_like_disk_image_rejects_text() {
+        let html = b"<!DOCTYPE html><html><body>404 Not Found</body></html>";
+        assert_eq!(looks_like_disk_image(html), None);
+        assert_eq!(looks_like_disk_image(&[]), None);
+        assert_eq!(
+            looks_like_disk_image(&vec![b'a'; DISK_IMAGE_PROBE_SIZE]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_boot_signature() {
+        let mut sector = vec![0u8; BOOT_SECTOR_SIZE];
+        assert_eq!(boot_signature(&sector), None);
+
+        sector[510] = 0x55;
+        sector[511] = 0xAA;
+        assert_eq!(boot_signature(&sector), Some("MBR"));
+
+        // Second partition entry marked as GPT protective
+        sector[0x1BE + 16 + 4] = 0xEE;
+        assert_eq!(boot_signature(&sector), Some("GPT protective MBR"));
+    }
+
+    #[test]
+    fn test_boot_signature_short_sector() {
+        assert_eq!(boot_signature(&[0x55, 0xAA]), None);
+        assert_eq!(boot_signature(&[]), None);
+    }
+
+    #[test]
+    fn test_image_kind_raw_images() {
+        assert_eq!(image_kind("core-image.wic"), Some("raw disk image (.wic)"));
+        assert_eq!(
+            image_kind("/tmp/board.SDCARD"),
+            Some("raw disk image (.sdcard)")
+        );
+        assert_eq!(image_kind("raspios.img"), Some("raw disk image (.img)"));
+        assert_eq!(image_kind("ubuntu.iso"), Some("ISO image (.iso)"));
+    }
+
+    #[test]
+    fn test_image_kind_ignores_compression_and_query() {
+        assert_eq!(
+            image_kind("core-image.wic.xz"),
+            Some("raw disk image (.wic)")
+        );
+        assert_eq!(
+            image_kind("https://example.com/board.sdcard.gz?token=abc"),
+            Some("raw disk image (.sdcard)")
+        );
+        assert_eq!(image_kind("image.tar.gz"), None);
+        assert_eq!(image_kind("image.xz"), None);
+        assert_eq!(image_kind("README"), None);
+    }
+
     #[test]
     fn test_detect_compression_from_magic_none() {
         let unknown = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
@@ -1863,12 +3830,211 @@ mod tests {
         assert_eq!(&buffer, data);
     }
 
+    #[test]
+    fn test_source_from_bytes() {
+        let mut source = Source::from_bytes(b"in-memory image".to_vec(), "memory.img");
+
+        let info = source.info();
+        assert_eq!(info.path, "memory.img");
+        assert_eq!(info.source_type, SourceType::LocalFile);
+        assert_eq!(info.size, Some(15));
+        assert!(info.seekable);
+        assert!(!source.is_compressed());
+        assert_eq!(source.size(), Some(15));
+
+        let mut buffer = Vec::new();
+        source.read_to_end(&mut buffer).unwrap();
+        assert_eq!(buffer, b"in-memory image");
+    }
+
+    #[test]
+    fn test_memory_source_seek() {
+        let mut source = MemorySource::new(b"0123456789".to_vec(), "seek.img");
+        source.seek(SeekFrom::Start(6)).unwrap();
+
+        let mut buffer = [0u8; 4];
+        source.read_exact(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"6789");
+    }
+
+    #[test]
+    fn test_source_from_bytes_empty() {
+        let source = Source::from_bytes(Vec::new(), "empty.img");
+        assert_eq!(source.size(), Some(0));
+    }
+
     #[test]
     fn test_source_not_found() {
         let result = Source::open("/nonexistent/file.iso");
         assert!(matches!(result, Err(Error::SourceNotFound(_))));
     }
 
+    // -------------------------------------------------------------------------
+    // Block device source tests (regular files stand in for devices)
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_is_block_device_false_for_regular_file() {
+        let temp = NamedTempFile::new().unwrap();
+        assert!(!is_block_device(temp.path().to_str().unwrap()));
+        assert!(!is_block_device("/nonexistent/device"));
+    }
+
+    #[test]
+    fn test_device_source_reports_size_and_reads() {
+        let mut temp = NamedTempFile::new().unwrap();
+        temp.write_all(b"device contents").unwrap();
+        temp.flush().unwrap();
+
+        let mut source = DeviceSource::open(temp.path().to_str().unwrap()).unwrap();
+        assert_eq!(source.info().size, Some(15));
+        assert_eq!(source.info().source_type, SourceType::LocalFile);
+        assert!(source.info().seekable);
+
+        source.seek(SeekFrom::Start(7)).unwrap();
+        let mut buffer = Vec::new();
+        source.read_to_end(&mut buffer).unwrap();
+        assert_eq!(buffer, b"contents");
+    }
+
+    #[test]
+    fn test_device_source_not_found() {
+        assert!(DeviceSource::open("/nonexistent/device").is_err());
+    }
+
+    // -------------------------------------------------------------------------
+    // Split image tests
+    // -------------------------------------------------------------------------
+
+    /// Write `data` into `dir` as `image.img.001`, `.002`, ... of `part_size` bytes
+    fn write_split_parts(dir: &Path, data: &[u8], part_size: usize) -> String {
+        for (i, chunk) in data.chunks(part_size).enumerate() {
+            std::fs::write(dir.join(format!("image.img.{:03}", i + 1)), chunk).unwrap();
+        }
+        dir.join("image.img.001").to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_detect_source_type_split() {
+        assert_eq!(detect_source_type("image.img.001"), SourceType::Split);
+        assert_eq!(detect_source_type("image.img.*"), SourceType::Split);
+        assert_eq!(detect_source_type("image.img.002"), SourceType::LocalFile);
+    }
+
+    #[test]
+    fn test_split_source_reconstructs_image() {
+        let dir = tempfile::tempdir().unwrap();
+        let data: Vec<u8> = (0..2500u32).map(|i| (i % 251) as u8).collect();
+        let first = write_split_parts(dir.path(), &data, 1500);
+
+        let mut source = Source::open(&first).unwrap();
+        assert_eq!(source.info().source_type, SourceType::Split);
+        assert_eq!(source.size(), Some(2500));
+        assert!(source.is_seekable());
+
+        let mut buffer = Vec::new();
+        source.read_to_end(&mut buffer).unwrap();
+        assert_eq!(buffer, data);
+    }
+
+    #[test]
+    fn test_split_source_from_glob() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = b"first part|second part".to_vec();
+        write_split_parts(dir.path(), &data, 11);
+        std::fs::write(dir.path().join("image.img.sha256"), "ignored").unwrap();
+
+        let glob = dir.path().join("image.img.*");
+        let mut source = Source::open(&glob.to_string_lossy()).unwrap();
+        let mut buffer = Vec::new();
+        source.read_to_end(&mut buffer).unwrap();
+        assert_eq!(buffer, data);
+    }
+
+    #[test]
+    fn test_split_source_seek_across_parts() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = write_split_parts(dir.path(), b"0123456789", 4);
+
+        let mut source = Source::open_with_offset(&first, 3).unwrap();
+        let mut buffer = [0u8; 6];
+        source.read_exact(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"345678");
+
+        let mut split = SplitSource::open(&first).unwrap();
+        assert_eq!(split.seek(SeekFrom::End(-2)).unwrap(), 8);
+        let mut rest = Vec::new();
+        split.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, b"89");
+    }
+
+    #[test]
+    fn test_split_parts_missing_part() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = write_split_parts(dir.path(), b"aaabbbccc", 3);
+        std::fs::remove_file(dir.path().join("image.img.002")).unwrap();
+
+        match split_parts(&first) {
+            Err(Error::SourceNotFound(missing)) => assert!(missing.ends_with("image.img.002")),
+            other => panic!("expected missing part error, got {:?}", other),
+        }
+        assert!(validate_source(&first).is_err());
+    }
+
+    #[test]
+    fn test_split_source_part_removed_while_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = write_split_parts(dir.path(), b"aaabbbccc", 3);
+        let mut split = SplitSource::open(&first).unwrap();
+        std::fs::remove_file(dir.path().join("image.img.002")).unwrap();
+
+        let mut buffer = Vec::new();
+        let err = split.read_to_end(&mut buffer).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+        assert!(err.to_string().contains("image.img.002"));
+        let source = std::error::Error::source(&err).expect("keeps the open error");
+        assert!(matches!(
+            source.downcast_ref::<Error>(),
+            Some(Error::SourceNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_split_source_sums_sizes() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = write_split_parts(dir.path(), &[7u8; 1000], 300);
+
+        let info = validate_source(&first).unwrap();
+        assert_eq!(info.source_type, SourceType::Split);
+        assert_eq!(info.size, Some(1000));
+        assert_eq!(get_source_size(&first).unwrap(), Some(1000));
+        assert_eq!(split_parts(&first).unwrap().len(), 4);
+    }
+
+    // -------------------------------------------------------------------------
+    // CountingReader tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_counting_reader_counts_bytes_read() {
+        let mut reader = CountingReader::new(Cursor::new(vec![1u8; 100]));
+        let counter = reader.counter();
+
+        let mut buffer = [0u8; 40];
+        reader.read_exact(&mut buffer).unwrap();
+        assert_eq!(reader.bytes_read(), 40);
+
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(counter.get(), 100);
+    }
+
+    #[test]
+    fn test_uncompressed_source_has_no_compressed_counter() {
+        let source = Source::from_bytes(vec![0u8; 16], "image.img");
+        assert!(source.compressed_counter().is_none());
+    }
+
     // -------------------------------------------------------------------------
     // Compression tests (require compression feature)
     // -------------------------------------------------------------------------
@@ -1900,6 +4066,38 @@ mod tests {
         std::fs::remove_file(&path).unwrap();
     }
 
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_gzip_source_counts_compressed_bytes() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path().to_str().unwrap().to_string() + ".gz";
+
+        let file = File::create(&path).unwrap();
+        let mut encoder = GzEncoder::new(file, Compression::default());
+        let data: Vec<u8> = (0..256 * 1024).map(|i| (i * 7 % 251) as u8).collect();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+        let compressed_size = std::fs::metadata(&path).unwrap().len();
+
+        let mut source = Source::open(&path).unwrap();
+        let counter = source.compressed_counter().unwrap();
+
+        let mut buffer = vec![0u8; 1024];
+        source.read_exact(&mut buffer).unwrap();
+        let after_first_read = counter.get();
+        assert!(after_first_read > 0);
+
+        let mut rest = Vec::new();
+        source.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest.len() + buffer.len(), data.len());
+        assert_eq!(counter.get(), compressed_size);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
     #[cfg(feature = "compression")]
     #[test]
     fn test_source_open_xz() {
@@ -1950,6 +4148,64 @@ mod tests {
         std::fs::remove_file(&path).unwrap();
     }
 
+    /// Write `data` as a long-distance-matching zstd file with a `window_log` window
+    #[cfg(feature = "compression")]
+    fn write_long_zstd(data: &[u8], window_log: u32) -> (NamedTempFile, String) {
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path().to_str().unwrap().to_string() + ".zst";
+
+        let mut encoder = zstd::Encoder::new(File::create(&path).unwrap(), 3).unwrap();
+        encoder.long_distance_matching(true).unwrap();
+        encoder.window_log(window_log).unwrap();
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap();
+        (temp, path)
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_zstd_options_window_log_clamped() {
+        assert_eq!(ZstdOptions::default().window_log, ZSTD_WINDOW_LOG_MAX);
+        assert_eq!(
+            ZstdOptions::new().window_log(1).window_log,
+            ZSTD_WINDOW_LOG_MIN
+        );
+        assert_eq!(
+            ZstdOptions::new().window_log(99).window_log,
+            ZSTD_WINDOW_LOG_MAX
+        );
+        assert_eq!(ZstdOptions::new().window_log(27).window_log, 27);
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_source_open_zstd_long_window() {
+        let data: Vec<u8> = (0..2 * 1024 * 1024u32).map(|i| (i % 251) as u8).collect();
+        let (_temp, path) = write_long_zstd(&data, 20);
+
+        let mut source = Source::open_zstd_with_options(&path, ZstdOptions::default()).unwrap();
+        assert_eq!(source.info().source_type, SourceType::Zstd);
+        let mut decoded = Vec::new();
+        source.read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, data);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_source_open_zstd_rejects_window_over_limit() {
+        let data: Vec<u8> = (0..2 * 1024 * 1024u32).map(|i| (i % 251) as u8).collect();
+        let (_temp, path) = write_long_zstd(&data, 20);
+
+        let options = ZstdOptions::new().window_log(17);
+        let mut source = Source::open_zstd_with_options(&path, options).unwrap();
+        let mut decoded = Vec::new();
+        assert!(source.read_to_end(&mut decoded).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
     #[cfg(feature = "compression")]
     #[test]
     fn test_source_open_bzip2() {
@@ -2037,6 +4293,130 @@ mod tests {
         assert!(!info.seekable);
     }
 
+    // -------------------------------------------------------------------------
+    // HttpOptions tests
+    // -------------------------------------------------------------------------
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_http_options_default() {
+        let options = HttpOptions::default();
+        assert_eq!(options.timeout.as_secs(), DEFAULT_HTTP_TIMEOUT_SECS);
+        assert_eq!(options.max_retries, DEFAULT_HTTP_MAX_RETRIES);
+        assert_eq!(options.backoff_base.as_millis(), 250);
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_http_options_from_settings() {
+        let settings = NetworkSettings {
+            http_timeout_secs: 5,
+            http_max_retries: 7,
+            http_backoff_base_ms: 40,
+            ..NetworkSettings::default()
+        };
+        let options = HttpOptions::from(&settings);
+        assert_eq!(options.timeout.as_secs(), 5);
+        assert_eq!(options.max_retries, 7);
+        assert_eq!(options.backoff_base.as_millis(), 40);
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_http_options_backoff_doubles() {
+        let options = HttpOptions::new().backoff_base(std::time::Duration::from_millis(100));
+        assert_eq!(options.backoff_delay(0).as_millis(), 100);
+        assert_eq!(options.backoff_delay(1).as_millis(), 200);
+        assert_eq!(options.backoff_delay(3).as_millis(), 800);
+        // Growth is capped so large attempt counts can't overflow
+        assert_eq!(options.backoff_delay(50), options.backoff_delay(10));
+    }
+
+    // -------------------------------------------------------------------------
+    // Server digest tests
+    // -------------------------------------------------------------------------
+
+    #[cfg(feature = "remote")]
+    const HELLO_SHA256_B64: &str = "LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=";
+
+    #[cfg(feature = "remote")]
+    const HELLO_SHA256_HEX: &str =
+        "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_decode_base64() {
+        assert_eq!(decode_base64("").unwrap(), b"");
+        assert_eq!(decode_base64("aGVsbG8=").unwrap(), b"hello");
+        assert_eq!(decode_base64("aGVsbG8h").unwrap(), b"hello!");
+        assert_eq!(decode_base64("aGk=").unwrap(), b"hi");
+        assert_eq!(decode_base64("aA==").unwrap(), b"h");
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_decode_base64_rejects_malformed() {
+        assert!(decode_base64("aGVsbG8").is_none());
+        assert!(decode_base64("aGV*bG8=").is_none());
+        assert!(decode_base64("aA==aGk=").is_none());
+        assert!(decode_base64("a===").is_none());
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_parse_repr_digest_sha256() {
+        let value = format!("sha-256=:{}:", HELLO_SHA256_B64);
+        assert_eq!(
+            parse_repr_digest(&value),
+            Some((ChecksumAlgorithm::Sha256, HELLO_SHA256_HEX.to_string()))
+        );
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_parse_repr_digest_prefers_strongest() {
+        let value = format!(
+            "md5=:XUFAKrxLKna5cZ2REBfFkg==:, SHA-256=:{}:, unixsum=:AAAA:",
+            HELLO_SHA256_B64
+        );
+        let (algorithm, hex) = parse_repr_digest(&value).unwrap();
+        assert_eq!(algorithm, ChecksumAlgorithm::Sha256);
+        assert_eq!(hex, HELLO_SHA256_HEX);
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_parse_repr_digest_skips_unknown_and_malformed() {
+        assert_eq!(parse_repr_digest("crc32c=:AAAAAA==:"), None);
+        // Right algorithm, wrong digest length
+        assert_eq!(parse_repr_digest("sha-256=:aGVsbG8=:"), None);
+        // Missing the colons of a byte sequence
+        assert_eq!(
+            parse_repr_digest(&format!("sha-256={}", HELLO_SHA256_B64)),
+            None
+        );
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_server_digest_from_headers() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("content-md5", "XUFAKrxLKna5cZ2REBfFkg==".parse().unwrap());
+        let md5 = (
+            ChecksumAlgorithm::Md5,
+            "5d41402abc4b2a76b9719d911017c592".to_string(),
+        );
+        assert_eq!(server_digest(&headers, false), Some(md5));
+        // Content-MD5 of a partial response covers only the range
+        assert_eq!(server_digest(&headers, true), None);
+
+        let repr = format!("sha-256=:{}:", HELLO_SHA256_B64);
+        headers.insert("repr-digest", repr.parse().unwrap());
+        let sha256 = (ChecksumAlgorithm::Sha256, HELLO_SHA256_HEX.to_string());
+        assert_eq!(server_digest(&headers, false), Some(sha256.clone()));
+        assert_eq!(server_digest(&headers, true), Some(sha256));
+    }
+
     // -------------------------------------------------------------------------
     // Source with offset tests
     // -------------------------------------------------------------------------
@@ -2053,6 +4433,33 @@ mod tests {
         assert_eq!(&buffer, b"ABCDEF");
     }
 
+    #[test]
+    fn test_source_reopen_local_starts_at_beginning() {
+        let mut temp = NamedTempFile::new().unwrap();
+        temp.write_all(b"0123456789ABCDEF").unwrap();
+
+        let source = Source::open_with_offset(temp.path().to_str().unwrap(), 10).unwrap();
+        let mut reopened = source.reopen().unwrap();
+
+        let mut buffer = Vec::new();
+        reopened.read_to_end(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"0123456789ABCDEF");
+        assert_eq!(reopened.info().path, source.info().path);
+    }
+
+    #[test]
+    fn test_source_reopen_memory() {
+        let mut source = Source::from_bytes(b"hello world".to_vec(), "buffer.img");
+        let mut consumed = Vec::new();
+        source.read_to_end(&mut consumed).unwrap();
+
+        let mut reopened = source.reopen().unwrap();
+        let mut buffer = Vec::new();
+        reopened.read_to_end(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"hello world");
+        assert_eq!(reopened.info().path, "buffer.img");
+    }
+
     #[cfg(feature = "compression")]
     #[test]
     fn test_source_compressed_cannot_resume() {
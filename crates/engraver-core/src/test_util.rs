@@ -0,0 +1,104 @@
+//! Fixtures shared by the unit tests of several modules
+
+use engraver_platform::{DeviceInfo, RawDevice};
+use std::cell::Cell;
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+
+/// An in-memory device that counts [`RawDevice::drop_cache`],
+/// [`RawDevice::write_batch`] and [`RawDevice::sync`] calls
+pub(crate) struct CacheCountingDevice {
+    inner: Cursor<Vec<u8>>,
+    info: DeviceInfo,
+    pub(crate) drops: Cell<usize>,
+    pub(crate) batches: Cell<usize>,
+    pub(crate) syncs: Cell<usize>,
+    /// Fail every `write_batch` call, as a device that cannot batch would
+    pub(crate) fail_batches: bool,
+}
+
+impl CacheCountingDevice {
+    /// A zero-filled device of `size` bytes
+    pub(crate) fn new(size: usize) -> Self {
+        Self {
+            inner: Cursor::new(vec![0u8; size]),
+            info: DeviceInfo {
+                path: "mem".to_string(),
+                size: size as u64,
+                block_size: 512,
+                direct_io: false,
+                physical_block_size: 0,
+                optimal_io_size: None,
+                is_rotational: false,
+            },
+            drops: Cell::new(0),
+            batches: Cell::new(0),
+            syncs: Cell::new(0),
+            fail_batches: false,
+        }
+    }
+
+    /// Everything written so far
+    pub(crate) fn data(&self) -> &[u8] {
+        self.inner.get_ref()
+    }
+}
+
+impl Read for CacheCountingDevice {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl Write for CacheCountingDevice {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for CacheCountingDevice {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+impl RawDevice for CacheCountingDevice {
+    fn info(&self) -> &DeviceInfo {
+        &self.info
+    }
+
+    fn sync(&self) -> engraver_platform::Result<()> {
+        self.syncs.set(self.syncs.get() + 1);
+        Ok(())
+    }
+
+    fn drop_cache(&self) -> engraver_platform::Result<()> {
+        self.drops.set(self.drops.get() + 1);
+        Ok(())
+    }
+
+    fn write_at(&mut self, offset: u64, data: &[u8]) -> engraver_platform::Result<usize> {
+        self.inner.seek(SeekFrom::Start(offset))?;
+        Ok(self.inner.write(data)?)
+    }
+
+    fn read_at(&mut self, offset: u64, buffer: &mut [u8]) -> engraver_platform::Result<usize> {
+        self.inner.seek(SeekFrom::Start(offset))?;
+        Ok(self.inner.read(buffer)?)
+    }
+
+    fn write_batch(&mut self, chunks: &[(u64, &[u8])]) -> engraver_platform::Result<usize> {
+        self.batches.set(self.batches.get() + 1);
+        if self.fail_batches {
+            return Err(std::io::Error::other("batch rejected").into());
+        }
+        let mut written = 0;
+        for &(offset, data) in chunks {
+            written += self.write_at(offset, data)?;
+        }
+        Ok(written)
+    }
+}
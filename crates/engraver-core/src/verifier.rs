@@ -1,7 +1,7 @@
 //! Verification and checksum module for Engraver
 //!
 //! This module provides:
-//! - Checksum calculation (SHA-256, SHA-512, MD5, CRC32)
+//! - Checksum calculation (SHA-256, SHA-512, SHA3-256, MD5, CRC32)
 //! - Post-write verification (read-back and compare)
 //! - Checksum file parsing (.sha256, .md5, etc.)
 //! - Progress tracking during verification
@@ -26,11 +26,13 @@
 //! # Ok::<(), engraver_core::Error>(())
 //! ```
 
+use crate::cancel::CancelToken;
 use crate::error::{Error, Result};
+use engraver_platform::RawDevice;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::io::{Read, Seek, SeekFrom};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
 use std::time::{Duration, Instant};
+use subtle::ConstantTimeEq;
 
 // ============================================================================
 // Constants
@@ -45,6 +47,12 @@ pub const MIN_VERIFY_BLOCK_SIZE: usize = 4 * 1024;
 /// Maximum block size (16 MB)
 pub const MAX_VERIFY_BLOCK_SIZE: usize = 16 * 1024 * 1024;
 
+/// Default cap on [`VerificationResult::mismatch_offsets`]
+pub const DEFAULT_MAX_RECORDED_MISMATCHES: usize = 1024;
+
+/// Seed for choosing the blocks of a sampled verify, so runs are reproducible
+pub const VERIFY_SAMPLE_SEED: u64 = 0x656e_6772_6176_6572;
+
 // ============================================================================
 // Checksum Algorithm
 // ============================================================================
@@ -57,10 +65,17 @@ pub enum ChecksumAlgorithm {
     Sha256,
     /// SHA-512
     Sha512,
+    /// SHA-1 (legacy, for older checksum files only)
+    Sha1,
     /// MD5 (legacy, not recommended for security)
     Md5,
     /// CRC32 (fast, not cryptographic)
     Crc32,
+    /// SHA3-256
+    ///
+    /// Same digest length as SHA-256, so it is never inferred from a bare hex
+    /// string; select it by name (e.g. `sha3-256`).
+    Sha3_256,
 }
 
 impl ChecksumAlgorithm {
@@ -69,8 +84,10 @@ impl ChecksumAlgorithm {
         match self {
             ChecksumAlgorithm::Sha256 => 32,
             ChecksumAlgorithm::Sha512 => 64,
+            ChecksumAlgorithm::Sha1 => 20,
             ChecksumAlgorithm::Md5 => 16,
             ChecksumAlgorithm::Crc32 => 4,
+            ChecksumAlgorithm::Sha3_256 => 32,
         }
     }
 
@@ -79,13 +96,42 @@ impl ChecksumAlgorithm {
         self.byte_length() * 2
     }
 
+    /// Short lowercase identifier, as accepted by `--checksum-algo` (e.g. `"sha256"`)
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Sha256 => "sha256",
+            ChecksumAlgorithm::Sha512 => "sha512",
+            ChecksumAlgorithm::Sha1 => "sha1",
+            ChecksumAlgorithm::Md5 => "md5",
+            ChecksumAlgorithm::Crc32 => "crc32",
+            ChecksumAlgorithm::Sha3_256 => "sha3-256",
+        }
+    }
+
     /// Get algorithm name
     pub fn name(&self) -> &'static str {
         match self {
             ChecksumAlgorithm::Sha256 => "SHA-256",
             ChecksumAlgorithm::Sha512 => "SHA-512",
+            ChecksumAlgorithm::Sha1 => "SHA-1",
+            ChecksumAlgorithm::Md5 => "MD5",
+            ChecksumAlgorithm::Crc32 => "CRC32",
+            ChecksumAlgorithm::Sha3_256 => "SHA3-256",
+        }
+    }
+
+    /// Tag used in BSD-style checksum lines, as written by `sha256sum --tag`
+    ///
+    /// Unlike [`name`](Self::name) the SHA-1 and SHA-2 tags have no hyphen
+    /// (`SHA256 (image.iso) = ...`), which is what checksum tools expect.
+    pub fn bsd_tag(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Sha256 => "SHA256",
+            ChecksumAlgorithm::Sha512 => "SHA512",
+            ChecksumAlgorithm::Sha1 => "SHA1",
             ChecksumAlgorithm::Md5 => "MD5",
             ChecksumAlgorithm::Crc32 => "CRC32",
+            ChecksumAlgorithm::Sha3_256 => "SHA3-256",
         }
     }
 
@@ -94,16 +140,22 @@ impl ChecksumAlgorithm {
         match self {
             ChecksumAlgorithm::Sha256 => ".sha256",
             ChecksumAlgorithm::Sha512 => ".sha512",
+            ChecksumAlgorithm::Sha1 => ".sha1",
             ChecksumAlgorithm::Md5 => ".md5",
             ChecksumAlgorithm::Crc32 => ".crc32",
+            ChecksumAlgorithm::Sha3_256 => ".sha3-256",
         }
     }
 
     /// Try to detect algorithm from a hex string length
+    ///
+    /// 64 characters is always taken as SHA-256; SHA3-256 must be selected
+    /// explicitly.
     pub fn from_hex_length(len: usize) -> Option<Self> {
         match len {
             64 => Some(ChecksumAlgorithm::Sha256),
             128 => Some(ChecksumAlgorithm::Sha512),
+            40 => Some(ChecksumAlgorithm::Sha1),
             32 => Some(ChecksumAlgorithm::Md5),
             8 => Some(ChecksumAlgorithm::Crc32),
             _ => None,
@@ -116,8 +168,10 @@ impl ChecksumAlgorithm {
         match ext.as_str() {
             ".sha256" | "sha256" | ".sha256sum" => Some(ChecksumAlgorithm::Sha256),
             ".sha512" | "sha512" | ".sha512sum" => Some(ChecksumAlgorithm::Sha512),
+            ".sha1" | "sha1" | ".sha1sum" | "sha1sum" => Some(ChecksumAlgorithm::Sha1),
             ".md5" | "md5" | ".md5sum" => Some(ChecksumAlgorithm::Md5),
             ".crc32" | "crc32" | ".crc" => Some(ChecksumAlgorithm::Crc32),
+            ".sha3-256" | "sha3-256" | ".sha3-256sum" => Some(ChecksumAlgorithm::Sha3_256),
             _ => None,
         }
     }
@@ -127,8 +181,10 @@ impl ChecksumAlgorithm {
         &[
             ChecksumAlgorithm::Sha256,
             ChecksumAlgorithm::Sha512,
+            ChecksumAlgorithm::Sha1,
             ChecksumAlgorithm::Md5,
             ChecksumAlgorithm::Crc32,
+            ChecksumAlgorithm::Sha3_256,
         ]
     }
 }
@@ -147,8 +203,10 @@ impl std::str::FromStr for ChecksumAlgorithm {
         match s.as_str() {
             "sha256" | "sha-256" => Ok(ChecksumAlgorithm::Sha256),
             "sha512" | "sha-512" => Ok(ChecksumAlgorithm::Sha512),
+            "sha1" | "sha-1" => Ok(ChecksumAlgorithm::Sha1),
             "md5" => Ok(ChecksumAlgorithm::Md5),
             "crc32" | "crc-32" => Ok(ChecksumAlgorithm::Crc32),
+            "sha3-256" | "sha3_256" | "sha3" => Ok(ChecksumAlgorithm::Sha3_256),
             _ => Err(Error::InvalidConfig(format!(
                 "Unknown checksum algorithm: {}",
                 s
@@ -157,12 +215,33 @@ impl std::str::FromStr for ChecksumAlgorithm {
     }
 }
 
+/// Serialized as [`ChecksumAlgorithm::short_name`]
+impl Serialize for ChecksumAlgorithm {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.short_name())
+    }
+}
+
+/// Accepts any spelling understood by [`FromStr`](std::str::FromStr), e.g.
+/// `"sha256"` or `"SHA-256"`
+impl<'de> Deserialize<'de> for ChecksumAlgorithm {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 // ============================================================================
 // Checksum Result
 // ============================================================================
 
 /// Checksum calculation result
-#[derive(Debug, Clone)]
+///
+/// Serializes as `{ "algorithm": "sha256", "value": "<hex>" }`; the value is
+/// checked against the algorithm's length when deserializing, as in
+/// [`Checksum::from_hex`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "ChecksumRepr", try_from = "ChecksumRepr")]
 pub struct Checksum {
     /// The algorithm used
     pub algorithm: ChecksumAlgorithm,
@@ -208,6 +287,23 @@ impl Checksum {
         let hex = hex.trim().to_lowercase();
         self.to_hex() == hex
     }
+
+    /// Check if this checksum matches a hex string, in constant time
+    ///
+    /// Agrees with [`matches_hex`](Self::matches_hex), but the time taken
+    /// does not depend on where the digests first differ. Only the length of
+    /// `hex` can be learned from timing. Use it when the expected value is a
+    /// secret, such as a keyed hash.
+    pub fn matches_hex_ct(&self, hex: &str) -> bool {
+        let hex = hex.trim();
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return false;
+        }
+        match hex_to_bytes(hex) {
+            Ok(bytes) => bytes.ct_eq(&self.bytes).into(),
+            Err(_) => false,
+        }
+    }
 }
 
 impl std::fmt::Display for Checksum {
@@ -222,6 +318,30 @@ impl PartialEq for Checksum {
     }
 }
 
+/// Serialized form of [`Checksum`]
+#[derive(Serialize, Deserialize)]
+struct ChecksumRepr {
+    algorithm: ChecksumAlgorithm,
+    value: String,
+}
+
+impl From<Checksum> for ChecksumRepr {
+    fn from(checksum: Checksum) -> Self {
+        Self {
+            value: checksum.to_hex(),
+            algorithm: checksum.algorithm,
+        }
+    }
+}
+
+impl TryFrom<ChecksumRepr> for Checksum {
+    type Error = Error;
+
+    fn try_from(repr: ChecksumRepr) -> Result<Self> {
+        Checksum::from_hex(repr.algorithm, &repr.value)
+    }
+}
+
 // ============================================================================
 // Verification Progress
 // ============================================================================
@@ -229,6 +349,21 @@ impl PartialEq for Checksum {
 /// Progress callback type
 pub type ProgressCallback = Box<dyn FnMut(&VerificationProgress) + Send>;
 
+/// Phase callback type, see [`Verifier::on_phase`]
+pub type PhaseCallback = Box<dyn FnMut(VerifyPhase) + Send>;
+
+/// Phase of a verification, reported to [`Verifier::on_phase`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VerifyPhase {
+    /// Reading a single stream to checksum it
+    Reading,
+    /// Reading source and target side by side and comparing them
+    Comparing,
+    /// The checksum or comparison finished (whether or not it matched)
+    Complete,
+}
+
 /// Verification progress information
 #[derive(Debug, Clone)]
 pub struct VerificationProgress {
@@ -314,6 +449,20 @@ pub struct VerificationResult {
     pub elapsed: Duration,
     /// Average speed
     pub speed_bps: u64,
+    /// Whether the target still had data past the compared length
+    ///
+    /// Only checked when [`VerifyConfig::check_target_length`] is enabled.
+    pub target_has_trailing_data: bool,
+    /// Whether only a sample of blocks was compared, see [`VerifyConfig::sample_fraction`]
+    pub sampled: bool,
+    /// Bytes compared by a sampled verify (0 for a full verify)
+    pub sampled_bytes: u64,
+    /// Where each mismatching block first differs, in order
+    ///
+    /// Holds at most [`VerifyConfig::max_recorded_mismatches`] entries, so it
+    /// can be shorter than `mismatches`. With `stop_on_mismatch` disabled it
+    /// shows whether failures cluster in one region or are spread out.
+    pub mismatch_offsets: Vec<u64>,
 }
 
 impl VerificationResult {
@@ -332,6 +481,10 @@ impl VerificationResult {
             first_mismatch_offset: None,
             elapsed,
             speed_bps,
+            target_has_trailing_data: false,
+            sampled: false,
+            sampled_bytes: 0,
+            mismatch_offsets: Vec::new(),
         }
     }
 
@@ -355,6 +508,10 @@ impl VerificationResult {
             first_mismatch_offset,
             elapsed,
             speed_bps,
+            target_has_trailing_data: false,
+            sampled: false,
+            sampled_bytes: 0,
+            mismatch_offsets: Vec::new(),
         }
     }
 }
@@ -370,6 +527,16 @@ pub struct VerifyConfig {
     pub block_size: usize,
     /// Stop on first mismatch
     pub stop_on_mismatch: bool,
+    /// Treat data on the target past the compared length as a mismatch (file targets only)
+    pub check_target_length: bool,
+    /// Emit CRC checksums in little-endian byte order
+    pub crc_little_endian: bool,
+    /// Compare only this fraction of blocks (`None` compares everything)
+    pub sample_fraction: Option<f64>,
+    /// Drop the target's cached pages before reading it back
+    pub drop_cache: bool,
+    /// Most mismatch offsets kept in [`VerificationResult::mismatch_offsets`]
+    pub max_recorded_mismatches: usize,
 }
 
 impl Default for VerifyConfig {
@@ -377,6 +544,11 @@ impl Default for VerifyConfig {
         Self {
             block_size: DEFAULT_VERIFY_BLOCK_SIZE,
             stop_on_mismatch: true,
+            check_target_length: false,
+            crc_little_endian: false,
+            sample_fraction: None,
+            drop_cache: true,
+            max_recorded_mismatches: DEFAULT_MAX_RECORDED_MISMATCHES,
         }
     }
 }
@@ -398,13 +570,73 @@ impl VerifyConfig {
         self.stop_on_mismatch = stop;
         self
     }
+
+    /// Set how many mismatch offsets a comparison records
+    ///
+    /// Every mismatch is still counted in [`VerificationResult::mismatches`];
+    /// this only bounds the memory used by
+    /// [`mismatch_offsets`](VerificationResult::mismatch_offsets). Defaults
+    /// to [`DEFAULT_MAX_RECORDED_MISMATCHES`]; 0 records none.
+    pub fn max_recorded_mismatches(mut self, max: usize) -> Self {
+        self.max_recorded_mismatches = max;
+        self
+    }
+
+    /// Set whether to check for trailing data on the target
+    ///
+    /// After `size` bytes have been compared, the length the target reports
+    /// is checked. If it is longer, the result is marked as a mismatch with
+    /// `target_has_trailing_data` set. Useful when a file that held a larger
+    /// image is expected to have been fully replaced.
+    ///
+    /// Only meaningful for file targets: a block device reports its whole
+    /// capacity, so any image smaller than the device would be flagged.
+    pub fn check_target_length(mut self, check: bool) -> Self {
+        self.check_target_length = check;
+        self
+    }
+
+    /// Set the byte order of finalized CRC checksums
+    ///
+    /// CRC32 values are emitted big-endian by default (`3610a686` for
+    /// "hello"); some vendors publish the little-endian form (`86a61036`).
+    /// Only affects CRC variants; other algorithms are byte strings already.
+    pub fn crc_little_endian(mut self, little_endian: bool) -> Self {
+        self.crc_little_endian = little_endian;
+        self
+    }
+
+    /// Compare only a fraction (`0.0..=1.0`) of blocks in [`Verifier::compare`]
+    ///
+    /// The blocks are spread pseudo-randomly over the whole range but chosen
+    /// from [`VERIFY_SAMPLE_SEED`], so the same size and block size always
+    /// check the same offsets. This trades coverage for speed: a sampled pass
+    /// catches a failing or counterfeit device, but not an isolated bad block,
+    /// and is no substitute for a full verify. Fractions of 1.0 or more (and
+    /// non-positive or NaN ones) fall back to comparing everything.
+    pub fn sample_fraction(mut self, fraction: f64) -> Self {
+        self.sample_fraction = (fraction > 0.0 && fraction < 1.0).then_some(fraction);
+        self
+    }
+
+    /// Set whether [`Verifier::drop_target_cache`] drops the target's cached pages
+    ///
+    /// On by default. Data that was just written is still in the page cache,
+    /// so without dropping it a read-back verify compares the source against
+    /// memory and can pass on a drive that never stored the data. Turn it off
+    /// only to measure cached read speed or when the target is not a device.
+    pub fn drop_cache(mut self, drop: bool) -> Self {
+        self.drop_cache = drop;
+        self
+    }
 }
 
 /// Verifier for checksums and data comparison
 pub struct Verifier {
     config: VerifyConfig,
     progress_callback: Option<ProgressCallback>,
-    cancel_flag: Arc<AtomicBool>,
+    phase_callback: Option<PhaseCallback>,
+    cancel_flag: CancelToken,
 }
 
 impl Verifier {
@@ -413,7 +645,8 @@ impl Verifier {
         Self {
             config: VerifyConfig::default(),
             progress_callback: None,
-            cancel_flag: Arc::new(AtomicBool::new(false)),
+            phase_callback: None,
+            cancel_flag: CancelToken::new(),
         }
     }
 
@@ -422,7 +655,8 @@ impl Verifier {
         Self {
             config,
             progress_callback: None,
-            cancel_flag: Arc::new(AtomicBool::new(false)),
+            phase_callback: None,
+            cancel_flag: CancelToken::new(),
         }
     }
 
@@ -435,9 +669,40 @@ impl Verifier {
         self
     }
 
+    /// Set a callback for each [`VerifyPhase`] an operation moves through
+    ///
+    /// Checksumming reports `Reading` then `Complete`; comparing reports
+    /// `Comparing` then `Complete`. An error or cancellation ends the
+    /// operation without `Complete`.
+    pub fn on_phase<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(VerifyPhase) + Send + 'static,
+    {
+        self.phase_callback = Some(Box::new(callback));
+        self
+    }
+
     /// Get a handle to cancel the operation
-    pub fn cancel_handle(&self) -> Arc<AtomicBool> {
-        Arc::clone(&self.cancel_flag)
+    pub fn cancel_handle(&self) -> CancelToken {
+        self.cancel_flag.clone()
+    }
+
+    /// Cancel through `token` instead of a token of the verifier's own
+    pub(crate) fn with_cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel_flag = token;
+        self
+    }
+
+    /// Drop `target`'s cached pages so the read-back hits the device
+    ///
+    /// Call this before comparing or checksumming a device that was just
+    /// written. Does nothing if [`VerifyConfig::drop_cache`] is off, or on
+    /// platforms without a way to drop a device's cache.
+    pub fn drop_target_cache<D: RawDevice + ?Sized>(&self, target: &D) -> Result<()> {
+        if !self.config.drop_cache {
+            return Ok(());
+        }
+        Ok(target.drop_cache()?)
     }
 
     /// Calculate checksum of a reader
@@ -448,45 +713,39 @@ impl Verifier {
         algorithm: ChecksumAlgorithm,
         total_size: Option<u64>,
     ) -> Result<Checksum> {
-        use sha2::Digest;
+        let mut checksums = self.calculate_checksums(reader, &[algorithm], total_size)?;
+        Ok(checksums.remove(0))
+    }
 
-        self.cancel_flag.store(false, Ordering::SeqCst);
+    /// Calculate several checksums of a reader in a single pass
+    ///
+    /// Returns one checksum per entry in `algorithms`, in the same order.
+    #[cfg(feature = "checksum")]
+    pub fn calculate_checksums<R: Read + ?Sized>(
+        &mut self,
+        reader: &mut R,
+        algorithms: &[ChecksumAlgorithm],
+        total_size: Option<u64>,
+    ) -> Result<Vec<Checksum>> {
+        self.cancel_flag.reset();
+        self.enter_phase(VerifyPhase::Reading);
         let start = Instant::now();
         let mut bytes_processed = 0u64;
         let mut buffer = vec![0u8; self.config.block_size];
-
-        // Create the appropriate hasher
-        enum Hasher {
-            Sha256(sha2::Sha256),
-            Sha512(sha2::Sha512),
-            Md5(md5::Md5),
-            Crc32(crc32fast::Hasher),
-        }
-
-        let mut hasher = match algorithm {
-            ChecksumAlgorithm::Sha256 => Hasher::Sha256(sha2::Sha256::new()),
-            ChecksumAlgorithm::Sha512 => Hasher::Sha512(sha2::Sha512::new()),
-            ChecksumAlgorithm::Md5 => Hasher::Md5(md5::Md5::new()),
-            ChecksumAlgorithm::Crc32 => Hasher::Crc32(crc32fast::Hasher::new()),
-        };
+        let mut hashers: Vec<StreamHasher> =
+            algorithms.iter().map(|&a| StreamHasher::new(a)).collect();
 
         loop {
             // Check for cancellation
-            if self.cancel_flag.load(Ordering::SeqCst) {
-                return Err(Error::Cancelled);
-            }
+            self.cancel_flag.check()?;
 
             let n = read_full(reader, &mut buffer)?;
             if n == 0 {
                 break;
             }
 
-            // Update hasher
-            match &mut hasher {
-                Hasher::Sha256(h) => h.update(&buffer[..n]),
-                Hasher::Sha512(h) => h.update(&buffer[..n]),
-                Hasher::Md5(h) => h.update(&buffer[..n]),
-                Hasher::Crc32(h) => h.update(&buffer[..n]),
+            for hasher in &mut hashers {
+                hasher.update(&buffer[..n]);
             }
 
             bytes_processed += n as u64;
@@ -519,15 +778,12 @@ impl Verifier {
             }
         }
 
-        // Finalize and get result
-        let bytes = match hasher {
-            Hasher::Sha256(h) => h.finalize().to_vec(),
-            Hasher::Sha512(h) => h.finalize().to_vec(),
-            Hasher::Md5(h) => h.finalize().to_vec(),
-            Hasher::Crc32(h) => h.finalize().to_be_bytes().to_vec(),
-        };
-
-        Ok(Checksum::new(algorithm, bytes))
+        self.enter_phase(VerifyPhase::Complete);
+        let little_endian = self.config.crc_little_endian;
+        Ok(hashers
+            .into_iter()
+            .map(|hasher| hasher.finalize(little_endian))
+            .collect())
     }
 
     /// Calculate checksum and verify against expected value
@@ -544,7 +800,7 @@ impl Verifier {
         let actual = self.calculate_checksum(reader, algorithm, total_size)?;
         let elapsed = start.elapsed();
 
-        if actual.matches_hex(expected) {
+        if actual.matches_hex_ct(expected) {
             Ok(VerificationResult::success(
                 total_size.unwrap_or(0),
                 elapsed,
@@ -568,65 +824,159 @@ impl Verifier {
         R: Read + Seek + ?Sized,
         T: Read + Seek + ?Sized,
     {
-        self.cancel_flag.store(false, Ordering::SeqCst);
+        let result = match self.config.sample_fraction {
+            Some(fraction) => self.compare_sampled(source, target, size, fraction),
+            None => self.compare_inner(source, target, size, 0, |_| {}),
+        }?;
+        self.enter_phase(VerifyPhase::Complete);
+        Ok(result)
+    }
+
+    /// Compare source and target byte-by-byte, starting at `start_offset`
+    ///
+    /// Used to resume an interrupted verification: the first `start_offset`
+    /// bytes are taken as already verified. Progress, `bytes_verified` and
+    /// mismatch offsets are absolute positions, while `speed_bps` only counts
+    /// the bytes compared by this call. A sampled verify cannot be resumed.
+    pub fn compare_from_offset<R, T>(
+        &mut self,
+        source: &mut R,
+        target: &mut T,
+        size: u64,
+        start_offset: u64,
+    ) -> Result<VerificationResult>
+    where
+        R: Read + Seek + ?Sized,
+        T: Read + Seek + ?Sized,
+    {
+        if start_offset == 0 {
+            return self.compare(source, target, size);
+        }
+        if self.config.sample_fraction.is_some() {
+            return Err(Error::InvalidConfig(
+                "A sampled verification cannot resume from an offset".to_string(),
+            ));
+        }
+        if start_offset > size {
+            return Err(Error::InvalidConfig(format!(
+                "Resume offset {} is past the end of the {} bytes to verify",
+                start_offset, size
+            )));
+        }
+        let result = self.compare_inner(source, target, size, start_offset, |_| {})?;
+        self.enter_phase(VerifyPhase::Complete);
+        Ok(result)
+    }
+
+    /// Compare source and target byte-by-byte while hashing the source
+    ///
+    /// Both streams are read once, so this replaces a [`compare`](Self::compare)
+    /// followed by a separate [`calculate_checksum`](Self::calculate_checksum)
+    /// of the source. Progress is reported as [`VerificationOperation::Compare`].
+    /// The returned checksum always covers the first `size` bytes of the source,
+    /// even when `stop_on_mismatch` ends the comparison early, so
+    /// [`VerifyConfig::sample_fraction`] is ignored here.
+    #[cfg(feature = "checksum")]
+    pub fn compare_and_hash<R, T>(
+        &mut self,
+        source: &mut R,
+        target: &mut T,
+        size: u64,
+        algorithm: ChecksumAlgorithm,
+    ) -> Result<(VerificationResult, Checksum)>
+    where
+        R: Read + Seek + ?Sized,
+        T: Read + Seek + ?Sized,
+    {
+        let mut hasher = StreamHasher::new(algorithm);
+        let mut hashed = 0u64;
+        let result = self.compare_inner(source, target, size, 0, |block| {
+            hasher.update(block);
+            hashed += block.len() as u64;
+        })?;
+
+        // Finish hashing the source if the comparison stopped at a mismatch
+        let mut buffer = vec![0u8; self.config.block_size];
+        while hashed < size {
+            self.cancel_flag.check()?;
+
+            let to_read = buffer.len().min((size - hashed) as usize);
+            let n = read_full(source, &mut buffer[..to_read])?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buffer[..n]);
+            hashed += n as u64;
+        }
+
+        self.enter_phase(VerifyPhase::Complete);
+        Ok((result, hasher.finalize(self.config.crc_little_endian)))
+    }
+
+    /// Byte-by-byte comparison from `start_offset`, passing each block read
+    /// from the source to `on_source`
+    fn compare_inner<R, T, F>(
+        &mut self,
+        source: &mut R,
+        target: &mut T,
+        size: u64,
+        start_offset: u64,
+        mut on_source: F,
+    ) -> Result<VerificationResult>
+    where
+        R: Read + Seek + ?Sized,
+        T: Read + Seek + ?Sized,
+        F: FnMut(&[u8]),
+    {
+        self.cancel_flag.reset();
+        self.enter_phase(VerifyPhase::Comparing);
         let start = Instant::now();
 
-        // Seek both to start
-        source.seek(SeekFrom::Start(0))?;
-        target.seek(SeekFrom::Start(0))?;
+        // Seek both to the first byte still to compare
+        source.seek(SeekFrom::Start(start_offset))?;
+        target.seek(SeekFrom::Start(start_offset))?;
 
         let block_size = self.config.block_size;
         let mut source_buf = vec![0u8; block_size];
         let mut target_buf = vec![0u8; block_size];
-        let mut bytes_verified = 0u64;
+        let mut bytes_verified = start_offset;
         let mut mismatches = 0u64;
         let mut first_mismatch: Option<u64> = None;
+        let mut mismatch_offsets = Vec::new();
 
         while bytes_verified < size {
             // Check for cancellation
-            if self.cancel_flag.load(Ordering::SeqCst) {
-                return Err(Error::Cancelled);
-            }
+            self.cancel_flag.check()?;
 
             let to_read = block_size.min((size - bytes_verified) as usize);
 
             let source_read = read_full(source, &mut source_buf[..to_read])?;
+            on_source(&source_buf[..source_read]);
             let target_read = read_full(target, &mut target_buf[..to_read])?;
 
-            // Check for size mismatch
-            if source_read != target_read {
-                mismatches += 1;
-                if first_mismatch.is_none() {
-                    first_mismatch = Some(bytes_verified);
-                }
-                if self.config.stop_on_mismatch {
-                    let elapsed = start.elapsed();
-                    return Ok(VerificationResult::failure(
-                        bytes_verified,
-                        mismatches,
-                        first_mismatch,
-                        elapsed,
-                    ));
-                }
-            } else if source_buf[..source_read] != target_buf[..target_read] {
+            // A short read counts from the block start, a difference from its exact offset
+            let difference = if source_read != target_read {
+                Some(0)
+            } else {
+                source_buf[..source_read]
+                    .iter()
+                    .zip(&target_buf[..target_read])
+                    .position(|(a, b)| a != b)
+            };
+            if let Some(index) = difference {
+                let offset = bytes_verified + index as u64;
                 mismatches += 1;
-                if first_mismatch.is_none() {
-                    // Find exact offset
-                    for i in 0..source_read {
-                        if source_buf[i] != target_buf[i] {
-                            first_mismatch = Some(bytes_verified + i as u64);
-                            break;
-                        }
-                    }
-                }
+                first_mismatch.get_or_insert(offset);
+                self.record_mismatch(&mut mismatch_offsets, offset);
                 if self.config.stop_on_mismatch {
-                    let elapsed = start.elapsed();
-                    return Ok(VerificationResult::failure(
+                    let mut result = VerificationResult::failure(
                         bytes_verified,
                         mismatches,
                         first_mismatch,
-                        elapsed,
-                    ));
+                        start.elapsed(),
+                    );
+                    result.mismatch_offsets = mismatch_offsets;
+                    return Ok(result);
                 }
             }
 
@@ -636,7 +986,7 @@ impl Verifier {
             if let Some(ref mut callback) = self.progress_callback {
                 let elapsed = start.elapsed();
                 let speed_bps = if elapsed.as_secs_f64() > 0.0 {
-                    (bytes_verified as f64 / elapsed.as_secs_f64()) as u64
+                    ((bytes_verified - start_offset) as f64 / elapsed.as_secs_f64()) as u64
                 } else {
                     0
                 };
@@ -662,20 +1012,177 @@ impl Verifier {
             }
         }
 
+        // Check whether the target has leftover data past the compared length
+        let mut target_has_trailing_data = false;
+        if self.config.check_target_length && has_data_past(target, bytes_verified)? {
+            target_has_trailing_data = true;
+            mismatches += 1;
+            first_mismatch.get_or_insert(bytes_verified);
+            self.record_mismatch(&mut mismatch_offsets, bytes_verified);
+        }
+
         let elapsed = start.elapsed();
-        if mismatches == 0 {
-            Ok(VerificationResult::success(bytes_verified, elapsed))
+        let mut result = if mismatches == 0 {
+            VerificationResult::success(bytes_verified, elapsed)
         } else {
-            Ok(VerificationResult::failure(
-                bytes_verified,
-                mismatches,
-                first_mismatch,
-                elapsed,
-            ))
+            VerificationResult::failure(bytes_verified, mismatches, first_mismatch, elapsed)
+        };
+        result.target_has_trailing_data = target_has_trailing_data;
+        result.mismatch_offsets = mismatch_offsets;
+        if start_offset > 0 {
+            // Only this call's bytes count towards the speed of a resumed compare
+            let resumed = VerificationResult::success(bytes_verified - start_offset, elapsed);
+            result.speed_bps = resumed.speed_bps;
+        }
+        Ok(result)
+    }
+
+    /// Compare a reproducible sample of `fraction` of the blocks in `0..size`
+    fn compare_sampled<R, T>(
+        &mut self,
+        source: &mut R,
+        target: &mut T,
+        size: u64,
+        fraction: f64,
+    ) -> Result<VerificationResult>
+    where
+        R: Read + Seek + ?Sized,
+        T: Read + Seek + ?Sized,
+    {
+        self.cancel_flag.reset();
+        self.enter_phase(VerifyPhase::Comparing);
+        let start = Instant::now();
+
+        let block_size = self.config.block_size;
+        let total_blocks = size.div_ceil(block_size as u64);
+        let wanted = ((total_blocks as f64 * fraction).ceil() as u64).min(total_blocks);
+        let planned_bytes = (wanted * block_size as u64).min(size);
+
+        let mut source_buf = vec![0u8; block_size];
+        let mut target_buf = vec![0u8; block_size];
+        let mut rng = SplitMix64(VERIFY_SAMPLE_SEED);
+        let mut selected = 0u64;
+        let mut bytes_verified = 0u64;
+        let mut mismatches = 0u64;
+        let mut first_mismatch: Option<u64> = None;
+        let mut mismatch_offsets = Vec::new();
+
+        // Selection sampling (Knuth's Algorithm S): visits blocks in order and
+        // picks exactly `wanted` of them without materializing an index list
+        for block in 0..total_blocks {
+            if selected == wanted {
+                break;
+            }
+            if rng.next_below(total_blocks - block) >= wanted - selected {
+                continue;
+            }
+            selected += 1;
+            self.cancel_flag.check()?;
+
+            let offset = block * block_size as u64;
+            let to_read = block_size.min((size - offset) as usize);
+            source.seek(SeekFrom::Start(offset))?;
+            target.seek(SeekFrom::Start(offset))?;
+            let source_read = read_full(source, &mut source_buf[..to_read])?;
+            let target_read = read_full(target, &mut target_buf[..to_read])?;
+
+            let difference = if source_read != target_read {
+                Some(source_read.min(target_read))
+            } else {
+                source_buf[..source_read]
+                    .iter()
+                    .zip(&target_buf[..target_read])
+                    .position(|(a, b)| a != b)
+            };
+            if let Some(index) = difference {
+                mismatches += 1;
+                first_mismatch.get_or_insert(offset + index as u64);
+                self.record_mismatch(&mut mismatch_offsets, offset + index as u64);
+                if self.config.stop_on_mismatch {
+                    let mut result = VerificationResult::failure(
+                        bytes_verified,
+                        mismatches,
+                        first_mismatch,
+                        start.elapsed(),
+                    );
+                    result.sampled = true;
+                    result.sampled_bytes = bytes_verified;
+                    result.mismatch_offsets = mismatch_offsets;
+                    return Ok(result);
+                }
+            }
+
+            bytes_verified += source_read as u64;
+
+            if let Some(ref mut callback) = self.progress_callback {
+                let elapsed = start.elapsed();
+                let speed_bps = if elapsed.as_secs_f64() > 0.0 {
+                    (bytes_verified as f64 / elapsed.as_secs_f64()) as u64
+                } else {
+                    0
+                };
+                let eta_seconds = if speed_bps > 0 && bytes_verified < planned_bytes {
+                    Some((planned_bytes - bytes_verified) / speed_bps)
+                } else {
+                    None
+                };
+
+                callback(&VerificationProgress {
+                    bytes_processed: bytes_verified,
+                    total_bytes: Some(planned_bytes),
+                    speed_bps,
+                    eta_seconds,
+                    elapsed,
+                    operation: VerificationOperation::Compare,
+                });
+            }
+        }
+
+        let mut target_has_trailing_data = false;
+        if self.config.check_target_length && has_data_past(target, size)? {
+            target_has_trailing_data = true;
+            mismatches += 1;
+            first_mismatch.get_or_insert(size);
+            self.record_mismatch(&mut mismatch_offsets, size);
+        }
+
+        let elapsed = start.elapsed();
+        let mut result = if mismatches == 0 {
+            VerificationResult::success(bytes_verified, elapsed)
+        } else {
+            VerificationResult::failure(bytes_verified, mismatches, first_mismatch, elapsed)
+        };
+        result.target_has_trailing_data = target_has_trailing_data;
+        result.mismatch_offsets = mismatch_offsets;
+        result.sampled = true;
+        result.sampled_bytes = bytes_verified;
+        Ok(result)
+    }
+
+    fn enter_phase(&mut self, phase: VerifyPhase) {
+        if let Some(ref mut callback) = self.phase_callback {
+            callback(phase);
+        }
+    }
+
+    /// Keep a mismatch offset unless [`VerifyConfig::max_recorded_mismatches`] are recorded
+    fn record_mismatch(&self, offsets: &mut Vec<u64>, offset: u64) {
+        if offsets.len() < self.config.max_recorded_mismatches {
+            offsets.push(offset);
         }
     }
 }
 
+/// Whether `target` holds data past `end`
+///
+/// Asks the target for its length instead of reading past `end`: a device
+/// opened with `O_DIRECT` rejects an unaligned probe read with `EINVAL`. That
+/// length is a device's full capacity, hence
+/// [`VerifyConfig::check_target_length`] being for file targets.
+fn has_data_past<T: Seek + ?Sized>(target: &mut T, end: u64) -> Result<bool> {
+    Ok(target.seek(SeekFrom::End(0))? > end)
+}
+
 impl Default for Verifier {
     fn default() -> Self {
         Self::new()
@@ -799,9 +1306,12 @@ pub struct DetectedChecksum {
 /// Attempt to find and parse a checksum file for the given source path
 ///
 /// This function looks for checksum files in common locations:
-/// 1. `{source}.sha256`, `{source}.sha512`, `{source}.md5` (direct extensions)
-/// 2. `{source}.sha256sum`, `{source}.sha512sum`, `{source}.md5sum`
-/// 3. `SHA256SUMS`, `SHA512SUMS`, `MD5SUMS` in the same directory
+/// 1. `{source}.sha256`, `{source}.sha512`, `{source}.sha1`, `{source}.md5` (direct extensions)
+/// 2. `{source}.sha256sum`, `{source}.sha512sum`, `{source}.sha1sum`, `{source}.md5sum`
+/// 3. `SHA256SUMS`, `SHA512SUMS`, `SHA1SUMS`, `MD5SUMS` in the same directory
+///
+/// SHA-1 and MD5 are only checked after the SHA-2 variants, for older
+/// projects that still publish legacy sums.
 ///
 /// Returns the checksum value and algorithm if found.
 ///
@@ -835,6 +1345,8 @@ pub fn auto_detect_checksum(source_path: &str) -> Option<DetectedChecksum> {
         ("sha256sum", ChecksumAlgorithm::Sha256),
         ("sha512", ChecksumAlgorithm::Sha512),
         ("sha512sum", ChecksumAlgorithm::Sha512),
+        ("sha1", ChecksumAlgorithm::Sha1),
+        ("sha1sum", ChecksumAlgorithm::Sha1),
         ("md5", ChecksumAlgorithm::Md5),
         ("md5sum", ChecksumAlgorithm::Md5),
     ];
@@ -863,6 +1375,10 @@ pub fn auto_detect_checksum(source_path: &str) -> Option<DetectedChecksum> {
         ("SHA512SUMS", ChecksumAlgorithm::Sha512),
         ("SHA512SUM", ChecksumAlgorithm::Sha512),
         ("sha512sums", ChecksumAlgorithm::Sha512),
+        ("SHA1SUMS", ChecksumAlgorithm::Sha1),
+        ("SHA1SUM", ChecksumAlgorithm::Sha1),
+        ("sha1sums", ChecksumAlgorithm::Sha1),
+        ("sha1sum.txt", ChecksumAlgorithm::Sha1),
         ("MD5SUMS", ChecksumAlgorithm::Md5),
         ("MD5SUM", ChecksumAlgorithm::Md5),
         ("md5sums", ChecksumAlgorithm::Md5),
@@ -933,6 +1449,51 @@ pub fn find_checksum_for_file<'a>(
     })
 }
 
+/// The checksum listed for `name` in the sums file at `sums_path`
+///
+/// `name` is matched with [`find_checksum_for_file`], so a bare filename
+/// also finds an entry listed with a path.
+pub fn checksum_from_sums(sums_path: &std::path::Path, name: &str) -> Result<Checksum> {
+    let content = std::fs::read_to_string(sums_path)?;
+    let entries = parse_checksum_file(&content);
+    let entry = find_checksum_for_file(&entries, name).ok_or_else(|| {
+        Error::InvalidConfig(format!(
+            "No checksum for {} in {}",
+            name,
+            sums_path.display()
+        ))
+    })?;
+    let algorithm = entry.algorithm.ok_or_else(|| {
+        Error::InvalidConfig(format!(
+            "Unknown checksum algorithm for {} in {}",
+            name,
+            sums_path.display()
+        ))
+    })?;
+    Checksum::from_hex(algorithm, &entry.checksum)
+}
+
+/// Verify the first `size` bytes of a device against `name`'s entry in a sums file
+///
+/// This checks a drive that was flashed earlier against a published
+/// `SHA256SUMS` (or similar) without needing the original image. Returns
+/// [`Error::ChecksumMismatch`] if the hashes differ.
+#[cfg(feature = "checksum")]
+pub fn verify_device_against_sums<R: Read + ?Sized>(
+    device: &mut R,
+    size: u64,
+    sums_path: &std::path::Path,
+    name: &str,
+) -> Result<VerificationResult> {
+    let expected = checksum_from_sums(sums_path, name)?;
+    Verifier::new().verify_checksum(
+        &mut device.take(size),
+        expected.algorithm,
+        &expected.to_hex(),
+        Some(size),
+    )
+}
+
 // ============================================================================
 // Legacy API (for backwards compatibility)
 // ============================================================================
@@ -960,6 +1521,68 @@ where
 // Helper Functions
 // ============================================================================
 
+/// Incremental hasher for any supported [`ChecksumAlgorithm`]
+#[cfg(feature = "checksum")]
+enum StreamHasher {
+    Sha256(sha2::Sha256),
+    Sha512(sha2::Sha512),
+    Sha1(sha1::Sha1),
+    Md5(md5::Md5),
+    Crc32(crc32fast::Hasher),
+    Sha3_256(sha3::Sha3_256),
+}
+
+#[cfg(feature = "checksum")]
+impl StreamHasher {
+    fn new(algorithm: ChecksumAlgorithm) -> Self {
+        use sha2::Digest;
+
+        match algorithm {
+            ChecksumAlgorithm::Sha256 => Self::Sha256(sha2::Sha256::new()),
+            ChecksumAlgorithm::Sha512 => Self::Sha512(sha2::Sha512::new()),
+            ChecksumAlgorithm::Sha1 => Self::Sha1(sha1::Sha1::new()),
+            ChecksumAlgorithm::Md5 => Self::Md5(md5::Md5::new()),
+            ChecksumAlgorithm::Crc32 => Self::Crc32(crc32fast::Hasher::new()),
+            ChecksumAlgorithm::Sha3_256 => Self::Sha3_256(sha3::Sha3_256::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        use sha2::Digest;
+
+        match self {
+            Self::Sha256(h) => h.update(data),
+            Self::Sha512(h) => h.update(data),
+            Self::Sha1(h) => h.update(data),
+            Self::Md5(h) => h.update(data),
+            Self::Crc32(h) => h.update(data),
+            Self::Sha3_256(h) => h.update(data),
+        }
+    }
+
+    /// Finish hashing; `crc_little_endian` picks the CRC byte order
+    fn finalize(self, crc_little_endian: bool) -> Checksum {
+        use sha2::Digest;
+
+        match self {
+            Self::Sha256(h) => Checksum::new(ChecksumAlgorithm::Sha256, h.finalize().to_vec()),
+            Self::Sha512(h) => Checksum::new(ChecksumAlgorithm::Sha512, h.finalize().to_vec()),
+            Self::Sha1(h) => Checksum::new(ChecksumAlgorithm::Sha1, h.finalize().to_vec()),
+            Self::Md5(h) => Checksum::new(ChecksumAlgorithm::Md5, h.finalize().to_vec()),
+            Self::Crc32(h) => {
+                let crc = h.finalize();
+                let bytes = if crc_little_endian {
+                    crc.to_le_bytes()
+                } else {
+                    crc.to_be_bytes()
+                };
+                Checksum::new(ChecksumAlgorithm::Crc32, bytes.to_vec())
+            }
+            Self::Sha3_256(h) => Checksum::new(ChecksumAlgorithm::Sha3_256, h.finalize().to_vec()),
+        }
+    }
+}
+
 /// Read as much as possible into buffer
 fn read_full<R: Read + ?Sized>(reader: &mut R, buf: &mut [u8]) -> Result<usize> {
     let mut total = 0;
@@ -974,8 +1597,26 @@ fn read_full<R: Read + ?Sized>(reader: &mut R, buf: &mut [u8]) -> Result<usize>
     Ok(total)
 }
 
+/// Small deterministic PRNG for choosing verify samples
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `0..bound` (`bound` must be non-zero)
+    fn next_below(&mut self, bound: u64) -> u64 {
+        ((u128::from(self.next()) * u128::from(bound)) >> 64) as u64
+    }
+}
+
 /// Convert bytes to lowercase hex string
-fn bytes_to_hex(bytes: &[u8]) -> String {
+pub(crate) fn bytes_to_hex(bytes: &[u8]) -> String {
     bytes.iter().map(|b| format!("{:02x}", b)).collect()
 }
 
@@ -1005,7 +1646,10 @@ fn hex_to_bytes(hex: &str) -> Result<Vec<u8>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::CacheCountingDevice;
     use std::io::Cursor;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
 
     // -------------------------------------------------------------------------
     // ChecksumAlgorithm tests
@@ -1015,6 +1659,7 @@ mod tests {
     fn test_algorithm_byte_length() {
         assert_eq!(ChecksumAlgorithm::Sha256.byte_length(), 32);
         assert_eq!(ChecksumAlgorithm::Sha512.byte_length(), 64);
+        assert_eq!(ChecksumAlgorithm::Sha1.byte_length(), 20);
         assert_eq!(ChecksumAlgorithm::Md5.byte_length(), 16);
         assert_eq!(ChecksumAlgorithm::Crc32.byte_length(), 4);
     }
@@ -1023,6 +1668,7 @@ mod tests {
     fn test_algorithm_hex_length() {
         assert_eq!(ChecksumAlgorithm::Sha256.hex_length(), 64);
         assert_eq!(ChecksumAlgorithm::Sha512.hex_length(), 128);
+        assert_eq!(ChecksumAlgorithm::Sha1.hex_length(), 40);
         assert_eq!(ChecksumAlgorithm::Md5.hex_length(), 32);
         assert_eq!(ChecksumAlgorithm::Crc32.hex_length(), 8);
     }
@@ -1031,14 +1677,24 @@ mod tests {
     fn test_algorithm_name() {
         assert_eq!(ChecksumAlgorithm::Sha256.name(), "SHA-256");
         assert_eq!(ChecksumAlgorithm::Sha512.name(), "SHA-512");
+        assert_eq!(ChecksumAlgorithm::Sha1.name(), "SHA-1");
         assert_eq!(ChecksumAlgorithm::Md5.name(), "MD5");
         assert_eq!(ChecksumAlgorithm::Crc32.name(), "CRC32");
     }
 
+    #[test]
+    fn test_algorithm_bsd_tag_round_trips() {
+        assert_eq!(ChecksumAlgorithm::Sha256.bsd_tag(), "SHA256");
+        for algo in ChecksumAlgorithm::all() {
+            assert_eq!(algo.bsd_tag().parse::<ChecksumAlgorithm>().unwrap(), *algo);
+        }
+    }
+
     #[test]
     fn test_algorithm_extension() {
         assert_eq!(ChecksumAlgorithm::Sha256.extension(), ".sha256");
         assert_eq!(ChecksumAlgorithm::Sha512.extension(), ".sha512");
+        assert_eq!(ChecksumAlgorithm::Sha1.extension(), ".sha1");
         assert_eq!(ChecksumAlgorithm::Md5.extension(), ".md5");
         assert_eq!(ChecksumAlgorithm::Crc32.extension(), ".crc32");
     }
@@ -1053,6 +1709,10 @@ mod tests {
             ChecksumAlgorithm::from_hex_length(128),
             Some(ChecksumAlgorithm::Sha512)
         );
+        assert_eq!(
+            ChecksumAlgorithm::from_hex_length(40),
+            Some(ChecksumAlgorithm::Sha1)
+        );
         assert_eq!(
             ChecksumAlgorithm::from_hex_length(32),
             Some(ChecksumAlgorithm::Md5)
@@ -1078,7 +1738,15 @@ mod tests {
             ChecksumAlgorithm::from_extension(".md5"),
             Some(ChecksumAlgorithm::Md5)
         );
-        assert_eq!(ChecksumAlgorithm::from_extension(".unknown"), None);
+        assert_eq!(
+            ChecksumAlgorithm::from_extension(".sha1"),
+            Some(ChecksumAlgorithm::Sha1)
+        );
+        assert_eq!(
+            ChecksumAlgorithm::from_extension("sha1sum"),
+            Some(ChecksumAlgorithm::Sha1)
+        );
+        assert_eq!(ChecksumAlgorithm::from_extension(".unknown"), None);
     }
 
     #[test]
@@ -1095,9 +1763,48 @@ mod tests {
             "md5".parse::<ChecksumAlgorithm>().unwrap(),
             ChecksumAlgorithm::Md5
         );
+        assert_eq!(
+            "sha1".parse::<ChecksumAlgorithm>().unwrap(),
+            ChecksumAlgorithm::Sha1
+        );
+        assert_eq!(
+            "SHA-1".parse::<ChecksumAlgorithm>().unwrap(),
+            ChecksumAlgorithm::Sha1
+        );
         assert!("invalid".parse::<ChecksumAlgorithm>().is_err());
     }
 
+    #[test]
+    fn test_algorithm_short_name_parses_back() {
+        for &algo in ChecksumAlgorithm::all() {
+            assert_eq!(
+                algo.short_name().parse::<ChecksumAlgorithm>().unwrap(),
+                algo
+            );
+        }
+    }
+
+    #[test]
+    fn test_algorithm_serde() {
+        assert_eq!(
+            serde_json::to_string(&ChecksumAlgorithm::Sha256).unwrap(),
+            "\"sha256\""
+        );
+        for &algo in ChecksumAlgorithm::all() {
+            let json = serde_json::to_string(&algo).unwrap();
+            assert_eq!(
+                serde_json::from_str::<ChecksumAlgorithm>(&json).unwrap(),
+                algo
+            );
+        }
+        // Display names are accepted too
+        assert_eq!(
+            serde_json::from_str::<ChecksumAlgorithm>("\"SHA-256\"").unwrap(),
+            ChecksumAlgorithm::Sha256
+        );
+        assert!(serde_json::from_str::<ChecksumAlgorithm>("\"blake3\"").is_err());
+    }
+
     #[test]
     fn test_algorithm_display() {
         assert_eq!(format!("{}", ChecksumAlgorithm::Sha256), "SHA-256");
@@ -1107,11 +1814,13 @@ mod tests {
     #[test]
     fn test_algorithm_all() {
         let all = ChecksumAlgorithm::all();
-        assert_eq!(all.len(), 4);
+        assert_eq!(all.len(), 6);
         assert!(all.contains(&ChecksumAlgorithm::Sha256));
         assert!(all.contains(&ChecksumAlgorithm::Sha512));
+        assert!(all.contains(&ChecksumAlgorithm::Sha1));
         assert!(all.contains(&ChecksumAlgorithm::Md5));
         assert!(all.contains(&ChecksumAlgorithm::Crc32));
+        assert!(all.contains(&ChecksumAlgorithm::Sha3_256));
     }
 
     // -------------------------------------------------------------------------
@@ -1154,12 +1863,75 @@ mod tests {
         assert!(!checksum.matches_hex("00000000"));
     }
 
+    #[test]
+    fn test_checksum_matches_hex_ct_agrees_with_matches_hex() {
+        let checksum = Checksum::new(ChecksumAlgorithm::Crc32, vec![0xab, 0xcd, 0xef, 0x12]);
+        for hex in [
+            "abcdef12",
+            "ABCDEF12",
+            " abcdef12\n",
+            "abcdef13",
+            "00000000",
+            "abcdef",
+            "abcdef1200",
+            "abcdef1",
+            "",
+            "+bcdef12",
+            "zzcdef12",
+            "ébcdef1",
+        ] {
+            assert_eq!(
+                checksum.matches_hex_ct(hex),
+                checksum.matches_hex(hex),
+                "{:?}",
+                hex
+            );
+        }
+    }
+
     #[test]
     fn test_checksum_display() {
         let checksum = Checksum::new(ChecksumAlgorithm::Crc32, vec![0xab, 0xcd, 0xef, 0x12]);
         assert_eq!(format!("{}", checksum), "abcdef12");
     }
 
+    #[test]
+    fn test_checksum_serde_round_trip() {
+        let checksum = Checksum::new(ChecksumAlgorithm::Crc32, vec![0xab, 0xcd, 0xef, 0x12]);
+        let json = serde_json::to_value(&checksum).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "algorithm": "crc32", "value": "abcdef12" })
+        );
+
+        let parsed: Checksum = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, checksum);
+        assert_eq!(parsed.to_hex(), checksum.to_hex());
+    }
+
+    #[test]
+    fn test_checksum_deserialize_matches_from_hex() {
+        let hex = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        let json = format!(
+            r#"{{ "algorithm": "SHA-256", "value": "{}" }}"#,
+            hex.to_uppercase()
+        );
+        let parsed: Checksum = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            parsed,
+            Checksum::from_hex(ChecksumAlgorithm::Sha256, hex).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_checksum_deserialize_rejects_bad_value() {
+        let wrong_length = r#"{ "algorithm": "sha256", "value": "abcd" }"#;
+        assert!(serde_json::from_str::<Checksum>(wrong_length).is_err());
+
+        let not_hex = r#"{ "algorithm": "crc32", "value": "zzzzzzzz" }"#;
+        assert!(serde_json::from_str::<Checksum>(not_hex).is_err());
+    }
+
     // -------------------------------------------------------------------------
     // VerificationProgress tests
     // -------------------------------------------------------------------------
@@ -1235,6 +2007,74 @@ mod tests {
         assert_eq!(result.first_mismatch_offset, Some(100));
     }
 
+    // -------------------------------------------------------------------------
+    // Phase callback tests
+    // -------------------------------------------------------------------------
+
+    fn phase_recorder() -> (Verifier, Arc<std::sync::Mutex<Vec<VerifyPhase>>>) {
+        let phases = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let phases_clone = phases.clone();
+        let verifier =
+            Verifier::new().on_phase(move |phase| phases_clone.lock().unwrap().push(phase));
+        (verifier, phases)
+    }
+
+    #[test]
+    fn test_on_phase_compare() {
+        let data = vec![0x42u8; 4096];
+        let (mut verifier, phases) = phase_recorder();
+
+        let result = verifier
+            .compare(&mut Cursor::new(&data), &mut Cursor::new(&data), 4096)
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(
+            *phases.lock().unwrap(),
+            vec![VerifyPhase::Comparing, VerifyPhase::Complete]
+        );
+    }
+
+    #[test]
+    fn test_on_phase_checksum() {
+        let (mut verifier, phases) = phase_recorder();
+
+        verifier
+            .calculate_checksum(
+                &mut Cursor::new(b"hello"),
+                ChecksumAlgorithm::Sha256,
+                Some(5),
+            )
+            .unwrap();
+
+        assert_eq!(
+            *phases.lock().unwrap(),
+            vec![VerifyPhase::Reading, VerifyPhase::Complete]
+        );
+    }
+
+    #[test]
+    fn test_on_phase_compare_and_hash_completes_once() {
+        let source = vec![0x42u8; 4096];
+        let target = vec![0u8; 4096];
+        let (mut verifier, phases) = phase_recorder();
+
+        let (result, _) = verifier
+            .compare_and_hash(
+                &mut Cursor::new(&source),
+                &mut Cursor::new(&target),
+                4096,
+                ChecksumAlgorithm::Sha256,
+            )
+            .unwrap();
+
+        assert!(!result.success);
+        assert_eq!(
+            *phases.lock().unwrap(),
+            vec![VerifyPhase::Comparing, VerifyPhase::Complete]
+        );
+    }
+
     // -------------------------------------------------------------------------
     // VerifyConfig tests
     // -------------------------------------------------------------------------
@@ -1244,16 +2084,136 @@ mod tests {
         let config = VerifyConfig::default();
         assert_eq!(config.block_size, DEFAULT_VERIFY_BLOCK_SIZE);
         assert!(config.stop_on_mismatch);
+        assert!(!config.check_target_length);
     }
 
     #[test]
     fn test_config_builder() {
         let config = VerifyConfig::new()
             .block_size(512 * 1024)
-            .stop_on_mismatch(false);
+            .stop_on_mismatch(false)
+            .check_target_length(true);
 
         assert_eq!(config.block_size, 512 * 1024);
         assert!(!config.stop_on_mismatch);
+        assert!(config.check_target_length);
+    }
+
+    #[test]
+    fn test_max_recorded_mismatches_builder() {
+        assert_eq!(
+            VerifyConfig::default().max_recorded_mismatches,
+            DEFAULT_MAX_RECORDED_MISMATCHES
+        );
+        assert_eq!(
+            VerifyConfig::new()
+                .max_recorded_mismatches(3)
+                .max_recorded_mismatches,
+            3
+        );
+    }
+
+    /// Source data and a copy with one byte flipped in each of `blocks`
+    fn corrupted_blocks(blocks: &[usize]) -> (Vec<u8>, Vec<u8>) {
+        let data: Vec<u8> = (0..MIN_VERIFY_BLOCK_SIZE * 8)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let mut target = data.clone();
+        for &block in blocks {
+            target[block * MIN_VERIFY_BLOCK_SIZE + 7] ^= 0xFF;
+        }
+        (data, target)
+    }
+
+    #[test]
+    fn test_compare_records_every_mismatch_offset() {
+        let (data, target) = corrupted_blocks(&[1, 4, 6]);
+        let mut verifier = Verifier::with_config(
+            VerifyConfig::new()
+                .block_size(MIN_VERIFY_BLOCK_SIZE)
+                .stop_on_mismatch(false),
+        );
+
+        let result = verifier
+            .compare(
+                &mut Cursor::new(data.clone()),
+                &mut Cursor::new(target),
+                data.len() as u64,
+            )
+            .unwrap();
+
+        let offsets: Vec<u64> = [1, 4, 6]
+            .iter()
+            .map(|b| (b * MIN_VERIFY_BLOCK_SIZE + 7) as u64)
+            .collect();
+        assert!(!result.success);
+        assert_eq!(result.mismatches, 3);
+        assert_eq!(result.first_mismatch_offset, Some(offsets[0]));
+        assert_eq!(result.mismatch_offsets, offsets);
+    }
+
+    #[test]
+    fn test_compare_caps_recorded_mismatches() {
+        let (data, target) = corrupted_blocks(&[0, 2, 3, 5, 7]);
+        let mut verifier = Verifier::with_config(
+            VerifyConfig::new()
+                .block_size(MIN_VERIFY_BLOCK_SIZE)
+                .stop_on_mismatch(false)
+                .max_recorded_mismatches(2),
+        );
+
+        let result = verifier
+            .compare(
+                &mut Cursor::new(data.clone()),
+                &mut Cursor::new(target),
+                data.len() as u64,
+            )
+            .unwrap();
+
+        assert_eq!(result.mismatches, 5);
+        assert_eq!(
+            result.mismatch_offsets,
+            vec![7, (2 * MIN_VERIFY_BLOCK_SIZE + 7) as u64]
+        );
+    }
+
+    #[test]
+    fn test_compare_stop_on_mismatch_records_first_offset() {
+        let (data, target) = corrupted_blocks(&[2, 5]);
+        let mut verifier =
+            Verifier::with_config(VerifyConfig::new().block_size(MIN_VERIFY_BLOCK_SIZE));
+
+        let result = verifier
+            .compare(
+                &mut Cursor::new(data.clone()),
+                &mut Cursor::new(target),
+                data.len() as u64,
+            )
+            .unwrap();
+
+        assert_eq!(result.mismatches, 1);
+        assert_eq!(
+            result.mismatch_offsets,
+            vec![(2 * MIN_VERIFY_BLOCK_SIZE + 7) as u64]
+        );
+    }
+
+    #[test]
+    fn test_config_drop_cache_default_on() {
+        assert!(VerifyConfig::default().drop_cache);
+        assert!(!VerifyConfig::new().drop_cache(false).drop_cache);
+    }
+
+    #[test]
+    fn test_drop_target_cache() {
+        let device = CacheCountingDevice::new(0);
+        Verifier::new().drop_target_cache(&device).unwrap();
+        assert_eq!(device.drops.get(), 1);
+
+        Verifier::with_config(VerifyConfig::new().drop_cache(false))
+            .drop_target_cache(&device)
+            .unwrap();
+        assert_eq!(device.drops.get(), 1);
     }
 
     #[test]
@@ -1300,6 +2260,91 @@ mod tests {
         assert!(result.first_mismatch_offset.is_some());
     }
 
+    #[test]
+    fn test_compare_target_trailing_data_ignored_by_default() {
+        let mut source = Cursor::new(vec![1u8, 2, 3, 4]);
+        let mut target = Cursor::new(vec![1u8, 2, 3, 4, 0xAA, 0xBB]);
+
+        let mut verifier = Verifier::new();
+        let result = verifier.compare(&mut source, &mut target, 4).unwrap();
+
+        assert!(result.success);
+        assert!(!result.target_has_trailing_data);
+    }
+
+    #[test]
+    fn test_compare_target_trailing_data_detected() {
+        let mut source = Cursor::new(vec![1u8, 2, 3, 4]);
+        let mut target = Cursor::new(vec![1u8, 2, 3, 4, 0xAA, 0xBB]);
+
+        let config = VerifyConfig::new().check_target_length(true);
+        let mut verifier = Verifier::with_config(config);
+        let result = verifier.compare(&mut source, &mut target, 4).unwrap();
+
+        assert!(!result.success);
+        assert!(result.target_has_trailing_data);
+        assert_eq!(result.bytes_verified, 4);
+        assert_eq!(result.mismatches, 1);
+        assert_eq!(result.first_mismatch_offset, Some(4));
+    }
+
+    /// A target that, like a device opened with `O_DIRECT`, fails unaligned reads
+    struct AlignedReadsOnly(Cursor<Vec<u8>>);
+
+    impl Read for AlignedReadsOnly {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if !self.0.position().is_multiple_of(512) || !buf.len().is_multiple_of(512) {
+                return Err(std::io::ErrorKind::InvalidInput.into());
+            }
+            self.0.read(buf)
+        }
+    }
+
+    impl Seek for AlignedReadsOnly {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.0.seek(pos)
+        }
+    }
+
+    #[test]
+    fn test_compare_target_length_check_on_aligned_target() {
+        let data = vec![7u8; 1024];
+        let config = VerifyConfig::new().check_target_length(true);
+
+        let mut source = Cursor::new(data.clone());
+        let mut target = AlignedReadsOnly(Cursor::new(data.clone()));
+        let result = Verifier::with_config(config.clone())
+            .compare(&mut source, &mut target, 1024)
+            .unwrap();
+        assert!(result.success);
+        assert!(!result.target_has_trailing_data);
+
+        let mut source = Cursor::new(data.clone());
+        let mut target = AlignedReadsOnly(Cursor::new(vec![7u8; 2048]));
+        let result = Verifier::with_config(config)
+            .compare(&mut source, &mut target, 1024)
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.target_has_trailing_data);
+        assert_eq!(result.first_mismatch_offset, Some(1024));
+    }
+
+    #[test]
+    fn test_compare_target_exact_length_passes_check() {
+        let data = vec![7u8; MIN_VERIFY_BLOCK_SIZE + 3];
+        let mut source = Cursor::new(data.clone());
+        let mut target = Cursor::new(data.clone());
+
+        let config = VerifyConfig::new().check_target_length(true);
+        let mut verifier = Verifier::with_config(config);
+        let result = verifier
+            .compare(&mut source, &mut target, data.len() as u64)
+            .unwrap();
+
+        assert!(result.success);
+        assert!(!result.target_has_trailing_data);
+    }
+
     #[test]
     fn test_compare_empty() {
         let mut source = Cursor::new(Vec::<u8>::new());
@@ -1334,6 +2379,165 @@ mod tests {
         assert!(progress_count.load(Ordering::SeqCst) >= 4);
     }
 
+    fn sampled_verifier(fraction: f64) -> Verifier {
+        Verifier::with_config(
+            VerifyConfig::new()
+                .block_size(MIN_VERIFY_BLOCK_SIZE)
+                .stop_on_mismatch(false)
+                .sample_fraction(fraction),
+        )
+    }
+
+    #[test]
+    fn test_sample_fraction_builder() {
+        assert_eq!(VerifyConfig::new().sample_fraction, None);
+        assert_eq!(
+            VerifyConfig::new().sample_fraction(0.25).sample_fraction,
+            Some(0.25)
+        );
+        assert_eq!(
+            VerifyConfig::new().sample_fraction(1.0).sample_fraction,
+            None
+        );
+        assert_eq!(
+            VerifyConfig::new().sample_fraction(0.0).sample_fraction,
+            None
+        );
+        assert_eq!(
+            VerifyConfig::new()
+                .sample_fraction(f64::NAN)
+                .sample_fraction,
+            None
+        );
+    }
+
+    #[test]
+    fn test_compare_sampled_checks_requested_fraction() {
+        let data = vec![5u8; MIN_VERIFY_BLOCK_SIZE * 100];
+        let mut source = Cursor::new(data.clone());
+        let mut target = Cursor::new(data.clone());
+
+        let result = sampled_verifier(0.1)
+            .compare(&mut source, &mut target, data.len() as u64)
+            .unwrap();
+
+        assert!(result.success);
+        assert!(result.sampled);
+        assert_eq!(result.sampled_bytes, 10 * MIN_VERIFY_BLOCK_SIZE as u64);
+        assert_eq!(result.bytes_verified, result.sampled_bytes);
+    }
+
+    #[test]
+    fn test_compare_sampled_is_reproducible() {
+        // Every block differs, so every sampled block is a mismatch
+        let size = MIN_VERIFY_BLOCK_SIZE * 50;
+        let mut source = Cursor::new(vec![0u8; size]);
+        let mut target = Cursor::new(vec![1u8; size]);
+
+        let first = sampled_verifier(0.2)
+            .compare(&mut source, &mut target, size as u64)
+            .unwrap();
+        let second = sampled_verifier(0.2)
+            .compare(&mut source, &mut target, size as u64)
+            .unwrap();
+
+        assert!(!first.success);
+        assert_eq!(first.mismatches, 10);
+        assert_eq!(first.first_mismatch_offset, second.first_mismatch_offset);
+        assert_eq!(
+            first.first_mismatch_offset.unwrap() % MIN_VERIFY_BLOCK_SIZE as u64,
+            0
+        );
+    }
+
+    #[test]
+    fn test_compare_sampled_short_last_block() {
+        let data = vec![9u8; MIN_VERIFY_BLOCK_SIZE * 3 + 10];
+        let mut source = Cursor::new(data.clone());
+        let mut target = Cursor::new(data.clone());
+
+        let result = sampled_verifier(0.99)
+            .compare(&mut source, &mut target, data.len() as u64)
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.sampled_bytes, data.len() as u64);
+    }
+
+    #[test]
+    fn test_compare_full_is_not_sampled() {
+        let mut source = Cursor::new(vec![1u8; 16]);
+        let mut target = Cursor::new(vec![1u8; 16]);
+
+        let result = Verifier::new()
+            .compare(&mut source, &mut target, 16)
+            .unwrap();
+
+        assert!(!result.sampled);
+        assert_eq!(result.sampled_bytes, 0);
+    }
+
+    #[test]
+    fn test_compare_from_offset_skips_verified_bytes() {
+        // The mismatch lies before the resume point, so it is not re-checked
+        let mut source = Cursor::new(vec![1u8, 2, 3, 4, 5, 6, 7, 8]);
+        let mut target = Cursor::new(vec![0u8, 2, 3, 4, 5, 6, 7, 8]);
+
+        let result = Verifier::new()
+            .compare_from_offset(&mut source, &mut target, 8, 4)
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.bytes_verified, 8);
+    }
+
+    #[test]
+    fn test_compare_from_offset_reports_absolute_mismatch() {
+        let mut source = Cursor::new(vec![1u8; 16]);
+        let mut target = Cursor::new(vec![1u8; 16]);
+        target.get_mut()[12] = 0;
+
+        let result = Verifier::new()
+            .compare_from_offset(&mut source, &mut target, 16, 8)
+            .unwrap();
+
+        assert!(!result.success);
+        assert_eq!(result.first_mismatch_offset, Some(12));
+    }
+
+    #[test]
+    fn test_compare_from_offset_progress_is_absolute() {
+        let data = vec![3u8; 64];
+        let mut source = Cursor::new(data.clone());
+        let mut target = Cursor::new(data);
+        let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorder = seen.clone();
+
+        let mut verifier = Verifier::new().on_progress(move |p| {
+            recorder.lock().unwrap().push(p.bytes_processed);
+        });
+        verifier
+            .compare_from_offset(&mut source, &mut target, 64, 32)
+            .unwrap();
+
+        let seen = seen.lock().unwrap();
+        assert!(seen.iter().all(|&b| b > 32));
+        assert_eq!(seen.last(), Some(&64));
+    }
+
+    #[test]
+    fn test_compare_from_offset_rejects_sampled_and_out_of_range() {
+        let mut source = Cursor::new(vec![1u8; 16]);
+        let mut target = Cursor::new(vec![1u8; 16]);
+
+        assert!(sampled_verifier(0.5)
+            .compare_from_offset(&mut source, &mut target, 16, 4)
+            .is_err());
+        assert!(Verifier::new()
+            .compare_from_offset(&mut source, &mut target, 16, 17)
+            .is_err());
+    }
+
     // -------------------------------------------------------------------------
     // Checksum calculation tests (require feature)
     // -------------------------------------------------------------------------
@@ -1384,6 +2588,35 @@ mod tests {
             assert_eq!(checksum.to_hex(), "5d41402abc4b2a76b9719d911017c592");
         }
 
+        #[test]
+        fn test_calculate_sha1() {
+            // SHA-1 of "hello"
+            let mut reader = Cursor::new(b"hello".to_vec());
+            let mut verifier = Verifier::new();
+            let checksum = verifier
+                .calculate_checksum(&mut reader, ChecksumAlgorithm::Sha1, None)
+                .unwrap();
+
+            assert_eq!(
+                checksum.to_hex(),
+                "aaf4c61ddcc5e8a2dabede0f3b482cd9aea9434d"
+            );
+        }
+
+        #[test]
+        fn test_calculate_sha3_256_empty() {
+            let mut reader = Cursor::new(Vec::new());
+            let mut verifier = Verifier::new();
+            let checksum = verifier
+                .calculate_checksum(&mut reader, ChecksumAlgorithm::Sha3_256, None)
+                .unwrap();
+
+            assert_eq!(
+                checksum.to_hex(),
+                "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+            );
+        }
+
         #[test]
         fn test_calculate_crc32() {
             // CRC32 of "hello"
@@ -1396,6 +2629,50 @@ mod tests {
             assert_eq!(checksum.to_hex(), "3610a686");
         }
 
+        #[test]
+        fn test_calculate_crc32_byte_order() {
+            for (little_endian, expected) in [(false, "3610a686"), (true, "86a61036")] {
+                let mut reader = Cursor::new(b"hello".to_vec());
+                let mut verifier =
+                    Verifier::with_config(VerifyConfig::new().crc_little_endian(little_endian));
+                let checksum = verifier
+                    .calculate_checksum(&mut reader, ChecksumAlgorithm::Crc32, None)
+                    .unwrap();
+                assert_eq!(checksum.to_hex(), expected);
+            }
+        }
+
+        #[test]
+        fn test_crc_little_endian_ignored_for_sha256() {
+            let mut reader = Cursor::new(b"hello".to_vec());
+            let mut verifier = Verifier::with_config(VerifyConfig::new().crc_little_endian(true));
+            let checksum = verifier
+                .calculate_checksum(&mut reader, ChecksumAlgorithm::Sha256, None)
+                .unwrap();
+            assert_eq!(
+                checksum.to_hex(),
+                "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+            );
+        }
+
+        #[test]
+        fn test_calculate_checksums_single_pass() {
+            let mut reader = Cursor::new(b"hello".to_vec());
+            let mut verifier = Verifier::new();
+            let checksums = verifier
+                .calculate_checksums(
+                    &mut reader,
+                    &[ChecksumAlgorithm::Md5, ChecksumAlgorithm::Crc32],
+                    None,
+                )
+                .unwrap();
+
+            assert_eq!(checksums.len(), 2);
+            assert_eq!(checksums[0].algorithm, ChecksumAlgorithm::Md5);
+            assert_eq!(checksums[0].to_hex(), "5d41402abc4b2a76b9719d911017c592");
+            assert_eq!(checksums[1].to_hex(), "3610a686");
+        }
+
         #[test]
         fn test_verify_checksum_match() {
             let mut reader = Cursor::new(b"hello".to_vec());
@@ -1451,6 +2728,108 @@ mod tests {
 
             assert!(progress_count.load(Ordering::SeqCst) >= 4);
         }
+
+        fn sha256_of(data: &[u8]) -> String {
+            Verifier::new()
+                .calculate_checksum(&mut Cursor::new(data), ChecksumAlgorithm::Sha256, None)
+                .unwrap()
+                .to_hex()
+        }
+
+        #[test]
+        fn test_compare_and_hash_matching() {
+            let data: Vec<u8> = (0..MIN_VERIFY_BLOCK_SIZE * 3 + 17)
+                .map(|i| (i % 251) as u8)
+                .collect();
+            let mut source = Cursor::new(data.clone());
+            let mut target = Cursor::new(data.clone());
+
+            let config = VerifyConfig::new().block_size(MIN_VERIFY_BLOCK_SIZE);
+            let mut verifier = Verifier::with_config(config);
+            let (result, checksum) = verifier
+                .compare_and_hash(
+                    &mut source,
+                    &mut target,
+                    data.len() as u64,
+                    ChecksumAlgorithm::Sha256,
+                )
+                .unwrap();
+
+            assert!(result.success);
+            assert_eq!(result.bytes_verified, data.len() as u64);
+            assert_eq!(checksum.algorithm, ChecksumAlgorithm::Sha256);
+            assert_eq!(checksum.to_hex(), sha256_of(&data));
+        }
+
+        #[test]
+        fn test_compare_and_hash_all_algorithms() {
+            let data = b"engraver streaming verify".to_vec();
+            for &algorithm in ChecksumAlgorithm::all() {
+                let expected = Verifier::new()
+                    .calculate_checksum(&mut Cursor::new(&data), algorithm, None)
+                    .unwrap();
+                let (result, checksum) = Verifier::new()
+                    .compare_and_hash(
+                        &mut Cursor::new(data.clone()),
+                        &mut Cursor::new(data.clone()),
+                        data.len() as u64,
+                        algorithm,
+                    )
+                    .unwrap();
+                assert!(result.success);
+                assert!(checksum.matches(&expected), "{:?}", algorithm);
+            }
+        }
+
+        #[test]
+        fn test_compare_and_hash_mismatch_still_hashes_full_source() {
+            let source_data = vec![3u8; MIN_VERIFY_BLOCK_SIZE * 4];
+            let mut target_data = source_data.clone();
+            target_data[10] = 0xFF;
+
+            let config = VerifyConfig::new()
+                .block_size(MIN_VERIFY_BLOCK_SIZE)
+                .stop_on_mismatch(true);
+            let mut verifier = Verifier::with_config(config);
+            let (result, checksum) = verifier
+                .compare_and_hash(
+                    &mut Cursor::new(source_data.clone()),
+                    &mut Cursor::new(target_data),
+                    source_data.len() as u64,
+                    ChecksumAlgorithm::Sha256,
+                )
+                .unwrap();
+
+            assert!(!result.success);
+            assert_eq!(result.first_mismatch_offset, Some(10));
+            assert_eq!(checksum.to_hex(), sha256_of(&source_data));
+        }
+
+        #[test]
+        fn test_compare_and_hash_reports_compare_progress() {
+            let data = vec![0u8; MIN_VERIFY_BLOCK_SIZE * 4];
+            let operations = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let operations_clone = Arc::clone(&operations);
+
+            let config = VerifyConfig::new().block_size(MIN_VERIFY_BLOCK_SIZE);
+            let mut verifier = Verifier::with_config(config).on_progress(move |p| {
+                operations_clone.lock().unwrap().push(p.operation);
+            });
+            verifier
+                .compare_and_hash(
+                    &mut Cursor::new(data.clone()),
+                    &mut Cursor::new(data.clone()),
+                    data.len() as u64,
+                    ChecksumAlgorithm::Crc32,
+                )
+                .unwrap();
+
+            let operations = operations.lock().unwrap();
+            assert_eq!(operations.len(), 4);
+            assert!(operations
+                .iter()
+                .all(|op| *op == VerificationOperation::Compare));
+        }
     }
 
     // -------------------------------------------------------------------------
@@ -1538,6 +2917,47 @@ mod tests {
         assert!(found.is_none());
     }
 
+    /// A sums file listing the SHA-256 of "hello" as `image.img`
+    fn hello_sums() -> tempfile::TempDir {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("SHA256SUMS"),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824  images/image.img\n",
+        )
+        .unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_checksum_from_sums() {
+        let temp_dir = hello_sums();
+        let sums = temp_dir.path().join("SHA256SUMS");
+
+        let checksum = checksum_from_sums(&sums, "image.img").unwrap();
+        assert_eq!(checksum.algorithm, ChecksumAlgorithm::Sha256);
+
+        let err = checksum_from_sums(&sums, "other.img").unwrap_err();
+        assert!(err.to_string().contains("No checksum for other.img"));
+        assert!(checksum_from_sums(&temp_dir.path().join("missing"), "image.img").is_err());
+    }
+
+    #[cfg(feature = "checksum")]
+    #[test]
+    fn test_verify_device_against_sums() {
+        let temp_dir = hello_sums();
+        let sums = temp_dir.path().join("SHA256SUMS");
+
+        // Only the first `size` bytes of the device hold the image
+        let mut device = Cursor::new(b"hello, and the rest of the drive".to_vec());
+        let result = verify_device_against_sums(&mut device, 5, &sums, "image.img").unwrap();
+        assert!(result.success);
+        assert_eq!(result.bytes_verified, 5);
+
+        let mut device = Cursor::new(b"jello".to_vec());
+        let err = verify_device_against_sums(&mut device, 5, &sums, "image.img").unwrap_err();
+        assert!(matches!(err, Error::ChecksumMismatch { .. }));
+    }
+
     // -------------------------------------------------------------------------
     // Helper function tests
     // -------------------------------------------------------------------------
@@ -1664,6 +3084,28 @@ mod tests {
         assert_eq!(detected.checksum, "d41d8cd98f00b204e9800998ecf8427e");
     }
 
+    #[test]
+    fn test_auto_detect_checksum_sha1sums() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let iso_path = temp_dir.path().join("legacy.iso");
+        let sums_path = temp_dir.path().join("SHA1SUMS");
+
+        std::fs::write(&iso_path, b"legacy content").unwrap();
+        std::fs::write(
+            &sums_path,
+            "aaf4c61ddcc5e8a2dabede0f3b482cd9aea9434d  legacy.iso
+",
+        )
+        .unwrap();
+
+        let detected = auto_detect_checksum(iso_path.to_str().unwrap()).unwrap();
+        assert_eq!(detected.algorithm, ChecksumAlgorithm::Sha1);
+        assert_eq!(
+            detected.checksum,
+            "aaf4c61ddcc5e8a2dabede0f3b482cd9aea9434d"
+        );
+    }
+
     #[test]
     fn test_auto_detect_checksum_not_found() {
         let temp_dir = tempfile::TempDir::new().unwrap();
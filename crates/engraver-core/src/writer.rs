@@ -6,13 +6,19 @@
 //! - Retry logic for transient errors
 //! - Sync/flush management
 
+use crate::cancel::{CancelToken, PauseHandle};
 use crate::error::{Error, Result};
 use crate::settings::{DEFAULT_RETRY_ATTEMPTS, DEFAULT_RETRY_DELAY_MS};
-use crate::verifier::ChecksumAlgorithm;
+use crate::source::ByteCounter;
+#[cfg(feature = "checksum")]
+use crate::verifier::Checksum;
+use crate::verifier::{ChecksumAlgorithm, VerificationResult, Verifier, VerifyConfig};
+use engraver_platform::{aligned_buffer, AlignedBuffer, RawDevice};
 use std::borrow::Cow;
 use std::io::{Read, Seek, SeekFrom, Write};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
 use std::sync::Arc;
+use std::thread::Scope;
 use std::time::{Duration, Instant};
 
 /// Trait alias for types that can be read and seeked (used for verification)
@@ -24,8 +30,10 @@ impl<T: Read + Seek> ReadSeek for T {}
 enum SourceHasher {
     Sha256(sha2::Sha256),
     Sha512(sha2::Sha512),
+    Sha1(sha1::Sha1),
     Md5(md5::Md5),
     Crc32(crc32fast::Hasher),
+    Sha3_256(sha3::Sha3_256),
 }
 
 #[cfg(feature = "checksum")]
@@ -35,8 +43,10 @@ impl SourceHasher {
         match self {
             SourceHasher::Sha256(h) => h.update(data),
             SourceHasher::Sha512(h) => h.update(data),
+            SourceHasher::Sha1(h) => h.update(data),
             SourceHasher::Md5(h) => h.update(data),
             SourceHasher::Crc32(h) => h.update(data),
+            SourceHasher::Sha3_256(h) => h.update(data),
         }
     }
 
@@ -45,10 +55,12 @@ impl SourceHasher {
         match self {
             SourceHasher::Sha256(h) => bytes_to_hex(&h.finalize()),
             SourceHasher::Sha512(h) => bytes_to_hex(&h.finalize()),
+            SourceHasher::Sha1(h) => bytes_to_hex(&h.finalize()),
             SourceHasher::Md5(h) => bytes_to_hex(&h.finalize()),
             SourceHasher::Crc32(h) => {
                 format!("{:08x}", h.finalize())
             }
+            SourceHasher::Sha3_256(h) => bytes_to_hex(&h.finalize()),
         }
     }
 }
@@ -73,14 +85,105 @@ pub const MIN_BLOCK_SIZE: usize = 4 * 1024;
 /// Maximum block size (64 MB)
 pub const MAX_BLOCK_SIZE: usize = 64 * 1024 * 1024;
 
-/// Phase of the write operation (used for progress reporting)
+/// Default number of block buffers in flight, see [`WriteConfig::buffer_count`]
+pub const DEFAULT_BUFFER_COUNT: usize = 2;
+
+/// Maximum number of block buffers in flight
+pub const MAX_BUFFER_COUNT: usize = 8;
+
+/// Default device block size a padded final block is aligned to (512 bytes)
+pub const DEFAULT_DEVICE_BLOCK_SIZE: usize = 512;
+
+/// Head written in small blocks when [`WriteConfig::small_head_bytes`] is turned on (1 MiB)
+pub const DEFAULT_SMALL_HEAD_BYTES: u64 = 1024 * 1024;
+
+/// Number of blocks before the resume offset re-checked by `verify_on_resume`
+pub const RESUME_VERIFY_BLOCKS: u64 = 4;
+
+/// Suggested number of blocks sampled by [`Writer::is_blank`]
+pub const BLANK_CHECK_SAMPLES: usize = 16;
+
+/// Default minimum time between progress callbacks (100 ms)
+pub const DEFAULT_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often the writer invokes its progress callback
+///
+/// Whatever the interval, a final callback is made once the last block has
+/// been written, so consumers always see the write reach 100%.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressInterval {
+    /// After every block; the number of callbacks depends on the block size
+    EveryBlock,
+    /// Once at least this many bytes have been written since the last callback
+    EveryBytes(u64),
+    /// At most once per duration
+    EveryDuration(Duration),
+}
+
+impl Default for ProgressInterval {
+    fn default() -> Self {
+        Self::EveryDuration(DEFAULT_PROGRESS_INTERVAL)
+    }
+}
+
+/// Decides when a progress callback is due under a [`ProgressInterval`]
+struct ProgressTicker {
+    interval: ProgressInterval,
+    last_bytes: u64,
+    last_tick: Instant,
+    pending: bool,
+}
+
+impl ProgressTicker {
+    fn new(interval: ProgressInterval) -> Self {
+        Self {
+            interval,
+            last_bytes: 0,
+            last_tick: Instant::now(),
+            pending: false,
+        }
+    }
+
+    /// Record progress up to `bytes`, returning whether a callback is due
+    fn tick(&mut self, bytes: u64) -> bool {
+        let due = match self.interval {
+            ProgressInterval::EveryBlock => true,
+            ProgressInterval::EveryBytes(n) => bytes.saturating_sub(self.last_bytes) >= n,
+            ProgressInterval::EveryDuration(d) => self.last_tick.elapsed() >= d,
+        };
+        if due {
+            self.last_bytes = bytes;
+            self.last_tick = Instant::now();
+        }
+        self.pending = !due;
+        due
+    }
+
+    /// Whether the most recent update was skipped and still needs reporting
+    fn finish(&mut self) -> bool {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+/// Phase of the write operation
+///
+/// [`WriteProgress::phase`] is only ever `Writing` or `Verifying`; the
+/// callback set with [`Writer::on_phase`] sees every transition, in order:
+/// `Starting`, `Writing`, `Syncing` (if [`WriteConfig::sync_on_complete`]),
+/// `Verifying` (for [`Writer::write_and_verify`]) and finally `Complete`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum WritePhase {
+    /// Preparing to write: seeking the target and setting up buffers
+    Starting,
     /// Writing data from source to target
     Writing,
+    /// Flushing the target after the last block
+    Syncing,
     /// Verifying written data by reading back and checksumming
     Verifying,
+    /// The write (and any verification) finished successfully
+    Complete,
 }
 
 /// Write progress information
@@ -89,10 +192,20 @@ pub struct WriteProgress {
     /// Current phase of the operation
     pub phase: WritePhase,
 
-    /// Bytes written so far
+    /// Bytes written by this call so far
+    ///
+    /// For a resumed write this excludes the bytes written by earlier runs,
+    /// so it is the amount to use for speed and session totals.
     pub bytes_written: u64,
 
-    /// Total bytes to write
+    /// Absolute position on the target device, in bytes
+    ///
+    /// Equal to the resume offset plus [`bytes_written`](Self::bytes_written).
+    /// Compare this, not `bytes_written`, against [`total_bytes`](Self::total_bytes)
+    /// to show how much of the image is on the device.
+    pub current_offset: u64,
+
+    /// Total size of the image, in bytes
     pub total_bytes: u64,
 
     /// Current write speed in bytes per second
@@ -112,6 +225,16 @@ pub struct WriteProgress {
 
     /// Number of retries that occurred
     pub retry_count: u32,
+
+    /// Compressed bytes consumed from a decompressing source
+    ///
+    /// Set while writing when the writer was given a counter with
+    /// [`Writer::track_compressed_input`], `None` otherwise.
+    pub compressed_bytes_read: Option<u64>,
+
+    /// Compressed size of the source, set alongside
+    /// [`compressed_bytes_read`](Self::compressed_bytes_read)
+    pub compressed_total: Option<u64>,
 }
 
 impl WriteProgress {
@@ -121,6 +244,7 @@ impl WriteProgress {
         Self {
             phase: WritePhase::Writing,
             bytes_written: 0,
+            current_offset: 0,
             total_bytes,
             speed_bps: 0,
             eta_seconds: None,
@@ -128,21 +252,31 @@ impl WriteProgress {
             total_blocks,
             elapsed: Duration::ZERO,
             retry_count: 0,
+            compressed_bytes_read: None,
+            compressed_total: None,
         }
     }
 
-    /// Calculate completion percentage (0.0 to 100.0)
+    /// Calculate completion percentage (0.0 to 100.0) from `current_offset`
     pub fn percentage(&self) -> f64 {
         if self.total_bytes == 0 {
             100.0
         } else {
-            (self.bytes_written as f64 / self.total_bytes as f64) * 100.0
+            (self.current_offset as f64 / self.total_bytes as f64) * 100.0
         }
     }
 
-    /// Check if write is complete
+    /// Compressed bytes consumed and the compressed total, when both are known
+    ///
+    /// For a source whose decompressed size is unknown this is the better
+    /// measure of how far the write has got.
+    pub fn compressed_position(&self) -> Option<(u64, u64)> {
+        Some((self.compressed_bytes_read?, self.compressed_total?))
+    }
+
+    /// Check if the whole image has been written
     pub fn is_complete(&self) -> bool {
-        self.bytes_written >= self.total_bytes
+        self.current_offset >= self.total_bytes
     }
 
     /// Format speed for display (e.g., "45.2 MB/s")
@@ -162,6 +296,12 @@ impl WriteProgress {
 /// Progress callback type
 pub type ProgressCallback = Box<dyn Fn(&WriteProgress) + Send + Sync>;
 
+/// Checkpoint callback type, see [`Writer::on_checkpoint`]
+pub type CheckpointCallback = Box<dyn FnMut(&WriteProgress) + Send + Sync>;
+
+/// Phase callback type, see [`Writer::on_phase`]
+pub type PhaseCallback = Box<dyn FnMut(WritePhase) + Send + Sync>;
+
 /// Configuration for write operations
 #[derive(Debug, Clone)]
 pub struct WriteConfig {
@@ -174,19 +314,52 @@ pub struct WriteConfig {
     /// Whether to sync after write completes
     pub sync_on_complete: bool,
 
+    /// Sync after every N bytes written (ignored when `sync_each_block` is set)
+    pub sync_interval_bytes: Option<u64>,
+
     /// Number of retry attempts on error
     pub retry_attempts: u32,
 
     /// Delay between retries
     pub retry_delay: Duration,
 
-    /// Whether to verify writes (read back and compare)
+    /// Whether [`Writer::write_verified`] reads the target back and checks it
     pub verify: bool,
 
     /// Checksum algorithm for parallel verification (calculated during write)
     /// When set, the checksum is computed while writing data and then verified
     /// by reading back the written data, avoiding a second read of the source.
     pub checksum_algorithm: Option<ChecksumAlgorithm>,
+
+    /// Whether to re-check the blocks before a resume offset against the source
+    pub verify_on_resume: bool,
+
+    /// How often the progress callback is invoked
+    pub progress_interval: ProgressInterval,
+
+    /// Blocks that may be skipped after exhausting retries before the write fails
+    pub max_bad_blocks: u32,
+
+    /// Whether to zero-pad a short final block up to `device_block_size`
+    pub pad_final_block: bool,
+
+    /// Device block size that a padded final block is aligned to
+    pub device_block_size: usize,
+
+    /// Number of block buffers a [`Prefetch`] source is read ahead into (1 disables read-ahead)
+    pub buffer_count: usize,
+
+    /// Whether to drop the device's cached pages before reading it back
+    pub drop_cache: bool,
+
+    /// Target offset the write must not reach, protecting everything after it
+    pub write_limit: Option<u64>,
+
+    /// Leading bytes of the target written one device block at a time (0 = off)
+    pub small_head_bytes: u64,
+
+    /// Whether each block is read back and compared right after it is written
+    pub verify_each_block: bool,
 }
 
 impl Default for WriteConfig {
@@ -195,10 +368,21 @@ impl Default for WriteConfig {
             block_size: DEFAULT_BLOCK_SIZE,
             sync_each_block: false,
             sync_on_complete: true,
+            sync_interval_bytes: None,
             retry_attempts: DEFAULT_RETRY_ATTEMPTS,
             retry_delay: Duration::from_millis(DEFAULT_RETRY_DELAY_MS),
             verify: false,
             checksum_algorithm: None,
+            verify_on_resume: false,
+            progress_interval: ProgressInterval::default(),
+            max_bad_blocks: 0,
+            pad_final_block: false,
+            device_block_size: DEFAULT_DEVICE_BLOCK_SIZE,
+            buffer_count: DEFAULT_BUFFER_COUNT,
+            drop_cache: true,
+            write_limit: None,
+            small_head_bytes: 0,
+            verify_each_block: false,
         }
     }
 }
@@ -227,6 +411,19 @@ impl WriteConfig {
         self
     }
 
+    /// Set periodic sync interval in bytes
+    ///
+    /// Periodic syncs bound how much dirty data the OS buffers for the target,
+    /// which keeps progress honest on slow media. `sync_each_block` takes
+    /// precedence, and `sync_on_complete` still applies at the end. A
+    /// [`RawDevice`] target written through [`Writer::write_to_device`] or
+    /// [`Writer::write_and_verify_device`] is synced with
+    /// [`RawDevice::sync`]; other targets are only flushed.
+    pub fn sync_interval_bytes(mut self, interval: Option<u64>) -> Self {
+        self.sync_interval_bytes = interval.filter(|&n| n > 0);
+        self
+    }
+
     /// Set retry attempts
     pub fn retry_attempts(mut self, attempts: u32) -> Self {
         self.retry_attempts = attempts;
@@ -254,6 +451,119 @@ impl WriteConfig {
         self.checksum_algorithm = algorithm;
         self
     }
+
+    /// Set whether [`Writer::write_and_verify_device`] drops cached pages first
+    ///
+    /// On by default, for the reasons given at [`VerifyConfig::drop_cache`].
+    pub fn drop_cache(mut self, drop: bool) -> Self {
+        self.drop_cache = drop;
+        self
+    }
+
+    /// Set the target offset no block may be written at or past
+    ///
+    /// A block that would cross `limit` fails the write with
+    /// [`Error::WriteLimitExceeded`] before any of it is written, so the
+    /// bytes from `limit` on are left as they were. This only protects a
+    /// byte range; partitions there are not moved or resized. A padded final
+    /// block counts with its padding. Defaults to no limit.
+    pub fn write_limit(mut self, limit: Option<u64>) -> Self {
+        self.write_limit = limit;
+        self
+    }
+
+    /// Set how many leading bytes are written in device-block-sized writes
+    ///
+    /// Some hybrid ISOs fail to boot on old BIOSes when a single large write
+    /// spans the MBR and GPT structures at the start of the image. Writing the
+    /// first `bytes` (typically 1 MiB) as [`WriteConfig::device_block_size`]
+    /// writes, 512 bytes by default, lands them the way `dd bs=512` would; the
+    /// rest uses the configured block size. The bytes written are the same
+    /// either way. Defaults to 0, which writes every block whole.
+    pub fn small_head_bytes(mut self, bytes: u64) -> Self {
+        self.small_head_bytes = bytes;
+        self
+    }
+
+    /// Set whether each block is read back and compared as soon as it is written
+    ///
+    /// After writing a block the target is flushed, the block is read back
+    /// from the same offset and compared with what was written. A mismatch
+    /// rewrites the block, up to [`WriteConfig::retry_attempts`] times, and
+    /// then fails with [`Error::VerificationFailed`] at the first differing
+    /// byte, so a bad sector is pinpointed exactly (or skipped, within
+    /// [`WriteConfig::max_bad_blocks`]). Much slower than verifying afterwards,
+    /// and only meaningful with direct I/O: a buffered read-back is served
+    /// from the page cache rather than the device. Defaults to off.
+    ///
+    /// The target must be readable, so this needs
+    /// [`Writer::write_with_read_back`] or one of the verifying entry points;
+    /// [`Writer::write`] and the other write-only ones return
+    /// [`Error::InvalidConfig`] when it is set.
+    pub fn verify_each_block(mut self, verify: bool) -> Self {
+        self.verify_each_block = verify;
+        self
+    }
+
+    /// Set whether to verify the tail of a previous write before resuming
+    ///
+    /// See [`Writer::verify_resume_offset`].
+    pub fn verify_on_resume(mut self, verify: bool) -> Self {
+        self.verify_on_resume = verify;
+        self
+    }
+
+    /// Set how often the progress callback is invoked
+    ///
+    /// Defaults to [`ProgressInterval::EveryDuration`] of
+    /// [`DEFAULT_PROGRESS_INTERVAL`], which keeps callback overhead low on fast
+    /// devices. Use [`ProgressInterval::EveryBlock`] or
+    /// [`ProgressInterval::EveryBytes`] for deterministic callback counts.
+    pub fn progress_interval(mut self, interval: ProgressInterval) -> Self {
+        self.progress_interval = interval;
+        self
+    }
+
+    /// Set how many unwritable blocks may be skipped
+    ///
+    /// A block that still fails after all retries is normally fatal. With a
+    /// non-zero limit, up to `count` such blocks are left unwritten and their
+    /// offsets recorded in [`WriteResult::bad_blocks`]; the next one fails the
+    /// write. Defaults to 0.
+    pub fn max_bad_blocks(mut self, count: u32) -> Self {
+        self.max_bad_blocks = count;
+        self
+    }
+
+    /// Set whether to zero-pad a short final block to the device block size
+    ///
+    /// Devices opened with direct I/O reject writes that are not a multiple
+    /// of their block size, which the last block of an unaligned image
+    /// usually is. When enabled, that block is extended with zeros up to the
+    /// next [`device_block_size`](Self::device_block_size) boundary. Off by
+    /// default; enable it when the target uses direct I/O.
+    pub fn pad_final_block(mut self, pad: bool) -> Self {
+        self.pad_final_block = pad;
+        self
+    }
+
+    /// Set the device block size used by [`pad_final_block`](Self::pad_final_block)
+    pub fn device_block_size(mut self, size: usize) -> Self {
+        self.device_block_size = size;
+        self
+    }
+
+    /// Set how many block buffers are in flight (clamped to 1..=[`MAX_BUFFER_COUNT`])
+    ///
+    /// Above 1, a [`Prefetch`] source is read on its own thread into the next
+    /// buffers while the current one is written, hiding read latency behind
+    /// write latency. Other sources are always read in turn with the writes.
+    /// 2 is double buffering; 1 reads and writes in turn on one thread. Memory
+    /// use is `count * block_size`. Defaults to [`DEFAULT_BUFFER_COUNT`].
+    pub fn buffer_count(mut self, count: usize) -> Self {
+        self.buffer_count = count.clamp(1, MAX_BUFFER_COUNT);
+        self
+    }
 }
 
 /// Result of a write operation
@@ -262,7 +572,7 @@ pub struct WriteResult {
     /// Total bytes written
     pub bytes_written: u64,
 
-    /// Total time elapsed
+    /// Total time elapsed, excluding any time spent paused
     pub elapsed: Duration,
 
     /// Average speed in bytes per second
@@ -282,6 +592,20 @@ pub struct WriteResult {
 
     /// Time spent on verification (if performed)
     pub verification_elapsed: Option<Duration>,
+
+    /// Full verification outcome from [`Writer::write_verified`] or [`Writer::write_and_verify`]
+    ///
+    /// A checksum verify can't locate a difference, so when it fails
+    /// `mismatches` is 1 and `first_mismatch_offset` is `None`.
+    pub verification: Option<VerificationResult>,
+
+    /// Offsets of blocks skipped after exhausting retries, see [`WriteConfig::max_bad_blocks`]
+    pub bad_blocks: Vec<u64>,
+
+    /// Zero bytes written after the source, see [`WriteConfig::pad_final_block`]
+    ///
+    /// Not included in `bytes_written`.
+    pub padded_bytes: u64,
 }
 
 impl WriteResult {
@@ -295,7 +619,11 @@ impl WriteResult {
 pub struct Writer {
     config: WriteConfig,
     progress_callback: Option<ProgressCallback>,
-    cancel_flag: Arc<AtomicBool>,
+    checkpoint_callback: Option<(Duration, CheckpointCallback)>,
+    phase_callback: Option<PhaseCallback>,
+    compressed_input: Option<(ByteCounter, u64)>,
+    cancel_flag: CancelToken,
+    pause_flag: PauseHandle,
 }
 
 impl Writer {
@@ -304,7 +632,11 @@ impl Writer {
         Self {
             config: WriteConfig::default(),
             progress_callback: None,
-            cancel_flag: Arc::new(AtomicBool::new(false)),
+            checkpoint_callback: None,
+            phase_callback: None,
+            compressed_input: None,
+            cancel_flag: CancelToken::new(),
+            pause_flag: PauseHandle::new(),
         }
     }
 
@@ -313,7 +645,11 @@ impl Writer {
         Self {
             config,
             progress_callback: None,
-            cancel_flag: Arc::new(AtomicBool::new(false)),
+            checkpoint_callback: None,
+            phase_callback: None,
+            compressed_input: None,
+            cancel_flag: CancelToken::new(),
+            pause_flag: PauseHandle::new(),
         }
     }
 
@@ -326,16 +662,63 @@ impl Writer {
         self
     }
 
+    /// Set a checkpoint callback, called at most once per `interval`
+    ///
+    /// The target is flushed immediately before each call, so the reported
+    /// `current_offset` is safe to resume from after a crash or power loss.
+    /// The callback runs on the write thread and should hand the work off
+    /// (e.g. to [`crate::resume::CheckpointAutosave`]) rather than block.
+    pub fn on_checkpoint<F>(mut self, interval: Duration, callback: F) -> Self
+    where
+        F: FnMut(&WriteProgress) + Send + Sync + 'static,
+    {
+        self.checkpoint_callback = Some((interval, Box::new(callback)));
+        self
+    }
+
+    /// Set a callback for each [`WritePhase`] the write moves through
+    ///
+    /// Frontends can use this to label the operation ("Syncing...") without
+    /// inferring it from byte progress. A failed or cancelled write stops
+    /// without reaching [`WritePhase::Complete`].
+    pub fn on_phase<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(WritePhase) + Send + Sync + 'static,
+    {
+        self.phase_callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Report progress through the compressed input of a decompressing source
+    ///
+    /// `counter` comes from [`Source::compressed_counter`](crate::Source::compressed_counter)
+    /// and `total` is the compressed size. While writing, every
+    /// [`WriteProgress`] then carries both, and its ETA is estimated from
+    /// the compressed bytes left rather than from a decompressed size that
+    /// is not known.
+    pub fn track_compressed_input(mut self, counter: ByteCounter, total: u64) -> Self {
+        self.compressed_input = Some((counter, total));
+        self
+    }
+
     /// Get a handle to cancel the write operation
-    pub fn cancel_handle(&self) -> Arc<AtomicBool> {
-        Arc::clone(&self.cancel_flag)
+    pub fn cancel_handle(&self) -> CancelToken {
+        self.cancel_flag.clone()
+    }
+
+    /// Get a handle to pause and resume the write operation
+    ///
+    /// A paused write stops between blocks until resumed or cancelled. Time
+    /// spent paused is left out of the reported elapsed time and speed.
+    pub fn pause_handle(&self) -> PauseHandle {
+        self.pause_flag.clone()
     }
 
     /// Write from source to target
     ///
     /// # Arguments
     /// * `source` - Readable source (file, network stream, etc.)
-    /// * `target` - Writable target (device, file, etc.)
+    /// * `target` - Target device or file
     /// * `source_size` - Total size of source in bytes
     ///
     /// # Returns
@@ -343,7 +726,7 @@ impl Writer {
     /// * `Err(Error)` - Write failed
     pub fn write<R, W>(&mut self, source: R, target: W, source_size: u64) -> Result<WriteResult>
     where
-        R: Read,
+        R: BlockSource,
         W: Write + Seek,
     {
         self.write_from_offset(source, target, source_size, 0)
@@ -354,15 +737,17 @@ impl Writer {
     /// This is useful for resuming interrupted writes. The source must already
     /// be seeked to the correct position before calling this method.
     ///
+    /// Wrap a `Send` source in [`Prefetch`] to read it ahead on another thread.
+    ///
     /// # Arguments
     /// * `source` - Readable source (already seeked to start_offset)
-    /// * `target` - Writable target (device, file, etc.)
+    /// * `target` - Target device or file
     /// * `source_size` - Total size of source in bytes
     /// * `start_offset` - Byte offset to start writing from
     ///
     /// # Returns
     /// * `Ok(WriteResult)` - Write completed successfully
-    /// * `Err(Error)` - Write failed
+    /// * `Err(Error)` - Write failed, or [`WriteConfig::verify_each_block`] is set
     pub fn write_from_offset<R, W>(
         &mut self,
         source: R,
@@ -371,10 +756,232 @@ impl Writer {
         start_offset: u64,
     ) -> Result<WriteResult>
     where
-        R: Read,
+        R: BlockSource,
+        W: Write + Seek,
+    {
+        let result = self.write_internal(
+            source,
+            &mut target,
+            source_size,
+            start_offset,
+            TargetOps::write_only(),
+        )?;
+        self.enter_phase(WritePhase::Complete);
+        Ok(result)
+    }
+
+    /// [`write_from_offset`](Self::write_from_offset) to a target that can be read back
+    ///
+    /// This is the entry point for [`WriteConfig::verify_each_block`], which
+    /// reads each block back from `target` after writing it. Without that
+    /// option it behaves exactly like `write_from_offset`.
+    pub fn write_with_read_back<R, W>(
+        &mut self,
+        source: R,
+        mut target: W,
+        source_size: u64,
+        start_offset: u64,
+    ) -> Result<WriteResult>
+    where
+        R: BlockSource,
+        W: Read + Write + Seek,
+    {
+        let result = self.write_internal(
+            source,
+            &mut target,
+            source_size,
+            start_offset,
+            TargetOps::readable(),
+        )?;
+        self.enter_phase(WritePhase::Complete);
+        Ok(result)
+    }
+
+    /// [`write_with_read_back`](Self::write_with_read_back) to a [`RawDevice`]
+    ///
+    /// Up to [`WriteConfig::buffer_count`] blocks are handed to
+    /// [`RawDevice::write_batch`] at a time, so a platform with asynchronous
+    /// I/O (`io_uring` on Linux, with the `io-uring` feature) keeps them in
+    /// flight together. A batch that fails is written again block by block,
+    /// with the usual retries and bad-block handling; blocks in
+    /// [`WriteConfig::small_head_bytes`] or near [`WriteConfig::write_limit`]
+    /// are always written one at a time.
+    pub fn write_to_device<R, D>(
+        &mut self,
+        source: R,
+        target: &mut D,
+        source_size: u64,
+        start_offset: u64,
+    ) -> Result<WriteResult>
+    where
+        R: BlockSource,
+        D: RawDevice + ?Sized,
+    {
+        let result = self.write_internal(
+            source,
+            target,
+            source_size,
+            start_offset,
+            TargetOps::device(),
+        )?;
+        self.enter_phase(WritePhase::Complete);
+        Ok(result)
+    }
+
+    /// Check the blocks just before a resume offset and rewind past torn writes
+    ///
+    /// Re-reads up to [`RESUME_VERIFY_BLOCKS`] blocks before `start_offset`
+    /// from both `source` and `target` and compares them. If they all match,
+    /// `start_offset` is returned unchanged; otherwise the offset of the start
+    /// of the first mismatching block is returned, so the write resumes from
+    /// the last verified block boundary. This guards against a partially
+    /// written final block after a power cut.
+    ///
+    /// Returns `start_offset` without reading anything if
+    /// [`WriteConfig::verify_on_resume`] is disabled. Both streams are left at
+    /// unspecified positions; seek them before writing.
+    pub fn verify_resume_offset<R, T>(
+        &self,
+        source: &mut R,
+        target: &mut T,
+        start_offset: u64,
+    ) -> Result<u64>
+    where
+        R: Read + Seek + ?Sized,
+        T: Read + Seek + ?Sized,
+    {
+        if !self.config.verify_on_resume || start_offset == 0 {
+            return Ok(start_offset);
+        }
+
+        let block_size = self.config.block_size as u64;
+        let window_start =
+            (start_offset / block_size).saturating_sub(RESUME_VERIFY_BLOCKS) * block_size;
+
+        let mut verifier = Verifier::with_config(
+            VerifyConfig::new()
+                .block_size(self.config.block_size)
+                .stop_on_mismatch(true),
+        );
+        let result = verifier.compare(
+            &mut OffsetView::new(source, window_start)?,
+            &mut OffsetView::new(target, window_start)?,
+            start_offset - window_start,
+        )?;
+
+        if result.success {
+            return Ok(start_offset);
+        }
+
+        let mismatch = result.first_mismatch_offset.unwrap_or(0);
+        Ok(window_start + (mismatch / block_size) * block_size)
+    }
+
+    /// Check whether a device looks blank before it is overwritten
+    ///
+    /// Reads `sample_points` blocks of [`WriteConfig::block_size`], spread
+    /// evenly from the first block to the last, and returns `true` if each one
+    /// is entirely `0x00` or entirely `0xFF` (the erased state of most flash
+    /// media). This is a cheap heuristic, not a full scan: data between the
+    /// samples is not seen. An empty device or `sample_points == 0` counts as
+    /// blank. The device is left at an unspecified position.
+    pub fn is_blank<T>(&self, device: &mut T, sample_points: usize) -> Result<bool>
+    where
+        T: Read + Seek + ?Sized,
+    {
+        let size = device.seek(SeekFrom::End(0))?;
+        if size == 0 || sample_points == 0 {
+            return Ok(true);
+        }
+
+        let block_size = (self.config.block_size as u64).min(size);
+        let last_block = (size - block_size) / block_size;
+        let mut buffer = vec![0u8; block_size as usize];
+
+        for i in 0..sample_points as u64 {
+            let block = match sample_points {
+                1 => 0,
+                n => last_block * i / (n as u64 - 1),
+            };
+            device.seek(SeekFrom::Start(block * block_size))?;
+            device.read_exact(&mut buffer)?;
+
+            let fill = buffer[0];
+            if (fill != 0x00 && fill != 0xFF) || buffer.iter().any(|&b| b != fill) {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Write from source to target, hashing each block as it is written
+    ///
+    /// The returned [`Checksum`] is the hash of the source data exactly as it
+    /// reached the target, so a later read-back of the device can be compared
+    /// against it without reading the source a second time. This is what makes
+    /// verification of remote and compressed sources cheap.
+    ///
+    /// `algorithm` is used for this write only; the configured
+    /// `checksum_algorithm` is left unchanged.
+    ///
+    /// # Arguments
+    /// * `source` - Readable source
+    /// * `target` - Target device or file
+    /// * `source_size` - Total size of source in bytes
+    /// * `algorithm` - Hash algorithm to apply to the written data
+    ///
+    /// # Returns
+    /// * `Ok((WriteResult, Checksum))` - Write result and the source hash
+    /// * `Err(Error)` - Write failed, or [`WriteConfig::verify_each_block`] is set
+    #[cfg(feature = "checksum")]
+    pub fn write_hashing<R, W>(
+        &mut self,
+        source: R,
+        target: W,
+        source_size: u64,
+        algorithm: ChecksumAlgorithm,
+    ) -> Result<(WriteResult, Checksum)>
+    where
+        R: BlockSource,
         W: Write + Seek,
     {
-        self.write_internal(source, &mut target, source_size, start_offset)
+        let mut target = target;
+        let written = self.write_hashing_inner(
+            source,
+            &mut target,
+            source_size,
+            algorithm,
+            TargetOps::write_only(),
+        )?;
+        self.enter_phase(WritePhase::Complete);
+        Ok(written)
+    }
+
+    /// [`write_hashing`](Self::write_hashing) without reporting [`WritePhase::Complete`]
+    #[cfg(feature = "checksum")]
+    fn write_hashing_inner<R, W>(
+        &mut self,
+        source: R,
+        target: &mut W,
+        source_size: u64,
+        algorithm: ChecksumAlgorithm,
+        ops: TargetOps<W>,
+    ) -> Result<(WriteResult, Checksum)>
+    where
+        R: BlockSource,
+        W: Write + Seek + ?Sized,
+    {
+        let configured = self.config.checksum_algorithm.replace(algorithm);
+        let result = self.write_internal(source, target, source_size, 0, ops);
+        self.config.checksum_algorithm = configured;
+
+        let result = result?;
+        let hex = result.source_checksum.as_deref().ok_or_else(|| {
+            Error::InvalidConfig("source checksum was not calculated".to_string())
+        })?;
+        let checksum = Checksum::from_hex(algorithm, hex)?;
+        Ok((result, checksum))
     }
 
     /// Write from source to target with parallel verification
@@ -397,41 +1004,190 @@ impl Writer {
     pub fn write_and_verify<R, W>(
         &mut self,
         source: R,
-        mut target: W,
+        target: W,
         source_size: u64,
     ) -> Result<WriteResult>
     where
-        R: Read,
+        R: BlockSource,
         W: Read + Write + Seek,
     {
-        // First, write the data (this calculates source checksum if algorithm is set)
-        let mut result = self.write_internal(source, &mut target, source_size, 0)?;
+        let mut target = target;
+        self.write_and_verify_with(
+            source,
+            &mut target,
+            source_size,
+            TargetOps::readable(),
+            |_| Ok(()),
+        )
+    }
+
+    /// [`write_and_verify`](Self::write_and_verify) to a device, reading back past the page cache
+    ///
+    /// Before the read-back the device's cached pages are dropped (see
+    /// [`RawDevice::drop_cache`]) unless [`WriteConfig::drop_cache`] is off,
+    /// so the checksum is of what the device stored rather than of memory.
+    #[cfg(feature = "checksum")]
+    pub fn write_and_verify_device<R, D>(
+        &mut self,
+        source: R,
+        target: &mut D,
+        source_size: u64,
+    ) -> Result<WriteResult>
+    where
+        R: BlockSource,
+        D: RawDevice + ?Sized,
+    {
+        let drop_cache = self.config.drop_cache;
+        self.write_and_verify_with(source, target, source_size, TargetOps::device(), |target| {
+            if drop_cache {
+                target.drop_cache()?;
+            }
+            Ok(())
+        })
+    }
 
-        // If we have a source checksum, verify by reading back the target
-        if let Some(ref source_checksum) = result.source_checksum {
-            let verify_start = Instant::now();
+    /// Write, hash, run `before_read_back` on the target, then read it back
+    #[cfg(feature = "checksum")]
+    fn write_and_verify_with<R, W, F>(
+        &mut self,
+        source: R,
+        target: &mut W,
+        source_size: u64,
+        ops: TargetOps<W>,
+        before_read_back: F,
+    ) -> Result<WriteResult>
+    where
+        R: BlockSource,
+        W: Read + Write + Seek + ?Sized,
+        F: FnOnce(&W) -> Result<()>,
+    {
+        // Without an algorithm there is nothing to verify against
+        let Some(algorithm) = self.config.checksum_algorithm else {
+            let result = self.write_internal(source, target, source_size, 0, ops)?;
+            self.enter_phase(WritePhase::Complete);
+            return Ok(result);
+        };
 
-            // Seek back to start
-            target.seek(SeekFrom::Start(0))?;
+        let (mut result, source_checksum) =
+            self.write_hashing_inner(source, target, source_size, algorithm, ops)?;
+        before_read_back(target)?;
 
-            // Calculate target checksum
-            let algorithm = self.config.checksum_algorithm.ok_or_else(|| {
-                Error::InvalidConfig("checksum_algorithm must be set for verification".to_string())
-            })?;
+        // Verify by reading back the target
+        self.enter_phase(WritePhase::Verifying);
+        let verify_start = Instant::now();
+        target.seek(SeekFrom::Start(0))?;
+        let target_checksum = self.calculate_checksum(target, source_size, algorithm)?;
+
+        let verified = target_checksum == source_checksum.to_hex();
+        let verify_elapsed = verify_start.elapsed();
+        result.verified = Some(verified);
+        result.target_checksum = Some(target_checksum);
+        result.verification_elapsed = Some(verify_elapsed);
+        result.verification = Some(if verified {
+            VerificationResult::success(source_size, verify_elapsed)
+        } else {
+            VerificationResult::failure(source_size, 1, None, verify_elapsed)
+        });
 
-            let target_checksum = self.calculate_checksum(&mut target, source_size, algorithm)?;
+        self.enter_phase(WritePhase::Complete);
+        Ok(result)
+    }
 
-            result.verified = Some(&target_checksum == source_checksum);
-            result.target_checksum = Some(target_checksum);
-            result.verification_elapsed = Some(verify_start.elapsed());
+    /// Write, then check the target if [`WriteConfig::verify`] is on
+    ///
+    /// With verification off this is just [`write`](Self::write). With it on,
+    /// the outcome is recorded in [`WriteResult::verified`] and
+    /// [`WriteResult::verification`]:
+    /// - with [`WriteConfig::checksum_algorithm`] set, the source is hashed
+    ///   while writing and compared with a hash of the read-back target, as in
+    ///   [`write_and_verify`](Self::write_and_verify);
+    /// - otherwise source and target are compared byte for byte by a
+    ///   [`Verifier`], reported as [`WritePhase::Verifying`] progress.
+    ///
+    /// A mismatch is not an error: the result has `verified == Some(false)`.
+    /// For devices, drop the page cache first (see
+    /// [`RawDevice::drop_cache`](engraver_platform::RawDevice::drop_cache))
+    /// or use [`write_and_verify_device`](Self::write_and_verify_device).
+    pub fn write_verified<R, W>(
+        &mut self,
+        mut source: R,
+        mut target: W,
+        source_size: u64,
+    ) -> Result<WriteResult>
+    where
+        R: Read + Seek,
+        W: Read + Write + Seek,
+    {
+        if !self.config.verify {
+            return self.write_with_read_back(source, target, source_size, 0);
+        }
+        #[cfg(feature = "checksum")]
+        if self.config.checksum_algorithm.is_some() {
+            return self.write_and_verify(source, target, source_size);
         }
 
+        let mut result = self.write_internal(
+            &mut source,
+            &mut target,
+            source_size,
+            0,
+            TargetOps::readable(),
+        )?;
+        self.enter_phase(WritePhase::Verifying);
+        let verification = self.compare_written(&mut source, &mut target, source_size)?;
+        result.verified = Some(verification.success);
+        result.verification_elapsed = Some(verification.elapsed);
+        result.verification = Some(verification);
+        self.enter_phase(WritePhase::Complete);
         Ok(result)
     }
 
+    /// Byte-compare the first `size` bytes of `source` and `target`
+    ///
+    /// The [`Verifier`] shares this writer's cancel token, and its progress is
+    /// passed to the progress callback as [`WritePhase::Verifying`].
+    fn compare_written<R, T>(
+        &mut self,
+        source: &mut R,
+        target: &mut T,
+        size: u64,
+    ) -> Result<VerificationResult>
+    where
+        R: Read + Seek,
+        T: Read + Seek,
+    {
+        let mut verifier = Verifier::with_config(
+            VerifyConfig::new()
+                .block_size(self.config.block_size)
+                .drop_cache(false),
+        )
+        .with_cancel_token(self.cancel_flag.clone());
+
+        let callback = self.progress_callback.take().map(Arc::new);
+        if let Some(callback) = callback.clone() {
+            let block_size = self.config.block_size;
+            verifier = verifier.on_progress(move |p| {
+                let mut progress = WriteProgress::new(size, block_size);
+                progress.phase = WritePhase::Verifying;
+                progress.bytes_written = p.bytes_processed;
+                progress.current_offset = p.bytes_processed;
+                progress.current_block = p.bytes_processed.div_ceil(block_size as u64);
+                progress.speed_bps = p.speed_bps;
+                progress.eta_seconds = p.eta_seconds;
+                progress.elapsed = p.elapsed;
+                (*callback)(&progress);
+            });
+        }
+
+        let result = verifier.compare(source, target, size);
+        drop(verifier);
+        self.progress_callback = callback.and_then(|callback| Arc::try_unwrap(callback).ok());
+        result
+    }
+
     /// Calculate checksum of a reader (used for verification)
     #[cfg(feature = "checksum")]
-    fn calculate_checksum<R: Read>(
+    fn calculate_checksum<R: Read + ?Sized>(
         &self,
         reader: &mut R,
         size: u64,
@@ -442,8 +1198,10 @@ impl Writer {
         let mut hasher = match algorithm {
             ChecksumAlgorithm::Sha256 => SourceHasher::Sha256(sha2::Sha256::new()),
             ChecksumAlgorithm::Sha512 => SourceHasher::Sha512(sha2::Sha512::new()),
+            ChecksumAlgorithm::Sha1 => SourceHasher::Sha1(sha1::Sha1::new()),
             ChecksumAlgorithm::Md5 => SourceHasher::Md5(md5::Md5::new()),
             ChecksumAlgorithm::Crc32 => SourceHasher::Crc32(crc32fast::Hasher::new()),
+            ChecksumAlgorithm::Sha3_256 => SourceHasher::Sha3_256(sha3::Sha3_256::new()),
         };
 
         let block_size = self.config.block_size;
@@ -454,13 +1212,12 @@ impl Writer {
         let mut progress = WriteProgress::new(size, block_size);
         progress.phase = WritePhase::Verifying;
         let mut speed_tracker = SpeedTracker::new();
+        let mut ticker = ProgressTicker::new(self.config.progress_interval);
         let verify_start = Instant::now();
 
         while bytes_read_total < size {
             // Check for cancellation
-            if self.cancel_flag.load(Ordering::SeqCst) {
-                return Err(Error::Cancelled);
-            }
+            self.cancel_flag.check()?;
 
             let to_read = block_size.min((size - bytes_read_total) as usize);
             let bytes_read = read_exact_or_eof(reader, &mut buffer[..to_read])?;
@@ -474,17 +1231,22 @@ impl Writer {
 
             // Update and report verification progress
             progress.bytes_written = bytes_read_total;
+            progress.current_offset = bytes_read_total;
             progress.current_block = bytes_read_total.div_ceil(block_size as u64);
             progress.elapsed = verify_start.elapsed();
             speed_tracker.update(bytes_read_total);
             progress.speed_bps = speed_tracker.current_speed();
             progress.eta_seconds = calculate_eta(bytes_read_total, size, progress.speed_bps);
 
-            if let Some(ref callback) = self.progress_callback {
-                callback(&progress);
+            if ticker.tick(bytes_read_total) {
+                self.report_progress(&progress);
             }
         }
 
+        if ticker.finish() {
+            self.report_progress(&progress);
+        }
+
         Ok(hasher.finalize_hex())
     }
 
@@ -496,25 +1258,30 @@ impl Writer {
         target: &mut W,
         source_size: u64,
         start_offset: u64,
+        ops: TargetOps<W>,
     ) -> Result<WriteResult>
     where
-        R: Read,
-        W: Write + Seek,
+        R: BlockSource,
+        W: Write + Seek + ?Sized,
     {
         use sha2::Digest;
 
+        let read_block = self.block_reader_for_verify(ops.read_block)?;
+
         // Reset cancel flag
-        self.cancel_flag.store(false, Ordering::SeqCst);
+        self.cancel_flag.reset();
+        self.enter_phase(WritePhase::Starting);
 
         let start_time = Instant::now();
         let block_size = self.config.block_size;
 
-        let mut buffer = vec![0u8; block_size];
         let mut progress = WriteProgress::new(source_size, block_size);
         let mut speed_tracker = SpeedTracker::new();
+        let mut ticker = ProgressTicker::new(self.config.progress_interval);
+        let mut paused = Duration::ZERO;
 
-        // Initialize progress with already-written bytes for resumed writes
-        progress.bytes_written = start_offset;
+        // Resumed writes start at the offset; `bytes_written` counts this session only
+        progress.current_offset = start_offset;
         progress.current_block = start_offset / block_size as u64;
 
         // Create hasher if checksum algorithm is set
@@ -522,76 +1289,160 @@ impl Writer {
             self.config.checksum_algorithm.map(|alg| match alg {
                 ChecksumAlgorithm::Sha256 => SourceHasher::Sha256(sha2::Sha256::new()),
                 ChecksumAlgorithm::Sha512 => SourceHasher::Sha512(sha2::Sha512::new()),
+                ChecksumAlgorithm::Sha1 => SourceHasher::Sha1(sha1::Sha1::new()),
                 ChecksumAlgorithm::Md5 => SourceHasher::Md5(md5::Md5::new()),
                 ChecksumAlgorithm::Crc32 => SourceHasher::Crc32(crc32fast::Hasher::new()),
+                ChecksumAlgorithm::Sha3_256 => SourceHasher::Sha3_256(sha3::Sha3_256::new()),
             });
 
         // Seek target to the starting offset
         target.seek(SeekFrom::Start(start_offset))?;
 
-        loop {
-            // Check for cancellation
-            if self.cancel_flag.load(Ordering::SeqCst) {
-                return Err(Error::Cancelled);
-            }
+        let mut bytes_since_sync: u64 = 0;
+        let mut last_checkpoint = Instant::now();
+        let mut bad_blocks = Vec::new();
+        let mut padded_bytes: u64 = 0;
+        let mut read_back = Vec::new();
+
+        self.enter_phase(WritePhase::Writing);
+        let cancel = self.cancel_flag.clone();
+        std::thread::scope(|scope| -> Result<()> {
+            let mut blocks = source.blocks(
+                scope,
+                block_size,
+                self.config.device_block_size,
+                self.config.buffer_count,
+                cancel,
+            );
 
-            // Read a block from source
-            let bytes_read = read_exact_or_eof(&mut source, &mut buffer)?;
+            // Several blocks at once when the target can batch them; never
+            // more than `buffer_count`, which a prefetching reader has filled
+            let batch_len = match ops.write_batch {
+                Some(_) => self.config.buffer_count,
+                None => 1,
+            };
+            let mut batch: Vec<(Block, usize)> = Vec::with_capacity(batch_len);
+            let mut at_end = false;
+
+            while !at_end {
+                // Check for cancellation, and hold here while paused
+                let waited = self.pause_flag.wait(&self.cancel_flag)?;
+                if !waited.is_zero() {
+                    paused += waited;
+                    speed_tracker.reset();
+                }
 
-            if bytes_read == 0 {
-                break; // EOF
-            }
+                // Take the next blocks read from the source
+                while batch.len() < batch_len {
+                    let Some((mut buffer, bytes_read)) = blocks.next()? else {
+                        at_end = true;
+                        break;
+                    };
 
-            // Update hasher with source data
-            if let Some(ref mut h) = hasher {
-                h.update(&buffer[..bytes_read]);
-            }
+                    // Update hasher with source data
+                    if let Some(ref mut h) = hasher {
+                        h.update(&buffer[..bytes_read]);
+                    }
 
-            // Write the block with retry logic
-            let write_result = self.write_block_with_retry(
-                target,
-                &buffer[..bytes_read],
-                progress.bytes_written,
-                &mut progress.retry_count,
-            );
+                    let write_len = self.padded_len(&mut buffer, bytes_read);
+                    batch.push(((buffer, bytes_read), write_len));
+                }
+                let batched =
+                    self.write_batched(target, ops.write_batch, &batch, progress.current_offset)?;
+
+                for ((buffer, bytes_read), write_len) in batch.drain(..) {
+                    // Write the block with retry logic
+                    let write_result = if batched {
+                        Ok(write_len)
+                    } else {
+                        self.write_block(
+                            target,
+                            &buffer[..write_len],
+                            progress.current_offset,
+                            &mut progress.retry_count,
+                        )
+                    };
+                    let write_result = write_result.and_then(|written| {
+                        if let Some(read_block) = read_block {
+                            self.verify_block(
+                                target,
+                                &buffer[..write_len],
+                                progress.current_offset,
+                                &mut progress.retry_count,
+                                read_block,
+                                &mut read_back,
+                            )?;
+                        }
+                        Ok(written)
+                    });
 
-            match write_result {
-                Ok(bytes_written) => {
-                    progress.bytes_written += bytes_written as u64;
+                    match write_result {
+                        Ok(bytes_written) => {
+                            progress.bytes_written += bytes_written.min(bytes_read) as u64;
+                            padded_bytes += (write_len - bytes_read) as u64;
+                        }
+                        Err(e) => {
+                            self.skip_bad_block(
+                                target,
+                                progress.current_offset,
+                                bytes_read,
+                                e,
+                                &mut bad_blocks,
+                            )?;
+                        }
+                    }
+                    progress.current_offset += bytes_read as u64;
                     progress.current_block += 1;
-                }
-                Err(e) => {
-                    return Err(e);
-                }
-            }
 
-            // Sync if configured
-            if self.config.sync_each_block {
-                target.flush()?;
+                    // Sync if configured
+                    if self.config.sync_each_block {
+                        sync_target(target, ops.sync)?;
+                    } else if let Some(interval) = self.config.sync_interval_bytes {
+                        bytes_since_sync += bytes_read as u64;
+                        if bytes_since_sync >= interval {
+                            sync_target(target, ops.sync)?;
+                            bytes_since_sync = 0;
+                        }
+                    }
+
+                    // Update progress
+                    progress.elapsed = start_time.elapsed().saturating_sub(paused);
+                    speed_tracker.update(progress.bytes_written);
+                    progress.speed_bps = speed_tracker.current_speed();
+                    progress.eta_seconds = calculate_eta(
+                        progress.current_offset,
+                        progress.total_bytes,
+                        progress.speed_bps,
+                    );
+
+                    // Call progress callback
+                    if ticker.tick(progress.bytes_written) {
+                        self.report_progress(&progress);
+                    }
+
+                    if self.checkpoint_if_due(target, &progress, &mut last_checkpoint)? {
+                        bytes_since_sync = 0;
+                    }
+
+                    blocks.recycle(buffer);
+                }
             }
 
-            // Update progress
-            progress.elapsed = start_time.elapsed();
-            speed_tracker.update(progress.bytes_written);
-            progress.speed_bps = speed_tracker.current_speed();
-            progress.eta_seconds = calculate_eta(
-                progress.bytes_written,
-                progress.total_bytes,
-                progress.speed_bps,
-            );
+            Ok(())
+        })?;
 
-            // Call progress callback
-            if let Some(ref callback) = self.progress_callback {
-                callback(&progress);
-            }
+        // Always report the final position, even if its tick was skipped
+        if ticker.finish() {
+            self.report_progress(&progress);
         }
 
         // Final sync
         if self.config.sync_on_complete {
+            self.enter_phase(WritePhase::Syncing);
             target.flush()?;
         }
 
-        let write_elapsed = start_time.elapsed();
+        let write_elapsed = start_time.elapsed().saturating_sub(paused);
         let average_speed = if write_elapsed.as_secs() > 0 {
             progress.bytes_written / write_elapsed.as_secs()
         } else {
@@ -602,7 +1453,7 @@ impl Writer {
         let source_checksum = hasher.map(|h| h.finalize_hex());
 
         Ok(WriteResult {
-            bytes_written: progress.bytes_written,
+            bytes_written: progress.current_offset,
             elapsed: write_elapsed,
             average_speed,
             retry_count: progress.retry_count,
@@ -610,6 +1461,9 @@ impl Writer {
             source_checksum,
             target_checksum: None,
             verification_elapsed: None,
+            verification: None,
+            bad_blocks,
+            padded_bytes,
         })
     }
 
@@ -621,86 +1475,173 @@ impl Writer {
         target: &mut W,
         source_size: u64,
         start_offset: u64,
+        ops: TargetOps<W>,
     ) -> Result<WriteResult>
     where
-        R: Read,
-        W: Write + Seek,
+        R: BlockSource,
+        W: Write + Seek + ?Sized,
     {
+        let read_block = self.block_reader_for_verify(ops.read_block)?;
+
         // Reset cancel flag
-        self.cancel_flag.store(false, Ordering::SeqCst);
+        self.cancel_flag.reset();
+        self.enter_phase(WritePhase::Starting);
 
         let start_time = Instant::now();
         let block_size = self.config.block_size;
 
-        let mut buffer = vec![0u8; block_size];
         let mut progress = WriteProgress::new(source_size, block_size);
         let mut speed_tracker = SpeedTracker::new();
+        let mut ticker = ProgressTicker::new(self.config.progress_interval);
+        let mut paused = Duration::ZERO;
 
-        // Initialize progress with already-written bytes for resumed writes
-        progress.bytes_written = start_offset;
+        // Resumed writes start at the offset; `bytes_written` counts this session only
+        progress.current_offset = start_offset;
         progress.current_block = start_offset / block_size as u64;
 
         // Seek target to the starting offset
         target.seek(SeekFrom::Start(start_offset))?;
 
-        loop {
-            // Check for cancellation
-            if self.cancel_flag.load(Ordering::SeqCst) {
-                return Err(Error::Cancelled);
-            }
+        let mut bytes_since_sync: u64 = 0;
+        let mut last_checkpoint = Instant::now();
+        let mut bad_blocks = Vec::new();
+        let mut padded_bytes: u64 = 0;
+        let mut read_back = Vec::new();
+
+        self.enter_phase(WritePhase::Writing);
+        let cancel = self.cancel_flag.clone();
+        std::thread::scope(|scope| -> Result<()> {
+            let mut blocks = source.blocks(
+                scope,
+                block_size,
+                self.config.device_block_size,
+                self.config.buffer_count,
+                cancel,
+            );
 
-            // Read a block from source
-            let bytes_read = read_exact_or_eof(&mut source, &mut buffer)?;
+            // Several blocks at once when the target can batch them; never
+            // more than `buffer_count`, which a prefetching reader has filled
+            let batch_len = match ops.write_batch {
+                Some(_) => self.config.buffer_count,
+                None => 1,
+            };
+            let mut batch: Vec<(Block, usize)> = Vec::with_capacity(batch_len);
+            let mut at_end = false;
+
+            while !at_end {
+                // Check for cancellation, and hold here while paused
+                let waited = self.pause_flag.wait(&self.cancel_flag)?;
+                if !waited.is_zero() {
+                    paused += waited;
+                    speed_tracker.reset();
+                }
 
-            if bytes_read == 0 {
-                break; // EOF
-            }
+                // Take the next blocks read from the source
+                while batch.len() < batch_len {
+                    let Some((mut buffer, bytes_read)) = blocks.next()? else {
+                        at_end = true;
+                        break;
+                    };
 
-            // Write the block with retry logic
-            let write_result = self.write_block_with_retry(
-                target,
-                &buffer[..bytes_read],
-                progress.bytes_written,
-                &mut progress.retry_count,
-            );
+                    let write_len = self.padded_len(&mut buffer, bytes_read);
+                    batch.push(((buffer, bytes_read), write_len));
+                }
+                let batched =
+                    self.write_batched(target, ops.write_batch, &batch, progress.current_offset)?;
+
+                for ((buffer, bytes_read), write_len) in batch.drain(..) {
+                    // Write the block with retry logic
+                    let write_result = if batched {
+                        Ok(write_len)
+                    } else {
+                        self.write_block(
+                            target,
+                            &buffer[..write_len],
+                            progress.current_offset,
+                            &mut progress.retry_count,
+                        )
+                    };
+                    let write_result = write_result.and_then(|written| {
+                        if let Some(read_block) = read_block {
+                            self.verify_block(
+                                target,
+                                &buffer[..write_len],
+                                progress.current_offset,
+                                &mut progress.retry_count,
+                                read_block,
+                                &mut read_back,
+                            )?;
+                        }
+                        Ok(written)
+                    });
 
-            match write_result {
-                Ok(bytes_written) => {
-                    progress.bytes_written += bytes_written as u64;
+                    match write_result {
+                        Ok(bytes_written) => {
+                            progress.bytes_written += bytes_written.min(bytes_read) as u64;
+                            padded_bytes += (write_len - bytes_read) as u64;
+                        }
+                        Err(e) => {
+                            self.skip_bad_block(
+                                target,
+                                progress.current_offset,
+                                bytes_read,
+                                e,
+                                &mut bad_blocks,
+                            )?;
+                        }
+                    }
+                    progress.current_offset += bytes_read as u64;
                     progress.current_block += 1;
-                }
-                Err(e) => {
-                    return Err(e);
-                }
-            }
 
-            // Sync if configured
-            if self.config.sync_each_block {
-                target.flush()?;
+                    // Sync if configured
+                    if self.config.sync_each_block {
+                        sync_target(target, ops.sync)?;
+                    } else if let Some(interval) = self.config.sync_interval_bytes {
+                        bytes_since_sync += bytes_read as u64;
+                        if bytes_since_sync >= interval {
+                            sync_target(target, ops.sync)?;
+                            bytes_since_sync = 0;
+                        }
+                    }
+
+                    // Update progress
+                    progress.elapsed = start_time.elapsed().saturating_sub(paused);
+                    speed_tracker.update(progress.bytes_written);
+                    progress.speed_bps = speed_tracker.current_speed();
+                    progress.eta_seconds = calculate_eta(
+                        progress.current_offset,
+                        progress.total_bytes,
+                        progress.speed_bps,
+                    );
+
+                    // Call progress callback
+                    if ticker.tick(progress.bytes_written) {
+                        self.report_progress(&progress);
+                    }
+
+                    if self.checkpoint_if_due(target, &progress, &mut last_checkpoint)? {
+                        bytes_since_sync = 0;
+                    }
+
+                    blocks.recycle(buffer);
+                }
             }
 
-            // Update progress
-            progress.elapsed = start_time.elapsed();
-            speed_tracker.update(progress.bytes_written);
-            progress.speed_bps = speed_tracker.current_speed();
-            progress.eta_seconds = calculate_eta(
-                progress.bytes_written,
-                progress.total_bytes,
-                progress.speed_bps,
-            );
+            Ok(())
+        })?;
 
-            // Call progress callback
-            if let Some(ref callback) = self.progress_callback {
-                callback(&progress);
-            }
+        // Always report the final position, even if its tick was skipped
+        if ticker.finish() {
+            self.report_progress(&progress);
         }
 
         // Final sync
         if self.config.sync_on_complete {
+            self.enter_phase(WritePhase::Syncing);
             target.flush()?;
         }
 
-        let elapsed = start_time.elapsed();
+        let elapsed = start_time.elapsed().saturating_sub(paused);
         let average_speed = if elapsed.as_secs() > 0 {
             progress.bytes_written / elapsed.as_secs()
         } else {
@@ -708,7 +1649,7 @@ impl Writer {
         };
 
         Ok(WriteResult {
-            bytes_written: progress.bytes_written,
+            bytes_written: progress.current_offset,
             elapsed,
             average_speed,
             retry_count: progress.retry_count,
@@ -716,6 +1657,250 @@ impl Writer {
             source_checksum: None,
             target_checksum: None,
             verification_elapsed: None,
+            verification: None,
+            bad_blocks,
+            padded_bytes,
+        })
+    }
+
+    /// Invoke the phase callback, if one is set
+    fn enter_phase(&mut self, phase: WritePhase) {
+        if let Some(ref mut callback) = self.phase_callback {
+            callback(phase);
+        }
+    }
+
+    /// Invoke the progress callback, if one is set
+    fn report_progress(&self, progress: &WriteProgress) {
+        let Some(ref callback) = self.progress_callback else {
+            return;
+        };
+        match self.compressed_input {
+            Some((ref counter, total)) if progress.phase == WritePhase::Writing => {
+                let mut progress = progress.clone();
+                let read = counter.get();
+                progress.compressed_bytes_read = Some(read);
+                progress.compressed_total = Some(total);
+                progress.eta_seconds = compressed_eta(read, total, progress.elapsed);
+                callback(&progress);
+            }
+            _ => callback(progress),
+        }
+    }
+
+    /// Flush the target and run the checkpoint callback if its interval has elapsed
+    ///
+    /// Returns whether the target was flushed.
+    fn checkpoint_if_due<W: Write + ?Sized>(
+        &mut self,
+        target: &mut W,
+        progress: &WriteProgress,
+        last_checkpoint: &mut Instant,
+    ) -> Result<bool> {
+        let Some((interval, ref mut callback)) = self.checkpoint_callback else {
+            return Ok(false);
+        };
+        if last_checkpoint.elapsed() < interval {
+            return Ok(false);
+        }
+
+        target.flush()?;
+        callback(progress);
+        *last_checkpoint = Instant::now();
+        Ok(true)
+    }
+
+    /// Length to write for a block of `len` bytes, zero-padding `buffer` if needed
+    ///
+    /// Only a block that does not end on a device block boundary is padded,
+    /// which [`read_exact_or_eof`] limits to the last one.
+    ///
+    /// Block buffers have room up to the next device block boundary (see
+    /// [`block_buffer`]), so padding never reallocates.
+    fn padded_len(&self, buffer: &mut [u8], len: usize) -> usize {
+        let align = self.config.device_block_size;
+        if !self.config.pad_final_block || align == 0 || len.is_multiple_of(align) {
+            return len;
+        }
+
+        let padded = len.div_ceil(align) * align;
+        buffer[len..padded].fill(0);
+        padded
+    }
+
+    /// Leave a block that failed all retries unwritten, if the bad-block limit allows
+    ///
+    /// Records `offset` and positions the target after the block. Returns
+    /// `error` once more than [`WriteConfig::max_bad_blocks`] blocks have failed.
+    fn skip_bad_block<W: Seek + ?Sized>(
+        &self,
+        target: &mut W,
+        offset: u64,
+        len: usize,
+        error: Error,
+        bad_blocks: &mut Vec<u64>,
+    ) -> Result<()> {
+        // Skipping past a full device would only fill the rest with holes,
+        // and skipping past the write limit would write into what it protects
+        if matches!(
+            error,
+            Error::DeviceFull { .. } | Error::WriteLimitExceeded { .. }
+        ) || bad_blocks.len() >= self.config.max_bad_blocks as usize
+        {
+            return Err(error);
+        }
+
+        tracing::warn!("Skipping bad block at offset {}: {}", offset, error);
+        bad_blocks.push(offset);
+        target.seek(SeekFrom::Start(offset + len as u64))?;
+        Ok(())
+    }
+
+    /// Write `batch`, starting at `offset`, with one [`RawDevice::write_batch`] call
+    ///
+    /// Returns whether every block was written that way; if not, they still
+    /// need writing one at a time and the target is positioned at `offset`.
+    /// Without a `write_batch`, a single block, or a batch reaching into
+    /// [`WriteConfig::small_head_bytes`] or past [`WriteConfig::write_limit`],
+    /// nothing is attempted.
+    fn write_batched<W: Seek + ?Sized>(
+        &self,
+        target: &mut W,
+        write_batch: Option<WriteBatch<W>>,
+        batch: &[(Block, usize)],
+        offset: u64,
+    ) -> Result<bool> {
+        let Some(write_batch) = write_batch else {
+            return Ok(false);
+        };
+        let mut chunks = Vec::with_capacity(batch.len());
+        let mut chunk_offset = offset;
+        for ((buffer, bytes_read), write_len) in batch {
+            chunks.push((chunk_offset, &buffer[..*write_len]));
+            chunk_offset += *bytes_read as u64;
+        }
+        let Some(&(last_offset, last)) = chunks.last() else {
+            return Ok(false);
+        };
+        let end = last_offset + last.len() as u64;
+        if chunks.len() < 2
+            || offset < self.config.small_head_bytes
+            || self.config.write_limit.is_some_and(|limit| end > limit)
+        {
+            return Ok(false);
+        }
+
+        let expected = chunks.iter().map(|(_, data)| data.len()).sum::<usize>();
+        match write_batch(target, &chunks) {
+            Ok(written) if written == expected => {
+                target.seek(SeekFrom::Start(end))?;
+                return Ok(true);
+            }
+            Ok(written) => tracing::debug!(
+                "Batched write at offset {} wrote {} of {} bytes; writing blocks one at a time",
+                offset,
+                written,
+                expected
+            ),
+            Err(e) => tracing::debug!(
+                "Batched write at offset {} failed: {}; writing blocks one at a time",
+                offset,
+                e
+            ),
+        }
+        target.seek(SeekFrom::Start(offset))?;
+        Ok(false)
+    }
+
+    /// Write a block, splitting the part before [`WriteConfig::small_head_bytes`]
+    /// into device-block-sized writes
+    fn write_block<W: Write + Seek + ?Sized>(
+        &self,
+        target: &mut W,
+        data: &[u8],
+        offset: u64,
+        retry_count: &mut u32,
+    ) -> Result<usize> {
+        let head_end = self.config.small_head_bytes;
+        if offset >= head_end {
+            return self.write_block_with_retry(target, data, offset, retry_count);
+        }
+
+        let head_len = (head_end - offset).min(data.len() as u64) as usize;
+        let (head, rest) = data.split_at(head_len);
+        let mut written = 0;
+        for chunk in head.chunks(self.config.device_block_size.max(1)) {
+            written +=
+                self.write_block_with_retry(target, chunk, offset + written as u64, retry_count)?;
+        }
+        if !rest.is_empty() {
+            written +=
+                self.write_block_with_retry(target, rest, offset + written as u64, retry_count)?;
+        }
+        Ok(written)
+    }
+
+    /// The read-back to run after each block, if [`WriteConfig::verify_each_block`] is on
+    ///
+    /// Fails if the option is on but the entry point was given a target it
+    /// cannot read from.
+    fn block_reader_for_verify<W: ?Sized>(
+        &self,
+        read_block: Option<ReadBlock<W>>,
+    ) -> Result<Option<ReadBlock<W>>> {
+        match read_block {
+            _ if !self.config.verify_each_block => Ok(None),
+            Some(read_block) => Ok(Some(read_block)),
+            None => Err(Error::InvalidConfig(
+                "verify_each_block needs a target that can be read back; \
+                 use Writer::write_with_read_back"
+                    .to_string(),
+            )),
+        }
+    }
+
+    /// Read a block back after writing it and rewrite it until it matches
+    ///
+    /// See [`WriteConfig::verify_each_block`]. Each rewrite counts as a retry.
+    /// On success the target is positioned after the block.
+    fn verify_block<W: Write + Seek + ?Sized>(
+        &self,
+        target: &mut W,
+        data: &[u8],
+        offset: u64,
+        retry_count: &mut u32,
+        read_block: ReadBlock<W>,
+        read_back: &mut Vec<u8>,
+    ) -> Result<()> {
+        read_back.resize(data.len(), 0);
+        let mut mismatch = 0;
+
+        for attempt in 0..=self.config.retry_attempts {
+            if attempt > 0 {
+                *retry_count += 1;
+                std::thread::sleep(self.config.retry_delay);
+                target.seek(SeekFrom::Start(offset))?;
+                self.write_block(target, data, offset, retry_count)?;
+            }
+
+            target.flush()?;
+            target.seek(SeekFrom::Start(offset))?;
+            read_block(target, read_back)?;
+            match data.iter().zip(read_back.iter()).position(|(a, b)| a != b) {
+                None => return Ok(()),
+                Some(position) => mismatch = position,
+            }
+            tracing::warn!(
+                "Block at offset {} read back differently at byte {}",
+                offset,
+                offset + mismatch as u64
+            );
+        }
+
+        Err(Error::VerificationFailed {
+            offset: offset + mismatch as u64,
+            expected: format!("0x{:02x}", data[mismatch]),
+            actual: format!("0x{:02x}", read_back[mismatch]),
         })
     }
 
@@ -724,13 +1909,24 @@ impl Writer {
     /// Each retry waits `base_delay * 2^(attempt-1)`, capped at `8 * base_delay`.
     /// A small deterministic jitter derived from the offset is added to avoid
     /// thundering-herd effects in multi-device scenarios.
-    fn write_block_with_retry<W: Write + Seek>(
+    ///
+    /// Running out of space (`ENOSPC`, [`std::io::ErrorKind::StorageFull`]) is
+    /// not retried; it returns [`Error::DeviceFull`] with `offset` as the
+    /// amount written. A block crossing [`WriteConfig::write_limit`] is not
+    /// written at all.
+    fn write_block_with_retry<W: Write + Seek + ?Sized>(
         &self,
         target: &mut W,
         data: &[u8],
         offset: u64,
         retry_count: &mut u32,
     ) -> Result<usize> {
+        if let Some(limit) = self.config.write_limit {
+            if offset + data.len() as u64 > limit {
+                return Err(Error::WriteLimitExceeded { limit });
+            }
+        }
+
         let mut last_error = None;
         let base_delay = self.config.retry_delay;
         let max_delay = base_delay.saturating_mul(8);
@@ -756,12 +1952,24 @@ impl Writer {
             match target.write(data) {
                 Ok(n) if n == data.len() => return Ok(n),
                 Ok(n) => {
+                    // A file system hands out its last free bytes as a short
+                    // write; only writing the rest reports ENOSPC
+                    if is_storage_full(target.write(&data[n..])) {
+                        return Err(Error::DeviceFull {
+                            bytes_written: offset,
+                        });
+                    }
                     // Partial write - this is an error for block devices
                     last_error = Some(Error::PartialWrite {
                         expected: data.len(),
                         actual: n,
                     });
                 }
+                Err(e) if e.kind() == std::io::ErrorKind::StorageFull => {
+                    return Err(Error::DeviceFull {
+                        bytes_written: offset,
+                    });
+                }
                 Err(e) => {
                     last_error = Some(Error::Io(e));
                 }
@@ -778,6 +1986,160 @@ impl Default for Writer {
     }
 }
 
+/// Whether a write failed because the target is out of space
+fn is_storage_full(result: std::io::Result<usize>) -> bool {
+    result.is_err_and(|e| e.kind() == std::io::ErrorKind::StorageFull)
+}
+
+/// A view of a stream that starts at `base`, so offset 0 maps to `base`
+///
+/// Lets [`Verifier::compare`], which always starts at offset 0, compare a
+/// range in the middle of a device.
+struct OffsetView<'a, S: ?Sized> {
+    inner: &'a mut S,
+    base: u64,
+}
+
+impl<'a, S: Seek + ?Sized> OffsetView<'a, S> {
+    fn new(inner: &'a mut S, base: u64) -> std::io::Result<Self> {
+        inner.seek(SeekFrom::Start(base))?;
+        Ok(Self { inner, base })
+    }
+}
+
+impl<S: Read + ?Sized> Read for OffsetView<'_, S> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<S: Seek + ?Sized> Seek for OffsetView<'_, S> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(offset) => SeekFrom::Start(self.base + offset),
+            other => other,
+        };
+        Ok(self.inner.seek(pos)?.saturating_sub(self.base))
+    }
+}
+
+/// Reads a block back from a target, for [`WriteConfig::verify_each_block`]
+type ReadBlock<W> = fn(&mut W, &mut [u8]) -> std::io::Result<()>;
+
+/// Writes `(offset, data)` chunks together, see [`RawDevice::write_batch`]
+type WriteBatch<W> = fn(&mut W, &[(u64, &[u8])]) -> engraver_platform::Result<usize>;
+
+/// Syncs pending writes to the device, see [`RawDevice::sync`]
+type SyncTarget<W> = fn(&W) -> engraver_platform::Result<()>;
+
+/// What the write loop can do with a target beyond [`Write`] and [`Seek`]
+///
+/// Each entry point supplies what its target's bounds allow, so the others
+/// keep a plain `Write + Seek` bound.
+struct TargetOps<W: ?Sized> {
+    read_block: Option<ReadBlock<W>>,
+    write_batch: Option<WriteBatch<W>>,
+    sync: Option<SyncTarget<W>>,
+}
+
+impl<W: ?Sized> TargetOps<W> {
+    /// Plain writes only
+    fn write_only() -> Self {
+        Self {
+            read_block: None,
+            write_batch: None,
+            sync: None,
+        }
+    }
+}
+
+impl<W: Read + ?Sized> TargetOps<W> {
+    /// Plain writes, with blocks read back through [`Read`]
+    fn readable() -> Self {
+        Self {
+            read_block: Some(|target, buffer| target.read_exact(buffer)),
+            write_batch: None,
+            sync: None,
+        }
+    }
+}
+
+impl<D: RawDevice + ?Sized> TargetOps<D> {
+    /// Batched device writes and syncs, with blocks read back through [`Read`]
+    fn device() -> Self {
+        Self {
+            read_block: Some(|device, buffer| device.read_exact(buffer)),
+            write_batch: Some(|device, chunks| device.write_batch(chunks)),
+            sync: Some(|device| device.sync()),
+        }
+    }
+}
+
+/// Flush `target`, then sync it to the device when it has a `sync`
+fn sync_target<W: Write + ?Sized>(target: &mut W, sync: Option<SyncTarget<W>>) -> Result<()> {
+    target.flush()?;
+    if let Some(sync) = sync {
+        sync(target)?;
+    }
+    Ok(())
+}
+
+/// A forward-only stream (stdout, a pipe) usable as a [`Writer`] target
+///
+/// The writer seeks its target to the start offset before writing; that
+/// seek succeeds because it lands on the current position. Any other seek
+/// fails with [`std::io::ErrorKind::Unsupported`], so resume offsets and
+/// bad-block skipping report an error instead of producing a corrupt stream.
+///
+/// Writes always take the whole block, as a short write to a pipe would
+/// otherwise be reported as a [`Error::PartialWrite`].
+pub struct StreamTarget<W> {
+    inner: W,
+    position: u64,
+}
+
+impl<W: Write> StreamTarget<W> {
+    /// Wrap `inner`, treating its current position as offset 0
+    pub fn new(inner: W) -> Self {
+        Self { inner, position: 0 }
+    }
+
+    /// Bytes written through this target so far
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Unwrap the underlying stream
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for StreamTarget<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.write_all(buf)?;
+        self.position += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W> Seek for StreamTarget<W> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        match pos {
+            SeekFrom::Start(offset) if offset == self.position => Ok(offset),
+            SeekFrom::Current(0) => Ok(self.position),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "cannot seek a stream target",
+            )),
+        }
+    }
+}
+
 /// Speed tracking with smoothing
 struct SpeedTracker {
     samples: Vec<(Instant, u64)>,
@@ -802,6 +2164,11 @@ impl SpeedTracker {
         self.samples.push((now, bytes_written));
     }
 
+    /// Forget samples taken before a pause, so it does not drag the speed down
+    fn reset(&mut self) {
+        self.samples.clear();
+    }
+
     fn current_speed(&self) -> u64 {
         if self.samples.len() < 2 {
             return 0;
@@ -821,8 +2188,225 @@ impl SpeedTracker {
     }
 }
 
+/// A block read from the source: the buffer and how many bytes of it are data
+type Block = (AlignedBuffer, usize);
+
+/// Buffer for one block, aligned to `device_block_size` for direct I/O
+///
+/// It is rounded up to a whole number of device blocks so that
+/// [`Writer::padded_len`] can pad a short final block in place.
+fn block_buffer(block_size: usize, device_block_size: usize) -> AlignedBuffer {
+    let len = match device_block_size {
+        0 => block_size,
+        align => block_size.div_ceil(align) * align,
+    };
+    aligned_buffer(len, device_block_size)
+}
+
+/// A source the [`Writer`] reads blocks from
+///
+/// Every [`Read`] type is one, and is read on the writing thread between
+/// writes. Wrap a `Send` source in [`Prefetch`] to have it read ahead on a
+/// thread of its own instead.
+pub trait BlockSource: sealed::Sealed {}
+
+impl<R: Read> BlockSource for R {}
+impl<R: Read + Send> BlockSource for Prefetch<R> {}
+
+// `BlockReader` is private, so no type outside this module can be a source
+#[allow(private_interfaces)]
+mod sealed {
+    use super::{BlockReader, CancelToken, Prefetch, Read, Scope};
+
+    pub trait Sealed {
+        /// Start reading blocks for one write, see [`BlockReader::prefetch`]
+        fn blocks<'a, 'scope>(
+            &'a mut self,
+            scope: &'scope Scope<'scope, '_>,
+            block_size: usize,
+            device_block_size: usize,
+            buffer_count: usize,
+            cancel: CancelToken,
+        ) -> BlockReader<'a>
+        where
+            'a: 'scope;
+    }
+
+    impl<R: Read> Sealed for R {
+        fn blocks<'a, 'scope>(
+            &'a mut self,
+            _scope: &'scope Scope<'scope, '_>,
+            block_size: usize,
+            device_block_size: usize,
+            _buffer_count: usize,
+            _cancel: CancelToken,
+        ) -> BlockReader<'a>
+        where
+            'a: 'scope,
+        {
+            BlockReader::inline(self, block_size, device_block_size)
+        }
+    }
+
+    impl<R: Read + Send> Sealed for Prefetch<R> {
+        fn blocks<'a, 'scope>(
+            &'a mut self,
+            scope: &'scope Scope<'scope, '_>,
+            block_size: usize,
+            device_block_size: usize,
+            buffer_count: usize,
+            cancel: CancelToken,
+        ) -> BlockReader<'a>
+        where
+            'a: 'scope,
+        {
+            BlockReader::prefetch(
+                scope,
+                &mut self.inner,
+                block_size,
+                device_block_size,
+                buffer_count,
+                cancel,
+            )
+        }
+    }
+}
+
+/// A source that the [`Writer`] reads ahead of the write on its own thread
+///
+/// Up to [`WriteConfig::buffer_count`] blocks are read while earlier ones are
+/// being written, so a slow source (network, decompression) is read while
+/// the device is busy. The source must be `Send` for the reader thread; a
+/// source passed without this wrapper is read on the writing thread.
+pub struct Prefetch<R> {
+    inner: R,
+}
+
+impl<R: Read + Send> Prefetch<R> {
+    /// Wrap `inner` so the writer reads it ahead
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Unwrap the underlying source
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+/// Source blocks for the write loop
+///
+/// Inline, each block is read on the write thread when it is needed.
+/// Prefetching, a reader thread fills up to [`WriteConfig::buffer_count`]
+/// buffers ahead of the writer, so a slow source is read while the previous
+/// block is being written. Buffers are handed back with
+/// [`recycle`](Self::recycle), which bounds memory use to `buffer_count`
+/// blocks.
+enum BlockReader<'a> {
+    /// Read on demand, reusing recycled buffers
+    Inline {
+        source: &'a mut dyn Read,
+        spare: Vec<AlignedBuffer>,
+        block_size: usize,
+        device_block_size: usize,
+    },
+    /// Read ahead on a scoped thread
+    Prefetch {
+        filled: Receiver<Result<Block>>,
+        empty: SyncSender<AlignedBuffer>,
+    },
+}
+
+impl<'a> BlockReader<'a> {
+    /// Read `source` on the calling thread, in blocks of `block_size`
+    fn inline(source: &'a mut dyn Read, block_size: usize, device_block_size: usize) -> Self {
+        BlockReader::Inline {
+            source,
+            spare: Vec::new(),
+            block_size,
+            device_block_size,
+        }
+    }
+
+    /// Start reading `source` in blocks of `block_size`, into buffers aligned to `device_block_size`
+    ///
+    /// The reader thread belongs to `scope`, which joins it. It stops after
+    /// the end of the source, a read error or cancellation (each passed on
+    /// through [`next`](Self::next)), or once this reader is dropped. With a
+    /// `buffer_count` of 1 the source is read inline instead.
+    fn prefetch<'scope, R: Read + Send>(
+        scope: &'scope Scope<'scope, '_>,
+        source: &'a mut R,
+        block_size: usize,
+        device_block_size: usize,
+        buffer_count: usize,
+        cancel: CancelToken,
+    ) -> Self
+    where
+        'a: 'scope,
+    {
+        if buffer_count <= 1 {
+            return Self::inline(source, block_size, device_block_size);
+        }
+
+        let (empty, empty_rx) = sync_channel::<AlignedBuffer>(buffer_count);
+        let (filled_tx, filled) = sync_channel(buffer_count);
+        for _ in 0..buffer_count {
+            // Cannot fail: the channel has room for every buffer
+            let _ = empty.send(block_buffer(block_size, device_block_size));
+        }
+
+        scope.spawn(move || {
+            while let Ok(mut buffer) = empty_rx.recv() {
+                let result = cancel
+                    .check()
+                    .and_then(|()| read_exact_or_eof(source, &mut buffer[..block_size]));
+                let last = !matches!(result, Ok(n) if n > 0);
+                if filled_tx.send(result.map(|n| (buffer, n))).is_err() || last {
+                    break;
+                }
+            }
+        });
+
+        BlockReader::Prefetch { filled, empty }
+    }
+
+    /// The next block, or `None` at the end of the source
+    fn next(&mut self) -> Result<Option<Block>> {
+        let (buffer, len) = match self {
+            BlockReader::Inline {
+                source,
+                spare,
+                block_size,
+                device_block_size,
+            } => {
+                let mut buffer = spare
+                    .pop()
+                    .unwrap_or_else(|| block_buffer(*block_size, *device_block_size));
+                let len = read_exact_or_eof(source, &mut buffer[..*block_size])?;
+                (buffer, len)
+            }
+            BlockReader::Prefetch { filled, .. } => filled.recv().map_err(|_| {
+                Error::Unknown("Source reader thread stopped unexpectedly".to_string())
+            })??,
+        };
+        Ok((len > 0).then_some((buffer, len)))
+    }
+
+    /// Hand a buffer back to be filled again
+    fn recycle(&mut self, buffer: AlignedBuffer) {
+        match self {
+            BlockReader::Inline { spare, .. } => spare.push(buffer),
+            BlockReader::Prefetch { empty, .. } => {
+                // The reader may already have stopped at the end of the source
+                let _ = empty.send(buffer);
+            }
+        }
+    }
+}
+
 /// Read exactly the buffer size or until EOF
-fn read_exact_or_eof<R: Read>(reader: &mut R, buffer: &mut [u8]) -> Result<usize> {
+fn read_exact_or_eof<R: Read + ?Sized>(reader: &mut R, buffer: &mut [u8]) -> Result<usize> {
     let mut total_read = 0;
 
     while total_read < buffer.len() {
@@ -847,6 +2431,16 @@ fn calculate_eta(bytes_written: u64, total_bytes: u64, speed_bps: u64) -> Option
     Some(remaining / speed_bps)
 }
 
+/// Estimate time remaining from the share of the compressed input consumed so far
+fn compressed_eta(read: u64, total: u64, elapsed: Duration) -> Option<u64> {
+    if read == 0 || read >= total {
+        return None;
+    }
+
+    let remaining = u128::from(total - read);
+    Some((remaining * elapsed.as_millis() / u128::from(read) / 1000) as u64)
+}
+
 /// Format speed for display
 pub fn format_speed(bytes_per_second: u64) -> String {
     const KB: u64 = 1024;
@@ -886,8 +2480,12 @@ pub fn format_duration(seconds: u64) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cancel::CancelReason;
+    use crate::source::CountingReader;
+    use crate::test_util::CacheCountingDevice;
     use std::io::Cursor;
-    use std::sync::atomic::AtomicU64;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
 
     // -------------------------------------------------------------------------
     // WriteProgress tests
@@ -897,6 +2495,7 @@ mod tests {
     fn test_write_progress_new() {
         let progress = WriteProgress::new(1024 * 1024, 4096);
         assert_eq!(progress.bytes_written, 0);
+        assert_eq!(progress.current_offset, 0);
         assert_eq!(progress.total_bytes, 1024 * 1024);
         assert_eq!(progress.total_blocks, 256);
         assert!(!progress.is_complete());
@@ -908,10 +2507,10 @@ mod tests {
 
         assert_eq!(progress.percentage(), 0.0);
 
-        progress.bytes_written = 500;
+        progress.current_offset = 500;
         assert_eq!(progress.percentage(), 50.0);
 
-        progress.bytes_written = 1000;
+        progress.current_offset = 1000;
         assert_eq!(progress.percentage(), 100.0);
     }
 
@@ -926,13 +2525,13 @@ mod tests {
         let mut progress = WriteProgress::new(1000, 100);
         assert!(!progress.is_complete());
 
-        progress.bytes_written = 999;
+        progress.current_offset = 999;
         assert!(!progress.is_complete());
 
-        progress.bytes_written = 1000;
+        progress.current_offset = 1000;
         assert!(progress.is_complete());
 
-        progress.bytes_written = 1001;
+        progress.current_offset = 1001;
         assert!(progress.is_complete());
     }
 
@@ -974,7 +2573,18 @@ mod tests {
         assert_eq!(config.block_size, DEFAULT_BLOCK_SIZE);
         assert!(!config.sync_each_block);
         assert!(config.sync_on_complete);
+        assert!(config.sync_interval_bytes.is_none());
         assert_eq!(config.retry_attempts, DEFAULT_RETRY_ATTEMPTS);
+        assert!(!config.verify_on_resume);
+    }
+
+    #[test]
+    fn test_write_config_sync_interval_zero_disables() {
+        let config = WriteConfig::new().sync_interval_bytes(Some(0));
+        assert!(config.sync_interval_bytes.is_none());
+
+        let config = WriteConfig::new().sync_interval_bytes(Some(4096));
+        assert_eq!(config.sync_interval_bytes, Some(4096));
     }
 
     #[test]
@@ -1123,27 +2733,143 @@ mod tests {
     }
 
     #[test]
-    fn test_writer_with_progress() {
-        // Use 4 blocks worth of data at MIN_BLOCK_SIZE (4096 * 4 = 16384)
+    fn test_writer_reports_compressed_input() {
         let data_size = MIN_BLOCK_SIZE * 4;
-        let source_data = vec![0xABu8; data_size];
-        let source = Cursor::new(source_data);
+        let source = CountingReader::new(Cursor::new(vec![0xABu8; data_size]));
+        let counter = source.counter();
         let target = Cursor::new(vec![0u8; data_size]);
 
-        let progress_count = Arc::new(AtomicU64::new(0));
-        let progress_count_clone = Arc::clone(&progress_count);
-
-        let config = WriteConfig::new().block_size(MIN_BLOCK_SIZE);
-        let mut writer = Writer::with_config(config).on_progress(move |_progress| {
-            progress_count_clone.fetch_add(1, Ordering::SeqCst);
-        });
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen_clone = Arc::clone(&seen);
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .progress_interval(ProgressInterval::EveryBlock);
+        let mut writer = Writer::with_config(config)
+            .track_compressed_input(counter, data_size as u64 * 2)
+            .on_progress(move |progress| {
+                seen_clone
+                    .lock()
+                    .unwrap()
+                    .push(progress.compressed_position());
+            });
 
-        let _result = writer.write(source, target, data_size as u64).unwrap();
+        writer.write(source, target, data_size as u64).unwrap();
+
+        let seen = seen.lock().unwrap();
+        let positions: Vec<(u64, u64)> = seen.iter().map(|p| p.unwrap()).collect();
+        assert!(positions.windows(2).all(|w| w[0].0 <= w[1].0));
+        assert_eq!(
+            positions.last(),
+            Some(&(data_size as u64, data_size as u64 * 2))
+        );
+    }
+
+    #[test]
+    fn test_writer_without_compressed_input() {
+        let source = Cursor::new(vec![0u8; MIN_BLOCK_SIZE]);
+        let target = Cursor::new(vec![0u8; MIN_BLOCK_SIZE]);
+        let seen = Arc::new(std::sync::Mutex::new(None));
+        let seen_clone = Arc::clone(&seen);
+        let mut writer = Writer::new().on_progress(move |progress| {
+            *seen_clone.lock().unwrap() = Some(progress.compressed_position());
+        });
+
+        writer.write(source, target, MIN_BLOCK_SIZE as u64).unwrap();
+        assert_eq!(*seen.lock().unwrap(), Some(None));
+    }
+
+    #[test]
+    fn test_writer_with_progress() {
+        // Use 4 blocks worth of data at MIN_BLOCK_SIZE (4096 * 4 = 16384)
+        let data_size = MIN_BLOCK_SIZE * 4;
+        let source_data = vec![0xABu8; data_size];
+        let source = Cursor::new(source_data);
+        let target = Cursor::new(vec![0u8; data_size]);
+
+        let progress_count = Arc::new(AtomicU64::new(0));
+        let progress_count_clone = Arc::clone(&progress_count);
+
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .progress_interval(ProgressInterval::EveryBlock);
+        let mut writer = Writer::with_config(config).on_progress(move |_progress| {
+            progress_count_clone.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let _result = writer.write(source, target, data_size as u64).unwrap();
 
         // Should have 4 progress callbacks (one per block)
         assert_eq!(progress_count.load(Ordering::SeqCst), 4);
     }
 
+    /// Count progress callbacks for a 64-block in-memory write, recording the last offset
+    fn count_progress_ticks(interval: ProgressInterval) -> (u64, u64) {
+        let data_size = MIN_BLOCK_SIZE * 64;
+        let source = Cursor::new(vec![0x5Au8; data_size]);
+        let target = Cursor::new(vec![0u8; data_size]);
+
+        let ticks = Arc::new(AtomicU64::new(0));
+        let last_offset = Arc::new(AtomicU64::new(0));
+        let ticks_clone = Arc::clone(&ticks);
+        let last_offset_clone = Arc::clone(&last_offset);
+
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .progress_interval(interval);
+        let mut writer = Writer::with_config(config).on_progress(move |p| {
+            ticks_clone.fetch_add(1, Ordering::SeqCst);
+            last_offset_clone.store(p.current_offset, Ordering::SeqCst);
+        });
+        writer.write(source, target, data_size as u64).unwrap();
+
+        (
+            ticks.load(Ordering::SeqCst),
+            last_offset.load(Ordering::SeqCst),
+        )
+    }
+
+    #[test]
+    fn test_progress_interval_default() {
+        assert_eq!(
+            WriteConfig::default().progress_interval,
+            ProgressInterval::EveryDuration(DEFAULT_PROGRESS_INTERVAL)
+        );
+    }
+
+    #[test]
+    fn test_progress_interval_every_block() {
+        let (ticks, last) = count_progress_ticks(ProgressInterval::EveryBlock);
+        assert_eq!(ticks, 64);
+        assert_eq!(last, (MIN_BLOCK_SIZE * 64) as u64);
+    }
+
+    #[test]
+    fn test_progress_interval_every_bytes() {
+        // Exact multiple of the block size: the 100% tick is already on schedule
+        let (ticks, last) =
+            count_progress_ticks(ProgressInterval::EveryBytes(16 * MIN_BLOCK_SIZE as u64));
+        assert_eq!(ticks, 4);
+        assert_eq!(last, (MIN_BLOCK_SIZE * 64) as u64);
+
+        // Otherwise every 10th block ticks, plus the final tick at 100%
+        let (ticks, last) =
+            count_progress_ticks(ProgressInterval::EveryBytes(10 * MIN_BLOCK_SIZE as u64));
+        assert_eq!(ticks, 7);
+        assert_eq!(last, (MIN_BLOCK_SIZE * 64) as u64);
+    }
+
+    #[test]
+    fn test_progress_interval_every_duration() {
+        // Nothing is due within an hour, so only the final tick fires
+        let (ticks, last) =
+            count_progress_ticks(ProgressInterval::EveryDuration(Duration::from_secs(3600)));
+        assert_eq!(ticks, 1);
+        assert_eq!(last, (MIN_BLOCK_SIZE * 64) as u64);
+
+        let (ticks, _) = count_progress_ticks(ProgressInterval::EveryDuration(Duration::ZERO));
+        assert_eq!(ticks, 64);
+    }
+
     #[test]
     fn test_writer_verify_data_integrity() {
         let source_data: Vec<u8> = (0..4096).map(|i| (i % 256) as u8).collect();
@@ -1167,23 +2893,93 @@ mod tests {
         let source = Cursor::new(source_data);
         let target = Cursor::new(vec![0u8; data_size]);
 
-        let config = WriteConfig::new().block_size(MIN_BLOCK_SIZE);
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .progress_interval(ProgressInterval::EveryBlock);
         let writer = Writer::with_config(config);
 
         let cancel_handle = writer.cancel_handle();
-        let cancel_clone = Arc::clone(&cancel_handle);
+        let cancel_clone = cancel_handle.clone();
 
         // Cancel after first block via progress callback
         let writer = writer.on_progress(move |progress| {
             if progress.current_block >= 1 {
-                cancel_clone.store(true, Ordering::SeqCst);
+                cancel_clone.cancel(CancelReason::UserRequested);
             }
         });
 
         let mut writer = writer;
         let result = writer.write(source, target, data_size as u64);
 
-        assert!(matches!(result, Err(Error::Cancelled)));
+        assert!(matches!(
+            result,
+            Err(Error::Cancelled(CancelReason::UserRequested))
+        ));
+    }
+
+    #[test]
+    fn test_writer_pause_and_resume() {
+        let data: Vec<u8> = (0..MIN_BLOCK_SIZE * 4).map(|i| (i % 241) as u8).collect();
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .progress_interval(ProgressInterval::EveryBlock);
+        let writer = Writer::with_config(config);
+        let pause = writer.pause_handle();
+
+        // Pause after the first block; resume from another thread
+        let pause_clone = pause.clone();
+        let mut writer = writer.on_progress(move |progress| {
+            if progress.current_block == 1 {
+                pause_clone.pause();
+                let resume = pause_clone.clone();
+                std::thread::spawn(move || {
+                    std::thread::sleep(Duration::from_millis(300));
+                    resume.resume();
+                });
+            }
+        });
+
+        let mut target = Cursor::new(Vec::new());
+        let started = Instant::now();
+        let result = writer
+            .write(Cursor::new(&data), &mut target, data.len() as u64)
+            .unwrap();
+
+        assert_eq!(target.into_inner(), data);
+        assert!(!pause.is_paused());
+        assert!(started.elapsed() >= Duration::from_millis(300));
+        // The pause is not counted as write time
+        assert!(result.elapsed < Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_writer_cancel_while_paused() {
+        let data = vec![0x5Au8; MIN_BLOCK_SIZE * 4];
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .progress_interval(ProgressInterval::EveryBlock);
+        let writer = Writer::with_config(config);
+        let pause = writer.pause_handle();
+        let cancel = writer.cancel_handle();
+
+        let mut writer = writer.on_progress(move |_| {
+            pause.pause();
+            let cancel = cancel.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(100));
+                cancel.cancel(CancelReason::UserRequested);
+            });
+        });
+
+        let result = writer.write(
+            Cursor::new(&data),
+            Cursor::new(Vec::new()),
+            data.len() as u64,
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Cancelled(CancelReason::UserRequested))
+        ));
     }
 
     #[test]
@@ -1197,6 +2993,42 @@ mod tests {
         assert_eq!(result.bytes_written, 0);
     }
 
+    #[test]
+    fn test_write_from_offset_progress_offsets() {
+        let block = MIN_BLOCK_SIZE as u64;
+        let data = vec![0x42u8; MIN_BLOCK_SIZE * 4];
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .progress_interval(ProgressInterval::EveryBlock);
+        let mut writer = Writer::with_config(config).on_progress(move |p| {
+            seen_clone
+                .lock()
+                .unwrap()
+                .push((p.bytes_written, p.current_offset, p.percentage()));
+        });
+
+        // Resume after the first two blocks; the source is already positioned there
+        let mut target = Cursor::new(vec![0u8; data.len()]);
+        let result = writer
+            .write_from_offset(
+                Cursor::new(&data[2 * MIN_BLOCK_SIZE..]),
+                &mut target,
+                data.len() as u64,
+                2 * block,
+            )
+            .unwrap();
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![(block, 3 * block, 75.0), (2 * block, 4 * block, 100.0)]
+        );
+        // The result still reports the total on the device
+        assert_eq!(result.bytes_written, 4 * block);
+    }
+
     // -------------------------------------------------------------------------
     // SpeedTracker tests
     // -------------------------------------------------------------------------
@@ -1247,6 +3079,9 @@ mod tests {
             source_checksum: None,
             target_checksum: None,
             verification_elapsed: None,
+            verification: None,
+            bad_blocks: Vec::new(),
+            padded_bytes: 0,
         };
 
         assert_eq!(result.speed_display(), "50.0 MB/s");
@@ -1313,6 +3148,282 @@ mod tests {
         assert_eq!(result.source_checksum, result.target_checksum);
         assert_eq!(result.verified, Some(true));
         assert!(result.verification_elapsed.is_some());
+        assert!(result.verification.unwrap().success);
+    }
+
+    #[test]
+    fn test_write_verified_without_verify_matches_write() {
+        let source = Cursor::new(vec![0x5Au8; 4096]);
+        let target = Cursor::new(vec![0u8; 4096]);
+
+        let mut writer = Writer::with_config(WriteConfig::new().block_size(MIN_BLOCK_SIZE));
+        let result = writer.write_verified(source, target, 4096).unwrap();
+
+        assert_eq!(result.bytes_written, 4096);
+        assert!(result.verified.is_none());
+        assert!(result.verification.is_none());
+    }
+
+    #[test]
+    fn test_write_verified_byte_compare() {
+        use std::sync::{Arc, Mutex};
+
+        let config = WriteConfig::new().block_size(MIN_BLOCK_SIZE).verify(true);
+        let phases_seen = Arc::new(Mutex::new(Vec::new()));
+        let phases_clone = phases_seen.clone();
+        let mut writer = Writer::with_config(config).on_progress(move |progress| {
+            let mut phases = phases_clone.lock().unwrap();
+            if phases.last() != Some(&progress.phase) {
+                phases.push(progress.phase);
+            }
+        });
+
+        for _ in 0..2 {
+            let source = Cursor::new(vec![0x5Au8; 8192]);
+            let target = Cursor::new(vec![0u8; 8192]);
+            let result = writer.write_verified(source, target, 8192).unwrap();
+
+            assert_eq!(result.verified, Some(true));
+            assert!(result.target_checksum.is_none());
+            let verification = result.verification.unwrap();
+            assert!(verification.success);
+            assert_eq!(verification.bytes_verified, 8192);
+        }
+
+        // The progress callback is handed back after each verify
+        let phases = phases_seen.lock().unwrap();
+        assert_eq!(
+            *phases,
+            vec![
+                WritePhase::Writing,
+                WritePhase::Verifying,
+                WritePhase::Writing,
+                WritePhase::Verifying
+            ]
+        );
+    }
+
+    /// A target that stores the complement of every byte written to it
+    struct InvertingTarget(Cursor<Vec<u8>>);
+
+    impl Read for InvertingTarget {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.0.read(buf)
+        }
+    }
+
+    impl Write for InvertingTarget {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let inverted: Vec<u8> = buf.iter().map(|b| !b).collect();
+            self.0.write(&inverted)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Seek for InvertingTarget {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.0.seek(pos)
+        }
+    }
+
+    #[test]
+    fn test_write_verified_reports_mismatch() {
+        let source = Cursor::new(vec![0x5Au8; 4096]);
+        let target = InvertingTarget(Cursor::new(vec![0u8; 4096]));
+
+        let config = WriteConfig::new().block_size(MIN_BLOCK_SIZE).verify(true);
+        let result = Writer::with_config(config)
+            .write_verified(source, target, 4096)
+            .unwrap();
+
+        assert_eq!(result.verified, Some(false));
+        let verification = result.verification.unwrap();
+        assert!(!verification.success);
+        assert_eq!(verification.first_mismatch_offset, Some(0));
+    }
+
+    #[test]
+    fn test_write_verified_with_checksum_algorithm() {
+        use crate::verifier::ChecksumAlgorithm;
+
+        let source = Cursor::new(vec![0x5Au8; 4096]);
+        let target = InvertingTarget(Cursor::new(vec![0u8; 4096]));
+
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .verify(true)
+            .checksum_algorithm(Some(ChecksumAlgorithm::Sha256));
+        let result = Writer::with_config(config)
+            .write_verified(source, target, 4096)
+            .unwrap();
+
+        assert_eq!(result.verified, Some(false));
+        assert!(result.target_checksum.is_some());
+        let verification = result.verification.unwrap();
+        assert_eq!(verification.mismatches, 1);
+        assert!(verification.first_mismatch_offset.is_none());
+    }
+
+    #[test]
+    fn test_write_and_verify_device_drops_cache() {
+        use crate::verifier::ChecksumAlgorithm;
+
+        let data = vec![0xC3u8; 8192];
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .checksum_algorithm(Some(ChecksumAlgorithm::Sha256));
+        assert!(config.drop_cache);
+        let mut device = CacheCountingDevice::new(data.len());
+
+        let result = Writer::with_config(config)
+            .write_and_verify_device(Cursor::new(&data), &mut device, data.len() as u64)
+            .unwrap();
+
+        assert_eq!(result.verified, Some(true));
+        assert_eq!(device.drops.get(), 1);
+    }
+
+    #[test]
+    fn test_write_and_verify_device_without_drop_cache() {
+        use crate::verifier::ChecksumAlgorithm;
+
+        let data = vec![0xC3u8; 8192];
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .checksum_algorithm(Some(ChecksumAlgorithm::Sha256))
+            .drop_cache(false);
+        let mut device = CacheCountingDevice::new(data.len());
+
+        let result = Writer::with_config(config)
+            .write_and_verify_device(Cursor::new(&data), &mut device, data.len() as u64)
+            .unwrap();
+
+        assert_eq!(result.verified, Some(true));
+        assert_eq!(device.drops.get(), 0);
+    }
+
+    fn write_to_counting_device(fail_batches: bool) -> CacheCountingDevice {
+        let data: Vec<u8> = (0..8 * MIN_BLOCK_SIZE).map(|i| (i % 251) as u8).collect();
+        let mut device = CacheCountingDevice::new(data.len());
+        device.fail_batches = fail_batches;
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .buffer_count(4)
+            .small_head_bytes(0)
+            .verify_each_block(true);
+
+        let result = Writer::with_config(config)
+            .write_to_device(
+                Prefetch::new(Cursor::new(&data)),
+                &mut device,
+                data.len() as u64,
+                0,
+            )
+            .unwrap();
+
+        assert_eq!(result.bytes_written, data.len() as u64);
+        assert!(result.bad_blocks.is_empty());
+        assert_eq!(device.data(), &data[..]);
+        device
+    }
+
+    #[test]
+    fn test_write_to_device_batches_blocks() {
+        let device = write_to_counting_device(false);
+        assert_eq!(device.batches.get(), 2);
+    }
+
+    #[test]
+    fn test_write_to_device_falls_back_when_batch_fails() {
+        let device = write_to_counting_device(true);
+        assert_eq!(device.batches.get(), 2);
+    }
+
+    #[test]
+    fn test_write_to_device_syncs_at_interval() {
+        let data = vec![0x3Cu8; 8 * MIN_BLOCK_SIZE];
+        let mut device = CacheCountingDevice::new(data.len());
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .sync_on_complete(false)
+            .sync_interval_bytes(Some(2 * MIN_BLOCK_SIZE as u64));
+
+        Writer::with_config(config)
+            .write_to_device(Cursor::new(&data), &mut device, data.len() as u64, 0)
+            .unwrap();
+
+        assert_eq!(device.syncs.get(), 4);
+    }
+
+    #[test]
+    fn test_write_with_read_back_does_not_batch() {
+        let data = vec![0x5Au8; 8 * MIN_BLOCK_SIZE];
+        let mut device = CacheCountingDevice::new(data.len());
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .buffer_count(4)
+            .small_head_bytes(0);
+
+        Writer::with_config(config)
+            .write_with_read_back(Cursor::new(&data), &mut device, data.len() as u64, 0)
+            .unwrap();
+
+        assert_eq!(device.batches.get(), 0);
+        assert_eq!(device.data(), &data[..]);
+    }
+
+    #[test]
+    fn test_write_hashing_returns_source_checksum() {
+        let source_data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let mut target = Cursor::new(vec![0u8; source_data.len()]);
+
+        let mut writer = Writer::with_config(WriteConfig::new().block_size(MIN_BLOCK_SIZE));
+        let (result, checksum) = writer
+            .write_hashing(
+                Cursor::new(source_data.clone()),
+                &mut target,
+                source_data.len() as u64,
+                ChecksumAlgorithm::Sha256,
+            )
+            .unwrap();
+
+        let expected = Verifier::new()
+            .calculate_checksum(
+                &mut Cursor::new(&source_data),
+                ChecksumAlgorithm::Sha256,
+                None,
+            )
+            .unwrap();
+        assert_eq!(checksum, expected);
+        assert_eq!(result.source_checksum, Some(checksum.to_hex()));
+        assert_eq!(result.bytes_written, source_data.len() as u64);
+        assert_eq!(&target.get_ref()[..], &source_data[..]);
+    }
+
+    #[test]
+    fn test_write_hashing_keeps_configured_algorithm() {
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .checksum_algorithm(Some(ChecksumAlgorithm::Md5));
+        let mut writer = Writer::with_config(config);
+
+        let (_, checksum) = writer
+            .write_hashing(
+                Cursor::new(vec![1u8; 4096]),
+                Cursor::new(vec![0u8; 4096]),
+                4096,
+                ChecksumAlgorithm::Crc32,
+            )
+            .unwrap();
+
+        assert_eq!(checksum.algorithm, ChecksumAlgorithm::Crc32);
+        assert_eq!(
+            writer.config.checksum_algorithm,
+            Some(ChecksumAlgorithm::Md5)
+        );
     }
 
     #[test]
@@ -1389,6 +3500,105 @@ mod tests {
         assert_eq!(*phases, vec![WritePhase::Writing, WritePhase::Verifying]);
     }
 
+    /// Write `data` with `config`, returning every phase passed to `on_phase`
+    fn record_phases(
+        config: WriteConfig,
+        data: &[u8],
+        verify: bool,
+    ) -> (Result<WriteResult>, Vec<WritePhase>) {
+        use std::sync::{Arc, Mutex};
+
+        let phases = Arc::new(Mutex::new(Vec::new()));
+        let phases_clone = phases.clone();
+        let mut writer = Writer::with_config(config.block_size(MIN_BLOCK_SIZE))
+            .on_phase(move |phase| phases_clone.lock().unwrap().push(phase));
+
+        let target = Cursor::new(vec![0u8; data.len()]);
+        let result = if verify {
+            writer.write_and_verify(Cursor::new(data), target, data.len() as u64)
+        } else {
+            writer.write(Cursor::new(data), target, data.len() as u64)
+        };
+        let phases = phases.lock().unwrap().clone();
+        (result, phases)
+    }
+
+    #[test]
+    fn test_on_phase_ordering_for_small_write() {
+        let (result, phases) = record_phases(WriteConfig::new(), &[0x11u8; 8192], false);
+
+        result.unwrap();
+        assert_eq!(
+            phases,
+            vec![
+                WritePhase::Starting,
+                WritePhase::Writing,
+                WritePhase::Syncing,
+                WritePhase::Complete
+            ]
+        );
+    }
+
+    #[test]
+    fn test_on_phase_skips_syncing_without_sync_on_complete() {
+        let config = WriteConfig::new().sync_on_complete(false);
+        let (_, phases) = record_phases(config, &[0x11u8; 8192], false);
+
+        assert_eq!(
+            phases,
+            vec![
+                WritePhase::Starting,
+                WritePhase::Writing,
+                WritePhase::Complete
+            ]
+        );
+    }
+
+    #[test]
+    fn test_on_phase_ordering_for_write_and_verify() {
+        use crate::verifier::ChecksumAlgorithm;
+
+        let config = WriteConfig::new().checksum_algorithm(Some(ChecksumAlgorithm::Sha256));
+        let (result, phases) = record_phases(config, &[0x11u8; 8192], true);
+
+        assert_eq!(result.unwrap().verified, Some(true));
+        assert_eq!(
+            phases,
+            vec![
+                WritePhase::Starting,
+                WritePhase::Writing,
+                WritePhase::Syncing,
+                WritePhase::Verifying,
+                WritePhase::Complete
+            ]
+        );
+    }
+
+    #[test]
+    fn test_on_phase_not_complete_when_cancelled() {
+        use std::sync::{Arc, Mutex};
+
+        let phases = Arc::new(Mutex::new(Vec::new()));
+        let phases_clone = phases.clone();
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .progress_interval(ProgressInterval::EveryBlock);
+        let writer = Writer::with_config(config)
+            .on_phase(move |phase| phases_clone.lock().unwrap().push(phase));
+        let cancel = writer.cancel_handle();
+        let mut writer = writer.on_progress(move |_| cancel.cancel(CancelReason::UserRequested));
+
+        let data = vec![0x11u8; MIN_BLOCK_SIZE * 4];
+        let result = writer.write(
+            Cursor::new(&data),
+            Cursor::new(Vec::new()),
+            data.len() as u64,
+        );
+
+        assert!(result.is_err());
+        assert!(!phases.lock().unwrap().contains(&WritePhase::Complete));
+    }
+
     #[test]
     fn test_write_and_verify_cancel_during_verify() {
         use crate::verifier::ChecksumAlgorithm;
@@ -1401,7 +3611,8 @@ mod tests {
 
         let config = WriteConfig::new()
             .block_size(MIN_BLOCK_SIZE)
-            .checksum_algorithm(Some(ChecksumAlgorithm::Sha256));
+            .checksum_algorithm(Some(ChecksumAlgorithm::Sha256))
+            .progress_interval(ProgressInterval::EveryBlock);
 
         let writer = Writer::with_config(config);
 
@@ -1411,13 +3622,13 @@ mod tests {
         let writer = writer.on_progress(move |progress| {
             // Cancel once we enter the verification phase
             if progress.phase == WritePhase::Verifying {
-                cancel_handle.store(true, Ordering::SeqCst);
+                cancel_handle.cancel(CancelReason::UserRequested);
             }
         });
         let mut writer = writer;
 
         let result = writer.write_and_verify(source, target, size as u64);
-        assert!(matches!(result, Err(crate::error::Error::Cancelled)));
+        assert!(matches!(result, Err(crate::error::Error::Cancelled(_))));
     }
 
     #[test]
@@ -1477,6 +3688,9 @@ mod tests {
             source_checksum: None,
             target_checksum: None,
             verification_elapsed: None,
+            verification: None,
+            bad_blocks: Vec::new(),
+            padded_bytes: 0,
         };
 
         assert!(result.verified.is_none());
@@ -1497,6 +3711,9 @@ mod tests {
             source_checksum: Some("abc123".to_string()),
             target_checksum: Some("abc123".to_string()),
             verification_elapsed: Some(Duration::from_millis(500)),
+            verification: None,
+            bad_blocks: Vec::new(),
+            padded_bytes: 0,
         };
 
         assert_eq!(result.verified, Some(true));
@@ -1519,6 +3736,9 @@ mod tests {
             source_checksum: Some("aaa".to_string()),
             target_checksum: Some("bbb".to_string()),
             verification_elapsed: Some(Duration::from_millis(200)),
+            verification: None,
+            bad_blocks: Vec::new(),
+            padded_bytes: 0,
         };
 
         assert_eq!(result.verified, Some(false));
@@ -1555,6 +3775,14 @@ mod tests {
         assert_eq!(target.into_inner(), source_data);
     }
 
+    #[test]
+    fn test_compressed_eta() {
+        let elapsed = Duration::from_secs(10);
+        assert_eq!(compressed_eta(0, 1000, elapsed), None);
+        assert_eq!(compressed_eta(1000, 1000, elapsed), None);
+        assert_eq!(compressed_eta(250, 1000, elapsed), Some(30));
+    }
+
     // -------------------------------------------------------------------------
     // calculate_eta edge cases
     // -------------------------------------------------------------------------
@@ -1666,4 +3894,956 @@ mod tests {
         assert!(matches!(result, Err(Error::Io(_))));
         assert_eq!(retry_count, 2);
     }
+
+    /// A file on a filesystem with room for `capacity` bytes
+    struct FullDevice {
+        inner: Cursor<Vec<u8>>,
+        capacity: u64,
+    }
+
+    impl Read for FullDevice {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.inner.read(buf)
+        }
+    }
+
+    impl Write for FullDevice {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let room = self.capacity.saturating_sub(self.inner.position());
+            if room == 0 {
+                return Err(std::io::Error::from_raw_os_error(28)); // ENOSPC
+            }
+            let n = buf.len().min(room as usize);
+            self.inner.write(&buf[..n])
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Seek for FullDevice {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_to_full_device_reports_bytes_written() {
+        let config = WriteConfig::new()
+            .block_size(1024)
+            .retry_attempts(3)
+            .retry_delay(Duration::from_millis(1))
+            .max_bad_blocks(10);
+        let mut writer = Writer::with_config(config);
+        let source = vec![0xAB; 8192];
+        let mut target = FullDevice {
+            inner: Cursor::new(Vec::new()),
+            capacity: 4096 + 512,
+        };
+
+        let err = writer
+            .write(Cursor::new(source), &mut target, 8192)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::DeviceFull {
+                bytes_written: 4096
+            }
+        ));
+        assert!(err.to_string().contains("4096 bytes"));
+    }
+
+    #[test]
+    fn test_write_limit_leaves_preserved_region_untouched() {
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .max_bad_blocks(10)
+            .write_limit(Some(MIN_BLOCK_SIZE as u64 * 2));
+        let mut writer = Writer::with_config(config);
+        let source = vec![0xAB; MIN_BLOCK_SIZE * 3];
+        let mut target = Cursor::new(vec![0u8; MIN_BLOCK_SIZE * 4]);
+
+        let err = writer
+            .write(Cursor::new(source), &mut target, MIN_BLOCK_SIZE as u64 * 3)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::WriteLimitExceeded { limit } if limit == MIN_BLOCK_SIZE as u64 * 2
+        ));
+
+        let target = target.into_inner();
+        assert!(target[..MIN_BLOCK_SIZE * 2].iter().all(|&b| b == 0xAB));
+        assert!(target[MIN_BLOCK_SIZE * 2..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_write_limit_allows_write_up_to_limit() {
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .write_limit(Some(MIN_BLOCK_SIZE as u64 * 2));
+        let mut writer = Writer::with_config(config);
+        let source = vec![0xAB; MIN_BLOCK_SIZE * 2];
+        let target = Cursor::new(vec![0u8; MIN_BLOCK_SIZE * 4]);
+
+        let result = writer
+            .write(Cursor::new(source), target, MIN_BLOCK_SIZE as u64 * 2)
+            .unwrap();
+        assert_eq!(result.bytes_written, MIN_BLOCK_SIZE as u64 * 2);
+    }
+
+    // -------------------------------------------------------------------------
+    // Bad block tests
+    // -------------------------------------------------------------------------
+
+    /// An in-memory device whose writes fail at the given offsets.
+    struct BadBlockDevice {
+        inner: Cursor<Vec<u8>>,
+        bad_offsets: Vec<u64>,
+    }
+
+    impl BadBlockDevice {
+        fn new(size: usize, bad_offsets: &[u64]) -> Self {
+            Self {
+                inner: Cursor::new(vec![0u8; size]),
+                bad_offsets: bad_offsets.to_vec(),
+            }
+        }
+    }
+
+    impl Read for BadBlockDevice {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.inner.read(buf)
+        }
+    }
+
+    impl Write for BadBlockDevice {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.bad_offsets.contains(&self.inner.position()) {
+                return Err(std::io::Error::other("simulated bad block"));
+            }
+            self.inner.write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Seek for BadBlockDevice {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    fn bad_block_writer(max_bad_blocks: u32) -> Writer {
+        Writer::with_config(
+            WriteConfig::new()
+                .block_size(MIN_BLOCK_SIZE)
+                .retry_attempts(1)
+                .retry_delay(Duration::ZERO)
+                .max_bad_blocks(max_bad_blocks),
+        )
+    }
+
+    #[test]
+    fn test_config_max_bad_blocks_default() {
+        assert_eq!(WriteConfig::new().max_bad_blocks, 0);
+        assert_eq!(WriteConfig::new().max_bad_blocks(3).max_bad_blocks, 3);
+    }
+
+    #[test]
+    fn test_bad_block_fails_write_by_default() {
+        let block = MIN_BLOCK_SIZE as u64;
+        let data = vec![0xAAu8; MIN_BLOCK_SIZE * 4];
+        let mut target = BadBlockDevice::new(data.len(), &[block]);
+
+        let result = bad_block_writer(0).write(Cursor::new(&data), &mut target, data.len() as u64);
+
+        assert!(matches!(result, Err(Error::Io(_))));
+    }
+
+    #[test]
+    fn test_bad_blocks_within_limit_are_skipped() {
+        let block = MIN_BLOCK_SIZE as u64;
+        let data = vec![0xAAu8; MIN_BLOCK_SIZE * 4];
+        let mut target = BadBlockDevice::new(data.len(), &[block, 3 * block]);
+
+        let result = bad_block_writer(2)
+            .write(Cursor::new(&data), &mut target, data.len() as u64)
+            .unwrap();
+
+        assert_eq!(result.bad_blocks, vec![block, 3 * block]);
+        assert_eq!(result.bytes_written, data.len() as u64);
+        assert_eq!(result.retry_count, 2);
+
+        // Good blocks land at their own offsets; skipped ones stay untouched
+        let written = target.inner.into_inner();
+        let block = MIN_BLOCK_SIZE;
+        assert!(written[..block].iter().all(|&b| b == 0xAA));
+        assert!(written[block..2 * block].iter().all(|&b| b == 0));
+        assert!(written[2 * block..3 * block].iter().all(|&b| b == 0xAA));
+        assert!(written[3 * block..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_bad_blocks_over_limit_fail_write() {
+        let block = MIN_BLOCK_SIZE as u64;
+        let data = vec![0xAAu8; MIN_BLOCK_SIZE * 4];
+        let mut target = BadBlockDevice::new(data.len(), &[0, block]);
+
+        let result = bad_block_writer(1).write(Cursor::new(&data), &mut target, data.len() as u64);
+
+        assert!(matches!(result, Err(Error::Io(_))));
+    }
+
+    // -------------------------------------------------------------------------
+    // Final block padding tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_config_pad_final_block_default() {
+        let config = WriteConfig::new();
+        assert!(!config.pad_final_block);
+        assert_eq!(config.device_block_size, DEFAULT_DEVICE_BLOCK_SIZE);
+
+        let config = config.pad_final_block(true).device_block_size(4096);
+        assert!(config.pad_final_block);
+        assert_eq!(config.device_block_size, 4096);
+    }
+
+    #[test]
+    fn test_unaligned_source_written_as_is_by_default() {
+        let data = vec![0xAAu8; MIN_BLOCK_SIZE * 2 + 100];
+        let mut writer = Writer::with_config(WriteConfig::new().block_size(MIN_BLOCK_SIZE));
+        let mut target = Cursor::new(Vec::new());
+
+        let result = writer
+            .write(Cursor::new(&data), &mut target, data.len() as u64)
+            .unwrap();
+
+        assert_eq!(result.padded_bytes, 0);
+        assert_eq!(target.into_inner(), data);
+    }
+
+    #[test]
+    fn test_pad_final_block_to_device_block_size() {
+        let data = vec![0xAAu8; MIN_BLOCK_SIZE * 2 + 100];
+        let mut writer = Writer::with_config(
+            WriteConfig::new()
+                .block_size(MIN_BLOCK_SIZE)
+                .pad_final_block(true)
+                .device_block_size(512),
+        );
+        let mut target = Cursor::new(Vec::new());
+
+        let result = writer
+            .write(Cursor::new(&data), &mut target, data.len() as u64)
+            .unwrap();
+
+        // The reported size is the source's; the padding is tracked separately
+        assert_eq!(result.bytes_written, data.len() as u64);
+        assert_eq!(result.padded_bytes, 412);
+
+        let written = target.into_inner();
+        assert_eq!(written.len(), MIN_BLOCK_SIZE * 2 + 512);
+        assert_eq!(&written[..data.len()], &data[..]);
+        assert!(written[data.len()..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_pad_final_block_leaves_aligned_source_alone() {
+        let data = vec![0xAAu8; MIN_BLOCK_SIZE + 1024];
+        let mut writer = Writer::with_config(
+            WriteConfig::new()
+                .block_size(MIN_BLOCK_SIZE)
+                .pad_final_block(true),
+        );
+        let mut target = Cursor::new(Vec::new());
+
+        let result = writer
+            .write(Cursor::new(&data), &mut target, data.len() as u64)
+            .unwrap();
+
+        assert_eq!(result.padded_bytes, 0);
+        assert_eq!(target.into_inner(), data);
+    }
+
+    /// An in-memory device that records whether every write buffer was aligned
+    struct AlignmentCheckingDevice {
+        inner: Cursor<Vec<u8>>,
+        alignment: usize,
+        misaligned: usize,
+    }
+
+    impl Read for AlignmentCheckingDevice {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.inner.read(buf)
+        }
+    }
+
+    impl Write for AlignmentCheckingDevice {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if !(buf.as_ptr() as usize).is_multiple_of(self.alignment) {
+                self.misaligned += 1;
+            }
+            self.inner.write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Seek for AlignmentCheckingDevice {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    /// An in-memory device that records the length of every write
+    struct WriteSizeRecordingDevice {
+        inner: Cursor<Vec<u8>>,
+        sizes: Vec<usize>,
+    }
+
+    impl Read for WriteSizeRecordingDevice {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.inner.read(buf)
+        }
+    }
+
+    impl Write for WriteSizeRecordingDevice {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.sizes.push(buf.len());
+            self.inner.write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Seek for WriteSizeRecordingDevice {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    fn record_write_sizes(config: WriteConfig, data: &[u8]) -> Vec<usize> {
+        let mut device = WriteSizeRecordingDevice {
+            inner: Cursor::new(Vec::new()),
+            sizes: Vec::new(),
+        };
+        Writer::with_config(config)
+            .write(Cursor::new(data), &mut device, data.len() as u64)
+            .unwrap();
+        assert_eq!(device.inner.into_inner(), data);
+        device.sizes
+    }
+
+    #[test]
+    fn test_small_head_written_in_device_blocks() {
+        let data: Vec<u8> = (0..MIN_BLOCK_SIZE * 3).map(|i| (i % 251) as u8).collect();
+        // The head ends partway through the second block
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .small_head_bytes(MIN_BLOCK_SIZE as u64 + 1024);
+        let sizes = record_write_sizes(config, &data);
+
+        let head_writes = (MIN_BLOCK_SIZE + 1024) / DEFAULT_DEVICE_BLOCK_SIZE;
+        assert!(sizes[..head_writes]
+            .iter()
+            .all(|&n| n == DEFAULT_DEVICE_BLOCK_SIZE));
+        assert_eq!(
+            &sizes[head_writes..],
+            &[MIN_BLOCK_SIZE - 1024, MIN_BLOCK_SIZE]
+        );
+    }
+
+    #[test]
+    fn test_small_head_off_by_default() {
+        let data = vec![0x5Au8; MIN_BLOCK_SIZE * 2];
+        let sizes = record_write_sizes(WriteConfig::new().block_size(MIN_BLOCK_SIZE), &data);
+        assert_eq!(sizes, vec![MIN_BLOCK_SIZE, MIN_BLOCK_SIZE]);
+    }
+
+    fn count_misaligned_writes(device_block_size: usize, buffer_count: usize) -> usize {
+        let data = vec![0x5Au8; MIN_BLOCK_SIZE * 3 + 100];
+        let mut device = AlignmentCheckingDevice {
+            inner: Cursor::new(Vec::new()),
+            alignment: device_block_size,
+            misaligned: 0,
+        };
+        let mut writer = Writer::with_config(
+            WriteConfig::new()
+                .block_size(MIN_BLOCK_SIZE)
+                .buffer_count(buffer_count)
+                .pad_final_block(true)
+                .device_block_size(device_block_size),
+        );
+        writer
+            .write(
+                Prefetch::new(Cursor::new(&data)),
+                &mut device,
+                data.len() as u64,
+            )
+            .unwrap();
+        assert_eq!(&device.inner.get_ref()[..data.len()], &data[..]);
+        device.misaligned
+    }
+
+    #[test]
+    fn test_buffers_aligned_to_512_byte_sectors() {
+        assert_eq!(count_misaligned_writes(512, 1), 0);
+        assert_eq!(count_misaligned_writes(512, 4), 0);
+    }
+
+    #[test]
+    fn test_buffers_aligned_to_4096_byte_sectors() {
+        assert_eq!(count_misaligned_writes(4096, 1), 0);
+        assert_eq!(count_misaligned_writes(4096, 4), 0);
+    }
+
+    // -------------------------------------------------------------------------
+    // Per-block verification tests
+    // -------------------------------------------------------------------------
+
+    /// An in-memory device that corrupts the byte at `flip_at` on its next `flips` writes
+    struct ByteFlippingDevice {
+        inner: Cursor<Vec<u8>>,
+        flip_at: u64,
+        flips: u32,
+    }
+
+    impl Read for ByteFlippingDevice {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.inner.read(buf)
+        }
+    }
+
+    impl Write for ByteFlippingDevice {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let start = self.inner.position();
+            let n = self.inner.write(buf)?;
+            if self.flips > 0 && (start..start + n as u64).contains(&self.flip_at) {
+                self.flips -= 1;
+                self.inner.get_mut()[self.flip_at as usize] ^= 0xFF;
+            }
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Seek for ByteFlippingDevice {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    fn verify_each_block_writer() -> Writer {
+        Writer::with_config(
+            WriteConfig::new()
+                .block_size(MIN_BLOCK_SIZE)
+                .verify_each_block(true)
+                .retry_attempts(2)
+                .retry_delay(Duration::ZERO),
+        )
+    }
+
+    #[test]
+    fn test_verify_each_block_rewrites_transient_corruption() {
+        let data = vec![0x5Au8; MIN_BLOCK_SIZE * 3];
+        let mut device = ByteFlippingDevice {
+            inner: Cursor::new(Vec::new()),
+            flip_at: MIN_BLOCK_SIZE as u64 + 7,
+            flips: 1,
+        };
+
+        let result = verify_each_block_writer()
+            .write_with_read_back(Cursor::new(&data), &mut device, data.len() as u64, 0)
+            .unwrap();
+
+        assert_eq!(result.retry_count, 1);
+        assert_eq!(device.inner.into_inner(), data);
+    }
+
+    #[test]
+    fn test_verify_each_block_fails_at_persistent_corruption() {
+        let data = vec![0x5Au8; MIN_BLOCK_SIZE * 3];
+        let flip_at = MIN_BLOCK_SIZE as u64 * 2 + 100;
+        let mut device = ByteFlippingDevice {
+            inner: Cursor::new(Vec::new()),
+            flip_at,
+            flips: u32::MAX,
+        };
+
+        let err = verify_each_block_writer()
+            .write_with_read_back(Cursor::new(&data), &mut device, data.len() as u64, 0)
+            .unwrap_err();
+
+        match err {
+            Error::VerificationFailed {
+                offset,
+                expected,
+                actual,
+            } => {
+                assert_eq!(offset, flip_at);
+                assert_eq!(expected, "0x5a");
+                assert_eq!(actual, "0xa5");
+            }
+            other => panic!("expected VerificationFailed, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_verify_each_block_needs_readable_entry_point() {
+        let data = vec![0x5Au8; MIN_BLOCK_SIZE];
+        let mut target = Cursor::new(Vec::new());
+
+        let err = verify_each_block_writer()
+            .write(Cursor::new(&data), &mut target, data.len() as u64)
+            .unwrap_err();
+
+        assert!(matches!(err, Error::InvalidConfig(_)));
+        assert!(target.get_ref().is_empty());
+    }
+
+    #[test]
+    fn test_verify_each_block_off_by_default() {
+        let data = vec![0x5Au8; MIN_BLOCK_SIZE];
+        let mut device = ByteFlippingDevice {
+            inner: Cursor::new(Vec::new()),
+            flip_at: 0,
+            flips: 1,
+        };
+
+        let config = WriteConfig::new().block_size(MIN_BLOCK_SIZE);
+        Writer::with_config(config)
+            .write(Cursor::new(&data), &mut device, data.len() as u64)
+            .unwrap();
+        assert_eq!(device.inner.get_ref()[0], 0xA5);
+    }
+
+    // -------------------------------------------------------------------------
+    // Read-ahead tests
+    // -------------------------------------------------------------------------
+
+    /// A source that fails after yielding `fail_after` bytes
+    struct FailingReader {
+        inner: Cursor<Vec<u8>>,
+        fail_after: u64,
+    }
+
+    impl Read for FailingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.inner.position() >= self.fail_after {
+                return Err(std::io::Error::other("source went away"));
+            }
+            self.inner.read(buf)
+        }
+    }
+
+    #[test]
+    fn test_config_buffer_count() {
+        assert_eq!(WriteConfig::new().buffer_count, DEFAULT_BUFFER_COUNT);
+        assert_eq!(WriteConfig::new().buffer_count(3).buffer_count, 3);
+        assert_eq!(WriteConfig::new().buffer_count(0).buffer_count, 1);
+        assert_eq!(
+            WriteConfig::new().buffer_count(1000).buffer_count,
+            MAX_BUFFER_COUNT
+        );
+    }
+
+    #[test]
+    fn test_write_same_output_for_each_buffer_count() {
+        let data: Vec<u8> = (0..MIN_BLOCK_SIZE * 7 + 123)
+            .map(|i| (i % 251) as u8)
+            .collect();
+
+        for count in [1, 2, 3, MAX_BUFFER_COUNT] {
+            let config = WriteConfig::new()
+                .block_size(MIN_BLOCK_SIZE)
+                .buffer_count(count);
+            let mut writer = Writer::with_config(config);
+            let mut target = Cursor::new(Vec::new());
+
+            let result = writer
+                .write(
+                    Prefetch::new(Cursor::new(&data)),
+                    &mut target,
+                    data.len() as u64,
+                )
+                .unwrap();
+
+            assert_eq!(result.bytes_written, data.len() as u64, "count {}", count);
+            assert_eq!(target.into_inner(), data, "count {}", count);
+        }
+    }
+
+    #[test]
+    fn test_write_read_ahead_propagates_source_error() {
+        for count in [1, 2, 4] {
+            let source = FailingReader {
+                inner: Cursor::new(vec![0x5Au8; MIN_BLOCK_SIZE * 8]),
+                fail_after: (MIN_BLOCK_SIZE * 3) as u64,
+            };
+            let mut writer = Writer::with_config(
+                WriteConfig::new()
+                    .block_size(MIN_BLOCK_SIZE)
+                    .buffer_count(count),
+            );
+            let mut target = Cursor::new(Vec::new());
+
+            let result = writer.write(
+                Prefetch::new(source),
+                &mut target,
+                (MIN_BLOCK_SIZE * 8) as u64,
+            );
+
+            assert!(matches!(result, Err(Error::Io(_))), "count {}", count);
+            // Blocks read before the failure were still written
+            assert_eq!(target.into_inner().len(), MIN_BLOCK_SIZE * 3);
+        }
+    }
+
+    #[test]
+    fn test_write_accepts_non_send_source() {
+        // `Rc` is not `Send`, so this source can only be read inline
+        struct RcReader(std::rc::Rc<std::cell::RefCell<Cursor<Vec<u8>>>>);
+        impl Read for RcReader {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                self.0.borrow_mut().read(buf)
+            }
+        }
+
+        let data = vec![0x5Au8; MIN_BLOCK_SIZE * 3];
+        let source = RcReader(std::rc::Rc::new(std::cell::RefCell::new(Cursor::new(
+            data.clone(),
+        ))));
+        let mut writer = Writer::with_config(WriteConfig::new().block_size(MIN_BLOCK_SIZE));
+        let mut target = Cursor::new(Vec::new());
+
+        writer
+            .write(source, &mut target, data.len() as u64)
+            .unwrap();
+        assert_eq!(target.into_inner(), data);
+    }
+
+    #[test]
+    fn test_write_read_ahead_cancel_stops_reader() {
+        let data_size = MIN_BLOCK_SIZE * 64;
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .buffer_count(4)
+            .progress_interval(ProgressInterval::EveryBlock);
+        let writer = Writer::with_config(config);
+        let cancel = writer.cancel_handle();
+        let mut writer = writer.on_progress(move |progress| {
+            if progress.current_block >= 2 {
+                cancel.cancel(CancelReason::UserRequested);
+            }
+        });
+        let mut target = Cursor::new(Vec::new());
+
+        let result = writer.write(
+            Prefetch::new(Cursor::new(vec![0u8; data_size])),
+            &mut target,
+            data_size as u64,
+        );
+
+        // Returning at all means the reader thread was joined
+        assert!(matches!(
+            result,
+            Err(Error::Cancelled(CancelReason::UserRequested))
+        ));
+        assert_eq!(target.into_inner().len(), MIN_BLOCK_SIZE * 2);
+    }
+
+    // -------------------------------------------------------------------------
+    // Periodic sync tests
+    // -------------------------------------------------------------------------
+
+    /// An in-memory device that counts flush (sync) calls.
+    struct SyncCountingDevice {
+        inner: Cursor<Vec<u8>>,
+        syncs: usize,
+    }
+
+    impl SyncCountingDevice {
+        fn new() -> Self {
+            Self {
+                inner: Cursor::new(Vec::new()),
+                syncs: 0,
+            }
+        }
+    }
+
+    impl Read for SyncCountingDevice {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.inner.read(buf)
+        }
+    }
+
+    impl Write for SyncCountingDevice {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.inner.write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.syncs += 1;
+            Ok(())
+        }
+    }
+
+    impl Seek for SyncCountingDevice {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    fn count_syncs(config: WriteConfig, blocks: usize) -> usize {
+        let data = vec![0xA5u8; MIN_BLOCK_SIZE * blocks];
+        let mut device = SyncCountingDevice::new();
+        let mut writer = Writer::with_config(config.block_size(MIN_BLOCK_SIZE));
+        writer
+            .write(Cursor::new(&data), &mut device, data.len() as u64)
+            .unwrap();
+        assert_eq!(device.inner.get_ref(), &data);
+        device.syncs
+    }
+
+    #[test]
+    fn test_sync_interval_bytes_syncs_periodically() {
+        let config = WriteConfig::new()
+            .sync_on_complete(false)
+            .sync_interval_bytes(Some(2 * MIN_BLOCK_SIZE as u64));
+        assert_eq!(count_syncs(config, 8), 4);
+    }
+
+    #[test]
+    fn test_sync_interval_bytes_with_sync_on_complete() {
+        let config = WriteConfig::new()
+            .sync_on_complete(true)
+            .sync_interval_bytes(Some(3 * MIN_BLOCK_SIZE as u64));
+        // Two interval syncs (after blocks 3 and 6) plus the final sync
+        assert_eq!(count_syncs(config, 8), 3);
+    }
+
+    #[test]
+    fn test_sync_each_block_overrides_interval() {
+        let config = WriteConfig::new()
+            .sync_each_block(true)
+            .sync_on_complete(false)
+            .sync_interval_bytes(Some(4 * MIN_BLOCK_SIZE as u64));
+        assert_eq!(count_syncs(config, 8), 8);
+    }
+
+    #[test]
+    fn test_no_sync_interval_only_syncs_on_complete() {
+        let config = WriteConfig::new().sync_on_complete(true);
+        assert_eq!(count_syncs(config, 8), 1);
+    }
+
+    // -------------------------------------------------------------------------
+    // StreamTarget tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_stream_target_receives_every_byte() {
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let config = WriteConfig::new().block_size(4096).retry_attempts(0);
+        let mut writer = Writer::with_config(config);
+        let mut target = StreamTarget::new(Vec::new());
+
+        let result = writer
+            .write(Cursor::new(data.clone()), &mut target, data.len() as u64)
+            .unwrap();
+
+        assert_eq!(result.bytes_written, data.len() as u64);
+        assert_eq!(target.position(), data.len() as u64);
+        assert_eq!(target.into_inner(), data);
+    }
+
+    #[test]
+    fn test_stream_target_refuses_seek() {
+        let mut target = StreamTarget::new(Vec::new());
+        assert_eq!(target.seek(SeekFrom::Start(0)).unwrap(), 0);
+        target.write_all(b"abcd").unwrap();
+        assert_eq!(target.stream_position().unwrap(), 4);
+        assert_eq!(
+            target.seek(SeekFrom::Start(0)).unwrap_err().kind(),
+            std::io::ErrorKind::Unsupported
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    // Checkpoint callback tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_on_checkpoint_reports_advancing_progress() {
+        let data = vec![0x5Au8; MIN_BLOCK_SIZE * 4];
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+
+        let mut device = SyncCountingDevice::new();
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .sync_on_complete(false);
+        let mut writer = Writer::with_config(config).on_checkpoint(Duration::ZERO, move |p| {
+            seen_clone.lock().unwrap().push(p.bytes_written);
+        });
+        writer
+            .write(Cursor::new(&data), &mut device, data.len() as u64)
+            .unwrap();
+
+        let block = MIN_BLOCK_SIZE as u64;
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![block, 2 * block, 3 * block, 4 * block]
+        );
+        // Every checkpoint is preceded by a flush
+        assert_eq!(device.syncs, 4);
+    }
+
+    #[test]
+    fn test_on_checkpoint_respects_interval() {
+        let data = vec![0x5Au8; MIN_BLOCK_SIZE * 4];
+        let calls = Arc::new(AtomicU64::new(0));
+        let calls_clone = calls.clone();
+
+        let mut device = SyncCountingDevice::new();
+        let config = WriteConfig::new()
+            .block_size(MIN_BLOCK_SIZE)
+            .sync_on_complete(false);
+        let mut writer =
+            Writer::with_config(config).on_checkpoint(Duration::from_secs(3600), move |_| {
+                calls_clone.fetch_add(1, Ordering::SeqCst);
+            });
+        writer
+            .write(Cursor::new(&data), &mut device, data.len() as u64)
+            .unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(device.syncs, 0);
+    }
+
+    // -------------------------------------------------------------------------
+    // Resume verification tests
+    // -------------------------------------------------------------------------
+
+    fn resume_writer() -> Writer {
+        Writer::with_config(
+            WriteConfig::new()
+                .block_size(MIN_BLOCK_SIZE)
+                .verify_on_resume(true),
+        )
+    }
+
+    #[test]
+    fn test_is_blank_zeroed_and_erased() {
+        let writer = Writer::with_config(WriteConfig::new().block_size(MIN_BLOCK_SIZE));
+        let mut zeroed = Cursor::new(vec![0u8; MIN_BLOCK_SIZE * 32]);
+        let mut erased = Cursor::new(vec![0xFFu8; MIN_BLOCK_SIZE * 32]);
+
+        assert!(writer.is_blank(&mut zeroed, BLANK_CHECK_SAMPLES).unwrap());
+        assert!(writer.is_blank(&mut erased, BLANK_CHECK_SAMPLES).unwrap());
+        assert!(writer.is_blank(&mut Cursor::new(Vec::new()), 4).unwrap());
+    }
+
+    #[test]
+    fn test_is_blank_detects_data_in_sampled_block() {
+        let writer = Writer::with_config(WriteConfig::new().block_size(MIN_BLOCK_SIZE));
+        // The last block is always sampled
+        let mut data = vec![0u8; MIN_BLOCK_SIZE * 32];
+        data[MIN_BLOCK_SIZE * 31 + 7] = 0x42;
+        assert!(!writer.is_blank(&mut Cursor::new(data), 4).unwrap());
+
+        // A block that mixes the two blank patterns is not blank
+        let mut mixed = vec![0u8; MIN_BLOCK_SIZE * 4];
+        mixed[..MIN_BLOCK_SIZE / 2].fill(0xFF);
+        assert!(!writer.is_blank(&mut Cursor::new(mixed), 1).unwrap());
+    }
+
+    #[test]
+    fn test_is_blank_device_smaller_than_block() {
+        let writer = Writer::new();
+        let mut device = Cursor::new(vec![0u8; 1000]);
+        assert!(writer.is_blank(&mut device, BLANK_CHECK_SAMPLES).unwrap());
+    }
+
+    #[test]
+    fn test_verify_resume_offset_disabled_is_noop() {
+        let writer = Writer::with_config(WriteConfig::new().block_size(MIN_BLOCK_SIZE));
+        let mut source = Cursor::new(vec![1u8; MIN_BLOCK_SIZE * 4]);
+        let mut target = Cursor::new(vec![0u8; MIN_BLOCK_SIZE * 4]);
+        let offset = (MIN_BLOCK_SIZE * 3) as u64;
+
+        assert_eq!(
+            writer
+                .verify_resume_offset(&mut source, &mut target, offset)
+                .unwrap(),
+            offset
+        );
+    }
+
+    #[test]
+    fn test_verify_resume_offset_intact_tail() {
+        let data: Vec<u8> = (0..MIN_BLOCK_SIZE * 8).map(|i| (i % 251) as u8).collect();
+        let mut source = Cursor::new(data.clone());
+        let mut target = Cursor::new(data);
+        let offset = (MIN_BLOCK_SIZE * 6) as u64;
+
+        assert_eq!(
+            resume_writer()
+                .verify_resume_offset(&mut source, &mut target, offset)
+                .unwrap(),
+            offset
+        );
+    }
+
+    #[test]
+    fn test_verify_resume_offset_rewinds_to_torn_block() {
+        let block = MIN_BLOCK_SIZE;
+        let data: Vec<u8> = (0..block * 8).map(|i| (i % 251) as u8).collect();
+        let mut written = data.clone();
+        // Block 5 was only half written before the power cut
+        written[block * 5 + block / 2..block * 6].fill(0);
+
+        let mut source = Cursor::new(data);
+        let mut target = Cursor::new(written);
+        let offset = (block * 6) as u64;
+
+        assert_eq!(
+            resume_writer()
+                .verify_resume_offset(&mut source, &mut target, offset)
+                .unwrap(),
+            (block * 5) as u64
+        );
+    }
+
+    #[test]
+    fn test_verify_resume_offset_only_checks_recent_blocks() {
+        let block = MIN_BLOCK_SIZE;
+        let data = vec![0xA5u8; block * 8];
+        let mut written = data.clone();
+        // Corruption before the verification window is not detected
+        written[0] = 0;
+
+        let mut source = Cursor::new(data);
+        let mut target = Cursor::new(written);
+        let offset = (block * 8) as u64;
+
+        assert_eq!(
+            resume_writer()
+                .verify_resume_offset(&mut source, &mut target, offset)
+                .unwrap(),
+            offset
+        );
+    }
 }
@@ -5,7 +5,10 @@
 
 #![cfg(feature = "compression")]
 
-use engraver_core::{detect_source_type, Source, SourceType, WriteConfig, Writer, MIN_BLOCK_SIZE};
+use engraver_core::{
+    detect_source_type, list_tar_entries, validate_source, validate_source_with_entry, Source,
+    SourceType, WriteConfig, Writer, MIN_BLOCK_SIZE,
+};
 use std::io::{Read, Seek, SeekFrom, Write};
 use tempfile::NamedTempFile;
 
@@ -79,6 +82,36 @@ fn create_bzip2_file(data: &[u8]) -> (tempfile::TempDir, String) {
     (dir, path_str)
 }
 
+/// Build a tar archive holding the given (path, data) entries.
+fn build_tar(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut builder = tar::Builder::new(Vec::new());
+    for (path, data) in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, path, *data).unwrap();
+    }
+    builder.into_inner().unwrap()
+}
+
+/// Write a tar (or gzip-compressed tar, if `name` ends in .tar.gz) to a temp dir.
+fn create_tar_file(name: &str, entries: &[(&str, &[u8])]) -> (tempfile::TempDir, String) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(name);
+    let tar = build_tar(entries);
+    let mut file = std::fs::File::create(&path).unwrap();
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::fast());
+        encoder.write_all(&tar).unwrap();
+        encoder.finish().unwrap();
+    } else {
+        file.write_all(&tar).unwrap();
+    }
+    let path_str = path.to_str().unwrap().to_string();
+    (dir, path_str)
+}
+
 // ============================================================================
 // Source type detection by extension
 // ============================================================================
@@ -492,3 +525,119 @@ fn all_formats_4mb_roundtrip() {
         );
     }
 }
+
+// ============================================================================
+// Tar archive tests
+// ============================================================================
+
+#[test]
+fn tar_single_entry_write_pipeline() {
+    let data = test_data(100 * 1024 + 17); // not a multiple of the tar block size
+    let (_dir, path) = create_tar_file("image.tar", &[("image.img", &data)]);
+
+    let info = validate_source(&path).unwrap();
+    assert_eq!(info.source_type, SourceType::Tar);
+    assert_eq!(info.size, Some(data.len() as u64));
+
+    let mut source = Source::open(&path).unwrap();
+    assert_eq!(source.size(), Some(data.len() as u64));
+    let mut device = create_test_device(data.len() as u64);
+
+    let config = WriteConfig::new().block_size(MIN_BLOCK_SIZE);
+    let mut writer = Writer::with_config(config);
+    let result = writer
+        .write(&mut source, device.as_file_mut(), data.len() as u64)
+        .unwrap();
+
+    assert_eq!(result.bytes_written, data.len() as u64);
+    assert_eq!(read_all(device.as_file_mut()), data);
+}
+
+#[test]
+fn tar_gz_single_entry() {
+    let data = test_data(256 * 1024);
+    let (_dir, path) = create_tar_file("image.tar.gz", &[("disk/image.img", &data)]);
+
+    let info = validate_source(&path).unwrap();
+    assert_eq!(info.source_type, SourceType::TarGz);
+    assert_eq!(info.size, Some(data.len() as u64));
+
+    let mut source = Source::open(&path).unwrap();
+    let mut out = Vec::new();
+    source.read_to_end(&mut out).unwrap();
+    assert_eq!(out, data);
+}
+
+#[test]
+fn tar_multiple_entries_requires_entry() {
+    let image = test_data(8 * 1024);
+    let (_dir, path) = create_tar_file(
+        "bundle.tgz",
+        &[("README.txt", b"hello"), ("image.img", &image)],
+    );
+
+    let err = Source::open(&path).err().unwrap().to_string();
+    assert!(err.contains("README.txt"));
+    assert!(err.contains("image.img"));
+    assert!(validate_source(&path).is_err());
+
+    let info = validate_source_with_entry(&path, None, Some("*.img")).unwrap();
+    assert_eq!(info.size, Some(image.len() as u64));
+
+    let mut source = Source::open_with_entry(&path, 0, Some("*.img")).unwrap();
+    let mut out = Vec::new();
+    source.read_to_end(&mut out).unwrap();
+    assert_eq!(out, image);
+}
+
+#[test]
+fn tar_reopen_keeps_selected_entry() {
+    let image = test_data(8 * 1024);
+    let (_dir, path) = create_tar_file(
+        "bundle.tar",
+        &[("README.txt", b"hello"), ("image.img", &image)],
+    );
+
+    let mut source = Source::open_with_entry(&path, 0, Some("*.img")).unwrap();
+    let mut out = Vec::new();
+    source.read_to_end(&mut out).unwrap();
+
+    let mut reopened = source.reopen().unwrap();
+    let mut again = Vec::new();
+    reopened.read_to_end(&mut again).unwrap();
+    assert_eq!(again, image);
+}
+
+#[test]
+fn tar_list_entries_skips_directories() {
+    let mut builder = tar::Builder::new(Vec::new());
+    let mut dir_header = tar::Header::new_gnu();
+    dir_header.set_entry_type(tar::EntryType::Directory);
+    dir_header.set_size(0);
+    dir_header.set_mode(0o755);
+    dir_header.set_cksum();
+    builder
+        .append_data(&mut dir_header, "out/", std::io::empty())
+        .unwrap();
+    let mut header = tar::Header::new_gnu();
+    header.set_size(4);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, "out/image.img", &b"data"[..])
+        .unwrap();
+    let tar = builder.into_inner().unwrap();
+
+    let entries = list_tar_entries(&tar[..]).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].path, "out/image.img");
+    assert_eq!(entries[0].size, 4);
+    assert_eq!(entries[0].data_offset, 1024);
+}
+
+#[test]
+fn tar_cannot_resume() {
+    let data = test_data(4096);
+    let (_dir, path) = create_tar_file("image.tar", &[("image.img", &data)]);
+    assert!(Source::open_with_offset(&path, 512).is_err());
+}
@@ -5,11 +5,14 @@
 
 #![cfg(feature = "remote")]
 
+use engraver_core::source::HttpSource;
 use engraver_core::{
-    detect_source_type, validate_source, Source, SourceType, WriteConfig, Writer, MIN_BLOCK_SIZE,
+    detect_source_type, get_source_size, validate_source, ChecksumAlgorithm, DownloadCache,
+    HttpClient, HttpOptions, Source, SourceType, WriteConfig, Writer, MIN_BLOCK_SIZE,
 };
 use std::io::{Read, Seek, SeekFrom, Write};
 use std::sync::Arc;
+use std::time::Duration;
 use tempfile::NamedTempFile;
 use tiny_http::{Header, Response, Server, StatusCode};
 
@@ -33,6 +36,23 @@ enum MockBehavior {
     ServeWithResume(Vec<u8>),
     /// Return a fixed status code with a body
     StatusCode(u16, String),
+    /// Return 503 for the first N requests, then serve data
+    FailThenServe(usize, Vec<u8>),
+    /// Serve data with an ETag, honouring Range only when If-Range matches it
+    ServeWithEtag(Vec<u8>, String),
+    /// Serve data with one extra response header (name, value)
+    ServeWithHeader(Vec<u8>, &'static str, String),
+    /// Serve data only to requests carrying header (name, value), else 403
+    RequireHeader(Vec<u8>, &'static str, String),
+}
+
+/// Value of request header `name`, compared case-insensitively
+fn request_header(request: &tiny_http::Request, name: &str) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|h| h.field.as_str().as_str().eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str().to_string())
 }
 
 fn start_mock(behavior: MockBehavior) -> MockHttpServer {
@@ -42,7 +62,9 @@ fn start_mock(behavior: MockBehavior) -> MockHttpServer {
 
     let server_clone = Arc::clone(&server);
     std::thread::spawn(move || {
+        let mut requests_seen = 0;
         while let Ok(request) = server_clone.recv() {
+            requests_seen += 1;
             match &behavior {
                 MockBehavior::ServeData(data) => {
                     let response = Response::from_data(data.clone()).with_header(
@@ -117,6 +139,52 @@ fn start_mock(behavior: MockBehavior) -> MockHttpServer {
                         Response::from_string(body.clone()).with_status_code(StatusCode(*code));
                     let _ = request.respond(response);
                 }
+                MockBehavior::ServeWithHeader(data, name, value) => {
+                    let response = Response::from_data(data.clone()).with_header(
+                        Header::from_bytes(name.as_bytes(), value.as_bytes()).unwrap(),
+                    );
+                    let _ = request.respond(response);
+                }
+                MockBehavior::RequireHeader(data, name, value) => {
+                    let response = if request_header(&request, name).as_ref() == Some(value) {
+                        Response::from_data(data.clone())
+                    } else {
+                        Response::from_data(b"Forbidden".to_vec()).with_status_code(StatusCode(403))
+                    };
+                    let _ = request.respond(response);
+                }
+                MockBehavior::ServeWithEtag(data, etag) => {
+                    let start = request_header(&request, "Range")
+                        .and_then(|r| {
+                            r.strip_prefix("bytes=")?
+                                .strip_suffix('-')?
+                                .parse::<usize>()
+                                .ok()
+                        })
+                        .filter(|_| {
+                            request_header(&request, "If-Range").is_none_or(|tag| tag == *etag)
+                        });
+                    let (status, body) = match start {
+                        Some(start) => (206, data[start.min(data.len())..].to_vec()),
+                        None => (200, data.clone()),
+                    };
+                    let response = Response::from_data(body)
+                        .with_status_code(StatusCode(status))
+                        .with_header(Header::from_bytes(b"ETag", etag.as_bytes()).unwrap())
+                        .with_header(
+                            Header::from_bytes(b"Accept-Ranges", b"bytes" as &[u8]).unwrap(),
+                        );
+                    let _ = request.respond(response);
+                }
+                MockBehavior::FailThenServe(failures, data) => {
+                    let response = if requests_seen <= *failures {
+                        Response::from_string("Service Unavailable")
+                            .with_status_code(StatusCode(503))
+                    } else {
+                        Response::from_data(data.clone())
+                    };
+                    let _ = request.respond(response);
+                }
             }
         }
     });
@@ -127,6 +195,48 @@ fn start_mock(behavior: MockBehavior) -> MockHttpServer {
     }
 }
 
+/// Start a raw TCP server whose first response is cut off after `cut_at`
+/// bytes; later requests are answered from their `Range` header.
+fn start_dropping_server(data: Vec<u8>, cut_at: usize) -> String {
+    use std::io::BufRead;
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    std::thread::spawn(move || {
+        for (index, stream) in listener.incoming().enumerate() {
+            let Ok(mut stream) = stream else { break };
+
+            // Read request headers, picking out the range start
+            let mut start = 0;
+            let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap_or(0) > 0 && line != "\r\n" {
+                if let Some(range) = line.to_lowercase().strip_prefix("range: bytes=") {
+                    start = range.trim().trim_end_matches('-').parse().unwrap_or(0);
+                }
+                line.clear();
+            }
+
+            let (status, body) = if index == 0 {
+                ("200 OK", &data[..cut_at])
+            } else {
+                ("206 Partial Content", &data[start..])
+            };
+            let length = if index == 0 { data.len() } else { body.len() };
+            let header = format!(
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nAccept-Ranges: bytes\r\nConnection: close\r\n\r\n",
+                status, length
+            );
+            let _ = stream.write_all(header.as_bytes());
+            let _ = stream.write_all(body);
+        }
+    });
+
+    url
+}
+
 // ============================================================================
 // Helpers
 // ============================================================================
@@ -183,8 +293,6 @@ fn http_source_basic_download() {
 
 #[test]
 fn http_source_content_length_reported() {
-    use engraver_core::source::HttpSource;
-
     let data = test_data(32 * 1024);
     let server = start_mock(MockBehavior::ServeData(data.clone()));
     let url = format!("{}/image.iso", server.url);
@@ -331,6 +439,122 @@ fn http_source_connection_refused() {
     );
 }
 
+// ============================================================================
+// Retry with backoff
+// ============================================================================
+
+// ============================================================================
+// Shared and injected clients
+// ============================================================================
+
+#[test]
+fn http_source_uses_injected_client_headers() {
+    let data = test_data(4096);
+    let server = start_mock(MockBehavior::RequireHeader(
+        data.clone(),
+        "X-Engraver-Token",
+        "secret".to_string(),
+    ));
+    let url = format!("{}/image.img", server.url);
+
+    // The shared client does not send the header
+    assert!(HttpSource::open(&url).is_err());
+
+    let client = HttpClient::builder()
+        .header("X-Engraver-Token", "secret")
+        .build()
+        .unwrap();
+    let options = HttpOptions::new().client(client.clone());
+    assert_eq!(options.client, Some(client));
+
+    let mut source = HttpSource::open_with_options(&url, 0, options).unwrap();
+    let mut received = Vec::new();
+    source.read_to_end(&mut received).unwrap();
+    assert_eq!(received, data);
+}
+
+#[test]
+fn http_client_rejects_invalid_header() {
+    let err = HttpClient::builder()
+        .header("bad header", "value")
+        .build()
+        .unwrap_err();
+    assert!(err.to_string().contains("header name"));
+}
+
+#[test]
+fn http_shared_client_is_reused() {
+    assert_eq!(HttpClient::shared().unwrap(), HttpClient::shared().unwrap());
+    assert_ne!(
+        HttpClient::shared().unwrap(),
+        HttpClient::builder().build().unwrap()
+    );
+}
+
+#[test]
+fn http_get_source_size_reads_content_length() {
+    let data = test_data(12_345);
+    let server = start_mock(MockBehavior::ServeData(data));
+    let url = format!("{}/image.img", server.url);
+
+    assert_eq!(get_source_size(&url).unwrap(), Some(12_345));
+}
+
+fn fast_retry_options() -> HttpOptions {
+    HttpOptions::new()
+        .max_retries(3)
+        .backoff_base(Duration::from_millis(10))
+}
+
+#[test]
+fn http_source_retries_503_then_succeeds() {
+    let data = test_data(16 * 1024);
+    let server = start_mock(MockBehavior::FailThenServe(2, data.clone()));
+    let url = format!("{}/flaky.iso", server.url);
+
+    let mut source = HttpSource::open_with_options(&url, 0, fast_retry_options()).unwrap();
+    let mut buf = Vec::new();
+    source.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, data);
+}
+
+#[test]
+fn http_source_gives_up_after_max_retries() {
+    let server = start_mock(MockBehavior::FailThenServe(10, test_data(1024)));
+    let url = format!("{}/down.iso", server.url);
+
+    let result = HttpSource::open_with_options(&url, 0, fast_retry_options());
+    assert!(result.is_err());
+}
+
+#[test]
+fn http_source_no_retry_on_404() {
+    let server = start_mock(MockBehavior::StatusCode(404, "Not Found".to_string()));
+    let url = format!("{}/missing.iso", server.url);
+
+    let start = std::time::Instant::now();
+    let options = fast_retry_options().backoff_base(Duration::from_secs(5));
+    assert!(HttpSource::open_with_options(&url, 0, options).is_err());
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn http_source_resumes_dropped_stream() {
+    let data = test_data(256 * 1024);
+    let url = format!(
+        "{}/image.iso",
+        start_dropping_server(data.clone(), 100 * 1024)
+    );
+
+    let mut source = HttpSource::open_with_options(&url, 0, fast_retry_options()).unwrap();
+    let mut buf = Vec::new();
+    source.read_to_end(&mut buf).unwrap();
+
+    assert_eq!(buf.len(), data.len());
+    assert_eq!(buf, data);
+    assert_eq!(source.bytes_read(), data.len() as u64);
+}
+
 // ============================================================================
 // Write + verify via HTTP
 // ============================================================================
@@ -455,3 +679,214 @@ fn http_multiple_requests_same_server() {
         assert_eq!(buf, data, "Request {} failed", i);
     }
 }
+
+// ============================================================================
+// Download cache
+// ============================================================================
+
+#[test]
+fn download_cache_fetches_and_reuses_copy() {
+    let data = test_data(96 * 1024);
+    let server = start_mock(MockBehavior::ServeWithResume(data.clone()));
+    let url = format!("{}/image.img", server.url);
+    let dir = tempfile::TempDir::new().unwrap();
+
+    let progress = Arc::new(std::sync::atomic::AtomicU64::new(0));
+    let progress_clone = Arc::clone(&progress);
+    let mut cache = DownloadCache::new(dir.path()).on_progress(move |done, _| {
+        progress_clone.store(done, std::sync::atomic::Ordering::SeqCst);
+    });
+
+    let path = cache.fetch(&url).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), data);
+    assert_eq!(
+        progress.load(std::sync::atomic::Ordering::SeqCst),
+        data.len() as u64
+    );
+    assert!(!path.with_extension("img.part").exists());
+
+    // A complete copy is reused as-is
+    assert_eq!(cache.fetch(&url).unwrap(), path);
+}
+
+#[test]
+fn download_cache_reuses_copy_only_with_etag() {
+    let data = test_data(8 * 1024);
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut cache = DownloadCache::new(dir.path());
+
+    // With an ETag and a matching size, the cached copy is trusted
+    let tagged = start_mock(MockBehavior::ServeWithEtag(data.clone(), "\"v1\"".into()));
+    let url = format!("{}/image.img", tagged.url);
+    let path = cache.fetch(&url).unwrap();
+    std::fs::write(&path, vec![0xEE; data.len()]).unwrap();
+    assert_eq!(
+        std::fs::read(cache.fetch(&url).unwrap()).unwrap(),
+        vec![0xEE; data.len()]
+    );
+
+    // Without one, a same-sized copy may be stale and is fetched again
+    let untagged = start_mock(MockBehavior::ServeData(data.clone()));
+    let url = format!("{}/image.img", untagged.url);
+    let path = cache.fetch(&url).unwrap();
+    std::fs::write(&path, vec![0xEE; data.len()]).unwrap();
+    assert_eq!(std::fs::read(cache.fetch(&url).unwrap()).unwrap(), data);
+}
+
+#[test]
+fn download_cache_resumes_partial_download() {
+    let data = test_data(128 * 1024);
+    let server = start_mock(MockBehavior::ServeWithResume(data.clone()));
+    let url = format!("{}/image.img", server.url);
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut cache = DownloadCache::new(dir.path());
+
+    // Leave a marked first half behind, as an interrupted download would
+    let half = data.len() / 2;
+    let path = cache.path_for(&url, None);
+    std::fs::write(path.with_extension("img.part"), vec![0xEE; half]).unwrap();
+
+    let fetched = cache.fetch(&url).unwrap();
+    let cached = std::fs::read(&fetched).unwrap();
+
+    assert_eq!(fetched, path);
+    assert!(cached[..half].iter().all(|&b| b == 0xEE));
+    assert_eq!(&cached[half..], &data[half..]);
+}
+
+#[test]
+fn download_cache_restarts_without_range_support() {
+    let data = test_data(64 * 1024);
+    let server = start_mock(MockBehavior::ServeData(data.clone()));
+    let url = format!("{}/image.img", server.url);
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut cache = DownloadCache::new(dir.path());
+
+    let path = cache.path_for(&url, None);
+    std::fs::write(path.with_extension("img.part"), vec![0xEE; 1024]).unwrap();
+
+    let fetched = cache.fetch(&url).unwrap();
+    assert_eq!(std::fs::read(fetched).unwrap(), data);
+}
+
+#[test]
+fn download_cache_rejects_non_http_source() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = NamedTempFile::new().unwrap();
+    let mut cache = DownloadCache::new(dir.path());
+
+    assert!(cache.fetch(file.path().to_str().unwrap()).is_err());
+}
+
+// ============================================================================
+// Validate-then-open Tests
+// ============================================================================
+
+#[test]
+fn http_open_from_info_reuses_validation() {
+    let data = test_data(4096);
+    let mock = start_mock(MockBehavior::ServeWithEtag(data.clone(), "\"v1\"".into()));
+    let url = format!("{}/image.img", mock.url);
+
+    let info = validate_source(&url).unwrap();
+    assert_eq!(info.etag.as_deref(), Some("\"v1\""));
+
+    let mut source = Source::open_from_info(&info, 0).unwrap();
+    assert_eq!(source.info().size, Some(4096));
+    assert!(source.info().resumable);
+    let mut buf = Vec::new();
+    source.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, data);
+}
+
+#[test]
+fn http_open_from_info_resumes_with_matching_etag() {
+    let data = test_data(4096);
+    let mock = start_mock(MockBehavior::ServeWithEtag(data.clone(), "\"v1\"".into()));
+    let url = format!("{}/image.img", mock.url);
+
+    let info = validate_source(&url).unwrap();
+    let mut source = HttpSource::open_from_info(&info, 1000, HttpOptions::default()).unwrap();
+    assert!(source.resumed());
+    assert_eq!(source.info().size, Some(4096));
+    let mut buf = Vec::new();
+    source.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, &data[1000..]);
+}
+
+#[test]
+fn http_open_from_info_rejects_changed_file_on_resume() {
+    let mock = start_mock(MockBehavior::ServeWithEtag(
+        test_data(4096),
+        "\"v2\"".into(),
+    ));
+    let url = format!("{}/image.img", mock.url);
+
+    let mut info = validate_source(&url).unwrap();
+    info.etag = Some("\"v1\"".to_string());
+    let err = HttpSource::open_from_info(&info, 1000, HttpOptions::default())
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("changed since it was checked"));
+}
+
+#[test]
+fn http_open_from_info_rejects_ignored_range() {
+    let mock = start_mock(MockBehavior::ServeData(test_data(4096)));
+    let url = format!("{}/image.img", mock.url);
+
+    let info = validate_source(&url).unwrap();
+    let err = HttpSource::open_from_info(&info, 1000, HttpOptions::default())
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("ignored the range request"));
+}
+
+#[test]
+fn http_validate_captures_repr_digest() {
+    let mock = start_mock(MockBehavior::ServeWithHeader(
+        b"hello".to_vec(),
+        "Repr-Digest",
+        "sha-256=:LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=:".into(),
+    ));
+    let url = format!("{}/image.img", mock.url);
+
+    let info = validate_source(&url).unwrap();
+    let (algorithm, hex) = info.server_digest.unwrap();
+    assert_eq!(algorithm, ChecksumAlgorithm::Sha256);
+    assert_eq!(
+        hex,
+        "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+    );
+}
+
+#[test]
+fn http_open_captures_content_md5() {
+    let mock = start_mock(MockBehavior::ServeWithHeader(
+        b"hello".to_vec(),
+        "Content-MD5",
+        "XUFAKrxLKna5cZ2REBfFkg==".into(),
+    ));
+    let url = format!("{}/image.img", mock.url);
+
+    let source = HttpSource::open(&url).unwrap();
+    assert_eq!(
+        source.info().server_digest,
+        Some((
+            ChecksumAlgorithm::Md5,
+            "5d41402abc4b2a76b9719d911017c592".to_string()
+        ))
+    );
+}
+
+#[test]
+fn http_ignores_unknown_digest() {
+    let mock = start_mock(MockBehavior::ServeWithHeader(
+        b"hello".to_vec(),
+        "Repr-Digest",
+        "crc32c=:mnG7TA==:".into(),
+    ));
+    let url = format!("{}/image.img", mock.url);
+
+    assert!(validate_source(&url).unwrap().server_digest.is_none());
+}
@@ -3,9 +3,9 @@
 //! These tests verify the complete write pipeline using temporary files.
 
 use engraver_core::{
-    detect_source_type, format_duration, format_speed, get_source_size, validate_source, Error,
-    Source, SourceInfo, SourceType, WriteConfig, WriteProgress, Writer, DEFAULT_BLOCK_SIZE,
-    MAX_BLOCK_SIZE, MIN_BLOCK_SIZE,
+    detect_source_type, format_duration, format_speed, get_source_size, validate_source,
+    CancelReason, Error, ProgressInterval, Source, SourceInfo, SourceType, WriteConfig,
+    WriteProgress, Writer, DEFAULT_BLOCK_SIZE, MAX_BLOCK_SIZE, MIN_BLOCK_SIZE,
 };
 use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -70,7 +70,9 @@ fn test_write_with_progress_tracking() {
     let progress_updates_clone = Arc::clone(&progress_updates);
     let last_percentage_clone = Arc::clone(&last_percentage);
 
-    let config = WriteConfig::new().block_size(4096); // 4 KB blocks = 4 updates
+    let config = WriteConfig::new()
+        .block_size(4096) // 4 KB blocks = 4 updates
+        .progress_interval(ProgressInterval::EveryBlock);
     let mut writer = Writer::with_config(config).on_progress(move |progress| {
         progress_updates_clone.fetch_add(1, Ordering::SeqCst);
         let mut last = last_percentage_clone.lock().unwrap();
@@ -97,23 +99,28 @@ fn test_write_cancellation() {
     let source = Cursor::new(source_data);
     let target = Cursor::new(vec![0u8; size]);
 
-    let config = WriteConfig::new().block_size(4096);
+    let config = WriteConfig::new()
+        .block_size(4096)
+        .progress_interval(ProgressInterval::EveryBlock);
     let writer = Writer::with_config(config);
 
     let cancel_handle = writer.cancel_handle();
 
     // Set up progress callback to cancel after first block
-    let cancel = Arc::clone(&cancel_handle);
+    let cancel = cancel_handle.clone();
     let writer = writer.on_progress(move |progress| {
         if progress.current_block >= 1 {
-            cancel.store(true, Ordering::SeqCst);
+            cancel.cancel(CancelReason::UserRequested);
         }
     });
 
     let mut writer = writer;
     let result = writer.write(source, target, size as u64);
 
-    assert!(matches!(result, Err(Error::Cancelled)));
+    assert!(matches!(
+        result,
+        Err(Error::Cancelled(CancelReason::UserRequested))
+    ));
 }
 
 #[test]
@@ -229,10 +236,10 @@ fn test_write_progress_calculations() {
     assert_eq!(progress.percentage(), 0.0);
     assert!(!progress.is_complete());
 
-    progress.bytes_written = 500;
+    progress.current_offset = 500;
     assert_eq!(progress.percentage(), 50.0);
 
-    progress.bytes_written = 1000;
+    progress.current_offset = 1000;
     assert!(progress.is_complete());
     assert_eq!(progress.percentage(), 100.0);
 }
@@ -329,8 +336,8 @@ fn test_format_duration_ranges() {
 
 #[test]
 fn test_error_display() {
-    let err = Error::Cancelled;
-    assert_eq!(err.to_string(), "Operation cancelled");
+    let err = Error::Cancelled(CancelReason::UserRequested);
+    assert_eq!(err.to_string(), "Operation cancelled: requested by user");
 
     let err = Error::PartialWrite {
         expected: 4096,
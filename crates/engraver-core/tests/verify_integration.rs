@@ -3,7 +3,9 @@
 //! Tests the Verifier against real temporary files, exercising file I/O
 //! code paths for both byte-comparison and checksum verification.
 
-use engraver_core::{Verifier, VerifyConfig, WriteConfig, Writer, MIN_VERIFY_BLOCK_SIZE};
+use engraver_core::{
+    CancelReason, Verifier, VerifyConfig, WriteConfig, Writer, MIN_VERIFY_BLOCK_SIZE,
+};
 use std::io::{Seek, SeekFrom, Write};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -285,7 +287,7 @@ fn verify_cancellation() {
     // Cancel after the first progress callback (compare() resets the flag on entry)
     let cancel_clone = cancel.clone();
     let mut verifier = verifier.on_progress(move |_p| {
-        cancel_clone.store(true, Ordering::SeqCst);
+        cancel_clone.cancel(CancelReason::UserRequested);
     });
 
     let result = verifier.compare(
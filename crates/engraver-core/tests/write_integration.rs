@@ -4,8 +4,8 @@
 //! exercising file I/O paths that in-memory Cursor tests don't cover.
 
 use engraver_core::{
-    ChecksumAlgorithm, WriteConfig, WritePhase, WriteProgress, Writer, DEFAULT_BLOCK_SIZE,
-    MAX_BLOCK_SIZE, MIN_BLOCK_SIZE,
+    CancelReason, ChecksumAlgorithm, ProgressInterval, Source, WriteConfig, WritePhase,
+    WriteProgress, Writer, DEFAULT_BLOCK_SIZE, MAX_BLOCK_SIZE, MIN_BLOCK_SIZE,
 };
 use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -326,7 +326,9 @@ fn write_cancellation_mid_stream() {
     let source = Cursor::new(data);
     let mut device = create_test_device(1024 * 1024);
 
-    let config = WriteConfig::new().block_size(MIN_BLOCK_SIZE); // 4 KB blocks = 256 blocks
+    let config = WriteConfig::new()
+        .block_size(MIN_BLOCK_SIZE) // 4 KB blocks = 256 blocks
+        .progress_interval(ProgressInterval::EveryBlock);
     let writer = Writer::with_config(config);
     let cancel = writer.cancel_handle();
 
@@ -338,7 +340,7 @@ fn write_cancellation_mid_stream() {
         bytes_clone.store(p.bytes_written, Ordering::SeqCst);
         // Cancel after writing ~25% (64 blocks)
         if p.current_block >= 64 {
-            cancel_clone.store(true, Ordering::SeqCst);
+            cancel_clone.cancel(CancelReason::UserRequested);
         }
     });
 
@@ -418,6 +420,26 @@ fn write_from_file_source_to_file_device() {
     assert_eq!(read_all(device.as_file_mut()), data);
 }
 
+// ============================================================================
+// In-memory source (Source::from_bytes path)
+// ============================================================================
+
+#[test]
+fn write_from_memory_source_to_cursor() {
+    let data = test_data(200 * 1024);
+    let mut source = Source::from_bytes(data.clone(), "memory.img");
+    let total = source.size().unwrap();
+    let mut target = Cursor::new(vec![0u8; data.len()]);
+
+    let config = WriteConfig::new().block_size(64 * 1024);
+    let mut writer = Writer::with_config(config);
+
+    let result = writer.write(&mut source, &mut target, total).unwrap();
+
+    assert_eq!(result.bytes_written, data.len() as u64);
+    assert_eq!(target.into_inner(), data);
+}
+
 // ============================================================================
 // Random data (catches buffer reuse bugs)
 // ============================================================================
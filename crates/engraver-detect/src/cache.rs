@@ -0,0 +1,189 @@
+//! Cached drive enumeration
+//!
+//! [`list_drives`] shells out to system tools on every call. Callers that
+//! poll (a TUI, `write --watch`) can hold a [`DriveCache`] instead, which
+//! only re-enumerates once the previous result is older than a given age.
+
+use std::time::{Duration, Instant};
+
+use crate::{list_drives, Drive, Result};
+
+/// Source of drive enumerations for a [`DriveCache`]
+#[cfg_attr(test, mockall::automock)]
+pub trait DriveEnumerator {
+    /// Enumerate all attached drives
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if enumeration fails.
+    fn enumerate(&self) -> Result<Vec<Drive>>;
+}
+
+/// Enumerates drives with [`list_drives`]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemEnumerator;
+
+impl DriveEnumerator for SystemEnumerator {
+    fn enumerate(&self) -> Result<Vec<Drive>> {
+        list_drives()
+    }
+}
+
+/// The last drive enumeration and when it was taken
+///
+/// This is opt-in; [`list_drives`] itself never caches.
+#[derive(Debug)]
+pub struct DriveCache<E = SystemEnumerator> {
+    enumerator: E,
+    cached: Option<(Instant, Vec<Drive>)>,
+}
+
+impl DriveCache {
+    /// Create an empty cache backed by [`list_drives`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_enumerator(SystemEnumerator)
+    }
+}
+
+impl Default for DriveCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: DriveEnumerator> DriveCache<E> {
+    /// Create an empty cache backed by a custom enumerator
+    pub fn with_enumerator(enumerator: E) -> Self {
+        Self {
+            enumerator,
+            cached: None,
+        }
+    }
+
+    /// Drives from the cache, re-enumerating if it is empty or older than `max_age`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a re-enumeration is needed and fails. The
+    /// previous result is kept, but the cache stays stale.
+    pub fn get(&mut self, max_age: Duration) -> Result<&[Drive]> {
+        let fresh = self.age().is_some_and(|age| age <= max_age);
+        if fresh {
+            return Ok(self.cached_drives());
+        }
+        self.refresh()
+    }
+
+    /// Re-enumerate now, regardless of the cache's age
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if enumeration fails; the previous result is kept.
+    pub fn refresh(&mut self) -> Result<&[Drive]> {
+        let drives = self.enumerator.enumerate()?;
+        self.cached = Some((Instant::now(), drives));
+        Ok(self.cached_drives())
+    }
+
+    /// Drop the cached result so the next [`get`](Self::get) re-enumerates
+    pub fn invalidate(&mut self) {
+        self.cached = None;
+    }
+
+    /// Time since the cached result was taken, or `None` if there is none
+    #[must_use]
+    pub fn age(&self) -> Option<Duration> {
+        self.cached.as_ref().map(|(taken, _)| taken.elapsed())
+    }
+
+    fn cached_drives(&self) -> &[Drive] {
+        self.cached.as_ref().map_or(&[], |(_, drives)| drives)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DetectError;
+
+    const LONG: Duration = Duration::MAX;
+
+    fn drive(path: &str) -> Drive {
+        Drive {
+            path: path.to_string(),
+            ..Drive::default()
+        }
+    }
+
+    #[test]
+    fn test_get_reuses_fresh_result() {
+        let mut enumerator = MockDriveEnumerator::new();
+        enumerator
+            .expect_enumerate()
+            .times(1)
+            .returning(|| Ok(vec![drive("/dev/sdb")]));
+        let mut cache = DriveCache::with_enumerator(enumerator);
+
+        assert_eq!(cache.get(LONG).unwrap()[0].path, "/dev/sdb");
+        assert_eq!(cache.get(LONG).unwrap().len(), 1);
+        assert!(cache.age().is_some());
+    }
+
+    #[test]
+    fn test_get_re_enumerates_when_stale() {
+        let mut enumerator = MockDriveEnumerator::new();
+        enumerator
+            .expect_enumerate()
+            .times(2)
+            .returning(|| Ok(Vec::new()));
+        let mut cache = DriveCache::with_enumerator(enumerator);
+
+        cache.get(Duration::ZERO).unwrap();
+        std::thread::sleep(Duration::from_millis(2));
+        cache.get(Duration::ZERO).unwrap();
+    }
+
+    #[test]
+    fn test_invalidate_forces_re_enumeration() {
+        let mut enumerator = MockDriveEnumerator::new();
+        let mut seq = mockall::Sequence::new();
+        enumerator
+            .expect_enumerate()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|| Ok(vec![drive("/dev/sdb")]));
+        enumerator
+            .expect_enumerate()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|| Ok(vec![drive("/dev/sdb"), drive("/dev/sdc")]));
+        let mut cache = DriveCache::with_enumerator(enumerator);
+
+        assert_eq!(cache.get(LONG).unwrap().len(), 1);
+        cache.invalidate();
+        assert!(cache.age().is_none());
+        assert_eq!(cache.get(LONG).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_failed_refresh_keeps_previous_result() {
+        let mut enumerator = MockDriveEnumerator::new();
+        let mut seq = mockall::Sequence::new();
+        enumerator
+            .expect_enumerate()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|| Ok(vec![drive("/dev/sdb")]));
+        enumerator
+            .expect_enumerate()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|| Err(DetectError::UnsupportedPlatform));
+        let mut cache = DriveCache::with_enumerator(enumerator);
+
+        cache.get(LONG).unwrap();
+        assert!(cache.refresh().is_err());
+        assert_eq!(cache.get(LONG).unwrap()[0].path, "/dev/sdb");
+    }
+}
@@ -7,6 +7,7 @@
 //!
 //! This crate uses multiple heuristics to identify system drives:
 //! - Drives containing mount points like `/`, `/home`, `C:\`
+//! - The disk the running OS booted from, even with nothing on it mounted
 //! - Non-removable internal drives
 //! - Drives with system partitions (EFI, Recovery, etc.)
 //!
@@ -15,10 +16,15 @@
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 
+use engraver_platform::glob_match;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use thiserror::Error;
 
+mod cache;
+
+pub use cache::{DriveCache, DriveEnumerator, SystemEnumerator};
+
 /// Drive detection errors
 #[derive(Error, Debug)]
 #[non_exhaustive]
@@ -37,6 +43,14 @@ pub enum DetectError {
     #[error("Permission denied: {0}")]
     PermissionDenied(String),
 
+    /// No drive matched a [`TargetSelector`]
+    #[error("{0}")]
+    DriveNotFound(String),
+
+    /// More than one drive matched a [`TargetSelector`]
+    #[error("{0}")]
+    AmbiguousSelector(String),
+
     /// Platform not supported
     #[error("Platform not supported")]
     UnsupportedPlatform,
@@ -71,10 +85,14 @@ pub enum DriveType {
     Usb,
     /// SD card (via built-in or USB reader)
     SdCard,
+    /// Soldered-on eMMC storage (usually the boot drive on single-board computers)
+    Emmc,
     /// `NVMe` drive (external/portable)
     Nvme,
     /// SATA drive
     Sata,
+    /// Drive behind a Thunderbolt or USB4 link, usually an external `NVMe` enclosure
+    Thunderbolt,
     /// Other/unknown connection type
     #[default]
     Other,
@@ -85,13 +103,33 @@ impl fmt::Display for DriveType {
         match self {
             DriveType::Usb => write!(f, "USB"),
             DriveType::SdCard => write!(f, "SD Card"),
+            DriveType::Emmc => write!(f, "eMMC"),
             DriveType::Nvme => write!(f, "NVMe"),
             DriveType::Sata => write!(f, "SATA"),
+            DriveType::Thunderbolt => write!(f, "Thunderbolt"),
             DriveType::Other => write!(f, "Other"),
         }
     }
 }
 
+impl DriveType {
+    /// Classify a bus or protocol name as reported by the OS
+    ///
+    /// Accepts the names used by sysfs subsystems, `diskutil` and WMI,
+    /// ignoring case. Returns `None` for a bus that says nothing about the
+    /// connection, such as `pci`.
+    pub(crate) fn from_bus(bus: &str) -> Option<Self> {
+        match bus.trim().to_ascii_lowercase().as_str() {
+            "usb" | "usb-storage" => Some(DriveType::Usb),
+            "thunderbolt" => Some(DriveType::Thunderbolt),
+            "nvme" | "pcie" | "pci-express" => Some(DriveType::Nvme),
+            "sata" | "sas" | "ata" | "scsi" => Some(DriveType::Sata),
+            "sd" | "secure digital" => Some(DriveType::SdCard),
+            _ => None,
+        }
+    }
+}
+
 /// USB connection speed
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[non_exhaustive]
@@ -163,6 +201,7 @@ impl fmt::Display for UsbSpeed {
 
 /// Represents a detected drive/device
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)] // independent facts reported by the platform
 pub struct Drive {
     /// Device path (e.g., `/dev/sdb`, `/dev/disk2`, `\\.\PhysicalDrive1`)
     pub path: String,
@@ -205,6 +244,26 @@ pub struct Drive {
 
     /// USB connection speed (only for USB drives)
     pub usb_speed: Option<UsbSpeed>,
+
+    /// Whether media is inserted (false for an empty card reader)
+    #[serde(default = "media_present_default")]
+    pub media_present: bool,
+
+    /// Whether this is a SATA disk behind a USB bridge (an enclosure or dock)
+    #[serde(default)]
+    pub enclosure: bool,
+
+    /// Whether the drive reports spinning media
+    #[serde(default)]
+    pub rotational: bool,
+
+    /// Why writing this drive deserves an extra confirmation, even though it is safe
+    #[serde(default)]
+    pub caution_reason: Option<String>,
+}
+
+fn media_present_default() -> bool {
+    true
 }
 
 impl Default for Drive {
@@ -224,6 +283,10 @@ impl Default for Drive {
             partitions: Vec::new(),
             system_reason: None,
             usb_speed: None,
+            media_present: true,
+            enclosure: false,
+            rotational: false,
+            caution_reason: None,
         }
     }
 }
@@ -245,6 +308,62 @@ pub struct Partition {
 
     /// Mount point if mounted
     pub mount_point: Option<String>,
+
+    /// Bytes in use on the mounted filesystem (`None` if unmounted or unknown)
+    pub used: Option<u64>,
+
+    /// Bytes free for ordinary users on the mounted filesystem (`None` if unmounted or unknown)
+    pub available: Option<u64>,
+}
+
+impl Partition {
+    /// Compact space usage such as `3.1/7.4 GB used`, if it is known
+    ///
+    /// The total is `used + available`, so blocks reserved for root are
+    /// left out.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn usage_display(&self) -> Option<String> {
+        let used = self.used?;
+        let total = used + self.available?;
+        let (unit, name) = byte_unit(total);
+        if unit == 1 {
+            return Some(format!("{used}/{total} B used"));
+        }
+        Some(format!(
+            "{:.1}/{:.1} {name} used",
+            used as f64 / unit as f64,
+            total as f64 / unit as f64
+        ))
+    }
+}
+
+/// Fill in [`Partition::used`] and [`Partition::available`] for mounted partitions
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub(crate) fn add_filesystem_space(partitions: &mut [Partition]) {
+    for partition in partitions {
+        let space = partition.mount_point.as_deref().and_then(filesystem_space);
+        if let Some((used, available)) = space {
+            partition.used = Some(used);
+            partition.available = Some(available);
+        }
+    }
+}
+
+/// Used and available bytes of the filesystem mounted at `mount_point`
+///
+/// Returns `None` if it cannot be stat'd.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+// The block counts are `u32` on macOS and `u64` on Linux
+#[allow(clippy::useless_conversion)]
+pub(crate) fn filesystem_space(mount_point: &str) -> Option<(u64, u64)> {
+    let stat = nix::sys::statvfs::statvfs(mount_point)
+        .inspect_err(|e| tracing::debug!("Failed to stat {mount_point}: {e}"))
+        .ok()?;
+    let fragment = u64::from(stat.fragment_size());
+    let used = u64::from(stat.blocks()).saturating_sub(u64::from(stat.blocks_free()));
+    let available = u64::from(stat.blocks_available());
+    Some((used * fragment, available * fragment))
 }
 
 impl Drive {
@@ -312,12 +431,27 @@ impl Drive {
         self.removable && !self.is_system
     }
 
+    /// Check if this looks like a drive built into the machine
+    ///
+    /// Disks in a USB enclosure are external even when they report as
+    /// non-removable.
+    #[must_use]
+    pub fn is_internal(&self) -> bool {
+        !self.removable && !self.enclosure
+    }
+
     /// Format size for human-readable display
     #[must_use]
     pub fn size_display(&self) -> String {
         format_bytes(self.size)
     }
 
+    /// Total size of all known partitions on the drive, in bytes
+    #[must_use]
+    pub fn partitions_size(&self) -> u64 {
+        self.partitions.iter().map(|p| p.size).sum()
+    }
+
     /// Get a display string for the drive
     #[must_use]
     pub fn display_name(&self) -> String {
@@ -336,21 +470,54 @@ impl Drive {
 #[must_use]
 #[allow(clippy::cast_precision_loss)]
 pub fn format_bytes(bytes: u64) -> String {
+    let (unit, name) = byte_unit(bytes);
+    if unit == 1 {
+        format!("{bytes} B")
+    } else {
+        format!("{:.1} {name}", bytes as f64 / unit as f64)
+    }
+}
+
+/// The largest binary unit not above `bytes`, as its size and name
+fn byte_unit(bytes: u64) -> (u64, &'static str) {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
     const GB: u64 = MB * 1024;
     const TB: u64 = GB * 1024;
 
-    if bytes >= TB {
-        format!("{:.1} TB", bytes as f64 / TB as f64)
-    } else if bytes >= GB {
-        format!("{:.1} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.1} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.1} KB", bytes as f64 / KB as f64)
+    [(TB, "TB"), (GB, "GB"), (MB, "MB"), (KB, "KB")]
+        .into_iter()
+        .find(|&(unit, _)| bytes >= unit)
+        .unwrap_or((1, "B"))
+}
+
+/// Removable drives larger than this get a [`Drive::caution_reason`]
+///
+/// USB sticks and SD cards rarely exceed it; USB hard drives, which often
+/// hold backups, usually do.
+pub const LARGE_REMOVABLE_SIZE: u64 = 256 * 1024 * 1024 * 1024;
+
+/// Why a safe target still deserves an extra confirmation, if it does
+///
+/// Only removable, non-system drives get one: the others are refused or
+/// warned about already.
+#[must_use]
+pub fn caution_reason(drive: &Drive) -> Option<String> {
+    if !drive.is_safe_target() {
+        return None;
+    }
+    if drive.rotational {
+        Some("Spinning hard disk, which often holds backups or other data".to_string())
+    } else if drive.enclosure {
+        Some("Disk in a USB enclosure or dock, not a flash drive".to_string())
+    } else if drive.size > LARGE_REMOVABLE_SIZE {
+        Some(format!(
+            "Larger than {} ({}), unusually big for a flash drive",
+            format_bytes(LARGE_REMOVABLE_SIZE),
+            drive.size_display()
+        ))
     } else {
-        format!("{bytes} B")
+        None
     }
 }
 
@@ -389,18 +556,23 @@ pub fn is_system_mount_point(mount_point: &str) -> bool {
 cfg_if::cfg_if! {
     if #[cfg(target_os = "linux")] {
         mod linux;
-        pub use linux::list_drives;
+        pub use linux::{current_boot_disk, list_drives};
     } else if #[cfg(target_os = "macos")] {
         mod macos;
-        pub use macos::list_drives;
+        pub use macos::{current_boot_disk, list_drives};
     } else if #[cfg(target_os = "windows")] {
         mod windows;
-        pub use windows::list_drives;
+        pub use windows::{current_boot_disk, list_drives};
     } else {
         /// List removable drives (unsupported platform)
         pub fn list_drives() -> Result<Vec<Drive>> {
             Err(DetectError::UnsupportedPlatform)
         }
+
+        /// Find the disk the running system booted from (unsupported platform)
+        pub fn current_boot_disk() -> Result<Option<String>> {
+            Err(DetectError::UnsupportedPlatform)
+        }
     }
 }
 
@@ -428,6 +600,85 @@ pub fn list_all_drives() -> Result<Vec<Drive>> {
     list_drives()
 }
 
+/// How to pick a target drive
+///
+/// Device paths like `/dev/sdb` can change across reboots and replugs; a
+/// serial number or partition label picks the same stick every time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TargetSelector {
+    /// Device path, matched against both [`Drive::path`] and [`Drive::raw_path`]
+    Path(String),
+    /// Exact [`Drive::serial`]
+    Serial(String),
+    /// Exact label of any of the drive's partitions
+    Label(String),
+}
+
+impl TargetSelector {
+    /// Check whether `drive` is selected
+    #[must_use]
+    pub fn matches(&self, drive: &Drive) -> bool {
+        match self {
+            TargetSelector::Path(path) => drive.path == *path || drive.raw_path == *path,
+            TargetSelector::Serial(serial) => drive.serial.as_deref() == Some(serial.as_str()),
+            TargetSelector::Label(label) => drive
+                .partitions
+                .iter()
+                .any(|p| p.label.as_deref() == Some(label.as_str())),
+        }
+    }
+}
+
+impl fmt::Display for TargetSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TargetSelector::Path(path) => write!(f, "{path}"),
+            TargetSelector::Serial(serial) => write!(f, "serial number {serial}"),
+            TargetSelector::Label(label) => write!(f, "partition label {label}"),
+        }
+    }
+}
+
+/// Find the one drive in `drives` chosen by `selector`
+///
+/// # Errors
+///
+/// Returns [`DetectError::DriveNotFound`] if no drive matches, or
+/// [`DetectError::AmbiguousSelector`] if more than one does.
+pub fn find_drive_by<'a>(drives: &'a [Drive], selector: &TargetSelector) -> Result<&'a Drive> {
+    let matches: Vec<&Drive> = drives.iter().filter(|d| selector.matches(d)).collect();
+    match matches.as_slice() {
+        [drive] => Ok(drive),
+        [] => Err(DetectError::DriveNotFound(match selector {
+            TargetSelector::Path(path) => format!("Device not found: {path}"),
+            _ => format!("No drive with {selector}"),
+        })),
+        _ => Err(DetectError::AmbiguousSelector(format!(
+            "{} drives match {}: {}",
+            matches.len(),
+            selector,
+            matches
+                .iter()
+                .map(|d| d.path.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+/// Drives whose [`Drive::path`] or [`Drive::raw_path`] match a shell-style glob
+///
+/// Supports `*`, `?` and bracket classes with ranges and negation, so
+/// `/dev/sd[b-f]` picks `/dev/sdb` through `/dev/sdf`. Safety is left to the
+/// caller: system and non-removable drives match like any other.
+#[must_use]
+pub fn find_drives_matching<'a>(drives: &'a [Drive], pattern: &str) -> Vec<&'a Drive> {
+    drives
+        .iter()
+        .filter(|d| glob_match(pattern, &d.path) || glob_match(pattern, &d.raw_path))
+        .collect()
+}
+
 /// Validate that a device path is safe to write to
 ///
 /// Returns the Drive if valid and safe, or an error explaining why not.
@@ -439,24 +690,46 @@ pub fn list_all_drives() -> Result<Vec<Drive>> {
 /// - The specified device is not found
 /// - The device is a system drive
 /// - The device is not removable
+/// - The device has no media inserted
 pub fn validate_target(device_path: &str) -> Result<Drive> {
+    validate_target_by(&TargetSelector::Path(device_path.to_string()))
+}
+
+/// Validate that the drive chosen by `selector` is safe to write to
+///
+/// Like [`validate_target`], but the drive can also be picked by serial
+/// number or partition label.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - Drive enumeration fails
+/// - No drive, or more than one, matches the selector
+/// - The device is a system drive
+/// - The device is not removable
+/// - The device has no media inserted
+pub fn validate_target_by(selector: &TargetSelector) -> Result<Drive> {
     let drives = list_drives()?;
+    let drive = find_drive_by(&drives, selector)?;
+    check_target(drive)?;
+    Ok(drive.clone())
+}
 
-    // Find the drive
-    let drive = drives
-        .into_iter()
-        .find(|d| d.path == device_path || d.raw_path == device_path)
-        .ok_or_else(|| DetectError::EnumerationFailed {
-            message: format!("Device not found: {device_path}"),
+/// Safety checks shared by [`validate_target`] and [`validate_target_by`]
+fn check_target(drive: &Drive) -> Result<()> {
+    if !drive.media_present {
+        return Err(DetectError::EnumerationFailed {
+            message: format!("No card inserted in {}", drive.path),
             source: None,
-        })?;
+        });
+    }
 
     // Check if safe
     if drive.is_system {
         return Err(DetectError::EnumerationFailed {
             message: format!(
                 "Refusing to use system drive: {} ({})",
-                device_path,
+                drive.path,
                 drive
                     .system_reason
                     .as_deref()
@@ -469,13 +742,14 @@ pub fn validate_target(device_path: &str) -> Result<Drive> {
     if !drive.removable {
         return Err(DetectError::EnumerationFailed {
             message: format!(
-                "Drive is not removable: {device_path}. Use --force to override (dangerous!)"
+                "Drive is not removable: {}. Use --force to override (dangerous!)",
+                drive.path
             ),
             source: None,
         });
     }
 
-    Ok(drive)
+    Ok(())
 }
 
 // ============================================================================
@@ -709,6 +983,114 @@ mod tests {
         assert_eq!(deserialized.drive_type, DriveType::Usb);
     }
 
+    #[test]
+    fn test_drive_is_internal() {
+        assert!(Drive::new("/dev/sda").is_internal());
+        assert!(!Drive::new("/dev/sdb").with_removable(true).is_internal());
+
+        let docked = Drive {
+            enclosure: true,
+            ..Drive::new("/dev/sdc")
+        };
+        assert!(!docked.is_internal());
+    }
+
+    #[test]
+    fn test_caution_reason_small_stick_has_none() {
+        let stick = Drive::new("/dev/sdb")
+            .with_removable(true)
+            .with_size(64 * 1024 * 1024 * 1024);
+        assert!(caution_reason(&stick).is_none());
+
+        let at_limit = stick.with_size(LARGE_REMOVABLE_SIZE);
+        assert!(caution_reason(&at_limit).is_none());
+    }
+
+    #[test]
+    fn test_caution_reason_large_removable() {
+        let drive = Drive::new("/dev/sdb")
+            .with_removable(true)
+            .with_size(2 * 1024 * 1024 * 1024 * 1024);
+        let reason = caution_reason(&drive).unwrap();
+        assert!(reason.contains("256.0 GB"));
+        assert!(reason.contains("2.0 TB"));
+    }
+
+    #[test]
+    fn test_caution_reason_spinning_and_enclosure() {
+        let hdd = Drive {
+            rotational: true,
+            ..Drive::new("/dev/sdb").with_removable(true)
+        };
+        assert!(caution_reason(&hdd).unwrap().contains("Spinning"));
+
+        let docked = Drive {
+            enclosure: true,
+            ..Drive::new("/dev/sdb").with_removable(true)
+        };
+        assert!(caution_reason(&docked).unwrap().contains("enclosure"));
+    }
+
+    #[test]
+    fn test_caution_reason_skips_unsafe_targets() {
+        let system = Drive {
+            rotational: true,
+            ..Drive::new("/dev/sda").with_system(true, None)
+        };
+        assert!(caution_reason(&system).is_none());
+    }
+
+    // -------------------------------------------------------------------------
+    // find_drives_matching tests
+    // -------------------------------------------------------------------------
+
+    fn paths(drives: &[&Drive]) -> Vec<String> {
+        drives.iter().map(|d| d.path.clone()).collect()
+    }
+
+    #[test]
+    fn test_find_drives_matching_range() {
+        let drives: Vec<Drive> = [
+            "/dev/sda",
+            "/dev/sdb",
+            "/dev/sdc",
+            "/dev/sdg",
+            "/dev/nvme0n1",
+        ]
+        .into_iter()
+        .map(Drive::new)
+        .collect();
+
+        let matched = find_drives_matching(&drives, "/dev/sd[b-f]");
+        assert_eq!(paths(&matched), ["/dev/sdb", "/dev/sdc"]);
+
+        let matched = find_drives_matching(&drives, "/dev/sd[!a]");
+        assert_eq!(paths(&matched), ["/dev/sdb", "/dev/sdc", "/dev/sdg"]);
+    }
+
+    #[test]
+    fn test_find_drives_matching_wildcards() {
+        let drives: Vec<Drive> = ["/dev/sdb", "/dev/sdbb", "/dev/mmcblk0"]
+            .into_iter()
+            .map(Drive::new)
+            .collect();
+
+        assert_eq!(find_drives_matching(&drives, "/dev/sd?").len(), 1);
+        assert_eq!(find_drives_matching(&drives, "/dev/sd*").len(), 2);
+        assert_eq!(find_drives_matching(&drives, "*").len(), 3);
+        assert!(find_drives_matching(&drives, "/dev/sdz").is_empty());
+    }
+
+    #[test]
+    fn test_find_drives_matching_raw_path() {
+        let drive = Drive {
+            raw_path: "/dev/rdisk2".to_string(),
+            ..Drive::new("/dev/disk2")
+        };
+        let drives = [drive];
+        assert_eq!(find_drives_matching(&drives, "/dev/rdisk[0-9]").len(), 1);
+    }
+
     // -------------------------------------------------------------------------
     // DriveType tests
     // -------------------------------------------------------------------------
@@ -717,11 +1099,36 @@ mod tests {
     fn test_drive_type_display() {
         assert_eq!(DriveType::Usb.to_string(), "USB");
         assert_eq!(DriveType::SdCard.to_string(), "SD Card");
+        assert_eq!(DriveType::Emmc.to_string(), "eMMC");
         assert_eq!(DriveType::Nvme.to_string(), "NVMe");
         assert_eq!(DriveType::Sata.to_string(), "SATA");
+        assert_eq!(DriveType::Thunderbolt.to_string(), "Thunderbolt");
         assert_eq!(DriveType::Other.to_string(), "Other");
     }
 
+    #[test]
+    fn test_drive_type_from_bus() {
+        assert_eq!(
+            DriveType::from_bus("thunderbolt"),
+            Some(DriveType::Thunderbolt)
+        );
+        assert_eq!(
+            DriveType::from_bus("Thunderbolt"),
+            Some(DriveType::Thunderbolt)
+        );
+        assert_eq!(DriveType::from_bus("USB"), Some(DriveType::Usb));
+        assert_eq!(DriveType::from_bus("usb-storage"), Some(DriveType::Usb));
+        assert_eq!(DriveType::from_bus("PCI-Express"), Some(DriveType::Nvme));
+        assert_eq!(DriveType::from_bus("NVMe"), Some(DriveType::Nvme));
+        assert_eq!(DriveType::from_bus("scsi"), Some(DriveType::Sata));
+        assert_eq!(
+            DriveType::from_bus("Secure Digital"),
+            Some(DriveType::SdCard)
+        );
+        assert_eq!(DriveType::from_bus("pci"), None);
+        assert_eq!(DriveType::from_bus(""), None);
+    }
+
     #[test]
     fn test_drive_type_default() {
         assert_eq!(DriveType::default(), DriveType::Other);
@@ -755,6 +1162,8 @@ mod tests {
             filesystem: Some("vfat".to_string()),
             size: 512 * 1024 * 1024,
             mount_point: Some("/media/usb".to_string()),
+            used: Some(1024),
+            available: None,
         };
 
         let json = serde_json::to_string(&part).expect("Should serialize");
@@ -762,6 +1171,54 @@ mod tests {
 
         assert_eq!(deserialized.path, "/dev/sdb1");
         assert_eq!(deserialized.label, Some("UBUNTU".to_string()));
+        assert_eq!(deserialized.used, Some(1024));
+        assert!(deserialized.available.is_none());
+    }
+
+    #[test]
+    fn test_partition_usage_display() {
+        const GB: u64 = 1024 * 1024 * 1024;
+        let mut part = Partition {
+            used: Some(GB * 31 / 10),
+            available: Some(GB * 43 / 10),
+            ..Default::default()
+        };
+        assert_eq!(part.usage_display().as_deref(), Some("3.1/7.4 GB used"));
+
+        part.used = Some(100);
+        part.available = Some(400);
+        assert_eq!(part.usage_display().as_deref(), Some("100/500 B used"));
+
+        part.available = None;
+        assert!(part.usage_display().is_none());
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn test_filesystem_space_of_root() {
+        let (used, available) = filesystem_space("/").expect("/ should be stat'able");
+        assert!(used > 0 || available > 0);
+        assert!(filesystem_space("/nonexistent/mount").is_none());
+    }
+
+    #[test]
+    fn test_drive_partitions_size() {
+        let mut drive = Drive::new("/dev/sdb");
+        assert_eq!(drive.partitions_size(), 0);
+
+        drive.partitions = vec![
+            Partition {
+                path: "/dev/sdb1".to_string(),
+                size: 512 * 1024 * 1024,
+                ..Default::default()
+            },
+            Partition {
+                path: "/dev/sdb2".to_string(),
+                size: 1024 * 1024 * 1024,
+                ..Default::default()
+            },
+        ];
+        assert_eq!(drive.partitions_size(), 1536 * 1024 * 1024);
     }
 
     // -------------------------------------------------------------------------
@@ -932,4 +1389,134 @@ mod tests {
         assert_eq!(drive.path, "/dev/sdb");
         assert_eq!(drive.raw_path, "/dev/sdb");
     }
+
+    // -------------------------------------------------------------------------
+    // TargetSelector tests
+    // -------------------------------------------------------------------------
+
+    fn labelled(path: &str, serial: &str, labels: &[&str]) -> Drive {
+        Drive {
+            serial: Some(serial.to_string()),
+            partitions: labels
+                .iter()
+                .enumerate()
+                .map(|(i, label)| Partition {
+                    path: format!("{path}{}", i + 1),
+                    label: Some((*label).to_string()),
+                    filesystem: None,
+                    size: 0,
+                    mount_point: None,
+                    used: None,
+                    available: None,
+                })
+                .collect(),
+            ..Drive::new(path)
+        }
+    }
+
+    #[test]
+    fn test_find_drive_by_path_or_raw_path() {
+        let mut drive = Drive::new("/dev/disk2");
+        drive.raw_path = "/dev/rdisk2".to_string();
+        let drives = vec![Drive::new("/dev/disk1"), drive];
+
+        let by_raw = TargetSelector::Path("/dev/rdisk2".to_string());
+        assert_eq!(find_drive_by(&drives, &by_raw).unwrap().path, "/dev/disk2");
+
+        let missing = TargetSelector::Path("/dev/disk9".to_string());
+        let err = find_drive_by(&drives, &missing).unwrap_err();
+        assert!(matches!(err, DetectError::DriveNotFound(_)));
+        assert_eq!(err.to_string(), "Device not found: /dev/disk9");
+    }
+
+    #[test]
+    fn test_find_drive_by_serial_is_exact() {
+        let drives = vec![
+            labelled("/dev/sdb", "4C530001", &[]),
+            labelled("/dev/sdc", "4C5300012", &[]),
+        ];
+
+        let selector = TargetSelector::Serial("4C530001".to_string());
+        assert_eq!(find_drive_by(&drives, &selector).unwrap().path, "/dev/sdb");
+
+        let err = find_drive_by(&drives, &TargetSelector::Serial("4C53".to_string())).unwrap_err();
+        assert_eq!(err.to_string(), "No drive with serial number 4C53");
+    }
+
+    #[test]
+    fn test_find_drive_by_any_partition_label() {
+        let drives = vec![
+            labelled("/dev/sdb", "A", &["boot", "rootfs"]),
+            labelled("/dev/sdc", "B", &["DATA"]),
+        ];
+
+        let selector = TargetSelector::Label("rootfs".to_string());
+        assert_eq!(find_drive_by(&drives, &selector).unwrap().path, "/dev/sdb");
+        assert!(find_drive_by(&drives, &TargetSelector::Label("data".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_find_drive_by_ambiguous() {
+        let drives = vec![
+            labelled("/dev/sdb", "A", &["boot"]),
+            labelled("/dev/sdc", "B", &["boot"]),
+        ];
+
+        let err = find_drive_by(&drives, &TargetSelector::Label("boot".to_string())).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2 drives match partition label boot: /dev/sdb, /dev/sdc"
+        );
+    }
+    // -------------------------------------------------------------------------
+    // check_target tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_check_target_accepts_removable() {
+        let drive = Drive::new("/dev/sdb")
+            .with_size(16_000_000_000)
+            .with_removable(true);
+        assert!(check_target(&drive).is_ok());
+    }
+
+    #[test]
+    fn test_check_target_rejects_empty_reader() {
+        let drive = Drive {
+            media_present: false,
+            ..Drive::new("/dev/sdb").with_removable(true)
+        };
+
+        let err = check_target(&drive).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Failed to enumerate drives: No card inserted in /dev/sdb"
+        );
+    }
+
+    #[test]
+    fn test_check_target_rejects_system_and_fixed() {
+        let system = Drive::new("/dev/sda")
+            .with_removable(true)
+            .with_system(true, Some("Contains /".to_string()));
+        assert!(check_target(&system)
+            .unwrap_err()
+            .to_string()
+            .contains("Refusing to use system drive"));
+
+        let fixed = Drive::new("/dev/sdc");
+        assert!(check_target(&fixed)
+            .unwrap_err()
+            .to_string()
+            .contains("not removable"));
+    }
+
+    #[test]
+    fn test_drive_deserializes_without_media_present() {
+        let mut value = serde_json::to_value(Drive::new("/dev/sdb")).unwrap();
+        value.as_object_mut().unwrap().remove("media_present");
+
+        let drive: Drive = serde_json::from_value(value).unwrap();
+        assert!(drive.media_present);
+    }
 }
@@ -2,10 +2,13 @@
 //!
 //! Uses /sys/block for device enumeration and /proc/mounts for mount point detection.
 
-use super::{is_system_mount_point, DetectError, Drive, DriveType, Partition, Result, UsbSpeed};
+use super::{
+    add_filesystem_space, caution_reason, is_system_mount_point, DetectError, Drive, DriveType,
+    Partition, Result, UsbSpeed,
+};
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::{debug, trace, warn};
 
 /// Mount information for a device
@@ -27,6 +30,7 @@ pub fn list_drives() -> Result<Vec<Drive>> {
     let mut drives = Vec::new();
     let mount_map = get_mount_info()?;
     let label_map = get_partition_labels();
+    let boot_disks = read_boot_disks();
 
     let block_dir = Path::new("/sys/block");
     if !block_dir.exists() {
@@ -45,7 +49,8 @@ pub fn list_drives() -> Result<Vec<Drive>> {
             continue;
         }
 
-        if let Some(drive) = parse_block_device(&name, &mount_map, &label_map) {
+        let boot_disk = boot_disks.contains(&name);
+        if let Some(drive) = parse_block_device(&name, &mount_map, &label_map, boot_disk) {
             drives.push(drive);
         }
     }
@@ -61,6 +66,18 @@ pub(crate) fn should_skip_device(name: &str) -> bool {
         || name.starts_with("zram")
         || name.starts_with("sr")  // CD/DVD drives
         || name.starts_with("fd") // Floppy drives
+        || is_mmc_hardware_partition(name)
+}
+
+/// Check if a device is an eMMC boot or RPMB hardware partition
+///
+/// These appear in /sys/block alongside the main `mmcblkN` device but are
+/// not independently writable targets.
+pub(crate) fn is_mmc_hardware_partition(name: &str) -> bool {
+    name.strip_prefix("mmcblk").is_some_and(|rest| {
+        let suffix = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+        suffix.starts_with("boot") || suffix == "rpmb"
+    })
 }
 
 /// Parse a block device from /sys/block
@@ -68,6 +85,7 @@ fn parse_block_device(
     name: &str,
     mount_map: &HashMap<String, MountInfo>,
     label_map: &HashMap<String, String>,
+    boot_disk: bool,
 ) -> Option<Drive> {
     let sys_path = format!("/sys/block/{name}");
     let dev_path = format!("/dev/{name}");
@@ -88,15 +106,14 @@ fn parse_block_device(
         })
         .map_or(0, |sectors| sectors * 512);
 
-    if size == 0 {
+    let removable = read_sys_value(&format!("{sys_path}/removable")).is_ok_and(|s| s.trim() == "1");
+
+    // An empty card reader reports size 0 but is still worth listing
+    if size == 0 && !removable {
         debug!("Skipping device {name}: size is 0");
         return None;
     }
 
-    let removable = read_sys_value(&format!("{sys_path}/removable"))
-        .map(|s| s.trim() == "1")
-        .unwrap_or(false);
-
     let vendor = read_sys_value(&format!("{sys_path}/device/vendor"))
         .ok()
         .map(|s| s.trim().to_string())
@@ -108,6 +125,12 @@ fn parse_block_device(
         .filter(|s| !s.is_empty());
 
     let drive_type = detect_drive_type(name, &sys_path);
+    let udev = read_udev_properties(&sys_path);
+    let serial = disk_serial(&udev);
+    let enclosure = is_enclosure(drive_type, &udev);
+    let sysfs_rotational =
+        read_sys_value(&format!("{sys_path}/queue/rotational")).is_ok_and(|s| s == "1");
+    let rotational = is_rotational(drive_type, &udev, sysfs_rotational);
     let partitions = get_partitions(name, mount_map, label_map);
 
     let mount_points: Vec<String> = partitions
@@ -115,7 +138,8 @@ fn parse_block_device(
         .filter_map(|p| p.mount_point.clone())
         .collect();
 
-    let (is_system, system_reason) = check_if_system_drive(name, &mount_points, removable);
+    let (is_system, system_reason) =
+        check_if_system_drive(name, &mount_points, removable, drive_type, boot_disk);
 
     // Detect USB speed for USB drives
     let usb_speed = if drive_type == DriveType::Usb {
@@ -131,7 +155,7 @@ fn parse_block_device(
         (None, None) => name.to_string(),
     };
 
-    Some(Drive {
+    let mut drive = Drive {
         path: dev_path.clone(),
         raw_path: dev_path,
         name: display_name,
@@ -141,12 +165,18 @@ fn parse_block_device(
         drive_type,
         vendor,
         model,
-        serial: None,
+        serial,
         mount_points,
         partitions,
         system_reason,
         usb_speed,
-    })
+        media_present: size > 0,
+        enclosure,
+        rotational,
+        caution_reason: None,
+    };
+    drive.caution_reason = caution_reason(&drive);
+    Some(drive)
 }
 
 /// Get mount info from /proc/mounts (mount point and filesystem type)
@@ -297,10 +327,19 @@ fn get_partitions(
                 // Get mount info (mount point and filesystem)
                 let mount_info = mount_map.get(&part_path);
                 let mount_point = mount_info.map(|m| m.mount_point.clone());
-                let filesystem = mount_info.and_then(|m| m.filesystem.clone());
+
+                // Unmounted partitions have no mount entry, so fall back to
+                // the filesystem probe udev recorded for the device
+                let udev = read_udev_properties(&part_sys_path);
+                let filesystem = mount_info
+                    .and_then(|m| m.filesystem.clone())
+                    .or_else(|| non_empty(udev.get("ID_FS_TYPE")));
 
                 // Get label from /dev/disk/by-label/
-                let label = label_map.get(&part_path).cloned();
+                let label = label_map
+                    .get(&part_path)
+                    .cloned()
+                    .or_else(|| non_empty(udev.get("ID_FS_LABEL")));
 
                 partitions.push(Partition {
                     path: part_path,
@@ -308,44 +347,49 @@ fn get_partitions(
                     filesystem,
                     size,
                     mount_point,
+                    used: None,
+                    available: None,
                 });
             }
         }
     }
 
     partitions.sort_by(|a, b| a.path.cmp(&b.path));
+    add_filesystem_space(&mut partitions);
     partitions
 }
 
 /// Detect drive type from name and sysfs info
 pub(crate) fn detect_drive_type(name: &str, sys_path: &str) -> DriveType {
-    // NVMe devices
+    // NVMe devices, internal or in a Thunderbolt enclosure
     if name.starts_with("nvme") {
+        if is_thunderbolt_attached(sys_path) {
+            return DriveType::Thunderbolt;
+        }
         return DriveType::Nvme;
     }
 
-    // SD cards via MMC subsystem
+    // SD cards and eMMC via MMC subsystem
     if name.starts_with("mmcblk") {
-        return DriveType::SdCard;
+        let card_type = read_sys_value(&format!("{sys_path}/device/type")).ok();
+        let has_boot_partitions = Path::new(&format!("{sys_path}boot0")).exists()
+            || Path::new(&format!("{sys_path}boot1")).exists();
+        return classify_mmc_device(card_type.as_deref(), has_boot_partitions);
     }
 
     // Check USB via device subsystem
     if let Ok(subsystem_link) = fs::read_link(format!("{sys_path}/device/subsystem")) {
         if let Some(subsystem_name) = subsystem_link.file_name() {
             let sub = subsystem_name.to_string_lossy();
-            if sub == "usb" || sub == "usb-storage" {
-                return DriveType::Usb;
-            }
-            if sub == "ata" || sub == "scsi" {
-                return DriveType::Sata;
+            if let Some(drive_type @ (DriveType::Usb | DriveType::Sata)) = DriveType::from_bus(&sub)
+            {
+                return drive_type;
             }
         }
     }
 
     // Check removable attribute as fallback for USB
-    let removable = read_sys_value(&format!("{sys_path}/removable"))
-        .map(|s| s.trim() == "1")
-        .unwrap_or(false);
+    let removable = read_sys_value(&format!("{sys_path}/removable")).is_ok_and(|s| s.trim() == "1");
 
     if removable && name.starts_with("sd") {
         return DriveType::Usb;
@@ -354,11 +398,57 @@ pub(crate) fn detect_drive_type(name: &str, sys_path: &str) -> DriveType {
     DriveType::Other
 }
 
+/// Check whether an `NVMe` controller sits behind a Thunderbolt/USB4 port
+///
+/// The kernel marks PCI functions behind a Thunderbolt port as `removable`.
+/// That alone also matches hot-plug bays, so a device must also be
+/// connected on the `thunderbolt` bus.
+fn is_thunderbolt_attached(sys_path: &str) -> bool {
+    let pci_removable = read_sys_value(&format!("{sys_path}/device/device/removable"))
+        .is_ok_and(|s| s == "removable");
+    if !pci_removable {
+        return false;
+    }
+
+    fs::read_dir("/sys/bus/thunderbolt/devices").is_ok_and(|entries| {
+        entries
+            .filter_map(std::result::Result::ok)
+            .any(|entry| is_thunderbolt_device_name(&entry.file_name().to_string_lossy()))
+    })
+}
+
+/// Whether a `/sys/bus/thunderbolt/devices` entry is a connected device
+///
+/// Routers are named `{domain}-{route}`; route 0 is the host's own
+/// controller. Entries with a `:` are services or retimers, not devices.
+pub(crate) fn is_thunderbolt_device_name(name: &str) -> bool {
+    !name.contains(':')
+        && name
+            .split_once('-')
+            .is_some_and(|(_, route)| route.parse::<u64>().is_ok_and(|r| r != 0))
+}
+
+/// Classify an `mmcblk` device as an SD card or eMMC
+///
+/// `card_type` is the contents of `device/type` ("SD", "MMC", "SDIO").
+/// When it is unavailable, the presence of `boot0`/`boot1` hardware
+/// partitions identifies eMMC, since SD cards never have them.
+pub(crate) fn classify_mmc_device(card_type: Option<&str>, has_boot_partitions: bool) -> DriveType {
+    match card_type.map(str::trim) {
+        Some(t) if t.eq_ignore_ascii_case("MMC") => DriveType::Emmc,
+        Some(t) if t.eq_ignore_ascii_case("SD") => DriveType::SdCard,
+        _ if has_boot_partitions => DriveType::Emmc,
+        _ => DriveType::SdCard,
+    }
+}
+
 /// Check if a drive is a system drive
 pub(crate) fn check_if_system_drive(
     name: &str,
     mount_points: &[String],
     removable: bool,
+    drive_type: DriveType,
+    boot_disk: bool,
 ) -> (bool, Option<String>) {
     // Check mount points for system paths
     for mp in mount_points {
@@ -367,6 +457,16 @@ pub(crate) fn check_if_system_drive(
         }
     }
 
+    // Catches boot disks whose root sits on LVM, dm-crypt or RAID
+    if boot_disk {
+        return (true, Some("Boot disk of the running system".to_string()));
+    }
+
+    // eMMC is soldered on and almost always holds the OS on single-board computers
+    if drive_type == DriveType::Emmc {
+        return (true, Some("Internal eMMC storage".to_string()));
+    }
+
     // Non-removable drives are likely system drives
     // Exception: some external NVMe drives report as non-removable
     if !removable && !name.starts_with("nvme") {
@@ -376,6 +476,134 @@ pub(crate) fn check_if_system_drive(
     (false, None)
 }
 
+/// Mount points whose backing disk holds the running system
+const BOOT_MOUNT_POINTS: [&str; 2] = ["/", "/boot/efi"];
+
+/// Find the disk the running system booted from, e.g. `/dev/nvme0n1`
+///
+/// Resolves the device mounted at `/` (or, failing that, `/boot/efi`) to its
+/// whole disk, through partitions and stacked devices such as LVM, dm-crypt
+/// and md RAID. Returns `None` if neither can be resolved, e.g. when the root
+/// is a network or overlay filesystem.
+///
+/// # Errors
+///
+/// Returns an error if `/proc/self/mountinfo` can't be read.
+pub fn current_boot_disk() -> Result<Option<String>> {
+    let mountinfo = read_mountinfo()?;
+    Ok(boot_disk_names(&mountinfo, Path::new("/sys"))
+        .into_iter()
+        .next()
+        .map(|name| format!("/dev/{name}")))
+}
+
+/// Names of every disk backing `/` or `/boot/efi`, empty if unknown
+fn read_boot_disks() -> Vec<String> {
+    match read_mountinfo() {
+        Ok(mountinfo) => boot_disk_names(&mountinfo, Path::new("/sys")),
+        Err(e) => {
+            debug!("Boot disk detection skipped: {e}");
+            Vec::new()
+        }
+    }
+}
+
+fn read_mountinfo() -> Result<String> {
+    fs::read_to_string("/proc/self/mountinfo").map_err(|e| DetectError::EnumerationFailed {
+        message: "Failed to read /proc/self/mountinfo".to_string(),
+        source: Some(e),
+    })
+}
+
+/// Resolve [`BOOT_MOUNT_POINTS`] to whole-disk names using the sysfs tree at `sys`
+pub(crate) fn boot_disk_names(mountinfo: &str, sys: &Path) -> Vec<String> {
+    let mut disks = Vec::new();
+    for mount_point in BOOT_MOUNT_POINTS {
+        let Some((dev, source)) = parse_mountinfo_device(mountinfo, mount_point) else {
+            continue;
+        };
+        // Btrfs and other filesystems report an anonymous 0:N device; fall
+        // back to the mount source
+        let block = sys
+            .join("dev/block")
+            .join(&dev)
+            .canonicalize()
+            .ok()
+            .or_else(|| {
+                let device = Path::new(&source)
+                    .canonicalize()
+                    .unwrap_or_else(|_| PathBuf::from(&source));
+                sys.join("class/block")
+                    .join(device.file_name()?)
+                    .canonicalize()
+                    .ok()
+            });
+        if let Some(block) = block {
+            collect_whole_disks(sys, &block, 0, &mut disks);
+        }
+    }
+    disks
+}
+
+/// Device number (`MAJOR:MINOR`) and source of the last mount at `mount_point`
+///
+/// Format: `id parent MAJOR:MINOR root mount_point options [tags] - fstype source options`
+pub(crate) fn parse_mountinfo_device(
+    mountinfo: &str,
+    mount_point: &str,
+) -> Option<(String, String)> {
+    mountinfo.lines().rev().find_map(|line| {
+        let (mount, filesystem) = line.split_once(" - ")?;
+        let fields: Vec<&str> = mount.split_whitespace().collect();
+        let source = filesystem.split_whitespace().nth(1)?;
+        (fields.len() >= 5 && fields[4] == mount_point)
+            .then(|| (fields[2].to_string(), source.to_string()))
+    })
+}
+
+/// Add the whole disks under the sysfs block directory `dir` to `disks`
+///
+/// Stacked devices list what they sit on in `slaves/`; a partition's
+/// directory sits inside its disk's.
+fn collect_whole_disks(sys: &Path, dir: &Path, depth: usize, disks: &mut Vec<String>) {
+    // Guard against unexpected cycles in the device stack
+    if depth > 8 {
+        return;
+    }
+
+    let slaves: Vec<PathBuf> = fs::read_dir(dir.join("slaves"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    sys.join("class/block")
+                        .join(entry.file_name())
+                        .canonicalize()
+                        .ok()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if !slaves.is_empty() {
+        for slave in slaves {
+            collect_whole_disks(sys, &slave, depth + 1, disks);
+        }
+        return;
+    }
+
+    let disk = if dir.join("partition").exists() {
+        dir.parent()
+    } else {
+        Some(dir)
+    };
+    if let Some(name) = disk.and_then(Path::file_name) {
+        let name = name.to_string_lossy().to_string();
+        if !disks.contains(&name) {
+            disks.push(name);
+        }
+    }
+}
+
 /// Detect USB connection speed for a block device
 ///
 /// Traverses the sysfs device hierarchy upward from the block device
@@ -422,6 +650,68 @@ pub(crate) fn detect_usb_speed(sys_path: &str) -> Option<UsbSpeed> {
     None
 }
 
+/// Read the udev database entry for a block device
+///
+/// Returns an empty map if udev has no record of the device.
+fn read_udev_properties(sys_path: &str) -> HashMap<String, String> {
+    let Ok(dev) = read_sys_value(&format!("{sys_path}/dev")) else {
+        return HashMap::new();
+    };
+
+    fs::read_to_string(format!("/run/udev/data/b{dev}"))
+        .inspect_err(|e| debug!("No udev data for block device {dev}: {e}"))
+        .map(|content| parse_udev_properties(&content))
+        .unwrap_or_default()
+}
+
+/// Parse the `E:KEY=VALUE` property lines of a udev database entry
+pub(crate) fn parse_udev_properties(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter_map(|line| line.strip_prefix("E:"))
+        .filter_map(|prop| prop.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+/// The drive's serial number from its udev properties
+///
+/// `ID_SERIAL_SHORT` is the bare serial; `ID_SERIAL` prefixes it with the
+/// vendor and model and is only used when the short form is missing.
+pub(crate) fn disk_serial(udev: &HashMap<String, String>) -> Option<String> {
+    non_empty(udev.get("ID_SERIAL_SHORT")).or_else(|| non_empty(udev.get("ID_SERIAL")))
+}
+
+/// Check if a USB drive is an ATA disk behind a USB bridge
+///
+/// udev's `ata_id` only answers through SATA bridges, which flash drives
+/// don't have, so `ID_ATA=1` on a USB drive means an enclosure or dock.
+pub(crate) fn is_enclosure(drive_type: DriveType, udev: &HashMap<String, String>) -> bool {
+    drive_type == DriveType::Usb && udev.get("ID_ATA").is_some_and(|v| v == "1")
+}
+
+/// Check if a drive has spinning media
+///
+/// Most USB flash drives claim to be rotational in sysfs, so USB drives
+/// only count when `ata_id` reports a rotation rate.
+pub(crate) fn is_rotational(
+    drive_type: DriveType,
+    udev: &HashMap<String, String>,
+    sysfs_rotational: bool,
+) -> bool {
+    if drive_type == DriveType::Usb {
+        udev.get("ID_ATA_ROTATION_RATE_RPM")
+            .and_then(|rpm| rpm.parse::<u32>().ok())
+            .is_some_and(|rpm| rpm > 0)
+    } else {
+        sysfs_rotational && matches!(drive_type, DriveType::Sata | DriveType::Other)
+    }
+}
+
+fn non_empty(value: Option<&String>) -> Option<String> {
+    value.filter(|v| !v.is_empty()).cloned()
+}
+
 /// Read a value from sysfs
 fn read_sys_value(path: &str) -> Result<String> {
     fs::read_to_string(path)
@@ -480,6 +770,15 @@ mod tests {
         assert!(!should_skip_device("vda"));
     }
 
+    #[test]
+    fn test_should_skip_mmc_hardware_partitions() {
+        assert!(should_skip_device("mmcblk0boot0"));
+        assert!(should_skip_device("mmcblk0boot1"));
+        assert!(should_skip_device("mmcblk2rpmb"));
+        assert!(!should_skip_device("mmcblk1"));
+        assert!(!should_skip_device("mmcblk10"));
+    }
+
     // -------------------------------------------------------------------------
     // parse_mount_line tests
     // -------------------------------------------------------------------------
@@ -579,18 +878,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_thunderbolt_device_name() {
+        assert!(is_thunderbolt_device_name("0-1"));
+        assert!(is_thunderbolt_device_name("1-301"));
+        assert!(!is_thunderbolt_device_name("0-0"));
+        assert!(!is_thunderbolt_device_name("0-1:1.1"));
+        assert!(!is_thunderbolt_device_name("domain0"));
+    }
+
     #[test]
     fn test_detect_drive_type_sd_card() {
+        // Without sysfs we cannot see eMMC markers, so default to SD card
         assert_eq!(
-            detect_drive_type("mmcblk0", "/sys/block/mmcblk0"),
+            detect_drive_type("mmcblk0", "/nonexistent/mmcblk0"),
             DriveType::SdCard
         );
         assert_eq!(
-            detect_drive_type("mmcblk1", "/sys/block/mmcblk1"),
+            detect_drive_type("mmcblk1", "/nonexistent/mmcblk1"),
             DriveType::SdCard
         );
     }
 
+    #[test]
+    fn test_classify_mmc_device_from_type() {
+        assert_eq!(classify_mmc_device(Some("SD"), false), DriveType::SdCard);
+        assert_eq!(classify_mmc_device(Some("MMC"), false), DriveType::Emmc);
+        assert_eq!(classify_mmc_device(Some("MMC\n"), false), DriveType::Emmc);
+        // The reported type wins over the boot-partition heuristic
+        assert_eq!(classify_mmc_device(Some("SD"), true), DriveType::SdCard);
+    }
+
+    #[test]
+    fn test_classify_mmc_device_from_boot_partitions() {
+        assert_eq!(classify_mmc_device(None, true), DriveType::Emmc);
+        assert_eq!(classify_mmc_device(None, false), DriveType::SdCard);
+        assert_eq!(classify_mmc_device(Some("SDIO"), true), DriveType::Emmc);
+    }
+
     #[test]
     fn test_detect_drive_type_unknown() {
         // Without sysfs, we get Other for regular devices
@@ -603,34 +928,53 @@ mod tests {
 
     #[test]
     fn test_check_if_system_drive_root() {
-        let (is_system, reason) = check_if_system_drive("sda", &["/".to_string()], false);
+        let (is_system, reason) =
+            check_if_system_drive("sda", &["/".to_string()], false, DriveType::Other, false);
         assert!(is_system);
         assert!(reason.unwrap().contains("system mount point"));
     }
 
     #[test]
     fn test_check_if_system_drive_home() {
-        let (is_system, reason) = check_if_system_drive("sda", &["/home".to_string()], false);
+        let (is_system, reason) = check_if_system_drive(
+            "sda",
+            &["/home".to_string()],
+            false,
+            DriveType::Other,
+            false,
+        );
         assert!(is_system);
         assert!(reason.unwrap().contains("/home"));
     }
 
     #[test]
     fn test_check_if_system_drive_boot() {
-        let (is_system, _reason) = check_if_system_drive("sda", &["/boot".to_string()], false);
+        let (is_system, _reason) = check_if_system_drive(
+            "sda",
+            &["/boot".to_string()],
+            false,
+            DriveType::Other,
+            false,
+        );
         assert!(is_system);
     }
 
     #[test]
     fn test_check_if_system_drive_non_removable() {
-        let (is_system, reason) = check_if_system_drive("sda", &[], false);
+        let (is_system, reason) = check_if_system_drive("sda", &[], false, DriveType::Other, false);
         assert!(is_system);
         assert!(reason.unwrap().contains("Non-removable"));
     }
 
     #[test]
     fn test_check_if_system_drive_removable_no_system_mounts() {
-        let (is_system, reason) = check_if_system_drive("sdb", &["/mnt/usb".to_string()], true);
+        let (is_system, reason) = check_if_system_drive(
+            "sdb",
+            &["/mnt/usb".to_string()],
+            true,
+            DriveType::Other,
+            false,
+        );
         assert!(!is_system);
         assert!(reason.is_none());
     }
@@ -638,7 +982,8 @@ mod tests {
     #[test]
     fn test_check_if_system_drive_nvme_non_removable_allowed() {
         // External NVMe drives report as non-removable but aren't system drives
-        let (is_system, reason) = check_if_system_drive("nvme1n1", &[], false);
+        let (is_system, reason) =
+            check_if_system_drive("nvme1n1", &[], false, DriveType::Other, false);
         assert!(!is_system);
         assert!(reason.is_none());
     }
@@ -649,7 +994,19 @@ mod tests {
             "/media/user/USB".to_string(),
             "/run/media/user/disk".to_string(),
         ];
-        let (is_system, _) = check_if_system_drive("sdc", &mount_points, true);
+        let (is_system, _) =
+            check_if_system_drive("sdc", &mount_points, true, DriveType::Other, false);
+        assert!(!is_system);
+    }
+
+    #[test]
+    fn test_check_if_system_drive_emmc() {
+        let (is_system, reason) =
+            check_if_system_drive("mmcblk0", &[], true, DriveType::Emmc, false);
+        assert!(is_system);
+        assert!(reason.unwrap().contains("eMMC"));
+
+        let (is_system, _) = check_if_system_drive("mmcblk1", &[], true, DriveType::SdCard, false);
         assert!(!is_system);
     }
 
@@ -738,4 +1095,170 @@ mod tests {
         // Non-existent path should return None
         assert!(detect_usb_speed("/sys/block/nonexistent").is_none());
     }
+
+    // -------------------------------------------------------------------------
+    // udev property tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_parse_udev_properties() {
+        let content = "S:disk/by-label/BOOT\nW:12\nE:ID_FS_TYPE=vfat\nE:ID_FS_LABEL=BOOT\nE:ID_FS_UUID=1234-ABCD\nG:systemd\n";
+        let props = parse_udev_properties(content);
+        assert_eq!(props.get("ID_FS_TYPE").map(String::as_str), Some("vfat"));
+        assert_eq!(props.get("ID_FS_LABEL").map(String::as_str), Some("BOOT"));
+        assert_eq!(props.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_udev_properties_empty_value() {
+        let props = parse_udev_properties("E:ID_FS_LABEL=\n");
+        assert_eq!(props.get("ID_FS_LABEL").map(String::as_str), Some(""));
+        assert!(non_empty(props.get("ID_FS_LABEL")).is_none());
+    }
+
+    #[test]
+    fn test_disk_serial_prefers_short_form() {
+        let udev = parse_udev_properties(
+            "E:ID_SERIAL=SanDisk_Ultra_4C530001\nE:ID_SERIAL_SHORT=4C530001\n",
+        );
+        assert_eq!(disk_serial(&udev).as_deref(), Some("4C530001"));
+
+        let udev = parse_udev_properties("E:ID_SERIAL=Generic_Reader\nE:ID_SERIAL_SHORT=\n");
+        assert_eq!(disk_serial(&udev).as_deref(), Some("Generic_Reader"));
+
+        assert_eq!(disk_serial(&HashMap::new()), None);
+    }
+
+    #[test]
+    fn test_is_enclosure_needs_usb_and_ata() {
+        let udev = parse_udev_properties("E:ID_BUS=usb\nE:ID_ATA=1\n");
+        assert!(is_enclosure(DriveType::Usb, &udev));
+        assert!(!is_enclosure(DriveType::Sata, &udev));
+        assert!(!is_enclosure(DriveType::Usb, &HashMap::new()));
+    }
+
+    #[test]
+    fn test_is_rotational_usb_uses_rotation_rate() {
+        let hdd = parse_udev_properties("E:ID_ATA=1\nE:ID_ATA_ROTATION_RATE_RPM=5400\n");
+        let ssd = parse_udev_properties("E:ID_ATA=1\nE:ID_ATA_ROTATION_RATE_RPM=0\n");
+        assert!(is_rotational(DriveType::Usb, &hdd, false));
+        assert!(!is_rotational(DriveType::Usb, &ssd, true));
+        // Flash drives claim rotational in sysfs
+        assert!(!is_rotational(DriveType::Usb, &HashMap::new(), true));
+    }
+
+    #[test]
+    fn test_is_rotational_sata_uses_sysfs() {
+        assert!(is_rotational(DriveType::Sata, &HashMap::new(), true));
+        assert!(!is_rotational(DriveType::Sata, &HashMap::new(), false));
+        assert!(!is_rotational(DriveType::SdCard, &HashMap::new(), true));
+    }
+
+    #[test]
+    fn test_read_udev_properties_nonexistent() {
+        assert!(read_udev_properties("/sys/block/nonexistent/nonexistent1").is_empty());
+    }
+
+    // -------------------------------------------------------------------------
+    // Boot disk detection tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_check_if_system_drive_boot_disk() {
+        // An unmounted internal NVMe is normally allowed, unless it is the boot disk
+        let (is_system, reason) =
+            check_if_system_drive("nvme0n1", &[], false, DriveType::Nvme, true);
+        assert!(is_system);
+        assert!(reason.unwrap().contains("Boot disk"));
+    }
+
+    #[test]
+    fn test_parse_mountinfo_device() {
+        let mountinfo = "\
+22 1 254:0 / / rw,relatime shared:1 - ext4 /dev/mapper/vg-root rw
+30 22 259:2 / /boot/efi rw,relatime shared:9 - vfat /dev/nvme0n1p2 rw,fmask=0077
+31 22 0:31 / /home rw - btrfs /dev/sda3 rw
+";
+        assert_eq!(
+            parse_mountinfo_device(mountinfo, "/"),
+            Some(("254:0".to_string(), "/dev/mapper/vg-root".to_string()))
+        );
+        assert_eq!(
+            parse_mountinfo_device(mountinfo, "/boot/efi"),
+            Some(("259:2".to_string(), "/dev/nvme0n1p2".to_string()))
+        );
+        assert_eq!(parse_mountinfo_device(mountinfo, "/boot"), None);
+    }
+
+    #[test]
+    fn test_parse_mountinfo_device_last_mount_wins() {
+        let mountinfo = "\
+1 0 8:1 / / rw - ext4 /dev/sda1 rw
+2 1 0:5 / / rw - overlay overlay rw
+";
+        assert_eq!(
+            parse_mountinfo_device(mountinfo, "/"),
+            Some(("0:5".to_string(), "overlay".to_string()))
+        );
+    }
+
+    /// A sysfs tree with the root on LVM over `sda3` and the ESP on `nvme0n1p2`
+    fn fake_sysfs() -> tempfile::TempDir {
+        use std::os::unix::fs::symlink;
+
+        let sys = tempfile::TempDir::new().unwrap();
+        let root = sys.path();
+        for (dir, partition) in [
+            ("devices/pci/sda/sda3", true),
+            ("devices/pci/nvme0n1/nvme0n1p2", true),
+            ("devices/virtual/block/dm-0/slaves", false),
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+            if partition {
+                fs::write(root.join(dir).join("partition"), "1").unwrap();
+            }
+        }
+        fs::write(root.join("devices/virtual/block/dm-0/slaves/sda3"), "").unwrap();
+        fs::create_dir_all(root.join("class/block")).unwrap();
+        fs::create_dir_all(root.join("dev/block")).unwrap();
+        for (link, target) in [
+            ("class/block/sda3", "devices/pci/sda/sda3"),
+            ("class/block/nvme0n1p2", "devices/pci/nvme0n1/nvme0n1p2"),
+            ("dev/block/259:2", "devices/pci/nvme0n1/nvme0n1p2"),
+            ("dev/block/254:0", "devices/virtual/block/dm-0"),
+        ] {
+            symlink(root.join(target), root.join(link)).unwrap();
+        }
+        sys
+    }
+
+    #[test]
+    fn test_boot_disk_names_through_lvm_and_partitions() {
+        let sys = fake_sysfs();
+        let mountinfo = "\
+22 1 254:0 / / rw - ext4 /dev/mapper/vg-root rw
+30 22 259:2 / /boot/efi rw - vfat /dev/nvme0n1p2 rw
+";
+        assert_eq!(
+            boot_disk_names(mountinfo, sys.path()),
+            vec!["sda".to_string(), "nvme0n1".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_boot_disk_names_anonymous_device_uses_source() {
+        let sys = fake_sysfs();
+        let mountinfo = "22 1 0:27 / / rw - btrfs /dev/sda3 rw\n";
+        assert_eq!(
+            boot_disk_names(mountinfo, sys.path()),
+            vec!["sda".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_boot_disk_names_unresolvable() {
+        let sys = fake_sysfs();
+        assert!(boot_disk_names("22 1 0:5 / / rw - overlay overlay rw\n", sys.path()).is_empty());
+        assert!(boot_disk_names("", sys.path()).is_empty());
+    }
 }
@@ -2,7 +2,10 @@
 //!
 //! Uses `diskutil` command for device enumeration and information.
 
-use super::{is_system_mount_point, DetectError, Drive, DriveType, Partition, Result, UsbSpeed};
+use super::{
+    add_filesystem_space, caution_reason, is_system_mount_point, DetectError, Drive, DriveType,
+    Partition, Result, UsbSpeed,
+};
 use std::collections::HashMap;
 use std::process::Command;
 
@@ -32,11 +35,15 @@ pub fn list_drives() -> Result<Vec<Drive>> {
 
     let plist_str = String::from_utf8_lossy(&output.stdout);
     let disk_names = parse_disk_list(&plist_str)?;
+    let boot_disk = current_boot_disk()
+        .inspect_err(|e| tracing::debug!("Boot disk detection skipped: {e}"))
+        .ok()
+        .flatten();
 
     let mut drives = Vec::new();
 
     for disk_name in disk_names {
-        match get_disk_info(&disk_name) {
+        match get_disk_info(&disk_name, boot_disk.as_deref()) {
             Ok(Some(drive)) => drives.push(drive),
             Ok(None) => {}
             Err(e) => {
@@ -128,8 +135,53 @@ pub(crate) fn parse_disk_list(plist: &str) -> Result<Vec<String>> {
     Ok(disks)
 }
 
+/// Find the disk the running system booted from, e.g. `/dev/disk0`
+///
+/// Uses `diskutil info -plist /`. On APFS the root volume lives in a
+/// synthesized container disk, so the container's physical store is
+/// followed to the real disk.
+///
+/// # Errors
+///
+/// Returns an error if `diskutil` fails to execute.
+pub fn current_boot_disk() -> Result<Option<String>> {
+    let output = Command::new("diskutil")
+        .args(["info", "-plist", "/"])
+        .output()
+        .map_err(|e| DetectError::CommandFailed {
+            message: "diskutil info / failed".to_string(),
+            source: Some(e),
+        })?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let info = parse_disk_info(&String::from_utf8_lossy(&output.stdout));
+    Ok(boot_disk_from_info(&info).map(|disk| format!("/dev/{disk}")))
+}
+
+/// Whole disk holding the volume described by `info`
+pub(crate) fn boot_disk_from_info(info: &HashMap<String, String>) -> Option<String> {
+    info.get("APFSPhysicalStore")
+        .map(|store| whole_disk_name(store).to_string())
+        .or_else(|| info.get("ParentWholeDisk").cloned())
+}
+
+/// Strip a slice suffix: `disk0s2` -> `disk0`
+pub(crate) fn whole_disk_name(name: &str) -> &str {
+    let name = name.trim_start_matches("/dev/");
+    match name.strip_prefix("disk") {
+        Some(rest) => {
+            let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            &name[..4 + digits]
+        }
+        None => name,
+    }
+}
+
 /// Get detailed info for a specific disk
-fn get_disk_info(disk_name: &str) -> Result<Option<Drive>> {
+fn get_disk_info(disk_name: &str, boot_disk: Option<&str>) -> Result<Option<Drive>> {
     let output = Command::new("diskutil")
         .args(["info", "-plist", disk_name])
         .output()
@@ -174,21 +226,28 @@ fn get_disk_info(disk_name: &str) -> Result<Option<Drive>> {
         })
         .unwrap_or(0);
 
-    if size == 0 {
+    let removable = info.get("RemovableMedia").is_some_and(|s| s == "true")
+        || info.get("Ejectable").is_some_and(|s| s == "true");
+
+    // An empty card reader reports size 0 but is still worth listing
+    if size == 0 && !removable {
         tracing::debug!("Skipping disk {disk_name}: size is 0");
         return Ok(None);
     }
 
-    let removable = info.get("RemovableMedia").is_some_and(|s| s == "true")
-        || info.get("Ejectable").is_some_and(|s| s == "true");
-
     let internal = info.get("Internal").is_none_or(|s| s == "true");
+    // Only reported for drives that answer; USB flash drives usually don't
+    let rotational = info.get("SolidState").is_some_and(|s| s == "false");
 
     let vendor = info.get("MediaName").cloned();
     let model = info.get("IORegistryEntryName").cloned();
     let serial = info.get("DeviceSerialNumber").cloned();
 
-    let drive_type = detect_drive_type(&info);
+    let mut drive_type = detect_drive_type(&info);
+    // Thunderbolt NVMe enclosures report plain PCI-Express
+    if drive_type == DriveType::Nvme && !internal && thunderbolt_device_connected() {
+        drive_type = DriveType::Thunderbolt;
+    }
     let partitions = get_disk_partitions(disk_name)?;
 
     let mount_points: Vec<String> = partitions
@@ -196,7 +255,9 @@ fn get_disk_info(disk_name: &str) -> Result<Option<Drive>> {
         .filter_map(|p| p.mount_point.clone())
         .collect();
 
-    let (is_system, system_reason) = check_if_system_drive(&info, &mount_points, internal);
+    let is_boot_disk = boot_disk == Some(device_node.as_str());
+    let (is_system, system_reason) =
+        check_if_system_drive(&info, &mount_points, internal, is_boot_disk);
 
     // Get USB speed for USB devices
     let usb_speed = if drive_type == DriveType::Usb {
@@ -212,7 +273,7 @@ fn get_disk_info(disk_name: &str) -> Result<Option<Drive>> {
 
     let raw_path = format!("/dev/r{disk_name}");
 
-    Ok(Some(Drive {
+    let mut drive = Drive {
         path: device_node,
         raw_path,
         name: display_name.clone(),
@@ -227,7 +288,13 @@ fn get_disk_info(disk_name: &str) -> Result<Option<Drive>> {
         partitions,
         system_reason,
         usb_speed,
-    }))
+        media_present: size > 0,
+        enclosure: false,
+        rotational,
+        caution_reason: None,
+    };
+    drive.caution_reason = caution_reason(&drive);
+    Ok(Some(drive))
 }
 
 /// Parse disk info plist into a key-value map
@@ -285,7 +352,9 @@ fn get_disk_partitions(disk_name: &str) -> Result<Vec<Partition>> {
     }
 
     let plist_str = String::from_utf8_lossy(&output.stdout);
-    Ok(parse_partitions(&plist_str, disk_name))
+    let mut partitions = parse_partitions(&plist_str, disk_name);
+    add_filesystem_space(&mut partitions);
+    Ok(partitions)
 }
 
 /// Parse partitions from diskutil list output
@@ -333,6 +402,8 @@ pub(crate) fn parse_partitions(plist: &str, disk_name: &str) -> Vec<Partition> {
                         filesystem: current_partition.get("Content").cloned(),
                         size,
                         mount_point,
+                        used: None,
+                        available: None,
                     });
                 }
             }
@@ -376,12 +447,8 @@ pub(crate) fn detect_drive_type(info: &HashMap<String, String>) -> DriveType {
     let bus = info.get("BusProtocol").map(std::string::String::as_str);
     let media_name = info.get("MediaName").map(|s| s.to_lowercase());
 
-    match protocol.or(bus) {
-        Some("USB") => return DriveType::Usb,
-        Some("NVMe" | "PCI-Express") => return DriveType::Nvme,
-        Some("SATA" | "SAS") => return DriveType::Sata,
-        Some("Secure Digital" | "SD") => return DriveType::SdCard,
-        _ => {}
+    if let Some(drive_type) = protocol.or(bus).and_then(DriveType::from_bus) {
+        return drive_type;
     }
 
     if let Some(name) = media_name {
@@ -393,6 +460,43 @@ pub(crate) fn detect_drive_type(info: &HashMap<String, String>) -> DriveType {
     DriveType::Other
 }
 
+/// Check `system_profiler` for a device on any Thunderbolt port
+fn thunderbolt_device_connected() -> bool {
+    let output = match Command::new("system_profiler")
+        .args(["SPThunderboltDataType", "-detailLevel", "mini"])
+        .output()
+    {
+        Ok(output) if output.status.success() => output,
+        Ok(_) => {
+            tracing::debug!("system_profiler returned non-zero exit code");
+            return false;
+        }
+        Err(e) => {
+            tracing::debug!("Failed to run system_profiler: {e}");
+            return false;
+        }
+    };
+
+    parse_thunderbolt_profiler(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Whether `system_profiler SPThunderboltDataType` output shows a connected device
+///
+/// Each port of the host controller has a status line:
+/// ```text
+/// Port (Receptacle 1):
+///   Status: Device connected
+/// Port (Receptacle 2):
+///   Status: No device connected
+/// ```
+pub(crate) fn parse_thunderbolt_profiler(output: &str) -> bool {
+    output.lines().any(|line| {
+        line.trim()
+            .strip_prefix("Status:")
+            .is_some_and(|status| status.trim().eq_ignore_ascii_case("Device connected"))
+    })
+}
+
 /// Get USB speed for a device by querying `system_profiler`
 ///
 /// Parses `system_profiler SPUSBDataType` output to find the speed
@@ -519,6 +623,7 @@ fn check_if_system_drive(
     info: &HashMap<String, String>,
     mount_points: &[String],
     internal: bool,
+    boot_disk: bool,
 ) -> (bool, Option<String>) {
     if info.get("SystemImage").is_some_and(|s| s == "true") {
         return (true, Some("System image volume".to_string()));
@@ -528,6 +633,10 @@ fn check_if_system_drive(
         return (true, Some("Boot device".to_string()));
     }
 
+    if boot_disk {
+        return (true, Some("Boot disk of the running system".to_string()));
+    }
+
     for mp in mount_points {
         if is_system_mount_point(mp) {
             return (true, Some(format!("Contains system mount point: {mp}")));
@@ -716,6 +825,64 @@ mod tests {
         assert_eq!(info.get("VolumeName"), Some(&"My USB & Drive".to_string()));
     }
 
+    // -------------------------------------------------------------------------
+    // Boot disk detection tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_boot_disk_from_info_apfs_physical_store() {
+        // `diskutil info -plist /` on APFS: the parent is the synthesized container
+        let plist = r"
+<dict>
+    <key>APFSPhysicalStores</key>
+    <array>
+        <dict>
+            <key>APFSPhysicalStore</key>
+            <string>disk0s2</string>
+        </dict>
+    </array>
+    <key>MountPoint</key>
+    <string>/</string>
+    <key>ParentWholeDisk</key>
+    <string>disk3</string>
+</dict>
+        ";
+
+        let info = parse_disk_info(plist);
+        assert_eq!(boot_disk_from_info(&info), Some("disk0".to_string()));
+    }
+
+    #[test]
+    fn test_boot_disk_from_info_hfs() {
+        let plist = r"
+<dict>
+    <key>ParentWholeDisk</key>
+    <string>disk1</string>
+</dict>
+        ";
+
+        let info = parse_disk_info(plist);
+        assert_eq!(boot_disk_from_info(&info), Some("disk1".to_string()));
+        assert_eq!(boot_disk_from_info(&HashMap::new()), None);
+    }
+
+    #[test]
+    fn test_whole_disk_name() {
+        assert_eq!(whole_disk_name("disk0s2"), "disk0");
+        assert_eq!(whole_disk_name("/dev/disk12s1"), "disk12");
+        assert_eq!(whole_disk_name("disk4"), "disk4");
+    }
+
+    #[test]
+    fn test_check_if_system_drive_boot_disk() {
+        let (is_system, reason) = check_if_system_drive(&HashMap::new(), &[], false, true);
+        assert!(is_system);
+        assert!(reason.unwrap().contains("Boot disk"));
+
+        let (is_system, _) = check_if_system_drive(&HashMap::new(), &[], false, false);
+        assert!(!is_system);
+    }
+
     // -------------------------------------------------------------------------
     // detect_drive_type tests
     // -------------------------------------------------------------------------
@@ -742,6 +909,13 @@ mod tests {
         assert_eq!(detect_drive_type(&info), DriveType::Nvme);
     }
 
+    #[test]
+    fn test_detect_drive_type_thunderbolt() {
+        let mut info = HashMap::new();
+        info.insert("BusProtocol".to_string(), "Thunderbolt".to_string());
+        assert_eq!(detect_drive_type(&info), DriveType::Thunderbolt);
+    }
+
     #[test]
     fn test_detect_drive_type_sata() {
         let mut info = HashMap::new();
@@ -842,6 +1016,22 @@ mod tests {
         assert!(system_drive.is_some(), "Should identify a system drive");
     }
 
+    // -------------------------------------------------------------------------
+    // Thunderbolt detection tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_parse_thunderbolt_profiler() {
+        let connected = "Thunderbolt/USB4:\n\n    Thunderbolt/USB4 Bus 0:\n\n      \
+                         Port (Receptacle 1):\n            Status: Device connected\n";
+        assert!(parse_thunderbolt_profiler(connected));
+
+        let empty = "Thunderbolt/USB4:\n\n    Thunderbolt/USB4 Bus 0:\n\n      \
+                     Port (Receptacle 1):\n            Status: No device connected\n";
+        assert!(!parse_thunderbolt_profiler(empty));
+        assert!(!parse_thunderbolt_profiler(""));
+    }
+
     // -------------------------------------------------------------------------
     // USB speed parsing tests
     // -------------------------------------------------------------------------
@@ -2,7 +2,10 @@
 //!
 //! Uses PowerShell and WMI/CIM queries for device enumeration.
 
-use super::{is_system_mount_point, DetectError, Drive, DriveType, Partition, Result, UsbSpeed};
+use super::{
+    caution_reason, is_system_mount_point, DetectError, Drive, DriveType, Partition, Result,
+    UsbSpeed,
+};
 use std::collections::HashMap;
 use std::process::Command;
 
@@ -10,18 +13,26 @@ use std::process::Command;
 pub fn list_drives() -> Result<Vec<Drive>> {
     let disks = get_physical_disks()?;
     let volumes = get_volumes()?;
+    let boot_disks = boot_disk_numbers().unwrap_or_else(|e| {
+        tracing::debug!("Boot disk detection skipped: {e}");
+        Vec::new()
+    });
 
     let mut drives = Vec::new();
 
     for disk in disks {
-        let partitions = get_disk_partitions(&disk.device_id, &volumes);
+        let partitions = get_disk_partitions(disk.index, &volumes);
         let mount_points: Vec<String> = partitions
             .iter()
             .filter_map(|p| p.mount_point.clone())
             .collect();
 
-        let (is_system, system_reason) =
-            check_if_system_drive(&disk.media_type, &mount_points, &disk.interface_type);
+        let (is_system, system_reason) = check_if_system_drive(
+            &disk.media_type,
+            &mount_points,
+            &disk.interface_type,
+            boot_disks.contains(&disk.index),
+        );
 
         let raw_path = format!("\\\\.\\PhysicalDrive{}", disk.index);
 
@@ -37,7 +48,10 @@ pub fn list_drives() -> Result<Vec<Drive>> {
             None
         };
 
-        drives.push(Drive {
+        // USB sticks report "Removable Media"; disks behind a USB bridge don't
+        let enclosure = disk.media_type.contains("External hard disk");
+
+        let mut drive = Drive {
             path: raw_path.clone(),
             raw_path,
             name: disk.model.clone(),
@@ -52,12 +66,65 @@ pub fn list_drives() -> Result<Vec<Drive>> {
             partitions,
             system_reason,
             usb_speed,
-        });
+            media_present: disk.size > 0,
+            enclosure,
+            rotational: false,
+            caution_reason: None,
+        };
+        drive.caution_reason = caution_reason(&drive);
+        drives.push(drive);
     }
 
     Ok(drives)
 }
 
+/// Find the disk the running system booted from, e.g. `\\.\PhysicalDrive0`
+///
+/// Prefers the disk holding the boot volume (the Windows partition) over the
+/// one holding the system volume (the EFI partition), when they differ.
+///
+/// # Errors
+///
+/// Returns an error if PowerShell fails to execute.
+pub fn current_boot_disk() -> Result<Option<String>> {
+    Ok(boot_disk_numbers()?
+        .first()
+        .map(|index| format!("\\\\.\\PhysicalDrive{index}")))
+}
+
+/// Disk numbers holding the boot and system volumes, boot volume first
+fn boot_disk_numbers() -> Result<Vec<u32>> {
+    let ps_command = r#"Get-Partition | Where-Object { $_.IsBoot -or $_.IsSystem } | Sort-Object -Property IsBoot -Descending | Select-Object -ExpandProperty DiskNumber"#;
+
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", ps_command])
+        .output()
+        .map_err(|e| DetectError::CommandFailed {
+            message: format!("PowerShell failed"),
+            source: Some(e),
+        })?;
+
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    Ok(parse_disk_numbers(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse one disk number per line, dropping duplicates
+pub(crate) fn parse_disk_numbers(output: &str) -> Vec<u32> {
+    let mut numbers = Vec::new();
+    for number in output
+        .lines()
+        .filter_map(|line| line.trim().parse::<u32>().ok())
+    {
+        if !numbers.contains(&number) {
+            numbers.push(number);
+        }
+    }
+    numbers
+}
+
 /// Physical disk info from WMI
 #[derive(Debug, Clone)]
 pub(crate) struct PhysicalDisk {
@@ -188,16 +255,37 @@ fn parse_csv_line(line: &str) -> Vec<String> {
 /// Volume info
 #[derive(Debug, Clone)]
 pub(crate) struct VolumeInfo {
+    /// Physical disk index, when known (Get-Partition only)
+    pub disk_number: Option<u32>,
+    /// Partition number on the disk, when known (Get-Partition only)
+    pub partition_number: Option<u32>,
     pub drive_letter: String,
     pub label: Option<String>,
     pub filesystem: Option<String>,
     pub size: u64,
+    /// Filesystem size and free bytes, when the volume is mounted
+    pub space: Option<(u64, u64)>,
 }
 
-/// Get volumes using PowerShell Get-CimInstance
+/// Get volumes using PowerShell Get-Partition, falling back to Win32_Volume
+///
+/// Get-Partition reports which disk each partition lives on and includes
+/// partitions without a drive letter; Win32_Volume only knows lettered
+/// volumes and cannot be tied to a disk.
 fn get_volumes() -> Result<Vec<VolumeInfo>> {
-    let ps_command = r#"Get-CimInstance -ClassName Win32_Volume | Where-Object { $_.DriveLetter -ne $null } | Select-Object DriveLetter,Label,FileSystem,Capacity | ConvertTo-Csv -NoTypeInformation"#;
+    let ps_command = r#"Get-Partition | ForEach-Object { $v = $_ | Get-Volume -ErrorAction SilentlyContinue; $letter = if ($_.DriveLetter -and $_.DriveLetter -ne [char]0) { "$($_.DriveLetter):" } else { "" }; [pscustomobject]@{ DiskNumber = $_.DiskNumber; PartitionNumber = $_.PartitionNumber; DriveLetter = $letter; Label = $v.FileSystemLabel; FileSystem = $v.FileSystem; Capacity = $_.Size; VolumeSize = $v.Size; Free = $v.SizeRemaining } } | ConvertTo-Csv -NoTypeInformation"#;
 
+    match run_volume_query(ps_command)? {
+        Some(volumes) => Ok(volumes),
+        None => {
+            let ps_command = r#"Get-CimInstance -ClassName Win32_Volume | Where-Object { $_.DriveLetter -ne $null } | Select-Object DriveLetter,Label,FileSystem,Capacity,@{n='VolumeSize';e={$_.Capacity}},@{n='Free';e={$_.FreeSpace}} | ConvertTo-Csv -NoTypeInformation"#;
+            Ok(run_volume_query(ps_command)?.unwrap_or_default())
+        }
+    }
+}
+
+/// Run a volume query, returning `None` if PowerShell reported a failure
+fn run_volume_query(ps_command: &str) -> Result<Option<Vec<VolumeInfo>>> {
     let output = Command::new("powershell")
         .args(["-NoProfile", "-NonInteractive", "-Command", ps_command])
         .output()
@@ -207,11 +295,11 @@ fn get_volumes() -> Result<Vec<VolumeInfo>> {
         })?;
 
     if !output.status.success() {
-        return Ok(Vec::new());
+        return Ok(None);
     }
 
     let output_str = String::from_utf8_lossy(&output.stdout);
-    Ok(parse_powershell_volumes(&output_str))
+    Ok(Some(parse_powershell_volumes(&output_str)))
 }
 
 /// Parse PowerShell CSV output for volumes
@@ -247,8 +335,14 @@ pub(crate) fn parse_powershell_volumes(csv: &str) -> Vec<VolumeInfo> {
             }
         }
 
+        let disk_number = row.get("DiskNumber").and_then(|s| s.parse::<u32>().ok());
+        let partition_number = row
+            .get("PartitionNumber")
+            .and_then(|s| s.parse::<u32>().ok());
+
+        // Unlettered volumes are only useful when we know which partition they are
         let drive_letter = row.get("DriveLetter").unwrap_or(&"").to_string();
-        if drive_letter.is_empty() {
+        if drive_letter.is_empty() && partition_number.is_none() {
             continue;
         }
 
@@ -264,12 +358,17 @@ pub(crate) fn parse_powershell_volumes(csv: &str) -> Vec<VolumeInfo> {
             .get("Capacity")
             .and_then(|s| s.parse::<u64>().ok())
             .unwrap_or(0);
+        let parse = |key: &str| row.get(key).and_then(|s| s.parse::<u64>().ok());
+        let space = parse("VolumeSize").zip(parse("Free"));
 
         volumes.push(VolumeInfo {
+            disk_number,
+            partition_number,
             drive_letter,
             label,
             filesystem,
             size,
+            space,
         });
     }
 
@@ -277,17 +376,31 @@ pub(crate) fn parse_powershell_volumes(csv: &str) -> Vec<VolumeInfo> {
 }
 
 /// Get partitions for a disk
-fn get_disk_partitions(_device_id: &str, volumes: &[VolumeInfo]) -> Vec<Partition> {
-    // Simplified - maps volumes to partitions
-    // Full implementation would use Win32_DiskDriveToDiskPartition
+///
+/// Volumes without a disk number (Win32_Volume fallback) cannot be tied to a
+/// disk and are attached to every disk, as before.
+pub(crate) fn get_disk_partitions(disk_index: u32, volumes: &[VolumeInfo]) -> Vec<Partition> {
     volumes
         .iter()
-        .map(|v| Partition {
-            path: v.drive_letter.clone(),
-            label: v.label.clone(),
-            filesystem: v.filesystem.clone(),
-            size: v.size,
-            mount_point: Some(v.drive_letter.clone()),
+        .filter(|v| v.disk_number.is_none_or(|n| n == disk_index))
+        .map(|v| {
+            let mount_point = (!v.drive_letter.is_empty()).then(|| v.drive_letter.clone());
+            let path = match (mount_point.as_ref(), v.partition_number) {
+                (Some(letter), _) => letter.clone(),
+                (None, Some(number)) => format!("\\\\.\\Harddisk{disk_index}Partition{number}"),
+                (None, None) => String::new(),
+            };
+            // Only a lettered volume is mounted, so only its space is meaningful
+            let space = v.space.filter(|_| mount_point.is_some());
+            Partition {
+                path,
+                label: v.label.clone(),
+                filesystem: v.filesystem.clone(),
+                size: v.size,
+                mount_point,
+                used: space.map(|(total, free)| total.saturating_sub(free)),
+                available: space.map(|(_, free)| free),
+            }
         })
         .collect()
 }
@@ -295,8 +408,6 @@ fn get_disk_partitions(_device_id: &str, volumes: &[VolumeInfo]) -> Vec<Partitio
 /// Detect drive type from interface and media type
 pub(crate) fn detect_drive_type(interface_type: &str, media_type: &str) -> DriveType {
     match interface_type.to_uppercase().as_str() {
-        "USB" => DriveType::Usb,
-        "NVME" | "PCIE" => DriveType::Nvme,
         "SCSI" | "SATA" | "IDE" => {
             if media_type.contains("Removable") || media_type.contains("External") {
                 DriveType::Usb
@@ -304,14 +415,13 @@ pub(crate) fn detect_drive_type(interface_type: &str, media_type: &str) -> Drive
                 DriveType::Sata
             }
         }
-        "SD" => DriveType::SdCard,
-        _ => {
+        other => DriveType::from_bus(other).unwrap_or_else(|| {
             if media_type.contains("Removable") {
                 DriveType::Usb
             } else {
                 DriveType::Other
             }
-        }
+        }),
     }
 }
 
@@ -426,6 +536,7 @@ pub(crate) fn check_if_system_drive(
     media_type: &str,
     mount_points: &[String],
     interface_type: &str,
+    boot_disk: bool,
 ) -> (bool, Option<String>) {
     // Check for system drive letter
     for mp in mount_points {
@@ -434,6 +545,12 @@ pub(crate) fn check_if_system_drive(
         }
     }
 
+    // The EFI system partition has no drive letter, so a disk holding only
+    // the boot loader would otherwise pass as a data disk
+    if boot_disk {
+        return (true, Some("Boot disk of the running system".to_string()));
+    }
+
     // Fixed internal drives are likely system drives
     if media_type == "Fixed hard disk media" && interface_type.to_uppercase() != "USB" {
         return (true, Some("Fixed internal hard disk".to_string()));
@@ -534,6 +651,32 @@ mod tests {
         assert_eq!(volumes[0].drive_letter, "E:");
     }
 
+    #[test]
+    fn test_parse_powershell_partitions_per_disk() {
+        let csv = r#""DiskNumber","PartitionNumber","DriveLetter","Label","FileSystem","Capacity","VolumeSize","Free"
+"0","1","","","","104857600","",""
+"0","2","C:","Windows","NTFS","511999156224","511999156224","100000000000"
+"1","1","E:","BOOT","FAT32","268435456","268431360","68431360"
+"1","2","","","ext4","31742492672","",""
+"#;
+        let volumes = parse_powershell_volumes(csv);
+        assert_eq!(volumes.len(), 4);
+        assert_eq!(volumes[2].disk_number, Some(1));
+        assert_eq!(volumes[2].partition_number, Some(1));
+
+        let partitions = get_disk_partitions(1, &volumes);
+        assert_eq!(partitions.len(), 2);
+        assert_eq!(partitions[0].path, "E:");
+        assert_eq!(partitions[0].mount_point, Some("E:".to_string()));
+        assert_eq!(partitions[0].label, Some("BOOT".to_string()));
+        assert_eq!(partitions[0].used, Some(200_000_000));
+        assert_eq!(partitions[0].available, Some(68_431_360));
+        assert_eq!(partitions[1].path, r"\\.\Harddisk1Partition2");
+        assert!(partitions[1].mount_point.is_none());
+        assert!(partitions[1].used.is_none());
+        assert_eq!(partitions[1].filesystem, Some("ext4".to_string()));
+    }
+
     // -------------------------------------------------------------------------
     // detect_drive_type tests
     // -------------------------------------------------------------------------
@@ -613,7 +756,7 @@ mod tests {
     #[test]
     fn test_check_if_system_drive_c_drive() {
         let (is_system, reason) =
-            check_if_system_drive("Fixed hard disk media", &["C:".to_string()], "SCSI");
+            check_if_system_drive("Fixed hard disk media", &["C:".to_string()], "SCSI", false);
         assert!(is_system);
         assert!(reason.unwrap().contains("C:"));
     }
@@ -624,6 +767,7 @@ mod tests {
             "Fixed hard disk media",
             &["C:\\Windows".to_string()],
             "SCSI",
+            false,
         );
         assert!(is_system);
     }
@@ -631,14 +775,15 @@ mod tests {
     #[test]
     fn test_check_if_system_drive_fixed_internal() {
         let (is_system, reason) =
-            check_if_system_drive("Fixed hard disk media", &["D:".to_string()], "SCSI");
+            check_if_system_drive("Fixed hard disk media", &["D:".to_string()], "SCSI", false);
         assert!(is_system);
         assert!(reason.unwrap().contains("Fixed internal"));
     }
 
     #[test]
     fn test_check_if_system_drive_usb() {
-        let (is_system, _) = check_if_system_drive("Removable Media", &["E:".to_string()], "USB");
+        let (is_system, _) =
+            check_if_system_drive("Removable Media", &["E:".to_string()], "USB", false);
         assert!(!is_system);
     }
 
@@ -646,10 +791,24 @@ mod tests {
     fn test_check_if_system_drive_external_usb_fixed() {
         // USB drive with "Fixed hard disk media" (like some external HDDs)
         let (is_system, _) =
-            check_if_system_drive("Fixed hard disk media", &["F:".to_string()], "USB");
+            check_if_system_drive("Fixed hard disk media", &["F:".to_string()], "USB", false);
         assert!(!is_system); // USB interface overrides the fixed media type
     }
 
+    #[test]
+    fn test_check_if_system_drive_boot_disk() {
+        // A USB disk is normally allowed, unless the system booted from it
+        let (is_system, reason) = check_if_system_drive("Removable Media", &[], "USB", true);
+        assert!(is_system);
+        assert!(reason.unwrap().contains("Boot disk"));
+    }
+
+    #[test]
+    fn test_parse_disk_numbers() {
+        assert_eq!(parse_disk_numbers("1\r\n0\r\n1\r\n"), vec![1, 0]);
+        assert_eq!(parse_disk_numbers("\r\n"), Vec::<u32>::new());
+    }
+
     // -------------------------------------------------------------------------
     // Integration tests (require actual Windows system)
     // -------------------------------------------------------------------------
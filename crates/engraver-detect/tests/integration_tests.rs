@@ -177,6 +177,8 @@ fn test_partition_creation() {
         filesystem: Some("vfat".to_string()),
         size: 32 * 1024 * 1024 * 1024,
         mount_point: Some("/media/usb".to_string()),
+        used: None,
+        available: None,
     };
 
     assert_eq!(partition.path, "/dev/sdb1");
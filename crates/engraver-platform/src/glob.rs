@@ -0,0 +1,112 @@
+//! Shell-style glob matching for device paths and file names
+
+/// Match `text` against a glob of `*`, `?` and `[...]`
+///
+/// Bracket classes support ranges (`[b-f]`) and negation (`[!0-9]` or
+/// `[^0-9]`); a `]` right after the opening `[` is part of the class, and an
+/// unterminated `[` matches itself.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if p.get(pi) == Some(&'*') {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some(len) = match_one(&p[pi..], t[ti]) {
+            pi += len;
+            ti += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+/// Match `c` against the token at the start of `pattern` (anything but `*`)
+///
+/// Returns the number of pattern characters the token spans.
+fn match_one(pattern: &[char], c: char) -> Option<usize> {
+    match pattern.split_first()? {
+        ('?', _) => Some(1),
+        ('[', rest) => match class_len(rest) {
+            Some(len) => class_matches(&rest[..len], c).then_some(len + 1),
+            None => (c == '[').then_some(1),
+        },
+        (&p, _) => (p == c).then_some(1),
+    }
+}
+
+/// Length of the bracket class at the start of `pattern`, including its `]`
+///
+/// A `]` right after the opening `[` (or `[!`) is part of the class.
+fn class_len(pattern: &[char]) -> Option<usize> {
+    let start = usize::from(matches!(pattern.first(), Some('!' | '^')));
+    pattern
+        .iter()
+        .skip(start + 1)
+        .position(|&c| c == ']')
+        .map(|i| start + i + 2)
+}
+
+/// Check `c` against a bracket class, given without its opening `[`
+fn class_matches(class: &[char], c: char) -> bool {
+    let (negated, body) = match class.split_first() {
+        Some(('!' | '^', body)) => (true, body),
+        _ => (false, class),
+    };
+    let body = &body[..body.len() - 1];
+
+    let mut matched = false;
+    let mut i = 0;
+    while i < body.len() {
+        if body.get(i + 1) == Some(&'-') && i + 2 < body.len() {
+            matched |= (body[i]..=body[i + 2]).contains(&c);
+            i += 3;
+        } else {
+            matched |= body[i] == c;
+            i += 1;
+        }
+    }
+    matched != negated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match_wildcards() {
+        assert!(glob_match("*.img", "disk.img"));
+        assert!(glob_match("disk-?.img", "disk-1.img"));
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("a*b*c", "aXXbYYc"));
+        assert!(!glob_match("*.img", "disk.iso"));
+        assert!(!glob_match("disk-?.img", "disk-10.img"));
+        assert!(!glob_match("disk", "disk.img"));
+    }
+
+    #[test]
+    fn test_glob_match_bracket_classes() {
+        assert!(glob_match("/dev/sd[b-f]", "/dev/sdc"));
+        assert!(!glob_match("/dev/sd[b-f]", "/dev/sda"));
+        assert!(glob_match("/dev/sd[!a]", "/dev/sdb"));
+        assert!(glob_match("/dev/mmcblk[0-9]*", "/dev/mmcblk0p1"));
+    }
+
+    #[test]
+    fn test_glob_match_bracket_edge_cases() {
+        assert!(glob_match("a[]]b", "a]b"));
+        assert!(glob_match("a[b-]", "a-"));
+        assert!(glob_match("a[", "a["));
+        assert!(!glob_match("a[bc]", "a"));
+        assert!(!glob_match("a[bc]", "ad"));
+    }
+}
@@ -15,6 +15,7 @@
 #![warn(clippy::all)]
 
 use std::io::{Read, Seek, Write};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 /// Platform-specific errors
@@ -41,6 +42,14 @@ pub enum PlatformError {
     #[error("Unmount failed: {0}")]
     UnmountFailed(String),
 
+    /// Failed to eject or power down device
+    #[error("Eject failed: {0}")]
+    EjectFailed(String),
+
+    /// Failed to make the OS re-read the partition table
+    #[error("Partition table re-read failed: {0}")]
+    RereadFailed(String),
+
     /// Operation not supported on this platform
     #[error("Not supported: {0}")]
     NotSupported(String),
@@ -52,6 +61,10 @@ pub enum PlatformError {
     /// Alignment error for direct I/O
     #[error("Alignment error: {0}")]
     AlignmentError(String),
+
+    /// Failed to create or format a partition
+    #[error("Partitioning failed: {0}")]
+    PartitionFailed(String),
 }
 
 /// Result type for platform operations
@@ -71,8 +84,21 @@ pub struct OpenOptions {
 
     /// Block size for alignment (typically 512 or 4096)
     pub block_size: usize,
+
+    /// Extra attempts when the device is still busy after unmounting (macOS)
+    pub open_retries: u32,
+
+    /// Refuse to open a device that is mounted or held by another program
+    /// (only applies when opening for writing)
+    pub exclusive: bool,
+
+    /// Leave all-zero writes to a regular file as holes (ignored for devices)
+    pub sparse: bool,
 }
 
+/// Default [`OpenOptions::open_retries`]
+pub const DEFAULT_OPEN_RETRIES: u32 = 3;
+
 impl Default for OpenOptions {
     fn default() -> Self {
         Self {
@@ -80,6 +106,9 @@ impl Default for OpenOptions {
             read: true,
             write: true,
             block_size: 4096,
+            open_retries: DEFAULT_OPEN_RETRIES,
+            exclusive: true,
+            sparse: false,
         }
     }
 }
@@ -113,6 +142,47 @@ impl OpenOptions {
         self.block_size = size;
         self
     }
+
+    /// Set how often a busy device is retried before giving up
+    ///
+    /// On macOS a raw disk can report "Resource busy" for a moment after
+    /// `diskutil unmountDisk`. Each retry waits briefly; once they run out,
+    /// a device opened for writing gets a forced unmount before one final
+    /// open, while a read-only open fails. Other platforms ignore this setting.
+    pub fn open_retries(mut self, retries: u32) -> Self {
+        self.open_retries = retries;
+        self
+    }
+
+    /// Set whether a write open takes an exclusive lock on the device
+    ///
+    /// Enabled by default, so that two writes to the same device, or a write
+    /// to a device something has mounted, fail with
+    /// [`PlatformError::DeviceBusy`] instead of corrupting it. Linux opens
+    /// the block device with `O_EXCL`, macOS takes an `flock` on it and
+    /// Windows locks it with `FSCTL_LOCK_VOLUME`. Read-only opens never lock.
+    pub fn exclusive(mut self, exclusive: bool) -> Self {
+        self.exclusive = exclusive;
+        self
+    }
+
+    /// Set whether all-zero writes to a regular file leave holes
+    ///
+    /// Off by default. When the target is a regular file rather than a block
+    /// device, a write whose data is all zeros seeks past the region instead,
+    /// so a mostly empty image produces a sparse file with the same apparent
+    /// size. On Linux a region that already holds data is cleared with
+    /// `fallocate(FALLOC_FL_PUNCH_HOLE)`; macOS only skips zeros past the end
+    /// of the file. Block devices and Windows ignore this setting.
+    pub fn sparse(mut self, sparse: bool) -> Self {
+        self.sparse = sparse;
+        self
+    }
+
+    /// Whether the open should lock the device
+    pub(crate) fn locks_device(&self) -> bool {
+        self.exclusive && self.write
+    }
 }
 
 /// Information about an open device
@@ -124,11 +194,23 @@ pub struct DeviceInfo {
     /// Total size in bytes
     pub size: u64,
 
-    /// Physical block size
+    /// Logical block size, which direct I/O must be aligned to
     pub block_size: u32,
 
     /// Whether direct I/O is enabled
     pub direct_io: bool,
+
+    /// Physical block size, or 0 if the platform doesn't report it
+    ///
+    /// Larger than `block_size` on 512e drives, where writes smaller than a
+    /// physical sector cost a read-modify-write in the firmware.
+    pub physical_block_size: u32,
+
+    /// Optimal I/O size the device advertises, if any
+    pub optimal_io_size: Option<u32>,
+
+    /// Whether the device reports itself as spinning media
+    pub is_rotational: bool,
 }
 
 /// Trait for raw device I/O operations
@@ -144,11 +226,120 @@ pub trait RawDevice: Read + Write + Seek + Send {
     /// Sync all pending writes to the device
     fn sync(&self) -> Result<()>;
 
+    /// Sync all pending writes, reporting how much is still to be flushed
+    ///
+    /// `progress` receives the number of dirty bytes still waiting to be
+    /// written back. The default implementation just calls
+    /// [`sync`](Self::sync) without reporting; on Linux the kernel's
+    /// dirty/writeback page counts are polled while the sync runs.
+    fn sync_with_progress(&self, progress: &mut dyn FnMut(u64)) -> Result<()> {
+        let _ = progress;
+        self.sync()
+    }
+
+    /// Drop the device's pages from the OS page cache
+    ///
+    /// Reads straight after a write can be served from the cache rather
+    /// than the device, so a read-back verify would check memory and could
+    /// pass on a drive that silently lost the data. Pending writes are
+    /// synced first so every cached page can be dropped. The default
+    /// implementation does nothing; Linux uses `posix_fadvise(DONTNEED)`.
+    fn drop_cache(&self) -> Result<()> {
+        Ok(())
+    }
+
     /// Write data at a specific offset
     fn write_at(&mut self, offset: u64, data: &[u8]) -> Result<usize>;
 
     /// Read data from a specific offset
     fn read_at(&mut self, offset: u64, buffer: &mut [u8]) -> Result<usize>;
+
+    /// Write several `(offset, data)` chunks, returning the total bytes written
+    ///
+    /// The default implementation calls [`write_at`](Self::write_at) for each
+    /// chunk in order. Platforms with an asynchronous I/O path (e.g. `io_uring`
+    /// on Linux with the `io-uring` feature) may keep several writes in flight.
+    fn write_batch(&mut self, chunks: &[(u64, &[u8])]) -> Result<usize> {
+        let mut total = 0;
+        for (offset, data) in chunks {
+            total += self.write_at(*offset, data)?;
+        }
+        Ok(total)
+    }
+}
+
+/// Filesystem for a data partition created in the space after an image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DataFilesystem {
+    /// FAT32, readable everywhere
+    Fat32,
+    /// exFAT, for files over 4 GiB
+    ExFat,
+}
+
+impl DataFilesystem {
+    /// Display name, as used by the native formatting tools
+    pub fn name(&self) -> &'static str {
+        match self {
+            DataFilesystem::Fat32 => "FAT32",
+            DataFilesystem::ExFat => "exFAT",
+        }
+    }
+}
+
+impl std::fmt::Display for DataFilesystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl std::str::FromStr for DataFilesystem {
+    type Err = PlatformError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "fat32" | "vfat" => Ok(DataFilesystem::Fat32),
+            "exfat" => Ok(DataFilesystem::ExFat),
+            _ => Err(PlatformError::NotSupported(format!(
+                "Unknown filesystem: {} (expected fat32 or exfat)",
+                s
+            ))),
+        }
+    }
+}
+
+/// Volume label given to a data partition
+pub const DATA_PARTITION_LABEL: &str = "DATA";
+
+/// Data partitions start on a 1 MiB boundary, like native partitioning tools
+pub const DATA_PARTITION_ALIGNMENT: u64 = 1024 * 1024;
+
+/// Smallest data partition worth creating (FAT32 needs roughly 32 MiB)
+pub const MIN_DATA_PARTITION_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Where a data partition after an image ending at `image_end` would go
+///
+/// Returns the aligned `(start, size)` in bytes, or
+/// [`PlatformError::PartitionFailed`] if the image leaves too little room on a
+/// device of `device_size` bytes.
+pub fn data_partition_span(device_size: u64, image_end: u64) -> Result<(u64, u64)> {
+    let start = image_end
+        .div_ceil(DATA_PARTITION_ALIGNMENT)
+        .saturating_mul(DATA_PARTITION_ALIGNMENT);
+    let size = device_size.saturating_sub(start);
+
+    if size < MIN_DATA_PARTITION_SIZE {
+        return Err(PlatformError::PartitionFailed(format!(
+            "The image occupies {} of the device's {} bytes, leaving no room for a data partition \
+             (at least {} MiB needed)",
+            image_end,
+            device_size,
+            MIN_DATA_PARTITION_SIZE / (1024 * 1024)
+        )));
+    }
+
+    Ok((start, size))
 }
 
 /// Platform operations interface
@@ -157,7 +348,24 @@ pub trait PlatformOps {
     fn open_device(path: &str, options: OpenOptions) -> Result<Box<dyn RawDevice>>;
 
     /// Unmount all filesystems on a device
-    fn unmount_device(path: &str) -> Result<()>;
+    fn unmount_device(path: &str) -> Result<()> {
+        Self::unmount_device_with_progress(path, &mut |_| {})
+    }
+
+    /// Unmount all filesystems on a device, reporting each step
+    ///
+    /// `progress` receives short status strings such as
+    /// `"unmounting /media/usb"`, `"retrying /media/usb"` and `"done"`.
+    fn unmount_device_with_progress(path: &str, progress: &mut dyn FnMut(&str)) -> Result<()>;
+
+    /// Eject the media or power down the device
+    fn eject(path: &str) -> Result<()>;
+
+    /// Ask the OS to re-read the device's partition table
+    fn reread_partition_table(path: &str) -> Result<()>;
+
+    /// Create a partition spanning the free space after `start_offset` and format it as `fs`
+    fn create_data_partition(path: &str, fs: DataFilesystem, start_offset: u64) -> Result<()>;
 
     /// Sync all pending writes system-wide
     fn sync_all() -> Result<()>;
@@ -167,8 +375,74 @@ pub trait PlatformOps {
 
     /// Get the recommended block size for a device
     fn get_block_size(path: &str) -> Result<u32>;
+
+    /// Flash a drive's activity LED for about `duration` so it can be found
+    ///
+    /// The default reads from the device in bursts, which blinks the
+    /// activity LED most USB sticks and card readers have. Platforms with
+    /// LED control may use that instead.
+    fn identify(path: &str, duration: Duration) -> Result<()>
+    where
+        Self: Sized,
+    {
+        identify_by_reading::<Self>(path, duration)
+    }
 }
 
+/// How long each burst of reads lasts while identifying a drive
+pub(crate) const IDENTIFY_BURST: Duration = Duration::from_millis(300);
+
+/// Pause between bursts of reads while identifying a drive
+pub(crate) const IDENTIFY_PAUSE: Duration = Duration::from_millis(300);
+
+/// Blocks skipped between consecutive identify reads
+///
+/// A large prime spreads the reads across the device, so they keep hitting
+/// the hardware instead of the page cache when direct I/O is unavailable.
+const IDENTIFY_STRIDE_BLOCKS: u64 = 7919;
+
+/// Blink a drive's activity LED by reading from it in bursts
+pub(crate) fn identify_by_reading<P: PlatformOps>(path: &str, duration: Duration) -> Result<()> {
+    let options = OpenOptions::new().write(false);
+    let mut device = match P::open_device(path, options.clone()) {
+        Err(e) if is_direct_io_unsupported(&e) => P::open_device(path, options.direct_io(false))?,
+        result => result?,
+    };
+    blink_with_reads(device.as_mut(), duration)
+}
+
+/// Read from `device` in bursts of [`IDENTIFY_BURST`] until `duration` has passed
+fn blink_with_reads(device: &mut dyn RawDevice, duration: Duration) -> Result<()> {
+    let block_size = u64::from(device.info().block_size.max(512));
+    let blocks = (device.info().size / block_size).max(1);
+    let mut buffer = vec![0u8; block_size as usize];
+    let mut read_index: u64 = 0;
+
+    let start = Instant::now();
+    while start.elapsed() < duration {
+        let burst = Instant::now();
+        while burst.elapsed() < IDENTIFY_BURST {
+            let block = read_index.wrapping_mul(IDENTIFY_STRIDE_BLOCKS) % blocks;
+            device.read_at(block * block_size, &mut buffer)?;
+            read_index += 1;
+        }
+        std::thread::sleep(IDENTIFY_PAUSE.min(duration.saturating_sub(start.elapsed())));
+    }
+    Ok(())
+}
+
+/// How many times a busy mount is tried before unmounting gives up
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub(crate) const UNMOUNT_ATTEMPTS: u32 = 3;
+
+/// Pause between unmount attempts while a volume is busy
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub(crate) const UNMOUNT_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Pause between attempts to open a device that is still busy
+#[cfg(target_os = "macos")]
+pub(crate) const OPEN_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(250);
+
 /// Align a value up to the given alignment
 #[inline]
 pub fn align_up(value: usize, alignment: usize) -> usize {
@@ -204,6 +478,83 @@ pub fn is_ptr_aligned<T>(ptr: *const T, alignment: usize) -> bool {
     is_aligned(ptr as usize, alignment)
 }
 
+/// Zeroed heap buffer whose contents start on an aligned address
+///
+/// Direct I/O needs buffers aligned to the device's logical block size (512
+/// or 4096 bytes, depending on the device), which `Vec<u8>` does not
+/// guarantee. The buffer over-allocates by `align` bytes and exposes the
+/// aligned part as a slice; it never reallocates, so the alignment holds
+/// for its whole life.
+pub struct AlignedBuffer {
+    data: Vec<u8>,
+    offset: usize,
+    len: usize,
+    alignment: usize,
+}
+
+impl AlignedBuffer {
+    /// Allocate `len` zeroed bytes starting on an `align`-byte boundary
+    ///
+    /// An `align` of 0 or 1 means no alignment.
+    pub fn new(len: usize, align: usize) -> Self {
+        let align = align.max(1);
+        let data = vec![0u8; len + align - 1];
+        let misalignment = data.as_ptr() as usize % align;
+        let offset = (align - misalignment) % align;
+        Self {
+            data,
+            offset,
+            len,
+            alignment: align,
+        }
+    }
+
+    /// Alignment of the buffer's start, in bytes
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+}
+
+impl std::ops::Deref for AlignedBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.data[self.offset..self.offset + self.len]
+    }
+}
+
+impl std::ops::DerefMut for AlignedBuffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.data[self.offset..self.offset + self.len]
+    }
+}
+
+impl std::fmt::Debug for AlignedBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AlignedBuffer")
+            .field("len", &self.len)
+            .field("alignment", &self.alignment)
+            .finish()
+    }
+}
+
+/// Allocate a zeroed buffer of `len` bytes aligned to `align` (see [`AlignedBuffer`])
+pub fn aligned_buffer(len: usize, align: usize) -> AlignedBuffer {
+    AlignedBuffer::new(len, align)
+}
+
+/// Whether an open error means direct I/O is unsupported rather than a real failure
+fn is_direct_io_unsupported(err: &PlatformError) -> bool {
+    match err {
+        PlatformError::AlignmentError(_) => true,
+        PlatformError::Io(e) => e.kind() == std::io::ErrorKind::InvalidInput,
+        _ => false,
+    }
+}
+
+mod glob;
+pub use glob::glob_match;
+
 // Platform-specific implementations
 cfg_if::cfg_if! {
     if #[cfg(target_os = "linux")] {
@@ -222,8 +573,21 @@ cfg_if::cfg_if! {
 cfg_if::cfg_if! {
     if #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))] {
         /// Open a device for raw I/O using platform defaults
+        ///
+        /// If direct I/O is requested but the device or filesystem rejects it
+        /// (e.g. `EINVAL` from `O_DIRECT`), the device is reopened with
+        /// buffered I/O. Check [`DeviceInfo::direct_io`] on the result to see
+        /// which mode is in use.
         pub fn open_device(path: &str, options: OpenOptions) -> Result<Box<dyn RawDevice>> {
-            Platform::open_device(path, options)
+            match Platform::open_device(path, options.clone()) {
+                Err(e) if options.direct_io && is_direct_io_unsupported(&e) => {
+                    tracing::warn!(
+                        "Direct I/O not supported for {path} ({e}); falling back to buffered I/O, performance may suffer"
+                    );
+                    Platform::open_device(path, options.direct_io(false))
+                }
+                result => result,
+            }
         }
 
         /// Unmount all filesystems on a device
@@ -231,6 +595,45 @@ cfg_if::cfg_if! {
             Platform::unmount_device(path)
         }
 
+        /// Unmount all filesystems on a device, reporting each step to `progress`
+        pub fn unmount_device_with_progress(
+            path: &str,
+            progress: &mut dyn FnMut(&str),
+        ) -> Result<()> {
+            Platform::unmount_device_with_progress(path, progress)
+        }
+
+        /// Eject or power down a device
+        pub fn eject_device(path: &str) -> Result<()> {
+            Platform::eject(path)
+        }
+
+        /// Ask the OS to re-read a device's partition table
+        pub fn reread_partition_table(path: &str) -> Result<()> {
+            Platform::reread_partition_table(path)
+        }
+
+        /// Create and format a partition in the free space after `start_offset`
+        ///
+        /// `start_offset` is the end of the written image; the partition starts
+        /// at the next [`DATA_PARTITION_ALIGNMENT`] boundary and is labelled
+        /// [`DATA_PARTITION_LABEL`]. The device must not be mounted.
+        pub fn create_data_partition(
+            path: &str,
+            fs: DataFilesystem,
+            start_offset: u64,
+        ) -> Result<()> {
+            Platform::create_data_partition(path, fs, start_offset)
+        }
+
+        /// Flash a drive's activity LED for about `duration`
+        ///
+        /// Lets the user match a device path to the physical drive before
+        /// writing to it. See [`PlatformOps::identify`].
+        pub fn identify_device(path: &str, duration: Duration) -> Result<()> {
+            Platform::identify(path, duration)
+        }
+
         /// Check if running with elevated privileges
         pub fn has_elevated_privileges() -> bool {
             Platform::has_elevated_privileges()
@@ -251,6 +654,38 @@ cfg_if::cfg_if! {
             Err(PlatformError::NotSupported("Platform not supported".to_string()))
         }
 
+        /// Unmount a device with progress (unsupported platform)
+        pub fn unmount_device_with_progress(
+            _path: &str,
+            _progress: &mut dyn FnMut(&str),
+        ) -> Result<()> {
+            Err(PlatformError::NotSupported("Platform not supported".to_string()))
+        }
+
+        /// Eject a device (unsupported platform)
+        pub fn eject_device(_path: &str) -> Result<()> {
+            Err(PlatformError::NotSupported("Platform not supported".to_string()))
+        }
+
+        /// Re-read a partition table (unsupported platform)
+        pub fn reread_partition_table(_path: &str) -> Result<()> {
+            Err(PlatformError::NotSupported("Platform not supported".to_string()))
+        }
+
+        /// Create a data partition (unsupported platform)
+        pub fn create_data_partition(
+            _path: &str,
+            _fs: DataFilesystem,
+            _start_offset: u64,
+        ) -> Result<()> {
+            Err(PlatformError::NotSupported("Platform not supported".to_string()))
+        }
+
+        /// Identify a drive (unsupported platform)
+        pub fn identify_device(_path: &str, _duration: Duration) -> Result<()> {
+            Err(PlatformError::NotSupported("Platform not supported".to_string()))
+        }
+
         /// Check privileges (unsupported platform)
         pub fn has_elevated_privileges() -> bool {
             false
@@ -353,6 +788,8 @@ mod tests {
         assert!(opts.read);
         assert!(opts.write);
         assert_eq!(opts.block_size, 4096);
+        assert_eq!(opts.open_retries, DEFAULT_OPEN_RETRIES);
+        assert!(opts.exclusive);
     }
 
     #[test]
@@ -361,12 +798,21 @@ mod tests {
             .direct_io(false)
             .read(true)
             .write(false)
-            .block_size(512);
+            .block_size(512)
+            .open_retries(0);
 
         assert!(!opts.direct_io);
         assert!(opts.read);
         assert!(!opts.write);
         assert_eq!(opts.block_size, 512);
+        assert_eq!(opts.open_retries, 0);
+    }
+
+    #[test]
+    fn test_open_options_exclusive_only_locks_writes() {
+        assert!(OpenOptions::new().locks_device());
+        assert!(!OpenOptions::new().exclusive(false).locks_device());
+        assert!(!OpenOptions::new().write(false).locks_device());
     }
 
     // -------------------------------------------------------------------------
@@ -399,6 +845,12 @@ mod tests {
         assert!(err.to_string().contains("Unmount failed"));
         assert!(err.to_string().contains("still in use"));
 
+        let err = PlatformError::EjectFailed("udisksctl missing".to_string());
+        assert!(err.to_string().contains("Eject failed"));
+
+        let err = PlatformError::RereadFailed("device busy".to_string());
+        assert!(err.to_string().contains("Partition table re-read failed"));
+
         let err = PlatformError::NotSupported("feature X".to_string());
         assert!(err.to_string().contains("Not supported"));
 
@@ -410,6 +862,24 @@ mod tests {
         assert!(err.to_string().contains("buffer not 4K aligned"));
     }
 
+    #[test]
+    fn test_is_direct_io_unsupported() {
+        let einval = std::io::Error::from(std::io::ErrorKind::InvalidInput);
+        assert!(is_direct_io_unsupported(&PlatformError::Io(einval)));
+        assert!(is_direct_io_unsupported(&PlatformError::AlignmentError(
+            "offset".to_string()
+        )));
+
+        let denied = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        assert!(!is_direct_io_unsupported(&PlatformError::Io(denied)));
+        assert!(!is_direct_io_unsupported(&PlatformError::DeviceBusy(
+            "/dev/sdb".to_string()
+        )));
+        assert!(!is_direct_io_unsupported(&PlatformError::DeviceNotFound(
+            "/dev/sdz".to_string()
+        )));
+    }
+
     // -------------------------------------------------------------------------
     // is_ptr_aligned tests
     // -------------------------------------------------------------------------
@@ -457,6 +927,48 @@ mod tests {
         assert_eq!(align_up(value, 4096), value);
     }
 
+    // -------------------------------------------------------------------------
+    // AlignedBuffer tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_aligned_buffer_512() {
+        let buffer = aligned_buffer(4096, 512);
+        assert_eq!(buffer.len(), 4096);
+        assert_eq!(buffer.alignment(), 512);
+        assert!(is_ptr_aligned(buffer.as_ptr(), 512));
+        assert!(buffer.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_aligned_buffer_4096() {
+        for len in [4096, align_up(5000, 4096), 1024 * 1024] {
+            let buffer = aligned_buffer(len, 4096);
+            assert_eq!(buffer.len(), len);
+            assert!(is_ptr_aligned(buffer.as_ptr(), 4096));
+            assert!(is_aligned(buffer.len(), 4096));
+        }
+    }
+
+    #[test]
+    fn test_aligned_buffer_is_writable_and_stays_aligned() {
+        let mut buffer = aligned_buffer(1024, 512);
+        buffer[..5].copy_from_slice(b"hello");
+
+        // Moving the buffer does not move its heap allocation
+        let moved = buffer;
+        assert_eq!(&moved[..5], b"hello");
+        assert!(is_ptr_aligned(moved.as_ptr(), 512));
+    }
+
+    #[test]
+    fn test_aligned_buffer_no_alignment() {
+        let buffer = aligned_buffer(100, 0);
+        assert_eq!(buffer.len(), 100);
+        assert_eq!(buffer.alignment(), 1);
+        assert!(aligned_buffer(0, 4096).is_empty());
+    }
+
     // -------------------------------------------------------------------------
     // DeviceInfo tests
     // -------------------------------------------------------------------------
@@ -468,6 +980,9 @@ mod tests {
             size: 32 * 1024 * 1024 * 1024,
             block_size: 512,
             direct_io: true,
+            physical_block_size: 4096,
+            optimal_io_size: Some(1024 * 1024),
+            is_rotational: false,
         };
 
         assert_eq!(info.path, "/dev/sdb");
@@ -483,12 +998,18 @@ mod tests {
             size: 1024,
             block_size: 4096,
             direct_io: false,
+            physical_block_size: 4096,
+            optimal_io_size: None,
+            is_rotational: true,
         };
         let cloned = info.clone();
         assert_eq!(cloned.path, info.path);
         assert_eq!(cloned.size, info.size);
         assert_eq!(cloned.block_size, info.block_size);
         assert_eq!(cloned.direct_io, info.direct_io);
+        assert_eq!(cloned.physical_block_size, info.physical_block_size);
+        assert_eq!(cloned.optimal_io_size, info.optimal_io_size);
+        assert_eq!(cloned.is_rotational, info.is_rotational);
     }
 
     // -------------------------------------------------------------------------
@@ -512,4 +1033,47 @@ mod tests {
         let opts = OpenOptions::new().block_size(4096);
         assert_eq!(opts.block_size, 4096);
     }
+
+    // -------------------------------------------------------------------------
+    // Data partition tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_data_filesystem_from_str() {
+        assert_eq!(
+            "fat32".parse::<DataFilesystem>().unwrap(),
+            DataFilesystem::Fat32
+        );
+        assert_eq!(
+            "FAT32".parse::<DataFilesystem>().unwrap(),
+            DataFilesystem::Fat32
+        );
+        assert_eq!(
+            "exfat".parse::<DataFilesystem>().unwrap(),
+            DataFilesystem::ExFat
+        );
+        assert!("ntfs".parse::<DataFilesystem>().is_err());
+        assert_eq!(DataFilesystem::ExFat.to_string(), "exFAT");
+    }
+
+    #[test]
+    fn test_data_partition_span_aligns_start() {
+        const MIB: u64 = 1024 * 1024;
+        let (start, size) = data_partition_span(1024 * MIB, 300 * MIB + 1).unwrap();
+        assert_eq!(start, 301 * MIB);
+        assert_eq!(size, 723 * MIB);
+
+        let (start, _) = data_partition_span(1024 * MIB, 300 * MIB).unwrap();
+        assert_eq!(start, 300 * MIB);
+    }
+
+    #[test]
+    fn test_data_partition_span_image_fills_device() {
+        const MIB: u64 = 1024 * 1024;
+        let err = data_partition_span(1024 * MIB, 1000 * MIB).unwrap_err();
+        assert!(matches!(err, PlatformError::PartitionFailed(_)));
+        assert!(err.to_string().contains("no room for a data partition"));
+
+        assert!(data_partition_span(1024 * MIB, 2048 * MIB).is_err());
+    }
 }
@@ -3,7 +3,9 @@
 //! Uses O_DIRECT for direct I/O and standard POSIX file operations.
 
 use crate::{
-    align_up, is_aligned, DeviceInfo, OpenOptions, PlatformError, PlatformOps, RawDevice, Result,
+    align_up, data_partition_span, is_aligned, AlignedBuffer, DataFilesystem, DeviceInfo,
+    OpenOptions, PlatformError, PlatformOps, RawDevice, Result, DATA_PARTITION_LABEL,
+    UNMOUNT_ATTEMPTS, UNMOUNT_RETRY_DELAY,
 };
 use std::fs::{File, OpenOptions as StdOpenOptions};
 use std::io::{Read, Seek, SeekFrom, Write};
@@ -11,11 +13,19 @@ use std::os::unix::fs::OpenOptionsExt;
 use std::os::unix::io::AsRawFd;
 use std::path::Path;
 use std::process::Command;
+use std::sync::mpsc;
+use std::time::Duration;
+
+#[cfg(feature = "io-uring")]
+mod uring;
 
 /// Linux O_DIRECT flag
 #[cfg(target_os = "linux")]
 const O_DIRECT: i32 = 0o40000;
 
+/// How often `/proc/meminfo` is polled while a sync is running
+const SYNC_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
 /// Linux platform implementation
 pub struct LinuxPlatform;
 
@@ -24,8 +34,20 @@ impl PlatformOps for LinuxPlatform {
         LinuxDevice::open(path, options).map(|d| Box::new(d) as Box<dyn RawDevice>)
     }
 
-    fn unmount_device(path: &str) -> Result<()> {
-        unmount_linux_device(path)
+    fn unmount_device_with_progress(path: &str, progress: &mut dyn FnMut(&str)) -> Result<()> {
+        unmount_linux_device(path, progress)
+    }
+
+    fn eject(path: &str) -> Result<()> {
+        power_off_linux_device(path)
+    }
+
+    fn reread_partition_table(path: &str) -> Result<()> {
+        reread_linux_partition_table(path)
+    }
+
+    fn create_data_partition(path: &str, fs: DataFilesystem, start_offset: u64) -> Result<()> {
+        create_linux_data_partition(path, fs, start_offset)
     }
 
     fn sync_all() -> Result<()> {
@@ -53,6 +75,13 @@ impl PlatformOps for LinuxPlatform {
     fn get_block_size(path: &str) -> Result<u32> {
         get_device_block_size(path)
     }
+
+    fn identify(path: &str, duration: Duration) -> Result<()> {
+        if locate_with_ledctl(path, duration) {
+            return Ok(());
+        }
+        crate::identify_by_reading::<Self>(path, duration)
+    }
 }
 
 /// Linux device wrapper for raw I/O
@@ -61,39 +90,11 @@ pub struct LinuxDevice {
     info: DeviceInfo,
     /// Aligned buffer for direct I/O operations
     aligned_buffer: Option<AlignedBuffer>,
-}
-
-/// Aligned buffer for O_DIRECT operations
-struct AlignedBuffer {
-    data: Vec<u8>,
-    alignment: usize,
-}
-
-impl AlignedBuffer {
-    fn new(size: usize, alignment: usize) -> Self {
-        // Allocate with extra space for alignment
-        let total_size = size + alignment;
-        let data = vec![0u8; total_size];
-        Self { data, alignment }
-    }
-
-    /// Returns an aligned slice for reading.
-    ///
-    /// This is the immutable counterpart to [`Self::as_aligned_slice_mut`].
-    #[allow(dead_code)] // Provided for API completeness
-    fn as_aligned_slice(&self, len: usize) -> &[u8] {
-        let ptr = self.data.as_ptr();
-        let aligned_ptr = align_up(ptr as usize, self.alignment) as *const u8;
-        let offset = aligned_ptr as usize - ptr as usize;
-        &self.data[offset..offset + len]
-    }
-
-    fn as_aligned_slice_mut(&mut self, len: usize) -> &mut [u8] {
-        let ptr = self.data.as_ptr();
-        let aligned_ptr = align_up(ptr as usize, self.alignment) as *mut u8;
-        let offset = aligned_ptr as usize - ptr as usize;
-        &mut self.data[offset..offset + len]
-    }
+    /// Leave all-zero writes as holes (regular files opened with `sparse`)
+    sparse: bool,
+    /// io_uring writer for batched writes (None if unavailable)
+    #[cfg(feature = "io-uring")]
+    uring: Option<uring::UringWriter>,
 }
 
 impl LinuxDevice {
@@ -110,10 +111,17 @@ impl LinuxDevice {
         let mut std_options = StdOpenOptions::new();
         std_options.read(options.read).write(options.write);
 
-        // Add O_DIRECT for direct I/O if requested
+        // Add O_DIRECT for direct I/O and O_EXCL for an exclusive claim if requested
         #[cfg(target_os = "linux")]
-        if options.direct_io {
-            std_options.custom_flags(O_DIRECT);
+        {
+            let mut flags = 0;
+            if options.direct_io {
+                flags |= O_DIRECT;
+            }
+            if options.locks_device() {
+                flags |= libc::O_EXCL;
+            }
+            std_options.custom_flags(flags);
         }
 
         // Try to open the device
@@ -123,8 +131,13 @@ impl LinuxDevice {
                     "Cannot open {}: {}. Try running with sudo.",
                     path, e
                 ))
-            } else if e.raw_os_error() == Some(16) {
-                // EBUSY
+            } else if e.raw_os_error() == Some(libc::EBUSY) && options.locks_device() {
+                PlatformError::DeviceBusy(format!(
+                    "{} is in use: it is mounted or open in another program \
+                     (such as another engraver write). Unmount it or close that program first.",
+                    path
+                ))
+            } else if e.raw_os_error() == Some(libc::EBUSY) {
                 PlatformError::DeviceBusy(format!("{} is busy. Try unmounting first.", path))
             } else {
                 PlatformError::Io(e)
@@ -133,31 +146,69 @@ impl LinuxDevice {
 
         // Get device size
         let size = get_device_size(&file, path)?;
-        let block_size = options.block_size as u32;
+
+        // Direct I/O alignment follows the device's logical block size (512 on
+        // many SD cards and 512e drives), not the requested write block size
+        let block_size = logical_block_size(&file).unwrap_or(options.block_size as u32);
+        let geometry = DeviceGeometry::of(&file, path);
 
         let info = DeviceInfo {
             path: path.to_string(),
             size,
             block_size,
             direct_io: options.direct_io,
+            physical_block_size: geometry.physical_block_size,
+            optimal_io_size: geometry.optimal_io_size,
+            is_rotational: geometry.is_rotational,
         };
 
         // Create aligned buffer for direct I/O
         let aligned_buffer = if options.direct_io {
             Some(AlignedBuffer::new(
-                options.block_size * 2,
-                options.block_size,
+                align_up(options.block_size * 2, block_size as usize),
+                block_size as usize,
             ))
         } else {
             None
         };
 
+        let sparse = options.sparse && file.metadata().is_ok_and(|m| m.is_file());
+
         Ok(Self {
             file,
             info,
             aligned_buffer,
+            sparse,
+            #[cfg(feature = "io-uring")]
+            uring: if options.write {
+                uring::UringWriter::new()
+            } else {
+                None
+            },
         })
     }
+
+    /// Leave all-zero `data` at `offset` as a hole instead of writing it
+    ///
+    /// Returns `false` if the zeros still have to be written: the device is
+    /// not sparse, the data is not all zeros, or a hole could not be punched
+    /// over existing data. On success the file position is past the region.
+    fn write_hole(&mut self, offset: u64, data: &[u8]) -> std::io::Result<bool> {
+        if !self.sparse || data.iter().any(|&b| b != 0) {
+            return Ok(false);
+        }
+
+        let end = offset + data.len() as u64;
+        let len = self.file.metadata()?.len();
+        if offset < len && !punch_hole(&self.file, offset, end.min(len) - offset) {
+            return Ok(false);
+        }
+        if end > len {
+            self.file.set_len(end)?;
+        }
+        self.file.seek(SeekFrom::Start(end))?;
+        Ok(true)
+    }
 }
 
 impl RawDevice for LinuxDevice {
@@ -166,20 +217,54 @@ impl RawDevice for LinuxDevice {
     }
 
     fn sync(&self) -> Result<()> {
-        // Use fsync to flush writes
-        let fd = self.file.as_raw_fd();
-        // SAFETY: fsync() is called with a valid file descriptor obtained from as_raw_fd().
-        // The fd remains valid for the lifetime of self.file.
+        fsync_file(&self.file)
+    }
+
+    fn drop_cache(&self) -> Result<()> {
+        fsync_file(&self.file)?;
+        // SAFETY: posix_fadvise only reads its arguments, and the fd is valid
+        // for the lifetime of `self.file`. A length of 0 means "to the end".
         #[allow(unsafe_code)]
-        let result = unsafe { libc::fsync(fd) };
-        if result == 0 {
-            Ok(())
-        } else {
-            Err(PlatformError::Io(std::io::Error::last_os_error()))
+        let ret =
+            unsafe { libc::posix_fadvise(self.file.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED) };
+        if ret != 0 {
+            return Err(PlatformError::Io(std::io::Error::from_raw_os_error(ret)));
         }
+        Ok(())
+    }
+
+    fn sync_with_progress(&self, progress: &mut dyn FnMut(u64)) -> Result<()> {
+        let file = &self.file;
+        std::thread::scope(|scope| {
+            let (done_tx, done_rx) = mpsc::channel();
+            scope.spawn(move || {
+                let _ = done_tx.send(fsync_file(file));
+            });
+
+            // The kernel only tracks dirty pages system-wide, so this counts
+            // every pending write, not just this device's
+            loop {
+                if let Some(dirty) = read_dirty_bytes() {
+                    progress(dirty);
+                }
+                match done_rx.recv_timeout(SYNC_POLL_INTERVAL) {
+                    Ok(result) => return result,
+                    Err(mpsc::RecvTimeoutError::Timeout) => {}
+                    Err(mpsc::RecvTimeoutError::Disconnected) => {
+                        return Err(PlatformError::Io(std::io::Error::other(
+                            "sync thread exited without a result",
+                        )));
+                    }
+                }
+            }
+        })
     }
 
     fn write_at(&mut self, offset: u64, data: &[u8]) -> Result<usize> {
+        if self.write_hole(offset, data)? {
+            return Ok(data.len());
+        }
+
         // Seek to offset
         self.file.seek(SeekFrom::Start(offset))?;
 
@@ -203,7 +288,7 @@ impl RawDevice for LinuxDevice {
             // Use aligned buffer
             if let Some(ref mut buffer) = self.aligned_buffer {
                 let aligned_len = align_up(data.len(), block_size);
-                let aligned_slice = buffer.as_aligned_slice_mut(aligned_len);
+                let aligned_slice = &mut buffer[..aligned_len];
 
                 // Copy data to aligned buffer
                 aligned_slice[..data.len()].copy_from_slice(data);
@@ -247,7 +332,7 @@ impl RawDevice for LinuxDevice {
             // Use aligned buffer
             if let Some(ref mut aligned_buf) = self.aligned_buffer {
                 let aligned_len = align_up(buffer.len(), block_size);
-                let aligned_slice = aligned_buf.as_aligned_slice_mut(aligned_len);
+                let aligned_slice = &mut aligned_buf[..aligned_len];
 
                 let bytes_read = self.file.read(&mut aligned_slice[..aligned_len])?;
                 let copy_len = bytes_read.min(buffer.len());
@@ -260,6 +345,36 @@ impl RawDevice for LinuxDevice {
             self.file.read(buffer).map_err(PlatformError::Io)
         }
     }
+
+    #[cfg(feature = "io-uring")]
+    fn write_batch(&mut self, chunks: &[(u64, &[u8])]) -> Result<usize> {
+        // Sparse writes check each chunk for zeros, so they go one at a time
+        let uring = self.uring.as_mut().filter(|_| !self.sparse);
+        let Some(uring) = uring else {
+            return chunks.iter().try_fold(0, |total, (offset, data)| {
+                Ok(total + self.write_at(*offset, data)?)
+            });
+        };
+
+        let result = uring.write_batch(
+            self.file.as_raw_fd(),
+            chunks,
+            self.info.block_size as usize,
+            self.info.direct_io,
+        );
+        if uring.is_poisoned() {
+            tracing::warn!("io_uring ring failed with writes in flight, using synchronous writes");
+            self.uring = None;
+        }
+        let written = result?;
+
+        // Leave the file position where sequential write_at calls would have
+        if let (Some((offset, _)), Some(last)) = (chunks.last(), written.last()) {
+            self.file.seek(SeekFrom::Start(offset + *last as u64))?;
+        }
+
+        Ok(written.iter().sum())
+    }
 }
 
 impl Read for LinuxDevice {
@@ -271,7 +386,7 @@ impl Read for LinuxDevice {
             let block_size = self.info.block_size as usize;
             if let Some(ref mut aligned_buf) = self.aligned_buffer {
                 let aligned_len = align_up(buf.len(), block_size);
-                let aligned_slice = aligned_buf.as_aligned_slice_mut(aligned_len);
+                let aligned_slice = &mut aligned_buf[..aligned_len];
                 let bytes_read = self.file.read(&mut aligned_slice[..aligned_len])?;
                 let copy_len = bytes_read.min(buf.len());
                 buf[..copy_len].copy_from_slice(&aligned_slice[..copy_len]);
@@ -287,6 +402,12 @@ impl Read for LinuxDevice {
 
 impl Write for LinuxDevice {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.sparse {
+            let offset = self.file.stream_position()?;
+            if self.write_hole(offset, buf)? {
+                return Ok(buf.len());
+            }
+        }
         if self.info.direct_io
             && (!is_aligned(buf.len(), self.info.block_size as usize)
                 || !is_aligned(buf.as_ptr() as usize, self.info.block_size as usize))
@@ -294,7 +415,7 @@ impl Write for LinuxDevice {
             let block_size = self.info.block_size as usize;
             if let Some(ref mut aligned_buf) = self.aligned_buffer {
                 let aligned_len = align_up(buf.len(), block_size);
-                let aligned_slice = aligned_buf.as_aligned_slice_mut(aligned_len);
+                let aligned_slice = &mut aligned_buf[..aligned_len];
                 aligned_slice[..buf.len()].copy_from_slice(buf);
                 for byte in &mut aligned_slice[buf.len()..aligned_len] {
                     *byte = 0;
@@ -321,6 +442,33 @@ impl Seek for LinuxDevice {
     }
 }
 
+/// Deallocate `len` bytes at `offset`, keeping the file size
+///
+/// Returns `false` if the file system cannot punch holes.
+fn punch_hole(file: &File, offset: u64, len: u64) -> bool {
+    let (Ok(offset), Ok(len)) = (libc::off_t::try_from(offset), libc::off_t::try_from(len)) else {
+        return false;
+    };
+    // SAFETY: fallocate only reads its arguments, and the fd is valid for the
+    // lifetime of `file`
+    #[allow(unsafe_code)]
+    let ret = unsafe {
+        libc::fallocate(
+            file.as_raw_fd(),
+            libc::FALLOC_FL_PUNCH_HOLE | libc::FALLOC_FL_KEEP_SIZE,
+            offset,
+            len,
+        )
+    };
+    if ret != 0 {
+        tracing::debug!(
+            "Cannot punch a hole: {}; writing zeros",
+            std::io::Error::last_os_error()
+        );
+    }
+    ret == 0
+}
+
 /// Get device size using ioctl
 fn get_device_size(file: &File, path: &str) -> Result<u64> {
     let fd = file.as_raw_fd();
@@ -371,8 +519,99 @@ fn get_device_block_size(path: &str) -> Result<u32> {
         .open(path)
         .map_err(PlatformError::Io)?;
 
-    let fd = file.as_raw_fd();
+    if let Some(block_size) = logical_block_size(&file) {
+        return Ok(block_size);
+    }
+    tracing::debug!("BLKSSZGET ioctl failed for {path}, defaulting to 512");
 
+    // Default to 512
+    Ok(512)
+}
+
+/// Geometry beyond the logical block size, for [`DeviceInfo`]
+#[derive(Debug, Default, PartialEq, Eq)]
+struct DeviceGeometry {
+    physical_block_size: u32,
+    optimal_io_size: Option<u32>,
+    is_rotational: bool,
+}
+
+impl DeviceGeometry {
+    /// Read the geometry of an open device from sysfs, falling back to ioctls
+    ///
+    /// Regular files (image files, tests) have no geometry and get the default.
+    fn of(file: &File, path: &str) -> Self {
+        use std::os::unix::fs::FileTypeExt;
+
+        if !file
+            .metadata()
+            .is_ok_and(|m| m.file_type().is_block_device())
+        {
+            return Self::default();
+        }
+
+        let queue = sysfs_queue_dir(path);
+        let from_sysfs = |attr: &str| queue.as_deref().and_then(|q| read_queue_attr(q, attr));
+
+        Self {
+            physical_block_size: from_sysfs("physical_block_size")
+                .or_else(|| block_ioctl_u32(file, BLKPBSZGET))
+                .unwrap_or(0),
+            optimal_io_size: from_sysfs("optimal_io_size")
+                .or_else(|| block_ioctl_u32(file, BLKIOOPT))
+                .filter(|&size| size > 0),
+            is_rotational: from_sysfs("rotational") == Some(1),
+        }
+    }
+}
+
+/// Physical block size ioctl
+const BLKPBSZGET: u32 = 0x127B;
+/// Optimal I/O size ioctl
+const BLKIOOPT: u32 = 0x1279;
+
+/// Run a block-device ioctl that writes an unsigned int, `None` if it fails
+fn block_ioctl_u32(file: &File, request: u32) -> Option<u32> {
+    #[cfg(target_os = "linux")]
+    {
+        let mut value: libc::c_uint = 0;
+        // SAFETY: BLKPBSZGET and BLKIOOPT write an unsigned int to the provided
+        // pointer. We pass a valid mutable reference to a c_uint, and fd is valid.
+        #[allow(unsafe_code)]
+        let result = unsafe { libc::ioctl(file.as_raw_fd(), request as libc::Ioctl, &mut value) };
+        if result == 0 {
+            return Some(value);
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    let _ = (file, request);
+
+    None
+}
+
+/// sysfs `queue` directory for a device path
+///
+/// Partitions have no queue of their own and share their disk's, which sits
+/// one level up from the partition's `/sys/class/block` entry.
+fn sysfs_queue_dir(device_path: &str) -> Option<std::path::PathBuf> {
+    let sys_dev = Path::new("/sys/class/block").join(Path::new(device_path).file_name()?);
+    [sys_dev.join("queue"), sys_dev.join("../queue")]
+        .into_iter()
+        .find(|queue| queue.is_dir())
+}
+
+/// Read a numeric attribute such as `physical_block_size` from a queue directory
+fn read_queue_attr(queue: &Path, attr: &str) -> Option<u32> {
+    std::fs::read_to_string(queue.join(attr))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Logical block size of an open block device, or `None` for other files
+fn logical_block_size(file: &File) -> Option<u32> {
     #[cfg(target_os = "linux")]
     {
         // Use libc::Ioctl type for cross-platform compatibility
@@ -382,75 +621,329 @@ fn get_device_block_size(path: &str) -> Result<u32> {
         // SAFETY: ioctl with BLKSSZGET writes an i32 to the provided pointer.
         // We pass a valid mutable reference to an i32, and fd is valid.
         #[allow(unsafe_code)]
-        let result = unsafe { libc::ioctl(fd, BLKSSZGET, &mut block_size) };
+        let result = unsafe { libc::ioctl(file.as_raw_fd(), BLKSSZGET, &mut block_size) };
 
         if result == 0 && block_size > 0 {
-            return Ok(block_size as u32);
+            return Some(block_size as u32);
         }
-        tracing::debug!("BLKSSZGET ioctl failed for {path}, defaulting to 512");
     }
 
-    // Default to 512
-    Ok(512)
+    #[cfg(not(target_os = "linux"))]
+    let _ = file;
+
+    None
+}
+
+/// Re-read the partition table with the BLKRRPART ioctl
+///
+/// Fails with EBUSY while any partition on the device is still mounted.
+fn reread_linux_partition_table(path: &str) -> Result<()> {
+    let file = StdOpenOptions::new()
+        .read(true)
+        .open(path)
+        .map_err(PlatformError::Io)?;
+
+    // BLKRRPART = _IO(0x12, 95)
+    const BLKRRPART: libc::Ioctl = 0x125Fu32 as libc::Ioctl;
+
+    // SAFETY: BLKRRPART takes no argument; fd is valid for the lifetime of `file`.
+    #[allow(unsafe_code)]
+    let result = unsafe { libc::ioctl(file.as_raw_fd(), BLKRRPART) };
+
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(PlatformError::RereadFailed(format!(
+            "BLKRRPART on {} failed: {}",
+            path,
+            std::io::Error::last_os_error()
+        )))
+    }
+}
+
+/// Power down a device so it can be unplugged
+///
+/// Prefers `udisksctl power-off`, which spins the drive down and detaches
+/// its USB port. Without udisks, asks the kernel to remove the device via
+/// `/sys/block/<dev>/device/delete`.
+fn power_off_linux_device(device_path: &str) -> Result<()> {
+    tracing::debug!("Powering off {}", device_path);
+
+    let udisks_error = match Command::new("udisksctl")
+        .args(["power-off", "--no-user-interaction", "-b", device_path])
+        .output()
+    {
+        Ok(out) if out.status.success() => return Ok(()),
+        Ok(out) => String::from_utf8_lossy(&out.stderr).trim().to_string(),
+        Err(e) => format!("Failed to run udisksctl: {}", e),
+    };
+    tracing::debug!("udisksctl power-off failed: {}", udisks_error);
+
+    let delete_path = sysfs_delete_path(device_path)
+        .ok_or_else(|| PlatformError::EjectFailed(format!("{} (no sysfs entry)", udisks_error)))?;
+
+    std::fs::write(&delete_path, "1").map_err(|e| {
+        PlatformError::EjectFailed(format!(
+            "{}; writing {} failed: {}",
+            udisks_error,
+            delete_path.display(),
+            e
+        ))
+    })
+}
+
+/// Light a drive's locate LED with `ledctl` for `duration`
+///
+/// Only drives in an enclosure with LED control (SES, VMD) have one, so
+/// this returns `false` without waiting if `ledctl` is missing or refuses.
+fn locate_with_ledctl(device_path: &str, duration: Duration) -> bool {
+    match Command::new("ledctl")
+        .arg(format!("locate={}", device_path))
+        .output()
+    {
+        Ok(out) if out.status.success() => {}
+        Ok(out) => {
+            tracing::debug!(
+                "ledctl locate failed: {}",
+                String::from_utf8_lossy(&out.stderr).trim()
+            );
+            return false;
+        }
+        Err(e) => {
+            tracing::debug!("Failed to run ledctl: {}", e);
+            return false;
+        }
+    }
+
+    std::thread::sleep(duration);
+    match Command::new("ledctl")
+        .arg(format!("locate_off={}", device_path))
+        .status()
+    {
+        Ok(status) if status.success() => {}
+        _ => tracing::warn!("Could not turn off the locate LED of {}", device_path),
+    }
+    true
+}
+
+/// Get the sysfs `delete` attribute for a whole-disk device path
+fn sysfs_delete_path(device_path: &str) -> Option<std::path::PathBuf> {
+    let name = Path::new(device_path).file_name()?.to_str()?;
+    if name.is_empty() {
+        return None;
+    }
+    Some(Path::new("/sys/block").join(name).join("device/delete"))
 }
 
 /// Unmount all filesystems on a device
-fn unmount_linux_device(device_path: &str) -> Result<()> {
+fn unmount_linux_device(device_path: &str, progress: &mut dyn FnMut(&str)) -> Result<()> {
     // Find all mounted partitions for this device
     let mounts = std::fs::read_to_string("/proc/mounts")
         .map_err(|e| PlatformError::UnmountFailed(format!("Cannot read /proc/mounts: {}", e)))?;
 
+    let mount_points = device_mount_points(&mounts, device_path);
+
+    for mount_point in &mount_points {
+        progress(&format!("unmounting {}", mount_point));
+        unmount_mount_point(mount_point, progress)?;
+    }
+
+    if !mount_points.is_empty() {
+        // Give the kernel time to process
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+
+    progress("done");
+    Ok(())
+}
+
+/// Run `umount` on one mount point, retrying while it is busy
+fn unmount_mount_point(mount_point: &str, progress: &mut dyn FnMut(&str)) -> Result<()> {
+    let mut attempt = 1;
+    loop {
+        let status = Command::new("umount")
+            .arg(mount_point)
+            .status()
+            .map_err(|e| PlatformError::UnmountFailed(format!("Failed to run umount: {}", e)))?;
+
+        if status.success() {
+            return Ok(());
+        }
+        if attempt >= UNMOUNT_ATTEMPTS {
+            return Err(PlatformError::UnmountFailed(format!(
+                "Failed to unmount {}: exit code {:?}",
+                mount_point,
+                status.code()
+            )));
+        }
+
+        attempt += 1;
+        progress(&format!("retrying {}", mount_point));
+        std::thread::sleep(UNMOUNT_RETRY_DELAY);
+    }
+}
+
+/// Flush `file` to the device with fsync(2)
+fn fsync_file(file: &File) -> Result<()> {
+    let fd = file.as_raw_fd();
+    // SAFETY: fsync() is called with a valid file descriptor obtained from as_raw_fd().
+    // The fd remains valid for the lifetime of `file`.
+    #[allow(unsafe_code)]
+    let result = unsafe { libc::fsync(fd) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(PlatformError::Io(std::io::Error::last_os_error()))
+    }
+}
+
+/// Bytes of dirty and under-writeback pages, from `/proc/meminfo`
+fn read_dirty_bytes() -> Option<u64> {
+    std::fs::read_to_string("/proc/meminfo")
+        .ok()
+        .and_then(|meminfo| parse_dirty_bytes(&meminfo))
+}
+
+/// Sum of the `Dirty` and `Writeback` lines of `/proc/meminfo` content, in bytes
+fn parse_dirty_bytes(meminfo: &str) -> Option<u64> {
+    let mut total = None;
+    for line in meminfo.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        if key != "Dirty" && key != "Writeback" {
+            continue;
+        }
+        let kib: u64 = value.trim().trim_end_matches("kB").trim().parse().ok()?;
+        total = Some(total.unwrap_or(0) + kib * 1024);
+    }
+    total
+}
+
+/// Mount points in `/proc/mounts` content that belong to `device_path`, in mount order
+fn device_mount_points(mounts: &str, device_path: &str) -> Vec<String> {
     let device_base = Path::new(device_path)
         .file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("");
 
-    let mut unmounted_any = false;
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let mount_device = parts.next()?;
+            let mount_point = parts.next()?;
+
+            // Check if this mount is on our device
+            let on_device = mount_device.starts_with(device_path)
+                || (mount_device.contains(device_base) && device_base.len() > 2);
+            on_device.then(|| {
+                tracing::debug!("Unmounting {} from {}", mount_device, mount_point);
+                mount_point.to_string()
+            })
+        })
+        .collect()
+}
 
-    for line in mounts.lines() {
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() < 2 {
-            continue;
-        }
+/// Create a data partition with `parted` and format it with `mkfs`
+fn create_linux_data_partition(path: &str, fs: DataFilesystem, start_offset: u64) -> Result<()> {
+    let device_size =
+        LinuxDevice::open(path, OpenOptions::new().write(false).direct_io(false))?.size();
+    let (start, _) = data_partition_span(device_size, start_offset)?;
+
+    // parted only uses the type to pick the MBR partition ID; exFAT shares
+    // 0x07 with NTFS
+    let part_type = match fs {
+        DataFilesystem::Fat32 => "fat32",
+        DataFilesystem::ExFat => "ntfs",
+    };
 
-        let mount_device = parts[0];
-        let mount_point = parts[1];
+    // --fix moves a hybrid image's GPT backup header to the real end of the disk
+    run_partition_tool(
+        Command::new("parted")
+            .args([
+                "--script", "--fix", path, "--", "unit", "B", "mkpart", "primary",
+            ])
+            .arg(part_type)
+            .arg(format!("{}B", start))
+            .arg("100%"),
+        "parted",
+    )?;
+
+    let partition = wait_for_partition_at(path, start)?;
+    tracing::debug!("Formatting {} as {}", partition, fs);
+
+    let (tool, label_flag) = match fs {
+        DataFilesystem::Fat32 => ("mkfs.vfat", "-n"),
+        DataFilesystem::ExFat => ("mkfs.exfat", "-L"),
+    };
+    let mut mkfs = Command::new(tool);
+    if fs == DataFilesystem::Fat32 {
+        mkfs.args(["-F", "32"]);
+    }
+    mkfs.args([label_flag, DATA_PARTITION_LABEL]);
+    run_partition_tool(mkfs.arg(&partition), tool)
+}
 
-        // Check if this mount is on our device
-        if mount_device.starts_with(device_path)
-            || (mount_device.contains(device_base) && device_base.len() > 2)
-        {
-            tracing::debug!("Unmounting {} from {}", mount_device, mount_point);
+/// Run a partitioning tool, turning a missing binary or failure into [`PlatformError::PartitionFailed`]
+fn run_partition_tool(command: &mut Command, tool: &str) -> Result<()> {
+    let output = command.output().map_err(|e| {
+        PlatformError::PartitionFailed(if e.kind() == std::io::ErrorKind::NotFound {
+            format!("{} not found; install it to create a data partition", tool)
+        } else {
+            format!("Failed to run {}: {}", tool, e)
+        })
+    })?;
 
-            let status = Command::new("umount").arg(mount_point).status();
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(PlatformError::PartitionFailed(format!(
+            "{} failed: {}",
+            tool,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
 
-            match status {
-                Ok(s) if s.success() => {
-                    unmounted_any = true;
-                }
-                Ok(s) => {
-                    return Err(PlatformError::UnmountFailed(format!(
-                        "Failed to unmount {}: exit code {:?}",
-                        mount_point,
-                        s.code()
-                    )));
-                }
-                Err(e) => {
-                    return Err(PlatformError::UnmountFailed(format!(
-                        "Failed to run umount: {}",
-                        e
-                    )));
-                }
+/// Wait for the kernel and udev to expose the partition starting at `start` bytes
+fn wait_for_partition_at(path: &str, start: u64) -> Result<String> {
+    let name = Path::new(path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| PlatformError::DeviceNotFound(path.to_string()))?;
+    let sys_block = Path::new("/sys/block").join(name);
+
+    for _ in 0..50 {
+        let _ = Command::new("udevadm").arg("settle").status();
+        if let Some(part) = find_partition_at(&sys_block, start) {
+            let dev = Path::new("/dev").join(&part);
+            if dev.exists() {
+                return Ok(dev.to_string_lossy().into_owned());
             }
         }
-    }
-
-    if unmounted_any {
-        // Give the kernel time to process
         std::thread::sleep(std::time::Duration::from_millis(100));
     }
 
-    Ok(())
+    Err(PlatformError::PartitionFailed(format!(
+        "New partition on {} did not appear; replug the drive and format it manually",
+        path
+    )))
+}
+
+/// Name of the partition under `sys_block` whose sysfs `start` (in 512-byte sectors) is `start` bytes
+fn find_partition_at(sys_block: &Path, start: u64) -> Option<String> {
+    std::fs::read_dir(sys_block)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .find_map(|entry| {
+            let sectors: u64 = std::fs::read_to_string(entry.path().join("start"))
+                .ok()?
+                .trim()
+                .parse()
+                .ok()?;
+            (sectors * 512 == start).then(|| entry.file_name().to_string_lossy().into_owned())
+        })
 }
 
 // ============================================================================
@@ -470,13 +963,13 @@ mod tests {
     #[test]
     fn test_aligned_buffer_creation() {
         let buffer = AlignedBuffer::new(4096, 512);
-        assert!(buffer.data.len() >= 4096);
+        assert!(buffer.len() >= 4096);
     }
 
     #[test]
     fn test_aligned_buffer_slice() {
         let buffer = AlignedBuffer::new(4096, 512);
-        let slice = buffer.as_aligned_slice(1024);
+        let slice = &buffer[..1024];
         assert_eq!(slice.len(), 1024);
         // Check alignment
         assert!(is_aligned(slice.as_ptr() as usize, 512));
@@ -485,7 +978,7 @@ mod tests {
     #[test]
     fn test_aligned_buffer_mut_slice() {
         let mut buffer = AlignedBuffer::new(4096, 512);
-        let slice = buffer.as_aligned_slice_mut(1024);
+        let slice = &mut buffer[..1024];
         assert_eq!(slice.len(), 1024);
         assert!(is_aligned(slice.as_ptr() as usize, 512));
 
@@ -516,6 +1009,21 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_open_device_direct_io_falls_back() {
+        // tmpfs and some other filesystems reject O_DIRECT with EINVAL;
+        // open_device must still succeed, reporting the mode it ended up in
+        let mut temp = NamedTempFile::new().unwrap();
+        temp.write_all(&[0u8; 8192]).unwrap();
+
+        let options = OpenOptions::new().direct_io(true);
+        let mut device = crate::open_device(temp.path().to_str().unwrap(), options).unwrap();
+        assert_eq!(device.size(), 8192);
+
+        let mut buf = vec![0u8; 4096];
+        assert_eq!(device.read_at(0, &mut buf).unwrap(), 4096);
+    }
+
     #[test]
     fn test_device_info() {
         let mut temp = NamedTempFile::new().unwrap();
@@ -549,6 +1057,54 @@ mod tests {
         assert_eq!(&buffer, data);
     }
 
+    #[test]
+    fn test_sparse_write_leaves_holes() {
+        use std::os::unix::fs::MetadataExt;
+
+        let temp = NamedTempFile::new().unwrap();
+        let options = OpenOptions::new().direct_io(false).sparse(true);
+        let mut device = LinuxDevice::open(temp.path().to_str().unwrap(), options).unwrap();
+
+        let block = 4096;
+        let zeros = vec![0u8; block];
+        device.write_all(&[0xAB; 4096]).unwrap();
+        for _ in 0..256 {
+            device.write_all(&zeros).unwrap();
+        }
+        device.write_all(&[0xCD; 4096]).unwrap();
+        // Trailing zeros still count toward the apparent size
+        for _ in 0..256 {
+            device.write_all(&zeros).unwrap();
+        }
+        device.sync().unwrap();
+
+        let size = (2 + 512) * block as u64;
+        let metadata = std::fs::metadata(temp.path()).unwrap();
+        assert_eq!(metadata.len(), size);
+        assert!(metadata.blocks() * 512 < size / 2);
+
+        let mut buffer = vec![0u8; block];
+        device.read_at(257 * block as u64, &mut buffer).unwrap();
+        assert!(buffer.iter().all(|&b| b == 0xCD));
+        device.read_at(block as u64, &mut buffer).unwrap();
+        assert!(buffer.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_sparse_write_clears_existing_data() {
+        let mut temp = NamedTempFile::new().unwrap();
+        temp.write_all(&[0xFF; 8192]).unwrap();
+
+        let options = OpenOptions::new().direct_io(false).sparse(true);
+        let mut device = LinuxDevice::open(temp.path().to_str().unwrap(), options).unwrap();
+        assert_eq!(device.write_at(0, &[0u8; 4096]).unwrap(), 4096);
+
+        let data = std::fs::read(temp.path()).unwrap();
+        assert_eq!(data.len(), 8192);
+        assert!(data[..4096].iter().all(|&b| b == 0));
+        assert!(data[4096..].iter().all(|&b| b == 0xFF));
+    }
+
     #[test]
     fn test_sync_regular_file() {
         let mut temp = NamedTempFile::new().unwrap();
@@ -561,6 +1117,45 @@ mod tests {
         assert!(device.sync().is_ok());
     }
 
+    #[test]
+    fn test_sync_with_progress_regular_file() {
+        let mut temp = NamedTempFile::new().unwrap();
+        temp.write_all(&[0u8; 4096]).unwrap();
+
+        let options = OpenOptions::new().direct_io(false);
+        let device = LinuxDevice::open(temp.path().to_str().unwrap(), options).unwrap();
+
+        let mut reports = 0;
+        device.sync_with_progress(&mut |_| reports += 1).unwrap();
+        // /proc/meminfo is always readable on Linux, so the first poll reports
+        assert!(reports >= 1);
+    }
+
+    #[test]
+    fn test_drop_cache_keeps_data() {
+        let temp = NamedTempFile::new().unwrap();
+        let options = OpenOptions::new().write(true).direct_io(false);
+        let mut device = LinuxDevice::open(temp.path().to_str().unwrap(), options).unwrap();
+        device.write_at(0, &[0xA5u8; 4096]).unwrap();
+
+        device.drop_cache().unwrap();
+
+        let mut buffer = [0u8; 4096];
+        device.read_at(0, &mut buffer).unwrap();
+        assert!(buffer.iter().all(|&b| b == 0xA5));
+    }
+
+    #[test]
+    fn test_parse_dirty_bytes() {
+        let meminfo = "MemTotal:       16318480 kB\n\
+                       Dirty:              1024 kB\n\
+                       Writeback:            16 kB\n\
+                       WritebackTmp:          0 kB\n";
+        assert_eq!(parse_dirty_bytes(meminfo), Some(1040 * 1024));
+        assert_eq!(parse_dirty_bytes("MemTotal: 100 kB\n"), None);
+        assert_eq!(parse_dirty_bytes("Dirty: lots\n"), None);
+    }
+
     // -------------------------------------------------------------------------
     // Platform privilege tests
     // -------------------------------------------------------------------------
@@ -592,6 +1187,50 @@ tmpfs /tmp tmpfs rw,nosuid 0 0
         }
     }
 
+    #[test]
+    fn test_device_mount_points() {
+        let mounts = "\
+/dev/nvme0n1p2 / ext4 rw,relatime 0 0
+/dev/sdb1 /media/user/BOOT vfat rw,nosuid 0 0
+proc /proc proc rw,nosuid 0 0
+/dev/sdb2 /media/user/rootfs ext4 rw,nosuid 0 0
+/dev/sdc1 /media/user/OTHER vfat rw 0 0
+";
+        assert_eq!(
+            device_mount_points(mounts, "/dev/sdb"),
+            vec!["/media/user/BOOT", "/media/user/rootfs"]
+        );
+        assert!(device_mount_points(mounts, "/dev/sdd").is_empty());
+    }
+
+    #[test]
+    fn test_find_partition_at() {
+        let dir = tempfile::tempdir().unwrap();
+        for (part, start) in [("sdb1", "2048"), ("sdb2", "616448")] {
+            std::fs::create_dir(dir.path().join(part)).unwrap();
+            std::fs::write(dir.path().join(part).join("start"), format!("{}\n", start)).unwrap();
+        }
+        // Device attributes without a start file are ignored
+        std::fs::create_dir(dir.path().join("queue")).unwrap();
+
+        assert_eq!(
+            find_partition_at(dir.path(), 616448 * 512).as_deref(),
+            Some("sdb2")
+        );
+        assert_eq!(find_partition_at(dir.path(), 4096), None);
+    }
+
+    #[test]
+    fn test_unmount_device_with_progress_reports_done() {
+        // A device with nothing mounted only reports completion
+        let mut messages = Vec::new();
+        LinuxPlatform::unmount_device_with_progress("/dev/engraver-test-none", &mut |m| {
+            messages.push(m.to_string())
+        })
+        .unwrap();
+        assert_eq!(messages, vec!["done"]);
+    }
+
     // -------------------------------------------------------------------------
     // LinuxDevice Read/Write/Seek trait tests
     // -------------------------------------------------------------------------
@@ -712,6 +1351,41 @@ tmpfs /tmp tmpfs rw,nosuid 0 0
         assert_eq!(&buffer, b"END");
     }
 
+    #[test]
+    fn test_linux_device_write_batch() {
+        let mut temp = NamedTempFile::new().unwrap();
+        temp.write_all(&[0u8; 64 * 1024]).unwrap();
+
+        let options = OpenOptions::new().direct_io(false);
+        let mut device = LinuxDevice::open(temp.path().to_str().unwrap(), options).unwrap();
+
+        // More chunks than the io_uring queue depth, written out of order
+        let chunks: Vec<(u64, Vec<u8>)> = (0..20u8)
+            .rev()
+            .map(|i| (u64::from(i) * 1024, vec![i + 1; 1000]))
+            .collect();
+        let refs: Vec<(u64, &[u8])> = chunks.iter().map(|(o, d)| (*o, d.as_slice())).collect();
+
+        let written = device.write_batch(&refs).unwrap();
+        assert_eq!(written, 20 * 1000);
+
+        for (offset, data) in &chunks {
+            let mut buffer = vec![0u8; data.len()];
+            device.read_at(*offset, &mut buffer).unwrap();
+            assert_eq!(&buffer, data);
+        }
+    }
+
+    #[test]
+    fn test_linux_device_write_batch_empty() {
+        let temp = NamedTempFile::new().unwrap();
+
+        let options = OpenOptions::new().direct_io(false);
+        let mut device = LinuxDevice::open(temp.path().to_str().unwrap(), options).unwrap();
+
+        assert_eq!(device.write_batch(&[]).unwrap(), 0);
+    }
+
     #[test]
     fn test_linux_device_read_at_empty_buffer() {
         let mut temp = NamedTempFile::new().unwrap();
@@ -733,7 +1407,7 @@ tmpfs /tmp tmpfs rw,nosuid 0 0
     fn test_aligned_buffer_various_alignments() {
         for alignment in [512, 1024, 4096] {
             let buffer = AlignedBuffer::new(alignment * 2, alignment);
-            let slice = buffer.as_aligned_slice(alignment);
+            let slice = &buffer[..alignment];
             assert!(is_aligned(slice.as_ptr() as usize, alignment));
         }
     }
@@ -743,11 +1417,11 @@ tmpfs /tmp tmpfs rw,nosuid 0 0
         let mut buffer = AlignedBuffer::new(4096, 512);
 
         // Write to aligned slice
-        let slice = buffer.as_aligned_slice_mut(100);
+        let slice = &mut buffer[..100];
         slice[0..5].copy_from_slice(b"hello");
 
         // Read back
-        let slice = buffer.as_aligned_slice(100);
+        let slice = &buffer[..100];
         assert_eq!(&slice[0..5], b"hello");
     }
 
@@ -755,6 +1429,50 @@ tmpfs /tmp tmpfs rw,nosuid 0 0
     // DeviceInfo tests
     // -------------------------------------------------------------------------
 
+    #[test]
+    fn test_reread_partition_table_regular_file() {
+        // A regular file has no partition table; the ioctl must fail cleanly
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let result = reread_linux_partition_table(file.path().to_str().unwrap());
+        assert!(matches!(result, Err(PlatformError::RereadFailed(_))));
+    }
+
+    #[test]
+    fn test_reread_partition_table_missing_device() {
+        let result = reread_linux_partition_table("/dev/nonexistent_engraver_test");
+        assert!(matches!(result, Err(PlatformError::Io(_))));
+    }
+
+    #[test]
+    fn test_identify_reads_regular_file() {
+        let mut temp = NamedTempFile::new().unwrap();
+        temp.write_all(&vec![0u8; 64 * 1024]).unwrap();
+        temp.flush().unwrap();
+
+        let start = std::time::Instant::now();
+        LinuxPlatform::identify(temp.path().to_str().unwrap(), Duration::from_millis(50)).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_identify_missing_device() {
+        let result = LinuxPlatform::identify("/dev/nonexistent_engraver_test", Duration::ZERO);
+        assert!(matches!(result, Err(PlatformError::DeviceNotFound(_))));
+    }
+
+    #[test]
+    fn test_sysfs_delete_path() {
+        assert_eq!(
+            sysfs_delete_path("/dev/sdb"),
+            Some(std::path::PathBuf::from("/sys/block/sdb/device/delete"))
+        );
+        assert_eq!(
+            sysfs_delete_path("/dev/mmcblk0"),
+            Some(std::path::PathBuf::from("/sys/block/mmcblk0/device/delete"))
+        );
+        assert_eq!(sysfs_delete_path("/"), None);
+    }
+
     #[test]
     fn test_linux_device_info_path() {
         let mut temp = NamedTempFile::new().unwrap();
@@ -778,6 +1496,46 @@ tmpfs /tmp tmpfs rw,nosuid 0 0
         assert_eq!(device.info().block_size, 8192);
     }
 
+    #[test]
+    fn test_logical_block_size_regular_file() {
+        // Only block devices report a logical block size; open falls back to the requested one
+        let temp = NamedTempFile::new().unwrap();
+        assert_eq!(logical_block_size(temp.as_file()), None);
+    }
+
+    #[test]
+    fn test_device_geometry_regular_file() {
+        // Regular files have no queue, so every field stays unavailable
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path().to_str().unwrap();
+        assert_eq!(
+            DeviceGeometry::of(temp.as_file(), path),
+            DeviceGeometry::default()
+        );
+        assert_eq!(block_ioctl_u32(temp.as_file(), BLKPBSZGET), None);
+
+        let device = LinuxDevice::open(path, OpenOptions::new().direct_io(false)).unwrap();
+        assert_eq!(device.info().physical_block_size, 0);
+        assert_eq!(device.info().optimal_io_size, None);
+        assert!(!device.info().is_rotational);
+    }
+
+    #[test]
+    fn test_read_queue_attr() {
+        let queue = tempfile::tempdir().unwrap();
+        std::fs::write(queue.path().join("physical_block_size"), "4096\n").unwrap();
+        std::fs::write(queue.path().join("rotational"), "1\n").unwrap();
+        std::fs::write(queue.path().join("scheduler"), "[none] mq-deadline\n").unwrap();
+
+        assert_eq!(
+            read_queue_attr(queue.path(), "physical_block_size"),
+            Some(4096)
+        );
+        assert_eq!(read_queue_attr(queue.path(), "rotational"), Some(1));
+        assert_eq!(read_queue_attr(queue.path(), "scheduler"), None);
+        assert_eq!(read_queue_attr(queue.path(), "optimal_io_size"), None);
+    }
+
     #[test]
     fn test_linux_device_info_size() {
         let mut temp = NamedTempFile::new().unwrap();
@@ -818,6 +1576,18 @@ tmpfs /tmp tmpfs rw,nosuid 0 0
         assert_eq!(n, 14);
     }
 
+    #[test]
+    fn test_open_exclusive_regular_file() {
+        // O_EXCL only claims block devices; regular files open as before
+        let mut temp = NamedTempFile::new().unwrap();
+        temp.write_all(&[0u8; 4096]).unwrap();
+        let path = temp.path().to_str().unwrap();
+
+        let options = OpenOptions::new().direct_io(false).exclusive(true);
+        let _first = LinuxDevice::open(path, options.clone()).unwrap();
+        assert!(LinuxDevice::open(path, options).is_ok());
+    }
+
     #[test]
     fn test_open_options_custom_block_size() {
         let mut temp = NamedTempFile::new().unwrap();
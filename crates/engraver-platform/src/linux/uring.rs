@@ -0,0 +1,302 @@
+//! `io_uring` batched writes for Linux
+//!
+//! Keeps up to [`URING_QUEUE_DEPTH`] aligned buffers in flight per submission.
+//! Only compiled with the `io-uring` feature; when the ring can't be set up
+//! (old kernel, seccomp filter, etc.) the device falls back to `write_at`.
+
+use crate::{align_up, is_aligned, AlignedBuffer, PlatformError, Result};
+use io_uring::{opcode, types, IoUring};
+use std::os::unix::io::RawFd;
+
+/// Number of writes kept in flight per submission
+pub(super) const URING_QUEUE_DEPTH: usize = 8;
+
+/// Failed `io_uring_enter` calls in a row before the ring is abandoned
+const MAX_ENTER_FAILURES: u32 = 3;
+
+/// An `io_uring` instance with its own set of aligned write buffers
+pub(super) struct UringWriter {
+    // Declared before `buffers` so the ring is torn down before the memory
+    // it may still reference is freed.
+    ring: IoUring,
+    buffers: Vec<AlignedBuffer>,
+    /// Set when the ring could not be drained; it must not be used again
+    poisoned: bool,
+}
+
+impl UringWriter {
+    /// Set up a ring, returning `None` if the kernel doesn't support it
+    pub(super) fn new() -> Option<Self> {
+        match IoUring::new(URING_QUEUE_DEPTH as u32) {
+            Ok(ring) => Some(Self {
+                ring,
+                buffers: Vec::new(),
+                poisoned: false,
+            }),
+            Err(e) => {
+                tracing::debug!("io_uring setup failed, using synchronous writes: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Whether a failed drain left writes in flight, so the ring is unusable
+    pub(super) fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+
+    /// Write each `(offset, data)` chunk to `fd`, returning bytes written per chunk
+    ///
+    /// Data is copied into owned buffers aligned to `alignment` and, for direct
+    /// I/O, zero padded to a multiple of it (mirroring `write_at`). Short
+    /// writes are resubmitted for the remainder. Nothing is left in flight
+    /// when this returns, on success or error.
+    pub(super) fn write_batch(
+        &mut self,
+        fd: RawFd,
+        chunks: &[(u64, &[u8])],
+        alignment: usize,
+        direct_io: bool,
+    ) -> Result<Vec<usize>> {
+        if self.poisoned {
+            return Err(PlatformError::Io(std::io::Error::other(
+                "io_uring ring is unusable after a failed drain",
+            )));
+        }
+
+        // Check every chunk before queueing any, so this error leaves nothing in flight
+        if direct_io {
+            if let Some((offset, _)) = chunks
+                .iter()
+                .find(|(offset, _)| !is_aligned(*offset as usize, alignment))
+            {
+                return Err(PlatformError::AlignmentError(format!(
+                    "Offset {} is not aligned to block size {}",
+                    offset, alignment
+                )));
+            }
+        }
+
+        let mut written = vec![0usize; chunks.len()];
+
+        for (group_index, group) in chunks.chunks(URING_QUEUE_DEPTH).enumerate() {
+            // Fill every buffer before the first submission, so none is
+            // reallocated while the kernel may still be reading another
+            let mut lens = Vec::with_capacity(group.len());
+            for (i, (_, data)) in group.iter().enumerate() {
+                let len = if direct_io {
+                    align_up(data.len(), alignment)
+                } else {
+                    data.len()
+                };
+                let buffer = self.buffer(i, len, alignment);
+                buffer[..data.len()].copy_from_slice(data);
+                buffer[data.len()..len].fill(0);
+                lens.push(len);
+            }
+
+            let done = self.write_group(fd, group, &lens)?;
+            let base = group_index * URING_QUEUE_DEPTH;
+            for (i, (_, data)) in group.iter().enumerate() {
+                written[base + i] = done[i].min(data.len());
+            }
+        }
+
+        Ok(written)
+    }
+
+    /// Write the first `lens.len()` buffers at the group's offsets
+    ///
+    /// Returns the bytes of each buffer written. Keeps going until every
+    /// submitted entry has completed, even after an error, so no completion
+    /// is left behind for the next batch.
+    fn write_group(
+        &mut self,
+        fd: RawFd,
+        group: &[(u64, &[u8])],
+        lens: &[usize],
+    ) -> Result<Vec<usize>> {
+        let mut done = vec![0usize; lens.len()];
+        let mut pending: Vec<usize> = (0..lens.len()).collect();
+        let mut outstanding = 0usize;
+        let mut first_error: Option<std::io::Error> = None;
+
+        loop {
+            if first_error.is_none() {
+                for i in std::mem::take(&mut pending) {
+                    let remainder = &self.buffers[i][done[i]..lens[i]];
+                    let entry = opcode::Write::new(
+                        types::Fd(fd),
+                        remainder.as_ptr(),
+                        remainder.len() as u32,
+                    )
+                    .offset(group[i].0 + done[i] as u64)
+                    .build()
+                    .user_data(i as u64);
+
+                    // SAFETY: the entry points into `self.buffers`, which are
+                    // not resized or freed until its completion is reaped
+                    // below, or are leaked if the ring can't be drained.
+                    #[allow(unsafe_code)]
+                    let pushed = unsafe { self.ring.submission().push(&entry) };
+                    if pushed.is_err() {
+                        first_error = Some(std::io::Error::other("io_uring submission queue full"));
+                        break;
+                    }
+                    outstanding += 1;
+                }
+            }
+
+            if outstanding == 0 {
+                break;
+            }
+            self.submit_and_wait_one()?;
+
+            for cqe in self.ring.completion() {
+                outstanding -= 1;
+                let i = cqe.user_data() as usize;
+                let result = cqe.result();
+                if result < 0 {
+                    let e = std::io::Error::from_raw_os_error(-result);
+                    if e.kind() == std::io::ErrorKind::Interrupted {
+                        pending.push(i);
+                    } else {
+                        first_error.get_or_insert(e);
+                    }
+                } else if result == 0 {
+                    first_error.get_or_insert_with(|| std::io::ErrorKind::WriteZero.into());
+                } else {
+                    done[i] += (result as usize).min(lens[i] - done[i]);
+                    if done[i] < lens[i] {
+                        pending.push(i);
+                    }
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(PlatformError::Io(e)),
+            None => Ok(done),
+        }
+    }
+
+    /// Submit queued entries and wait for at least one completion
+    ///
+    /// Interrupted waits are retried. If the ring keeps failing, entries may
+    /// still be queued or in flight, so the buffers are leaked rather than
+    /// freed under the kernel and the ring is poisoned.
+    fn submit_and_wait_one(&mut self) -> Result<()> {
+        let mut failures = 0;
+        loop {
+            match self.ring.submit_and_wait(1) {
+                Ok(_) => return Ok(()),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    failures += 1;
+                    if failures >= MAX_ENTER_FAILURES {
+                        std::mem::forget(std::mem::take(&mut self.buffers));
+                        self.poisoned = true;
+                        return Err(PlatformError::Io(e));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Get the `index`th buffer, growing it to hold at least `len` bytes
+    fn buffer(&mut self, index: usize, len: usize, alignment: usize) -> &mut AlignedBuffer {
+        while self.buffers.len() <= index {
+            self.buffers.push(AlignedBuffer::new(len, alignment));
+        }
+
+        let buffer = &mut self.buffers[index];
+        if buffer.alignment() != alignment.max(1) || buffer.len() < len {
+            *buffer = AlignedBuffer::new(len, alignment);
+        }
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::os::unix::io::AsRawFd;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_uring_write_batch() {
+        // Kernels or sandboxes without io_uring are covered by the fallback path
+        let Some(mut writer) = UringWriter::new() else {
+            return;
+        };
+
+        let mut temp = NamedTempFile::new().unwrap();
+        let chunks: [(u64, &[u8]); 2] = [(4, b"world"), (0, b"hi, ")];
+        let written = writer
+            .write_batch(temp.as_file().as_raw_fd(), &chunks, 512, false)
+            .unwrap();
+        assert_eq!(written, vec![5, 4]);
+
+        let mut content = String::new();
+        temp.read_to_string(&mut content).unwrap();
+        assert_eq!(content, "hi, world");
+    }
+
+    #[test]
+    fn test_uring_direct_io_rejects_unaligned_offset() {
+        let Some(mut writer) = UringWriter::new() else {
+            return;
+        };
+
+        let temp = NamedTempFile::new().unwrap();
+        let result = writer.write_batch(temp.as_file().as_raw_fd(), &[(100, b"x")], 512, true);
+        assert!(matches!(result, Err(PlatformError::AlignmentError(_))));
+    }
+
+    #[test]
+    fn test_uring_unaligned_chunk_in_later_group_writes_nothing() {
+        let Some(mut writer) = UringWriter::new() else {
+            return;
+        };
+
+        let temp = NamedTempFile::new().unwrap();
+        let data = [0xAAu8; 512];
+        let mut chunks: Vec<(u64, &[u8])> = (0..URING_QUEUE_DEPTH as u64)
+            .map(|i| (i * 512, &data[..]))
+            .collect();
+        chunks.push((100, &data[..]));
+
+        let result = writer.write_batch(temp.as_file().as_raw_fd(), &chunks, 512, true);
+        assert!(matches!(result, Err(PlatformError::AlignmentError(_))));
+        assert_eq!(temp.as_file().metadata().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_uring_error_leaves_no_stale_completions() {
+        let Some(mut writer) = UringWriter::new() else {
+            return;
+        };
+
+        // Writes to a read-only descriptor all fail with EBADF
+        let temp = NamedTempFile::new().unwrap();
+        let read_only = std::fs::File::open(temp.path()).unwrap();
+        let chunks: Vec<(u64, &[u8])> = (0..3).map(|i| (i * 4, &b"fail"[..])).collect();
+        assert!(writer
+            .write_batch(read_only.as_raw_fd(), &chunks, 512, false)
+            .is_err());
+        assert!(!writer.is_poisoned());
+
+        // The next batch only sees its own completions
+        let mut target = NamedTempFile::new().unwrap();
+        let chunks: [(u64, &[u8]); 2] = [(0, b"ok"), (2, b"!")];
+        let written = writer
+            .write_batch(target.as_file().as_raw_fd(), &chunks, 512, false)
+            .unwrap();
+        assert_eq!(written, vec![2, 1]);
+
+        let mut content = String::new();
+        target.read_to_string(&mut content).unwrap();
+        assert_eq!(content, "ok!");
+    }
+}
@@ -2,7 +2,11 @@
 //!
 //! Uses raw device nodes (/dev/rdiskN) and diskutil for unmounting.
 
-use crate::{DeviceInfo, OpenOptions, PlatformError, PlatformOps, RawDevice, Result};
+use crate::{
+    data_partition_span, DataFilesystem, DeviceInfo, OpenOptions, PlatformError, PlatformOps,
+    RawDevice, Result, DATA_PARTITION_LABEL, OPEN_RETRY_DELAY, UNMOUNT_ATTEMPTS,
+    UNMOUNT_RETRY_DELAY,
+};
 use std::fs::{File, OpenOptions as StdOpenOptions};
 use std::io::{Read, Seek, SeekFrom, Write};
 use std::os::unix::io::AsRawFd;
@@ -17,8 +21,21 @@ impl PlatformOps for MacOSPlatform {
         MacOSDevice::open(path, options).map(|d| Box::new(d) as Box<dyn RawDevice>)
     }
 
-    fn unmount_device(path: &str) -> Result<()> {
-        unmount_macos_device(path)
+    fn unmount_device_with_progress(path: &str, progress: &mut dyn FnMut(&str)) -> Result<()> {
+        unmount_macos_device(path, progress)
+    }
+
+    fn eject(path: &str) -> Result<()> {
+        eject_macos_device(path)
+    }
+
+    fn reread_partition_table(_path: &str) -> Result<()> {
+        // DiskArbitration picks up the new partition map on its own
+        Ok(())
+    }
+
+    fn create_data_partition(path: &str, fs: DataFilesystem, start_offset: u64) -> Result<()> {
+        create_macos_data_partition(path, fs, start_offset)
     }
 
     fn sync_all() -> Result<()> {
@@ -51,6 +68,8 @@ impl PlatformOps for MacOSPlatform {
 pub struct MacOSDevice {
     file: File,
     info: DeviceInfo,
+    /// Skip all-zero writes past the end of a regular file opened with `sparse`
+    sparse: bool,
 }
 
 impl MacOSDevice {
@@ -85,22 +104,18 @@ impl MacOSDevice {
         // We use F_NOCACHE via fcntl after opening
 
         // Try to open the device
-        let file = std_options.open(&actual_path).map_err(|e| {
-            if e.kind() == std::io::ErrorKind::PermissionDenied {
-                PlatformError::PermissionDenied(format!(
-                    "Cannot open {}: {}. Try running with sudo.",
-                    actual_path, e
-                ))
-            } else if e.raw_os_error() == Some(16) {
-                // EBUSY
-                PlatformError::DeviceBusy(format!(
-                    "{} is busy. Try running: diskutil unmountDisk {}",
-                    actual_path, path
-                ))
-            } else {
-                PlatformError::Io(e)
-            }
-        })?;
+        // Only a write may unmount the user's volumes by force; inspect and
+        // verify opens just report that the disk is busy
+        let file = open_when_not_busy(
+            &std_options,
+            &actual_path,
+            options.open_retries,
+            options.write,
+        )?;
+
+        if options.locks_device() {
+            lock_exclusive(&file, &actual_path)?;
+        }
 
         // Set F_NOCACHE for direct I/O
         if options.direct_io {
@@ -109,16 +124,43 @@ impl MacOSDevice {
 
         // Get device size
         let size = get_device_size(&file, &actual_path)?;
-        let block_size = options.block_size as u32;
+
+        // Alignment follows the device's logical block size, not the write block size
+        let block_size = logical_block_size(&file).unwrap_or(options.block_size as u32);
 
         let info = DeviceInfo {
             path: actual_path,
             size,
             block_size,
             direct_io: options.direct_io,
+            physical_block_size: physical_block_size(&file).unwrap_or(0),
+            optimal_io_size: None,
+            is_rotational: false,
         };
 
-        Ok(Self { file, info })
+        let sparse = options.sparse && file.metadata().is_ok_and(|m| m.is_file());
+
+        Ok(Self { file, info, sparse })
+    }
+
+    /// Leave all-zero `data` at `offset` as a hole instead of writing it
+    ///
+    /// There is no portable way to punch a hole here, so only zeros at or
+    /// past the end of the file are skipped. Returns `false` if the data
+    /// still has to be written; on success the file position is past it.
+    fn write_hole(&mut self, offset: u64, data: &[u8]) -> std::io::Result<bool> {
+        if !self.sparse || data.iter().any(|&b| b != 0) {
+            return Ok(false);
+        }
+
+        let end = offset + data.len() as u64;
+        let len = self.file.metadata()?.len();
+        if offset < len {
+            return Ok(false);
+        }
+        self.file.set_len(end)?;
+        self.file.seek(SeekFrom::Start(end))?;
+        Ok(true)
     }
 }
 
@@ -149,6 +191,9 @@ impl RawDevice for MacOSDevice {
     }
 
     fn write_at(&mut self, offset: u64, data: &[u8]) -> Result<usize> {
+        if self.write_hole(offset, data)? {
+            return Ok(data.len());
+        }
         self.file.seek(SeekFrom::Start(offset))?;
         self.file.write(data).map_err(PlatformError::Io)
     }
@@ -167,6 +212,12 @@ impl Read for MacOSDevice {
 
 impl Write for MacOSDevice {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.sparse {
+            let offset = self.file.stream_position()?;
+            if self.write_hole(offset, buf)? {
+                return Ok(buf.len());
+            }
+        }
         self.file.write(buf)
     }
 
@@ -193,6 +244,30 @@ fn to_raw_device_path(path: &str) -> String {
     }
 }
 
+/// Take an exclusive advisory lock on the device, failing if another process holds one
+///
+/// The lock is released when the file is closed.
+fn lock_exclusive(file: &File, path: &str) -> Result<()> {
+    let fd = file.as_raw_fd();
+
+    #[allow(unsafe_code)]
+    let result = unsafe { libc::flock(fd, libc::LOCK_EX | libc::LOCK_NB) };
+
+    if result == 0 {
+        return Ok(());
+    }
+    let e = std::io::Error::last_os_error();
+    if e.raw_os_error() == Some(libc::EWOULDBLOCK) {
+        Err(PlatformError::DeviceBusy(format!(
+            "{} is in use by another program (such as another engraver write). \
+             Close it and try again.",
+            path
+        )))
+    } else {
+        Err(PlatformError::Io(e))
+    }
+}
+
 /// Set F_NOCACHE on a file descriptor for direct I/O
 fn set_nocache(file: &File) -> Result<()> {
     let fd = file.as_raw_fd();
@@ -250,6 +325,27 @@ fn get_device_size(file: &File, path: &str) -> Result<u64> {
     }
 }
 
+/// Logical block size of an open disk device, or `None` for other files
+fn logical_block_size(file: &File) -> Option<u32> {
+    const DKIOCGETBLOCKSIZE: libc::c_ulong = 0x40046418;
+
+    let mut block_size: u32 = 0;
+    #[allow(unsafe_code)]
+    let result = unsafe { libc::ioctl(file.as_raw_fd(), DKIOCGETBLOCKSIZE, &mut block_size) };
+    (result == 0 && block_size > 0).then_some(block_size)
+}
+
+/// Physical block size of an open disk device, or `None` for other files
+fn physical_block_size(file: &File) -> Option<u32> {
+    const DKIOCGETPHYSICALBLOCKSIZE: libc::c_ulong = 0x4004644D;
+
+    let mut block_size: u32 = 0;
+    #[allow(unsafe_code)]
+    let result =
+        unsafe { libc::ioctl(file.as_raw_fd(), DKIOCGETPHYSICALBLOCKSIZE, &mut block_size) };
+    (result == 0 && block_size > 0).then_some(block_size)
+}
+
 /// Get device block size
 ///
 /// Returns the larger of the logical and physical block sizes. On Advanced
@@ -300,18 +396,122 @@ fn get_device_block_size(path: &str) -> Result<u32> {
     Ok(512)
 }
 
-/// Unmount all volumes on a disk using diskutil
-fn unmount_macos_device(device_path: &str) -> Result<()> {
-    // Extract disk identifier (e.g., "disk2" from "/dev/disk2" or "/dev/rdisk2")
-    let disk_id = device_path
+/// Open `path`, riding out `EBUSY` while a recent unmount settles
+///
+/// A busy open is retried up to `retries` times, [`OPEN_RETRY_DELAY`] apart.
+/// If the device is still busy and `force_unmount` is set,
+/// `diskutil unmountDisk force` runs once before a final attempt; only then
+/// is [`PlatformError::DeviceBusy`] returned.
+fn open_when_not_busy(
+    options: &StdOpenOptions,
+    path: &str,
+    retries: u32,
+    force_unmount: bool,
+) -> Result<File> {
+    let mut attempt = 0;
+    let mut forced = false;
+
+    loop {
+        let e = match options.open(path) {
+            Ok(file) => return Ok(file),
+            Err(e) => e,
+        };
+
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            return Err(PlatformError::PermissionDenied(format!(
+                "Cannot open {}: {}. Try running with sudo.",
+                path, e
+            )));
+        }
+        if e.raw_os_error() != Some(libc::EBUSY) {
+            return Err(PlatformError::Io(e));
+        }
+
+        if attempt < retries {
+            attempt += 1;
+            tracing::debug!("{} is busy, retrying open ({}/{})", path, attempt, retries);
+            std::thread::sleep(OPEN_RETRY_DELAY);
+        } else if force_unmount && !forced {
+            forced = true;
+            tracing::debug!("{} is still busy, forcing unmount", path);
+            if let Err(e) = run_unmount_disk(disk_identifier(path), true) {
+                tracing::debug!("Forced unmount failed: {}", e);
+            }
+        } else if !force_unmount {
+            return Err(PlatformError::DeviceBusy(format!(
+                "{} is still busy after {} retries.\n\
+                 Quit apps using the disk (Finder windows, Disk Utility, backup or \
+                 indexing tools) and try again",
+                path, retries
+            )));
+        } else {
+            return Err(PlatformError::DeviceBusy(format!(
+                "{} is still busy after {} retries and a forced unmount.\n\
+                 Quit apps using the disk (Finder windows, Disk Utility, backup or \
+                 indexing tools) and try again, or run: diskutil unmountDisk force /dev/{}",
+                path,
+                retries,
+                disk_identifier(path)
+            )));
+        }
+    }
+}
+
+/// Disk identifier (e.g. "disk2") from "/dev/disk2" or "/dev/rdisk2"
+fn disk_identifier(device_path: &str) -> &str {
+    device_path
         .trim_start_matches("/dev/")
-        .trim_start_matches('r');
+        .trim_start_matches('r')
+}
+
+/// Unmount all volumes on a disk using diskutil
+///
+/// Each mounted volume is reported before `diskutil unmountDisk` runs, since
+/// a busy volume can keep diskutil waiting for several seconds.
+fn unmount_macos_device(device_path: &str, progress: &mut dyn FnMut(&str)) -> Result<()> {
+    let disk_id = disk_identifier(device_path);
 
     tracing::debug!("Unmounting disk: {}", disk_id);
 
+    let volumes = Command::new("mount")
+        .output()
+        .map(|out| disk_volumes(&String::from_utf8_lossy(&out.stdout), disk_id))
+        .unwrap_or_default();
+    if volumes.is_empty() {
+        progress(&format!("unmounting /dev/{}", disk_id));
+    }
+    for volume in &volumes {
+        progress(&format!("unmounting {}", volume));
+    }
+
+    let mut attempt = 1;
+    loop {
+        match run_unmount_disk(disk_id, false) {
+            Ok(()) => {
+                progress("done");
+                return Ok(());
+            }
+            Err(e) if attempt < UNMOUNT_ATTEMPTS => {
+                tracing::debug!("Unmount attempt {} failed: {}", attempt, e);
+                attempt += 1;
+                progress(&format!("retrying /dev/{}", disk_id));
+                std::thread::sleep(UNMOUNT_RETRY_DELAY);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Run `diskutil unmountDisk` once, with `force` if requested
+fn run_unmount_disk(disk_id: &str, force: bool) -> Result<()> {
     // Use diskutil unmountDisk to unmount all volumes
-    let output = Command::new("diskutil")
-        .args(["unmountDisk", &format!("/dev/{}", disk_id)])
+    let mut command = Command::new("diskutil");
+    command.arg("unmountDisk");
+    if force {
+        command.arg("force");
+    }
+    let output = command
+        .arg(format!("/dev/{}", disk_id))
         .output()
         .map_err(|e| PlatformError::CommandFailed(format!("Failed to run diskutil: {}", e)))?;
 
@@ -338,6 +538,117 @@ fn unmount_macos_device(device_path: &str) -> Result<()> {
     }
 }
 
+/// Mount points of `disk_id`'s volumes in `mount` output
+///
+/// Lines look like `/dev/disk4s1 on /Volumes/NO NAME (msdos, local, ...)`.
+fn disk_volumes(mount_output: &str, disk_id: &str) -> Vec<String> {
+    let prefix = format!("/dev/{}s", disk_id);
+    mount_output
+        .lines()
+        .filter_map(|line| {
+            let (device, rest) = line.split_once(" on ")?;
+            if device != format!("/dev/{}", disk_id) && !device.starts_with(&prefix) {
+                return None;
+            }
+            let mount_point = rest.rsplit_once(" (").map_or(rest, |(point, _)| point);
+            Some(mount_point.to_string())
+        })
+        .collect()
+}
+
+/// Add a data partition with `diskutil addPartition`
+///
+/// diskutil places the new partition directly after the last existing one
+/// (the end of the image) and sizes it to fill the disk; `start_offset` is
+/// used to check there is room.
+fn create_macos_data_partition(
+    device_path: &str,
+    fs: DataFilesystem,
+    start_offset: u64,
+) -> Result<()> {
+    let device_size = MacOSDevice::open(
+        device_path,
+        OpenOptions::new().write(false).direct_io(false),
+    )?
+    .size();
+    data_partition_span(device_size, start_offset)?;
+
+    let disk_id = device_path
+        .trim_start_matches("/dev/")
+        .trim_start_matches('r');
+
+    let list = Command::new("diskutil")
+        .args(["list", &format!("/dev/{}", disk_id)])
+        .output()
+        .map_err(|e| PlatformError::CommandFailed(format!("Failed to run diskutil: {}", e)))?;
+    let last =
+        last_partition_id(&String::from_utf8_lossy(&list.stdout), disk_id).ok_or_else(|| {
+            PlatformError::PartitionFailed(format!(
+                "diskutil sees no partitions on {} to add one after",
+                disk_id
+            ))
+        })?;
+
+    let format = match fs {
+        DataFilesystem::Fat32 => "MS-DOS FAT32",
+        DataFilesystem::ExFat => "ExFAT",
+    };
+    tracing::debug!("Adding {} partition after {}", format, last);
+
+    // A size of 0 fills the remaining free space
+    let output = Command::new("diskutil")
+        .args(["addPartition", &last, format, DATA_PARTITION_LABEL, "0"])
+        .output()
+        .map_err(|e| PlatformError::CommandFailed(format!("Failed to run diskutil: {}", e)))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(PlatformError::PartitionFailed(format!(
+            "diskutil addPartition failed: {} {}",
+            String::from_utf8_lossy(&output.stdout).trim(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/// Highest-numbered partition identifier (e.g. `disk4s2`) in `diskutil list` output
+fn last_partition_id(list_output: &str, disk_id: &str) -> Option<String> {
+    let prefix = format!("{}s", disk_id);
+    list_output
+        .split_whitespace()
+        .filter_map(|token| {
+            let number = token.strip_prefix(&prefix)?;
+            number.parse::<u32>().ok().map(|n| (n, token))
+        })
+        .max_by_key(|(n, _)| *n)
+        .map(|(_, token)| token.to_string())
+}
+
+/// Eject a disk using diskutil
+fn eject_macos_device(device_path: &str) -> Result<()> {
+    let disk_id = device_path
+        .trim_start_matches("/dev/")
+        .trim_start_matches('r');
+
+    tracing::debug!("Ejecting disk: {}", disk_id);
+
+    let output = Command::new("diskutil")
+        .args(["eject", &format!("/dev/{}", disk_id)])
+        .output()
+        .map_err(|e| PlatformError::CommandFailed(format!("Failed to run diskutil: {}", e)))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(PlatformError::EjectFailed(format!(
+            "diskutil eject failed: {} {}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        )))
+    }
+}
+
 // ============================================================================
 // UNIT TESTS
 // ============================================================================
@@ -381,6 +692,44 @@ mod tests {
     // MacOSDevice tests with temp files
     // -------------------------------------------------------------------------
 
+    #[test]
+    fn test_last_partition_id() {
+        let list_output = "\
+/dev/disk4 (external, physical):
+   #:                       TYPE NAME                    SIZE       IDENTIFIER
+   0:     FDisk_partition_scheme                        *31.9 GB    disk4
+   1:             Windows_FAT_32 bootfs                  536.9 MB   disk4s1
+   2:                      Linux                         2.1 GB     disk4s2
+";
+        assert_eq!(
+            last_partition_id(list_output, "disk4").as_deref(),
+            Some("disk4s2")
+        );
+        assert_eq!(last_partition_id(list_output, "disk5"), None);
+    }
+
+    #[test]
+    fn test_disk_identifier() {
+        assert_eq!(disk_identifier("/dev/disk2"), "disk2");
+        assert_eq!(disk_identifier("/dev/rdisk2"), "disk2");
+        assert_eq!(disk_identifier("disk4"), "disk4");
+    }
+
+    #[test]
+    fn test_disk_volumes() {
+        let mount_output = "\
+/dev/disk3s1s1 on / (apfs, sealed, local, read-only, journaled)
+/dev/disk4s1 on /Volumes/NO NAME (msdos, local, nodev, nosuid, noowners)
+/dev/disk4s2 on /Volumes/rootfs (ext4, local)
+/dev/disk41s1 on /Volumes/Other (msdos, local)
+";
+        assert_eq!(
+            disk_volumes(mount_output, "disk4"),
+            vec!["/Volumes/NO NAME", "/Volumes/rootfs"]
+        );
+        assert!(disk_volumes(mount_output, "disk5").is_empty());
+    }
+
     #[test]
     fn test_open_nonexistent_device() {
         let result = MacOSDevice::open("/dev/nonexistent_disk_xyz", OpenOptions::default());
@@ -2,7 +2,9 @@
 //!
 //! Uses CreateFile with PhysicalDrive paths and volume locking.
 
-use crate::{DeviceInfo, OpenOptions, PlatformError, PlatformOps, RawDevice, Result};
+use crate::{
+    DataFilesystem, DeviceInfo, OpenOptions, PlatformError, PlatformOps, RawDevice, Result,
+};
 use std::io::{Read, Seek, SeekFrom, Write};
 
 #[cfg(target_os = "windows")]
@@ -39,10 +41,24 @@ impl PlatformOps for WindowsPlatform {
         }
     }
 
-    fn unmount_device(path: &str) -> Result<()> {
+    fn unmount_device_with_progress(path: &str, progress: &mut dyn FnMut(&str)) -> Result<()> {
+        #[cfg(target_os = "windows")]
+        {
+            unmount_windows_device(path, progress)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = progress;
+            Err(PlatformError::NotSupported(
+                "Windows API not available".to_string(),
+            ))
+        }
+    }
+
+    fn eject(path: &str) -> Result<()> {
         #[cfg(target_os = "windows")]
         {
-            unmount_windows_device(path)
+            eject_windows_device(path)
         }
         #[cfg(not(target_os = "windows"))]
         {
@@ -52,6 +68,33 @@ impl PlatformOps for WindowsPlatform {
         }
     }
 
+    fn reread_partition_table(path: &str) -> Result<()> {
+        #[cfg(target_os = "windows")]
+        {
+            update_windows_disk_properties(path)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            Err(PlatformError::NotSupported(
+                "Windows API not available".to_string(),
+            ))
+        }
+    }
+
+    fn create_data_partition(path: &str, fs: DataFilesystem, start_offset: u64) -> Result<()> {
+        #[cfg(target_os = "windows")]
+        {
+            create_windows_data_partition(path, fs, start_offset)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = (path, fs, start_offset);
+            Err(PlatformError::NotSupported(
+                "Windows API not available".to_string(),
+            ))
+        }
+    }
+
     fn sync_all() -> Result<()> {
         // Windows doesn't have a direct equivalent to sync
         // Flushing happens per-handle
@@ -153,9 +196,25 @@ impl WindowsDevice {
             size,
             block_size,
             direct_io: options.direct_io,
+            // Geometry needs IOCTL_STORAGE_QUERY_PROPERTY; not reported yet
+            physical_block_size: 0,
+            optimal_io_size: None,
+            is_rotational: false,
         };
 
-        Ok(Self { handle, info })
+        let device = Self { handle, info };
+        if options.locks_device() {
+            // The lock is released when the handle is closed
+            device.lock().map_err(|_| {
+                PlatformError::DeviceBusy(format!(
+                    "{} is in use: a volume on it is open in another program \
+                     (such as Explorer or another engraver write). Close it and try again.",
+                    device.info.path
+                ))
+            })?;
+        }
+
+        Ok(device)
     }
 
     /// Lock the volume for exclusive access
@@ -457,10 +516,11 @@ fn get_device_size(handle: HANDLE, _path: &str) -> Result<u64> {
 
 /// Unmount volumes on a Windows physical drive
 #[cfg(target_os = "windows")]
-fn unmount_windows_device(path: &str) -> Result<()> {
+fn unmount_windows_device(path: &str, progress: &mut dyn FnMut(&str)) -> Result<()> {
     use std::process::Command;
 
     let device_path = normalize_windows_path(path);
+    progress(&format!("unmounting {}", device_path));
 
     // Extract drive number
     let drive_num = device_path
@@ -492,12 +552,14 @@ fn unmount_windows_device(path: &str) -> Result<()> {
     match output {
         Ok(out) if out.status.success() => {
             std::thread::sleep(std::time::Duration::from_millis(100));
+            progress("done");
             Ok(())
         }
         Ok(out) => {
             let stderr = String::from_utf8_lossy(&out.stderr);
             if stderr.contains("not found") || stderr.is_empty() {
                 // No volumes to unmount
+                progress("done");
                 Ok(())
             } else {
                 Err(PlatformError::UnmountFailed(stderr.to_string()))
@@ -510,6 +572,123 @@ fn unmount_windows_device(path: &str) -> Result<()> {
     }
 }
 
+/// Eject media from a Windows physical drive
+#[cfg(target_os = "windows")]
+fn eject_windows_device(path: &str) -> Result<()> {
+    use windows_sys::Win32::System::Ioctl::IOCTL_STORAGE_EJECT_MEDIA;
+
+    let device = WindowsDevice::open(path, OpenOptions::new().write(false).direct_io(false))?;
+    let mut bytes_returned: u32 = 0;
+
+    // SAFETY: DeviceIoControl is called with a valid HANDLE obtained from successful open().
+    // IOCTL_STORAGE_EJECT_MEDIA requires no input/output buffers (null pointers are valid).
+    // bytes_returned is a valid mutable reference to u32.
+    #[allow(unsafe_code)]
+    let result = unsafe {
+        DeviceIoControl(
+            device.handle,
+            IOCTL_STORAGE_EJECT_MEDIA,
+            ptr::null(),
+            0,
+            ptr::null_mut(),
+            0,
+            &mut bytes_returned,
+            ptr::null_mut(),
+        )
+    };
+
+    if result == 0 {
+        Err(PlatformError::EjectFailed(
+            std::io::Error::last_os_error().to_string(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Refresh the cached partition layout of a Windows physical drive
+#[cfg(target_os = "windows")]
+fn update_windows_disk_properties(path: &str) -> Result<()> {
+    use windows_sys::Win32::System::Ioctl::IOCTL_DISK_UPDATE_PROPERTIES;
+
+    let device = WindowsDevice::open(path, OpenOptions::new().write(false).direct_io(false))?;
+    let mut bytes_returned: u32 = 0;
+
+    // SAFETY: DeviceIoControl is called with a valid HANDLE obtained from successful open().
+    // IOCTL_DISK_UPDATE_PROPERTIES requires no input/output buffers (null pointers are valid).
+    // bytes_returned is a valid mutable reference to u32.
+    #[allow(unsafe_code)]
+    let result = unsafe {
+        DeviceIoControl(
+            device.handle,
+            IOCTL_DISK_UPDATE_PROPERTIES,
+            ptr::null(),
+            0,
+            ptr::null_mut(),
+            0,
+            &mut bytes_returned,
+            ptr::null_mut(),
+        )
+    };
+
+    if result == 0 {
+        Err(PlatformError::RereadFailed(
+            std::io::Error::last_os_error().to_string(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Create and format a data partition with PowerShell's `New-Partition`
+///
+/// Windows refuses FAT32 volumes over 32 GB; use exFAT for larger drives.
+#[cfg(target_os = "windows")]
+fn create_windows_data_partition(path: &str, fs: DataFilesystem, start_offset: u64) -> Result<()> {
+    use crate::{data_partition_span, DATA_PARTITION_LABEL};
+    use std::process::Command;
+
+    let device_size =
+        WindowsDevice::open(path, OpenOptions::new().write(false).direct_io(false))?.size();
+    let (start, _) = data_partition_span(device_size, start_offset)?;
+
+    let device_path = normalize_windows_path(path);
+    let drive_num = device_path
+        .trim_start_matches("\\\\.\\PhysicalDrive")
+        .parse::<u32>()
+        .map_err(|_| PlatformError::DeviceNotFound(path.to_string()))?;
+
+    let file_system = match fs {
+        DataFilesystem::Fat32 => "FAT32",
+        DataFilesystem::ExFat => "exFAT",
+    };
+
+    let output = Command::new("powershell")
+        .args([
+            "-Command",
+            &format!(
+                "$ErrorActionPreference = 'Stop'; \
+                 Update-Disk -Number {disk}; \
+                 New-Partition -DiskNumber {disk} -Offset {start} -UseMaximumSize -AssignDriveLetter | \
+                 Format-Volume -FileSystem {fs} -NewFileSystemLabel {label} -Confirm:$false | Out-Null",
+                disk = drive_num,
+                start = start,
+                fs = file_system,
+                label = DATA_PARTITION_LABEL,
+            ),
+        ])
+        .output()
+        .map_err(|e| PlatformError::CommandFailed(format!("Failed to run PowerShell: {}", e)))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(PlatformError::PartitionFailed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
 /// Check if running with elevated privileges (Administrator)
 #[cfg(target_os = "windows")]
 fn is_elevated() -> bool {
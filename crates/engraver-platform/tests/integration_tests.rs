@@ -87,6 +87,9 @@ fn test_device_info_creation() {
         size: 32 * 1024 * 1024 * 1024,
         block_size: 512,
         direct_io: true,
+        physical_block_size: 512,
+        optimal_io_size: None,
+        is_rotational: false,
     };
 
     assert_eq!(info.path, "/dev/sdb");